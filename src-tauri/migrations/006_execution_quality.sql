@@ -0,0 +1,5 @@
+-- Migration 006: Execution quality tracking
+-- Intended (limit/signal) price per fill for slippage analysis
+
+ALTER TABLE trade_executions ADD COLUMN intended_price REAL;
+ALTER TABLE trade_executions ADD COLUMN order_type TEXT CHECK (order_type IN ('market', 'limit', 'stop', 'stop_limit'));
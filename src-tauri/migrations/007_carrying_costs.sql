@@ -0,0 +1,15 @@
+-- Migration 007: Carrying costs (borrow fees, margin interest, swaps)
+-- Positive amounts are costs, negative amounts are credits (e.g. positive swap)
+
+CREATE TABLE IF NOT EXISTS trade_carrying_costs (
+    id TEXT PRIMARY KEY,
+    trade_id TEXT NOT NULL REFERENCES trades(id) ON DELETE CASCADE,
+    cost_type TEXT NOT NULL CHECK (cost_type IN ('borrow_fee', 'margin_interest', 'swap')),
+    cost_date DATE NOT NULL,
+    amount REAL NOT NULL,
+    notes TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_carrying_costs_trade ON trade_carrying_costs(trade_id);
+CREATE INDEX IF NOT EXISTS idx_carrying_costs_date ON trade_carrying_costs(cost_date);
@@ -0,0 +1,18 @@
+-- Migration 008: Dividends received (long) or paid (short)
+-- Positive amounts are received, negative amounts are paid
+
+CREATE TABLE IF NOT EXISTS dividends (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id),
+    account_id TEXT NOT NULL REFERENCES accounts(id),
+    instrument_id TEXT NOT NULL REFERENCES instruments(id),
+    trade_id TEXT REFERENCES trades(id) ON DELETE SET NULL,
+    ex_date DATE NOT NULL,
+    pay_date DATE,
+    amount REAL NOT NULL,
+    notes TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_dividends_user_date ON dividends(user_id, ex_date);
+CREATE INDEX IF NOT EXISTS idx_dividends_trade ON dividends(trade_id);
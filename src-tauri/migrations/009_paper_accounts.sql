@@ -0,0 +1,4 @@
+-- Migration 009: Paper (simulated) accounts
+-- Paper accounts are excluded from account-wide metrics by default
+
+ALTER TABLE accounts ADD COLUMN is_paper INTEGER NOT NULL DEFAULT 0;
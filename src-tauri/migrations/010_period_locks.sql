@@ -0,0 +1,8 @@
+-- Migration 010: Period locks
+-- Trades on or before locked_through are read-only until the period is unlocked
+
+CREATE TABLE IF NOT EXISTS period_locks (
+    account_id TEXT PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+    locked_through DATE NOT NULL,
+    locked_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
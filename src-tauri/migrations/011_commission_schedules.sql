@@ -0,0 +1,13 @@
+-- Migration 011: Commission schedules
+-- Used to estimate fees for imported executions whose broker data omits them
+
+CREATE TABLE IF NOT EXISTS commission_schedules (
+    account_id TEXT PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+    per_order REAL NOT NULL DEFAULT 0,
+    per_share REAL NOT NULL DEFAULT 0,
+    per_contract REAL NOT NULL DEFAULT 0,
+    minimum_per_order REAL NOT NULL DEFAULT 0,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+ALTER TABLE trade_executions ADD COLUMN fees_estimated INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,16 @@
+-- Migration 012: Seed positions
+-- Positions already open at the start of the imported history
+
+CREATE TABLE IF NOT EXISTS seed_positions (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id),
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    symbol TEXT NOT NULL,
+    direction TEXT NOT NULL CHECK (direction IN ('long', 'short')),
+    quantity REAL NOT NULL,
+    avg_cost REAL NOT NULL,
+    open_date DATE NOT NULL,
+    as_of_date DATE NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (account_id, symbol)
+);
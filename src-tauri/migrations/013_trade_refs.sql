@@ -0,0 +1,20 @@
+-- Migration 013: Trade reference codes
+-- Short stable per-user references (T-<year>-<sequence>) for cross-referencing trades
+
+ALTER TABLE trades ADD COLUMN ref_code TEXT;
+
+-- Backfill existing trades in trade date order within each user and year
+UPDATE trades SET ref_code = (
+    SELECT 'T-' || substr(r.trade_date, 1, 4) || '-' || printf('%04d', r.seq)
+    FROM (
+        SELECT id, trade_date,
+               ROW_NUMBER() OVER (
+                   PARTITION BY user_id, substr(trade_date, 1, 4)
+                   ORDER BY trade_date, created_at, id
+               ) AS seq
+        FROM trades
+    ) r
+    WHERE r.id = trades.id
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_trades_user_ref_code ON trades(user_id, ref_code);
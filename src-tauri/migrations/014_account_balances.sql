@@ -0,0 +1,4 @@
+-- Migration 014: Account starting balances
+-- Used to express drawdowns as a percent of account equity
+
+ALTER TABLE accounts ADD COLUMN starting_balance REAL;
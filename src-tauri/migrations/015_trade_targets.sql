@@ -0,0 +1,5 @@
+-- Migration 015: Planned targets and best price reached
+-- Lets planned reward-to-risk be compared with how far trades actually went
+
+ALTER TABLE trades ADD COLUMN target_price REAL;
+ALTER TABLE trades ADD COLUMN max_favorable_price REAL; -- Best price reached while the trade was open
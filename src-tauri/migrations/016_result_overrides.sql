@@ -0,0 +1,4 @@
+-- Migration 016: Manual trade result overrides
+-- Stored next to the computed result, e.g. to count a rule-breaking win as a loss
+
+ALTER TABLE trades ADD COLUMN result_override TEXT;
@@ -0,0 +1,13 @@
+-- Migration 017: Per-trade checklist completion
+-- Which checklist items were checked on a trade, when, and whether before or after entry
+
+CREATE TABLE IF NOT EXISTS trade_checklist_items (
+    id TEXT PRIMARY KEY,
+    trade_id TEXT NOT NULL REFERENCES trades(id) ON DELETE CASCADE,
+    item TEXT NOT NULL,
+    phase TEXT NOT NULL CHECK (phase IN ('pre_entry', 'post_entry')),
+    checked_at DATETIME NOT NULL,
+    UNIQUE (trade_id, item)
+);
+
+CREATE INDEX IF NOT EXISTS idx_checklist_items_trade ON trade_checklist_items(trade_id);
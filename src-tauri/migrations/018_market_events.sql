@@ -0,0 +1,16 @@
+-- Migration 018: Market events and catalysts
+-- Market-wide events (FOMC, CPI) have no symbol; earnings belong to one symbol
+
+CREATE TABLE IF NOT EXISTS market_events (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id),
+    event_date DATE NOT NULL,
+    event_type TEXT NOT NULL CHECK (event_type IN ('fomc', 'cpi', 'earnings', 'other')),
+    symbol TEXT,
+    description TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_market_events_unique
+    ON market_events(user_id, event_date, event_type, COALESCE(symbol, ''));
+CREATE INDEX IF NOT EXISTS idx_market_events_date ON market_events(event_date);
@@ -0,0 +1,10 @@
+-- Migration 019: Market condition tags per trading day
+-- A day can carry several conditions, e.g. a gap up that became a trend day
+
+CREATE TABLE IF NOT EXISTS day_conditions (
+    user_id TEXT NOT NULL REFERENCES users(id),
+    condition_date DATE NOT NULL,
+    condition TEXT NOT NULL CHECK (condition IN ('trend', 'chop', 'gap_up', 'gap_down')),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, condition_date, condition)
+);
@@ -0,0 +1,22 @@
+-- Migration 020: Weekly process goal scorecards
+-- Goal thresholds are copied in so a scorecard keeps meaning after goals change
+
+CREATE TABLE IF NOT EXISTS goal_scorecards (
+    user_id TEXT NOT NULL REFERENCES users(id),
+    week_start DATE NOT NULL, -- Monday
+    max_trades_per_day INTEGER,
+    days_traded INTEGER NOT NULL DEFAULT 0,
+    days_over_trade_limit INTEGER NOT NULL DEFAULT 0,
+    journal_every_day INTEGER NOT NULL DEFAULT 0,
+    days_journaled INTEGER NOT NULL DEFAULT 0,
+    max_risk_per_trade REAL,
+    trades_over_risk INTEGER NOT NULL DEFAULT 0,
+    trades_without_stop INTEGER NOT NULL DEFAULT 0,
+    goals_met INTEGER NOT NULL DEFAULT 0,
+    goals_evaluated INTEGER NOT NULL DEFAULT 0,
+    trade_count INTEGER NOT NULL DEFAULT 0,
+    net_pnl REAL NOT NULL DEFAULT 0,
+    win_rate REAL,
+    evaluated_at DATETIME NOT NULL,
+    PRIMARY KEY (user_id, week_start)
+);
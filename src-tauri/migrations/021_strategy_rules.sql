@@ -0,0 +1,24 @@
+-- Migration 021: Strategy suggestion rules and the watchlists they refer to
+-- A rule suggests its strategy when every condition it sets matches; unset conditions match anything
+
+CREATE TABLE IF NOT EXISTS watchlist_symbols (
+    user_id TEXT NOT NULL REFERENCES users(id),
+    watchlist TEXT NOT NULL,
+    symbol TEXT NOT NULL,
+    PRIMARY KEY (user_id, watchlist, symbol)
+);
+
+CREATE TABLE IF NOT EXISTS strategy_rules (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id),
+    strategy TEXT NOT NULL,
+    priority INTEGER NOT NULL DEFAULT 0, -- Lower runs first
+    min_hold_minutes REAL,
+    max_hold_minutes REAL,
+    watchlist TEXT,
+    direction TEXT CHECK (direction IN ('long', 'short')),
+    asset_class TEXT CHECK (asset_class IN ('stock', 'option')),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_strategy_rules_user ON strategy_rules(user_id, priority);
@@ -0,0 +1,4 @@
+-- Migration 022: Worst price reached while a trade was open
+-- Pairs with max_favorable_price for MAE/MFE; both can be backfilled from cached candles
+
+ALTER TABLE trades ADD COLUMN max_adverse_price REAL;
@@ -0,0 +1,9 @@
+-- Migration 023: Daily closing prices for marking open positions to market
+-- Keyed by the traded symbol, so option contracts use their own symbol
+
+CREATE TABLE IF NOT EXISTS daily_closes (
+    symbol TEXT NOT NULL,
+    close_date DATE NOT NULL,
+    close REAL NOT NULL,
+    PRIMARY KEY (symbol, close_date)
+);
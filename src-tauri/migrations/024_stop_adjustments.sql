@@ -0,0 +1,11 @@
+-- Migration 024: Stop-loss changes over a trade's life
+-- trades.stop_loss_price stays the initial stop used for R; the latest adjustment sets the current stop
+
+CREATE TABLE IF NOT EXISTS stop_adjustments (
+    id TEXT PRIMARY KEY,
+    trade_id TEXT NOT NULL REFERENCES trades(id) ON DELETE CASCADE,
+    stop_price REAL NOT NULL,
+    adjusted_at DATETIME NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_stop_adjustments_trade ON stop_adjustments(trade_id, adjusted_at);
@@ -0,0 +1,7 @@
+-- Migration 025: Daily bar ranges and volatility at entry
+-- High/low on daily closes give true ranges; entry_atr is the average true range before the trade date
+
+ALTER TABLE daily_closes ADD COLUMN high REAL;
+ALTER TABLE daily_closes ADD COLUMN low REAL;
+
+ALTER TABLE trades ADD COLUMN entry_atr REAL;
@@ -0,0 +1,4 @@
+-- Migration 026: Minimum price increment per instrument
+-- NULL tick_size falls back to the asset class default
+
+ALTER TABLE instruments ADD COLUMN tick_size REAL;
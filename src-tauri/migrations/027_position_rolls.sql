@@ -0,0 +1,7 @@
+-- Migration 027: Rolled and transferred open positions
+-- A position left open at period end can be linked to the trade that continues it,
+-- so its exposure stops counting once the continuation trade takes over
+
+ALTER TABLE trades ADD COLUMN roll_type TEXT CHECK (roll_type IN ('rolled', 'transferred'));
+ALTER TABLE trades ADD COLUMN rolled_on DATE;
+ALTER TABLE trades ADD COLUMN continuation_trade_id TEXT REFERENCES trades(id) ON DELETE SET NULL;
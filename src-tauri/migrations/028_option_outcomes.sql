@@ -0,0 +1,5 @@
+-- Migration 028: Option expiration outcomes
+-- Records whether an option position ended by expiring worthless, being assigned or being exercised,
+-- since fills alone don't tell an assignment from an ordinary close
+
+ALTER TABLE trades ADD COLUMN option_outcome TEXT CHECK (option_outcome IN ('expired', 'assigned', 'exercised'));
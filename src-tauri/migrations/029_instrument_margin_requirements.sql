@@ -0,0 +1,4 @@
+-- Migration 029: Margin requirement per instrument
+-- Fraction of position value held as margin, e.g. 0.25; NULL means the position is fully paid
+
+ALTER TABLE instruments ADD COLUMN margin_requirement REAL;
@@ -0,0 +1,17 @@
+-- Migration 030: Account financing ledger
+-- Monthly margin interest and financing charges that belong to an account rather than a trade.
+-- Positive amounts are costs, negative amounts are credits (e.g. interest earned on cash)
+
+CREATE TABLE IF NOT EXISTS financing_charges (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id),
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    charge_month DATE NOT NULL, -- First day of the month charged
+    charge_type TEXT NOT NULL CHECK (charge_type IN ('margin_interest', 'financing')),
+    amount REAL NOT NULL,
+    source TEXT NOT NULL DEFAULT 'manual' CHECK (source IN ('manual', 'import')),
+    notes TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_financing_charges_account_month ON financing_charges(account_id, charge_month);
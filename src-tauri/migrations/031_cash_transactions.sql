@@ -0,0 +1,15 @@
+-- Migration 031: Account cash transactions
+-- Deposits and withdrawals, used to separate trading returns from money moved in and out of an account.
+-- Positive amounts are deposits, negative amounts are withdrawals
+
+CREATE TABLE IF NOT EXISTS cash_transactions (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id),
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    transaction_date DATE NOT NULL,
+    amount REAL NOT NULL,
+    notes TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_cash_transactions_account_date ON cash_transactions(account_id, transaction_date);
@@ -0,0 +1,10 @@
+-- Migration 032: FX rates for a reporting currency
+-- One unit of the base currency is worth `rate` units of the quote currency on the rate date
+
+CREATE TABLE IF NOT EXISTS fx_rates (
+    base_currency TEXT NOT NULL,
+    quote_currency TEXT NOT NULL,
+    rate_date DATE NOT NULL,
+    rate REAL NOT NULL,
+    PRIMARY KEY (base_currency, quote_currency, rate_date)
+);
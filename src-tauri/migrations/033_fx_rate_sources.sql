@@ -0,0 +1,4 @@
+-- Migration 033: Source of FX rates
+-- Rates fetched from the ECB are cached alongside manual ones; a manual rate overrides the fetched rate of its day
+
+ALTER TABLE fx_rates ADD COLUMN source TEXT NOT NULL DEFAULT 'manual' CHECK (source IN ('manual', 'ecb'));
@@ -0,0 +1,5 @@
+-- Migration 034: Borrow availability of short trades
+-- How hard the shares were to borrow when shorting, with a free-text note (locate source, quoted rate, ...)
+
+ALTER TABLE trades ADD COLUMN borrow_availability TEXT CHECK (borrow_availability IN ('easy', 'hard_to_borrow', 'locate_required'));
+ALTER TABLE trades ADD COLUMN borrow_note TEXT;
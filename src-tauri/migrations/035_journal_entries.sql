@@ -0,0 +1,13 @@
+-- Migration 035: Daily journal entries
+-- One entry per day. The end-of-day summary stores a draft that the trader reviews and edits.
+
+CREATE TABLE IF NOT EXISTS journal_entries (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id),
+    entry_date DATE NOT NULL,
+    content TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'draft', -- 'draft' or 'reviewed'
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(user_id, entry_date)
+);
@@ -0,0 +1,23 @@
+-- Migration 036: Backfill instrument option metadata
+-- Instruments created outside the TLG import never set the option columns added in migration 002
+
+-- Non-option instruments are their own underlying
+UPDATE instruments SET underlying_symbol = symbol
+WHERE underlying_symbol IS NULL AND asset_class != 'option';
+
+-- Decode OCC contract symbols, padded like 'AAPL  250905C00240000' or not like 'AAPL240315C00150000',
+-- from their last 15 characters: YYMMDD, C or P, and the strike in thousandths
+UPDATE instruments SET
+    underlying_symbol = TRIM(SUBSTR(symbol, 1, LENGTH(symbol) - 15)),
+    expiration_date = '20' || SUBSTR(symbol, -15, 2) || '-' || SUBSTR(symbol, -13, 2) || '-' || SUBSTR(symbol, -11, 2),
+    option_type = CASE SUBSTR(symbol, -9, 1) WHEN 'C' THEN 'call' ELSE 'put' END,
+    strike_price = CAST(SUBSTR(symbol, -8) AS INTEGER) / 1000.0
+WHERE asset_class = 'option'
+  AND option_type IS NULL
+  AND LENGTH(symbol) > 15
+  AND TRIM(SUBSTR(symbol, 1, LENGTH(symbol) - 15)) != ''
+  AND SUBSTR(symbol, -15, 6) GLOB '[0-9][0-9][0-9][0-9][0-9][0-9]'
+  AND SUBSTR(symbol, -9, 1) IN ('C', 'P')
+  AND SUBSTR(symbol, -8) GLOB '[0-9][0-9][0-9][0-9][0-9][0-9][0-9][0-9]';
+
+CREATE INDEX IF NOT EXISTS idx_instruments_underlying ON instruments(underlying_symbol);
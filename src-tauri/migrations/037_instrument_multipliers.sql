@@ -0,0 +1,4 @@
+-- Migration 037: Contract multiplier per instrument
+-- Futures contracts have their own multiplier, e.g. 50 for ES; NULL uses the asset class default
+
+ALTER TABLE instruments ADD COLUMN multiplier REAL;
@@ -0,0 +1,22 @@
+-- Migration 038: Daily performance summary per account
+-- Closed trades summed by the day they count toward, kept up to date as trades change so the
+-- calendar reads one row per day; amounts are in the account's base currency
+
+CREATE TABLE IF NOT EXISTS daily_performance (
+    user_id TEXT NOT NULL REFERENCES users(id),
+    account_id TEXT NOT NULL,
+    date DATE NOT NULL,
+    realized_net_pnl REAL NOT NULL,
+    gross_pnl REAL NOT NULL,
+    total_fees REAL NOT NULL,
+    total_volume REAL NOT NULL,
+    largest_win REAL,
+    largest_loss REAL,
+    trade_count INTEGER NOT NULL,
+    win_count INTEGER NOT NULL,
+    loss_count INTEGER NOT NULL,
+    breakeven_count INTEGER NOT NULL,
+    PRIMARY KEY (account_id, date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_daily_performance_user_date ON daily_performance(user_id, date);
@@ -0,0 +1,19 @@
+-- Migration 039: Nightly metric snapshots per account
+-- Each account's key metrics as of the close of each day, so long-term trend charts read one row
+-- per day instead of recomputing from every trade; amounts are in the account's base currency
+
+CREATE TABLE IF NOT EXISTS metric_snapshots (
+    user_id TEXT NOT NULL REFERENCES users(id),
+    account_id TEXT NOT NULL,
+    date DATE NOT NULL,
+    balance REAL,
+    cumulative_pnl REAL NOT NULL,
+    drawdown REAL NOT NULL,
+    max_drawdown REAL NOT NULL,
+    trade_count INTEGER NOT NULL,
+    win_count INTEGER NOT NULL,
+    loss_count INTEGER NOT NULL,
+    PRIMARY KEY (account_id, date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_metric_snapshots_user_date ON metric_snapshots(user_id, date);
@@ -0,0 +1,20 @@
+-- Migration 040: Import batches
+-- Each executed import is recorded as a batch stamped on the trades and executions it created,
+-- so a bad import can be undone as a whole
+
+CREATE TABLE IF NOT EXISTS import_batches (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id),
+    account_id TEXT NOT NULL,
+    trade_count INTEGER NOT NULL DEFAULT 0,
+    execution_count INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL,
+    undone_at DATETIME -- Set once the batch's trades are deleted
+);
+
+ALTER TABLE trades ADD COLUMN import_batch_id TEXT;
+ALTER TABLE trade_executions ADD COLUMN import_batch_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_import_batches_user ON import_batches(user_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_trades_import_batch ON trades(import_batch_id);
+CREATE INDEX IF NOT EXISTS idx_trade_executions_import_batch ON trade_executions(import_batch_id);
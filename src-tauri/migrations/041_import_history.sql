@@ -0,0 +1,8 @@
+-- Migration 041: Import history
+-- Import batches keep the file they came from and what was skipped or failed, and imports that
+-- created nothing are kept too, so the history shows every import that was run
+
+ALTER TABLE import_batches ADD COLUMN file_name TEXT;
+ALTER TABLE import_batches ADD COLUMN skipped_duplicates INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE import_batches ADD COLUMN error_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE import_batches ADD COLUMN errors TEXT; -- JSON array of the first error messages
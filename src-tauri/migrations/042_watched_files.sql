@@ -0,0 +1,12 @@
+-- Migration 042: Watched folder files
+-- Files the watched import folder already processed, so each version of a file is imported once
+
+CREATE TABLE IF NOT EXISTS watched_files (
+    path TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id),
+    size INTEGER NOT NULL,
+    modified_ms INTEGER NOT NULL, -- Modification time in milliseconds since the Unix epoch
+    import_batch_id TEXT,         -- Unset when nothing was imported
+    error TEXT,
+    processed_at DATETIME NOT NULL
+);
@@ -0,0 +1,5 @@
+-- Migration 043: Per-trade currency conversion
+-- Rate converting a trade's PnL from the currency its instrument is quoted in to the account's base
+-- currency, e.g. USD per JPY for a USD.JPY trade in a USD account. NULL when they are the same.
+
+ALTER TABLE trades ADD COLUMN fx_rate REAL;
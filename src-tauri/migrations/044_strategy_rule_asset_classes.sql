@@ -0,0 +1,27 @@
+-- Migration 044: Strategy rules for every asset class
+-- Migration 021 limited a rule's asset class to stocks and options; futures, forex and crypto are
+-- imported too. SQLite cannot change a CHECK constraint, so the table is rebuilt.
+
+CREATE TABLE strategy_rules_new (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id),
+    strategy TEXT NOT NULL,
+    priority INTEGER NOT NULL DEFAULT 0, -- Lower runs first
+    min_hold_minutes REAL,
+    max_hold_minutes REAL,
+    watchlist TEXT,
+    direction TEXT CHECK (direction IN ('long', 'short')),
+    asset_class TEXT CHECK (asset_class IN ('stock', 'option', 'future', 'forex', 'crypto')),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO strategy_rules_new (
+    id, user_id, strategy, priority, min_hold_minutes, max_hold_minutes, watchlist, direction, asset_class, created_at
+)
+SELECT id, user_id, strategy, priority, min_hold_minutes, max_hold_minutes, watchlist, direction, asset_class, created_at
+FROM strategy_rules;
+
+DROP TABLE strategy_rules;
+ALTER TABLE strategy_rules_new RENAME TO strategy_rules;
+
+CREATE INDEX IF NOT EXISTS idx_strategy_rules_user ON strategy_rules(user_id, priority);
@@ -1,41 +1,387 @@
-use std::collections::HashMap;
-use chrono::NaiveDate;
-use crate::models::{DailyPerformance, EquityPoint, PeriodMetrics, TradeResult, TradeWithDerived};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use chrono::{Datelike, NaiveDate};
+use crate::calculations::{adjusted_stop_as_of, calculate_open_risk_per_share, calculate_risk_amount};
+use crate::models::{DailyPerformance, EntryCohort, Direction, EquityCurveGrouping, EquityPoint, EquitySeries, OvertradingBreach, OvertradingReport, PerformanceBucket, PeriodMetrics, PortfolioHeatPoint, StopAdjustment, StrategyTrend, StrategyTrendPoint, SymbolMonthCell, SymbolMonthMatrix, SymbolMonthRow, TradeSequenceBucket, Status, Trade, TradeResult, TradeWithDerived};
+
+/// Sort trades by date; same-day trades by entry time, then by when they were recorded
+pub fn sort_chronologically(trades: &mut [&TradeWithDerived]) {
+    trades.sort_by(|a, b| {
+        (a.trade.trade_date, a.trade.entry_time.as_deref(), a.trade.created_at)
+            .cmp(&(b.trade.trade_date, b.trade.entry_time.as_deref(), b.trade.created_at))
+    });
+}
+
+/// Performance by position of a trade within its day (1st trade, 2nd trade, ...)
+/// avg_cumulative_pnl is the day's running PnL after that trade, averaged over days that reached it.
+pub fn calculate_trade_sequence_report(trades: &[TradeWithDerived]) -> Vec<TradeSequenceBucket> {
+    let mut closed: Vec<&TradeWithDerived> = trades.iter().filter(|t| t.net_pnl.is_some()).collect();
+    sort_chronologically(&mut closed);
+
+    let mut buckets: Vec<(PerformanceAccumulator, f64)> = Vec::new();
+    let mut current_date = None;
+    let mut sequence = 0;
+    let mut day_pnl = 0.0;
+
+    for trade in closed {
+        if current_date != Some(trade.trade.trade_date) {
+            current_date = Some(trade.trade.trade_date);
+            sequence = 0;
+            day_pnl = 0.0;
+        }
+        sequence += 1;
+        day_pnl += trade.net_pnl.unwrap_or(0.0);
+
+        if buckets.len() < sequence {
+            buckets.push((PerformanceAccumulator::default(), 0.0));
+        }
+        let (acc, cumulative) = &mut buckets[sequence - 1];
+        acc.add(trade);
+        *cumulative += day_pnl;
+    }
+
+    buckets
+        .into_iter()
+        .enumerate()
+        .map(|(i, (acc, cumulative))| {
+            let bucket = acc.into_bucket((i + 1).to_string());
+            TradeSequenceBucket {
+                sequence: i as i32 + 1,
+                avg_cumulative_pnl: cumulative / bucket.trade_count as f64,
+                trade_count: bucket.trade_count,
+                win_count: bucket.win_count,
+                loss_count: bucket.loss_count,
+                net_pnl: bucket.net_pnl,
+                avg_net_pnl: bucket.avg_net_pnl,
+                win_rate: bucket.win_rate,
+            }
+        })
+        .collect()
+}
+
+/// Performance of closed trades grouped by the month they were entered, oldest first
+/// Each cohort is compared with the previous one that had trades, so a rising expectancy_change
+/// shows later trades doing better than earlier ones.
+pub fn calculate_entry_cohort_report(trades: &[TradeWithDerived]) -> Vec<EntryCohort> {
+    let mut by_month: BTreeMap<String, PerformanceAccumulator> = BTreeMap::new();
+    for trade in trades.iter().filter(|t| t.net_pnl.is_some()) {
+        by_month
+            .entry(trade.trade.trade_date.format("%Y-%m").to_string())
+            .or_default()
+            .add(trade);
+    }
+
+    let mut previous_expectancy = None;
+    by_month
+        .into_iter()
+        .map(|(month, acc)| {
+            let performance = acc.into_bucket(month);
+            let expectancy_change = previous_expectancy
+                .zip(performance.avg_net_pnl)
+                .map(|(previous, current)| current - previous);
+            previous_expectancy = performance.avg_net_pnl;
+            EntryCohort {
+                performance,
+                expectancy_change,
+            }
+        })
+        .collect()
+}
+
+/// Net PnL and trade count of closed trades by symbol and month, best symbol first
+/// Trades count toward the month of their date in exit_dates, or of their trade date when absent.
+/// Months run without gaps from the first to the last with trades, so the heatmap reads as a timeline.
+pub fn calculate_symbol_month_matrix(trades: &[TradeWithDerived], exit_dates: &HashMap<String, NaiveDate>) -> SymbolMonthMatrix {
+    let mut cells: BTreeMap<(String, NaiveDate), SymbolMonthCell> = BTreeMap::new();
+    for trade in trades {
+        let Some(net_pnl) = trade.net_pnl else {
+            continue;
+        };
+        let date = exit_dates.get(&trade.trade.id).copied().unwrap_or(trade.trade.trade_date);
+        let cell = cells.entry((trade.trade.symbol.clone(), first_of_month(date))).or_default();
+        cell.net_pnl += net_pnl;
+        cell.trade_count += 1;
+    }
+
+    let (Some(first), Some(last)) = (cells.keys().map(|(_, m)| *m).min(), cells.keys().map(|(_, m)| *m).max()) else {
+        return SymbolMonthMatrix::default();
+    };
+    let months: Vec<NaiveDate> = std::iter::successors(Some(first), |m| m.checked_add_months(chrono::Months::new(1)))
+        .take_while(|m| *m <= last)
+        .collect();
+
+    let symbols: BTreeSet<&String> = cells.keys().map(|(symbol, _)| symbol).collect();
+    let mut rows: Vec<SymbolMonthRow> = symbols
+        .into_iter()
+        .map(|symbol| {
+            let row_cells: Vec<SymbolMonthCell> = months
+                .iter()
+                .map(|month| cells.get(&(symbol.clone(), *month)).cloned().unwrap_or_default())
+                .collect();
+            SymbolMonthRow {
+                symbol: symbol.clone(),
+                net_pnl: row_cells.iter().map(|c| c.net_pnl).sum(),
+                trade_count: row_cells.iter().map(|c| c.trade_count).sum(),
+                cells: row_cells,
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| b.net_pnl.total_cmp(&a.net_pnl).then_with(|| a.symbol.cmp(&b.symbol)));
+
+    SymbolMonthMatrix {
+        months: months.iter().map(|m| m.format("%Y-%m").to_string()).collect(),
+        rows,
+    }
+}
+
+/// Symbol by month matrix as CSV: a net PnL and a trade count column per month, then the totals
+pub fn symbol_month_matrix_csv(matrix: &SymbolMonthMatrix) -> String {
+    let mut header = vec!["symbol".to_string()];
+    for month in &matrix.months {
+        header.push(format!("{} net_pnl", month));
+        header.push(format!("{} trades", month));
+    }
+    header.extend(["total net_pnl".to_string(), "total trades".to_string()]);
+
+    let mut csv = header.join(",") + "\n";
+    for row in &matrix.rows {
+        let mut fields = vec![csv_field(&row.symbol)];
+        for cell in &row.cells {
+            fields.push(format!("{:.2}", cell.net_pnl));
+            fields.push(cell.trade_count.to_string());
+        }
+        fields.push(format!("{:.2}", row.net_pnl));
+        fields.push(row.trade_count.to_string());
+        csv += &(fields.join(",") + "\n");
+    }
+    csv
+}
+
+/// Quote a CSV field containing a comma, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// Find days where a symbol was traded more than max_trades times
+pub fn calculate_overtrading_report(trades: &[TradeWithDerived], max_trades: i32) -> OvertradingReport {
+    let mut groups: BTreeMap<(NaiveDate, String), (i32, f64)> = BTreeMap::new();
+    for trade in trades.iter().filter(|t| t.net_pnl.is_some()) {
+        let entry = groups
+            .entry((trade.trade.trade_date, trade.trade.symbol.clone()))
+            .or_default();
+        entry.0 += 1;
+        entry.1 += trade.net_pnl.unwrap_or(0.0);
+    }
+
+    let breaches: Vec<OvertradingBreach> = groups
+        .into_iter()
+        .filter(|(_, (count, _))| *count > max_trades)
+        .map(|((date, symbol), (trade_count, net_pnl))| OvertradingBreach {
+            date,
+            symbol,
+            trade_count,
+            net_pnl,
+        })
+        .collect();
+
+    OvertradingReport {
+        max_trades_per_symbol_per_day: max_trades,
+        breach_net_pnl: breaches.iter().map(|b| b.net_pnl).sum(),
+        breaches,
+    }
+}
+
+/// Running totals for a PerformanceBucket
+#[derive(Default)]
+pub(crate) struct PerformanceAccumulator {
+    trade_count: i32,
+    win_count: i32,
+    loss_count: i32,
+    net_pnl: f64,
+}
+
+impl PerformanceAccumulator {
+    /// Add a closed trade; trades without a net PnL are ignored
+    pub(crate) fn add(&mut self, trade: &TradeWithDerived) {
+        let Some(net_pnl) = trade.net_pnl else {
+            return;
+        };
+        self.trade_count += 1;
+        self.net_pnl += net_pnl;
+        match trade.result {
+            Some(TradeResult::Win) => self.win_count += 1,
+            Some(TradeResult::Loss) => self.loss_count += 1,
+            _ => {}
+        }
+    }
+
+    pub(crate) fn into_bucket(self, key: String) -> PerformanceBucket {
+        let decisive = self.win_count + self.loss_count;
+        PerformanceBucket {
+            key,
+            trade_count: self.trade_count,
+            win_count: self.win_count,
+            loss_count: self.loss_count,
+            net_pnl: self.net_pnl,
+            avg_net_pnl: (self.trade_count > 0).then(|| self.net_pnl / self.trade_count as f64),
+            win_rate: (decisive > 0).then(|| self.win_count as f64 / decisive as f64),
+        }
+    }
+}
 
 /// Calculate daily performance metrics from a list of trades
-pub fn calculate_daily_metrics(trades: &[TradeWithDerived]) -> Vec<DailyPerformance> {
+/// Trades count toward their date in exit_dates, or their trade date when absent. The stored
+/// summary is summed in SQL by daily_performance_repo; this is what its tests check it against.
+#[cfg(test)]
+pub fn calculate_daily_metrics(trades: &[TradeWithDerived], exit_dates: &HashMap<String, NaiveDate>) -> Vec<DailyPerformance> {
     let mut daily_map: HashMap<NaiveDate, DailyPerformance> = HashMap::new();
 
     for trade in trades {
         // Only include closed trades with net_pnl
         if let Some(net_pnl) = trade.net_pnl {
-            let date = trade.trade.trade_date;
-            let entry = daily_map.entry(date).or_insert_with(|| DailyPerformance {
-                date,
-                realized_net_pnl: 0.0,
-                trade_count: 0,
-                win_count: 0,
-                loss_count: 0,
-            });
+            let date = exit_dates.get(&trade.trade.id).copied().unwrap_or(trade.trade.trade_date);
+            let entry = daily_map.entry(date).or_insert_with(|| empty_day(date));
 
             entry.realized_net_pnl += net_pnl;
+            entry.gross_pnl += trade.gross_pnl.unwrap_or(0.0);
+            entry.total_fees += trade.trade.fees;
+            entry.total_volume += trade.trade.quantity.unwrap_or(0.0);
             entry.trade_count += 1;
 
+            if net_pnl > 0.0 {
+                entry.largest_win = Some(entry.largest_win.map_or(net_pnl, |w| w.max(net_pnl)));
+            } else if net_pnl < 0.0 {
+                entry.largest_loss = Some(entry.largest_loss.map_or(net_pnl, |l| l.min(net_pnl)));
+            }
+
             if let Some(result) = trade.result {
                 match result {
                     TradeResult::Win => entry.win_count += 1,
                     TradeResult::Loss => entry.loss_count += 1,
-                    TradeResult::Breakeven => {} // Excluded from win/loss counts
+                    TradeResult::Breakeven => entry.breakeven_count += 1, // Excluded from win/loss counts
                 }
             }
         }
     }
 
+    for day in daily_map.values_mut() {
+        day.scratch_rate = day.breakeven_count as f64 / day.trade_count as f64;
+        day.avg_trade_pnl = day.realized_net_pnl / day.trade_count as f64;
+    }
+
     let mut result: Vec<DailyPerformance> = daily_map.into_values().collect();
     result.sort_by_key(|d| d.date);
     result
 }
 
+/// Combine accounts' daily performance into one row per day
+pub fn merge_account_days(days: Vec<(String, DailyPerformance)>) -> Vec<DailyPerformance> {
+    let mut by_date: BTreeMap<NaiveDate, DailyPerformance> = BTreeMap::new();
+
+    for (_, day) in days {
+        let Some(merged) = by_date.get_mut(&day.date) else {
+            by_date.insert(day.date, day);
+            continue;
+        };
+        merged.realized_net_pnl += day.realized_net_pnl;
+        merged.gross_pnl += day.gross_pnl;
+        merged.total_fees += day.total_fees;
+        merged.total_volume += day.total_volume;
+        merged.largest_win = match (merged.largest_win, day.largest_win) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        merged.largest_loss = match (merged.largest_loss, day.largest_loss) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        merged.trade_count += day.trade_count;
+        merged.win_count += day.win_count;
+        merged.loss_count += day.loss_count;
+        merged.breakeven_count += day.breakeven_count;
+        merged.scratch_rate = merged.breakeven_count as f64 / merged.trade_count as f64;
+        merged.avg_trade_pnl = merged.realized_net_pnl / merged.trade_count as f64;
+    }
+
+    by_date.into_values().collect()
+}
+
+fn empty_day(date: NaiveDate) -> DailyPerformance {
+    DailyPerformance {
+        date,
+        realized_net_pnl: 0.0,
+        gross_pnl: 0.0,
+        total_fees: 0.0,
+        total_volume: 0.0,
+        largest_win: None,
+        largest_loss: None,
+        trade_count: 0,
+        win_count: 0,
+        loss_count: 0,
+        breakeven_count: 0,
+        scratch_rate: 0.0,
+        avg_trade_pnl: 0.0,
+        unrealized_pnl_change: None,
+    }
+}
+
+/// Add the day-over-day change in value of open trades, marked to each stored close, to daily performance
+/// The first mark is against the entry price. Days with only unrealized changes are added with no trades.
+pub fn add_unrealized_changes(
+    days: Vec<DailyPerformance>,
+    open_trades: &[TradeWithDerived],
+    closes: &HashMap<String, BTreeMap<NaiveDate, f64>>,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Vec<DailyPerformance> {
+    let mut by_date: BTreeMap<NaiveDate, DailyPerformance> = days.into_iter().map(|d| (d.date, d)).collect();
+
+    for trade in open_trades {
+        let (Some(quantity), Some(symbol_closes)) = (trade.trade.quantity, closes.get(&trade.trade.symbol)) else {
+            continue;
+        };
+        let sign = match trade.trade.direction {
+            Direction::Long => 1.0,
+            Direction::Short => -1.0,
+        };
+        let size = sign * quantity * trade.trade.value_multiplier();
+
+        let last_day = open_exposure_end(&trade.trade, end_date);
+        if last_day < trade.trade.trade_date {
+            continue;
+        }
+
+        let mut mark = trade.trade.entry_price;
+        for (&date, &close) in symbol_closes.range(trade.trade.trade_date..=last_day) {
+            if date >= start_date {
+                let day = by_date.entry(date).or_insert_with(|| empty_day(date));
+                *day.unrealized_pnl_change.get_or_insert(0.0) += (close - mark) * size;
+            }
+            mark = close;
+        }
+    }
+
+    by_date.into_values().collect()
+}
+
+/// Last day the exposure of a position without a closing exit counts
+/// A rolled or transferred position hands its exposure to the continuation trade on its roll date,
+/// open positions run to `end_date` and other closed ones end on their trade date.
+pub fn open_exposure_end(trade: &Trade, end_date: NaiveDate) -> NaiveDate {
+    match trade.rolled_on.and_then(|d| d.pred_opt()) {
+        Some(last_day) => last_day.min(end_date),
+        None if trade.status == Status::Open => end_date,
+        None => trade.trade_date,
+    }
+}
+
 /// Calculate period metrics from a list of trades
 pub fn calculate_period_metrics(trades: &[TradeWithDerived]) -> PeriodMetrics {
     if trades.is_empty() {
@@ -43,6 +389,7 @@ pub fn calculate_period_metrics(trades: &[TradeWithDerived]) -> PeriodMetrics {
     }
 
     let mut total_net_pnl = 0.0;
+    let mut total_gross_pnl = 0.0;
     let mut win_count = 0;
     let mut loss_count = 0;
     let mut breakeven_count = 0;
@@ -55,13 +402,14 @@ pub fn calculate_period_metrics(trades: &[TradeWithDerived]) -> PeriodMetrics {
     let mut max_win_streak = 0;
     let mut max_loss_streak = 0;
 
-    // Sort trades by date for streak calculation
+    // Sort trades chronologically for streak calculation
     let mut sorted_trades: Vec<&TradeWithDerived> = trades.iter().collect();
-    sorted_trades.sort_by_key(|t| t.trade.trade_date);
+    sort_chronologically(&mut sorted_trades);
 
     for trade in &sorted_trades {
         if let Some(net_pnl) = trade.net_pnl {
             total_net_pnl += net_pnl;
+            total_gross_pnl += trade.gross_pnl.unwrap_or(0.0);
 
             match trade.result {
                 Some(TradeResult::Win) => {
@@ -92,6 +440,25 @@ pub fn calculate_period_metrics(trades: &[TradeWithDerived]) -> PeriodMetrics {
     let trade_count = win_count + loss_count + breakeven_count;
     let decisive_count = win_count + loss_count;
 
+    // Overrides only reclassify results; PnL figures stay as computed
+    let override_count = sorted_trades
+        .iter()
+        .filter(|t| t.result.is_some() && t.trade.result_override.is_some())
+        .count() as i32;
+    let adjusted_wins = sorted_trades
+        .iter()
+        .filter(|t| t.effective_result() == Some(TradeResult::Win))
+        .count();
+    let adjusted_losses = sorted_trades
+        .iter()
+        .filter(|t| t.effective_result() == Some(TradeResult::Loss))
+        .count();
+    let adjusted_win_rate = if adjusted_wins + adjusted_losses > 0 {
+        Some(adjusted_wins as f64 / (adjusted_wins + adjusted_losses) as f64)
+    } else {
+        None
+    };
+
     // Win rate (excluding breakeven)
     let win_rate = if decisive_count > 0 {
         Some(win_count as f64 / decisive_count as f64)
@@ -123,12 +490,18 @@ pub fn calculate_period_metrics(trades: &[TradeWithDerived]) -> PeriodMetrics {
     };
 
     // Expectancy = (win_rate × avg_win) + ((1 - win_rate) × avg_loss)
-    let expectancy = match (win_rate, avg_win, avg_loss) {
+    let expectancy_at = |wr: Option<f64>| match (wr, avg_win, avg_loss) {
         (Some(wr), Some(aw), Some(al)) => {
             Some((wr * aw) + ((1.0 - wr) * al))
         }
         _ => None,
     };
+    let expectancy = expectancy_at(win_rate);
+
+    // The win rate's uncertainty carries into expectancy, with the average win and loss held fixed
+    let interval = wilson_interval(win_count, decisive_count);
+    let (win_rate_low, win_rate_high) = (interval.map(|(low, _)| low), interval.map(|(_, high)| high));
+    let (expectancy_low, expectancy_high) = (expectancy_at(win_rate_low), expectancy_at(win_rate_high));
 
     // Calculate max drawdown from equity curve
     let equity_curve = calculate_equity_curve(&sorted_trades);
@@ -139,19 +512,87 @@ pub fn calculate_period_metrics(trades: &[TradeWithDerived]) -> PeriodMetrics {
 
     PeriodMetrics {
         total_net_pnl,
+        total_gross_pnl,
         trade_count,
         win_count,
         loss_count,
         breakeven_count,
         win_rate,
+        win_rate_low,
+        win_rate_high,
+        override_count,
+        adjusted_win_rate,
         avg_win,
         avg_loss,
         profit_factor,
         expectancy,
+        expectancy_low,
+        expectancy_high,
         max_drawdown,
+        max_drawdown_r: None,
+        max_drawdown_pct: None,
         max_win_streak,
         max_loss_streak,
+        current_win_streak,
+        current_loss_streak,
+    }
+}
+
+/// z-score of the 95% confidence level reported around win rates
+const CONFIDENCE_Z: f64 = 1.96;
+
+/// 95% Wilson score interval for a proportion of `successes` in `trials`
+/// Unlike the normal approximation it stays within 0..1 and is sensible for small samples and
+/// proportions near 0 or 1.
+pub fn wilson_interval(successes: i32, trials: i32) -> Option<(f64, f64)> {
+    if trials <= 0 {
+        return None;
+    }
+    let n = trials as f64;
+    let p = successes as f64 / n;
+    let z2 = CONFIDENCE_Z * CONFIDENCE_Z;
+
+    let center = p + z2 / (2.0 * n);
+    let margin = CONFIDENCE_Z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+    let denominator = 1.0 + z2 / n;
+    Some((
+        ((center - margin) / denominator).max(0.0),
+        ((center + margin) / denominator).min(1.0),
+    ))
+}
+
+/// Average dollar risk of closed trades with a stop loss
+pub fn calculate_average_risk(trades: &[TradeWithDerived]) -> Option<f64> {
+    let risks: Vec<f64> = trades
+        .iter()
+        .filter(|t| t.net_pnl.is_some())
+        .filter_map(|t| match (t.risk_per_share, t.trade.quantity) {
+            (Some(risk), Some(qty)) => Some(calculate_risk_amount(risk, qty, t.trade.value_multiplier())),
+            _ => None,
+        })
+        .collect();
+
+    if risks.is_empty() {
+        None
+    } else {
+        Some(risks.iter().sum::<f64>() / risks.len() as f64)
+    }
+}
+
+/// Largest drawdown as a fraction of peak equity (starting balance + cumulative PnL at the peak)
+pub fn calculate_max_drawdown_pct(trades: &[TradeWithDerived], starting_balance: f64) -> Option<f64> {
+    if starting_balance <= 0.0 {
+        return None;
     }
+
+    let refs: Vec<&TradeWithDerived> = trades.iter().collect();
+    calculate_equity_curve(&refs)
+        .iter()
+        .map(|p| {
+            let peak_equity = starting_balance + p.cumulative_pnl + p.drawdown;
+            if peak_equity > 0.0 { p.drawdown / peak_equity } else { 1.0 }
+        })
+        .reduce(f64::max)
 }
 
 /// Calculate equity curve from a list of trades (aggregated by day)
@@ -194,6 +635,154 @@ pub fn calculate_equity_curve_owned(trades: &[TradeWithDerived]) -> Vec<EquityPo
     calculate_equity_curve(&refs)
 }
 
+/// Key used for trades without a strategy when grouping equity curves by strategy
+pub const NO_STRATEGY_KEY: &str = "No strategy";
+
+/// Calculate one equity curve per strategy or account, sorted by key
+pub fn calculate_equity_curves_by(
+    trades: &[TradeWithDerived],
+    grouping: EquityCurveGrouping,
+) -> Vec<EquitySeries> {
+    let mut groups: HashMap<String, Vec<&TradeWithDerived>> = HashMap::new();
+
+    for trade in trades {
+        let key = match grouping {
+            EquityCurveGrouping::Strategy => strategy_key(&trade.trade),
+            EquityCurveGrouping::Account => trade.trade.account_id.clone(),
+        };
+        groups.entry(key).or_default().push(trade);
+    }
+
+    let mut series: Vec<EquitySeries> = groups
+        .into_iter()
+        .map(|(key, trades)| EquitySeries {
+            key,
+            points: calculate_equity_curve(&trades),
+        })
+        .filter(|s| !s.points.is_empty())
+        .collect();
+    series.sort_by(|a, b| a.key.cmp(&b.key));
+    series
+}
+
+/// Strategy a trade is grouped under, NO_STRATEGY_KEY when it has none
+fn strategy_key(trade: &Trade) -> String {
+    trade
+        .strategy
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or(NO_STRATEGY_KEY)
+        .to_string()
+}
+
+/// Calculate each strategy's rolling performance, sorted by strategy
+/// There is a point for every day a strategy closed a trade from start_date on, covering its
+/// closed trades of the window_days calendar days ending that day. Earlier trades only fill windows.
+pub fn calculate_strategy_trends(
+    trades: &[TradeWithDerived],
+    window_days: i64,
+    start_date: Option<NaiveDate>,
+) -> Vec<StrategyTrend> {
+    let mut groups: BTreeMap<String, Vec<&TradeWithDerived>> = BTreeMap::new();
+    for trade in trades.iter().filter(|t| t.net_pnl.is_some()) {
+        groups.entry(strategy_key(&trade.trade)).or_default().push(trade);
+    }
+
+    groups
+        .into_iter()
+        .map(|(strategy, trades)| {
+            let mut dates: Vec<NaiveDate> = trades
+                .iter()
+                .map(|t| t.trade.trade_date)
+                .filter(|date| start_date.is_none_or(|start| *date >= start))
+                .collect();
+            dates.sort();
+            dates.dedup();
+
+            let points = dates
+                .into_iter()
+                .map(|date| {
+                    let window_start = date - chrono::Duration::days(window_days - 1);
+                    let mut acc = PerformanceAccumulator::default();
+                    for trade in trades.iter().filter(|t| (window_start..=date).contains(&t.trade.trade_date)) {
+                        acc.add(trade);
+                    }
+                    let bucket = acc.into_bucket(String::new());
+                    StrategyTrendPoint {
+                        date,
+                        trade_count: bucket.trade_count,
+                        net_pnl: bucket.net_pnl,
+                        expectancy: bucket.avg_net_pnl,
+                        win_rate: bucket.win_rate,
+                    }
+                })
+                .collect();
+            StrategyTrend { strategy, points }
+        })
+        .filter(|trend| !trend.points.is_empty())
+        .collect()
+}
+
+/// Calculate the total open stop-based risk for each day in a range
+/// A position counts as open from its trade date through its last exit date.
+/// Closed trades without exit executions are treated as closing on the trade date;
+/// open trades stay open through the end of the range, and rolled ones until the day before the roll.
+/// Risk is measured to the latest stop adjusted by that day, falling back to the initial stop.
+pub fn calculate_portfolio_heat(
+    trades: &[TradeWithDerived],
+    exit_dates: &HashMap<String, NaiveDate>,
+    stop_adjustments: &HashMap<String, Vec<StopAdjustment>>,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Vec<PortfolioHeatPoint> {
+    let mut points = Vec::new();
+    let mut date = start_date;
+
+    while date <= end_date {
+        let mut point = PortfolioHeatPoint {
+            date,
+            open_risk: 0.0,
+            open_positions: 0,
+            unprotected_positions: 0,
+        };
+
+        for trade in trades {
+            let opened = trade.trade.trade_date;
+            let closed = match exit_dates.get(&trade.trade.id) {
+                Some(&exit_date) if trade.trade.status == Status::Closed => exit_date,
+                _ => open_exposure_end(&trade.trade, end_date),
+            };
+            if date < opened || date > closed {
+                continue;
+            }
+
+            point.open_positions += 1;
+            let adjusted_stop = stop_adjustments
+                .get(&trade.trade.id)
+                .and_then(|adjustments| adjusted_stop_as_of(adjustments, date));
+            let risk_per_share = match adjusted_stop {
+                Some(stop) => Some(calculate_open_risk_per_share(trade.trade.direction, trade.trade.entry_price, stop)),
+                None => trade.risk_per_share,
+            };
+            match (risk_per_share, trade.trade.quantity) {
+                (Some(risk), Some(qty)) => {
+                    point.open_risk += calculate_risk_amount(risk, qty, trade.trade.value_multiplier());
+                }
+                _ => point.unprotected_positions += 1,
+            }
+        }
+
+        points.push(point);
+        date = match date.succ_opt() {
+            Some(next) => next,
+            None => break,
+        };
+    }
+
+    points
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -208,20 +797,38 @@ mod tests {
             instrument_id: "inst1".to_string(),
             symbol: "AAPL".to_string(),
             asset_class: AssetClass::Stock,
+            contract_multiplier: None,
+            fx_rate: None,
             trade_number: None,
+            ref_code: None,
             trade_date: date,
             direction: Direction::Long,
             quantity: Some(100.0),
             entry_price: 100.0,
             exit_price: Some(if net_pnl >= 0.0 { 101.0 } else { 99.0 }),
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
+            max_adverse_price: None,
+            entry_atr: None,
             entry_time: None,
             exit_time: None,
+            exit_date: None,
             fees: 0.0,
+            carrying_costs: 0.0,
+            dividends: 0.0,
             strategy: None,
             notes: None,
             screenshot_url: None,
             status: Status::Closed,
+            result_override: None,
+            roll_type: None,
+            rolled_on: None,
+            continuation_trade_id: None,
+            option_outcome: None,
+            borrow_availability: None,
+            borrow_note: None,
+            is_locked: false,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -233,7 +840,10 @@ mod tests {
             pnl_per_share: None,
             risk_per_share: None,
             r_multiple: None,
+            atr_multiple: None,
             result: Some(result),
+            trade_type: None,
+            executions: None,
         }
     }
 
@@ -309,6 +919,59 @@ mod tests {
         assert_eq!(metrics.max_win_streak, 3);
     }
 
+    #[test]
+    fn test_streaks_order_same_day_trades_by_entry_time() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let mut late_loss = create_test_trade(-100.0, TradeResult::Loss, date);
+        late_loss.trade.entry_time = Some("15:30:00".to_string());
+        let mut early_win = create_test_trade(100.0, TradeResult::Win, date);
+        early_win.trade.entry_time = Some("09:45:00".to_string());
+        let mut mid_win = create_test_trade(50.0, TradeResult::Win, date);
+        mid_win.trade.entry_time = Some("11:00:00".to_string());
+
+        let metrics = calculate_period_metrics(&[late_loss, early_win, mid_win]);
+        assert_eq!(metrics.max_win_streak, 2);
+        assert_eq!(metrics.current_win_streak, 0);
+        assert_eq!(metrics.current_loss_streak, 1);
+    }
+
+    #[test]
+    fn test_result_override_only_affects_adjusted_win_rate() {
+        let mut rule_break = create_test_trade(10.0, TradeResult::Win, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+        rule_break.trade.result_override = Some(TradeResult::Loss);
+        let trades = vec![
+            rule_break,
+            create_test_trade(100.0, TradeResult::Win, NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()),
+        ];
+
+        let metrics = calculate_period_metrics(&trades);
+        assert_eq!(metrics.win_rate, Some(1.0));
+        assert_eq!(metrics.override_count, 1);
+        assert_eq!(metrics.adjusted_win_rate, Some(0.5));
+        assert!((metrics.total_net_pnl - 110.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_trade_sequence_report_tracks_running_day_pnl() {
+        let day1 = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let day2 = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let mut second = create_test_trade(-80.0, TradeResult::Loss, day1);
+        second.trade.entry_time = Some("10:30:00".to_string());
+        let mut first = create_test_trade(100.0, TradeResult::Win, day1);
+        first.trade.entry_time = Some("09:35:00".to_string());
+        let only = create_test_trade(-20.0, TradeResult::Loss, day2);
+
+        let report = calculate_trade_sequence_report(&[second, first, only]);
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].sequence, 1);
+        assert_eq!(report[0].trade_count, 2);
+        assert!((report[0].net_pnl - 80.0).abs() < 0.01);
+        assert!((report[0].avg_cumulative_pnl - 40.0).abs() < 0.01);
+        assert_eq!(report[1].trade_count, 1);
+        assert_eq!(report[1].win_rate, Some(0.0));
+        assert!((report[1].avg_cumulative_pnl - 20.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_expectancy() {
         let trades = vec![
@@ -322,5 +985,217 @@ mod tests {
         // expectancy = (0.5 * 200) + (0.5 * -100) = 100 - 50 = 50
         assert!(metrics.expectancy.is_some());
         assert!((metrics.expectancy.unwrap() - 50.0).abs() < 0.01);
+        // Two trades leave the win rate anywhere from 9% to 91%
+        assert!((metrics.win_rate_low.unwrap() - 0.0945).abs() < 0.001);
+        assert!((metrics.expectancy_low.unwrap() - (-71.65)).abs() < 0.1);
+        assert!((metrics.expectancy_high.unwrap() - 171.65).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_wilson_interval_narrows_with_sample_size() {
+        let (low, high) = wilson_interval(7, 10).unwrap();
+        assert!((low - 0.3968).abs() < 0.001);
+        assert!((high - 0.8922).abs() < 0.001);
+
+        let (low, high) = wilson_interval(350, 500).unwrap();
+        assert!((low - 0.6585).abs() < 0.001);
+        assert!((high - 0.7384).abs() < 0.001);
+
+        // No losses still leaves room below 100%
+        let (low, high) = wilson_interval(5, 5).unwrap();
+        assert!(low < 0.6 && high == 1.0);
+        assert_eq!(wilson_interval(0, 0), None);
+    }
+
+    #[test]
+    fn test_portfolio_heat_overlapping_positions() {
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+
+        // Swing trade held Jan 1-3 risking 2 * 100 = 200
+        let mut swing = create_test_trade(100.0, TradeResult::Win, day(1));
+        swing.risk_per_share = Some(2.0);
+        // Day trade on Jan 2 risking 1 * 100 = 100
+        let mut day_trade = create_test_trade(50.0, TradeResult::Win, day(2));
+        day_trade.risk_per_share = Some(1.0);
+        // Day trade on Jan 2 without a stop
+        let no_stop = create_test_trade(-25.0, TradeResult::Loss, day(2));
+
+        let mut exit_dates = HashMap::new();
+        exit_dates.insert(swing.trade.id.clone(), day(3));
+
+        let heat = calculate_portfolio_heat(&[swing, day_trade, no_stop], &exit_dates, &HashMap::new(), day(1), day(4));
+
+        assert_eq!(heat.len(), 4);
+        assert!((heat[0].open_risk - 200.0).abs() < 0.01);
+        assert_eq!(heat[1].open_positions, 3);
+        assert_eq!(heat[1].unprotected_positions, 1);
+        assert!((heat[1].open_risk - 300.0).abs() < 0.01);
+        assert!((heat[2].open_risk - 200.0).abs() < 0.01);
+        assert_eq!(heat[3].open_positions, 0);
+    }
+
+    #[test]
+    fn test_portfolio_heat_open_trade_extends_to_end() {
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+
+        let mut open = create_test_trade(0.0, TradeResult::Breakeven, day(1));
+        open.trade.status = Status::Open;
+        open.risk_per_share = Some(1.0);
+
+        let heat = calculate_portfolio_heat(&[open], &HashMap::new(), &HashMap::new(), day(1), day(5));
+
+        assert!(heat.iter().all(|p| p.open_positions == 1));
+        assert!((heat[4].open_risk - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_portfolio_heat_rolled_position_hands_off_exposure() {
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+
+        // March contract rolled into June on Jan 3; only one of them is held each day
+        let mut front = create_test_trade(0.0, TradeResult::Breakeven, day(1));
+        front.trade.rolled_on = Some(day(3));
+        let mut back = create_test_trade(0.0, TradeResult::Breakeven, day(3));
+        back.trade.id = "back".to_string();
+        back.trade.status = Status::Open;
+
+        let heat = calculate_portfolio_heat(&[front, back], &HashMap::new(), &HashMap::new(), day(1), day(5));
+
+        assert!(heat.iter().all(|p| p.open_positions == 1));
+    }
+
+    #[test]
+    fn test_equity_curves_by_strategy() {
+        let day1 = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let day2 = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+
+        let mut breakout_win = create_test_trade(100.0, TradeResult::Win, day1);
+        breakout_win.trade.strategy = Some("Breakout".to_string());
+        let mut breakout_loss = create_test_trade(-40.0, TradeResult::Loss, day2);
+        breakout_loss.trade.strategy = Some("Breakout".to_string());
+        let untagged = create_test_trade(25.0, TradeResult::Win, day2);
+
+        let series = calculate_equity_curves_by(
+            &[breakout_win, breakout_loss, untagged],
+            EquityCurveGrouping::Strategy,
+        );
+
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].key, "Breakout");
+        assert_eq!(series[0].points.len(), 2);
+        assert!((series[0].points[1].cumulative_pnl - 60.0).abs() < 0.01);
+        assert!((series[0].points[1].drawdown - 40.0).abs() < 0.01);
+        assert_eq!(series[1].key, NO_STRATEGY_KEY);
+        assert_eq!(series[1].points.len(), 1);
+    }
+
+    #[test]
+    fn test_strategy_trends_roll_over_window() {
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let breakout = |pnl: f64, result, d| {
+            let mut trade = create_test_trade(pnl, result, day(d));
+            trade.trade.strategy = Some("Breakout".to_string());
+            trade
+        };
+        let trades = vec![
+            breakout(300.0, TradeResult::Win, 1),
+            breakout(100.0, TradeResult::Win, 5),
+            breakout(-50.0, TradeResult::Loss, 10),
+            breakout(-70.0, TradeResult::Loss, 12),
+            create_test_trade(25.0, TradeResult::Win, day(12)),
+        ];
+
+        let trends = calculate_strategy_trends(&trades, 7, Some(day(5)));
+
+        assert_eq!(trends.len(), 2);
+        assert_eq!(trends[0].strategy, "Breakout");
+        let points = &trends[0].points;
+        // Jan 1 is before the start but still counts toward the Jan 5 window
+        assert_eq!(points.iter().map(|p| p.date).collect::<Vec<_>>(), vec![day(5), day(10), day(12)]);
+        assert_eq!(points[0].trade_count, 2);
+        assert_eq!(points[0].expectancy, Some(200.0));
+        // Jan 4 through Jan 10
+        assert_eq!(points[1].trade_count, 2);
+        assert_eq!(points[1].expectancy, Some(25.0));
+        // Jan 6 through Jan 12: the setup has decayed
+        assert_eq!(points[2].expectancy, Some(-60.0));
+        assert_eq!(points[2].win_rate, Some(0.0));
+        assert_eq!(trends[1].strategy, NO_STRATEGY_KEY);
+    }
+
+    #[test]
+    fn test_entry_cohort_report() {
+        let trades = vec![
+            create_test_trade(-100.0, TradeResult::Loss, NaiveDate::from_ymd_opt(2024, 1, 5).unwrap()),
+            create_test_trade(40.0, TradeResult::Win, NaiveDate::from_ymd_opt(2024, 1, 20).unwrap()),
+            create_test_trade(80.0, TradeResult::Win, NaiveDate::from_ymd_opt(2024, 3, 2).unwrap()),
+        ];
+
+        let cohorts = calculate_entry_cohort_report(&trades);
+
+        assert_eq!(cohorts.len(), 2);
+        assert_eq!(cohorts[0].performance.key, "2024-01");
+        assert_eq!(cohorts[0].performance.trade_count, 2);
+        assert_eq!(cohorts[0].performance.avg_net_pnl, Some(-30.0));
+        assert_eq!(cohorts[0].expectancy_change, None);
+        // February had no trades, so March is compared with January
+        assert_eq!(cohorts[1].performance.key, "2024-03");
+        assert_eq!(cohorts[1].expectancy_change, Some(110.0));
+    }
+
+    #[test]
+    fn test_symbol_month_matrix() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        let mut brk = create_test_trade(-50.0, TradeResult::Loss, date(1, 30));
+        brk.trade.symbol = "BRK,B".to_string();
+        let trades = vec![
+            create_test_trade(100.0, TradeResult::Win, date(1, 5)),
+            create_test_trade(-40.0, TradeResult::Loss, date(1, 20)),
+            create_test_trade(70.0, TradeResult::Win, date(3, 2)),
+            brk.clone(),
+        ];
+        // Exited in February
+        let exit_dates = HashMap::from([(brk.trade.id.clone(), date(2, 1))]);
+
+        let matrix = calculate_symbol_month_matrix(&trades, &exit_dates);
+        assert_eq!(matrix.months, vec!["2024-01", "2024-02", "2024-03"]);
+        assert_eq!(matrix.rows[0].symbol, "AAPL");
+        assert_eq!((matrix.rows[0].net_pnl, matrix.rows[0].trade_count), (130.0, 3));
+        assert_eq!(matrix.rows[0].cells[0], SymbolMonthCell { net_pnl: 60.0, trade_count: 2 });
+        assert_eq!(matrix.rows[0].cells[1], SymbolMonthCell::default());
+        assert_eq!(matrix.rows[1].cells[1].net_pnl, -50.0);
+
+        let csv = symbol_month_matrix_csv(&matrix);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "symbol,2024-01 net_pnl,2024-01 trades,2024-02 net_pnl,2024-02 trades,2024-03 net_pnl,2024-03 trades,total net_pnl,total trades"
+        );
+        assert_eq!(lines[1], "AAPL,60.00,2,0.00,0,70.00,1,130.00,3");
+        assert_eq!(lines[2], "\"BRK,B\",0.00,0,-50.00,1,0.00,0,-50.00,1");
+        assert!(calculate_symbol_month_matrix(&[], &exit_dates).rows.is_empty());
+    }
+
+    #[test]
+    fn test_max_drawdown_pct_uses_peak_equity() {
+        let trades = vec![
+            create_test_trade(1000.0, TradeResult::Win, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
+            create_test_trade(-550.0, TradeResult::Loss, NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()),
+        ];
+
+        // Peak equity 11,000, drawdown 550 = 5%
+        let pct = calculate_max_drawdown_pct(&trades, 10_000.0).unwrap();
+        assert!((pct - 0.05).abs() < 0.0001);
+        assert!(calculate_max_drawdown_pct(&trades, 0.0).is_none());
+    }
+
+    #[test]
+    fn test_average_risk_only_counts_trades_with_stops() {
+        let mut with_stop = create_test_trade(100.0, TradeResult::Win, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+        with_stop.risk_per_share = Some(2.0); // 100 shares
+        let without_stop = create_test_trade(-50.0, TradeResult::Loss, NaiveDate::from_ymd_opt(2024, 1, 2).unwrap());
+
+        assert_eq!(calculate_average_risk(&[with_stop, without_stop.clone()]), Some(200.0));
+        assert_eq!(calculate_average_risk(&[without_stop]), None);
     }
 }
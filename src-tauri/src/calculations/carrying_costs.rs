@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use crate::models::{CarryingCost, CarryingCostBucket, CarryingCostReport};
+
+/// Build buckets sorted with the most expensive group first
+fn into_buckets(map: HashMap<String, (i32, f64)>) -> Vec<CarryingCostBucket> {
+    let mut buckets: Vec<CarryingCostBucket> = map
+        .into_iter()
+        .map(|(key, (entry_count, total_amount))| CarryingCostBucket {
+            key,
+            entry_count,
+            total_amount,
+        })
+        .collect();
+    buckets.sort_by(|a, b| b.total_amount.total_cmp(&a.total_amount));
+    buckets
+}
+
+/// Aggregate carrying cost entries by cost type and by symbol
+pub fn calculate_carrying_cost_report(costs: &[CarryingCost]) -> CarryingCostReport {
+    let mut by_type: HashMap<String, (i32, f64)> = HashMap::new();
+    let mut by_symbol: HashMap<String, (i32, f64)> = HashMap::new();
+
+    for cost in costs {
+        let entry = by_type.entry(cost.cost_type.as_str().to_string()).or_default();
+        entry.0 += 1;
+        entry.1 += cost.amount;
+
+        let entry = by_symbol.entry(cost.symbol.clone()).or_default();
+        entry.0 += 1;
+        entry.1 += cost.amount;
+    }
+
+    CarryingCostReport {
+        total_amount: costs.iter().map(|c| c.amount).sum(),
+        entry_count: costs.len() as i32,
+        by_type: into_buckets(by_type),
+        by_symbol: into_buckets(by_symbol),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+    use crate::models::CarryingCostType;
+
+    fn cost(symbol: &str, cost_type: CarryingCostType, amount: f64) -> CarryingCost {
+        CarryingCost {
+            id: uuid::Uuid::new_v4().to_string(),
+            trade_id: "trade1".to_string(),
+            symbol: symbol.to_string(),
+            cost_type,
+            cost_date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            amount,
+            notes: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_carrying_cost_report_empty() {
+        let report = calculate_carrying_cost_report(&[]);
+        assert_eq!(report.entry_count, 0);
+        assert_eq!(report.total_amount, 0.0);
+        assert!(report.by_type.is_empty());
+    }
+
+    #[test]
+    fn test_carrying_cost_report_groups_by_type_and_symbol() {
+        let costs = vec![
+            cost("GME", CarryingCostType::BorrowFee, 12.0),
+            cost("GME", CarryingCostType::BorrowFee, 8.0),
+            cost("AAPL", CarryingCostType::MarginInterest, 3.0),
+            cost("EURUSD", CarryingCostType::Swap, -1.5), // Credit
+        ];
+
+        let report = calculate_carrying_cost_report(&costs);
+
+        assert_eq!(report.entry_count, 4);
+        assert!((report.total_amount - 21.5).abs() < 0.001);
+
+        assert_eq!(report.by_type[0].key, "borrow_fee");
+        assert_eq!(report.by_type[0].entry_count, 2);
+        assert!((report.by_type[0].total_amount - 20.0).abs() < 0.001);
+        assert_eq!(report.by_type[2].key, "swap");
+
+        assert_eq!(report.by_symbol[0].key, "GME");
+        assert_eq!(report.by_symbol.len(), 3);
+    }
+}
@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+use crate::models::{ChecklistCheck, ChecklistPhase, ChecklistReport, TradeWithDerived};
+
+/// Compare closed trades whose checklist was completed before entry with those entered before confirmation
+/// A trade counts as entered before confirmation when any of its items was checked after entry.
+pub fn calculate_checklist_report(trades: &[TradeWithDerived], checks: &[ChecklistCheck]) -> ChecklistReport {
+    let mut entered_early: HashMap<&str, bool> = HashMap::new();
+    for check in checks {
+        let early = entered_early.entry(check.trade_id.as_str()).or_default();
+        *early |= check.phase == ChecklistPhase::PostEntry;
+    }
+
+    let mut report = ChecklistReport::default();
+    for trade in trades {
+        let (Some(net_pnl), Some(&early)) = (trade.net_pnl, entered_early.get(trade.trade.id.as_str())) else {
+            continue;
+        };
+
+        report.checklist_trade_count += 1;
+        if early {
+            report.entered_before_confirmation_count += 1;
+            report.entered_before_confirmation_net_pnl += net_pnl;
+            report.entered_before_confirmation_trade_ids.push(trade.trade.id.clone());
+        } else {
+            report.confirmed_count += 1;
+            report.confirmed_net_pnl += net_pnl;
+        }
+    }
+
+    if report.checklist_trade_count > 0 {
+        report.entered_before_confirmation_rate =
+            Some(report.entered_before_confirmation_count as f64 / report.checklist_trade_count as f64);
+    }
+    report
+}
@@ -0,0 +1,319 @@
+use std::collections::{BTreeSet, HashMap};
+use chrono::{NaiveTime, Timelike};
+use crate::calculations::{calculate_hold_minutes, calculate_trade_r, sort_chronologically, PerformanceAccumulator};
+use crate::models::{TradeCluster, TradeClusterReport, TradeWithDerived};
+
+/// Closed trades per cluster when no cluster count is given
+const TRADES_PER_CLUSTER: usize = 15;
+/// Most clusters formed when no cluster count is given
+const MAX_DEFAULT_CLUSTERS: usize = 6;
+const MAX_ITERATIONS: usize = 100;
+/// Standard deviations from the average trade at which a feature sets a cluster apart
+const DISTINCTIVE_Z: f64 = 0.5;
+const MINUTES_PER_DAY: f64 = 24.0 * 60.0;
+
+/// Numeric features clustered on, each with how a cluster that stands out on it is described
+const FEATURES: [(&str, &str); 4] = [
+    ("Earlier entries", "Later entries"),
+    ("Shorter holds", "Longer holds"),
+    ("Lower R", "Higher R"),
+    ("Calmer symbols", "More volatile symbols"),
+];
+
+/// Group closed trades by similar time of day, hold time, R, symbol volatility and tags with k-means
+/// Numeric features are standardized so each weighs the same, and hold times are compared on a log
+/// scale so a day trade and a week-long swing are not dwarfed by a month-long position. Unknown
+/// values count as the average trade. Each tag is one more feature, 1 for tagged trades.
+/// Without a cluster count there is one cluster per 15 trades, from 2 to 6. Clusters start from
+/// the trades farthest apart, in chronological order, so the same trades always cluster the same.
+pub fn calculate_trade_clusters(
+    trades: &[TradeWithDerived],
+    tags: &HashMap<String, Vec<String>>,
+    cluster_count: Option<usize>,
+    default_risk: Option<f64>,
+) -> TradeClusterReport {
+    let mut closed: Vec<&TradeWithDerived> = trades.iter().filter(|t| t.net_pnl.is_some()).collect();
+    sort_chronologically(&mut closed);
+    if closed.is_empty() {
+        return TradeClusterReport::default();
+    }
+
+    let features: Vec<[Option<f64>; 4]> = closed.iter().map(|t| trade_features(t, default_risk)).collect();
+    let scales: Vec<Option<(f64, f64)>> = (0..FEATURES.len())
+        .map(|i| {
+            let values: Vec<f64> = features.iter().filter_map(|f| f[i].map(distance_scale(i))).collect();
+            mean_and_deviation(&values)
+        })
+        .collect();
+    let standardize = |i: usize, value: Option<f64>| match (value, scales[i]) {
+        (Some(value), Some((mean, deviation))) => Some((distance_scale(i)(value) - mean) / deviation),
+        _ => None,
+    };
+
+    let trade_tags: Vec<&[String]> = closed.iter().map(|t| tags.get(&t.trade.id).map_or(&[][..], |tags| tags.as_slice())).collect();
+    let tag_names: Vec<&String> = trade_tags.iter().flat_map(|tags| tags.iter()).collect::<BTreeSet<_>>().into_iter().collect();
+
+    let points: Vec<Vec<f64>> = features
+        .iter()
+        .zip(&trade_tags)
+        .map(|(values, tags)| {
+            let numeric = (0..FEATURES.len()).map(|i| standardize(i, values[i]).unwrap_or(0.0));
+            let tagged = tag_names.iter().map(|name| if tags.contains(name) { 1.0 } else { 0.0 });
+            numeric.chain(tagged).collect()
+        })
+        .collect();
+
+    let k = cluster_count
+        .unwrap_or((closed.len() / TRADES_PER_CLUSTER).clamp(2, MAX_DEFAULT_CLUSTERS))
+        .clamp(1, closed.len());
+    let assignment = kmeans(&points, k);
+
+    let tag_share = |members: &[usize], name: &String| {
+        members.iter().filter(|&&m| trade_tags[m].contains(name)).count() as f64 / members.len() as f64
+    };
+    let all: Vec<usize> = (0..closed.len()).collect();
+
+    let mut clusters: Vec<TradeCluster> = (0..k)
+        .map(|c| (0..closed.len()).filter(|&m| assignment[m] == c).collect::<Vec<usize>>())
+        .filter(|members| !members.is_empty())
+        .map(|members| {
+            let mut performance = PerformanceAccumulator::default();
+            let mut symbol_counts: HashMap<&str, usize> = HashMap::new();
+            for &m in &members {
+                performance.add(closed[m]);
+                *symbol_counts.entry(closed[m].trade.symbol.as_str()).or_default() += 1;
+            }
+            let mut symbols: Vec<(&str, usize)> = symbol_counts.into_iter().collect();
+            symbols.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+            let averages: Vec<Option<f64>> = (0..FEATURES.len())
+                .map(|i| mean(&members.iter().filter_map(|&m| features[m][i]).collect::<Vec<_>>()))
+                .collect();
+            let mut traits: Vec<String> = (0..FEATURES.len())
+                .filter_map(|i| {
+                    let z = mean(&members.iter().filter_map(|&m| standardize(i, features[m][i])).collect::<Vec<_>>())?;
+                    let (below, above) = FEATURES[i];
+                    let label = if z <= -DISTINCTIVE_Z {
+                        below
+                    } else if z >= DISTINCTIVE_Z {
+                        above
+                    } else {
+                        return None;
+                    };
+                    Some(format!("{} ({})", label, describe_feature(i, averages[i]?)))
+                })
+                .collect();
+
+            let cluster_tags: Vec<String> = tag_names
+                .iter()
+                .filter(|name| tag_share(&members, name) >= 0.5)
+                .map(|name| name.to_string())
+                .collect();
+            traits.extend(
+                cluster_tags
+                    .iter()
+                    .filter(|name| tag_share(&all, name) < tag_share(&members, name))
+                    .map(|name| format!("Tagged {}", name)),
+            );
+
+            TradeCluster {
+                performance: performance.into_bucket(String::new()),
+                traits,
+                avg_entry_minute: averages[0],
+                avg_hold_minutes: averages[1],
+                avg_r: averages[2],
+                avg_atr_pct: averages[3],
+                top_symbols: symbols.into_iter().take(3).map(|(symbol, _)| symbol.to_string()).collect(),
+                tags: cluster_tags,
+                trade_ids: members.iter().map(|&m| closed[m].trade.id.clone()).collect(),
+            }
+        })
+        .collect();
+
+    clusters.sort_by(|a, b| {
+        let avg = |c: &TradeCluster| c.performance.avg_net_pnl.unwrap_or(f64::NEG_INFINITY);
+        avg(b).total_cmp(&avg(a))
+    });
+    for (rank, cluster) in clusters.iter_mut().enumerate() {
+        cluster.performance.key = (rank + 1).to_string();
+    }
+
+    TradeClusterReport {
+        trade_count: closed.len() as i32,
+        clusters,
+    }
+}
+
+/// Entry minute after midnight, hold minutes, R and ATR as a fraction of the entry price
+fn trade_features(trade: &TradeWithDerived, default_risk: Option<f64>) -> [Option<f64>; 4] {
+    let t = &trade.trade;
+    let exit_date = t.exit_date.unwrap_or(t.trade_date);
+    let entry_minute = t
+        .entry_time
+        .as_deref()
+        .and_then(parse_time)
+        .map(|time| time.num_seconds_from_midnight() as f64 / 60.0);
+    // Overnight holds without times count whole days
+    let hold_minutes = calculate_hold_minutes(t.trade_date, t.entry_time.as_deref(), exit_date, t.exit_time.as_deref())
+        .or_else(|| {
+            let days = (exit_date - t.trade_date).num_days();
+            (days > 0).then_some(days as f64 * MINUTES_PER_DAY)
+        })
+        .map(|minutes| minutes.max(0.0));
+    let atr_pct = t
+        .entry_atr
+        .filter(|atr| *atr > 0.0)
+        .zip(Some(t.entry_price).filter(|price| *price > 0.0))
+        .map(|(atr, price)| atr / price);
+
+    [entry_minute, hold_minutes, calculate_trade_r(trade, default_risk).map(|(r, _)| r), atr_pct]
+}
+
+fn parse_time(time: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time.trim(), "%H:%M"))
+        .ok()
+}
+
+/// Scale a feature is compared on: logarithmic for hold times, linear otherwise
+fn distance_scale(feature: usize) -> fn(f64) -> f64 {
+    if feature == 1 {
+        f64::ln_1p
+    } else {
+        |value| value
+    }
+}
+
+fn describe_feature(feature: usize, average: f64) -> String {
+    match feature {
+        0 => {
+            let minute = average.round() as u32;
+            format!("avg {:02}:{:02}", minute / 60 % 24, minute % 60)
+        }
+        1 if average < 120.0 => format!("avg {:.0} min", average),
+        1 if average < 2.0 * MINUTES_PER_DAY => format!("avg {:.1} h", average / 60.0),
+        1 => format!("avg {:.1} days", average / MINUTES_PER_DAY),
+        2 => format!("avg {:.2}R", average),
+        _ => format!("ATR {:.1}% of price", average * 100.0),
+    }
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Mean and population standard deviation, None when the values do not vary
+fn mean_and_deviation(values: &[f64]) -> Option<(f64, f64)> {
+    let mean = mean(values)?;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    let deviation = variance.sqrt();
+    (deviation > 1e-9).then_some((mean, deviation))
+}
+
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+fn nearest(centroids: &[Vec<f64>], point: &[f64]) -> usize {
+    (0..centroids.len())
+        .min_by(|&a, &b| squared_distance(&centroids[a], point).total_cmp(&squared_distance(&centroids[b], point)))
+        .unwrap_or(0)
+}
+
+/// Cluster index of each point after Lloyd's iterations from farthest-first starting centroids
+fn kmeans(points: &[Vec<f64>], k: usize) -> Vec<usize> {
+    let mut centroids = vec![points[0].clone()];
+    while centroids.len() < k {
+        let distance_to_centroids = |p: &Vec<f64>| {
+            centroids.iter().map(|c| squared_distance(c, p)).fold(f64::INFINITY, f64::min)
+        };
+        let farthest = (0..points.len())
+            .max_by(|&a, &b| distance_to_centroids(&points[a]).total_cmp(&distance_to_centroids(&points[b])).then(b.cmp(&a)))
+            .unwrap_or(0);
+        centroids.push(points[farthest].clone());
+    }
+
+    let mut assignment: Vec<usize> = Vec::new();
+    for _ in 0..MAX_ITERATIONS {
+        let next: Vec<usize> = points.iter().map(|p| nearest(&centroids, p)).collect();
+        if next == assignment {
+            break;
+        }
+        assignment = next;
+
+        for (c, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<&Vec<f64>> = points.iter().zip(&assignment).filter(|(_, a)| **a == c).map(|(p, _)| p).collect();
+            if members.is_empty() {
+                continue;
+            }
+            for (d, value) in centroid.iter_mut().enumerate() {
+                *value = members.iter().map(|p| p[d]).sum::<f64>() / members.len() as f64;
+            }
+        }
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crate::models::CreateTradeInput;
+    use crate::test_utils::{create_test_trade, create_test_trade_input};
+
+    fn trade(id: usize, entry_time: &str, exit_time: &str, net_pnl: f64, symbol: &str) -> TradeWithDerived {
+        let input = CreateTradeInput {
+            trade_date: NaiveDate::from_ymd_opt(2024, 3, 1).unwrap() + chrono::Duration::days(id as i64),
+            entry_price: 50.0,
+            exit_price: Some(50.0 + net_pnl / 100.0),
+            stop_loss_price: None,
+            entry_time: Some(entry_time.to_string()),
+            exit_time: Some(exit_time.to_string()),
+            fees: Some(0.0),
+            ..create_test_trade_input("a1", symbol)
+        };
+        create_test_trade(&format!("t{}", id), input)
+    }
+
+    #[test]
+    fn test_calculate_trade_clusters() {
+        // Quick tagged morning scalps that mostly win against afternoon holds that mostly lose
+        let mut trades = Vec::new();
+        let mut tags = HashMap::new();
+        for i in 0..6 {
+            trades.push(trade(i, "09:35", "09:45", if i == 0 { -20.0 } else { 80.0 }, "AAPL"));
+            tags.insert(format!("t{}", i), vec!["opening drive".to_string()]);
+        }
+        for i in 6..12 {
+            trades.push(trade(i, "14:00", "15:50", if i == 6 { 50.0 } else { -60.0 }, if i % 2 == 0 { "TSLA" } else { "NVDA" }));
+        }
+
+        let report = calculate_trade_clusters(&trades, &tags, None, None);
+        assert_eq!(report.trade_count, 12);
+        assert_eq!(report.clusters.len(), 2);
+
+        let best = &report.clusters[0];
+        assert_eq!(best.performance.key, "1");
+        assert_eq!(best.performance.trade_count, 6);
+        assert!((best.performance.net_pnl - 380.0).abs() < 0.01);
+        assert_eq!(best.trade_ids, (0..6).map(|i| format!("t{}", i)).collect::<Vec<_>>());
+        assert_eq!(best.top_symbols, vec!["AAPL"]);
+        assert_eq!(best.tags, vec!["opening drive"]);
+        assert_eq!(
+            best.traits,
+            vec!["Earlier entries (avg 09:35)", "Shorter holds (avg 10 min)", "Tagged opening drive"]
+        );
+
+        let worst = &report.clusters[1];
+        assert_eq!(worst.performance.win_rate, Some(1.0 / 6.0));
+        assert_eq!(worst.top_symbols, vec!["NVDA", "TSLA"]);
+        assert_eq!(worst.avg_hold_minutes, Some(110.0));
+        assert_eq!(worst.traits[0], "Later entries (avg 14:00)");
+
+        // Asking for one cluster groups everything
+        let report = calculate_trade_clusters(&trades, &tags, Some(1), None);
+        assert_eq!(report.clusters.len(), 1);
+        assert!(report.clusters[0].traits.is_empty());
+        assert!(calculate_trade_clusters(&[], &tags, None, None).clusters.is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use chrono::{Months, NaiveDate};
+use crate::models::{AssetClass, CostBasisMethod, CryptoTaxReport, Direction, ExecutionFill, LotDisposal};
+
+/// Quantity below which a lot or a disposal counts as used up
+const QUANTITY_EPSILON: f64 = 1e-9;
+
+/// Crypto acquired by one entry fill and not yet disposed of
+struct Lot {
+    trade_id: String,
+    acquired_date: NaiveDate,
+    quantity: f64,
+    unit_cost: f64, // Price plus entry fees per unit, in the account currency
+}
+
+/// Match crypto disposals to earlier acquisitions of the same asset in the same account
+/// Fills must be ordered by time. Entries of long trades acquire lots and exits dispose of them,
+/// whichever trade a lot was acquired in; short trades are left out. Returns the matched parts
+/// of each disposal and the quantity sold with no lot left to match.
+pub fn match_crypto_lots(fills: &[ExecutionFill], method: CostBasisMethod) -> (Vec<LotDisposal>, f64) {
+    let mut lots: HashMap<(&str, &str), Vec<Lot>> = HashMap::new();
+    let mut disposals = Vec::new();
+    let mut unmatched_quantity = 0.0;
+
+    for fill in fills
+        .iter()
+        .filter(|f| f.asset_class == AssetClass::Crypto && f.direction == Direction::Long && f.quantity > 0.0)
+    {
+        let price = fill.price * fill.value_multiplier();
+        let fees_per_unit = fill.fees / fill.quantity;
+        let held = lots.entry((fill.account_id.as_str(), fill.symbol.as_str())).or_default();
+
+        if fill.execution_type == "entry" {
+            held.push(Lot {
+                trade_id: fill.trade_id.clone(),
+                acquired_date: fill.execution_date,
+                quantity: fill.quantity,
+                unit_cost: price + fees_per_unit,
+            });
+            continue;
+        }
+
+        let mut remaining = fill.quantity;
+        while remaining > QUANTITY_EPSILON {
+            let Some(i) = next_lot(held, method) else {
+                break;
+            };
+            let lot = &mut held[i];
+            let quantity = remaining.min(lot.quantity);
+            let cost_basis = quantity * lot.unit_cost;
+            let proceeds = quantity * (price - fees_per_unit);
+            disposals.push(LotDisposal {
+                trade_id: fill.trade_id.clone(),
+                lot_trade_id: lot.trade_id.clone(),
+                symbol: fill.symbol.clone(),
+                quantity,
+                acquired_date: lot.acquired_date,
+                disposed_date: fill.execution_date,
+                cost_basis,
+                proceeds,
+                gain: proceeds - cost_basis,
+                long_term: lot
+                    .acquired_date
+                    .checked_add_months(Months::new(12))
+                    .is_some_and(|year_later| fill.execution_date > year_later),
+            });
+
+            lot.quantity -= quantity;
+            remaining -= quantity;
+            if lot.quantity <= QUANTITY_EPSILON {
+                held.remove(i);
+            }
+        }
+        if remaining > QUANTITY_EPSILON {
+            unmatched_quantity += remaining;
+        }
+    }
+
+    (disposals, unmatched_quantity)
+}
+
+/// Index of the lot a disposal uses next; lots are held oldest first
+fn next_lot(lots: &[Lot], method: CostBasisMethod) -> Option<usize> {
+    match method {
+        CostBasisMethod::Fifo => (!lots.is_empty()).then_some(0),
+        CostBasisMethod::Lifo => lots.len().checked_sub(1),
+        // The oldest of equally expensive lots
+        CostBasisMethod::Hifo => lots
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by(|(_, a), (_, b)| a.unit_cost.total_cmp(&b.unit_cost))
+            .map(|(i, _)| i),
+    }
+}
+
+/// Realized crypto gains of disposals from `start_date` on, split into short and long term
+/// Fills must run from the first acquisition so that earlier lots are available to match.
+pub fn calculate_crypto_tax_report(
+    fills: &[ExecutionFill],
+    method: CostBasisMethod,
+    start_date: Option<NaiveDate>,
+) -> CryptoTaxReport {
+    let (disposals, unmatched_quantity) = match_crypto_lots(fills, method);
+    let disposals: Vec<LotDisposal> = disposals
+        .into_iter()
+        .filter(|d| start_date.is_none_or(|start| d.disposed_date >= start))
+        .collect();
+
+    let proceeds: f64 = disposals.iter().map(|d| d.proceeds).sum();
+    let cost_basis: f64 = disposals.iter().map(|d| d.cost_basis).sum();
+    let long_term_gain: f64 = disposals.iter().filter(|d| d.long_term).map(|d| d.gain).sum();
+    let short_term_gain: f64 = disposals.iter().filter(|d| !d.long_term).map(|d| d.gain).sum();
+
+    CryptoTaxReport {
+        method,
+        proceeds,
+        cost_basis,
+        short_term_gain,
+        long_term_gain,
+        total_gain: short_term_gain + long_term_gain,
+        unmatched_quantity,
+        disposals,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(trade_id: &str, execution_type: &str, day: u32, quantity: f64, price: f64) -> ExecutionFill {
+        ExecutionFill {
+            trade_id: trade_id.to_string(),
+            account_id: "acc1".to_string(),
+            symbol: "BTC".to_string(),
+            asset_class: AssetClass::Crypto,
+            direction: Direction::Long,
+            trade_entry_price: price,
+            contract_multiplier: None,
+            fx_rate: None,
+            tick_size: None,
+            execution_type: execution_type.to_string(),
+            execution_date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            execution_time: None,
+            quantity,
+            price,
+            fees: 0.0,
+            exchange: None,
+            intended_price: None,
+            order_type: None,
+        }
+    }
+
+    #[test]
+    fn test_methods_pick_different_lots() {
+        // Bought 1 at 100, 1 at 300 and 1 at 200, then sold 1 at 250
+        let fills = vec![
+            fill("t1", "entry", 2, 1.0, 100.0),
+            fill("t1", "entry", 3, 1.0, 300.0),
+            fill("t1", "entry", 4, 1.0, 200.0),
+            fill("t1", "exit", 5, 1.0, 250.0),
+        ];
+
+        let gain = |method| calculate_crypto_tax_report(&fills, method, None).total_gain;
+        assert!((gain(CostBasisMethod::Fifo) - 150.0).abs() < 1e-9);
+        assert!((gain(CostBasisMethod::Lifo) - 50.0).abs() < 1e-9);
+        assert!((gain(CostBasisMethod::Hifo) + 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_disposals_span_lots_and_count_unmatched_quantity() {
+        let fills = vec![
+            fill("t1", "entry", 2, 0.5, 100.0),
+            fill("t2", "entry", 3, 0.5, 120.0),
+            fill("t2", "exit", 4, 1.5, 130.0),
+        ];
+
+        let report = calculate_crypto_tax_report(&fills, CostBasisMethod::Fifo, None);
+        let lots: Vec<(&str, f64)> = report.disposals.iter().map(|d| (d.lot_trade_id.as_str(), d.quantity)).collect();
+        assert_eq!(lots, vec![("t1", 0.5), ("t2", 0.5)]);
+        assert!((report.total_gain - 20.0).abs() < 1e-9);
+        assert!((report.unmatched_quantity - 0.5).abs() < 1e-9);
+    }
+}
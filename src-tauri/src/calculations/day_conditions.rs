@@ -0,0 +1,37 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::NaiveDate;
+use crate::calculations::PerformanceAccumulator;
+use crate::models::{DayConditionType, DayConditions, PerformanceBucket, TradeWithDerived};
+
+/// Key of the bucket for trades on days without a condition
+pub const UNTAGGED_DAY_KEY: &str = "untagged";
+
+/// Closed-trade performance per day condition, with untagged days last
+/// A trade on a day with several conditions counts once in each of them.
+pub fn calculate_day_condition_report(trades: &[TradeWithDerived], days: &[DayConditions]) -> Vec<PerformanceBucket> {
+    let by_date: HashMap<NaiveDate, &[DayConditionType]> = days
+        .iter()
+        .map(|d| (d.date, d.conditions.as_slice()))
+        .collect();
+
+    let mut by_condition: BTreeMap<DayConditionType, PerformanceAccumulator> = BTreeMap::new();
+    let mut untagged = PerformanceAccumulator::default();
+
+    for trade in trades.iter().filter(|t| t.net_pnl.is_some()) {
+        match by_date.get(&trade.trade.trade_date) {
+            Some(conditions) if !conditions.is_empty() => {
+                for condition in conditions.iter() {
+                    by_condition.entry(*condition).or_default().add(trade);
+                }
+            }
+            _ => untagged.add(trade),
+        }
+    }
+
+    let mut buckets: Vec<PerformanceBucket> = by_condition
+        .into_iter()
+        .map(|(condition, acc)| acc.into_bucket(condition.as_str().to_string()))
+        .collect();
+    buckets.push(untagged.into_bucket(UNTAGGED_DAY_KEY.to_string()));
+    buckets
+}
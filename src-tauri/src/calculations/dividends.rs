@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+use crate::models::{Dividend, DividendBucket, DividendIncomeReport};
+
+#[derive(Default)]
+struct DividendAccumulator {
+    dividend_count: i32,
+    received: f64,
+    paid: f64,
+}
+
+impl DividendAccumulator {
+    fn add(&mut self, amount: f64) {
+        self.dividend_count += 1;
+        if amount >= 0.0 {
+            self.received += amount;
+        } else {
+            self.paid += -amount;
+        }
+    }
+
+    fn into_bucket(self, key: String) -> DividendBucket {
+        DividendBucket {
+            key,
+            dividend_count: self.dividend_count,
+            received: self.received,
+            paid: self.paid,
+            net: self.received - self.paid,
+        }
+    }
+}
+
+/// Aggregate dividends by symbol and by month of payment
+/// Dividends without a pay date are counted in the month of their ex-date.
+pub fn calculate_dividend_income(dividends: &[Dividend]) -> DividendIncomeReport {
+    let mut total = DividendAccumulator::default();
+    let mut by_symbol: BTreeMap<String, DividendAccumulator> = BTreeMap::new();
+    let mut by_month: BTreeMap<String, DividendAccumulator> = BTreeMap::new();
+
+    for dividend in dividends {
+        total.add(dividend.amount);
+        by_symbol.entry(dividend.symbol.clone()).or_default().add(dividend.amount);
+
+        let month = dividend.pay_date.unwrap_or(dividend.ex_date).format("%Y-%m").to_string();
+        by_month.entry(month).or_default().add(dividend.amount);
+    }
+
+    let mut by_symbol: Vec<DividendBucket> = by_symbol
+        .into_iter()
+        .map(|(key, acc)| acc.into_bucket(key))
+        .collect();
+    by_symbol.sort_by(|a, b| b.net.total_cmp(&a.net));
+
+    DividendIncomeReport {
+        total_received: total.received,
+        total_paid: total.paid,
+        net_income: total.received - total.paid,
+        by_symbol,
+        by_month: by_month
+            .into_iter()
+            .map(|(key, acc)| acc.into_bucket(key))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+
+    fn dividend(symbol: &str, ex_date: NaiveDate, pay_date: Option<NaiveDate>, amount: f64) -> Dividend {
+        Dividend {
+            id: uuid::Uuid::new_v4().to_string(),
+            user_id: "user1".to_string(),
+            account_id: "account1".to_string(),
+            instrument_id: "inst1".to_string(),
+            symbol: symbol.to_string(),
+            trade_id: None,
+            ex_date,
+            pay_date,
+            amount,
+            notes: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_dividend_income_received_and_paid() {
+        let jan = NaiveDate::from_ymd_opt(2024, 1, 30).unwrap();
+        let feb = NaiveDate::from_ymd_opt(2024, 2, 15).unwrap();
+        let dividends = vec![
+            dividend("KO", jan, Some(feb), 46.0),
+            dividend("KO", feb, None, 46.0),
+            dividend("T", jan, None, -27.75), // Paid on a short
+        ];
+
+        let report = calculate_dividend_income(&dividends);
+
+        assert!((report.total_received - 92.0).abs() < 0.001);
+        assert!((report.total_paid - 27.75).abs() < 0.001);
+        assert!((report.net_income - 64.25).abs() < 0.001);
+
+        assert_eq!(report.by_symbol[0].key, "KO");
+        assert_eq!(report.by_symbol[0].dividend_count, 2);
+        assert_eq!(report.by_symbol[1].key, "T");
+        assert!((report.by_symbol[1].net - (-27.75)).abs() < 0.001);
+
+        // KO's January ex-date pays in February
+        assert_eq!(report.by_month.len(), 2);
+        assert_eq!(report.by_month[0].key, "2024-01");
+        assert!((report.by_month[0].paid - 27.75).abs() < 0.001);
+        assert_eq!(report.by_month[1].key, "2024-02");
+        assert!((report.by_month[1].received - 92.0).abs() < 0.001);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::models::Direction;
+
+/// Best and worst prices reached while a trade was open, from (high, low) bars
+/// Returns (max_favorable_price, max_adverse_price), bounded by the entry price so a
+/// trade that never moved in one direction has an excursion of zero there.
+pub fn calculate_excursion_prices(
+    direction: Direction,
+    entry_price: f64,
+    bars: &[(f64, f64)],
+) -> Option<(f64, f64)> {
+    if bars.is_empty() {
+        return None;
+    }
+
+    let highest = bars.iter().map(|(high, _)| *high).fold(entry_price, f64::max);
+    let lowest = bars.iter().map(|(_, low)| *low).fold(entry_price, f64::min);
+
+    Some(match direction {
+        Direction::Long => (highest, lowest),
+        Direction::Short => (lowest, highest),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_excursion_prices_by_direction() {
+        let bars = [(101.0, 99.5), (103.0, 100.5), (102.0, 98.0)];
+        assert_eq!(calculate_excursion_prices(Direction::Long, 100.0, &bars), Some((103.0, 98.0)));
+        assert_eq!(calculate_excursion_prices(Direction::Short, 100.0, &bars), Some((98.0, 103.0)));
+
+        // Never traded below entry: no adverse excursion
+        assert_eq!(calculate_excursion_prices(Direction::Long, 99.0, &bars[..2]), Some((103.0, 99.0)));
+        assert_eq!(calculate_excursion_prices(Direction::Long, 100.0, &[]), None);
+    }
+}
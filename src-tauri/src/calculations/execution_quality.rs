@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use crate::models::{Direction, ExchangeBucket, ExecutionFill, ExecutionQualityReport, SlippageBucket};
+
+/// Calculate slippage per share/contract versus the intended price
+/// Buys (long entry, short exit): price - intended_price
+/// Sells (long exit, short entry): intended_price - price
+/// Positive values mean the fill was worse than intended
+pub fn calculate_slippage_per_unit(
+    direction: Direction,
+    execution_type: &str,
+    price: f64,
+    intended_price: f64,
+) -> f64 {
+    let is_buy = matches!(
+        (direction, execution_type),
+        (Direction::Long, "entry") | (Direction::Short, "exit")
+    );
+
+    if is_buy {
+        price - intended_price
+    } else {
+        intended_price - price
+    }
+}
+
+#[derive(Default)]
+struct SlippageAccumulator {
+    execution_count: i32,
+    total_slippage: f64,
+    total_per_unit: f64,
+    total_ticks: f64,
+}
+
+impl SlippageAccumulator {
+    fn add(&mut self, per_unit: f64, ticks: f64, dollars: f64) {
+        self.execution_count += 1;
+        self.total_per_unit += per_unit;
+        self.total_ticks += ticks;
+        self.total_slippage += dollars;
+    }
+
+    fn into_bucket(self, key: String) -> SlippageBucket {
+        let avg_per_unit = self.total_per_unit / self.execution_count as f64;
+        SlippageBucket {
+            key,
+            execution_count: self.execution_count,
+            total_slippage: self.total_slippage,
+            avg_slippage_cents: avg_per_unit * 100.0,
+            avg_slippage_ticks: self.total_ticks / self.execution_count as f64,
+        }
+    }
+}
+
+/// Build buckets sorted with the most expensive group first
+fn into_buckets(map: HashMap<String, SlippageAccumulator>) -> Vec<SlippageBucket> {
+    let mut buckets: Vec<SlippageBucket> = map
+        .into_iter()
+        .map(|(key, acc)| acc.into_bucket(key))
+        .collect();
+    buckets.sort_by(|a, b| b.total_slippage.total_cmp(&a.total_slippage));
+    buckets
+}
+
+/// Aggregate slippage by symbol, hour of day and order type
+/// Fills without an intended price are ignored.
+pub fn calculate_execution_quality(fills: &[ExecutionFill]) -> ExecutionQualityReport {
+    let mut by_symbol: HashMap<String, SlippageAccumulator> = HashMap::new();
+    let mut by_hour: HashMap<String, SlippageAccumulator> = HashMap::new();
+    let mut by_order_type: HashMap<String, SlippageAccumulator> = HashMap::new();
+    let mut execution_count = 0;
+    let mut total_slippage = 0.0;
+
+    for fill in fills {
+        let Some(intended_price) = fill.intended_price else {
+            continue;
+        };
+
+        let per_unit = calculate_slippage_per_unit(
+            fill.direction,
+            &fill.execution_type,
+            fill.price,
+            intended_price,
+        );
+        let ticks = per_unit / fill.tick_size();
+        let dollars = per_unit * fill.quantity * fill.value_multiplier();
+
+        execution_count += 1;
+        total_slippage += dollars;
+
+        by_symbol.entry(fill.symbol.clone()).or_default().add(per_unit, ticks, dollars);
+
+        let hour = fill
+            .execution_time
+            .as_deref()
+            .and_then(|t| t.get(0..2))
+            .unwrap_or("unknown")
+            .to_string();
+        by_hour.entry(hour).or_default().add(per_unit, ticks, dollars);
+
+        let order_type = fill
+            .order_type
+            .map(|o| o.as_str())
+            .unwrap_or("unknown")
+            .to_string();
+        by_order_type.entry(order_type).or_default().add(per_unit, ticks, dollars);
+    }
+
+    let mut by_hour = into_buckets(by_hour);
+    by_hour.sort_by(|a, b| a.key.cmp(&b.key));
+
+    ExecutionQualityReport {
+        execution_count,
+        total_slippage,
+        by_symbol: into_buckets(by_symbol),
+        by_hour,
+        by_order_type: into_buckets(by_order_type),
+    }
+}
+
+#[derive(Default)]
+struct ExchangeAccumulator {
+    fill_count: i32,
+    total_fees: f64,
+    slippage_fill_count: i32,
+    total_slippage_per_unit: f64,
+}
+
+/// Aggregate fill counts, fees and average slippage per exchange/route
+/// Sorted by fill count, busiest exchange first
+pub fn calculate_exchange_report(fills: &[ExecutionFill]) -> Vec<ExchangeBucket> {
+    let mut by_exchange: HashMap<String, ExchangeAccumulator> = HashMap::new();
+
+    for fill in fills {
+        let exchange = fill
+            .exchange
+            .as_deref()
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+            .unwrap_or("unknown")
+            .to_string();
+        let acc = by_exchange.entry(exchange).or_default();
+        acc.fill_count += 1;
+        acc.total_fees += fill.fees;
+
+        if let Some(intended_price) = fill.intended_price {
+            acc.slippage_fill_count += 1;
+            acc.total_slippage_per_unit += calculate_slippage_per_unit(
+                fill.direction,
+                &fill.execution_type,
+                fill.price,
+                intended_price,
+            );
+        }
+    }
+
+    let mut buckets: Vec<ExchangeBucket> = by_exchange
+        .into_iter()
+        .map(|(exchange, acc)| ExchangeBucket {
+            exchange,
+            fill_count: acc.fill_count,
+            total_fees: acc.total_fees,
+            avg_fees_per_fill: acc.total_fees / acc.fill_count as f64,
+            slippage_fill_count: acc.slippage_fill_count,
+            avg_slippage_cents: (acc.slippage_fill_count > 0)
+                .then(|| acc.total_slippage_per_unit / acc.slippage_fill_count as f64 * 100.0),
+        })
+        .collect();
+    buckets.sort_by(|a, b| {
+        b.fill_count
+            .cmp(&a.fill_count)
+            .then_with(|| a.exchange.cmp(&b.exchange))
+    });
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crate::models::{AssetClass, OrderType};
+
+    fn fill(
+        symbol: &str,
+        direction: Direction,
+        execution_type: &str,
+        time: &str,
+        price: f64,
+        intended_price: Option<f64>,
+        order_type: Option<OrderType>,
+    ) -> ExecutionFill {
+        ExecutionFill {
+            trade_id: "trade1".to_string(),
+            account_id: "acc1".to_string(),
+            symbol: symbol.to_string(),
+            asset_class: AssetClass::Stock,
+            direction,
+            trade_entry_price: 100.0,
+            contract_multiplier: None,
+            fx_rate: None,
+            tick_size: None,
+            execution_type: execution_type.to_string(),
+            execution_date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            execution_time: Some(time.to_string()),
+            quantity: 100.0,
+            price,
+            fees: 1.0,
+            exchange: Some("ISLAND".to_string()),
+            intended_price,
+            order_type,
+        }
+    }
+
+    #[test]
+    fn test_slippage_long_entry_paid_up() {
+        let slippage = calculate_slippage_per_unit(Direction::Long, "entry", 10.05, 10.00);
+        assert!((slippage - 0.05).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_slippage_short_entry_sold_lower() {
+        let slippage = calculate_slippage_per_unit(Direction::Short, "entry", 9.97, 10.00);
+        assert!((slippage - 0.03).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_slippage_price_improvement_is_negative() {
+        let slippage = calculate_slippage_per_unit(Direction::Long, "exit", 10.02, 10.00);
+        assert!((slippage - (-0.02)).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_execution_quality_groups_and_skips_missing_intended() {
+        let fills = vec![
+            fill("AAPL", Direction::Long, "entry", "09:31:00", 150.05, Some(150.00), Some(OrderType::Market)),
+            fill("AAPL", Direction::Long, "exit", "10:15:00", 151.00, Some(151.00), Some(OrderType::Limit)),
+            fill("TSLA", Direction::Long, "entry", "09:45:00", 200.10, Some(200.00), Some(OrderType::Market)),
+            fill("MSFT", Direction::Long, "entry", "11:00:00", 400.00, None, None),
+        ];
+
+        let report = calculate_execution_quality(&fills);
+
+        assert_eq!(report.execution_count, 3);
+        // 0.05 * 100 + 0 + 0.10 * 100 = 15
+        assert!((report.total_slippage - 15.0).abs() < 0.01);
+
+        // Worst symbol first
+        assert_eq!(report.by_symbol[0].key, "TSLA");
+        assert!((report.by_symbol[0].avg_slippage_cents - 10.0).abs() < 0.01);
+        assert!((report.by_symbol[0].avg_slippage_ticks - 10.0).abs() < 0.01);
+
+        assert_eq!(report.by_hour.len(), 2);
+        assert_eq!(report.by_hour[0].key, "09");
+        assert_eq!(report.by_hour[0].execution_count, 2);
+
+        assert_eq!(report.by_order_type[0].key, "market");
+        assert!((report.by_order_type[0].total_slippage - 15.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_slippage_ticks_use_the_instruments_tick_size() {
+        // Two pipettes on a forex pair
+        let mut eurusd = fill("EUR.USD", Direction::Long, "entry", "09:31:00", 1.08502, Some(1.08500), None);
+        eurusd.asset_class = AssetClass::Forex;
+        // Two ticks of an index future with a quarter-point tick
+        let mut es = fill("ES", Direction::Short, "entry", "09:45:00", 5000.00, Some(5000.50), None);
+        es.asset_class = AssetClass::Future;
+        es.tick_size = Some(0.25);
+
+        let report = calculate_execution_quality(&[eurusd, es]);
+
+        let ticks = |symbol: &str| report.by_symbol.iter().find(|b| b.key == symbol).unwrap().avg_slippage_ticks;
+        assert!((ticks("EUR.USD") - 2.0).abs() < 0.001);
+        assert!((ticks("ES") - 2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_exchange_report_counts_fees_and_slippage() {
+        let mut dark = fill("AAPL", Direction::Long, "entry", "09:31:00", 150.02, Some(150.00), None);
+        dark.exchange = Some("DARK".to_string());
+        dark.fees = 0.5;
+        let mut no_route = fill("AAPL", Direction::Long, "exit", "10:00:00", 151.00, None, None);
+        no_route.exchange = None;
+
+        let fills = vec![
+            fill("TSLA", Direction::Long, "entry", "09:45:00", 200.10, Some(200.00), None),
+            fill("TSLA", Direction::Long, "exit", "09:50:00", 201.00, None, None),
+            dark,
+            no_route,
+        ];
+
+        let report = calculate_exchange_report(&fills);
+
+        assert_eq!(report.len(), 3);
+        assert_eq!(report[0].exchange, "ISLAND");
+        assert_eq!(report[0].fill_count, 2);
+        assert!((report[0].total_fees - 2.0).abs() < 0.001);
+        assert_eq!(report[0].slippage_fill_count, 1);
+        assert!((report[0].avg_slippage_cents.unwrap() - 10.0).abs() < 0.01);
+
+        assert_eq!(report[1].exchange, "DARK");
+        assert!((report[1].avg_fees_per_fill - 0.5).abs() < 0.001);
+        assert!((report[1].avg_slippage_cents.unwrap() - 2.0).abs() < 0.01);
+
+        assert_eq!(report[2].exchange, "unknown");
+        assert!(report[2].avg_slippage_cents.is_none());
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::BTreeMap;
+use chrono::{Datelike, NaiveDate};
+use crate::models::{AccountPerformance, AccountPerformanceMonth, FinancingCharge, Status, TradeWithDerived};
+
+/// Account-level performance per month: closed trade PnL (by trade date) minus financing charges
+/// Financing charges never touch per-trade stats; they only reduce the account's net PnL and return.
+pub fn calculate_account_performance(
+    account_id: &str,
+    trades: &[TradeWithDerived],
+    charges: &[FinancingCharge],
+    starting_balance: Option<f64>,
+) -> AccountPerformance {
+    // (trade net PnL, financing costs) per month
+    let mut months: BTreeMap<NaiveDate, (f64, f64)> = BTreeMap::new();
+
+    for trade in trades {
+        if trade.trade.account_id != account_id || trade.trade.status != Status::Closed {
+            continue;
+        }
+        let date = trade.trade.trade_date;
+        let month = date.with_day(1).unwrap_or(date);
+        months.entry(month).or_default().0 += trade.net_pnl.unwrap_or(0.0);
+    }
+    for charge in charges.iter().filter(|c| c.account_id == account_id) {
+        months.entry(charge.charge_month).or_default().1 += charge.amount;
+    }
+
+    let months: Vec<AccountPerformanceMonth> = months
+        .into_iter()
+        .map(|(month, (trade_net_pnl, financing_costs))| AccountPerformanceMonth {
+            month,
+            trade_net_pnl,
+            financing_costs,
+            net_pnl: trade_net_pnl - financing_costs,
+        })
+        .collect();
+
+    let trade_net_pnl: f64 = months.iter().map(|m| m.trade_net_pnl).sum();
+    let financing_costs: f64 = months.iter().map(|m| m.financing_costs).sum();
+    let net_pnl = trade_net_pnl - financing_costs;
+
+    AccountPerformance {
+        account_id: account_id.to_string(),
+        trade_net_pnl,
+        financing_costs,
+        net_pnl,
+        starting_balance,
+        return_pct: starting_balance
+            .filter(|b| *b > 0.0)
+            .map(|b| net_pnl / b * 100.0),
+        months,
+    }
+}
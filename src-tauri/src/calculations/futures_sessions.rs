@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::America::Chicago;
+use crate::calculations::PerformanceAccumulator;
+use crate::models::{AssetClass, FuturesSessionReport, TradeWithDerived};
+use crate::parsers::product_code;
+
+/// Regular trading hours in exchange (Chicago) time by product code; the rest of the
+/// Globex day is overnight
+const REGULAR_SESSIONS: [(&str, &str, &str); 24] = [
+    ("ES", "08:30", "15:00"),
+    ("MES", "08:30", "15:00"),
+    ("NQ", "08:30", "15:00"),
+    ("MNQ", "08:30", "15:00"),
+    ("YM", "08:30", "15:00"),
+    ("MYM", "08:30", "15:00"),
+    ("RTY", "08:30", "15:00"),
+    ("M2K", "08:30", "15:00"),
+    ("CL", "08:00", "13:30"),
+    ("MCL", "08:00", "13:30"),
+    ("NG", "08:00", "13:30"),
+    ("GC", "07:20", "12:30"),
+    ("MGC", "07:20", "12:30"),
+    ("SI", "07:25", "12:25"),
+    ("HG", "07:10", "12:00"),
+    ("ZB", "07:20", "14:00"),
+    ("ZN", "07:20", "14:00"),
+    ("ZF", "07:20", "14:00"),
+    ("ZT", "07:20", "14:00"),
+    ("6E", "07:20", "14:00"),
+    ("6J", "07:20", "14:00"),
+    ("ZC", "08:30", "13:20"),
+    ("ZS", "08:30", "13:20"),
+    ("ZW", "08:30", "13:20"),
+];
+
+/// CME month codes, January to December
+const MONTH_CODES: [char; 12] = ['F', 'G', 'H', 'J', 'K', 'M', 'N', 'Q', 'U', 'V', 'X', 'Z'];
+
+/// Break closed futures trades down by contract month and by the session they were entered in.
+/// Entry times are stored in UTC and compared with the product's regular hours in Chicago time.
+pub fn calculate_futures_session_report(trades: &[TradeWithDerived]) -> FuturesSessionReport {
+    let mut trade_count = 0;
+    let mut by_month: BTreeMap<String, PerformanceAccumulator> = BTreeMap::new();
+    let mut by_session: BTreeMap<String, PerformanceAccumulator> = BTreeMap::new();
+
+    for trade in trades
+        .iter()
+        .filter(|t| t.trade.asset_class == AssetClass::Future && t.net_pnl.is_some())
+    {
+        trade_count += 1;
+
+        // A symbol without a month code is taken to be the product itself
+        let symbol = trade.trade.symbol.as_str();
+        let (month, product) = match contract_month(symbol, trade.trade.trade_date) {
+            Some((year, month)) => (format!("{}-{:02}", year, month), product_code(symbol)),
+            None => ("unknown".to_string(), symbol),
+        };
+        by_month.entry(month).or_default().add(trade);
+
+        let session = entry_session(product, trade.trade.trade_date, trade.trade.entry_time.as_deref());
+        by_session.entry(session.to_string()).or_default().add(trade);
+    }
+
+    FuturesSessionReport {
+        trade_count,
+        by_contract_month: by_month.into_iter().map(|(key, acc)| acc.into_bucket(key)).collect(),
+        by_session: by_session.into_iter().map(|(key, acc)| acc.into_bucket(key)).collect(),
+    }
+}
+
+/// Delivery (year, month) of a contract symbol like ESH4, ESH24 or ESH2024
+/// A one-digit year is the first matching year from the trade date on.
+fn contract_month(symbol: &str, trade_date: NaiveDate) -> Option<(i32, u32)> {
+    let without_year = symbol.trim_end_matches(|c: char| c.is_ascii_digit());
+    let digits = &symbol[without_year.len()..];
+    let code = without_year.chars().last()?;
+    let month = MONTH_CODES.iter().position(|&c| c == code)? as u32 + 1;
+    let value: i32 = digits.parse().ok()?;
+
+    let year = match digits.len() {
+        1 => {
+            let mut year = trade_date.year() - trade_date.year().rem_euclid(10) + value;
+            if year < trade_date.year() {
+                year += 10;
+            }
+            year
+        }
+        2 => trade_date.year() - trade_date.year().rem_euclid(100) + value,
+        4 => value,
+        _ => return None,
+    };
+    Some((year, month))
+}
+
+/// "rth" or "overnight" for an entry within or outside the product's regular hours, or
+/// "unknown" without an entry time or known hours
+fn entry_session(product: &str, trade_date: NaiveDate, entry_time: Option<&str>) -> &'static str {
+    let hours = REGULAR_SESSIONS
+        .iter()
+        .find(|(code, _, _)| code.eq_ignore_ascii_case(product))
+        .and_then(|(_, open, close)| Some((parse_time(open)?, parse_time(close)?)));
+    let time = entry_time.and_then(parse_time);
+
+    let (Some((open, close)), Some(time)) = (hours, time) else {
+        return "unknown";
+    };
+    let local = Utc
+        .from_utc_datetime(&NaiveDateTime::new(trade_date, time))
+        .with_timezone(&Chicago)
+        .time();
+
+    if local >= open && local < close {
+        "rth"
+    } else {
+        "overnight"
+    }
+}
+
+/// Time as HH:MM:SS or HH:MM
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contract_month_resolves_the_year() {
+        let date = NaiveDate::from_ymd_opt(2029, 11, 20).unwrap();
+        assert_eq!(contract_month("ESZ9", date), Some((2029, 12)));
+        assert_eq!(contract_month("ESH0", date), Some((2030, 3)));
+        assert_eq!(contract_month("6EM30", date), Some((2030, 6)));
+        assert_eq!(contract_month("ESU2031", date), Some((2031, 9)));
+        assert_eq!(contract_month("ES", date), None);
+        assert_eq!(contract_month("ESA4", date), None);
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::NaiveDate;
+use crate::models::{DailyPerformance, FxRate, TradeWithDerived};
+
+/// Stored FX rates by currency pair, usable in both directions
+pub struct FxTable {
+    rates: HashMap<(String, String), BTreeMap<NaiveDate, f64>>,
+}
+
+impl FxTable {
+    pub fn new(rates: &[FxRate]) -> Self {
+        let mut table: HashMap<(String, String), BTreeMap<NaiveDate, f64>> = HashMap::new();
+        for rate in rates.iter().filter(|r| r.rate > 0.0) {
+            table
+                .entry((rate.base_currency.to_uppercase(), rate.quote_currency.to_uppercase()))
+                .or_default()
+                .insert(rate.date, rate.rate);
+        }
+        Self { rates: table }
+    }
+
+    /// Units of `to` per unit of `from` on a day
+    /// Uses the latest rate on or before the day, else the earliest one after it; the inverse pair
+    /// is used when only that one is stored.
+    pub fn rate(&self, from: &str, to: &str, date: NaiveDate) -> Option<f64> {
+        if from.eq_ignore_ascii_case(to) {
+            return Some(1.0);
+        }
+
+        let lookup = |base: &str, quote: &str| {
+            let rates = self.rates.get(&(base.to_uppercase(), quote.to_uppercase()))?;
+            rates
+                .range(..=date)
+                .next_back()
+                .or_else(|| rates.range(date..).next())
+                .map(|(_, rate)| *rate)
+        };
+
+        lookup(from, to).or_else(|| lookup(to, from).map(|rate| 1.0 / rate))
+    }
+}
+
+/// Converts amounts of a user's accounts to the reporting currency
+pub struct ReportingConverter {
+    pub currency: String,
+    account_currencies: HashMap<String, String>,
+    table: FxTable,
+}
+
+impl ReportingConverter {
+    pub fn new(currency: &str, account_currencies: HashMap<String, String>, table: FxTable) -> Self {
+        Self {
+            currency: currency.to_uppercase(),
+            account_currencies,
+            table,
+        }
+    }
+
+    /// Base currency of an account, None when the account is unknown
+    pub fn account_currency(&self, account_id: &str) -> Option<&str> {
+        self.account_currencies.get(account_id).map(String::as_str)
+    }
+
+    /// Rate from an account's base currency to the reporting currency on a day
+    pub fn account_rate(&self, account_id: &str, date: NaiveDate) -> Result<f64, String> {
+        self.currency_rate(self.account_currency(account_id), date)
+    }
+
+    /// Rate from a currency to the reporting currency on a day; None converts at 1
+    pub fn currency_rate(&self, from: Option<&str>, date: NaiveDate) -> Result<f64, String> {
+        let Some(from) = from else {
+            return Ok(1.0);
+        };
+        self.table
+            .rate(from, &self.currency, date)
+            .ok_or_else(|| format!("No FX rate from {} to {}", from.to_uppercase(), self.currency))
+    }
+
+    /// Convert trades' amounts at the rate of their trade date
+    pub fn convert_trades(&self, trades: &mut [TradeWithDerived]) -> Result<(), String> {
+        for trade in trades.iter_mut() {
+            let rate = self.account_rate(&trade.trade.account_id, trade.trade.trade_date)?;
+            if rate != 1.0 {
+                convert_trade_amounts(trade, rate);
+            }
+        }
+        Ok(())
+    }
+
+    /// Convert accounts' daily performance at the rate of each day
+    pub fn convert_account_days(&self, days: &mut [(String, DailyPerformance)]) -> Result<(), String> {
+        for (account_id, day) in days.iter_mut() {
+            let rate = self.account_rate(account_id, day.date)?;
+            if rate != 1.0 {
+                convert_day_amounts(day, rate);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Scale a day's money amounts by an FX rate
+pub fn convert_day_amounts(day: &mut DailyPerformance, rate: f64) {
+    day.realized_net_pnl *= rate;
+    day.gross_pnl *= rate;
+    day.total_fees *= rate;
+    day.avg_trade_pnl *= rate;
+    day.largest_win = day.largest_win.map(|w| w * rate);
+    day.largest_loss = day.largest_loss.map(|l| l * rate);
+}
+
+/// Scale a trade's prices and money amounts by an FX rate
+/// Quantities, R and ATR multiples are unit-free and stay as they are.
+pub fn convert_trade_amounts(trade: &mut TradeWithDerived, rate: f64) {
+    let t = &mut trade.trade;
+    t.entry_price *= rate;
+    for price in [
+        &mut t.exit_price,
+        &mut t.stop_loss_price,
+        &mut t.target_price,
+        &mut t.max_favorable_price,
+        &mut t.max_adverse_price,
+        &mut t.entry_atr,
+    ] {
+        *price = price.map(|p| p * rate);
+    }
+    t.fees *= rate;
+    t.carrying_costs *= rate;
+    t.dividends *= rate;
+
+    for amount in [
+        &mut trade.gross_pnl,
+        &mut trade.net_pnl,
+        &mut trade.pnl_per_share,
+        &mut trade.risk_per_share,
+    ] {
+        *amount = amount.map(|a| a * rate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FxRateSource;
+
+    fn rate(base: &str, quote: &str, day: u32, rate: f64) -> FxRate {
+        FxRate {
+            base_currency: base.to_string(),
+            quote_currency: quote.to_string(),
+            date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            rate,
+            source: FxRateSource::Manual,
+        }
+    }
+
+    #[test]
+    fn test_fx_table_uses_latest_rate_and_inverse_pairs() {
+        let table = FxTable::new(&[rate("EUR", "USD", 10, 1.10), rate("EUR", "USD", 20, 1.20)]);
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+
+        assert_eq!(table.rate("EUR", "USD", day(15)), Some(1.10));
+        assert_eq!(table.rate("EUR", "USD", day(25)), Some(1.20));
+        // Before the first stored rate
+        assert_eq!(table.rate("EUR", "USD", day(1)), Some(1.10));
+        assert!((table.rate("usd", "eur", day(20)).unwrap() - 1.0 / 1.20).abs() < 1e-12);
+        assert_eq!(table.rate("USD", "USD", day(1)), Some(1.0));
+        assert_eq!(table.rate("GBP", "USD", day(1)), None);
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use chrono::NaiveDate;
+use crate::calculations::{calculate_risk_amount, PerformanceAccumulator};
+use crate::models::{OutcomeScore, ProcessGoals, ProcessScore, ProcessStreak, ProcessStreaks, TradeWithDerived};
+
+fn group_by_day(trades: &[TradeWithDerived]) -> BTreeMap<NaiveDate, Vec<&TradeWithDerived>> {
+    let mut days: BTreeMap<NaiveDate, Vec<&TradeWithDerived>> = BTreeMap::new();
+    for trade in trades {
+        days.entry(trade.trade.trade_date).or_default().push(trade);
+    }
+    days
+}
+
+/// A day is journaled when every one of its trades has notes
+fn is_journaled(day: &[&TradeWithDerived]) -> bool {
+    day.iter().all(|t| t.trade.notes.as_deref().is_some_and(|n| !n.trim().is_empty()))
+}
+
+/// Score a week of closed trades against the process goals
+/// A goal is met when the week has no breach of it; weeks without trades meet every goal.
+pub fn calculate_process_score(trades: &[TradeWithDerived], goals: &ProcessGoals) -> ProcessScore {
+    let days = group_by_day(trades);
+
+    let days_over_trade_limit = goals
+        .max_trades_per_day
+        .map(|max| days.values().filter(|d| d.len() > max as usize).count() as i32)
+        .unwrap_or(0);
+
+    let days_journaled = days.values().filter(|d| is_journaled(d)).count() as i32;
+
+    let mut trades_over_risk = 0;
+    let mut trades_without_stop = 0;
+    if let Some(max_risk) = goals.max_risk_per_trade {
+        for trade in trades {
+            match (trade.risk_per_share, trade.trade.quantity) {
+                (Some(risk), Some(qty)) => {
+                    if calculate_risk_amount(risk, qty, trade.trade.value_multiplier()) > max_risk {
+                        trades_over_risk += 1;
+                    }
+                }
+                _ => trades_without_stop += 1,
+            }
+        }
+    }
+
+    let days_traded = days.len() as i32;
+    let checks = [
+        goals.max_trades_per_day.map(|_| days_over_trade_limit == 0),
+        goals.journal_every_day.then_some(days_journaled == days_traded),
+        goals.max_risk_per_trade.map(|_| trades_over_risk + trades_without_stop == 0),
+    ];
+
+    ProcessScore {
+        max_trades_per_day: goals.max_trades_per_day,
+        days_traded,
+        days_over_trade_limit,
+        journal_every_day: goals.journal_every_day,
+        days_journaled,
+        max_risk_per_trade: goals.max_risk_per_trade,
+        trades_over_risk,
+        trades_without_stop,
+        goals_met: checks.iter().filter(|c| **c == Some(true)).count() as i32,
+        goals_evaluated: checks.iter().filter(|c| c.is_some()).count() as i32,
+    }
+}
+
+fn streak(days: impl Iterator<Item = (NaiveDate, bool)>) -> ProcessStreak {
+    let mut streak = ProcessStreak::default();
+    for (date, met) in days {
+        if met {
+            streak.current += 1;
+            streak.days_met += 1;
+            streak.longest = streak.longest.max(streak.current);
+        } else {
+            streak.current = 0;
+            streak.last_broken = Some(date);
+        }
+    }
+    streak
+}
+
+/// Journaling and loss limit streaks over the traded days of closed trades
+/// A day stays within the loss limit when the net PnL of the trades entered that day is no worse than -limit.
+pub fn calculate_process_streaks(trades: &[TradeWithDerived], daily_loss_limit: Option<f64>) -> ProcessStreaks {
+    let days = group_by_day(trades);
+
+    let within_loss_limit = daily_loss_limit.map(|limit| {
+        streak(days.iter().map(|(date, day)| {
+            let net: f64 = day.iter().filter_map(|t| t.net_pnl).sum();
+            (*date, net >= -limit)
+        }))
+    });
+
+    ProcessStreaks {
+        days_traded: days.len() as i32,
+        last_traded: days.keys().next_back().copied(),
+        journaled: streak(days.iter().map(|(date, day)| (*date, is_journaled(day)))),
+        daily_loss_limit,
+        within_loss_limit,
+    }
+}
+
+/// Summarize the PnL of a week of closed trades
+pub fn calculate_outcome_score(trades: &[TradeWithDerived]) -> OutcomeScore {
+    let mut acc = PerformanceAccumulator::default();
+    for trade in trades {
+        acc.add(trade);
+    }
+    let bucket = acc.into_bucket(String::new());
+
+    OutcomeScore {
+        trade_count: bucket.trade_count,
+        net_pnl: bucket.net_pnl,
+        win_rate: bucket.win_rate,
+    }
+}
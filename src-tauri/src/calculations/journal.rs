@@ -0,0 +1,42 @@
+use crate::models::DailyPerformance;
+
+/// Starting text of a day's journal entry, from the day's summary
+pub fn draft_day_journal(
+    performance: Option<&DailyPerformance>,
+    trades_opened: i32,
+    open_positions: i32,
+    symbols: &[String],
+) -> String {
+    let mut lines = Vec::new();
+
+    if let Some(day) = performance {
+        lines.push(format!(
+            "Net PnL {:.2} over {} closed trade{} ({} won, {} lost, {} breakeven)",
+            day.realized_net_pnl,
+            day.trade_count,
+            if day.trade_count == 1 { "" } else { "s" },
+            day.win_count,
+            day.loss_count,
+            day.breakeven_count,
+        ));
+        let extremes: Vec<String> = [("Largest win", day.largest_win), ("Largest loss", day.largest_loss)]
+            .iter()
+            .filter_map(|(label, value)| value.map(|v| format!("{} {:.2}", label, v)))
+            .collect();
+        if !extremes.is_empty() {
+            lines.push(extremes.join(", "));
+        }
+    }
+    if trades_opened > 0 {
+        lines.push(format!("Opened {} trade{} in {}", trades_opened, if trades_opened == 1 { "" } else { "s" }, symbols.join(", ")));
+    }
+    if open_positions > 0 {
+        lines.push(format!("{} position{} still open", open_positions, if open_positions == 1 { "" } else { "s" }));
+    }
+
+    lines.push(String::new());
+    lines.push("What went well:".to_string());
+    lines.push(String::new());
+    lines.push("What to improve:".to_string());
+    lines.join("\n")
+}
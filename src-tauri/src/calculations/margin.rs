@@ -0,0 +1,126 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::{NaiveDate, NaiveTime, Timelike};
+use crate::calculations::open_exposure_end;
+use crate::models::{MarginUtilizationDay, MarginUtilizationReport, Status, TradeWithDerived};
+
+const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+
+/// Seconds since midnight of an HH:MM or HH:MM:SS time
+fn seconds_of_day(time: Option<&str>) -> Option<u32> {
+    let time = time?.trim();
+    NaiveTime::parse_from_str(time, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+        .ok()
+        .map(|t| t.num_seconds_from_midnight())
+}
+
+/// Peak margin and position value held per account per day
+/// Positions are open from their trade date through their last exit date, as for portfolio heat. On the
+/// opening and closing day, entry and exit times bound the position; without times it counts for the whole day.
+/// Margin is position value × the instrument's margin requirement; instruments without one are fully paid.
+/// Account equity is the starting balance plus PnL of the account's trades closed before the day.
+pub fn calculate_margin_utilization(
+    trades: &[TradeWithDerived],
+    exit_dates: &HashMap<String, NaiveDate>,
+    margin_requirements: &HashMap<String, f64>,
+    starting_balances: &HashMap<String, f64>,
+    max_utilization_pct: f64,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> MarginUtilizationReport {
+    let mut report = MarginUtilizationReport {
+        max_utilization_pct,
+        ..Default::default()
+    };
+
+    let close_date = |trade: &TradeWithDerived| match exit_dates.get(&trade.trade.id) {
+        Some(&exit_date) if trade.trade.status == Status::Closed => exit_date,
+        _ => open_exposure_end(&trade.trade, end_date),
+    };
+
+    let mut by_account: BTreeMap<&str, Vec<&TradeWithDerived>> = BTreeMap::new();
+    for trade in trades {
+        by_account.entry(trade.trade.account_id.as_str()).or_default().push(trade);
+    }
+
+    for (account_id, account_trades) in by_account {
+        let mut date = start_date;
+        while date <= end_date {
+            // (seconds of day, opens, margin, notional); closes sort before opens at the same second
+            let mut events: Vec<(u32, bool, f64, f64)> = Vec::new();
+            for trade in &account_trades {
+                let opened = trade.trade.trade_date;
+                let closed = close_date(trade);
+                if date < opened || date > closed {
+                    continue;
+                }
+
+                let notional = trade.trade.entry_price
+                    * trade.trade.quantity.unwrap_or(0.0)
+                    * trade.trade.value_multiplier();
+                let margin = notional * margin_requirements.get(&trade.trade.instrument_id).copied().unwrap_or(1.0);
+                let start = if date == opened {
+                    seconds_of_day(trade.trade.entry_time.as_deref()).unwrap_or(0)
+                } else {
+                    0
+                };
+                let end = if date == closed && trade.trade.status == Status::Closed {
+                    seconds_of_day(trade.trade.exit_time.as_deref()).unwrap_or(SECONDS_PER_DAY)
+                } else {
+                    SECONDS_PER_DAY
+                };
+                events.push((start, true, margin, notional));
+                events.push((end, false, margin, notional));
+            }
+
+            if !events.is_empty() {
+                events.sort_by_key(|e| (e.0, e.1));
+                let (mut margin, mut notional) = (0.0, 0.0);
+                let (mut peak_margin, mut peak_notional): (f64, f64) = (0.0, 0.0);
+                for (_, opens, event_margin, event_notional) in events {
+                    let sign = if opens { 1.0 } else { -1.0 };
+                    margin += sign * event_margin;
+                    notional += sign * event_notional;
+                    peak_margin = peak_margin.max(margin);
+                    peak_notional = peak_notional.max(notional);
+                }
+
+                let realized: f64 = account_trades
+                    .iter()
+                    .filter(|t| t.trade.status == Status::Closed && close_date(t) < date)
+                    .filter_map(|t| t.net_pnl)
+                    .sum();
+                let equity = starting_balances
+                    .get(account_id)
+                    .map(|balance| balance + realized)
+                    .filter(|e| *e > 0.0);
+
+                match equity {
+                    Some(account_equity) => {
+                        let utilization_pct = peak_margin / account_equity * 100.0;
+                        report.days.push(MarginUtilizationDay {
+                            date,
+                            account_id: account_id.to_string(),
+                            account_equity,
+                            peak_margin,
+                            peak_notional,
+                            utilization_pct,
+                            leverage: peak_notional / account_equity,
+                            over_threshold: utilization_pct > max_utilization_pct,
+                        });
+                    }
+                    None => report.skipped_no_balance += 1,
+                }
+            }
+
+            date = match date.succ_opt() {
+                Some(next) => next,
+                None => break,
+            };
+        }
+    }
+
+    report.days.sort_by(|a, b| (a.date, &a.account_id).cmp(&(b.date, &b.account_id)));
+    report.over_threshold_count = report.days.iter().filter(|d| d.over_threshold).count() as i32;
+    report
+}
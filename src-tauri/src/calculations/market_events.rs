@@ -0,0 +1,92 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use chrono::{Duration, NaiveDate};
+use crate::calculations::PerformanceAccumulator;
+use crate::models::{EarningsWindowReport, EventDayReport, MarketEvent, MarketEventType, TradeWithDerived};
+
+/// Events on a trade's date: market-wide events and events for the trade's symbol
+pub fn events_for_trade<'a>(trade: &TradeWithDerived, events: &'a [MarketEvent]) -> Vec<&'a MarketEvent> {
+    events
+        .iter()
+        .filter(|e| e.event_date == trade.trade.trade_date)
+        .filter(|e| match &e.symbol {
+            Some(symbol) => symbol.eq_ignore_ascii_case(&trade.trade.symbol),
+            None => true,
+        })
+        .collect()
+}
+
+/// Compare closed trades taken on event days with those on normal days
+pub fn calculate_event_day_report(trades: &[TradeWithDerived], events: &[MarketEvent]) -> EventDayReport {
+    let mut event_days = PerformanceAccumulator::default();
+    let mut normal_days = PerformanceAccumulator::default();
+    let mut by_type: BTreeMap<&'static str, PerformanceAccumulator> = BTreeMap::new();
+
+    for trade in trades.iter().filter(|t| t.net_pnl.is_some()) {
+        let types: BTreeSet<MarketEventType> = events_for_trade(trade, events)
+            .iter()
+            .map(|e| e.event_type)
+            .collect();
+
+        if types.is_empty() {
+            normal_days.add(trade);
+            continue;
+        }
+
+        event_days.add(trade);
+        for event_type in types {
+            by_type.entry(event_type.as_str()).or_default().add(trade);
+        }
+    }
+
+    EventDayReport {
+        event_days: event_days.into_bucket("event_days".to_string()),
+        normal_days: normal_days.into_bucket("normal_days".to_string()),
+        by_event_type: by_type
+            .into_iter()
+            .map(|(key, acc)| acc.into_bucket(key.to_string()))
+            .collect(),
+    }
+}
+
+/// Compare closed trades held through earnings with trades closed shortly before
+/// A trade is held through when it was opened before an earnings date of its symbol and closed on or after it.
+/// Exit dates come from the last exit execution, falling back to the trade date.
+pub fn calculate_earnings_window_report(
+    trades: &[TradeWithDerived],
+    events: &[MarketEvent],
+    exit_dates: &HashMap<String, NaiveDate>,
+    window_days: i64,
+) -> EarningsWindowReport {
+    let mut earnings_dates: HashMap<String, Vec<NaiveDate>> = HashMap::new();
+    for event in events.iter().filter(|e| e.event_type == MarketEventType::Earnings) {
+        if let Some(symbol) = &event.symbol {
+            earnings_dates.entry(symbol.to_uppercase()).or_default().push(event.event_date);
+        }
+    }
+
+    let mut held_through = PerformanceAccumulator::default();
+    let mut closed_before = PerformanceAccumulator::default();
+    let mut held_through_trade_ids = Vec::new();
+
+    for trade in trades.iter().filter(|t| t.net_pnl.is_some()) {
+        let Some(dates) = earnings_dates.get(&trade.trade.symbol.to_uppercase()) else {
+            continue;
+        };
+        let opened = trade.trade.trade_date;
+        let closed = exit_dates.get(&trade.trade.id).copied().unwrap_or(opened);
+
+        if dates.iter().any(|&d| opened < d && closed >= d) {
+            held_through.add(trade);
+            held_through_trade_ids.push(trade.trade.id.clone());
+        } else if dates.iter().any(|&d| closed < d && closed >= d - Duration::days(window_days)) {
+            closed_before.add(trade);
+        }
+    }
+
+    EarningsWindowReport {
+        window_days,
+        held_through: held_through.into_bucket("held_through".to_string()),
+        closed_before: closed_before.into_bucket("closed_before".to_string()),
+        held_through_trade_ids,
+    }
+}
@@ -1,5 +1,61 @@
 pub mod pnl;
 pub mod aggregations;
+pub mod execution_quality;
+pub mod carrying_costs;
+pub mod dividends;
+pub mod reconciliation;
+pub mod r_multiples;
+pub mod checklists;
+pub mod market_events;
+pub mod day_conditions;
+pub mod timeline;
+pub mod goals;
+pub mod strategy_rules;
+pub mod excursions;
+pub mod stops;
+pub mod volatility;
+pub mod options;
+pub mod margin;
+pub mod financing;
+pub mod returns;
+pub mod fx;
+pub mod short_side;
+pub mod reminders;
+pub mod journal;
+pub mod trade_types;
+pub mod skill_progression;
+pub mod snapshots;
+pub mod clustering;
+pub mod futures_sessions;
+pub mod cost_basis;
 
 pub use pnl::*;
 pub use aggregations::*;
+pub use execution_quality::*;
+pub use carrying_costs::*;
+pub use dividends::*;
+pub use reconciliation::*;
+pub use r_multiples::*;
+pub use checklists::*;
+pub use market_events::*;
+pub use day_conditions::*;
+pub use timeline::*;
+pub use goals::*;
+pub use strategy_rules::*;
+pub use excursions::*;
+pub use stops::*;
+pub use volatility::*;
+pub use options::*;
+pub use margin::*;
+pub use financing::*;
+pub use returns::*;
+pub use fx::*;
+pub use short_side::*;
+pub use reminders::*;
+pub use journal::*;
+pub use trade_types::*;
+pub use skill_progression::*;
+pub use snapshots::*;
+pub use clustering::*;
+pub use futures_sessions::*;
+pub use cost_basis::*;
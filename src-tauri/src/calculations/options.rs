@@ -0,0 +1,68 @@
+use crate::calculations::PerformanceAccumulator;
+use crate::models::{AssetClass, Direction, OptionOutcome, OptionsPremiumBucket, OptionsPremiumReport, TradeWithDerived};
+
+/// Running totals for an OptionsPremiumBucket
+#[derive(Default)]
+struct PremiumAccumulator {
+    performance: PerformanceAccumulator,
+    closed_count: i32,
+    open_count: i32,
+    premium: f64,
+    expired_count: i32,
+    assigned_count: i32,
+    exercised_count: i32,
+}
+
+impl PremiumAccumulator {
+    fn add(&mut self, trade: &TradeWithDerived) {
+        let quantity = trade.trade.quantity.unwrap_or(0.0);
+        self.premium += trade.trade.entry_price * quantity * AssetClass::Option.multiplier();
+
+        if trade.net_pnl.is_none() {
+            self.open_count += 1;
+            return;
+        }
+        self.performance.add(trade);
+        self.closed_count += 1;
+        match trade.trade.option_outcome {
+            Some(OptionOutcome::Expired) => self.expired_count += 1,
+            Some(OptionOutcome::Assigned) => self.assigned_count += 1,
+            Some(OptionOutcome::Exercised) => self.exercised_count += 1,
+            None => {}
+        }
+    }
+
+    fn into_bucket(self, key: &str) -> OptionsPremiumBucket {
+        OptionsPremiumBucket {
+            performance: self.performance.into_bucket(key.to_string()),
+            open_count: self.open_count,
+            premium: self.premium,
+            expired_count: self.expired_count,
+            assigned_count: self.assigned_count,
+            exercised_count: self.exercised_count,
+            assignment_rate: (self.closed_count > 0)
+                .then(|| self.assigned_count as f64 / self.closed_count as f64),
+        }
+    }
+}
+
+/// Split option trades into premium sold (short) and premium bought (long)
+/// Win rate and PnL cover closed trades; premium also counts positions still open.
+pub fn calculate_options_premium_report(trades: &[TradeWithDerived]) -> OptionsPremiumReport {
+    let mut sold = PremiumAccumulator::default();
+    let mut bought = PremiumAccumulator::default();
+
+    for trade in trades.iter().filter(|t| t.trade.asset_class == AssetClass::Option) {
+        match trade.trade.direction {
+            Direction::Short => sold.add(trade),
+            Direction::Long => bought.add(trade),
+        }
+    }
+
+    let net_premium = sold.premium - bought.premium;
+    OptionsPremiumReport {
+        sold: sold.into_bucket("sold"),
+        bought: bought.into_bucket("bought"),
+        net_premium,
+    }
+}
@@ -1,4 +1,4 @@
-use crate::models::{Direction, DerivedFields, Trade, TradeResult};
+use crate::models::{Direction, DerivedFields, ResultBasis, Trade, TradeResult};
 
 /// Calculate gross PnL for a trade
 /// Long: (exit_price - entry_price) × quantity × multiplier
@@ -38,6 +38,27 @@ pub fn calculate_risk_per_share(entry_price: f64, stop_loss_price: f64) -> Optio
     }
 }
 
+/// Calculate the dollar amount at risk for a position
+/// risk_per_share × quantity × multiplier
+pub fn calculate_risk_amount(risk_per_share: f64, quantity: f64, multiplier: f64) -> f64 {
+    risk_per_share * quantity * multiplier
+}
+
+/// Snap a price onto a tick grid
+/// Returns None if the price is off the grid by more than float noise, e.g. 15.003 with a 0.01 tick
+pub fn snap_to_tick(price: f64, tick_size: f64) -> Option<f64> {
+    if tick_size <= 0.0 {
+        return Some(price);
+    }
+    let ticks = price / tick_size;
+    let rounded = ticks.round();
+    if (ticks - rounded).abs() > 1e-6 {
+        return None;
+    }
+    // Round away multiplication noise such as 1505 × 0.01 = 15.049999999999999
+    Some((rounded * tick_size * 1e10).round() / 1e10)
+}
+
 /// Calculate R-multiple
 /// pnl_per_share / risk_per_share
 /// Returns None if risk_per_share is None or zero
@@ -60,15 +81,20 @@ pub fn classify_result(net_pnl: f64) -> TradeResult {
 }
 
 /// Calculate all derived fields for a trade
-pub fn calculate_derived_fields(trade: &Trade) -> DerivedFields {
-    // Get the multiplier based on asset class (100 for options, 1 for stocks)
-    let multiplier = trade.asset_class.multiplier();
+/// The result is classified by gross or net PnL according to the basis; both PnL values are kept.
+pub fn calculate_derived_fields(trade: &Trade, result_basis: ResultBasis) -> DerivedFields {
+    // Contract multiplier of the instrument (100 for options, 1 for stocks, per contract for futures)
+    let multiplier = trade.multiplier();
 
     // Check if we have required data for PnL calculation
     let (gross_pnl, net_pnl, pnl_per_share) = match (trade.exit_price, trade.quantity) {
         (Some(exit), Some(qty)) => {
-            let gross = calculate_gross_pnl(trade.direction, trade.entry_price, exit, qty, multiplier);
-            let net = calculate_net_pnl(gross, trade.fees);
+            // Prices are in the instrument's quote currency; fees and costs already in the account's
+            let gross = calculate_gross_pnl(trade.direction, trade.entry_price, exit, qty, multiplier)
+                * trade.fx_rate.unwrap_or(1.0);
+            // Dividends received reduce costs, dividends paid on shorts add to them
+            let costs = trade.fees + trade.carrying_costs - trade.dividends;
+            let net = calculate_net_pnl(gross, costs);
             let pps = calculate_pnl_per_share(trade.direction, trade.entry_price, exit);
             (Some(gross), Some(net), Some(pps))
         }
@@ -83,8 +109,17 @@ pub fn calculate_derived_fields(trade: &Trade) -> DerivedFields {
     let r_multiple = pnl_per_share
         .and_then(|pps| calculate_r_multiple(pps, risk_per_share));
 
-    // Classify result if we have net PnL
-    let result = net_pnl.map(classify_result);
+    // Express the per-share move in average true ranges at entry
+    let atr_multiple = pnl_per_share
+        .zip(trade.entry_atr.filter(|&atr| atr > 0.0))
+        .map(|(pps, atr)| pps / atr);
+
+    // Classify result if we have PnL on the configured basis
+    let result = match result_basis {
+        ResultBasis::Gross => gross_pnl,
+        ResultBasis::Net => net_pnl,
+    }
+    .map(classify_result);
 
     DerivedFields {
         gross_pnl,
@@ -92,6 +127,7 @@ pub fn calculate_derived_fields(trade: &Trade) -> DerivedFields {
         pnl_per_share,
         risk_per_share,
         r_multiple,
+        atr_multiple,
         result,
     }
 }
@@ -100,6 +136,19 @@ pub fn calculate_derived_fields(trade: &Trade) -> DerivedFields {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_snap_to_tick_on_grid() {
+        assert_eq!(snap_to_tick(15.05, 0.01), Some(15.05));
+        assert_eq!(snap_to_tick(4512.75, 0.25), Some(4512.75));
+        assert_eq!(snap_to_tick(15.049999999999999, 0.01), Some(15.05));
+    }
+
+    #[test]
+    fn test_snap_to_tick_off_grid() {
+        assert_eq!(snap_to_tick(15.003, 0.01), None);
+        assert_eq!(snap_to_tick(4512.1, 0.25), None);
+    }
+
     #[test]
     fn test_gross_pnl_long_win() {
         let pnl = calculate_gross_pnl(Direction::Long, 100.0, 110.0, 10.0, 1.0);
@@ -163,6 +212,13 @@ mod tests {
         assert!(risk.is_none());
     }
 
+    #[test]
+    fn test_risk_amount_option_with_multiplier() {
+        // 5 contracts risking $0.50 each = 0.50 * 5 * 100 = 250
+        let risk = calculate_risk_amount(0.50, 5.0, 100.0);
+        assert!((risk - 250.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_r_multiple() {
         let r = calculate_r_multiple(10.0, Some(5.0));
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use crate::calculations::{calculate_risk_amount, sort_chronologically};
+use crate::models::{Direction, PositionSizingPoint, PositionSizingReport, RExpectancyReport, TargetCalibrationReport, TradeWithDerived};
+
+/// R-multiple of a closed trade, falling back to an assumed dollar risk without a stop loss
+/// Returns the R value and whether it came from the assumed risk.
+/// Stop-based R is per share (pnl_per_share / risk_per_share); assumed R is net_pnl / default_risk.
+pub fn calculate_trade_r(trade: &TradeWithDerived, default_risk: Option<f64>) -> Option<(f64, bool)> {
+    if let Some(r) = trade.r_multiple {
+        return Some((r, false));
+    }
+
+    let net_pnl = trade.net_pnl?;
+    default_risk
+        .filter(|risk| *risk > 0.0)
+        .map(|risk| (net_pnl / risk, true))
+}
+
+/// Calculate expectancy in R over closed trades, with ATR-multiples for trades that have an entry ATR
+/// Trades with neither a stop loss nor a default risk are counted as skipped.
+pub fn calculate_r_expectancy(trades: &[TradeWithDerived], default_risk: Option<f64>) -> RExpectancyReport {
+    let mut report = RExpectancyReport::default();
+    let mut total_win_r = 0.0;
+    let mut total_loss_r = 0.0;
+    let mut win_count = 0;
+    let mut loss_count = 0;
+
+    for trade in trades.iter().filter(|t| t.net_pnl.is_some()) {
+        report.trade_count += 1;
+
+        if let Some(atr_multiple) = trade.atr_multiple {
+            report.atr_trade_count += 1;
+            report.total_atr += atr_multiple;
+        }
+
+        let Some((r, assumed)) = calculate_trade_r(trade, default_risk) else {
+            report.skipped_count += 1;
+            continue;
+        };
+
+        if assumed {
+            report.assumed_risk_count += 1;
+        } else {
+            report.stop_based_count += 1;
+        }
+        report.total_r += r;
+
+        if r > 0.0 {
+            win_count += 1;
+            total_win_r += r;
+        } else if r < 0.0 {
+            loss_count += 1;
+            total_loss_r += r;
+        }
+    }
+
+    let measured = report.stop_based_count + report.assumed_risk_count;
+    report.expectancy_r = (measured > 0).then(|| report.total_r / measured as f64);
+    report.avg_win_r = (win_count > 0).then(|| total_win_r / win_count as f64);
+    report.avg_loss_r = (loss_count > 0).then(|| total_loss_r / loss_count as f64);
+    report.expectancy_atr = (report.atr_trade_count > 0).then(|| report.total_atr / report.atr_trade_count as f64);
+    report
+}
+
+/// Favorable move per share from entry to a price, negative when against the trade
+fn favorable_move(direction: Direction, entry_price: f64, price: f64) -> f64 {
+    match direction {
+        Direction::Long => price - entry_price,
+        Direction::Short => entry_price - price,
+    }
+}
+
+/// Planned reward-to-risk from the target and stop loss
+/// Returns None unless both are set and the target lies on the profit side of entry.
+pub fn calculate_planned_r(trade: &TradeWithDerived) -> Option<f64> {
+    let target = trade.trade.target_price?;
+    let risk = trade.risk_per_share.filter(|r| *r > 0.0)?;
+    let reward = favorable_move(trade.trade.direction, trade.trade.entry_price, target);
+    (reward > 0.0).then(|| reward / risk)
+}
+
+/// Calibrate planned targets against how far closed trades actually went
+/// The best price is the recorded max favorable price, or the exit price when it was not recorded.
+pub fn calculate_target_calibration(trades: &[TradeWithDerived], min_planned_r: f64) -> TargetCalibrationReport {
+    let mut report = TargetCalibrationReport {
+        min_planned_r,
+        ..Default::default()
+    };
+    let mut total_planned_r = 0.0;
+    let mut total_realized_r = 0.0;
+    let mut realized_count = 0;
+
+    for trade in trades.iter().filter(|t| t.net_pnl.is_some()) {
+        let (Some(planned_r), Some(risk), Some(exit_price)) =
+            (calculate_planned_r(trade), trade.risk_per_share, trade.trade.exit_price)
+        else {
+            continue;
+        };
+        if planned_r < min_planned_r {
+            continue;
+        }
+
+        let direction = trade.trade.direction;
+        let entry_price = trade.trade.entry_price;
+        report.planned_count += 1;
+        total_planned_r += planned_r;
+
+        let best_move = trade
+            .trade
+            .max_favorable_price
+            .map(|p| favorable_move(direction, entry_price, p))
+            .unwrap_or(f64::MIN)
+            .max(favorable_move(direction, entry_price, exit_price));
+
+        // Small tolerance so a fill exactly at the level counts
+        if best_move >= min_planned_r * risk - 1e-9 {
+            report.reached_count += 1;
+        } else if trade
+            .trade
+            .stop_loss_price
+            .is_some_and(|stop| favorable_move(direction, stop, exit_price) <= 0.0)
+        {
+            report.stopped_count += 1;
+        }
+
+        if let Some(r) = trade.r_multiple {
+            total_realized_r += r;
+            realized_count += 1;
+        }
+    }
+
+    if report.planned_count > 0 {
+        report.reach_rate = Some(report.reached_count as f64 / report.planned_count as f64);
+        report.avg_planned_r = Some(total_planned_r / report.planned_count as f64);
+    }
+    report.avg_realized_r = (realized_count > 0).then(|| total_realized_r / realized_count as f64);
+    report
+}
+
+/// Audit the initial stop risk of closed trades against a percent of account equity
+/// Equity is the account's starting balance plus PnL of its earlier closed trades; trades before
+/// start_date only build up equity and are not reported.
+pub fn calculate_position_sizing_audit(
+    trades: &[TradeWithDerived],
+    starting_balances: &HashMap<String, f64>,
+    risk_pct: f64,
+    start_date: Option<NaiveDate>,
+) -> PositionSizingReport {
+    let mut closed: Vec<&TradeWithDerived> = trades.iter().filter(|t| t.net_pnl.is_some()).collect();
+    sort_chronologically(&mut closed);
+
+    let mut report = PositionSizingReport {
+        risk_pct_setting: risk_pct,
+        ..Default::default()
+    };
+    let mut realized_by_account: HashMap<&str, f64> = HashMap::new();
+
+    for trade in closed {
+        let net_pnl = trade.net_pnl.unwrap_or(0.0);
+        let realized = realized_by_account.entry(trade.trade.account_id.as_str()).or_insert(0.0);
+        let equity = starting_balances
+            .get(&trade.trade.account_id)
+            .map(|balance| balance + *realized);
+        *realized += net_pnl;
+
+        if start_date.is_some_and(|start| trade.trade.trade_date < start) {
+            continue;
+        }
+
+        let (Some(risk_per_share), Some(quantity)) = (trade.risk_per_share, trade.trade.quantity) else {
+            report.skipped_no_stop += 1;
+            continue;
+        };
+        let Some(account_equity) = equity.filter(|e| *e > 0.0) else {
+            report.skipped_no_balance += 1;
+            continue;
+        };
+
+        let actual_risk = calculate_risk_amount(risk_per_share, quantity, trade.trade.value_multiplier());
+        let allowed_risk = account_equity * risk_pct / 100.0;
+        let oversized = actual_risk > allowed_risk;
+
+        report.total_net_pnl += net_pnl;
+        if oversized {
+            report.oversized_count += 1;
+            report.oversized_net_pnl += net_pnl;
+        }
+        report.points.push(PositionSizingPoint {
+            trade_id: trade.trade.id.clone(),
+            account_id: trade.trade.account_id.clone(),
+            symbol: trade.trade.symbol.clone(),
+            date: trade.trade.trade_date,
+            account_equity,
+            allowed_risk,
+            actual_risk,
+            risk_pct: actual_risk / account_equity * 100.0,
+            oversized,
+            net_pnl,
+        });
+    }
+
+    report
+}
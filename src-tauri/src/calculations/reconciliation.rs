@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use crate::models::{
+    ReconciliationIssue, ReconciliationIssueKind, ReconciliationReport, StatementTotals, Status,
+    TradeWithDerived,
+};
+
+/// Differences up to one cent are treated as rounding
+pub const RECONCILIATION_TOLERANCE: f64 = 0.01;
+
+fn issue(kind: ReconciliationIssueKind, trade: &TradeWithDerived) -> ReconciliationIssue {
+    ReconciliationIssue {
+        kind,
+        trade_id: trade.trade.id.clone(),
+        symbol: trade.trade.symbol.clone(),
+        trade_date: trade.trade.trade_date,
+        net_pnl: trade.net_pnl,
+    }
+}
+
+/// Compare closed journal trades against broker statement totals
+/// Open trades are excluded from the journal totals and reported as issues
+/// when the totals don't match. Financing charges of the period reduce the journal net PnL,
+/// as broker statements include them.
+pub fn reconcile_trades(
+    trades: &[TradeWithDerived],
+    financing_costs: f64,
+    totals: &StatementTotals,
+) -> ReconciliationReport {
+    let closed: Vec<&TradeWithDerived> = trades
+        .iter()
+        .filter(|t| t.trade.status == Status::Closed)
+        .collect();
+
+    let journal_net_pnl: f64 = closed.iter().filter_map(|t| t.net_pnl).sum::<f64>() - financing_costs;
+    let journal_fees: f64 = closed.iter().map(|t| t.trade.fees).sum();
+    let journal_trade_count = closed.len() as i32;
+
+    let net_pnl_difference = journal_net_pnl - totals.net_pnl;
+    let fees_difference = totals.fees.map(|fees| journal_fees - fees);
+    let trade_count_difference = totals.trade_count.map(|count| journal_trade_count - count);
+
+    let is_reconciled = net_pnl_difference.abs() <= RECONCILIATION_TOLERANCE
+        && !fees_difference.is_some_and(|d| d.abs() > RECONCILIATION_TOLERANCE)
+        && trade_count_difference.unwrap_or(0) == 0;
+
+    let mut issues = Vec::new();
+    if !is_reconciled {
+        // Identical fills recorded twice
+        let mut groups: HashMap<String, Vec<&TradeWithDerived>> = HashMap::new();
+        for trade in &closed {
+            let t = &trade.trade;
+            let key = format!(
+                "{}|{}|{}|{:?}|{}|{:?}",
+                t.symbol, t.trade_date, t.direction.as_str(), t.quantity, t.entry_price, t.exit_price
+            );
+            groups.entry(key).or_default().push(trade);
+        }
+        let mut duplicates: Vec<&TradeWithDerived> = groups
+            .into_values()
+            .filter(|group| group.len() > 1)
+            .flat_map(|group| group.into_iter().skip(1))
+            .collect();
+        duplicates.sort_by_key(|t| t.trade.trade_date);
+        issues.extend(
+            duplicates
+                .into_iter()
+                .map(|t| issue(ReconciliationIssueKind::PossibleDuplicate, t)),
+        );
+
+        // A single extra journal trade explains the whole difference
+        if net_pnl_difference.abs() > RECONCILIATION_TOLERANCE {
+            issues.extend(
+                closed
+                    .iter()
+                    .filter(|t| {
+                        t.net_pnl
+                            .is_some_and(|pnl| (pnl - net_pnl_difference).abs() <= RECONCILIATION_TOLERANCE)
+                    })
+                    .map(|t| issue(ReconciliationIssueKind::MatchesDifference, t)),
+            );
+        }
+
+        // Positions closed at the broker but still open in the journal; rolled ones are resolved
+        issues.extend(
+            trades
+                .iter()
+                .filter(|t| t.trade.status == Status::Open && t.trade.roll_type.is_none())
+                .map(|t| issue(ReconciliationIssueKind::OpenPosition, t)),
+        );
+    }
+
+    ReconciliationReport {
+        journal_net_pnl,
+        journal_financing: financing_costs,
+        statement_net_pnl: totals.net_pnl,
+        net_pnl_difference,
+        journal_fees,
+        statement_fees: totals.fees,
+        fees_difference,
+        journal_trade_count,
+        statement_trade_count: totals.trade_count,
+        trade_count_difference,
+        is_reconciled,
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+    use crate::calculations::calculate_derived_fields;
+    use crate::models::{AssetClass, Direction, ResultBasis, Trade};
+
+    fn create_trade(symbol: &str, day: u32, entry: f64, exit: Option<f64>, fees: f64) -> TradeWithDerived {
+        let trade = Trade {
+            id: uuid::Uuid::new_v4().to_string(),
+            user_id: "user1".to_string(),
+            account_id: "account1".to_string(),
+            instrument_id: "inst1".to_string(),
+            symbol: symbol.to_string(),
+            asset_class: AssetClass::Stock,
+            contract_multiplier: None,
+            fx_rate: None,
+            trade_number: None,
+            ref_code: None,
+            trade_date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            direction: Direction::Long,
+            quantity: Some(100.0),
+            entry_price: entry,
+            exit_price: exit,
+            stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
+            max_adverse_price: None,
+            entry_atr: None,
+            entry_time: None,
+            exit_time: None,
+            exit_date: None,
+            fees,
+            carrying_costs: 0.0,
+            dividends: 0.0,
+            strategy: None,
+            notes: None,
+            screenshot_url: None,
+            status: if exit.is_some() { Status::Closed } else { Status::Open },
+            result_override: None,
+            roll_type: None,
+            rolled_on: None,
+            continuation_trade_id: None,
+            option_outcome: None,
+            borrow_availability: None,
+            borrow_note: None,
+            is_locked: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let derived = calculate_derived_fields(&trade, ResultBasis::Net);
+        TradeWithDerived::from_trade(trade, derived)
+    }
+
+    #[test]
+    fn test_reconcile_matching_totals() {
+        let trades = vec![
+            create_trade("AAPL", 2, 100.0, Some(101.0), 2.0), // +98
+            create_trade("MSFT", 3, 50.0, Some(49.0), 2.0),   // -102
+        ];
+        let totals = StatementTotals { net_pnl: -4.0, fees: Some(4.0), trade_count: Some(2) };
+
+        let report = reconcile_trades(&trades, 0.0, &totals);
+
+        assert!(report.is_reconciled);
+        assert!(report.net_pnl_difference.abs() < 0.001);
+        assert_eq!(report.trade_count_difference, Some(0));
+        assert!(report.issues.is_empty());
+    }
+
+    #[test]
+    fn test_reconcile_flags_duplicate_trade() {
+        let trades = vec![
+            create_trade("AAPL", 2, 100.0, Some(101.0), 2.0), // +98
+            create_trade("AAPL", 2, 100.0, Some(101.0), 2.0), // Imported twice
+            create_trade("MSFT", 3, 50.0, Some(49.0), 2.0),   // -102
+        ];
+        let totals = StatementTotals { net_pnl: -4.0, fees: None, trade_count: Some(2) };
+
+        let report = reconcile_trades(&trades, 0.0, &totals);
+
+        assert!(!report.is_reconciled);
+        assert!((report.net_pnl_difference - 98.0).abs() < 0.001);
+        assert_eq!(report.trade_count_difference, Some(1));
+        assert_eq!(report.issues[0].kind, ReconciliationIssueKind::PossibleDuplicate);
+        assert!(report
+            .issues
+            .iter()
+            .any(|i| i.kind == ReconciliationIssueKind::MatchesDifference && i.symbol == "AAPL"));
+    }
+
+    #[test]
+    fn test_reconcile_reports_open_positions() {
+        let trades = vec![
+            create_trade("AAPL", 2, 100.0, Some(101.0), 2.0), // +98
+            create_trade("TSLA", 4, 200.0, None, 1.0),
+        ];
+        let totals = StatementTotals { net_pnl: 300.0, fees: None, trade_count: None };
+
+        let report = reconcile_trades(&trades, 0.0, &totals);
+
+        assert!(!report.is_reconciled);
+        assert_eq!(report.journal_trade_count, 1);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].kind, ReconciliationIssueKind::OpenPosition);
+        assert_eq!(report.issues[0].symbol, "TSLA");
+    }
+}
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use chrono::{Datelike, NaiveDateTime, NaiveTime};
+use crate::models::{DueReminder, Reminder, ReminderKind};
+
+/// Enabled reminders whose occurrence today has passed and has not fired yet
+/// A missed occurrence is caught up later the same day, e.g. on app start, but not on a later day.
+pub fn due_reminders(
+    reminders: &[Reminder],
+    last_fired: &HashMap<ReminderKind, NaiveDateTime>,
+    now: NaiveDateTime,
+) -> Vec<DueReminder> {
+    let weekday = now.date().weekday().num_days_from_monday();
+
+    reminders
+        .iter()
+        .filter(|r| r.enabled && r.weekdays.contains(&weekday))
+        .filter_map(|r| {
+            let time = NaiveTime::parse_from_str(&r.time, "%H:%M").ok()?;
+            let scheduled_for = now.date().and_time(time);
+            let fired = last_fired.get(&r.kind).is_some_and(|last| *last >= scheduled_for);
+            (scheduled_for <= now && !fired).then(|| DueReminder {
+                kind: r.kind,
+                message: r.kind.message().to_string(),
+                scheduled_for,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_due_reminders_fire_once_per_scheduled_day() {
+        let mut reminders = Reminder::defaults();
+        for reminder in &mut reminders {
+            reminder.enabled = true;
+        }
+        reminders[2].enabled = false; // Import statements on Fridays
+
+        // Friday Jan 19 2024: journal is due from 20:00, the import reminder is disabled
+        assert!(due_reminders(&reminders, &HashMap::new(), at(19, 19, 59)).is_empty());
+        let due = due_reminders(&reminders, &HashMap::new(), at(19, 21, 30));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].kind, ReminderKind::JournalTonight);
+        assert_eq!(due[0].scheduled_for, at(19, 20, 0));
+
+        let last_fired = HashMap::from([(ReminderKind::JournalTonight, at(19, 21, 30))]);
+        assert!(due_reminders(&reminders, &last_fired, at(19, 22, 0)).is_empty());
+
+        // Sunday: the weekly review, but no journal reminder and no catch-up of Friday's
+        let due = due_reminders(&reminders, &last_fired, at(21, 18, 0));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].kind, ReminderKind::WeeklyReview);
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+use chrono::{Datelike, Months, NaiveDate};
+use crate::models::{AccountReturnDay, AccountReturns, CashTransaction, FinancingCharge, Status, TradeWithDerived};
+
+/// Daily rate search bounds and iterations for the money-weighted return
+const MWR_MIN_DAILY_RATE: f64 = -0.99;
+const MWR_MAX_DAILY_RATE: f64 = 1.0;
+const MWR_ITERATIONS: usize = 200;
+
+/// Last day of a charge's month; financing is booked when the month closes
+fn month_end(month: NaiveDate) -> NaiveDate {
+    let first = month.with_day(1).unwrap_or(month);
+    first
+        .checked_add_months(Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(first)
+}
+
+/// Time-weighted and money-weighted returns of an account over a period
+/// Deposits and withdrawals happen at the start of their day, PnL (closed trades by trade date,
+/// financing charges at month end) at its end. TWR chains the daily returns, so flows don't affect it;
+/// MWR is the internal rate of return of the start value, flows and end value over the period.
+pub fn calculate_account_returns(
+    account_id: &str,
+    trades: &[TradeWithDerived],
+    charges: &[FinancingCharge],
+    cash_transactions: &[CashTransaction],
+    starting_balance: Option<f64>,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> AccountReturns {
+    // (net flows, pnl) per day
+    let mut days: BTreeMap<NaiveDate, (f64, f64)> = BTreeMap::new();
+
+    for trade in trades {
+        if trade.trade.account_id == account_id && trade.trade.status == Status::Closed {
+            days.entry(trade.trade.trade_date).or_default().1 += trade.net_pnl.unwrap_or(0.0);
+        }
+    }
+    for charge in charges.iter().filter(|c| c.account_id == account_id && c.charge_month <= end_date) {
+        let date = month_end(charge.charge_month).min(end_date);
+        days.entry(date).or_default().1 -= charge.amount;
+    }
+    for transaction in cash_transactions.iter().filter(|t| t.account_id == account_id) {
+        days.entry(transaction.transaction_date).or_default().0 += transaction.amount;
+    }
+
+    let start_value = starting_balance.unwrap_or(0.0)
+        + days
+            .range(..start_date)
+            .map(|(_, (flows, pnl))| flows + pnl)
+            .sum::<f64>();
+
+    let mut report = AccountReturns {
+        account_id: account_id.to_string(),
+        start_value,
+        ..Default::default()
+    };
+
+    let mut value = start_value;
+    let mut growth = 1.0;
+    let mut has_base = start_value > 0.0;
+    // (days since start, amount) of money put in; the start value counts as put in on day 0
+    let mut invested: Vec<(i64, f64)> = vec![(0, start_value)];
+
+    for (&date, &(net_flows, pnl)) in days.range(start_date..=end_date) {
+        let base = value + net_flows;
+        if base > 0.0 {
+            growth *= 1.0 + pnl / base;
+            has_base = true;
+        }
+        value = base + pnl;
+
+        report.net_flows += net_flows;
+        report.total_pnl += pnl;
+        if net_flows != 0.0 {
+            invested.push(((date - start_date).num_days(), net_flows));
+        }
+        report.days.push(AccountReturnDay {
+            date,
+            net_flows,
+            pnl,
+            end_value: value,
+            cumulative_twr_pct: (growth - 1.0) * 100.0,
+        });
+    }
+
+    report.end_value = value;
+    report.twr_pct = has_base.then_some((growth - 1.0) * 100.0);
+    report.mwr_pct = money_weighted_return(&invested, value, (end_date - start_date).num_days() + 1);
+    report
+}
+
+/// Period IRR: the daily rate at which the money put in grows to the end value, compounded over the period
+fn money_weighted_return(invested: &[(i64, f64)], end_value: f64, period_days: i64) -> Option<f64> {
+    if period_days <= 0 || invested.iter().map(|(_, amount)| amount.max(0.0)).sum::<f64>() <= 0.0 {
+        return None;
+    }
+
+    // Future value of the money put in minus the end value; increasing in the rate while more goes in than out
+    let excess = |rate: f64| {
+        invested
+            .iter()
+            .map(|(day, amount)| amount * (1.0 + rate).powf((period_days - day) as f64))
+            .sum::<f64>()
+            - end_value
+    };
+
+    let (mut low, mut high) = (MWR_MIN_DAILY_RATE, MWR_MAX_DAILY_RATE);
+    if excess(low) > 0.0 || excess(high) < 0.0 {
+        return None;
+    }
+    for _ in 0..MWR_ITERATIONS {
+        let mid = (low + high) / 2.0;
+        if excess(mid) < 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    let daily_rate = (low + high) / 2.0;
+    Some(((1.0 + daily_rate).powf(period_days as f64) - 1.0) * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::models::{FinancingChargeSource, FinancingChargeType};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    fn deposit(day: u32, amount: f64) -> CashTransaction {
+        CashTransaction {
+            id: format!("cash-{}", day),
+            account_id: "acc".to_string(),
+            transaction_date: date(day),
+            amount,
+            notes: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn interest_credit(amount: f64) -> FinancingCharge {
+        FinancingCharge {
+            id: "charge".to_string(),
+            account_id: "acc".to_string(),
+            charge_month: date(1),
+            charge_type: FinancingChargeType::MarginInterest,
+            amount: -amount,
+            source: FinancingChargeSource::Manual,
+            notes: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_returns_without_flows_agree() {
+        let report = calculate_account_returns(
+            "acc",
+            &[],
+            &[interest_credit(100.0)],
+            &[],
+            Some(10000.0),
+            date(1),
+            date(31),
+        );
+
+        assert!((report.end_value - 10100.0).abs() < 0.001);
+        assert!((report.twr_pct.unwrap() - 1.0).abs() < 0.0001);
+        assert!((report.mwr_pct.unwrap() - 1.0).abs() < 0.0001);
+        assert_eq!(report.days.len(), 1);
+        assert_eq!(report.days[0].date, date(31));
+    }
+
+    #[test]
+    fn test_deposit_is_not_counted_as_return() {
+        // The deposit doubles the account halfway through, before the month-end credit
+        let report = calculate_account_returns(
+            "acc",
+            &[],
+            &[interest_credit(200.0)],
+            &[deposit(16, 10000.0)],
+            Some(10000.0),
+            date(1),
+            date(31),
+        );
+
+        assert!((report.net_flows - 10000.0).abs() < 0.001);
+        assert!((report.total_pnl - 200.0).abs() < 0.001);
+        assert!((report.twr_pct.unwrap() - 1.0).abs() < 0.0001);
+        // Half the money was in for only half the month, so the same gain was earned on less average capital
+        let mwr = report.mwr_pct.unwrap();
+        assert!(mwr > 1.0 && mwr < 2.0);
+    }
+
+    #[test]
+    fn test_no_capital_has_no_returns() {
+        let report = calculate_account_returns("acc", &[], &[], &[], None, date(1), date(31));
+
+        assert!(report.twr_pct.is_none());
+        assert!(report.mwr_pct.is_none());
+    }
+}
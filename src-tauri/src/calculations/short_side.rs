@@ -0,0 +1,109 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::NaiveDate;
+use crate::calculations::PerformanceAccumulator;
+use crate::models::{Direction, HoldingDaysBucket, ShortSideReport, SideCostSummary, TradeWithDerived};
+
+/// Calendar days held by short trades: (label, min days, max days)
+const SHORT_HOLDING_DAYS: [(&str, i64, Option<i64>); 5] = [
+    ("Same day", 0, Some(0)),
+    ("1-2 days", 1, Some(2)),
+    ("3-5 days", 3, Some(5)),
+    ("6-10 days", 6, Some(10)),
+    ("Over 10 days", 11, None),
+];
+
+/// Running totals for a SideCostSummary
+#[derive(Default)]
+struct SideAccumulator {
+    performance: PerformanceAccumulator,
+    trade_count: i32,
+    fees: f64,
+    carrying_costs: f64,
+    notional: f64,
+}
+
+impl SideAccumulator {
+    fn add(&mut self, trade: &TradeWithDerived) {
+        self.performance.add(trade);
+        self.trade_count += 1;
+        self.fees += trade.trade.fees;
+        self.carrying_costs += trade.trade.carrying_costs;
+        self.notional += trade.trade.entry_price
+            * trade.trade.quantity.unwrap_or(0.0)
+            * trade.trade.value_multiplier();
+    }
+
+    fn into_summary(self, key: &str) -> SideCostSummary {
+        let costs = self.fees + self.carrying_costs;
+        SideCostSummary {
+            performance: self.performance.into_bucket(key.to_string()),
+            total_fees: self.fees,
+            total_carrying_costs: self.carrying_costs,
+            cost_per_trade: (self.trade_count > 0).then(|| costs / self.trade_count as f64),
+            cost_pct_of_notional: (self.notional > 0.0).then(|| costs / self.notional * 100.0),
+        }
+    }
+}
+
+/// Compare costs of short and long closed trades, and break short trades down by days held
+/// and borrow availability. Days held run from the trade date to the last exit date.
+pub fn calculate_short_side_report(
+    trades: &[TradeWithDerived],
+    exit_dates: &HashMap<String, NaiveDate>,
+) -> ShortSideReport {
+    let mut long = SideAccumulator::default();
+    let mut short = SideAccumulator::default();
+    let mut holding: Vec<(i32, f64, f64)> = vec![(0, 0.0, 0.0); SHORT_HOLDING_DAYS.len()];
+    let mut by_availability: BTreeMap<String, PerformanceAccumulator> = BTreeMap::new();
+
+    for trade in trades.iter().filter(|t| t.net_pnl.is_some()) {
+        if trade.trade.direction == Direction::Long {
+            long.add(trade);
+            continue;
+        }
+        short.add(trade);
+
+        let exit_date = exit_dates.get(&trade.trade.id).copied().unwrap_or(trade.trade.trade_date);
+        let days = (exit_date - trade.trade.trade_date).num_days().max(0);
+        if let Some(i) = SHORT_HOLDING_DAYS
+            .iter()
+            .position(|(_, min, max)| days >= *min && max.is_none_or(|max| days <= max))
+        {
+            holding[i].0 += 1;
+            holding[i].1 += trade.net_pnl.unwrap_or(0.0);
+            holding[i].2 += trade.trade.carrying_costs;
+        }
+
+        let key = trade.trade.borrow_availability.map_or("unknown", |a| a.as_str());
+        by_availability.entry(key.to_string()).or_default().add(trade);
+    }
+
+    let long = long.into_summary("long");
+    let short = short.into_summary("short");
+    let short_cost_premium_pct = short
+        .cost_pct_of_notional
+        .zip(long.cost_pct_of_notional)
+        .map(|(short_pct, long_pct)| short_pct - long_pct);
+
+    ShortSideReport {
+        long,
+        short,
+        short_cost_premium_pct,
+        short_holding_days: SHORT_HOLDING_DAYS
+            .iter()
+            .zip(holding)
+            .map(|((label, min_days, max_days), (trade_count, net_pnl, carrying_costs))| HoldingDaysBucket {
+                label: label.to_string(),
+                min_days: *min_days,
+                max_days: *max_days,
+                trade_count,
+                net_pnl,
+                carrying_costs,
+            })
+            .collect(),
+        by_borrow_availability: by_availability
+            .into_iter()
+            .map(|(key, acc)| acc.into_bucket(key))
+            .collect(),
+    }
+}
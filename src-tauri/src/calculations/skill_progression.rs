@@ -0,0 +1,187 @@
+use std::collections::{HashMap, HashSet};
+use crate::calculations::{calculate_checklist_report, calculate_trade_r, sort_chronologically, was_stop_widened};
+use crate::models::{ChecklistCheck, SkillComparison, SkillProgressionReport, SkillSample, StopAdjustment, TradeResult, TradeWithDerived};
+
+/// p-value below which a change counts as significant
+const SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+/// Compare the first sample_size closed trades with the most recent sample_size
+/// Samples shrink to half the closed trades when there are fewer than twice sample_size, so they
+/// never overlap. R-multiples fall back to the default risk like the R expectancy report.
+/// Win rates are compared with a two-proportion z-test and averages with Welch's test using the
+/// normal distribution, which is reliable from about 30 trades per sample.
+pub fn calculate_skill_progression(
+    trades: &[TradeWithDerived],
+    sample_size: usize,
+    default_risk: Option<f64>,
+    adjustments: &HashMap<String, Vec<StopAdjustment>>,
+    checks: &[ChecklistCheck],
+) -> SkillProgressionReport {
+    let mut closed: Vec<&TradeWithDerived> = trades.iter().filter(|t| t.net_pnl.is_some()).collect();
+    sort_chronologically(&mut closed);
+
+    let entered_early: HashSet<String> = calculate_checklist_report(trades, checks)
+        .entered_before_confirmation_trade_ids
+        .into_iter()
+        .collect();
+    let mistakes = |trade: &TradeWithDerived| {
+        let stop_widened = trade.trade.stop_loss_price.is_some_and(|stop| {
+            was_stop_widened(trade.trade.direction, stop, adjustments.get(&trade.trade.id))
+        });
+        [trade.trade.stop_loss_price.is_none(), stop_widened, entered_early.contains(&trade.trade.id)]
+            .iter()
+            .filter(|m| **m)
+            .count() as f64
+    };
+
+    let sample_size = sample_size.min(closed.len() / 2);
+    let earliest = &closed[..sample_size];
+    let recent = &closed[closed.len() - sample_size..];
+
+    let r_values = |sample: &[&TradeWithDerived]| -> Vec<f64> {
+        sample.iter().filter_map(|t| calculate_trade_r(t, default_risk).map(|(r, _)| r)).collect()
+    };
+    let mistake_counts = |sample: &[&TradeWithDerived]| -> Vec<f64> { sample.iter().map(|t| mistakes(t)).collect() };
+    let (earliest_r, recent_r) = (r_values(earliest), r_values(recent));
+    let (earliest_mistakes, recent_mistakes) = (mistake_counts(earliest), mistake_counts(recent));
+
+    SkillProgressionReport {
+        sample_size: sample_size as i32,
+        earliest: skill_sample(earliest, &earliest_r, &earliest_mistakes),
+        recent: skill_sample(recent, &recent_r, &recent_mistakes),
+        win_rate: compare_win_rates(earliest, recent),
+        avg_r: compare_means(&earliest_r, &recent_r),
+        mistakes_per_trade: compare_means(&earliest_mistakes, &recent_mistakes),
+    }
+}
+
+fn skill_sample(trades: &[&TradeWithDerived], r_values: &[f64], mistakes: &[f64]) -> SkillSample {
+    let (wins, losses) = win_loss_counts(trades);
+    SkillSample {
+        trade_count: trades.len() as i32,
+        first_date: trades.first().map(|t| t.trade.trade_date),
+        last_date: trades.last().map(|t| t.trade.trade_date),
+        win_rate: (wins + losses > 0).then(|| wins as f64 / (wins + losses) as f64),
+        avg_r: mean(r_values),
+        r_trade_count: r_values.len() as i32,
+        mistakes_per_trade: mean(mistakes),
+    }
+}
+
+fn win_loss_counts(trades: &[&TradeWithDerived]) -> (usize, usize) {
+    let count = |result| trades.iter().filter(|t| t.result == Some(result)).count();
+    (count(TradeResult::Win), count(TradeResult::Loss))
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Sample variance, dividing by n - 1
+fn variance(values: &[f64]) -> Option<f64> {
+    let mean = mean(values)?;
+    (values.len() > 1).then(|| values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64)
+}
+
+/// Two-proportion z-test of the win rates, excluding breakeven trades
+fn compare_win_rates(earliest: &[&TradeWithDerived], recent: &[&TradeWithDerived]) -> Option<SkillComparison> {
+    let (earliest_wins, earliest_losses) = win_loss_counts(earliest);
+    let (recent_wins, recent_losses) = win_loss_counts(recent);
+    let (n1, n2) = ((earliest_wins + earliest_losses) as f64, (recent_wins + recent_losses) as f64);
+    if n1 == 0.0 || n2 == 0.0 {
+        return None;
+    }
+
+    let (p1, p2) = (earliest_wins as f64 / n1, recent_wins as f64 / n2);
+    let pooled = (earliest_wins + recent_wins) as f64 / (n1 + n2);
+    let standard_error = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+    comparison(p2 - p1, standard_error)
+}
+
+/// Welch's test of two means, with the normal distribution
+fn compare_means(earliest: &[f64], recent: &[f64]) -> Option<SkillComparison> {
+    let standard_error = (variance(earliest)? / earliest.len() as f64 + variance(recent)? / recent.len() as f64).sqrt();
+    comparison(mean(recent)? - mean(earliest)?, standard_error)
+}
+
+fn comparison(difference: f64, standard_error: f64) -> Option<SkillComparison> {
+    if standard_error <= 0.0 {
+        return None;
+    }
+    let z_score = difference / standard_error;
+    let p_value = 2.0 * (1.0 - normal_cdf(z_score.abs()));
+    Some(SkillComparison {
+        difference,
+        z_score,
+        p_value,
+        significant: p_value < SIGNIFICANCE_LEVEL,
+    })
+}
+
+/// Standard normal CDF, from the Abramowitz and Stegun approximation of erf (error below 1.5e-7)
+fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 { 0.5 * (1.0 + erf) } else { 0.5 * (1.0 - erf) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+    use crate::models::CreateTradeInput;
+    use crate::test_utils::{create_test_trade, create_test_trade_input};
+
+    fn closed_trade(day: i64, net_pnl: f64, stop_loss_price: Option<f64>) -> TradeWithDerived {
+        let input = CreateTradeInput {
+            trade_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() + Duration::days(day),
+            entry_price: 100.0,
+            exit_price: Some(100.0 + net_pnl / 100.0),
+            stop_loss_price,
+            entry_time: None,
+            exit_time: None,
+            fees: Some(0.0),
+            ..create_test_trade_input("a1", "AAPL")
+        };
+        create_test_trade(&format!("t{}", day), input)
+    }
+
+    #[test]
+    fn test_skill_progression_compares_first_and_last_trades() {
+        // 40 early trades: 1 win in 4 and no stops; 40 recent trades: 3 wins in 4 with 1R stops
+        let mut trades = Vec::new();
+        for day in 0..40 {
+            trades.push(closed_trade(day, if day % 4 == 0 { 100.0 } else { -100.0 }, None));
+        }
+        for day in 100..140 {
+            trades.push(closed_trade(day, if day % 4 == 0 { -100.0 } else { 100.0 }, Some(99.0)));
+        }
+
+        let report = calculate_skill_progression(&trades, 50, Some(100.0), &HashMap::new(), &[]);
+
+        assert_eq!(report.sample_size, 40);
+        assert_eq!(report.earliest.last_date, NaiveDate::from_ymd_opt(2024, 2, 9));
+        assert_eq!(report.earliest.win_rate, Some(0.25));
+        assert_eq!(report.recent.win_rate, Some(0.75));
+        assert_eq!(report.earliest.avg_r, Some(-0.5));
+        assert_eq!(report.recent.avg_r, Some(0.5));
+        assert_eq!(report.earliest.mistakes_per_trade, Some(1.0));
+        assert_eq!(report.recent.mistakes_per_trade, Some(0.0));
+
+        let win_rate = report.win_rate.unwrap();
+        assert!((win_rate.difference - 0.5).abs() < 1e-9);
+        assert!(win_rate.significant);
+        assert!(report.avg_r.unwrap().p_value < 0.001);
+        // Mistakes never vary within a sample, so there is no spread to test against
+        assert!(report.mistakes_per_trade.is_none());
+    }
+
+    #[test]
+    fn test_normal_cdf() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-4);
+        assert!((normal_cdf(-1.0) - 0.158655).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use crate::models::{CashTransaction, DailyPerformance, MetricSnapshot};
+
+/// Extend an account's snapshots by one per day through `end_date`
+/// Continues from the day after `previous`, or from the account's first day of closed trades when
+/// it has no snapshot yet. `days` are the account's daily performance rows; days without one carry
+/// the previous day's metrics. Balances count the cash transactions through each day.
+pub fn calculate_metric_snapshots(
+    account_id: &str,
+    previous: Option<&MetricSnapshot>,
+    days: &[DailyPerformance],
+    cash_transactions: &[CashTransaction],
+    starting_balance: Option<f64>,
+    end_date: NaiveDate,
+) -> Vec<MetricSnapshot> {
+    let start_date = match previous {
+        Some(previous) => previous.date.succ_opt(),
+        None => days.iter().map(|d| d.date).min(),
+    };
+    let Some(start_date) = start_date else {
+        return Vec::new();
+    };
+
+    let days_by_date: HashMap<NaiveDate, &DailyPerformance> = days.iter().map(|d| (d.date, d)).collect();
+    let mut deposits: f64 = cash_transactions
+        .iter()
+        .filter(|t| t.transaction_date < start_date)
+        .map(|t| t.amount)
+        .sum();
+    let mut deposits_by_date: HashMap<NaiveDate, f64> = HashMap::new();
+    for transaction in cash_transactions.iter().filter(|t| t.transaction_date >= start_date) {
+        *deposits_by_date.entry(transaction.transaction_date).or_insert(0.0) += transaction.amount;
+    }
+
+    let mut cumulative_pnl = previous.map_or(0.0, |p| p.cumulative_pnl);
+    let mut peak = previous.map_or(0.0, |p| p.cumulative_pnl + p.drawdown);
+    let mut max_drawdown = previous.map_or(0.0, |p| p.max_drawdown);
+    let mut trade_count = previous.map_or(0, |p| p.trade_count);
+    let mut win_count = previous.map_or(0, |p| p.win_count);
+    let mut loss_count = previous.map_or(0, |p| p.loss_count);
+
+    let mut snapshots = Vec::new();
+    for date in start_date.iter_days().take_while(|date| *date <= end_date) {
+        if let Some(day) = days_by_date.get(&date) {
+            cumulative_pnl += day.realized_net_pnl;
+            trade_count += day.trade_count;
+            win_count += day.win_count;
+            loss_count += day.loss_count;
+        }
+        deposits += deposits_by_date.get(&date).copied().unwrap_or(0.0);
+        peak = peak.max(cumulative_pnl);
+        let drawdown = peak - cumulative_pnl;
+        max_drawdown = max_drawdown.max(drawdown);
+
+        snapshots.push(MetricSnapshot {
+            account_id: account_id.to_string(),
+            date,
+            balance: starting_balance.map(|balance| balance + cumulative_pnl + deposits),
+            cumulative_pnl,
+            drawdown,
+            max_drawdown,
+            trade_count,
+            win_count,
+            loss_count,
+            win_rate: (win_count + loss_count > 0).then(|| win_count as f64 / (win_count + loss_count) as f64),
+        });
+    }
+    snapshots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    fn day(day: u32, pnl: f64, wins: i32, losses: i32) -> DailyPerformance {
+        DailyPerformance {
+            date: date(day),
+            realized_net_pnl: pnl,
+            gross_pnl: pnl,
+            total_fees: 0.0,
+            total_volume: 0.0,
+            largest_win: None,
+            largest_loss: None,
+            trade_count: wins + losses,
+            win_count: wins,
+            loss_count: losses,
+            breakeven_count: 0,
+            scratch_rate: 0.0,
+            avg_trade_pnl: pnl / (wins + losses) as f64,
+            unrealized_pnl_change: None,
+        }
+    }
+
+    #[test]
+    fn test_calculate_metric_snapshots() {
+        let days = vec![day(2, 500.0, 1, 0), day(3, -300.0, 0, 2), day(5, 100.0, 1, 0)];
+        let deposits = vec![CashTransaction {
+            id: "c1".to_string(),
+            account_id: "a1".to_string(),
+            transaction_date: date(4),
+            amount: 1000.0,
+            notes: None,
+            created_at: Utc::now(),
+        }];
+
+        let snapshots = calculate_metric_snapshots("a1", None, &days, &deposits, Some(10000.0), date(5));
+        assert_eq!(snapshots.len(), 4);
+        assert_eq!(snapshots[0].date, date(2));
+        assert_eq!(snapshots[1].drawdown, 300.0);
+        // A day without trades carries the metrics, and the deposit adds to the balance
+        assert_eq!(snapshots[2].cumulative_pnl, 200.0);
+        assert_eq!(snapshots[2].balance, Some(11200.0));
+        assert_eq!(snapshots[3].drawdown, 200.0);
+        assert_eq!(snapshots[3].max_drawdown, 300.0);
+        assert_eq!(snapshots[3].win_rate, Some(0.5));
+
+        // Extending from the last snapshot matches computing the whole history at once
+        let more = vec![day(6, 400.0, 1, 0)];
+        let extended = calculate_metric_snapshots("a1", snapshots.last(), &more, &deposits, Some(10000.0), date(7));
+        let mut all_days = days;
+        all_days.extend(more);
+        let at_once = calculate_metric_snapshots("a1", None, &all_days, &deposits, Some(10000.0), date(7));
+        assert_eq!(extended, at_once[4..]);
+        assert_eq!(extended[0].drawdown, 0.0);
+        assert_eq!(extended[1].balance, Some(11700.0));
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use crate::models::{Direction, StopAdjustment, StopAdjustmentReport, TradeWithDerived};
+
+/// Stop set by the latest adjustment made on or before a date
+/// Adjustments must be ordered oldest first. Returns None when the initial stop is still in force.
+pub fn adjusted_stop_as_of(adjustments: &[StopAdjustment], date: NaiveDate) -> Option<f64> {
+    adjustments
+        .iter()
+        .rfind(|a| a.adjusted_at.date_naive() <= date)
+        .map(|a| a.stop_price)
+}
+
+/// Per-share risk left between the entry and a stop
+/// Zero once a trailing stop has moved past the entry and locks in a profit.
+pub fn calculate_open_risk_per_share(direction: Direction, entry_price: f64, stop_price: f64) -> f64 {
+    match direction {
+        Direction::Long => (entry_price - stop_price).max(0.0),
+        Direction::Short => (stop_price - entry_price).max(0.0),
+    }
+}
+
+/// Whether moving a stop from one price to another gives the trade more room to lose
+pub fn is_stop_widened(direction: Direction, previous_stop: f64, new_stop: f64) -> bool {
+    match direction {
+        Direction::Long => new_stop < previous_stop,
+        Direction::Short => new_stop > previous_stop,
+    }
+}
+
+/// Whether any adjustment, ordered oldest first, moved the stop further from entry than the one before
+pub fn was_stop_widened(direction: Direction, initial_stop: f64, adjustments: Option<&Vec<StopAdjustment>>) -> bool {
+    let mut previous_stop = initial_stop;
+    let mut widened = false;
+    for adjustment in adjustments.into_iter().flatten() {
+        widened |= is_stop_widened(direction, previous_stop, adjustment.stop_price);
+        previous_stop = adjustment.stop_price;
+    }
+    widened
+}
+
+/// Compare closed trades whose stop was widened with trades whose stop never moved away from entry
+/// R-multiples use the initial stop, so a widened stop that is then hit shows up as a loss beyond -1R.
+pub fn calculate_stop_adjustment_report(
+    trades: &[TradeWithDerived],
+    adjustments: &HashMap<String, Vec<StopAdjustment>>,
+) -> StopAdjustmentReport {
+    let mut report = StopAdjustmentReport::default();
+    let mut widened_r_total = 0.0;
+    let mut kept_r_total = 0.0;
+
+    for trade in trades {
+        let (Some(r_multiple), Some(initial_stop)) = (trade.r_multiple, trade.trade.stop_loss_price) else {
+            continue;
+        };
+
+        let widened = was_stop_widened(trade.trade.direction, initial_stop, adjustments.get(&trade.trade.id));
+
+        let larger_loss = r_multiple < -1.0;
+        report.trade_count += 1;
+        if widened {
+            report.widened_count += 1;
+            report.widened_larger_loss_count += larger_loss as i32;
+            report.widened_trade_ids.push(trade.trade.id.clone());
+            widened_r_total += r_multiple;
+        } else {
+            report.kept_count += 1;
+            report.kept_larger_loss_count += larger_loss as i32;
+            kept_r_total += r_multiple;
+        }
+    }
+
+    if report.widened_count > 0 {
+        let count = report.widened_count as f64;
+        report.widened_larger_loss_rate = Some(report.widened_larger_loss_count as f64 / count);
+        report.widened_avg_r = Some(widened_r_total / count);
+    }
+    if report.kept_count > 0 {
+        let count = report.kept_count as f64;
+        report.kept_larger_loss_rate = Some(report.kept_larger_loss_count as f64 / count);
+        report.kept_avg_r = Some(kept_r_total / count);
+    }
+    report
+}
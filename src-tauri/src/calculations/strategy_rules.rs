@@ -0,0 +1,98 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use crate::models::{StrategyRule, TradeTraits, Watchlist};
+
+/// Minutes from entry to exit; None unless both times parse as HH:MM or HH:MM:SS
+pub fn calculate_hold_minutes(
+    entry_date: NaiveDate,
+    entry_time: Option<&str>,
+    exit_date: NaiveDate,
+    exit_time: Option<&str>,
+) -> Option<f64> {
+    let parse = |t: &str| {
+        NaiveTime::parse_from_str(t.trim(), "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(t.trim(), "%H:%M"))
+            .ok()
+    };
+    let entry = NaiveDateTime::new(entry_date, parse(entry_time?)?);
+    let exit = NaiveDateTime::new(exit_date, parse(exit_time?)?);
+
+    Some((exit - entry).num_seconds() as f64 / 60.0)
+}
+
+/// Strategy of the first rule, in priority order, whose conditions all match the trade
+/// Rules on hold time never match trades whose hold time is unknown.
+pub fn suggest_strategy(
+    rules: &[StrategyRule],
+    watchlists: &[Watchlist],
+    trade: &TradeTraits,
+) -> Option<String> {
+    rules
+        .iter()
+        .find(|rule| {
+            let hold_ok = match (rule.min_hold_minutes, rule.max_hold_minutes) {
+                (None, None) => true,
+                (min, max) => trade.hold_minutes.is_some_and(|held| {
+                    min.is_none_or(|m| held >= m) && max.is_none_or(|m| held < m)
+                }),
+            };
+            let watchlist_ok = rule.watchlist.as_deref().is_none_or(|name| {
+                watchlists
+                    .iter()
+                    .find(|w| w.name.eq_ignore_ascii_case(name))
+                    .is_some_and(|w| w.symbols.iter().any(|s| s.eq_ignore_ascii_case(&trade.symbol)))
+            });
+
+            hold_ok
+                && watchlist_ok
+                && rule.direction.is_none_or(|d| d == trade.direction)
+                && rule.asset_class.is_none_or(|a| a == trade.asset_class)
+        })
+        .map(|rule| rule.strategy.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::models::{AssetClass, Direction};
+
+    fn rule(strategy: &str, max_hold_minutes: Option<f64>, watchlist: Option<&str>) -> StrategyRule {
+        StrategyRule {
+            id: strategy.to_string(),
+            strategy: strategy.to_string(),
+            priority: 0,
+            min_hold_minutes: None,
+            max_hold_minutes,
+            watchlist: watchlist.map(str::to_string),
+            direction: None,
+            asset_class: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_first_matching_rule_suggests_strategy() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let rules = vec![
+            rule("Scalp", Some(5.0), Some("momo")),
+            rule("Swing", None, None),
+        ];
+        let watchlists = vec![Watchlist { name: "momo".to_string(), symbols: vec!["TSLA".to_string()] }];
+        let mut trade = TradeTraits {
+            symbol: "tsla".to_string(),
+            direction: Direction::Long,
+            asset_class: AssetClass::Stock,
+            hold_minutes: calculate_hold_minutes(date, Some("09:31"), date, Some("09:34:30")),
+        };
+        assert_eq!(trade.hold_minutes, Some(3.5));
+        assert_eq!(suggest_strategy(&rules, &watchlists, &trade), Some("Scalp".to_string()));
+
+        // Held too long, or not on the watchlist: falls through to the catch-all rule
+        trade.hold_minutes = Some(12.0);
+        assert_eq!(suggest_strategy(&rules, &watchlists, &trade), Some("Swing".to_string()));
+        trade.hold_minutes = None;
+        assert_eq!(suggest_strategy(&rules, &watchlists, &trade), Some("Swing".to_string()));
+
+        assert_eq!(suggest_strategy(&rules[..1], &watchlists, &trade), None);
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use crate::models::{Direction, ExecutionFill, TimelineEvent};
+
+/// Build the timeline of a day's executions with running position and realized PnL per symbol
+/// Fills must be in time order and may start before the day so carried-in positions are counted.
+/// Exits realize PnL against their trade's average entry price; every fill's fees count against PnL.
+pub fn calculate_day_timeline(fills: &[ExecutionFill], date: NaiveDate) -> Vec<TimelineEvent> {
+    let mut positions: HashMap<&str, f64> = HashMap::new();
+    let mut symbol_pnl: HashMap<&str, f64> = HashMap::new();
+    let mut day_pnl = 0.0;
+    let mut events = Vec::new();
+
+    for fill in fills.iter().filter(|f| f.execution_date <= date) {
+        let is_entry = fill.execution_type == "entry";
+        let buys = matches!(
+            (fill.direction, is_entry),
+            (Direction::Long, true) | (Direction::Short, false)
+        );
+        let position = positions.entry(fill.symbol.as_str()).or_default();
+        *position += if buys { fill.quantity } else { -fill.quantity };
+
+        if fill.execution_date < date {
+            continue;
+        }
+
+        let gross = if is_entry {
+            0.0
+        } else {
+            let per_unit = match fill.direction {
+                Direction::Long => fill.price - fill.trade_entry_price,
+                Direction::Short => fill.trade_entry_price - fill.price,
+            };
+            per_unit * fill.quantity * fill.value_multiplier()
+        };
+        let realized_pnl = gross - fill.fees;
+
+        let running = symbol_pnl.entry(fill.symbol.as_str()).or_default();
+        *running += realized_pnl;
+        day_pnl += realized_pnl;
+
+        events.push(TimelineEvent {
+            trade_id: fill.trade_id.clone(),
+            symbol: fill.symbol.clone(),
+            direction: fill.direction,
+            execution_type: fill.execution_type.clone(),
+            execution_time: fill.execution_time.clone(),
+            quantity: fill.quantity,
+            price: fill.price,
+            fees: fill.fees,
+            position: *position,
+            realized_pnl,
+            symbol_pnl: *running,
+            day_pnl,
+        });
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AssetClass;
+
+    fn fill(symbol: &str, direction: Direction, execution_type: &str, day: u32, time: &str, qty: f64, price: f64) -> ExecutionFill {
+        ExecutionFill {
+            trade_id: format!("{}-{}", symbol, direction.as_str()),
+            account_id: "acc1".to_string(),
+            symbol: symbol.to_string(),
+            asset_class: AssetClass::Stock,
+            direction,
+            trade_entry_price: 100.0,
+            contract_multiplier: None,
+            fx_rate: None,
+            tick_size: None,
+            execution_type: execution_type.to_string(),
+            execution_date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            execution_time: Some(time.to_string()),
+            quantity: qty,
+            price,
+            fees: 1.0,
+            exchange: None,
+            intended_price: None,
+            order_type: None,
+        }
+    }
+
+    #[test]
+    fn test_day_timeline_tracks_carried_position_and_running_pnl() {
+        let fills = vec![
+            // Carried in from the previous day
+            fill("AAPL", Direction::Long, "entry", 1, "15:00:00", 100.0, 100.0),
+            fill("AAPL", Direction::Long, "exit", 2, "09:31:00", 60.0, 102.0),
+            fill("TSLA", Direction::Short, "entry", 2, "10:00:00", 50.0, 100.0),
+            fill("TSLA", Direction::Short, "exit", 2, "10:30:00", 50.0, 98.0),
+            fill("AAPL", Direction::Long, "exit", 2, "11:00:00", 40.0, 99.0),
+            fill("AAPL", Direction::Long, "entry", 3, "09:30:00", 10.0, 100.0),
+        ];
+
+        let events = calculate_day_timeline(&fills, NaiveDate::from_ymd_opt(2024, 1, 2).unwrap());
+        assert_eq!(events.len(), 4);
+
+        assert_eq!(events[0].position, 40.0);
+        assert!((events[0].realized_pnl - 119.0).abs() < 0.01);
+
+        assert_eq!(events[1].position, -50.0);
+        assert!((events[1].day_pnl - 118.0).abs() < 0.01);
+
+        assert_eq!(events[2].position, 0.0);
+        assert!((events[2].symbol_pnl - 98.0).abs() < 0.01);
+
+        // 40 * -1 - 1 fee
+        assert_eq!(events[3].position, 0.0);
+        assert!((events[3].symbol_pnl - 78.0).abs() < 0.01);
+        assert!((events[3].day_pnl - 176.0).abs() < 0.01);
+    }
+}
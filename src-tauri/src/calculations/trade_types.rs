@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+use crate::calculations::{calculate_hold_minutes, PerformanceAccumulator};
+use crate::models::{PerformanceBucket, Status, Trade, TradeType, TradeTypeThresholds, TradeWithDerived};
+
+/// Classify a closed trade by how long it was held; open trades have no type yet
+/// Trades held overnight, ending on their last exit execution's date, are swings or positions by
+/// calendar days held. Same-day trades are day trades unless their entry and exit times show a scalp.
+pub fn calculate_trade_type(trade: &Trade, thresholds: &TradeTypeThresholds) -> Option<TradeType> {
+    if trade.status != Status::Closed {
+        return None;
+    }
+
+    let exit_date = trade.exit_date.unwrap_or(trade.trade_date);
+    let days_held = (exit_date - trade.trade_date).num_days();
+    if days_held > 0 {
+        return Some(if days_held <= thresholds.swing_max_days { TradeType::Swing } else { TradeType::Position });
+    }
+
+    let hold_minutes = calculate_hold_minutes(
+        trade.trade_date,
+        trade.entry_time.as_deref(),
+        trade.trade_date,
+        trade.exit_time.as_deref(),
+    );
+    match hold_minutes {
+        Some(minutes) if minutes <= thresholds.scalp_max_minutes => Some(TradeType::Scalp),
+        _ => Some(TradeType::Day),
+    }
+}
+
+/// Performance of closed trades per trade type, from scalps to positions
+/// Types without trades are left out.
+pub fn calculate_trade_type_report(trades: &[TradeWithDerived]) -> Vec<PerformanceBucket> {
+    let mut by_type: BTreeMap<TradeType, PerformanceAccumulator> = BTreeMap::new();
+    for trade in trades.iter().filter(|t| t.net_pnl.is_some()) {
+        if let Some(trade_type) = trade.trade_type {
+            by_type.entry(trade_type).or_default().add(trade);
+        }
+    }
+
+    by_type
+        .into_iter()
+        .map(|(trade_type, acc)| acc.into_bucket(trade_type.as_str().to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+    use crate::models::{AssetClass, Direction};
+
+    fn closed_trade(exit_day: Option<u32>, entry_time: Option<&str>, exit_time: Option<&str>) -> Trade {
+        Trade {
+            id: "t1".to_string(),
+            user_id: "u1".to_string(),
+            account_id: "a1".to_string(),
+            instrument_id: "i1".to_string(),
+            symbol: "AAPL".to_string(),
+            asset_class: AssetClass::Stock,
+            contract_multiplier: None,
+            fx_rate: None,
+            trade_number: None,
+            ref_code: None,
+            trade_date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            direction: Direction::Long,
+            quantity: Some(100.0),
+            entry_price: 100.0,
+            exit_price: Some(101.0),
+            stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
+            max_adverse_price: None,
+            entry_atr: None,
+            entry_time: entry_time.map(str::to_string),
+            exit_time: exit_time.map(str::to_string),
+            exit_date: exit_day.map(|d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap()),
+            fees: 0.0,
+            carrying_costs: 0.0,
+            dividends: 0.0,
+            strategy: None,
+            notes: None,
+            screenshot_url: None,
+            status: Status::Closed,
+            result_override: None,
+            roll_type: None,
+            rolled_on: None,
+            continuation_trade_id: None,
+            option_outcome: None,
+            borrow_availability: None,
+            borrow_note: None,
+            is_locked: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_calculate_trade_type() {
+        let thresholds = TradeTypeThresholds {
+            scalp_max_minutes: 5.0,
+            swing_max_days: 10,
+        };
+        let classify = |trade: &Trade| calculate_trade_type(trade, &thresholds);
+
+        assert_eq!(classify(&closed_trade(None, Some("09:31"), Some("09:34:30"))), Some(TradeType::Scalp));
+        assert_eq!(classify(&closed_trade(Some(15), Some("09:31"), Some("11:00"))), Some(TradeType::Day));
+        // Without both times a same-day trade cannot be told apart from a day trade
+        assert_eq!(classify(&closed_trade(None, None, None)), Some(TradeType::Day));
+        assert_eq!(classify(&closed_trade(Some(16), Some("15:59"), Some("09:30"))), Some(TradeType::Swing));
+        assert_eq!(classify(&closed_trade(Some(25), None, None)), Some(TradeType::Swing));
+        assert_eq!(classify(&closed_trade(Some(26), None, None)), Some(TradeType::Position));
+
+        let mut open = closed_trade(None, None, None);
+        open.status = Status::Open;
+        assert_eq!(classify(&open), None);
+    }
+}
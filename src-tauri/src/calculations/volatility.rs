@@ -0,0 +1,40 @@
+/// Average true range over (high, low, close) bars, oldest first
+/// The first bar only supplies a previous close, so `period` true ranges need `period + 1` bars;
+/// returns None with fewer. Uses the most recent bars when more are given.
+pub fn calculate_atr(bars: &[(f64, f64, f64)], period: usize) -> Option<f64> {
+    if period == 0 || bars.len() < period + 1 {
+        return None;
+    }
+
+    let total: f64 = bars[bars.len() - period - 1..]
+        .windows(2)
+        .map(|pair| {
+            let (_, _, previous_close) = pair[0];
+            let (high, low, _) = pair[1];
+            (high - low)
+                .max((high - previous_close).abs())
+                .max((low - previous_close).abs())
+        })
+        .sum();
+
+    Some(total / period as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atr_uses_gaps_from_previous_close() {
+        let bars = [
+            (101.0, 99.0, 100.0),
+            (102.0, 100.0, 101.0),  // Range 2
+            (108.0, 105.0, 107.0),  // Gap up: 108 - 101 = 7
+            (106.0, 103.0, 104.0),  // Gap down: 107 - 103 = 4
+        ];
+
+        assert_eq!(calculate_atr(&bars, 3), Some(13.0 / 3.0));
+        assert_eq!(calculate_atr(&bars, 2), Some(5.5));
+        assert_eq!(calculate_atr(&bars, 4), None);
+    }
+}
@@ -1,6 +1,13 @@
+use std::fs;
+use chrono::NaiveDate;
 use tauri::State;
-use crate::models::Account;
+use crate::models::{
+    Account, AccountPerformance, AccountReturns, CashTransaction, CommissionSchedule, CreateCashTransactionInput,
+    CreateFinancingChargeInput, CreateSeedPositionInput,
+    FinancingCharge, FinancingImportResult, PeriodLock, SeedPosition,
+};
 use crate::repository::AccountRepository;
+use crate::services::{AccountService, MetricsService};
 use crate::AppState;
 
 #[tauri::command]
@@ -17,13 +24,240 @@ pub async fn create_account(
     state: State<'_, AppState>,
     name: String,
     base_currency: Option<String>,
+    is_paper: Option<bool>,
 ) -> Result<Account, String> {
-    AccountRepository::create(
+    state.writes.run(AccountRepository::create_with_paper_flag(
         &state.pool,
         &state.user_id,
         &name,
         base_currency.as_deref(),
-    )
+        is_paper.unwrap_or(false),
+    ))
     .await
     .map_err(|e| format!("Failed to create account: {}", e))
 }
+
+#[tauri::command]
+pub async fn set_account_paper(
+    state: State<'_, AppState>,
+    id: String,
+    is_paper: bool,
+) -> Result<Account, String> {
+    state.writes.run(AccountRepository::set_paper(&state.pool, &state.user_id, &id, is_paper))
+        .await
+        .map_err(|e| format!("Failed to update account: {}", e))?
+        .ok_or_else(|| format!("Account not found: {}", id))
+}
+
+#[tauri::command]
+pub async fn set_account_starting_balance(
+    state: State<'_, AppState>,
+    id: String,
+    starting_balance: Option<f64>,
+) -> Result<Account, String> {
+    if starting_balance.is_some_and(|b| b <= 0.0 || !b.is_finite()) {
+        return Err("Starting balance must be positive".to_string());
+    }
+
+    state.writes.run(AccountService::set_starting_balance(&state.pool, &state.user_id, &id, starting_balance)).await
+}
+
+#[tauri::command]
+pub async fn lock_period(
+    state: State<'_, AppState>,
+    account_id: String,
+    through_date: String,
+) -> Result<PeriodLock, String> {
+    let through = NaiveDate::parse_from_str(&through_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid through date: {}", e))?;
+
+    state.writes.run(AccountService::lock_period(&state.pool, &state.user_id, &account_id, through)).await
+}
+
+#[tauri::command]
+pub async fn unlock_period(
+    state: State<'_, AppState>,
+    account_id: String,
+) -> Result<(), String> {
+    state.writes.run(AccountService::unlock_period(&state.pool, &state.user_id, &account_id)).await
+}
+
+#[tauri::command]
+pub async fn get_period_locks(
+    state: State<'_, AppState>,
+) -> Result<Vec<PeriodLock>, String> {
+    AccountService::get_period_locks(&state.pool, &state.user_id).await
+}
+
+#[tauri::command]
+pub async fn get_commission_schedule(
+    state: State<'_, AppState>,
+    account_id: String,
+) -> Result<CommissionSchedule, String> {
+    AccountService::get_commission_schedule(&state.pool, &state.user_id, &account_id).await
+}
+
+#[tauri::command]
+pub async fn save_commission_schedule(
+    state: State<'_, AppState>,
+    schedule: CommissionSchedule,
+) -> Result<CommissionSchedule, String> {
+    state.writes.run(AccountService::save_commission_schedule(&state.pool, &state.user_id, schedule)).await
+}
+
+#[tauri::command]
+pub async fn add_seed_position(
+    state: State<'_, AppState>,
+    input: CreateSeedPositionInput,
+) -> Result<SeedPosition, String> {
+    state.writes.run(AccountService::add_seed_position(&state.pool, &state.user_id, input)).await
+}
+
+#[tauri::command]
+pub async fn get_seed_positions(
+    state: State<'_, AppState>,
+    account_id: Option<String>,
+) -> Result<Vec<SeedPosition>, String> {
+    AccountService::get_seed_positions(&state.pool, &state.user_id, account_id.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn delete_seed_position(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    state.writes.run(AccountService::delete_seed_position(&state.pool, &state.user_id, &id)).await
+}
+
+#[tauri::command]
+pub async fn add_financing_charge(
+    state: State<'_, AppState>,
+    input: CreateFinancingChargeInput,
+) -> Result<FinancingCharge, String> {
+    state.writes.run(AccountService::add_financing_charge(&state.pool, &state.user_id, input)).await
+}
+
+#[tauri::command]
+pub async fn get_financing_charges(
+    state: State<'_, AppState>,
+    account_id: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<Vec<FinancingCharge>, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    AccountService::get_financing_charges(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn delete_financing_charge(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    state.writes.run(AccountService::delete_financing_charge(&state.pool, &state.user_id, &id)).await
+}
+
+/// Import an account's financing charges from a CSV file (month,type,amount,notes)
+#[tauri::command]
+pub async fn import_financing_charges(
+    state: State<'_, AppState>,
+    account_id: String,
+    file_path: String,
+) -> Result<FinancingImportResult, String> {
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    state.writes.run(AccountService::import_financing_csv(&state.pool, &state.user_id, &account_id, &content)).await
+}
+
+#[tauri::command]
+pub async fn get_account_performance(
+    state: State<'_, AppState>,
+    account_id: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<AccountPerformance, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_account_performance(
+        &state.pool,
+        &state.user_id,
+        &account_id,
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn add_cash_transaction(
+    state: State<'_, AppState>,
+    input: CreateCashTransactionInput,
+) -> Result<CashTransaction, String> {
+    state.writes.run(AccountService::add_cash_transaction(&state.pool, &state.user_id, input)).await
+}
+
+#[tauri::command]
+pub async fn get_cash_transactions(
+    state: State<'_, AppState>,
+    account_id: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<Vec<CashTransaction>, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    AccountService::get_cash_transactions(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn delete_cash_transaction(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    state.writes.run(AccountService::delete_cash_transaction(&state.pool, &state.user_id, &id)).await
+}
+
+#[tauri::command]
+pub async fn get_account_returns(
+    state: State<'_, AppState>,
+    account_id: String,
+    start_date: String,
+    end_date: String,
+) -> Result<AccountReturns, String> {
+    let start = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid start date: {}", e))?;
+    let end = NaiveDate::parse_from_str(&end_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid end date: {}", e))?;
+
+    MetricsService::get_account_returns(
+        &state.pool,
+        &state.user_id,
+        &account_id,
+        start,
+        end,
+    )
+    .await
+}
@@ -0,0 +1,44 @@
+use chrono::Utc;
+use tauri::State;
+use crate::models::{ArchiveResult, ArchivedYear, TradeWithDerived};
+use crate::services::ArchiveService;
+use crate::AppState;
+
+/// Archive a year's closed trades; `compact` also shrinks the main database file
+#[tauri::command]
+pub async fn archive_trade_year(
+    state: State<'_, AppState>,
+    year: i32,
+    compact: Option<bool>,
+) -> Result<u64, String> {
+    state.writes.run(ArchiveService::archive_year(&state.pool, &state.user_id, year, compact.unwrap_or(false))).await
+}
+
+#[tauri::command]
+pub async fn unarchive_trade_year(state: State<'_, AppState>, year: i32) -> Result<u64, String> {
+    state.writes.run(ArchiveService::unarchive_year(&state.pool, &state.user_id, year)).await
+}
+
+/// Archive every year that ended more than `years` years ago; `compact` also shrinks the main database file
+#[tauri::command]
+pub async fn archive_trades_older_than(
+    state: State<'_, AppState>,
+    years: i32,
+    compact: Option<bool>,
+) -> Result<ArchiveResult, String> {
+    let today = Utc::now().date_naive();
+    state.writes.run(ArchiveService::archive_older_than(&state.pool, &state.user_id, years, today, compact.unwrap_or(false))).await
+}
+
+#[tauri::command]
+pub async fn get_archived_years(state: State<'_, AppState>) -> Result<Vec<ArchivedYear>, String> {
+    ArchiveService::get_archived_years(&state.pool, &state.user_id).await
+}
+
+#[tauri::command]
+pub async fn get_archived_trades(
+    state: State<'_, AppState>,
+    year: i32,
+) -> Result<Vec<TradeWithDerived>, String> {
+    ArchiveService::get_archived_trades(&state.pool, &state.user_id, year).await
+}
@@ -0,0 +1,51 @@
+use chrono::NaiveDate;
+use tauri::State;
+use crate::models::{CarryingCost, CarryingCostReport, CreateCarryingCostInput};
+use crate::services::CarryingCostService;
+use crate::AppState;
+
+#[tauri::command]
+pub async fn add_carrying_cost(
+    state: State<'_, AppState>,
+    input: CreateCarryingCostInput,
+) -> Result<CarryingCost, String> {
+    state.writes.run(CarryingCostService::add_cost(&state.pool, &state.user_id, input)).await
+}
+
+#[tauri::command]
+pub async fn get_carrying_costs(
+    state: State<'_, AppState>,
+    trade_id: String,
+) -> Result<Vec<CarryingCost>, String> {
+    CarryingCostService::get_trade_costs(&state.pool, &trade_id).await
+}
+
+#[tauri::command]
+pub async fn delete_carrying_cost(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    state.writes.run(CarryingCostService::delete_cost(&state.pool, &state.user_id, &id)).await
+}
+
+#[tauri::command]
+pub async fn get_carrying_cost_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<CarryingCostReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    CarryingCostService::get_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
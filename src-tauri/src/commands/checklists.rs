@@ -0,0 +1,51 @@
+use chrono::NaiveDate;
+use tauri::State;
+use crate::models::{CheckChecklistItemInput, ChecklistCheck, ChecklistReport};
+use crate::services::ChecklistService;
+use crate::AppState;
+
+#[tauri::command]
+pub async fn check_checklist_item(
+    state: State<'_, AppState>,
+    input: CheckChecklistItemInput,
+) -> Result<ChecklistCheck, String> {
+    state.writes.run(ChecklistService::check_item(&state.pool, &state.user_id, input)).await
+}
+
+#[tauri::command]
+pub async fn get_trade_checklist(
+    state: State<'_, AppState>,
+    trade_id: String,
+) -> Result<Vec<ChecklistCheck>, String> {
+    ChecklistService::get_trade_checklist(&state.pool, &trade_id).await
+}
+
+#[tauri::command]
+pub async fn uncheck_checklist_item(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    state.writes.run(ChecklistService::uncheck_item(&state.pool, &id)).await
+}
+
+#[tauri::command]
+pub async fn get_checklist_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<ChecklistReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    ChecklistService::get_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
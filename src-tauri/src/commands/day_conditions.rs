@@ -0,0 +1,53 @@
+use chrono::NaiveDate;
+use tauri::State;
+use crate::models::{DayConditions, PerformanceBucket};
+use crate::services::DayConditionService;
+use crate::AppState;
+
+#[tauri::command]
+pub async fn set_day_conditions(
+    state: State<'_, AppState>,
+    date: String,
+    conditions: Vec<String>,
+) -> Result<DayConditions, String> {
+    let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date: {}", e))?;
+
+    state.writes.run(DayConditionService::set_conditions(&state.pool, &state.user_id, date, conditions)).await
+}
+
+#[tauri::command]
+pub async fn get_day_conditions(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<Vec<DayConditions>, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    DayConditionService::get_conditions(&state.pool, &state.user_id, start, end).await
+}
+
+#[tauri::command]
+pub async fn get_day_condition_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<Vec<PerformanceBucket>, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    DayConditionService::get_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
@@ -0,0 +1,73 @@
+use chrono::NaiveDate;
+use tauri::State;
+use crate::models::{CreateDividendInput, Dividend, DividendIncomeReport};
+use crate::services::DividendService;
+use crate::AppState;
+
+#[tauri::command]
+pub async fn add_dividend(
+    state: State<'_, AppState>,
+    input: CreateDividendInput,
+) -> Result<Dividend, String> {
+    state.writes.run(DividendService::add_dividend(&state.pool, &state.user_id, input)).await
+}
+
+#[tauri::command]
+pub async fn get_dividends(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<Vec<Dividend>, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    DividendService::get_dividends(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_trade_dividends(
+    state: State<'_, AppState>,
+    trade_id: String,
+) -> Result<Vec<Dividend>, String> {
+    DividendService::get_trade_dividends(&state.pool, &state.user_id, &trade_id).await
+}
+
+#[tauri::command]
+pub async fn delete_dividend(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    state.writes.run(DividendService::delete_dividend(&state.pool, &state.user_id, &id)).await
+}
+
+#[tauri::command]
+pub async fn get_dividend_income_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<DividendIncomeReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    DividendService::get_income_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
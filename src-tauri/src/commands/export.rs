@@ -0,0 +1,117 @@
+use chrono::NaiveDate;
+use tauri::State;
+use tauri_plugin_dialog::DialogExt;
+
+use crate::models::{AnonymizedExport, SymbolMonthMatrix};
+use crate::services::ExportService;
+use crate::AppState;
+
+/// Open a save dialog to choose where to write a JSON export
+#[tauri::command]
+pub async fn select_export_file(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    save_file_dialog(&app, "JSON Files", "json", "trades-shared.json")
+}
+
+/// Open a save dialog to choose where to write a CSV export
+#[tauri::command]
+pub async fn select_csv_export_file(app: tauri::AppHandle, file_name: String) -> Result<Option<String>, String> {
+    save_file_dialog(&app, "CSV Files", "csv", &file_name)
+}
+
+/// Open a save dialog to choose where to write a calendar export
+#[tauri::command]
+pub async fn select_calendar_export_file(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    save_file_dialog(&app, "Calendar Files", "ics", "trading-days.ics")
+}
+
+/// Export trades for sharing, without account names, notes or real position sizes
+#[tauri::command]
+pub async fn export_anonymized_trades(
+    state: State<'_, AppState>,
+    file_path: Option<String>,
+    account_id: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<AnonymizedExport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    ExportService::export_anonymized(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+        file_path.as_deref(),
+    )
+    .await
+}
+
+/// Export net PnL and trade count by symbol and month to a CSV file
+#[tauri::command]
+pub async fn export_symbol_month_matrix(
+    state: State<'_, AppState>,
+    file_path: String,
+    account_id: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<SymbolMonthMatrix, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    ExportService::export_symbol_month_matrix(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+        &file_path,
+    )
+    .await
+}
+
+/// Export each trading day's net PnL and trade count as all-day events of an .ics calendar file
+#[tauri::command]
+pub async fn export_trading_calendar(
+    state: State<'_, AppState>,
+    file_path: String,
+    account_id: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<usize, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    ExportService::export_calendar(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+        &file_path,
+    )
+    .await
+}
+
+fn save_file_dialog(app: &tauri::AppHandle, filter_name: &str, extension: &str, file_name: &str) -> Result<Option<String>, String> {
+    let file_handle = app
+        .dialog()
+        .file()
+        .add_filter(filter_name, &[extension])
+        .set_file_name(file_name)
+        .blocking_save_file();
+
+    match file_handle {
+        Some(path) => {
+            let path_buf = path.into_path().map_err(|e| format!("Invalid path: {}", e))?;
+            Ok(Some(path_buf.to_string_lossy().to_string()))
+        }
+        None => Ok(None),
+    }
+}
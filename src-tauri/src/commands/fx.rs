@@ -0,0 +1,38 @@
+use chrono::NaiveDate;
+use tauri::State;
+use crate::models::{FxRate, FxRefreshResult};
+use crate::services::FxService;
+use crate::AppState;
+
+/// Store manual FX rates, overriding fetched rates of the same day
+#[tauri::command]
+pub async fn save_fx_rates(
+    state: State<'_, AppState>,
+    rates: Vec<FxRate>,
+) -> Result<usize, String> {
+    state.writes.run(FxService::save_rates(&state.pool, rates)).await
+}
+
+#[tauri::command]
+pub async fn get_fx_rates(state: State<'_, AppState>) -> Result<Vec<FxRate>, String> {
+    FxService::get_rates(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn delete_fx_rate(
+    state: State<'_, AppState>,
+    base_currency: String,
+    quote_currency: String,
+    date: String,
+) -> Result<(), String> {
+    let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date: {}", e))?;
+
+    state.writes.run(FxService::delete_rate(&state.pool, &base_currency, &quote_currency, date)).await
+}
+
+/// Fetch and cache daily ECB rates for the account currencies
+#[tauri::command]
+pub async fn refresh_fx_rates(state: State<'_, AppState>) -> Result<FxRefreshResult, String> {
+    state.writes.run(FxService::refresh_rates(&state.pool, &state.user_id)).await
+}
@@ -0,0 +1,36 @@
+use chrono::NaiveDate;
+use tauri::State;
+use crate::models::{GoalScorecard, ProcessStreaks};
+use crate::services::GoalService;
+use crate::AppState;
+
+/// Re-score the week containing a date, e.g. after journaling it late
+#[tauri::command]
+pub async fn evaluate_goal_week(
+    state: State<'_, AppState>,
+    date: String,
+) -> Result<GoalScorecard, String> {
+    let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date: {}", e))?;
+
+    state.writes.run(GoalService::evaluate_week(&state.pool, &state.user_id, date)).await
+}
+
+#[tauri::command]
+pub async fn get_goal_scorecards(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<Vec<GoalScorecard>, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    GoalService::get_scorecards(&state.pool, &state.user_id, start, end).await
+}
+
+#[tauri::command]
+pub async fn get_process_streaks(state: State<'_, AppState>) -> Result<ProcessStreaks, String> {
+    GoalService::get_process_streaks(&state.pool, &state.user_id).await
+}
@@ -1,10 +1,14 @@
 use std::fs;
+use std::path::Path;
 use tauri::State;
 use tauri_plugin_dialog::DialogExt;
 
+use crate::parsers::{supported_brokers, SupportedBroker, TlgParseError};
+use crate::services::{AccountService, WatchFolderService};
 use crate::services::import_service::{
-    AggregatedTrade, ImportPreview, ImportResult, ImportService,
+    AggregatedTrade, ChangedTrade, ImportPreview, ImportResult, ImportService, RollChainCandidate,
 };
+use crate::models::{ImportBatch, WatchFolder};
 use crate::AppState;
 
 /// Open a file picker dialog to select a TLG file
@@ -27,38 +31,200 @@ pub async fn select_tlg_file(app: tauri::AppHandle) -> Result<Option<String>, St
     }
 }
 
-/// Preview importing a TLG file
+/// Open a file picker dialog to select a CSV export, such as a thinkorswim Account Statement
+#[tauri::command]
+pub async fn select_csv_file(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let file_handle = app
+        .dialog()
+        .file()
+        .add_filter("CSV Files", &["csv"])
+        .add_filter("All Files", &["*"])
+        .blocking_pick_file();
+
+    match file_handle {
+        Some(path) => {
+            let path_buf = path.into_path().map_err(|e| format!("Invalid path: {}", e))?;
+            Ok(Some(path_buf.to_string_lossy().to_string()))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Open a save dialog to choose where to write the import error report
+#[tauri::command]
+pub async fn select_import_error_file(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let file_handle = app
+        .dialog()
+        .file()
+        .add_filter("TLG Files", &["tlg"])
+        .set_file_name("import-errors.tlg")
+        .blocking_save_file();
+
+    match file_handle {
+        Some(path) => {
+            let path_buf = path.into_path().map_err(|e| format!("Invalid path: {}", e))?;
+            Ok(Some(path_buf.to_string_lossy().to_string()))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Export lines that failed to parse, with error reasons, for correction and re-import
+#[tauri::command]
+pub async fn export_import_errors(
+    file_path: String,
+    errors: Vec<TlgParseError>,
+) -> Result<(), String> {
+    ImportService::export_parse_errors(&file_path, &errors)
+}
+
+/// Preview importing a TLG file or any other supported broker export, detected from its contents
 #[tauri::command]
 pub async fn preview_tlg_import(
     state: State<'_, AppState>,
     file_path: String,
+    account_id: Option<String>,
+) -> Result<ImportPreview, String> {
+    preview_broker_import(state, file_path, None, account_id).await
+}
+
+/// Brokers whose exports can be previewed, by ID and name
+#[tauri::command]
+pub async fn list_supported_brokers() -> Result<Vec<SupportedBroker>, String> {
+    Ok(supported_brokers())
+}
+
+/// Preview importing the export of a broker listed by list_supported_brokers
+/// Without a broker ID the format is detected from the contents.
+/// Previewed trades are imported with execute_tlg_import like TLG ones.
+#[tauri::command]
+pub async fn preview_broker_import(
+    state: State<'_, AppState>,
+    file_path: String,
+    broker_id: Option<String>,
+    account_id: Option<String>,
 ) -> Result<ImportPreview, String> {
-    // Read the file
     let content = fs::read_to_string(&file_path)
         .map_err(|e| format!("Failed to read file: {}", e))?;
 
-    // Generate preview
-    ImportService::preview_import(&state.pool, &content).await
+    // Closing executions of seeded symbols are matched against the account's seed positions
+    let seeds = match account_id {
+        Some(ref id) => AccountService::get_seed_positions(&state.pool, &state.user_id, Some(id)).await?,
+        None => Vec::new(),
+    };
+
+    match broker_id {
+        Some(ref id) => ImportService::preview_broker_import(&state.pool, &state.user_id, id, &content, &seeds).await,
+        None => ImportService::preview_import(&state.pool, &state.user_id, &content, &seeds).await,
+    }
+}
+
+/// Import a NinjaTrader Trades grid export directly, without a preview
+#[tauri::command]
+pub async fn import_ninjatrader_trades(
+    state: State<'_, AppState>,
+    file_path: String,
+    account_id: String,
+    skip_duplicates: bool,
+) -> Result<ImportResult, String> {
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let file_name = file_name(&file_path);
+    state.writes.run(ImportService::import_ninjatrader_trades(&state.pool, &state.user_id, &account_id, &content, skip_duplicates, file_name.as_deref())).await
+}
+
+/// Import the closed tickets of a MetaTrader 4 or 5 statement directly, without a preview
+#[tauri::command]
+pub async fn import_metatrader_statement(
+    state: State<'_, AppState>,
+    file_path: String,
+    account_id: String,
+    skip_duplicates: bool,
+) -> Result<ImportResult, String> {
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let file_name = file_name(&file_path);
+    state.writes.run(ImportService::import_metatrader_statement(&state.pool, &state.user_id, &account_id, &content, skip_duplicates, file_name.as_deref())).await
+}
+
+/// Import the List of Trades of a TradingView strategy tester or paper trading export into a paper account
+#[tauri::command]
+pub async fn import_tradingview_trades(
+    state: State<'_, AppState>,
+    file_path: String,
+    account_id: String,
+    symbol: String,
+    skip_duplicates: bool,
+) -> Result<ImportResult, String> {
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let file_name = file_name(&file_path);
+    state.writes.run(ImportService::import_tradingview_trades(&state.pool, &state.user_id, &account_id, &content, &symbol, skip_duplicates, file_name.as_deref())).await
 }
 
 /// Execute the import for selected trades
+/// The previewed file's path and the lines that failed to parse in its preview are given to
+/// record them in the import history.
 #[tauri::command]
 pub async fn execute_tlg_import(
     state: State<'_, AppState>,
     account_id: String,
     trades: Vec<AggregatedTrade>,
     skip_duplicates: bool,
+    roll_chains: Option<Vec<RollChainCandidate>>,
+    file_path: Option<String>,
+    parse_errors: Option<Vec<TlgParseError>>,
 ) -> Result<ImportResult, String> {
-    ImportService::execute_import(
+    let file_name = file_path.as_deref().and_then(file_name);
+    state.writes.run(ImportService::execute_import(
         &state.pool,
         &state.user_id,
         &account_id,
         trades,
         skip_duplicates,
-    )
+        &roll_chains.unwrap_or_default(),
+        &parse_errors.unwrap_or_default(),
+        file_name.as_deref(),
+    ))
     .await
 }
 
+/// Update previously imported trades with fills corrected at the broker
+#[tauri::command]
+pub async fn update_imported_trades(
+    state: State<'_, AppState>,
+    changed_trades: Vec<ChangedTrade>,
+) -> Result<ImportResult, String> {
+    state.writes.run(ImportService::update_changed_trades(&state.pool, &state.user_id, changed_trades)).await
+}
+
+/// List past imports, most recent first, with their file names, counts and errors
+#[tauri::command]
+pub async fn get_import_history(state: State<'_, AppState>) -> Result<Vec<ImportBatch>, String> {
+    ImportService::get_import_history(&state.pool, &state.user_id).await
+}
+
+/// Delete the trades and executions an import created
+#[tauri::command]
+pub async fn undo_import(state: State<'_, AppState>, batch_id: String) -> Result<u64, String> {
+    state.writes.run(ImportService::undo_import(&state.pool, &state.user_id, &batch_id)).await
+}
+
+/// Get the folder new exports are imported from automatically, if any
+#[tauri::command]
+pub async fn get_watch_folder(state: State<'_, AppState>) -> Result<Option<WatchFolder>, String> {
+    WatchFolderService::get_watch_folder(&state.pool).await
+}
+
+/// Watch a folder for new exports to import into an account, or stop watching with None
+#[tauri::command]
+pub async fn save_watch_folder(state: State<'_, AppState>, folder: Option<WatchFolder>) -> Result<(), String> {
+    state.writes.run(WatchFolderService::save_watch_folder(&state.pool, &state.user_id, folder)).await
+}
+
 /// Get executions for a specific trade
 #[tauri::command]
 pub async fn get_trade_executions(
@@ -67,3 +233,8 @@ pub async fn get_trade_executions(
 ) -> Result<Vec<crate::services::import_service::Execution>, String> {
     ImportService::get_trade_executions(&state.pool, &trade_id).await
 }
+
+/// Name of an imported file as shown in the import history
+fn file_name(file_path: &str) -> Option<String> {
+    Path::new(file_path).file_name().map(|name| name.to_string_lossy().into_owned())
+}
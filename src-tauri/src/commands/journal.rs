@@ -0,0 +1,44 @@
+use chrono::NaiveDate;
+use tauri::State;
+use crate::models::{EndOfDaySummary, JournalEntry};
+use crate::services::JournalService;
+use crate::AppState;
+
+#[tauri::command]
+pub async fn get_journal_entries(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<Vec<JournalEntry>, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    JournalService::get_entries(&state.pool, &state.user_id, start, end).await
+}
+
+/// Save the reviewed journal entry of a day
+#[tauri::command]
+pub async fn save_journal_entry(
+    state: State<'_, AppState>,
+    date: String,
+    content: String,
+) -> Result<JournalEntry, String> {
+    let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date: {}", e))?;
+
+    state.writes.run(JournalService::save_entry(&state.pool, &state.user_id, date, &content)).await
+}
+
+/// Summarize a day on demand, drafting its journal entry if it has none
+#[tauri::command]
+pub async fn summarize_day(
+    state: State<'_, AppState>,
+    date: String,
+) -> Result<Option<EndOfDaySummary>, String> {
+    let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date: {}", e))?;
+
+    state.writes.run(JournalService::summarize_day(&state.pool, &state.user_id, date)).await
+}
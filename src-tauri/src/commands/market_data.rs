@@ -1,7 +1,8 @@
 use tauri::State;
 
 use crate::services::market_data_service::{
-    parse_candle_kind, Candle, CandleKind, MarketDataService, MarketTapeQuote,
+    parse_candle_kind, Candle, CandleKind, EntryAtrBackfillResult, ExcursionBackfillResult, MarketDataService,
+    MarketTapeQuote,
 };
 use crate::AppState;
 
@@ -36,3 +37,15 @@ pub async fn get_market_tape(
 ) -> Result<Vec<MarketTapeQuote>, String> {
     MarketDataService::get_market_tape(&state.pool, symbols.as_deref()).await
 }
+
+/// Fill in missing MAE/MFE prices of closed trades from cached candles
+#[tauri::command]
+pub async fn backfill_excursions(state: State<'_, AppState>) -> Result<ExcursionBackfillResult, String> {
+    state.writes.run(MarketDataService::backfill_excursions(&state.pool, &state.user_id)).await
+}
+
+/// Fill in missing ATR at entry from stored daily bars
+#[tauri::command]
+pub async fn backfill_entry_atr(state: State<'_, AppState>) -> Result<EntryAtrBackfillResult, String> {
+    state.writes.run(MarketDataService::backfill_entry_atr(&state.pool, &state.user_id)).await
+}
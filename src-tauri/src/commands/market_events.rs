@@ -0,0 +1,102 @@
+use std::fs;
+use chrono::NaiveDate;
+use tauri::State;
+use crate::models::{CreateMarketEventInput, EarningsWindowReport, EventDayReport, MarketEvent, MarketEventImportResult};
+use crate::services::MarketEventService;
+use crate::AppState;
+
+#[tauri::command]
+pub async fn add_market_event(
+    state: State<'_, AppState>,
+    input: CreateMarketEventInput,
+) -> Result<MarketEvent, String> {
+    state.writes.run(MarketEventService::add_event(&state.pool, &state.user_id, input)).await
+}
+
+#[tauri::command]
+pub async fn get_market_events(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<Vec<MarketEvent>, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MarketEventService::get_events(&state.pool, &state.user_id, start, end).await
+}
+
+#[tauri::command]
+pub async fn delete_market_event(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    state.writes.run(MarketEventService::delete_event(&state.pool, &state.user_id, &id)).await
+}
+
+/// Import market events from a CSV file (date,type,symbol,description)
+#[tauri::command]
+pub async fn import_market_events(
+    state: State<'_, AppState>,
+    file_path: String,
+) -> Result<MarketEventImportResult, String> {
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    state.writes.run(MarketEventService::import_csv(&state.pool, &state.user_id, &content)).await
+}
+
+#[tauri::command]
+pub async fn get_trade_market_events(
+    state: State<'_, AppState>,
+    trade_id: String,
+) -> Result<Vec<MarketEvent>, String> {
+    MarketEventService::get_trade_events(&state.pool, &state.user_id, &trade_id).await
+}
+
+#[tauri::command]
+pub async fn get_event_day_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<EventDayReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MarketEventService::get_event_day_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_earnings_window_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+    window_days: Option<i64>,
+) -> Result<EarningsWindowReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MarketEventService::get_earnings_window_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+        window_days,
+    )
+    .await
+}
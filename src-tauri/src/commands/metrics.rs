@@ -1,7 +1,10 @@
 use chrono::NaiveDate;
 use tauri::State;
-use crate::models::{DailyPerformance, EquityPoint, PeriodMetrics};
-use crate::services::MetricsService;
+use crate::models::{
+    DailyClose, DailyPerformance, EquityCurveGrouping, EquityPoint, EquitySeries, ExchangeBucket, ExecutionQualityReport, MarginUtilizationReport, MetricSnapshot, PeriodMetrics, PortfolioHeatPoint, PositionSizingReport,
+    OptionsPremiumReport, OvertradingReport, PerformanceBucket, EntryCohort, CryptoTaxReport, FuturesSessionReport, ShortSideReport, SkillProgressionReport, SymbolMonthMatrix, TradeClusterReport, StrategyTrend, RExpectancyReport, ReconciliationReport, StatementTotals, TargetCalibrationReport, TimelineEvent, TradeSequenceBucket,
+};
+use crate::services::{MetricSnapshotService, MetricsService};
 use crate::AppState;
 
 #[tauri::command]
@@ -10,6 +13,7 @@ pub async fn get_daily_performance(
     start_date: String,
     end_date: String,
     account_id: Option<String>,
+    include_unrealized: Option<bool>,
 ) -> Result<Vec<DailyPerformance>, String> {
     let start = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
         .map_err(|e| format!("Invalid start date: {}", e))?;
@@ -22,10 +26,20 @@ pub async fn get_daily_performance(
         account_id.as_deref(),
         start,
         end,
+        include_unrealized.unwrap_or(false),
     )
     .await
 }
 
+/// Store daily closing prices used to mark open positions to market
+#[tauri::command]
+pub async fn save_daily_closes(
+    state: State<'_, AppState>,
+    closes: Vec<DailyClose>,
+) -> Result<usize, String> {
+    state.writes.run(MetricsService::save_daily_closes(&state.pool, closes)).await
+}
+
 #[tauri::command]
 pub async fn get_period_metrics(
     state: State<'_, AppState>,
@@ -82,3 +96,506 @@ pub async fn get_equity_curve(
     )
     .await
 }
+
+/// Get stored nightly snapshots of account metrics for long-term trend charts
+#[tauri::command]
+pub async fn get_metric_snapshots(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+    account_id: Option<String>,
+) -> Result<Vec<MetricSnapshot>, String> {
+    let start = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid start date: {}", e))?;
+    let end = NaiveDate::parse_from_str(&end_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid end date: {}", e))?;
+
+    MetricSnapshotService::get_snapshots(&state.pool, &state.user_id, account_id.as_deref(), Some(start), Some(end)).await
+}
+
+#[tauri::command]
+pub async fn get_equity_curve_series(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+    account_id: Option<String>,
+    group_by: EquityCurveGrouping,
+) -> Result<Vec<EquitySeries>, String> {
+    let start = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid start date: {}", e))?;
+    let end = NaiveDate::parse_from_str(&end_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid end date: {}", e))?;
+
+    MetricsService::get_equity_curve_series(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+        group_by,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_portfolio_heat(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+    account_id: Option<String>,
+) -> Result<Vec<PortfolioHeatPoint>, String> {
+    let start = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid start date: {}", e))?;
+    let end = NaiveDate::parse_from_str(&end_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid end date: {}", e))?;
+
+    MetricsService::get_portfolio_heat(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_r_expectancy(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<RExpectancyReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_r_expectancy(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_position_sizing_audit(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<PositionSizingReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_position_sizing_audit(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_target_calibration(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+    min_planned_r: Option<f64>,
+) -> Result<TargetCalibrationReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_target_calibration(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+        min_planned_r,
+    )
+    .await
+}
+
+/// Get each strategy's rolling expectancy and trade count, over 30-day windows by default
+#[tauri::command]
+pub async fn get_strategy_trends(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+    window_days: Option<i64>,
+) -> Result<Vec<StrategyTrend>, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_strategy_trends(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+        window_days,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_trade_sequence_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<Vec<TradeSequenceBucket>, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_trade_sequence_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_trade_type_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<Vec<PerformanceBucket>, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_trade_type_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_entry_cohort_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<Vec<EntryCohort>, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_entry_cohort_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_skill_progression(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+    sample_size: Option<i32>,
+) -> Result<SkillProgressionReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_skill_progression(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+        sample_size,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_trade_clusters(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+    cluster_count: Option<i32>,
+) -> Result<TradeClusterReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_trade_clusters(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+        cluster_count,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_symbol_month_matrix(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<SymbolMonthMatrix, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_symbol_month_matrix(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_overtrading_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<OvertradingReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_overtrading_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_day_timeline(
+    state: State<'_, AppState>,
+    date: String,
+    account_id: Option<String>,
+) -> Result<Vec<TimelineEvent>, String> {
+    let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date: {}", e))?;
+
+    MetricsService::get_day_timeline(&state.pool, &state.user_id, account_id.as_deref(), date).await
+}
+
+#[tauri::command]
+pub async fn get_execution_quality(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<ExecutionQualityReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_execution_quality(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_exchange_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<Vec<ExchangeBucket>, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_exchange_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_margin_utilization(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+    account_id: Option<String>,
+) -> Result<MarginUtilizationReport, String> {
+    let start = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid start date: {}", e))?;
+    let end = NaiveDate::parse_from_str(&end_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid end date: {}", e))?;
+
+    MetricsService::get_margin_utilization(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_options_premium_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<OptionsPremiumReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_options_premium_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_short_side_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<ShortSideReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_short_side_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_futures_session_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<FuturesSessionReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_futures_session_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_crypto_tax_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<CryptoTaxReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    MetricsService::get_crypto_tax_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn reconcile(
+    state: State<'_, AppState>,
+    account_id: String,
+    start_date: String,
+    end_date: String,
+    statement_totals: StatementTotals,
+) -> Result<ReconciliationReport, String> {
+    let start = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid start date: {}", e))?;
+    let end = NaiveDate::parse_from_str(&end_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid end date: {}", e))?;
+
+    MetricsService::reconcile(
+        &state.pool,
+        &state.user_id,
+        &account_id,
+        start,
+        end,
+        statement_totals,
+    )
+    .await
+}
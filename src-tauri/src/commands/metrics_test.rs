@@ -27,6 +27,8 @@ mod tests {
             entry_price: 100.0,
             exit_price: Some(100.0 + pnl / 100.0),
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(0.0),
@@ -51,6 +53,8 @@ mod tests {
             entry_price: 100.0,
             exit_price: Some(100.0 - loss.abs() / 100.0),
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(0.0),
@@ -94,7 +98,7 @@ mod tests {
         let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
         let end = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
 
-        let result = MetricsService::get_daily_performance(&pool, &user_id, None, start, end)
+        let result = MetricsService::get_daily_performance(&pool, &user_id, None, start, end, false)
             .await
             .unwrap();
 
@@ -113,7 +117,7 @@ mod tests {
         let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
         let end = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
 
-        let result = MetricsService::get_daily_performance(&pool, &user_id, None, start, end)
+        let result = MetricsService::get_daily_performance(&pool, &user_id, None, start, end, false)
             .await
             .unwrap();
 
@@ -138,7 +142,7 @@ mod tests {
         let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
         let end = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
 
-        let result = MetricsService::get_daily_performance(&pool, &user_id, None, start, end)
+        let result = MetricsService::get_daily_performance(&pool, &user_id, None, start, end, false)
             .await
             .unwrap();
 
@@ -180,6 +184,7 @@ mod tests {
             Some(&account_id),
             start,
             end,
+            false,
         )
         .await
         .unwrap();
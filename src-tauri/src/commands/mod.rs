@@ -4,6 +4,19 @@ pub mod metrics;
 pub mod import;
 pub mod market_data;
 pub mod settings;
+pub mod carrying_costs;
+pub mod dividends;
+pub mod export;
+pub mod checklists;
+pub mod market_events;
+pub mod day_conditions;
+pub mod goals;
+pub mod strategy_rules;
+pub mod stop_adjustments;
+pub mod position_rolls;
+pub mod fx;
+pub mod journal;
+pub mod archive;
 
 #[cfg(test)]
 mod trades_test;
@@ -18,3 +31,16 @@ pub use metrics::*;
 pub use import::*;
 pub use market_data::*;
 pub use settings::*;
+pub use carrying_costs::*;
+pub use dividends::*;
+pub use export::*;
+pub use checklists::*;
+pub use market_events::*;
+pub use day_conditions::*;
+pub use goals::*;
+pub use strategy_rules::*;
+pub use stop_adjustments::*;
+pub use position_rolls::*;
+pub use fx::*;
+pub use journal::*;
+pub use archive::*;
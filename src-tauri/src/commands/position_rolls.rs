@@ -0,0 +1,34 @@
+use chrono::NaiveDate;
+use tauri::State;
+use crate::models::{RollPositionInput, TradeWithDerived};
+use crate::services::PositionRollService;
+use crate::AppState;
+
+/// Open positions as of a period end still waiting to be closed out
+#[tauri::command]
+pub async fn get_unresolved_positions(
+    state: State<'_, AppState>,
+    period_end: String,
+    account_id: Option<String>,
+) -> Result<Vec<TradeWithDerived>, String> {
+    let period_end = NaiveDate::parse_from_str(&period_end, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid period end: {}", e))?;
+
+    PositionRollService::get_unresolved_positions(&state.pool, &state.user_id, account_id.as_deref(), period_end).await
+}
+
+#[tauri::command]
+pub async fn roll_position(
+    state: State<'_, AppState>,
+    input: RollPositionInput,
+) -> Result<TradeWithDerived, String> {
+    state.writes.run(PositionRollService::roll_position(&state.pool, &state.user_id, input)).await
+}
+
+#[tauri::command]
+pub async fn clear_position_roll(
+    state: State<'_, AppState>,
+    trade_id: String,
+) -> Result<TradeWithDerived, String> {
+    state.writes.run(PositionRollService::clear_roll(&state.pool, &state.user_id, &trade_id)).await
+}
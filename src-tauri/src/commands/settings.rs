@@ -1,6 +1,9 @@
 use tauri::State;
 
-use crate::services::settings_service::{AlpacaKeysStatus, SettingsService};
+use crate::config::{self, AppConfig, ConfigView};
+use crate::models::{CalculationReport, CostBasisMethod, DateAttribution, MaintenanceReport, ProcessGoals, Reminder, ResultBasis, TradeTypeThresholds};
+use crate::services::settings_service::{AlpacaKeysStatus, SettingsService, StorageUsage};
+use crate::services::{DailyPerformanceService, MaintenanceService};
 use crate::AppState;
 
 #[tauri::command]
@@ -16,12 +19,12 @@ pub async fn save_alpaca_keys(
     api_key_id: String,
     api_secret_key: String,
 ) -> Result<(), String> {
-    SettingsService::save_alpaca_keys(&state.pool, &api_key_id, &api_secret_key).await
+    state.writes.run(SettingsService::save_alpaca_keys(&state.pool, &api_key_id, &api_secret_key)).await
 }
 
 #[tauri::command]
 pub async fn clear_alpaca_keys(state: State<'_, AppState>) -> Result<(), String> {
-    SettingsService::clear_alpaca_keys(&state.pool).await
+    state.writes.run(SettingsService::clear_alpaca_keys(&state.pool)).await
 }
 
 #[tauri::command]
@@ -34,5 +37,254 @@ pub async fn save_manual_trade_timezone(
     state: State<'_, AppState>,
     timezone: String,
 ) -> Result<(), String> {
-    SettingsService::save_manual_trade_timezone(&state.pool, &timezone).await
+    state.writes.run(SettingsService::save_manual_trade_timezone(&state.pool, &timezone)).await
+}
+
+#[tauri::command]
+pub async fn get_include_dividends_in_pnl(state: State<'_, AppState>) -> Result<bool, String> {
+    SettingsService::get_include_dividends_in_pnl(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_include_dividends_in_pnl(
+    state: State<'_, AppState>,
+    include: bool,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_include_dividends_in_pnl(&state.pool, include)).await
+}
+
+#[tauri::command]
+pub async fn get_include_paper_trades(state: State<'_, AppState>) -> Result<bool, String> {
+    SettingsService::get_include_paper_trades(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_include_paper_trades(
+    state: State<'_, AppState>,
+    include: bool,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_include_paper_trades(&state.pool, include)).await
+}
+
+#[tauri::command]
+pub async fn get_result_basis(state: State<'_, AppState>) -> Result<ResultBasis, String> {
+    SettingsService::get_result_basis(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_result_basis(
+    state: State<'_, AppState>,
+    basis: ResultBasis,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_result_basis(&state.pool, basis)).await
+}
+
+#[tauri::command]
+pub async fn get_date_attribution(state: State<'_, AppState>) -> Result<DateAttribution, String> {
+    SettingsService::get_date_attribution(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_date_attribution(
+    state: State<'_, AppState>,
+    attribution: DateAttribution,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_date_attribution(&state.pool, attribution)).await
+}
+
+#[tauri::command]
+pub async fn get_crypto_cost_basis_method(state: State<'_, AppState>) -> Result<CostBasisMethod, String> {
+    SettingsService::get_crypto_cost_basis_method(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_crypto_cost_basis_method(
+    state: State<'_, AppState>,
+    method: CostBasisMethod,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_crypto_cost_basis_method(&state.pool, method)).await
+}
+
+#[tauri::command]
+pub async fn get_trade_type_thresholds(state: State<'_, AppState>) -> Result<TradeTypeThresholds, String> {
+    SettingsService::get_trade_type_thresholds(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_trade_type_thresholds(
+    state: State<'_, AppState>,
+    thresholds: TradeTypeThresholds,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_trade_type_thresholds(&state.pool, &thresholds)).await
+}
+
+#[tauri::command]
+pub async fn get_default_risk_per_trade(state: State<'_, AppState>) -> Result<Option<f64>, String> {
+    SettingsService::get_default_risk_per_trade(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_default_risk_per_trade(
+    state: State<'_, AppState>,
+    risk: Option<f64>,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_default_risk_per_trade(&state.pool, risk)).await
+}
+
+#[tauri::command]
+pub async fn get_account_risk_pct(state: State<'_, AppState>) -> Result<Option<f64>, String> {
+    SettingsService::get_account_risk_pct(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_account_risk_pct(
+    state: State<'_, AppState>,
+    risk_pct: Option<f64>,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_account_risk_pct(&state.pool, risk_pct)).await
+}
+
+#[tauri::command]
+pub async fn get_max_trades_per_symbol_per_day(state: State<'_, AppState>) -> Result<i32, String> {
+    SettingsService::get_max_trades_per_symbol_per_day(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_max_trades_per_symbol_per_day(
+    state: State<'_, AppState>,
+    max_trades: i32,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_max_trades_per_symbol_per_day(&state.pool, max_trades)).await
+}
+
+#[tauri::command]
+pub async fn get_max_margin_utilization_pct(state: State<'_, AppState>) -> Result<f64, String> {
+    SettingsService::get_max_margin_utilization_pct(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_max_margin_utilization_pct(
+    state: State<'_, AppState>,
+    max_pct: f64,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_max_margin_utilization_pct(&state.pool, max_pct)).await
+}
+
+#[tauri::command]
+pub async fn get_reporting_currency(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    SettingsService::get_reporting_currency(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_reporting_currency(
+    state: State<'_, AppState>,
+    currency: Option<String>,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_reporting_currency(&state.pool, currency.as_deref())).await
+}
+
+#[tauri::command]
+pub async fn get_daily_loss_limit(state: State<'_, AppState>) -> Result<Option<f64>, String> {
+    SettingsService::get_daily_loss_limit(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_daily_loss_limit(
+    state: State<'_, AppState>,
+    limit: Option<f64>,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_daily_loss_limit(&state.pool, limit)).await
+}
+
+#[tauri::command]
+pub async fn get_reminders(state: State<'_, AppState>) -> Result<Vec<Reminder>, String> {
+    SettingsService::get_reminders(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_reminders(
+    state: State<'_, AppState>,
+    reminders: Vec<Reminder>,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_reminders(&state.pool, &reminders)).await
+}
+
+#[tauri::command]
+pub async fn get_end_of_day_summary_time(state: State<'_, AppState>) -> Result<String, String> {
+    SettingsService::get_end_of_day_summary_time(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_end_of_day_summary_time(
+    state: State<'_, AppState>,
+    time: String,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_end_of_day_summary_time(&state.pool, &time)).await
+}
+
+#[tauri::command]
+pub async fn get_process_goals(state: State<'_, AppState>) -> Result<ProcessGoals, String> {
+    SettingsService::get_process_goals(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_process_goals(
+    state: State<'_, AppState>,
+    goals: ProcessGoals,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_process_goals(&state.pool, &goals)).await
+}
+
+#[tauri::command]
+pub async fn get_storage_usage(state: State<'_, AppState>) -> Result<StorageUsage, String> {
+    SettingsService::get_storage_usage(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn run_maintenance(state: State<'_, AppState>) -> Result<MaintenanceReport, String> {
+    state.writes.run(MaintenanceService::run_maintenance(&state.pool)).await
+}
+
+/// Recompute the daily performance summary from all closed trades, returning the account days written
+#[tauri::command]
+pub async fn rebuild_daily_performance(state: State<'_, AppState>) -> Result<usize, String> {
+    state.writes.run(DailyPerformanceService::rebuild(&state.pool, &state.user_id)).await
+}
+
+/// Developer check that recomputes derived metrics per trade and in SQL and reports where they disagree
+/// Only available with the developer_tools feature flag on.
+#[tauri::command]
+pub async fn verify_calculations(state: State<'_, AppState>) -> Result<CalculationReport, String> {
+    if !config::feature_enabled(config::FEATURE_DEVELOPER_TOOLS) {
+        return Err(format!(
+            "Developer tools are off; set {} = true under [features] in {}",
+            config::FEATURE_DEVELOPER_TOOLS,
+            config::CONFIG_FILE
+        ));
+    }
+    MaintenanceService::verify_calculations(&state.pool, &state.user_id).await
+}
+
+/// Startup configuration from config.toml and FTJ_ environment variables, without secrets
+#[tauri::command]
+pub async fn get_config() -> Result<ConfigView, String> {
+    config::current().map(AppConfig::view).ok_or_else(|| "Configuration is not loaded".to_string())
+}
+
+#[tauri::command]
+pub async fn get_last_maintenance_report(state: State<'_, AppState>) -> Result<Option<MaintenanceReport>, String> {
+    SettingsService::get_last_maintenance_report(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn get_maintenance_interval_days(state: State<'_, AppState>) -> Result<Option<i32>, String> {
+    SettingsService::get_maintenance_interval_days(&state.pool).await
+}
+
+#[tauri::command]
+pub async fn save_maintenance_interval_days(
+    state: State<'_, AppState>,
+    days: Option<i32>,
+) -> Result<(), String> {
+    state.writes.run(SettingsService::save_maintenance_interval_days(&state.pool, days)).await
 }
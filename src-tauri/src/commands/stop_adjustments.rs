@@ -0,0 +1,51 @@
+use chrono::NaiveDate;
+use tauri::State;
+use crate::models::{RecordStopAdjustmentInput, StopAdjustment, StopAdjustmentReport};
+use crate::services::StopAdjustmentService;
+use crate::AppState;
+
+#[tauri::command]
+pub async fn record_stop_adjustment(
+    state: State<'_, AppState>,
+    input: RecordStopAdjustmentInput,
+) -> Result<StopAdjustment, String> {
+    state.writes.run(StopAdjustmentService::record_adjustment(&state.pool, &state.user_id, input)).await
+}
+
+#[tauri::command]
+pub async fn get_trade_stops(
+    state: State<'_, AppState>,
+    trade_id: String,
+) -> Result<Vec<StopAdjustment>, String> {
+    StopAdjustmentService::get_trade_stops(&state.pool, &state.user_id, &trade_id).await
+}
+
+#[tauri::command]
+pub async fn delete_stop_adjustment(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    state.writes.run(StopAdjustmentService::delete_adjustment(&state.pool, &state.user_id, &id)).await
+}
+
+#[tauri::command]
+pub async fn get_stop_adjustment_report(
+    state: State<'_, AppState>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    account_id: Option<String>,
+) -> Result<StopAdjustmentReport, String> {
+    let start = start_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+    let end = end_date
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
+
+    StopAdjustmentService::get_report(
+        &state.pool,
+        &state.user_id,
+        account_id.as_deref(),
+        start,
+        end,
+    )
+    .await
+}
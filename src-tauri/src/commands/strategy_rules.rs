@@ -0,0 +1,40 @@
+use tauri::State;
+use crate::models::{CreateStrategyRuleInput, StrategyRule, Watchlist};
+use crate::services::StrategyRuleService;
+use crate::AppState;
+
+#[tauri::command]
+pub async fn create_strategy_rule(
+    state: State<'_, AppState>,
+    input: CreateStrategyRuleInput,
+) -> Result<StrategyRule, String> {
+    state.writes.run(StrategyRuleService::create_rule(&state.pool, &state.user_id, input)).await
+}
+
+#[tauri::command]
+pub async fn get_strategy_rules(state: State<'_, AppState>) -> Result<Vec<StrategyRule>, String> {
+    StrategyRuleService::get_rules(&state.pool, &state.user_id).await
+}
+
+#[tauri::command]
+pub async fn delete_strategy_rule(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, String> {
+    state.writes.run(StrategyRuleService::delete_rule(&state.pool, &state.user_id, &id)).await
+}
+
+/// Replace the symbols on a watchlist; an empty list removes it
+#[tauri::command]
+pub async fn set_watchlist(
+    state: State<'_, AppState>,
+    name: String,
+    symbols: Vec<String>,
+) -> Result<Watchlist, String> {
+    state.writes.run(StrategyRuleService::set_watchlist(&state.pool, &state.user_id, &name, symbols)).await
+}
+
+#[tauri::command]
+pub async fn get_watchlists(state: State<'_, AppState>) -> Result<Vec<Watchlist>, String> {
+    StrategyRuleService::get_watchlists(&state.pool, &state.user_id).await
+}
@@ -1,6 +1,6 @@
 use chrono::NaiveDate;
 use tauri::State;
-use crate::models::{CreateTradeInput, TradeWithDerived, UpdateTradeInput};
+use crate::models::{BulkCreateResult, CreateTradeInput, Instrument, TradeFilter, TradeStats, TradeWithDerived, UpdateTradeInput};
 use crate::services::TradeService;
 use crate::AppState;
 
@@ -10,28 +10,71 @@ pub async fn get_trades(
     account_id: Option<String>,
     start_date: Option<String>,
     end_date: Option<String>,
+    include_executions: Option<bool>,
 ) -> Result<Vec<TradeWithDerived>, String> {
     let start = start_date
         .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
     let end = end_date
         .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
 
-    TradeService::get_all_trades(
+    let mut trades = TradeService::get_all_trades(
         &state.pool,
         &state.user_id,
         account_id.as_deref(),
         start,
         end,
     )
-    .await
+    .await?;
+
+    if include_executions.unwrap_or(false) {
+        TradeService::attach_executions(&state.pool, &mut trades, false).await?;
+    }
+    Ok(trades)
 }
 
 #[tauri::command]
 pub async fn get_trade(
     state: State<'_, AppState>,
     id: String,
+    include_executions: Option<bool>,
+) -> Result<Option<TradeWithDerived>, String> {
+    let mut trade = TradeService::get_trade(&state.pool, &state.user_id, &id).await?;
+
+    if let Some(trade) = trade.as_mut().filter(|_| include_executions.unwrap_or(false)) {
+        TradeService::attach_executions(&state.pool, std::slice::from_mut(trade), false).await?;
+    }
+    Ok(trade)
+}
+
+/// Get trades matching a review filter, including result, minimum R and maximum duration
+#[tauri::command]
+pub async fn find_trades(
+    state: State<'_, AppState>,
+    filter: TradeFilter,
+    include_executions: Option<bool>,
+) -> Result<Vec<TradeWithDerived>, String> {
+    let mut trades = TradeService::find_trades(&state.pool, &state.user_id, &filter).await?;
+
+    if include_executions.unwrap_or(false) {
+        TradeService::attach_executions(&state.pool, &mut trades, filter.archived).await?;
+    }
+    Ok(trades)
+}
+
+#[tauri::command]
+pub async fn get_trade_stats(
+    state: State<'_, AppState>,
+    filter: TradeFilter,
+) -> Result<TradeStats, String> {
+    TradeService::get_trade_stats(&state.pool, &state.user_id, &filter).await
+}
+
+#[tauri::command]
+pub async fn resolve_trade_ref(
+    state: State<'_, AppState>,
+    ref_code: String,
 ) -> Result<Option<TradeWithDerived>, String> {
-    TradeService::get_trade(&state.pool, &id).await
+    TradeService::resolve_trade_ref(&state.pool, &state.user_id, &ref_code).await
 }
 
 #[tauri::command]
@@ -39,7 +82,16 @@ pub async fn create_trade(
     state: State<'_, AppState>,
     input: CreateTradeInput,
 ) -> Result<TradeWithDerived, String> {
-    TradeService::create_trade(&state.pool, &state.user_id, input).await
+    state.writes.run(TradeService::create_trade(&state.pool, &state.user_id, input)).await
+}
+
+/// Create many manual trades at once; nothing is created if any row is invalid
+#[tauri::command]
+pub async fn create_trades(
+    state: State<'_, AppState>,
+    inputs: Vec<CreateTradeInput>,
+) -> Result<BulkCreateResult, String> {
+    state.writes.run(TradeService::create_trades(&state.pool, &state.user_id, inputs)).await
 }
 
 #[tauri::command]
@@ -48,7 +100,7 @@ pub async fn update_trade(
     id: String,
     input: UpdateTradeInput,
 ) -> Result<TradeWithDerived, String> {
-    TradeService::update_trade(&state.pool, &id, input).await
+    state.writes.run(TradeService::update_trade(&state.pool, &state.user_id, &id, input)).await
 }
 
 #[tauri::command]
@@ -56,5 +108,61 @@ pub async fn delete_trade(
     state: State<'_, AppState>,
     id: String,
 ) -> Result<(), String> {
-    TradeService::delete_trade(&state.pool, &id).await
+    state.writes.run(TradeService::delete_trade(&state.pool, &state.user_id, &id)).await
+}
+
+#[tauri::command]
+pub async fn set_trade_result_override(
+    state: State<'_, AppState>,
+    id: String,
+    result: Option<String>,
+) -> Result<TradeWithDerived, String> {
+    state.writes.run(TradeService::set_result_override(&state.pool, &state.user_id, &id, result)).await
+}
+
+#[tauri::command]
+pub async fn set_trade_borrow_availability(
+    state: State<'_, AppState>,
+    id: String,
+    availability: Option<String>,
+    note: Option<String>,
+) -> Result<TradeWithDerived, String> {
+    state.writes.run(TradeService::set_borrow_availability(&state.pool, &state.user_id, &id, availability, note)).await
+}
+
+#[tauri::command]
+pub async fn set_trade_option_outcome(
+    state: State<'_, AppState>,
+    id: String,
+    outcome: Option<String>,
+) -> Result<TradeWithDerived, String> {
+    state.writes.run(TradeService::set_option_outcome(&state.pool, &state.user_id, &id, outcome)).await
+}
+
+#[tauri::command]
+pub async fn set_instrument_tick_size(
+    state: State<'_, AppState>,
+    symbol: String,
+    tick_size: Option<f64>,
+) -> Result<Instrument, String> {
+    state.writes.run(TradeService::set_instrument_tick_size(&state.pool, &symbol, tick_size)).await
+}
+
+#[tauri::command]
+pub async fn set_instrument_margin_requirement(
+    state: State<'_, AppState>,
+    symbol: String,
+    margin_requirement: Option<f64>,
+) -> Result<Instrument, String> {
+    state.writes.run(TradeService::set_instrument_margin_requirement(&state.pool, &symbol, margin_requirement)).await
+}
+
+#[tauri::command]
+pub async fn update_execution_quality(
+    state: State<'_, AppState>,
+    execution_id: String,
+    intended_price: Option<f64>,
+    order_type: Option<String>,
+) -> Result<(), String> {
+    state.writes.run(TradeService::update_execution_quality(&state.pool, &state.user_id, &execution_id, intended_price, order_type)).await
 }
@@ -126,7 +126,7 @@ mod tests {
         let input = create_test_trade_input(&account_id, "AAPL");
         let created = TradeService::create_trade(&pool, &user_id, input).await.unwrap();
 
-        let fetched = TradeService::get_trade(&pool, &created.trade.id)
+        let fetched = TradeService::get_trade(&pool, &user_id, &created.trade.id)
             .await
             .unwrap();
 
@@ -139,9 +139,9 @@ mod tests {
     #[tokio::test]
     async fn test_get_trade_not_found() {
         let pool = create_test_db().await;
-        let (_user_id, _account_id) = setup_test_user_and_account(&pool).await;
+        let (user_id, _account_id) = setup_test_user_and_account(&pool).await;
 
-        let result = TradeService::get_trade(&pool, "nonexistent-id")
+        let result = TradeService::get_trade(&pool, &user_id, "nonexistent-id")
             .await
             .unwrap();
 
@@ -165,6 +165,8 @@ mod tests {
             entry_price: 100.0,
             exit_price: Some(110.0), // +10 per share
             stop_loss_price: Some(95.0), // Risk of 5 per share
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(10.0),
@@ -176,7 +178,7 @@ mod tests {
         };
 
         let created = TradeService::create_trade(&pool, &user_id, input).await.unwrap();
-        let fetched = TradeService::get_trade(&pool, &created.trade.id)
+        let fetched = TradeService::get_trade(&pool, &user_id, &created.trade.id)
             .await
             .unwrap()
             .unwrap();
@@ -222,6 +224,8 @@ mod tests {
             entry_price: 0.0, // Invalid
             exit_price: Some(110.0),
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -253,6 +257,8 @@ mod tests {
             entry_price: 100.0,
             exit_price: Some(110.0),
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(-5.0), // Invalid
@@ -284,6 +290,8 @@ mod tests {
             entry_price: 100.0,
             exit_price: Some(110.0),
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -334,6 +342,8 @@ mod tests {
             entry_price: None,
             exit_price: Some(160.0),
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -343,7 +353,7 @@ mod tests {
             status: None,
         };
 
-        let updated = TradeService::update_trade(&pool, &created.trade.id, update)
+        let updated = TradeService::update_trade(&pool, &user_id, &created.trade.id, update)
             .await
             .unwrap();
 
@@ -355,7 +365,7 @@ mod tests {
     #[tokio::test]
     async fn test_update_trade_not_found() {
         let pool = create_test_db().await;
-        let (_user_id, _account_id) = setup_test_user_and_account(&pool).await;
+        let (user_id, _account_id) = setup_test_user_and_account(&pool).await;
 
         let update = UpdateTradeInput {
             account_id: None,
@@ -367,6 +377,8 @@ mod tests {
             entry_price: None,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -376,10 +388,9 @@ mod tests {
             status: None,
         };
 
-        let result = TradeService::update_trade(&pool, "nonexistent-id", update).await;
+        let result = TradeService::update_trade(&pool, &user_id, "nonexistent-id", update).await;
         assert!(result.is_err());
-        // Error message is "Failed to update trade: ..." wrapping the underlying error
-        assert!(result.unwrap_err().contains("Failed to update trade"));
+        assert!(result.unwrap_err().contains("Trade not found"));
     }
 
     #[tokio::test]
@@ -403,6 +414,8 @@ mod tests {
             entry_price: Some(0.0),
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -413,7 +426,7 @@ mod tests {
         };
 
         // This succeeds because update_trade doesn't validate
-        let result = TradeService::update_trade(&pool, &created.trade.id, update).await;
+        let result = TradeService::update_trade(&pool, &user_id, &created.trade.id, update).await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap().trade.entry_price, 0.0);
     }
@@ -436,6 +449,8 @@ mod tests {
             entry_price: None,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -445,7 +460,7 @@ mod tests {
             status: None,
         };
 
-        let updated = TradeService::update_trade(&pool, &created.trade.id, update)
+        let updated = TradeService::update_trade(&pool, &user_id, &created.trade.id, update)
             .await
             .unwrap();
 
@@ -469,6 +484,8 @@ mod tests {
             entry_price: 100.0,
             exit_price: Some(110.0),
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(10.0),
@@ -493,6 +510,8 @@ mod tests {
             entry_price: None,
             exit_price: Some(120.0), // Now +20 per share
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -502,7 +521,7 @@ mod tests {
             status: None,
         };
 
-        let updated = TradeService::update_trade(&pool, &created.trade.id, update)
+        let updated = TradeService::update_trade(&pool, &user_id, &created.trade.id, update)
             .await
             .unwrap();
 
@@ -521,11 +540,11 @@ mod tests {
         let created = TradeService::create_trade(&pool, &user_id, input).await.unwrap();
 
         // Delete trade
-        let result = TradeService::delete_trade(&pool, &created.trade.id).await;
+        let result = TradeService::delete_trade(&pool, &user_id, &created.trade.id).await;
         assert!(result.is_ok());
 
         // Verify deleted
-        let fetched = TradeService::get_trade(&pool, &created.trade.id)
+        let fetched = TradeService::get_trade(&pool, &user_id, &created.trade.id)
             .await
             .unwrap();
         assert!(fetched.is_none());
@@ -534,11 +553,11 @@ mod tests {
     #[tokio::test]
     async fn test_delete_trade_not_found() {
         let pool = create_test_db().await;
-        let (_user_id, _account_id) = setup_test_user_and_account(&pool).await;
+        let (user_id, _account_id) = setup_test_user_and_account(&pool).await;
 
-        // Deleting nonexistent trade should succeed (no error)
-        let result = TradeService::delete_trade(&pool, "nonexistent-id").await;
-        assert!(result.is_ok());
+        // Deleting a nonexistent trade is reported so stale references can be told apart
+        let result = TradeService::delete_trade(&pool, &user_id, "nonexistent-id").await;
+        assert_eq!(result.unwrap_err(), "Trade not found: nonexistent-id");
     }
 
     // ==================== DATE PARSING EDGE CASES ====================
@@ -590,7 +609,7 @@ mod tests {
         assert_eq!(created.trade.symbol, "AAPL");
 
         // 2. Get trade
-        let fetched = TradeService::get_trade(&pool, &created.trade.id)
+        let fetched = TradeService::get_trade(&pool, &user_id, &created.trade.id)
             .await
             .unwrap()
             .unwrap();
@@ -607,6 +626,8 @@ mod tests {
             entry_price: None,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -615,7 +636,7 @@ mod tests {
             screenshot_url: None,
             status: None,
         };
-        let updated = TradeService::update_trade(&pool, &created.trade.id, update)
+        let updated = TradeService::update_trade(&pool, &user_id, &created.trade.id, update)
             .await
             .unwrap();
         assert_eq!(updated.trade.strategy, Some("updated strategy".to_string()));
@@ -627,10 +648,10 @@ mod tests {
         assert_eq!(trades.len(), 1);
 
         // 5. Delete trade
-        TradeService::delete_trade(&pool, &created.trade.id).await.unwrap();
+        TradeService::delete_trade(&pool, &user_id, &created.trade.id).await.unwrap();
 
         // 6. Verify deleted
-        let fetched_after = TradeService::get_trade(&pool, &created.trade.id)
+        let fetched_after = TradeService::get_trade(&pool, &user_id, &created.trade.id)
             .await
             .unwrap();
         assert!(fetched_after.is_none());
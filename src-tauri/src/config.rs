@@ -0,0 +1,313 @@
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use log::{LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use crate::services::settings_service::mask_key_id;
+
+/// Name of the config file in the app data directory
+pub const CONFIG_FILE: &str = "config.toml";
+/// Database file in the app data directory when none is configured
+const DEFAULT_DATABASE_FILE: &str = "trades.db";
+/// Prefix of the environment variables that override the config file
+const ENV_PREFIX: &str = "FTJ_";
+/// Feature flag turning on developer tools such as the calculation check
+pub const FEATURE_DEVELOPER_TOOLS: &str = "developer_tools";
+/// Feature flags the app reads; any other name is rejected as misspelled. All are off by default.
+const FEATURES: [&str; 1] = [FEATURE_DEVELOPER_TOOLS];
+
+static CONFIG: OnceLock<AppConfig> = OnceLock::new();
+
+/// Contents of config.toml; every setting is optional
+/// Unknown keys are rejected so a misspelled setting is reported rather than ignored.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    database_path: Option<PathBuf>, // Relative paths are in the app data directory
+    log_level: Option<LogLevel>,
+    providers: ProvidersFile,
+    features: BTreeMap<String, bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ProvidersFile {
+    alpaca: AlpacaFile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AlpacaFile {
+    api_key_id: Option<String>,
+    api_secret_key: Option<String>,
+}
+
+/// Least severe messages written to the log
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    Error,
+    #[default]
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "off" => Some(LogLevel::Off),
+            "error" => Some(LogLevel::Error),
+            "warn" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            "trace" => Some(LogLevel::Trace),
+            _ => None,
+        }
+    }
+
+    fn filter(self) -> LevelFilter {
+        match self {
+            LogLevel::Off => LevelFilter::Off,
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warn => LevelFilter::Warn,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+            LogLevel::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+/// Startup configuration: config.toml in the app data directory, then environment overrides
+/// FTJ_DATABASE_PATH, FTJ_LOG_LEVEL, FTJ_ALPACA_API_KEY_ID and FTJ_ALPACA_API_SECRET_KEY replace
+/// their settings in the file, and FTJ_FEATURE_<NAME>=true|false sets the feature flag <name>.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppConfig {
+    pub config_file: Option<PathBuf>, // The config.toml read, None when there is none
+    pub database_path: PathBuf,
+    pub log_level: LogLevel,
+    pub alpaca_api_key_id: Option<String>, // Takes precedence over the keys saved in Settings
+    pub alpaca_api_secret_key: Option<String>,
+    pub features: BTreeMap<String, bool>,
+}
+
+/// The configuration as shown to the frontend, without secrets
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigView {
+    pub config_file: Option<String>,
+    pub database_path: String,
+    pub log_level: LogLevel,
+    pub masked_alpaca_key_id: Option<String>,
+    pub has_alpaca_secret_key: bool,
+    pub features: BTreeMap<String, bool>,
+}
+
+impl AppConfig {
+    /// Load the configuration of an app data directory with the process environment
+    pub fn load(app_data_dir: &Path) -> Result<Self, String> {
+        Self::load_with(app_data_dir, std::env::vars())
+    }
+
+    /// Load the configuration of an app data directory with the given environment variables
+    /// A missing config.toml leaves every setting at its default; one that does not parse is an error.
+    fn load_with(app_data_dir: &Path, vars: impl IntoIterator<Item = (String, String)>) -> Result<Self, String> {
+        let path = app_data_dir.join(CONFIG_FILE);
+        let (file, config_file) = match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                let file: ConfigFile = toml::from_str(&content).map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+                (file, Some(path))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => (ConfigFile::default(), None),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+
+        let mut config = AppConfig {
+            config_file,
+            database_path: file.database_path.unwrap_or_else(|| PathBuf::from(DEFAULT_DATABASE_FILE)),
+            log_level: file.log_level.unwrap_or_default(),
+            alpaca_api_key_id: file.providers.alpaca.api_key_id,
+            alpaca_api_secret_key: file.providers.alpaca.api_secret_key,
+            features: file.features,
+        };
+        config.apply_overrides(vars)?;
+        if let Some(unknown) = config.features.keys().find(|name| !FEATURES.contains(&name.as_str())) {
+            return Err(format!("Unknown feature flag: {}", unknown));
+        }
+        config.database_path = app_data_dir.join(&config.database_path);
+        Ok(config)
+    }
+
+    /// Every setting at its default, e.g. when config.toml cannot be used
+    pub fn default_in(app_data_dir: &Path) -> Self {
+        AppConfig {
+            config_file: None,
+            database_path: app_data_dir.join(DEFAULT_DATABASE_FILE),
+            log_level: LogLevel::default(),
+            alpaca_api_key_id: None,
+            alpaca_api_secret_key: None,
+            features: BTreeMap::new(),
+        }
+    }
+
+    /// Whether a feature flag is on
+    pub fn feature_enabled(&self, name: &str) -> bool {
+        self.features.get(name).copied().unwrap_or(false)
+    }
+
+    /// Replace settings with the FTJ_ environment variables among `vars`
+    fn apply_overrides(&mut self, vars: impl IntoIterator<Item = (String, String)>) -> Result<(), String> {
+        for (name, value) in vars {
+            let Some(key) = name.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            match key {
+                "DATABASE_PATH" => self.database_path = PathBuf::from(value),
+                "LOG_LEVEL" => {
+                    self.log_level = LogLevel::from_str(&value).ok_or_else(|| format!("Invalid {}: {}", name, value))?;
+                }
+                "ALPACA_API_KEY_ID" => self.alpaca_api_key_id = Some(value),
+                "ALPACA_API_SECRET_KEY" => self.alpaca_api_secret_key = Some(value),
+                _ => {
+                    if let Some(feature) = key.strip_prefix("FEATURE_") {
+                        let enabled = match value.trim().to_lowercase().as_str() {
+                            "true" | "1" | "on" => true,
+                            "false" | "0" | "off" => false,
+                            _ => return Err(format!("Invalid {}: {}", name, value)),
+                        };
+                        self.features.insert(feature.to_lowercase(), enabled);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Alpaca keys from the configuration, when both are set
+    pub fn alpaca_keys(&self) -> Option<(String, String)> {
+        let non_empty = |key: &Option<String>| key.as_deref().map(str::trim).filter(|k| !k.is_empty()).map(str::to_string);
+        non_empty(&self.alpaca_api_key_id).zip(non_empty(&self.alpaca_api_secret_key))
+    }
+
+    pub fn view(&self) -> ConfigView {
+        ConfigView {
+            config_file: self.config_file.as_ref().map(|p| p.display().to_string()),
+            database_path: self.database_path.display().to_string(),
+            log_level: self.log_level,
+            masked_alpaca_key_id: self.alpaca_api_key_id.as_deref().map(mask_key_id),
+            has_alpaca_secret_key: self.alpaca_api_secret_key.as_deref().is_some_and(|k| !k.trim().is_empty()),
+            features: self.features.clone(),
+        }
+    }
+}
+
+/// Make a loaded configuration the one `current` returns and start logging at its level
+/// Only the first configuration installed takes effect.
+pub fn install(config: AppConfig) {
+    if log::set_logger(&STDERR_LOGGER).is_ok() {
+        log::set_max_level(config.log_level.filter());
+    }
+    let _ = CONFIG.set(config);
+}
+
+/// The configuration loaded at startup, or None before it is installed, e.g. in tests
+pub fn current() -> Option<&'static AppConfig> {
+    CONFIG.get()
+}
+
+/// Whether a feature flag of the configuration loaded at startup is on; off before it is installed
+pub fn feature_enabled(name: &str) -> bool {
+    current().is_some_and(|config| config.feature_enabled(name))
+}
+
+static STDERR_LOGGER: StderrLogger = StderrLogger;
+
+/// Writes log records to stderr, which the terminal or the OS log of a bundled app collects
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{}] {}: {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_load_config_with_overrides() {
+        let dir = std::env::temp_dir().join(format!("ftj-config-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // Without a file everything is at its default
+        let config = AppConfig::load_with(&dir, Vec::new()).unwrap();
+        assert_eq!(config.config_file, None);
+        assert_eq!(config.database_path, dir.join("trades.db"));
+        assert_eq!(config.log_level, LogLevel::Warn);
+
+        std::fs::write(
+            dir.join(CONFIG_FILE),
+            r#"
+database_path = "journal.db"
+log_level = "info"
+
+[providers.alpaca]
+api_key_id = "PKTESTKEY1234"
+api_secret_key = "secret"
+
+[features]
+developer_tools = true
+"#,
+        )
+        .unwrap();
+        let config = AppConfig::load_with(
+            &dir,
+            vars(&[("FTJ_LOG_LEVEL", "debug"), ("FTJ_FEATURE_DEVELOPER_TOOLS", "false"), ("HOME", "/home/me")]),
+        )
+        .unwrap();
+        assert_eq!(config.config_file, Some(dir.join(CONFIG_FILE)));
+        assert_eq!(config.database_path, dir.join("journal.db"));
+        assert_eq!(config.log_level, LogLevel::Debug);
+        assert!(!config.feature_enabled(FEATURE_DEVELOPER_TOOLS));
+        assert_eq!(config.alpaca_keys(), Some(("PKTESTKEY1234".to_string(), "secret".to_string())));
+
+        // The frontend sees no secrets
+        let view = config.view();
+        assert_eq!(view.masked_alpaca_key_id.as_deref(), Some("PKTE••••1234"));
+        assert!(view.has_alpaca_secret_key);
+
+        assert_eq!(
+            AppConfig::load_with(&dir, vars(&[("FTJ_LOG_LEVEL", "loud")])).unwrap_err(),
+            "Invalid FTJ_LOG_LEVEL: loud"
+        );
+        assert_eq!(
+            AppConfig::load_with(&dir, vars(&[("FTJ_FEATURE_DEVELOPER_TOOLZ", "true")])).unwrap_err(),
+            "Unknown feature flag: developer_toolz"
+        );
+        assert!(AppConfig::load_with(&dir, vars(&[("FTJ_FEATURE_DEVELOPER_TOOLS", "true")])).unwrap().feature_enabled(FEATURE_DEVELOPER_TOOLS));
+        std::fs::write(dir.join(CONFIG_FILE), "log_levle = \"info\"\n").unwrap();
+        assert!(AppConfig::load_with(&dir, Vec::new()).unwrap_err().contains("unknown field `log_levle`"));
+
+        // What startup falls back to when the file cannot be used
+        let fallback = AppConfig::default_in(&dir);
+        assert_eq!(fallback.database_path, dir.join("trades.db"));
+        assert!(!fallback.feature_enabled(FEATURE_DEVELOPER_TOOLS));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
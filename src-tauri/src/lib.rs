@@ -1,19 +1,25 @@
 mod calculations;
 mod commands;
+mod config;
 mod models;
 mod parsers;
 mod repository;
+mod scheduler;
 mod services;
+mod write_queue;
 
 #[cfg(test)]
 mod test_utils;
 
 use sqlx::sqlite::SqlitePool;
 use tauri::Manager;
+use write_queue::WriteQueue;
 
 pub struct AppState {
     pub pool: SqlitePool,
     pub user_id: String,
+    /// Mutating commands run through this queue one at a time; reads use the pool directly
+    pub writes: WriteQueue,
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -31,8 +37,20 @@ pub fn run() {
                     .app_data_dir()
                     .expect("Failed to get app data directory");
 
+                // config.toml and FTJ_ environment variables; a broken file is logged and the
+                // defaults used, so a typo does not keep the journal from opening
+                let (config, load_error) = match config::AppConfig::load(&app_data_dir) {
+                    Ok(config) => (config, None),
+                    Err(e) => (config::AppConfig::default_in(&app_data_dir), Some(e)),
+                };
+                let db_path = config.database_path.clone();
+                config::install(config);
+                if let Some(e) = load_error {
+                    log::error!("{}; starting with the default configuration", e);
+                }
+
                 // Initialize database
-                let pool = repository::init_db(app_data_dir)
+                let pool = repository::init_db(db_path)
                     .await
                     .expect("Failed to initialize database");
 
@@ -41,8 +59,17 @@ pub fn run() {
                     .await
                     .expect("Failed to create defaults");
 
+                // Journals kept before the daily performance summary existed are summarized once;
+                // the app still starts if that fails, and it is tried again on the next launch
+                if let Err(e) = services::DailyPerformanceService::ensure_built(&pool, &user_id).await {
+                    log::error!("Failed to build daily performance: {}", e);
+                }
+
+                let writes = WriteQueue::default();
+                tauri::async_runtime::spawn(scheduler::run(app_handle.clone(), pool.clone(), user_id.clone(), writes.clone()));
+
                 // Store state
-                let state = AppState { pool, user_id };
+                let state = AppState { pool, user_id, writes };
                 app_handle.manage(state);
             });
 
@@ -52,31 +79,206 @@ pub fn run() {
             // Trade commands
             commands::get_trades,
             commands::get_trade,
+            commands::resolve_trade_ref,
+            commands::find_trades,
+            commands::get_trade_stats,
             commands::create_trade,
+            commands::create_trades,
             commands::update_trade,
             commands::delete_trade,
+            commands::set_trade_result_override,
+            commands::set_trade_option_outcome,
+            commands::set_trade_borrow_availability,
+            commands::set_instrument_tick_size,
+            commands::set_instrument_margin_requirement,
+            commands::update_execution_quality,
             // Account commands
             commands::get_accounts,
             commands::create_account,
+            commands::set_account_paper,
+            commands::set_account_starting_balance,
+            commands::lock_period,
+            commands::unlock_period,
+            commands::get_period_locks,
+            commands::get_commission_schedule,
+            commands::save_commission_schedule,
+            commands::add_seed_position,
+            commands::get_seed_positions,
+            commands::delete_seed_position,
+            commands::add_financing_charge,
+            commands::get_financing_charges,
+            commands::delete_financing_charge,
+            commands::import_financing_charges,
+            commands::get_account_performance,
+            commands::add_cash_transaction,
+            commands::get_cash_transactions,
+            commands::delete_cash_transaction,
+            commands::get_account_returns,
             // Metrics commands
             commands::get_daily_performance,
+            commands::save_daily_closes,
             commands::get_period_metrics,
             commands::get_all_time_metrics,
             commands::get_equity_curve,
+            commands::get_equity_curve_series,
+            commands::get_metric_snapshots,
+            commands::get_portfolio_heat,
+            commands::get_margin_utilization,
+            commands::get_r_expectancy,
+            commands::get_position_sizing_audit,
+            commands::get_target_calibration,
+            commands::get_trade_sequence_report,
+            commands::get_trade_type_report,
+            commands::get_strategy_trends,
+            commands::get_entry_cohort_report,
+            commands::get_skill_progression,
+            commands::get_trade_clusters,
+            commands::get_symbol_month_matrix,
+            commands::get_overtrading_report,
+            commands::get_day_timeline,
+            commands::get_execution_quality,
+            commands::get_exchange_report,
+            commands::get_options_premium_report,
+            commands::get_short_side_report,
+            commands::get_futures_session_report,
+            commands::get_crypto_tax_report,
+            commands::reconcile,
+            // Carrying cost commands
+            commands::add_carrying_cost,
+            commands::get_carrying_costs,
+            commands::delete_carrying_cost,
+            commands::get_carrying_cost_report,
+            // Dividend commands
+            commands::add_dividend,
+            commands::get_dividends,
+            commands::get_trade_dividends,
+            commands::delete_dividend,
+            commands::get_dividend_income_report,
+            // Checklist commands
+            commands::check_checklist_item,
+            commands::get_trade_checklist,
+            commands::uncheck_checklist_item,
+            commands::get_checklist_report,
+            // Market event commands
+            commands::add_market_event,
+            commands::get_market_events,
+            commands::delete_market_event,
+            commands::import_market_events,
+            commands::get_trade_market_events,
+            commands::get_event_day_report,
+            commands::get_earnings_window_report,
+            // Day condition commands
+            commands::set_day_conditions,
+            commands::get_day_conditions,
+            commands::get_day_condition_report,
+            // Archive commands
+            commands::archive_trade_year,
+            commands::unarchive_trade_year,
+            commands::archive_trades_older_than,
+            commands::get_archived_years,
+            commands::get_archived_trades,
+            // Journal commands
+            commands::get_journal_entries,
+            commands::save_journal_entry,
+            commands::summarize_day,
+            // Goal commands
+            commands::evaluate_goal_week,
+            commands::get_goal_scorecards,
+            commands::get_process_streaks,
+            // Strategy rule commands
+            commands::create_strategy_rule,
+            commands::get_strategy_rules,
+            commands::delete_strategy_rule,
+            commands::set_watchlist,
+            commands::get_watchlists,
+            // Stop adjustment commands
+            commands::record_stop_adjustment,
+            commands::get_trade_stops,
+            commands::delete_stop_adjustment,
+            commands::get_stop_adjustment_report,
+            // Position roll commands
+            commands::get_unresolved_positions,
+            commands::roll_position,
+            commands::clear_position_roll,
+            // FX rate commands
+            commands::save_fx_rates,
+            commands::get_fx_rates,
+            commands::delete_fx_rate,
+            commands::refresh_fx_rates,
             // Import commands
             commands::select_tlg_file,
             commands::preview_tlg_import,
             commands::execute_tlg_import,
+            commands::select_csv_file,
+            commands::list_supported_brokers,
+            commands::preview_broker_import,
+            commands::import_ninjatrader_trades,
+            commands::import_metatrader_statement,
+            commands::import_tradingview_trades,
+            commands::update_imported_trades,
+            commands::get_import_history,
+            commands::undo_import,
+            commands::get_watch_folder,
+            commands::save_watch_folder,
             commands::get_trade_executions,
+            commands::select_import_error_file,
+            commands::export_import_errors,
+            // Export commands
+            commands::select_export_file,
+            commands::export_anonymized_trades,
+            commands::select_csv_export_file,
+            commands::export_symbol_month_matrix,
+            commands::select_calendar_export_file,
+            commands::export_trading_calendar,
             // Market data commands
             commands::get_trade_candles,
             commands::get_market_tape,
+            commands::backfill_excursions,
+            commands::backfill_entry_atr,
             // Settings commands
             commands::get_alpaca_keys_status,
             commands::save_alpaca_keys,
             commands::clear_alpaca_keys,
             commands::get_manual_trade_timezone,
             commands::save_manual_trade_timezone,
+            commands::get_include_dividends_in_pnl,
+            commands::save_include_dividends_in_pnl,
+            commands::get_include_paper_trades,
+            commands::save_include_paper_trades,
+            commands::get_result_basis,
+            commands::save_result_basis,
+            commands::get_date_attribution,
+            commands::save_date_attribution,
+            commands::get_crypto_cost_basis_method,
+            commands::save_crypto_cost_basis_method,
+            commands::get_trade_type_thresholds,
+            commands::save_trade_type_thresholds,
+            commands::get_default_risk_per_trade,
+            commands::save_default_risk_per_trade,
+            commands::get_account_risk_pct,
+            commands::save_account_risk_pct,
+            commands::get_max_trades_per_symbol_per_day,
+            commands::save_max_trades_per_symbol_per_day,
+            commands::get_max_margin_utilization_pct,
+            commands::save_max_margin_utilization_pct,
+            commands::get_reporting_currency,
+            commands::save_reporting_currency,
+            commands::get_daily_loss_limit,
+            commands::save_daily_loss_limit,
+            commands::get_reminders,
+            commands::save_reminders,
+            commands::get_end_of_day_summary_time,
+            commands::save_end_of_day_summary_time,
+            commands::get_process_goals,
+            commands::save_process_goals,
+            commands::get_storage_usage,
+            commands::run_maintenance,
+            commands::rebuild_daily_performance,
+            commands::verify_calculations,
+            commands::get_config,
+            commands::get_last_maintenance_report,
+            commands::get_maintenance_interval_days,
+            commands::save_maintenance_interval_days,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
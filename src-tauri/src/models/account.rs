@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,5 +7,55 @@ pub struct Account {
     pub user_id: String,
     pub name: String,
     pub base_currency: String,
+    pub is_paper: bool, // Simulated account, excluded from headline metrics by default
+    pub starting_balance: Option<f64>, // Used for percent-based drawdowns
     pub created_at: DateTime<Utc>,
 }
+
+/// Finalized period of an account; trades through this date are read-only
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodLock {
+    pub account_id: String,
+    pub locked_through: NaiveDate,
+    pub locked_at: DateTime<Utc>,
+}
+
+impl PeriodLock {
+    /// Fail if a trade dated on the given day would change the locked period
+    pub fn ensure_unlocked(&self, trade_date: NaiveDate) -> Result<(), String> {
+        if trade_date <= self.locked_through {
+            return Err(format!(
+                "Trade date {} is in a locked period (locked through {}). Unlock the period to make changes.",
+                trade_date, self.locked_through
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Broker commission schedule of an account, used to estimate fees missing from imports
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommissionSchedule {
+    pub account_id: String,
+    pub per_order: f64,
+    pub per_share: f64,    // Stocks
+    pub per_contract: f64, // Options
+    pub minimum_per_order: f64,
+}
+
+impl CommissionSchedule {
+    /// Estimated commission for a single execution
+    pub fn estimate_fees(&self, quantity: f64, is_option: bool) -> f64 {
+        let per_unit = if is_option { self.per_contract } else { self.per_share };
+        let fees = self.per_order + per_unit * quantity.abs();
+        fees.max(self.minimum_per_order)
+    }
+
+    /// Whether the schedule charges anything at all
+    pub fn is_empty(&self) -> bool {
+        self.per_order == 0.0
+            && self.per_share == 0.0
+            && self.per_contract == 0.0
+            && self.minimum_per_order == 0.0
+    }
+}
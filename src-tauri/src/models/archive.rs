@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// A calendar year of trades moved to the archive database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedYear {
+    pub year: i32,
+    pub trade_count: i64,
+}
+
+/// Outcome of archiving every year past the retention period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveResult {
+    pub years: Vec<i32>,
+    pub trades_archived: u64,
+}
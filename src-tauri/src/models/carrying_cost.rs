@@ -0,0 +1,53 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CarryingCostType {
+    BorrowFee,
+    MarginInterest,
+    Swap,
+}
+
+impl CarryingCostType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CarryingCostType::BorrowFee => "borrow_fee",
+            CarryingCostType::MarginInterest => "margin_interest",
+            CarryingCostType::Swap => "swap",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "borrow_fee" => Some(CarryingCostType::BorrowFee),
+            "margin_interest" => Some(CarryingCostType::MarginInterest),
+            "swap" => Some(CarryingCostType::Swap),
+            _ => None,
+        }
+    }
+}
+
+/// Cost of holding a position on a given date
+/// Positive amounts reduce net PnL, negative amounts (credits) increase it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CarryingCost {
+    pub id: String,
+    pub trade_id: String,
+    pub symbol: String, // Denormalized from the trade's instrument
+    pub cost_type: CarryingCostType,
+    pub cost_date: NaiveDate,
+    pub amount: f64,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for adding a carrying cost entry to a trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateCarryingCostInput {
+    pub trade_id: String,
+    pub cost_type: CarryingCostType,
+    pub cost_date: NaiveDate,
+    pub amount: f64,
+    pub notes: Option<String>,
+}
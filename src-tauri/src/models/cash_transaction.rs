@@ -0,0 +1,22 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Deposit (positive amount) or withdrawal (negative amount) of an account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CashTransaction {
+    pub id: String,
+    pub account_id: String,
+    pub transaction_date: NaiveDate,
+    pub amount: f64,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for recording a deposit or withdrawal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateCashTransactionInput {
+    pub account_id: String,
+    pub transaction_date: NaiveDate,
+    pub amount: f64,
+    pub notes: Option<String>,
+}
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// When a checklist item was checked relative to the trade's entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecklistPhase {
+    PreEntry,
+    PostEntry,
+}
+
+impl ChecklistPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChecklistPhase::PreEntry => "pre_entry",
+            ChecklistPhase::PostEntry => "post_entry",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "pre_entry" => Some(ChecklistPhase::PreEntry),
+            "post_entry" => Some(ChecklistPhase::PostEntry),
+            _ => None,
+        }
+    }
+}
+
+/// Checklist item checked on a trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistCheck {
+    pub id: String,
+    pub trade_id: String,
+    pub item: String,
+    pub phase: ChecklistPhase,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Input for checking a checklist item on a trade
+/// Checking an item again replaces its phase and timestamp
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckChecklistItemInput {
+    pub trade_id: String,
+    pub item: String,
+    pub phase: ChecklistPhase,
+    pub checked_at: Option<DateTime<Utc>>, // Defaults to now
+}
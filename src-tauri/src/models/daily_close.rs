@@ -0,0 +1,15 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Closing price of a symbol on a trading day
+/// High and low are optional; bars with both give the true ranges behind ATR at entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyClose {
+    pub symbol: String,
+    pub date: NaiveDate,
+    pub close: f64,
+    #[serde(default)]
+    pub high: Option<f64>,
+    #[serde(default)]
+    pub low: Option<f64>,
+}
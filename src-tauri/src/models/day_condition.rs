@@ -0,0 +1,40 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Market condition of a trading day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DayConditionType {
+    Trend,
+    Chop,
+    GapUp,
+    GapDown,
+}
+
+impl DayConditionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DayConditionType::Trend => "trend",
+            DayConditionType::Chop => "chop",
+            DayConditionType::GapUp => "gap_up",
+            DayConditionType::GapDown => "gap_down",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "trend" => Some(DayConditionType::Trend),
+            "chop" => Some(DayConditionType::Chop),
+            "gap_up" => Some(DayConditionType::GapUp),
+            "gap_down" => Some(DayConditionType::GapDown),
+            _ => None,
+        }
+    }
+}
+
+/// Conditions tagged on a trading day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayConditions {
+    pub date: NaiveDate,
+    pub conditions: Vec<DayConditionType>,
+}
@@ -0,0 +1,32 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Dividend received on a long position or paid on a short position
+/// Positive amounts are received, negative amounts are paid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dividend {
+    pub id: String,
+    pub user_id: String,
+    pub account_id: String,
+    pub instrument_id: String,
+    pub symbol: String, // Denormalized for convenience
+    pub trade_id: Option<String>, // Position held through the ex-date, if linked
+    pub ex_date: NaiveDate,
+    pub pay_date: Option<NaiveDate>,
+    pub amount: f64,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for recording a dividend
+/// When linked to a trade, the sign of the amount follows the trade direction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateDividendInput {
+    pub account_id: String,
+    pub symbol: String,
+    pub trade_id: Option<String>,
+    pub ex_date: NaiveDate,
+    pub pay_date: Option<NaiveDate>,
+    pub amount: f64,
+    pub notes: Option<String>,
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::{AssetClass, Direction, PeriodMetrics, Status, TradeResult};
+
+/// Trade with identifying details removed and dollar amounts scaled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnonymizedTrade {
+    pub trade_number: i32, // Position in the export, not the journal's trade number
+    pub symbol: String,
+    pub asset_class: AssetClass,
+    pub trade_date: NaiveDate,
+    pub direction: Direction,
+    pub status: Status,
+    pub quantity: Option<f64>, // Scaled
+    pub entry_price: f64,
+    pub exit_price: Option<f64>,
+    pub stop_loss_price: Option<f64>,
+    pub entry_time: Option<String>,
+    pub exit_time: Option<String>,
+    pub fees: f64,               // Scaled
+    pub gross_pnl: Option<f64>,  // Scaled
+    pub net_pnl: Option<f64>,    // Scaled
+    pub pnl_per_share: Option<f64>,
+    pub return_pct: Option<f64>, // Price move as a percentage of entry
+    pub r_multiple: Option<f64>,
+    pub result: Option<TradeResult>,
+    pub strategy: Option<String>,
+}
+
+/// Shareable export without account names, notes or real position sizes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnonymizedExport {
+    pub generated_at: DateTime<Utc>,
+    pub trade_count: i32,
+    pub metrics: PeriodMetrics, // Dollar values scaled, ratios unchanged
+    pub trades: Vec<AnonymizedTrade>,
+}
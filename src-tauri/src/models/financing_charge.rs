@@ -0,0 +1,82 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FinancingChargeType {
+    MarginInterest,
+    Financing,
+}
+
+impl FinancingChargeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FinancingChargeType::MarginInterest => "margin_interest",
+            FinancingChargeType::Financing => "financing",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "margin_interest" => Some(FinancingChargeType::MarginInterest),
+            "financing" => Some(FinancingChargeType::Financing),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FinancingChargeSource {
+    Manual,
+    Import, // From a broker statement CSV; replaced when the same month is imported again
+}
+
+impl FinancingChargeSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FinancingChargeSource::Manual => "manual",
+            FinancingChargeSource::Import => "import",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "manual" => Some(FinancingChargeSource::Manual),
+            "import" => Some(FinancingChargeSource::Import),
+            _ => None,
+        }
+    }
+}
+
+/// Monthly financing charge of an account, kept apart from per-trade stats
+/// Positive amounts are costs, negative amounts are credits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinancingCharge {
+    pub id: String,
+    pub account_id: String,
+    pub charge_month: NaiveDate, // First day of the month charged
+    pub charge_type: FinancingChargeType,
+    pub amount: f64,
+    pub source: FinancingChargeSource,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for recording a financing charge; any day of the month may be given
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateFinancingChargeInput {
+    pub account_id: String,
+    pub charge_month: NaiveDate,
+    pub charge_type: FinancingChargeType,
+    pub amount: f64,
+    pub notes: Option<String>,
+}
+
+/// Result of importing financing charges from a CSV file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FinancingImportResult {
+    pub imported: i32,
+    pub replaced: i32, // Earlier imports of the same month and type that were overwritten
+    pub errors: Vec<String>, // One message per rejected line
+}
@@ -0,0 +1,45 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FxRateSource {
+    #[default]
+    Manual, // Entered by the user; never replaced by fetched rates
+    Ecb,    // ECB reference rate fetched by the FX provider
+}
+
+impl FxRateSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FxRateSource::Manual => "manual",
+            FxRateSource::Ecb => "ecb",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "manual" => Some(FxRateSource::Manual),
+            "ecb" => Some(FxRateSource::Ecb),
+            _ => None,
+        }
+    }
+}
+
+/// Value of one unit of the base currency in the quote currency on a day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FxRate {
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub date: NaiveDate,
+    pub rate: f64,
+    #[serde(default)]
+    pub source: FxRateSource,
+}
+
+/// Result of fetching FX rates for the currencies of a user's accounts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FxRefreshResult {
+    pub pairs: Vec<String>, // e.g. EUR/USD
+    pub fetched: i32,       // Rates stored or updated; manual rates of the same day are kept
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Process goals a week is scored against; unset goals are not evaluated
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessGoals {
+    pub max_trades_per_day: Option<i32>,
+    pub journal_every_day: bool, // Every trade of a traded day has notes
+    pub max_risk_per_trade: Option<f64>, // Dollar risk from the stop loss
+}
+
+/// How a week measured up against the process goals, independent of PnL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessScore {
+    pub max_trades_per_day: Option<i32>,
+    pub days_traded: i32,
+    pub days_over_trade_limit: i32,
+    pub journal_every_day: bool,
+    pub days_journaled: i32,
+    pub max_risk_per_trade: Option<f64>,
+    pub trades_over_risk: i32,
+    pub trades_without_stop: i32, // Count against the risk goal since their risk is unknown
+    pub goals_met: i32,
+    pub goals_evaluated: i32,
+}
+
+/// Results of the same week, kept apart from the process score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutcomeScore {
+    pub trade_count: i32,
+    pub net_pnl: f64,
+    pub win_rate: Option<f64>, // Excluding breakeven
+}
+
+/// Stored weekly goal-compliance scorecard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalScorecard {
+    pub week_start: NaiveDate, // Monday
+    pub week_end: NaiveDate,   // Sunday
+    pub process: ProcessScore,
+    pub outcome: OutcomeScore,
+    pub evaluated_at: DateTime<Utc>,
+}
+
+/// Run of consecutive traded days that kept a process habit
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProcessStreak {
+    pub current: i32, // Ending at the most recent traded day
+    pub longest: i32,
+    pub days_met: i32,
+    pub last_broken: Option<NaiveDate>,
+}
+
+/// Non-PnL streaks over traded days; days without trades neither extend nor break a streak
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessStreaks {
+    pub days_traded: i32,
+    pub last_traded: Option<NaiveDate>,
+    pub journaled: ProcessStreak, // Every trade of the day has notes
+    pub daily_loss_limit: Option<f64>,
+    pub within_loss_limit: Option<ProcessStreak>, // Only when a daily loss limit is set
+}
@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Error messages kept with an import batch; the rest are only counted
+pub const MAX_BATCH_ERRORS: usize = 20;
+
+/// One executed import, whose trades can be deleted together
+/// Imports that created nothing are kept as well, for the import history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportBatch {
+    pub id: String,
+    pub account_id: String,
+    pub file_name: Option<String>,
+    pub trade_count: i32, // Trades the import created
+    pub execution_count: i32,
+    pub skipped_duplicates: i32,
+    pub error_count: i32,
+    pub errors: Vec<String>, // The first error messages, up to MAX_BATCH_ERRORS
+    pub created_at: DateTime<Utc>,
+    pub undone_at: Option<DateTime<Utc>>,
+}
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,5 +7,19 @@ pub struct Instrument {
     pub symbol: String,
     pub asset_class: String,
     pub exchange: Option<String>,
+    #[serde(default)]
+    pub tick_size: Option<f64>, // Minimum price increment; None uses the asset class default
+    #[serde(default)]
+    pub margin_requirement: Option<f64>, // Fraction of position value held as margin; None is fully paid
+    #[serde(default)]
+    pub underlying_symbol: Option<String>, // The symbol itself for non-options
+    #[serde(default)]
+    pub option_type: Option<String>, // "call" or "put"
+    #[serde(default)]
+    pub strike_price: Option<f64>,
+    #[serde(default)]
+    pub expiration_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub multiplier: Option<f64>, // Contract multiplier; None uses the asset class default
     pub created_at: DateTime<Utc>,
 }
@@ -0,0 +1,50 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use super::DailyPerformance;
+
+/// Whether the trader has gone over a journal entry yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalEntryStatus {
+    Draft,
+    Reviewed,
+}
+
+impl JournalEntryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JournalEntryStatus::Draft => "draft",
+            JournalEntryStatus::Reviewed => "reviewed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "draft" => Some(JournalEntryStatus::Draft),
+            "reviewed" => Some(JournalEntryStatus::Reviewed),
+            _ => None,
+        }
+    }
+}
+
+/// Journal entry for one trading day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub id: String,
+    pub entry_date: NaiveDate,
+    pub content: String,
+    pub status: JournalEntryStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Summary of a trading day, emitted after the summary time with its draft journal entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndOfDaySummary {
+    pub date: NaiveDate,
+    pub performance: Option<DailyPerformance>, // None when no trade entered that day is closed
+    pub trades_opened: i32,
+    pub open_positions: i32, // Entered on or before the day and not yet closed
+    pub symbols: Vec<String>,
+    pub journal_entry: JournalEntry,
+}
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a database maintenance run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceReport {
+    pub ran_at: DateTime<Utc>,
+    pub bytes_before: i64,
+    pub bytes_after: i64,
+    pub bytes_reclaimed: i64,
+    pub integrity_issues: Vec<String>, // Empty when the integrity check passed
+    pub wal_frames_checkpointed: Option<i64>, // None when the database is not in WAL mode
+}
+
+/// Derived metrics of the journal computed two independent ways, per trade in Rust and in SQL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalculationReport {
+    pub checked_at: DateTime<Utc>,
+    pub trade_count: i64,
+    pub discrepancies: Vec<CalculationDiscrepancy>, // Empty when both ways agree
+}
+
+/// A metric on which the two computations disagree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalculationDiscrepancy {
+    pub scope: String, // "Journal", an account name, or a trade's reference code
+    pub metric: String,
+    pub per_trade: String, // From calculate_derived_fields, summed for aggregates
+    pub sql: String, // From the derived trade query and its aggregate
+}
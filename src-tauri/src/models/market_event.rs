@@ -0,0 +1,62 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarketEventType {
+    Fomc,
+    Cpi,
+    Earnings,
+    Other,
+}
+
+impl MarketEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketEventType::Fomc => "fomc",
+            MarketEventType::Cpi => "cpi",
+            MarketEventType::Earnings => "earnings",
+            MarketEventType::Other => "other",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "fomc" => Some(MarketEventType::Fomc),
+            "cpi" => Some(MarketEventType::Cpi),
+            "earnings" => Some(MarketEventType::Earnings),
+            "other" => Some(MarketEventType::Other),
+            _ => None,
+        }
+    }
+}
+
+/// Scheduled event or catalyst on a given date
+/// Events without a symbol apply to every trade on that date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketEvent {
+    pub id: String,
+    pub user_id: String,
+    pub event_date: NaiveDate,
+    pub event_type: MarketEventType,
+    pub symbol: Option<String>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for recording a market event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateMarketEventInput {
+    pub event_date: NaiveDate,
+    pub event_type: MarketEventType,
+    pub symbol: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Result of importing market events from a CSV file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketEventImportResult {
+    pub imported: i32,
+    pub duplicates: i32,
+    pub errors: Vec<String>, // One message per rejected line
+}
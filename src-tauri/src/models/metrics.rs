@@ -1,54 +1,181 @@
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use super::{CostBasisMethod, Direction};
 
 /// Daily performance aggregation for calendar view
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DailyPerformance {
     pub date: NaiveDate,
     pub realized_net_pnl: f64,
+    pub gross_pnl: f64,
+    pub total_fees: f64,
+    pub total_volume: f64, // Shares/contracts traded
+    pub largest_win: Option<f64>,
+    pub largest_loss: Option<f64>,
     pub trade_count: i32,
     pub win_count: i32,
     pub loss_count: i32,
+    pub breakeven_count: i32,
+    pub scratch_rate: f64, // Breakeven trades / trade count
+    pub avg_trade_pnl: f64,
+    #[serde(default)]
+    pub unrealized_pnl_change: Option<f64>, // Open positions marked to the day's close; only when requested
+}
+
+/// An account's key metrics as of the close of a day, stored nightly for long-term trend charts
+/// Counts and PnL run from the account's first closed trade; drawdowns are below the highest
+/// cumulative PnL so far.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricSnapshot {
+    pub account_id: String,
+    pub date: NaiveDate,
+    pub balance: Option<f64>, // Starting balance plus PnL and net deposits; None without a starting balance
+    pub cumulative_pnl: f64,
+    pub drawdown: f64,
+    pub max_drawdown: f64,
+    pub trade_count: i32,
+    pub win_count: i32,
+    pub loss_count: i32,
+    pub win_rate: Option<f64>, // Wins / (wins + losses); None before the first decisive trade
 }
 
 /// Period metrics for dashboard analytics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PeriodMetrics {
     pub total_net_pnl: f64,
+    #[serde(default)]
+    pub total_gross_pnl: f64, // Before fees and other costs
     pub trade_count: i32,
     pub win_count: i32,
     pub loss_count: i32,
     pub breakeven_count: i32,
     pub win_rate: Option<f64>,
+    pub win_rate_low: Option<f64>,  // 95% Wilson interval around win_rate for its sample size
+    pub win_rate_high: Option<f64>,
+    pub override_count: i32,           // Trades with a manual result override
+    pub adjusted_win_rate: Option<f64>, // Win rate using overridden results
     pub avg_win: Option<f64>,
     pub avg_loss: Option<f64>,
     pub profit_factor: Option<f64>,
     pub expectancy: Option<f64>,
+    pub expectancy_low: Option<f64>, // Expectancy at the bounds of the win rate interval; avg win and loss held fixed
+    pub expectancy_high: Option<f64>,
     pub max_drawdown: f64,
+    pub max_drawdown_r: Option<f64>,   // In units of the average risk per trade
+    pub max_drawdown_pct: Option<f64>, // Fraction of peak equity; needs a starting balance
     pub max_win_streak: i32,
     pub max_loss_streak: i32,
+    pub current_win_streak: i32, // Consecutive wins ending at the latest trade
+    pub current_loss_streak: i32,
 }
 
 impl Default for PeriodMetrics {
     fn default() -> Self {
         Self {
             total_net_pnl: 0.0,
+            total_gross_pnl: 0.0,
             trade_count: 0,
             win_count: 0,
             loss_count: 0,
             breakeven_count: 0,
             win_rate: None,
+            win_rate_low: None,
+            win_rate_high: None,
+            override_count: 0,
+            adjusted_win_rate: None,
             avg_win: None,
             avg_loss: None,
             profit_factor: None,
             expectancy: None,
+            expectancy_low: None,
+            expectancy_high: None,
             max_drawdown: 0.0,
+            max_drawdown_r: None,
+            max_drawdown_pct: None,
             max_win_streak: 0,
             max_loss_streak: 0,
+            current_win_streak: 0,
+            current_loss_streak: 0,
         }
     }
 }
 
+/// Expectancy in R-multiples over closed trades, alongside ATR-multiples
+/// Trades without a stop loss use the default risk per trade when one is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RExpectancyReport {
+    pub trade_count: i32,         // Closed trades with a net PnL
+    pub stop_based_count: i32,    // R from the stop loss
+    pub assumed_risk_count: i32,  // R from the default risk per trade
+    pub skipped_count: i32,       // No stop loss and no default risk
+    pub total_r: f64,
+    pub expectancy_r: Option<f64>, // Average R per measured trade
+    pub avg_win_r: Option<f64>,
+    pub avg_loss_r: Option<f64>,
+    #[serde(default)]
+    pub atr_trade_count: i32, // Closed trades with an ATR at entry
+    #[serde(default)]
+    pub total_atr: f64,       // Sum of ATR-multiples (pnl_per_share / entry_atr)
+    #[serde(default)]
+    pub expectancy_atr: Option<f64>,
+}
+
+/// How often trades planned at a minimum reward-to-risk actually got there
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TargetCalibrationReport {
+    pub min_planned_r: f64,
+    pub planned_count: i32,          // Closed trades with a stop and a target at or above min_planned_r
+    pub reached_count: i32,          // Moved min_planned_r in favor before exiting
+    pub stopped_count: i32,          // Exited at or through the stop without reaching it
+    pub reach_rate: Option<f64>,     // reached_count / planned_count
+    pub avg_planned_r: Option<f64>,
+    pub avg_realized_r: Option<f64>,
+}
+
+/// Dollar risk taken on one closed trade vs the risk allowed by the account risk-% setting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionSizingPoint {
+    pub trade_id: String,
+    pub account_id: String,
+    pub symbol: String,
+    pub date: NaiveDate,
+    pub account_equity: f64, // Starting balance plus the account's earlier closed PnL
+    pub allowed_risk: f64,
+    pub actual_risk: f64,    // Initial stop risk: risk_per_share × quantity × multiplier
+    pub risk_pct: f64,       // actual_risk as a percent of account_equity
+    pub oversized: bool,
+    pub net_pnl: f64,
+}
+
+/// Position sizing audit over closed trades, in trade order
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PositionSizingReport {
+    pub risk_pct_setting: f64,
+    pub points: Vec<PositionSizingPoint>,
+    pub oversized_count: i32,
+    pub oversized_net_pnl: f64, // PnL contributed by oversized trades
+    pub total_net_pnl: f64,     // PnL of all audited trades
+    pub skipped_no_stop: i32,
+    pub skipped_no_balance: i32, // Account has no starting balance or no positive equity
+}
+
+/// Execution in a day's timeline with running position and PnL of its symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEvent {
+    pub trade_id: String,
+    pub symbol: String,
+    pub direction: Direction,
+    pub execution_type: String, // "entry" or "exit"
+    pub execution_time: Option<String>,
+    pub quantity: f64,
+    pub price: f64,
+    pub fees: f64,
+    pub position: f64,     // Signed position in the symbol after this fill (short < 0)
+    pub realized_pnl: f64, // Realized by this fill, net of its fees
+    pub symbol_pnl: f64,   // Running realized PnL of the symbol for the day
+    pub day_pnl: f64,      // Running realized PnL across symbols for the day
+}
+
 /// Point on the equity curve
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EquityPoint {
@@ -56,3 +183,436 @@ pub struct EquityPoint {
     pub cumulative_pnl: f64,
     pub drawdown: f64,
 }
+
+/// Grouping for overlaid equity curves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EquityCurveGrouping {
+    Strategy,
+    Account,
+}
+
+/// Equity curve of one strategy or account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquitySeries {
+    pub key: String, // Strategy name or account ID
+    pub points: Vec<EquityPoint>,
+}
+
+/// Performance of a strategy over the window of days ending on a day it traded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyTrendPoint {
+    pub date: NaiveDate,
+    pub trade_count: i32, // Closed trades in the window
+    pub net_pnl: f64,
+    pub expectancy: Option<f64>, // Average net PnL per trade
+    pub win_rate: Option<f64>,   // Excluding breakeven
+}
+
+/// Rolling expectancy of one strategy over time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyTrend {
+    pub strategy: String,
+    pub points: Vec<StrategyTrendPoint>,
+}
+
+/// Total stop-based risk of positions open on a given day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioHeatPoint {
+    pub date: NaiveDate,
+    pub open_risk: f64,
+    pub open_positions: i32,
+    pub unprotected_positions: i32, // Open positions without a stop loss
+}
+
+/// Peak margin and leverage of one account on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarginUtilizationDay {
+    pub date: NaiveDate,
+    pub account_id: String,
+    pub account_equity: f64,   // Starting balance plus PnL of trades closed before the day
+    pub peak_margin: f64,      // Largest margin held at any moment of the day
+    pub peak_notional: f64,    // Largest position value held at any moment of the day
+    pub utilization_pct: f64,  // peak_margin as a percent of account_equity
+    pub leverage: f64,         // peak_notional / account_equity
+    pub over_threshold: bool,
+}
+
+/// Daily margin utilization per account against the configured threshold
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarginUtilizationReport {
+    pub max_utilization_pct: f64,
+    pub days: Vec<MarginUtilizationDay>, // Sorted by date, then account
+    pub over_threshold_count: i32,
+    pub skipped_no_balance: i32, // Account-days with positions but no starting balance or no positive equity
+}
+
+/// Trade PnL and financing charges of one account month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountPerformanceMonth {
+    pub month: NaiveDate, // First day of the month
+    pub trade_net_pnl: f64,
+    pub financing_costs: f64, // Positive = cost
+    pub net_pnl: f64,         // Trade PnL minus financing costs
+}
+
+/// Account-level performance including financing charges that per-trade stats leave out
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountPerformance {
+    pub account_id: String,
+    pub trade_net_pnl: f64,
+    pub financing_costs: f64,
+    pub net_pnl: f64,
+    pub starting_balance: Option<f64>,
+    pub return_pct: Option<f64>, // net_pnl / starting_balance; None without a positive starting balance
+    pub months: Vec<AccountPerformanceMonth>,
+}
+
+/// One day of an account's return series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountReturnDay {
+    pub date: NaiveDate,
+    pub net_flows: f64,      // Deposits minus withdrawals, at the start of the day
+    pub pnl: f64,            // Closed trade PnL minus financing charged that day
+    pub end_value: f64,
+    pub cumulative_twr_pct: f64,
+}
+
+/// Time- and money-weighted returns of an account over a period
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountReturns {
+    pub account_id: String,
+    pub start_value: f64, // Starting balance plus flows and PnL before the period
+    pub end_value: f64,
+    pub net_flows: f64,
+    pub total_pnl: f64,
+    pub twr_pct: Option<f64>, // None when the account has no positive value to grow
+    pub mwr_pct: Option<f64>, // Period (not annualized) IRR; None when it has no solution
+    pub days: Vec<AccountReturnDay>, // Days with flows or PnL
+}
+
+/// Slippage aggregated over a group of executions (symbol, hour, order type)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlippageBucket {
+    pub key: String,
+    pub execution_count: i32,
+    pub total_slippage: f64,      // Dollars; positive = cost
+    pub avg_slippage_cents: f64,  // Per share/contract
+    pub avg_slippage_ticks: f64,
+}
+
+/// Execution quality report for fills with a recorded intended price
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionQualityReport {
+    pub execution_count: i32,
+    pub total_slippage: f64,
+    pub by_symbol: Vec<SlippageBucket>,
+    pub by_hour: Vec<SlippageBucket>,
+    pub by_order_type: Vec<SlippageBucket>,
+}
+
+/// Fill statistics for one exchange or route
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeBucket {
+    pub exchange: String,
+    pub fill_count: i32,
+    pub total_fees: f64,
+    pub avg_fees_per_fill: f64,
+    pub slippage_fill_count: i32, // Fills with a recorded intended price
+    pub avg_slippage_cents: Option<f64>, // Per share/contract; None without intended prices
+}
+
+/// Carrying costs aggregated over a group of entries (cost type or symbol)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CarryingCostBucket {
+    pub key: String,
+    pub entry_count: i32,
+    pub total_amount: f64,
+}
+
+/// Carrying cost report for borrow fees, margin interest and swaps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CarryingCostReport {
+    pub total_amount: f64,
+    pub entry_count: i32,
+    pub by_type: Vec<CarryingCostBucket>,
+    pub by_symbol: Vec<CarryingCostBucket>,
+}
+
+/// Checklist discipline: trades confirmed before entry vs entered before confirmation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChecklistReport {
+    pub checklist_trade_count: i32, // Closed trades with at least one checked item
+    pub confirmed_count: i32,       // All items checked before entry
+    pub entered_before_confirmation_count: i32,
+    pub entered_before_confirmation_rate: Option<f64>,
+    pub confirmed_net_pnl: f64,
+    pub entered_before_confirmation_net_pnl: f64,
+    pub entered_before_confirmation_trade_ids: Vec<String>,
+}
+
+/// Stop discipline: closed trades whose stop was widened vs trades whose stop was never moved away from entry
+/// A larger loss is one beyond the initial stop's 1R.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StopAdjustmentReport {
+    pub trade_count: i32,   // Closed trades with an initial stop
+    pub widened_count: i32, // Stop moved further from entry at least once
+    pub widened_larger_loss_count: i32,
+    pub widened_larger_loss_rate: Option<f64>,
+    pub widened_avg_r: Option<f64>,
+    pub kept_count: i32,
+    pub kept_larger_loss_count: i32,
+    pub kept_larger_loss_rate: Option<f64>,
+    pub kept_avg_r: Option<f64>,
+    pub widened_trade_ids: Vec<String>,
+}
+
+/// Closed-trade performance over a group of trades (e.g. event days)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceBucket {
+    pub key: String,
+    pub trade_count: i32,
+    pub win_count: i32,
+    pub loss_count: i32,
+    pub net_pnl: f64,
+    pub avg_net_pnl: Option<f64>,
+    pub win_rate: Option<f64>, // Excluding breakeven
+}
+
+/// Closed trades at one end of a trading history
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SkillSample {
+    pub trade_count: i32,
+    pub first_date: Option<NaiveDate>,
+    pub last_date: Option<NaiveDate>,
+    pub win_rate: Option<f64>, // Excluding breakeven
+    pub avg_r: Option<f64>,
+    pub r_trade_count: i32, // Trades with an R-multiple
+    pub mistakes_per_trade: Option<f64>,
+}
+
+/// Change of one measure from the earliest to the most recent trades, with a two-sided z-test
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillComparison {
+    pub difference: f64, // Recent minus earliest
+    pub z_score: f64,
+    pub p_value: f64,
+    pub significant: bool, // p_value below 0.05
+}
+
+/// The first closed trades against the most recent ones, to tell whether trading is improving
+/// Mistakes are missing stops, widened stops and entries before the checklist was confirmed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillProgressionReport {
+    pub sample_size: i32, // Trades in each sample
+    pub earliest: SkillSample,
+    pub recent: SkillSample,
+    pub win_rate: Option<SkillComparison>,
+    pub avg_r: Option<SkillComparison>,
+    pub mistakes_per_trade: Option<SkillComparison>, // Lower is better
+}
+
+/// Closed trades grouped with the trades most like them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeCluster {
+    pub performance: PerformanceBucket, // Keyed by rank, "1" being the best average net PnL
+    pub traits: Vec<String>,            // What sets the cluster apart, e.g. "Earlier entries (avg 09:48)"
+    pub avg_entry_minute: Option<f64>,  // Minutes after midnight
+    pub avg_hold_minutes: Option<f64>,
+    pub avg_r: Option<f64>,
+    pub avg_atr_pct: Option<f64>, // ATR at entry as a fraction of the entry price
+    pub top_symbols: Vec<String>, // Most traded first, up to three
+    pub tags: Vec<String>,        // Tags on at least half the cluster's trades
+    pub trade_ids: Vec<String>,
+}
+
+/// Closed trades clustered by time of day, hold time, R, symbol volatility and tags
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TradeClusterReport {
+    pub trade_count: i32,
+    pub clusters: Vec<TradeCluster>, // Best average net PnL first
+}
+
+/// Closed trades entered in one month, compared with the month before it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryCohort {
+    pub performance: PerformanceBucket, // Keyed by entry month, e.g. "2024-01"
+    pub expectancy_change: Option<f64>, // Average net PnL minus that of the previous cohort
+}
+
+/// Closed trades of one symbol in one month
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SymbolMonthCell {
+    pub net_pnl: f64,
+    pub trade_count: i32,
+}
+
+/// One symbol's row of the symbol by month matrix
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolMonthRow {
+    pub symbol: String,
+    pub net_pnl: f64,
+    pub trade_count: i32,
+    pub cells: Vec<SymbolMonthCell>, // One per month of the matrix; zero for months without trades
+}
+
+/// Net PnL and trade count of closed trades by symbol and month, for a heatmap
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolMonthMatrix {
+    pub months: Vec<String>, // Every month from the first to the last with trades, e.g. "2024-01"
+    pub rows: Vec<SymbolMonthRow>,
+}
+
+/// Costs of one side (long or short) against its performance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SideCostSummary {
+    pub performance: PerformanceBucket,
+    pub total_fees: f64,
+    pub total_carrying_costs: f64, // Borrow fees, margin interest and swaps
+    pub cost_per_trade: Option<f64>,
+    pub cost_pct_of_notional: Option<f64>, // Fees and carrying costs per dollar of entry notional, in percent
+}
+
+/// Short trades grouped by calendar days held
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldingDaysBucket {
+    pub label: String,
+    pub min_days: i64,
+    pub max_days: Option<i64>, // None = open-ended
+    pub trade_count: i32,
+    pub net_pnl: f64,
+    pub carrying_costs: f64,
+}
+
+/// Short-side analytics: costs vs the long side, holding periods and borrow availability
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortSideReport {
+    pub long: SideCostSummary,
+    pub short: SideCostSummary,
+    pub short_cost_premium_pct: Option<f64>, // Short minus long cost_pct_of_notional
+    pub short_holding_days: Vec<HoldingDaysBucket>,
+    pub by_borrow_availability: Vec<PerformanceBucket>, // Key "unknown" for short trades without one
+}
+
+/// Futures performance by contract month and by the session trades were entered in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuturesSessionReport {
+    pub trade_count: i32,
+    pub by_contract_month: Vec<PerformanceBucket>, // Key YYYY-MM, or "unknown" without a month code
+    pub by_session: Vec<PerformanceBucket>, // Keys "rth", "overnight" or "unknown"
+}
+
+/// Part of a crypto disposal matched to one acquired lot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LotDisposal {
+    pub trade_id: String, // Trade the disposal closed part of
+    pub lot_trade_id: String, // Trade the lot was acquired in
+    pub symbol: String,
+    pub quantity: f64,
+    pub acquired_date: NaiveDate,
+    pub disposed_date: NaiveDate,
+    pub cost_basis: f64, // Including the lot's share of its entry fees
+    pub proceeds: f64, // Net of the disposal's share of its exit fees
+    pub gain: f64,
+    pub long_term: bool, // Held for more than a year
+}
+
+/// Realized crypto gains with disposals matched to acquisitions by a cost-basis method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CryptoTaxReport {
+    pub method: CostBasisMethod,
+    pub proceeds: f64,
+    pub cost_basis: f64,
+    pub short_term_gain: f64,
+    pub long_term_gain: f64,
+    pub total_gain: f64,
+    pub unmatched_quantity: f64, // Sold without an acquisition on record; left out of the gains
+    pub disposals: Vec<LotDisposal>,
+}
+
+/// Performance of the Nth trade of the day across days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeSequenceBucket {
+    pub sequence: i32, // 1 = first trade of the day
+    pub trade_count: i32,
+    pub win_count: i32,
+    pub loss_count: i32,
+    pub net_pnl: f64,
+    pub avg_net_pnl: Option<f64>,
+    pub win_rate: Option<f64>,
+    pub avg_cumulative_pnl: f64, // Day PnL through this trade, averaged over days with this many trades
+}
+
+/// Symbol traded more often in a day than the overtrading threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OvertradingBreach {
+    pub date: NaiveDate,
+    pub symbol: String,
+    pub trade_count: i32,
+    pub net_pnl: f64,
+}
+
+/// Days and symbols that exceeded the max trades per symbol per day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OvertradingReport {
+    pub max_trades_per_symbol_per_day: i32,
+    pub breaches: Vec<OvertradingBreach>, // Sorted by date, then symbol
+    pub breach_net_pnl: f64,
+}
+
+/// Performance on days with market events vs days without
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventDayReport {
+    pub event_days: PerformanceBucket,
+    pub normal_days: PerformanceBucket,
+    pub by_event_type: Vec<PerformanceBucket>, // A trade counts once per event type on its date
+}
+
+/// Trades held through an earnings date vs closed in the days before it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EarningsWindowReport {
+    pub window_days: i64,
+    pub held_through: PerformanceBucket,  // Opened before and closed on or after the earnings date
+    pub closed_before: PerformanceBucket, // Closed within window_days before the earnings date
+    pub held_through_trade_ids: Vec<String>,
+}
+
+/// Option trades on one side of the premium: sold (short) or bought (long)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionsPremiumBucket {
+    pub performance: PerformanceBucket, // Closed trades
+    pub open_count: i32,
+    pub premium: f64, // Entry price × contracts × multiplier, open trades included
+    pub expired_count: i32,
+    pub assigned_count: i32,
+    pub exercised_count: i32,
+    pub assignment_rate: Option<f64>, // assigned_count / closed trade count
+}
+
+/// Premium-selling vs premium-buying option strategies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionsPremiumReport {
+    pub sold: OptionsPremiumBucket,
+    pub bought: OptionsPremiumBucket,
+    pub net_premium: f64, // Premium collected minus premium paid
+}
+
+/// Dividend income aggregated over a group of dividends (symbol or month)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DividendBucket {
+    pub key: String,
+    pub dividend_count: i32,
+    pub received: f64,
+    pub paid: f64, // Positive value of dividends paid on shorts
+    pub net: f64,
+}
+
+/// Dividend income report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DividendIncomeReport {
+    pub total_received: f64,
+    pub total_paid: f64,
+    pub net_income: f64,
+    pub by_symbol: Vec<DividendBucket>,
+    pub by_month: Vec<DividendBucket>,
+}
@@ -2,10 +2,52 @@ pub mod account;
 pub mod instrument;
 pub mod trade;
 pub mod metrics;
+pub mod carrying_cost;
+pub mod dividend;
+pub mod reconciliation;
+pub mod export;
+pub mod seed_position;
+pub mod checklist;
+pub mod market_event;
+pub mod day_condition;
+pub mod goal;
+pub mod strategy_rule;
+pub mod daily_close;
+pub mod stop_adjustment;
+pub mod financing_charge;
+pub mod cash_transaction;
+pub mod fx_rate;
+pub mod reminder;
+pub mod journal_entry;
+pub mod archive;
+pub mod maintenance;
+pub mod import_batch;
+pub mod watch_folder;
 
-pub use account::Account;
+pub use account::{Account, CommissionSchedule, PeriodLock};
 pub use instrument::Instrument;
-pub use trade::{Trade, CreateTradeInput, UpdateTradeInput, TradeWithDerived, DerivedFields, Direction, Status, TradeResult, AssetClass};
+pub use trade::{Trade, CreateTradeInput, BulkCreateResult, BulkRowError, UpdateTradeInput, TradeWithDerived, DerivedFields, Direction, Status, TradeResult, ResultBasis, DateAttribution, CostBasisMethod, TradeType, TradeTypeThresholds, RollType, RollPositionInput, OptionOutcome, BorrowAvailability, AssetClass, OrderType, ExecutionFill, TradeFilter, TradeStats};
 #[cfg(test)]
 pub use trade::ExitExecution;
-pub use metrics::{DailyPerformance, PeriodMetrics, EquityPoint};
+pub use carrying_cost::{CarryingCost, CarryingCostType, CreateCarryingCostInput};
+pub use dividend::{Dividend, CreateDividendInput};
+pub use reconciliation::{StatementTotals, ReconciliationIssue, ReconciliationIssueKind, ReconciliationReport};
+pub use export::{AnonymizedExport, AnonymizedTrade};
+pub use seed_position::{SeedPosition, CreateSeedPositionInput};
+pub use checklist::{ChecklistCheck, ChecklistPhase, CheckChecklistItemInput};
+pub use market_event::{MarketEvent, MarketEventType, CreateMarketEventInput, MarketEventImportResult};
+pub use day_condition::{DayConditionType, DayConditions};
+pub use goal::{GoalScorecard, OutcomeScore, ProcessGoals, ProcessScore, ProcessStreak, ProcessStreaks};
+pub use strategy_rule::{CreateStrategyRuleInput, StrategyRule, TradeTraits, Watchlist};
+pub use daily_close::DailyClose;
+pub use stop_adjustment::{RecordStopAdjustmentInput, StopAdjustment};
+pub use financing_charge::{CreateFinancingChargeInput, FinancingCharge, FinancingChargeSource, FinancingChargeType, FinancingImportResult};
+pub use cash_transaction::{CashTransaction, CreateCashTransactionInput};
+pub use fx_rate::{FxRate, FxRateSource, FxRefreshResult};
+pub use reminder::{DueReminder, Reminder, ReminderKind};
+pub use journal_entry::{EndOfDaySummary, JournalEntry, JournalEntryStatus};
+pub use archive::{ArchiveResult, ArchivedYear};
+pub use maintenance::{CalculationDiscrepancy, CalculationReport, MaintenanceReport};
+pub use import_batch::{ImportBatch, MAX_BATCH_ERRORS};
+pub use watch_folder::WatchFolder;
+pub use metrics::{DailyPerformance, MetricSnapshot, PeriodMetrics, RExpectancyReport, PositionSizingPoint, PositionSizingReport, TargetCalibrationReport, EquityPoint, EquityCurveGrouping, EquitySeries, StrategyTrend, StrategyTrendPoint, PortfolioHeatPoint, MarginUtilizationDay, MarginUtilizationReport, AccountPerformance, AccountPerformanceMonth, AccountReturnDay, AccountReturns, SlippageBucket, ExecutionQualityReport, ExchangeBucket, CarryingCostBucket, CarryingCostReport, DividendBucket, DividendIncomeReport, ChecklistReport, StopAdjustmentReport, PerformanceBucket, EntryCohort, SymbolMonthCell, SymbolMonthRow, SymbolMonthMatrix, SkillSample, SkillComparison, SkillProgressionReport, TradeCluster, TradeClusterReport, TradeSequenceBucket, TimelineEvent, OvertradingBreach, OvertradingReport, EventDayReport, EarningsWindowReport, OptionsPremiumBucket, OptionsPremiumReport, SideCostSummary, HoldingDaysBucket, ShortSideReport, FuturesSessionReport, LotDisposal, CryptoTaxReport};
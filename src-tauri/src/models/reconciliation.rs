@@ -0,0 +1,47 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Totals from a broker statement, as entered by the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatementTotals {
+    pub net_pnl: f64,
+    pub fees: Option<f64>,
+    pub trade_count: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconciliationIssueKind {
+    PossibleDuplicate, // Same symbol, date, direction, size and prices as another trade
+    MatchesDifference, // Net PnL matches the unexplained difference
+    OpenPosition,      // Still open, so missing from the journal's realized PnL
+}
+
+/// Trade that likely explains a discrepancy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationIssue {
+    pub kind: ReconciliationIssueKind,
+    pub trade_id: String,
+    pub symbol: String,
+    pub trade_date: NaiveDate,
+    pub net_pnl: Option<f64>,
+}
+
+/// Journal totals compared against broker statement totals for a period
+/// Differences are journal minus statement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub journal_net_pnl: f64, // Closed trade PnL minus financing charges
+    #[serde(default)]
+    pub journal_financing: f64,
+    pub statement_net_pnl: f64,
+    pub net_pnl_difference: f64,
+    pub journal_fees: f64,
+    pub statement_fees: Option<f64>,
+    pub fees_difference: Option<f64>,
+    pub journal_trade_count: i32,
+    pub statement_trade_count: Option<i32>,
+    pub trade_count_difference: Option<i32>,
+    pub is_reconciled: bool,
+    pub issues: Vec<ReconciliationIssue>,
+}
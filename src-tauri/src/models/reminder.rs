@@ -0,0 +1,67 @@
+use chrono::{NaiveDateTime, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Built-in reminder a trader can schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderKind {
+    JournalTonight,
+    WeeklyReview,
+    ImportStatements,
+}
+
+impl ReminderKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReminderKind::JournalTonight => "journal_tonight",
+            ReminderKind::WeeklyReview => "weekly_review",
+            ReminderKind::ImportStatements => "import_statements",
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            ReminderKind::JournalTonight => "Journal today's trades",
+            ReminderKind::WeeklyReview => "Time for your weekly review",
+            ReminderKind::ImportStatements => "Import this week's broker statements",
+        }
+    }
+}
+
+/// Schedule of one reminder, in the computer's local time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reminder {
+    pub kind: ReminderKind,
+    pub enabled: bool,
+    pub weekdays: Vec<u32>, // Days since Monday, 0-6
+    pub time: String,       // HH:MM
+}
+
+impl Reminder {
+    /// Schedules used until the trader saves their own; all start disabled
+    pub fn defaults() -> Vec<Reminder> {
+        let reminder = |kind, weekdays: &[Weekday], time: &str| Reminder {
+            kind,
+            enabled: false,
+            weekdays: weekdays.iter().map(|d| d.num_days_from_monday()).collect(),
+            time: time.to_string(),
+        };
+        vec![
+            reminder(
+                ReminderKind::JournalTonight,
+                &[Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri],
+                "20:00",
+            ),
+            reminder(ReminderKind::WeeklyReview, &[Weekday::Sun], "18:00"),
+            reminder(ReminderKind::ImportStatements, &[Weekday::Fri], "17:00"),
+        ]
+    }
+}
+
+/// Payload of the reminder event emitted to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DueReminder {
+    pub kind: ReminderKind,
+    pub message: String,
+    pub scheduled_for: NaiveDateTime,
+}
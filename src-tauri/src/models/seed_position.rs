@@ -0,0 +1,31 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use super::Direction;
+
+/// Position already open when the imported history starts
+/// Imported closing executions are matched against it instead of opening a new trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedPosition {
+    pub id: String,
+    pub user_id: String,
+    pub account_id: String,
+    pub symbol: String,
+    pub direction: Direction,
+    pub quantity: f64,
+    pub avg_cost: f64,
+    pub open_date: NaiveDate,
+    pub as_of_date: NaiveDate, // Imported executions on or before this date are already reflected
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for declaring a seed position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSeedPositionInput {
+    pub account_id: String,
+    pub symbol: String,
+    pub direction: Direction,
+    pub quantity: f64,
+    pub avg_cost: f64,
+    pub open_date: NaiveDate,
+    pub as_of_date: NaiveDate,
+}
@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Stop-loss change recorded on a trade
+/// The trade's own stop_loss_price is the initial stop; adjustments replace it as the current stop.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StopAdjustment {
+    pub id: String,
+    pub trade_id: String,
+    pub stop_price: f64,
+    pub adjusted_at: DateTime<Utc>,
+}
+
+/// Input for recording a stop-loss change on a trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordStopAdjustmentInput {
+    pub trade_id: String,
+    pub stop_price: f64,
+    pub adjusted_at: Option<DateTime<Utc>>, // Defaults to now
+}
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use super::{AssetClass, Direction};
+
+/// Rule suggesting a strategy for trades matching all of its conditions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyRule {
+    pub id: String,
+    pub strategy: String,
+    pub priority: i32, // Lower runs first; the first matching rule wins
+    pub min_hold_minutes: Option<f64>, // Inclusive
+    pub max_hold_minutes: Option<f64>, // Exclusive, so 5 means "held under 5 minutes"
+    pub watchlist: Option<String>, // Symbol (or option underlying) must be on this watchlist
+    pub direction: Option<Direction>,
+    pub asset_class: Option<AssetClass>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for creating a strategy rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateStrategyRuleInput {
+    pub strategy: String,
+    pub priority: Option<i32>,
+    pub min_hold_minutes: Option<f64>,
+    pub max_hold_minutes: Option<f64>,
+    pub watchlist: Option<String>,
+    pub direction: Option<Direction>,
+    pub asset_class: Option<AssetClass>,
+}
+
+/// Named list of symbols that strategy rules can refer to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Watchlist {
+    pub name: String,
+    pub symbols: Vec<String>,
+}
+
+/// What strategy rules look at on a new or imported trade
+#[derive(Debug, Clone)]
+pub struct TradeTraits {
+    pub symbol: String, // Underlying for options
+    pub direction: Direction,
+    pub asset_class: AssetClass,
+    pub hold_minutes: Option<f64>, // None unless both entry and exit times are known
+}
@@ -60,12 +60,254 @@ pub enum TradeResult {
     Breakeven,
 }
 
+impl TradeResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradeResult::Win => "win",
+            TradeResult::Loss => "loss",
+            TradeResult::Breakeven => "breakeven",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "win" => Some(TradeResult::Win),
+            "loss" => Some(TradeResult::Loss),
+            "breakeven" => Some(TradeResult::Breakeven),
+            _ => None,
+        }
+    }
+}
+
+/// PnL a trade's result is classified by
+/// Gross judges the trade idea before commissions and other costs; net PnL is reported either way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultBasis {
+    Gross,
+    #[default]
+    Net,
+}
+
+impl ResultBasis {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResultBasis::Gross => "gross",
+            ResultBasis::Net => "net",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "gross" => Some(ResultBasis::Gross),
+            "net" => Some(ResultBasis::Net),
+            _ => None,
+        }
+    }
+}
+
+/// Date a closed trade is attributed to in date filters and daily metrics
+/// Exit reports PnL on the day it was realized, so a swing trade closed in the next month
+/// counts toward that month. Both matches date filters on either date and reports PnL on the
+/// exit day, where it can only count once. Open trades and trades without exit executions keep
+/// their trade date.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateAttribution {
+    #[default]
+    Entry,
+    Exit,
+    Both,
+}
+
+impl DateAttribution {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DateAttribution::Entry => "entry",
+            DateAttribution::Exit => "exit",
+            DateAttribution::Both => "both",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "entry" => Some(DateAttribution::Entry),
+            "exit" => Some(DateAttribution::Exit),
+            "both" => Some(DateAttribution::Both),
+            _ => None,
+        }
+    }
+}
+
+/// Order in which crypto disposals use up earlier acquisitions of the same asset for tax
+/// FIFO sells the oldest lots first, LIFO the newest and HIFO the most expensive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CostBasisMethod {
+    #[default]
+    Fifo,
+    Lifo,
+    Hifo,
+}
+
+impl CostBasisMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CostBasisMethod::Fifo => "fifo",
+            CostBasisMethod::Lifo => "lifo",
+            CostBasisMethod::Hifo => "hifo",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "fifo" => Some(CostBasisMethod::Fifo),
+            "lifo" => Some(CostBasisMethod::Lifo),
+            "hifo" => Some(CostBasisMethod::Hifo),
+            _ => None,
+        }
+    }
+}
+
+/// Holding style of a closed trade, derived from how long it was held
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeType {
+    Scalp,
+    Day,
+    Swing,
+    Position,
+}
+
+impl TradeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradeType::Scalp => "scalp",
+            TradeType::Day => "day",
+            TradeType::Swing => "swing",
+            TradeType::Position => "position",
+        }
+    }
+}
+
+/// Holding durations separating trade types
+/// Trades closed the day they were opened are scalps up to scalp_max_minutes and day trades beyond it.
+/// Trades held overnight are swings up to swing_max_days and positions beyond it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TradeTypeThresholds {
+    pub scalp_max_minutes: f64,
+    pub swing_max_days: i64,
+}
+
+impl Default for TradeTypeThresholds {
+    fn default() -> Self {
+        Self {
+            scalp_max_minutes: 5.0,
+            swing_max_days: 30,
+        }
+    }
+}
+
+/// How an unresolved open position was carried into a later period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RollType {
+    Rolled,      // Moved to a later expiry or contract month
+    Transferred, // Moved to another account or broker
+}
+
+impl RollType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RollType::Rolled => "rolled",
+            RollType::Transferred => "transferred",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "rolled" => Some(RollType::Rolled),
+            "transferred" => Some(RollType::Transferred),
+            _ => None,
+        }
+    }
+}
+
+/// How an option position ended other than by trading out of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionOutcome {
+    Expired,   // Expired worthless
+    Assigned,  // Short option assigned
+    Exercised, // Long option exercised
+}
+
+impl OptionOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OptionOutcome::Expired => "expired",
+            OptionOutcome::Assigned => "assigned",
+            OptionOutcome::Exercised => "exercised",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "expired" => Some(OptionOutcome::Expired),
+            "assigned" => Some(OptionOutcome::Assigned),
+            "exercised" => Some(OptionOutcome::Exercised),
+            _ => None,
+        }
+    }
+}
+
+/// How hard the shares of a short trade were to borrow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BorrowAvailability {
+    Easy,
+    HardToBorrow,
+    LocateRequired,
+}
+
+impl BorrowAvailability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BorrowAvailability::Easy => "easy",
+            BorrowAvailability::HardToBorrow => "hard_to_borrow",
+            BorrowAvailability::LocateRequired => "locate_required",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "easy" => Some(BorrowAvailability::Easy),
+            "hard_to_borrow" => Some(BorrowAvailability::HardToBorrow),
+            "locate_required" => Some(BorrowAvailability::LocateRequired),
+            _ => None,
+        }
+    }
+}
+
+/// Input for carrying an unresolved open position into its continuation trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollPositionInput {
+    pub trade_id: String,
+    pub roll_type: RollType,
+    pub rolled_on: NaiveDate,
+    pub continuation_trade_id: Option<String>, // May be linked later, once the continuation is recorded
+}
+
 /// Asset class for the trade
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum AssetClass {
     Stock,
     Option,
+    Future,
+    Forex,
+    Crypto,
 }
 
 impl AssetClass {
@@ -73,6 +315,9 @@ impl AssetClass {
         match self {
             AssetClass::Stock => "stock",
             AssetClass::Option => "option",
+            AssetClass::Future => "future",
+            AssetClass::Forex => "forex",
+            AssetClass::Crypto => "crypto",
         }
     }
 
@@ -80,15 +325,73 @@ impl AssetClass {
         match s.to_lowercase().as_str() {
             "stock" => Some(AssetClass::Stock),
             "option" => Some(AssetClass::Option),
+            "future" => Some(AssetClass::Future),
+            "forex" => Some(AssetClass::Forex),
+            "crypto" => Some(AssetClass::Crypto),
             _ => None,
         }
     }
 
     /// Returns the contract multiplier for this asset class
+    /// Futures multipliers differ per contract and are stored on the instrument.
     pub fn multiplier(&self) -> f64 {
         match self {
             AssetClass::Stock => 1.0,
             AssetClass::Option => 100.0,
+            AssetClass::Future => 1.0,
+            // Forex quantities are in units of the base currency rather than lots
+            AssetClass::Forex => 1.0,
+            // Crypto quantities are fractional units of the base asset
+            AssetClass::Crypto => 1.0,
+        }
+    }
+
+    /// Returns the default minimum price increment for this asset class at a given price
+    pub fn default_tick_size(&self, price: f64) -> f64 {
+        match self {
+            // Sub-dollar stocks may be quoted in hundredths of a cent
+            AssetClass::Stock if price < 1.0 => 0.0001,
+            AssetClass::Stock => 0.01,
+            AssetClass::Option => 0.01,
+            // Futures ticks differ per contract; set the instrument's tick size
+            AssetClass::Future => 0.01,
+            // A pipette, the smallest increment most forex brokers quote
+            AssetClass::Forex if price < 10.0 => 0.00001,
+            AssetClass::Forex => 0.001,
+            // Low-priced coins and pairs quoted in BTC are priced to eight decimal places
+            AssetClass::Crypto if price < 1.0 => 0.00000001,
+            AssetClass::Crypto => 0.01,
+        }
+    }
+}
+
+/// Order type used for an execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderType {
+    Market,
+    Limit,
+    Stop,
+    StopLimit,
+}
+
+impl OrderType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderType::Market => "market",
+            OrderType::Limit => "limit",
+            OrderType::Stop => "stop",
+            OrderType::StopLimit => "stop_limit",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "market" => Some(OrderType::Market),
+            "limit" => Some(OrderType::Limit),
+            "stop" => Some(OrderType::Stop),
+            "stop_limit" => Some(OrderType::StopLimit),
+            _ => None,
         }
     }
 }
@@ -105,7 +408,6 @@ pub struct ExitExecution {
 }
 
 /// Stored trade execution (from database)
-#[cfg(test)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeExecutionRecord {
     pub id: String,
@@ -118,6 +420,50 @@ pub struct TradeExecutionRecord {
     pub fees: f64,
 }
 
+/// Execution joined with its trade context, used for execution analytics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionFill {
+    pub trade_id: String,
+    #[serde(default)]
+    pub account_id: String,
+    pub symbol: String,
+    pub asset_class: AssetClass,
+    pub direction: Direction,
+    pub trade_entry_price: f64, // Average entry price of the fill's trade
+    pub execution_type: String, // "entry" or "exit"
+    pub execution_date: NaiveDate,
+    pub execution_time: Option<String>,
+    pub quantity: f64,
+    pub price: f64,
+    pub fees: f64,
+    pub exchange: Option<String>, // Exchange or route the fill was executed on
+    pub intended_price: Option<f64>,
+    pub order_type: Option<OrderType>,
+    #[serde(default)]
+    pub contract_multiplier: Option<f64>, // From the instrument; None uses the asset class default
+    #[serde(default)]
+    pub tick_size: Option<f64>, // From the instrument; None uses the asset class default
+    #[serde(default)]
+    pub fx_rate: Option<f64>, // From the fill's trade; None when quoted in the account's base currency
+}
+
+impl ExecutionFill {
+    /// Contract multiplier of the fill's instrument
+    pub fn multiplier(&self) -> f64 {
+        self.contract_multiplier.unwrap_or_else(|| self.asset_class.multiplier())
+    }
+
+    /// Account currency per unit of price move and quantity: the multiplier at the trade's fx rate
+    pub fn value_multiplier(&self) -> f64 {
+        self.multiplier() * self.fx_rate.unwrap_or(1.0)
+    }
+
+    /// Minimum price increment of the fill's instrument at the fill price
+    pub fn tick_size(&self) -> f64 {
+        self.tick_size.unwrap_or_else(|| self.asset_class.default_tick_size(self.price))
+    }
+}
+
 /// Core trade entity with input fields
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Trade {
@@ -127,24 +473,70 @@ pub struct Trade {
     pub instrument_id: String,
     pub symbol: String, // Denormalized for convenience
     pub asset_class: AssetClass, // From instrument
+    #[serde(default)]
+    pub contract_multiplier: Option<f64>, // From instrument; None uses the asset class default
+    #[serde(default)]
+    pub fx_rate: Option<f64>, // Account base currency per unit of the quote currency; None when they are the same
     pub trade_number: Option<i32>,
+    #[serde(default)]
+    pub ref_code: Option<String>, // Stable per-user reference, e.g. T-2024-0193
     pub trade_date: NaiveDate,
     pub direction: Direction,
     pub quantity: Option<f64>,
     pub entry_price: f64,
     pub exit_price: Option<f64>,
     pub stop_loss_price: Option<f64>,
+    pub target_price: Option<f64>,
+    pub max_favorable_price: Option<f64>, // Best price reached while open
+    #[serde(default)]
+    pub max_adverse_price: Option<f64>, // Worst price reached while open
+    #[serde(default)]
+    pub entry_atr: Option<f64>, // Average true range of daily bars before the trade date
     pub entry_time: Option<String>,
     pub exit_time: Option<String>,
+    #[serde(default)]
+    pub exit_date: Option<NaiveDate>, // Date of the last exit execution
     pub fees: f64,
+    #[serde(default)]
+    pub carrying_costs: f64, // Sum of borrow fees, margin interest and swaps
+    #[serde(default)]
+    pub dividends: f64, // Linked dividends counted in net PnL (0 unless enabled in settings)
     pub strategy: Option<String>,
     pub notes: Option<String>,
     pub screenshot_url: Option<String>,
     pub status: Status,
+    #[serde(default)]
+    pub result_override: Option<TradeResult>, // Manual classification, kept apart from the computed result
+    #[serde(default)]
+    pub roll_type: Option<RollType>, // Set when an open position was carried into a continuation trade
+    #[serde(default)]
+    pub rolled_on: Option<NaiveDate>, // Exposure belongs to the continuation trade from this date
+    #[serde(default)]
+    pub continuation_trade_id: Option<String>,
+    #[serde(default)]
+    pub option_outcome: Option<OptionOutcome>, // Expired, assigned or exercised instead of closed by a trade
+    #[serde(default)]
+    pub borrow_availability: Option<BorrowAvailability>, // Short trades only
+    #[serde(default)]
+    pub borrow_note: Option<String>,
+    #[serde(default)]
+    pub is_locked: bool, // Within a finalized period of its account
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Trade {
+    /// Contract multiplier of the trade's instrument, e.g. 50 for an E-mini S&P 500 future
+    pub fn multiplier(&self) -> f64 {
+        self.contract_multiplier.unwrap_or_else(|| self.asset_class.multiplier())
+    }
+
+    /// Account currency per unit of price move and quantity: the multiplier at the trade's fx rate
+    pub fn value_multiplier(&self) -> f64 {
+        self.multiplier() * self.fx_rate.unwrap_or(1.0)
+    }
+}
+
 /// Derived fields computed from trade data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DerivedFields {
@@ -153,6 +545,7 @@ pub struct DerivedFields {
     pub pnl_per_share: Option<f64>,
     pub risk_per_share: Option<f64>,
     pub r_multiple: Option<f64>,
+    pub atr_multiple: Option<f64>, // pnl_per_share / entry_atr
     pub result: Option<TradeResult>,
 }
 
@@ -166,7 +559,12 @@ pub struct TradeWithDerived {
     pub pnl_per_share: Option<f64>,
     pub risk_per_share: Option<f64>,
     pub r_multiple: Option<f64>,
+    pub atr_multiple: Option<f64>, // pnl_per_share / entry_atr
     pub result: Option<TradeResult>,
+    #[serde(default)]
+    pub trade_type: Option<TradeType>, // Closed trades only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub executions: Option<Vec<TradeExecutionRecord>>, // Only loaded when requested
 }
 
 impl TradeWithDerived {
@@ -178,9 +576,18 @@ impl TradeWithDerived {
             pnl_per_share: derived.pnl_per_share,
             risk_per_share: derived.risk_per_share,
             r_multiple: derived.r_multiple,
+            atr_multiple: derived.atr_multiple,
             result: derived.result,
+            trade_type: None,
+            executions: None,
         }
     }
+
+    /// Result used for discipline tracking: the manual override when set, else the computed result
+    /// Trades without a computed result (still open) have no effective result either.
+    pub fn effective_result(&self) -> Option<TradeResult> {
+        self.result.map(|computed| self.trade.result_override.unwrap_or(computed))
+    }
 }
 
 /// Input for creating a new trade
@@ -196,6 +603,8 @@ pub struct CreateTradeInput {
     pub entry_price: f64,
     pub exit_price: Option<f64>,
     pub stop_loss_price: Option<f64>,
+    pub target_price: Option<f64>,
+    pub max_favorable_price: Option<f64>, // Best price reached while open
     pub entry_time: Option<String>,
     pub exit_time: Option<String>,
     pub fees: Option<f64>,
@@ -206,6 +615,20 @@ pub struct CreateTradeInput {
     pub exits: Option<Vec<ExitExecution>>,
 }
 
+/// Input row rejected by a bulk create
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkRowError {
+    pub row: usize, // 1-based position in the submitted list
+    pub message: String,
+}
+
+/// Result of a bulk create: either every trade was created or none were
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkCreateResult {
+    pub created: Vec<TradeWithDerived>,
+    pub errors: Vec<BulkRowError>,
+}
+
 /// Input for updating an existing trade
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateTradeInput {
@@ -218,6 +641,8 @@ pub struct UpdateTradeInput {
     pub entry_price: Option<f64>,
     pub exit_price: Option<f64>,
     pub stop_loss_price: Option<f64>,
+    pub target_price: Option<f64>,
+    pub max_favorable_price: Option<f64>, // Best price reached while open
     pub entry_time: Option<String>,
     pub exit_time: Option<String>,
     pub fees: Option<f64>,
@@ -226,3 +651,34 @@ pub struct UpdateTradeInput {
     pub screenshot_url: Option<String>,
     pub status: Option<Status>,
 }
+
+/// Filter over a user's trades; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TradeFilter {
+    pub account_id: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub symbol: Option<String>,
+    pub direction: Option<Direction>,
+    pub status: Option<Status>,
+    pub result: Option<TradeResult>, // Only trades with a net PnL can match
+    pub min_r_multiple: Option<f64>, // Only trades with a stop loss can match
+    pub max_duration_minutes: Option<f64>, // Only trades with entry and exit times can match
+    pub trade_type: Option<TradeType>, // Only closed trades can match
+    pub date_attribution: Option<DateAttribution>, // Dates matched by start and end; the setting when unset
+    pub archived: bool, // Search the archive database instead of the main one
+}
+
+/// Summary of the trades matching a filter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeStats {
+    pub trade_count: i64,
+    pub closed_count: i64, // Trades with a net PnL
+    pub total_pnl: f64,
+    pub win_count: i64,
+    pub loss_count: i64,
+    pub win_rate: Option<f64>, // Excluding breakeven trades
+    pub first_trade_date: Option<NaiveDate>,
+    pub last_trade_date: Option<NaiveDate>,
+}
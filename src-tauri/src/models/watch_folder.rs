@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+/// Folder whose new TLG and CSV files are imported into an account automatically
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchFolder {
+    pub directory: String,
+    pub account_id: String,
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use super::crypto_pairs::{parse_asset_amount, parse_utc_time, split_pair, CryptoFill};
+use super::csv_export::{assign_content_ids, column, csv_records, has_columns, match_open_close, parse_amount};
+use super::{BrokerParser, OpeningPosition, TlgParseError, TlgParseResult};
+
+/// Parse a Binance spot trade history CSV into executions shaped like TLG ones
+/// Both the current export, whose amounts carry their asset ("0.01BTC"), and the older one with a
+/// Fee Coin column are read. Fills are matched against the running position of their pair, with
+/// fees in the quote asset; fees paid in a third asset such as BNB are reported as errors. Times stay in UTC. The export has no trade IDs, so executions get a
+/// stable ID from their contents.
+pub fn parse_binance_trades(content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+    let mut fills = Vec::new();
+    let mut errors = Vec::new();
+    let mut columns: Option<HashMap<String, usize>> = None;
+
+    for record in csv_records(content) {
+        if record.is_blank() {
+            continue;
+        }
+        let Some(ref header) = columns else {
+            columns = Some(record.header_columns());
+            continue;
+        };
+
+        match parse_trade(&record.fields, header) {
+            Ok(fill) => {
+                // The fill still counts toward its position; only the fee is reported
+                if let Some(error) = fill.unpriced_fee() {
+                    errors.push(TlgParseError {
+                        line_number: record.line_number,
+                        line_content: record.raw.clone(),
+                        error,
+                    });
+                }
+                fills.push(fill.into_sided_fill("BINANCE"));
+            }
+            Err(e) => errors.push(TlgParseError {
+                line_number: record.line_number,
+                line_content: record.raw,
+                error: e,
+            }),
+        }
+    }
+
+    let mut executions = match_open_close(fills, opening);
+    assign_content_ids("binance", &mut executions);
+    TlgParseResult { executions, errors }
+}
+
+/// Binance spot trade histories in the current or the older format
+pub struct BinanceParser;
+
+impl BrokerParser for BinanceParser {
+    fn id(&self) -> &'static str {
+        "binance"
+    }
+
+    fn name(&self) -> &'static str {
+        "Binance trade history"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        has_columns(content, &["Date(UTC)", "Pair", "Executed"]) || has_columns(content, &["Date(UTC)", "Market", "Fee Coin"])
+    }
+
+    fn parse(&self, content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+        parse_binance_trades(content, opening)
+    }
+}
+
+/// Parse one trade row
+/// Columns: Date(UTC),Pair,Side,Price,Executed,Amount,Fee
+/// Older exports: Date(UTC),Market,Type,Price,Amount,Total,Fee,Fee Coin
+fn parse_trade(fields: &[String], header: &HashMap<String, usize>) -> Result<CryptoFill, String> {
+    let field = |name: &str| column(fields, header, name);
+    let current = header.contains_key("Pair");
+
+    let pair = if current { field("Pair") } else { field("Market") };
+    let (mut base, mut quote) = split_pair(pair)?;
+
+    let side = if current { field("Side") } else { field("Type") };
+    let buy = match side.to_uppercase().as_str() {
+        "BUY" => true,
+        "SELL" => false,
+        _ => return Err(format!("Unknown side: {}", side)),
+    };
+
+    let time = parse_utc_time(field("Date(UTC)"))?;
+    let price = parse_amount(field("Price")).ok_or_else(|| format!("Invalid price: {}", field("Price")))?;
+
+    let (quantity, fee, fee_asset) = if current {
+        let (quantity, executed_asset) = parse_asset_amount(field("Executed"))
+            .ok_or_else(|| format!("Invalid executed amount: {}", field("Executed")))?;
+        // The amounts name the assets, which settles pairs that split more than one way
+        base = executed_asset;
+        if let Some((_, amount_asset)) = parse_asset_amount(field("Amount")) {
+            quote = amount_asset;
+        }
+        let (fee, fee_asset) = match field("Fee") {
+            "" => (0.0, String::new()),
+            fee => parse_asset_amount(fee).ok_or_else(|| format!("Invalid fee: {}", fee))?,
+        };
+        (quantity, fee, fee_asset)
+    } else {
+        let quantity = parse_amount(field("Amount")).ok_or_else(|| format!("Invalid amount: {}", field("Amount")))?;
+        let fee = parse_amount(field("Fee")).unwrap_or(0.0);
+        (quantity, fee, field("Fee Coin").to_uppercase())
+    };
+    if quantity <= 0.0 {
+        return Err("Missing quantity".to_string());
+    }
+
+    Ok(CryptoFill {
+        base,
+        quote,
+        buy,
+        time,
+        quantity,
+        price,
+        fee: fee.abs(),
+        fee_asset,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{TlgAction, TlgAssetType};
+
+    const TRADES: &str = r#"Date(UTC),Pair,Side,Price,Executed,Amount,Fee
+2024-01-16 14:00:05,BTCUSDT,SELL,43000,0.2BTC,8600USDT,8.6USDT
+2024-01-16 11:00:00,BTCUSDT,SELL,43000,0.1BTC,4300USDT,4.3USDT
+2024-01-15 16:20:00,ETHBTC,BUY,0.055,1ETH,0.055BTC,0.00075BNB
+2024-01-15 09:31:02,BTCUSDT,BUY,42500,0.3BTC,12750USDT,0.0003BTC
+2024-01-15 09:00:00,BTCUSDT,HOLD,42500,0.1BTC,4250USDT,0.0001BTC
+"#;
+
+    #[test]
+    fn test_parse_binance_trades() {
+        let result = parse_binance_trades(TRADES, &[]);
+        assert_eq!(result.errors.len(), 2);
+        assert_eq!(result.errors[1].error, "Unknown side: HOLD");
+        assert_eq!(result.executions.len(), 4);
+
+        let btc: Vec<_> = result.executions.iter().filter(|e| e.symbol == "BTC/USDT").collect();
+        assert_eq!(btc.len(), 3);
+        assert_eq!(btc[0].action, TlgAction::BuyToOpen);
+        assert_eq!(btc[0].asset_type, TlgAssetType::Crypto);
+        assert_eq!(btc[0].currency, "USD");
+        // Fees in BTC are converted to USDT at the fill's price
+        assert!((btc[0].fees + 12.75).abs() < 1e-9);
+
+        // 0.3 - 0.1 leaves a hair under 0.2 BTC, yet the last sell closes it without opening a short
+        assert_eq!(btc[2].action, TlgAction::SellToClose);
+        assert_eq!(btc[2].quantity, -0.2);
+        assert!((btc[2].fees + 8.6).abs() < 1e-9);
+
+        // A fee in BNB has no price in the export, so it is reported while the fill is kept
+        let eth = result.executions.iter().find(|e| e.symbol == "ETH/BTC").unwrap();
+        assert_eq!(eth.currency, "BTC");
+        assert_eq!(eth.fees, 0.0);
+        assert_eq!((result.errors[0].line_number, result.errors[0].error.starts_with("Fee of 0.00075 BNB left out")), (4, true));
+
+        let again = parse_binance_trades(TRADES, &[]);
+        assert_eq!(again.executions[0].broker_execution_id, result.executions[0].broker_execution_id);
+    }
+
+    #[test]
+    fn test_parse_binance_trades_older_export() {
+        let content = r#"Date(UTC),Market,Type,Price,Amount,Total,Fee,Fee Coin
+2021-05-03 12:00:00,DOGEUSDT,SELL,0.45,1000,450,0.45,USDT
+2021-05-01 08:30:00,DOGEUSDT,BUY,0.32,1000,320,1,DOGE
+"#;
+        let result = parse_binance_trades(content, &[]);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.executions.len(), 2);
+        assert_eq!(result.executions[0].symbol, "DOGE/USDT");
+        assert!((result.executions[0].fees + 0.32).abs() < 1e-9);
+        assert_eq!(result.executions[1].action, TlgAction::SellToClose);
+    }
+}
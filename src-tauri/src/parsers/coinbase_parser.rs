@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use super::crypto_pairs::{parse_utc_time, split_pair, CryptoFill};
+use super::csv_export::{assign_content_ids, column, csv_records, has_columns, match_open_close, parse_amount};
+use super::{BrokerParser, OpeningPosition, TlgParseError, TlgParseResult};
+
+/// Parse a Coinbase export into executions shaped like TLG ones
+/// Reads the Advanced Trade fills report as well as the transaction history, of which only buys
+/// and sells are trades; sends, receives, conversions and rewards are skipped. Fills are matched
+/// against the running position of their pair, with fees in the quote currency. Times stay in UTC,
+/// and executions get a stable ID from their contents like those of other exports.
+pub fn parse_coinbase_trades(content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+    let mut fills = Vec::new();
+    let mut errors = Vec::new();
+    let mut columns: Option<HashMap<String, usize>> = None;
+
+    for record in csv_records(content) {
+        if record.is_blank() {
+            continue;
+        }
+        // The transaction history starts with a note and the user's name before the header
+        let Some(ref header) = columns else {
+            let header = record.header_columns();
+            if header.contains_key("trade id") || header.contains_key("Transaction Type") {
+                columns = Some(header);
+            }
+            continue;
+        };
+
+        let parsed = if header.contains_key("trade id") {
+            parse_fill(&record.fields, header).map(Some)
+        } else {
+            parse_transaction(&record.fields, header)
+        };
+        match parsed {
+            Ok(Some(fill)) => fills.push(fill.into_sided_fill("COINBASE")),
+            Ok(None) => {}
+            Err(e) => errors.push(TlgParseError {
+                line_number: record.line_number,
+                line_content: record.raw,
+                error: e,
+            }),
+        }
+    }
+
+    let mut executions = match_open_close(fills, opening);
+    assign_content_ids("coinbase", &mut executions);
+    TlgParseResult { executions, errors }
+}
+
+/// Coinbase fills reports and transaction histories
+/// The transaction history shares its Transaction Type column with E*TRADE's and is told apart by its Asset column.
+pub struct CoinbaseParser;
+
+impl BrokerParser for CoinbaseParser {
+    fn id(&self) -> &'static str {
+        "coinbase"
+    }
+
+    fn name(&self) -> &'static str {
+        "Coinbase fills or transaction history"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        has_columns(content, &["trade id", "product", "side"])
+            || has_columns(content, &["Transaction Type", "Asset", "Quantity Transacted"])
+    }
+
+    fn parse(&self, content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+        parse_coinbase_trades(content, opening)
+    }
+}
+
+/// Parse one row of the fills report
+/// Columns: portfolio,trade id,product,side,created at,size,size unit,price,fee,total,price/fee/total unit
+fn parse_fill(fields: &[String], header: &HashMap<String, usize>) -> Result<CryptoFill, String> {
+    let field = |name: &str| column(fields, header, name);
+
+    let (base, quote) = split_pair(field("product"))?;
+    let buy = parse_side(field("side"))?;
+    let time = parse_utc_time(field("created at"))?;
+    let price = parse_amount(field("price")).ok_or_else(|| format!("Invalid price: {}", field("price")))?;
+    let size = parse_amount(field("size")).ok_or_else(|| format!("Invalid size: {}", field("size")))?;
+
+    // Orders placed for an amount of the quote currency report their size in it
+    let quantity = if field("size unit").eq_ignore_ascii_case(&quote) {
+        if price <= 0.0 {
+            return Err(format!("Invalid price: {}", field("price")));
+        }
+        size / price
+    } else {
+        size
+    };
+    if quantity <= 0.0 {
+        return Err("Missing quantity".to_string());
+    }
+
+    Ok(CryptoFill {
+        base,
+        quote,
+        buy,
+        time,
+        quantity,
+        price,
+        fee: parse_amount(field("fee")).unwrap_or(0.0).abs(),
+        fee_asset: String::new(),
+    })
+}
+
+/// Parse one row of the transaction history, or None when it is not a trade
+/// Columns: ID,Timestamp,Transaction Type,Asset,Quantity Transacted,Price Currency,Price at Transaction,
+/// Subtotal,Total (inclusive of fees and/or spread),Fees and/or Spread,Notes
+/// Older exports have no ID and name the price columns "Spot Price Currency" and "Spot Price at Transaction".
+fn parse_transaction(fields: &[String], header: &HashMap<String, usize>) -> Result<Option<CryptoFill>, String> {
+    let field = |name: &str| column(fields, header, name);
+    let either = |name: &str, older: &str| match field(name) {
+        "" => field(older),
+        value => value,
+    };
+
+    let transaction_type = field("Transaction Type").to_lowercase();
+    let buy = match transaction_type.strip_prefix("advanced trade ").unwrap_or(&transaction_type) {
+        "buy" => true,
+        "sell" => false,
+        _ => return Ok(None),
+    };
+
+    let base = field("Asset").to_uppercase();
+    let quote = either("Price Currency", "Spot Price Currency").to_uppercase();
+    if base.is_empty() || quote.is_empty() {
+        return Err("Missing asset".to_string());
+    }
+
+    let time = parse_utc_time(field("Timestamp"))?;
+    let price = either("Price at Transaction", "Spot Price at Transaction");
+    let price = parse_amount(price).ok_or_else(|| format!("Invalid price: {}", price))?;
+    let quantity = parse_amount(field("Quantity Transacted"))
+        .ok_or_else(|| format!("Invalid quantity: {}", field("Quantity Transacted")))?
+        .abs();
+    if quantity == 0.0 {
+        return Err("Missing quantity".to_string());
+    }
+
+    Ok(Some(CryptoFill {
+        base,
+        quote,
+        buy,
+        time,
+        quantity,
+        price,
+        fee: parse_amount(field("Fees and/or Spread")).unwrap_or(0.0).abs(),
+        fee_asset: String::new(),
+    }))
+}
+
+/// Whether a fill's side is BUY or SELL
+fn parse_side(side: &str) -> Result<bool, String> {
+    match side.to_uppercase().as_str() {
+        "BUY" => Ok(true),
+        "SELL" => Ok(false),
+        _ => Err(format!("Unknown side: {}", side)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crate::parsers::{TlgAction, TlgAssetType};
+
+    #[test]
+    fn test_parse_coinbase_fills() {
+        let content = r#"portfolio,trade id,product,side,created at,size,size unit,price,fee,total,price/fee/total unit
+default,5012,ETH-USD,SELL,2024-01-16T15:45:00.250Z,0.75,ETH,2600.00,7.80,1942.20,USD
+default,5011,ETH-USD,BUY,2024-01-15T14:30:12.100Z,1000.00,USD,2500.00,6.00,-1006.00,USD
+default,5010,ETH-USD,BUY,2024-01-15T14:30:00.000Z,0.35,ETH,2500.00,5.25,-880.25,USD
+default,5009,ETH-USD,HOLD,2024-01-15T14:00:00.000Z,0.35,ETH,2500.00,5.25,-880.25,USD
+"#;
+        let result = parse_coinbase_trades(content, &[]);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].error, "Unknown side: HOLD");
+        assert_eq!(result.executions.len(), 3);
+
+        let first = &result.executions[0];
+        assert_eq!(first.symbol, "ETH/USD");
+        assert_eq!(first.asset_type, TlgAssetType::Crypto);
+        assert_eq!(first.execution_date, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+        assert_eq!(first.execution_time, "14:30:00");
+        assert_eq!(first.fees, -5.25);
+
+        // $1,000 of ETH at $2,500 buys 0.4 ETH
+        assert!((result.executions[1].quantity - 0.4).abs() < 1e-9);
+        assert_eq!(result.executions[2].action, TlgAction::SellToClose);
+        assert_eq!(result.executions[2].quantity, -0.75);
+    }
+
+    #[test]
+    fn test_parse_coinbase_transaction_history() {
+        let content = r#"You can use this transaction report to inform your likely tax obligations.
+Transactions
+User,Jane Doe,a1b2c3
+ID,Timestamp,Transaction Type,Asset,Quantity Transacted,Price Currency,Price at Transaction,Subtotal,Total (inclusive of fees and/or spread),Fees and/or Spread,Notes
+65a1,2024-01-20 18:00:00 UTC,Send,BTC,0.005,USD,$41000.00,$205.00,$205.00,$0.00,Sent 0.005 BTC to an external wallet
+65a0,2024-01-18 12:00:00 UTC,Advanced Trade Sell,BTC,0.01,USD,"$43,000.00",$430.00,$427.42,$2.58,Sold 0.01 BTC for 427.42 USD on BTC-USD
+659f,2024-01-15 09:30:00 UTC,Buy,BTC,0.015,USD,"$42,000.00",$630.00,$639.45,$9.45,Bought 0.015 BTC for $639.45 USD
+"#;
+        let result = parse_coinbase_trades(content, &[]);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.executions.len(), 2);
+
+        let buy = &result.executions[0];
+        assert_eq!(buy.symbol, "BTC/USD");
+        assert_eq!(buy.action, TlgAction::BuyToOpen);
+        assert_eq!(buy.price, 42000.0);
+        assert_eq!(buy.fees, -9.45);
+        assert_eq!(result.executions[1].action, TlgAction::SellToClose);
+        assert_eq!(result.executions[1].quantity, -0.01);
+    }
+}
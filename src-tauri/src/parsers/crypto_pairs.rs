@@ -0,0 +1,140 @@
+use chrono::{DateTime, NaiveDateTime};
+use super::csv_export::{parse_amount, SidedFill};
+use super::{TlgAction, TlgAssetType, TlgExecution};
+
+/// Assets that pairs written without a separator, such as "BTCUSDT", are quoted in
+const QUOTE_ASSETS: &[&str] = &[
+    "USDT", "USDC", "FDUSD", "BUSD", "TUSD", "USDP", "DAI", "USD", "EUR", "GBP", "TRY", "BRL", "AUD", "JPY", "BTC",
+    "ETH", "BNB",
+];
+
+/// Stablecoins that settle like US dollars
+const USD_STABLECOINS: &[&str] = &["USDT", "USDC", "FDUSD", "BUSD", "TUSD", "USDP", "DAI"];
+
+/// A spot fill of a base asset bought or sold for a quote asset
+pub(super) struct CryptoFill {
+    pub base: String,
+    pub quote: String,
+    pub buy: bool,
+    pub time: NaiveDateTime, // UTC, as the exchanges export it
+    pub quantity: f64, // Units of the base asset, always positive and often fractional
+    pub price: f64, // Quote asset per unit of the base asset
+    pub fee: f64, // Always positive
+    pub fee_asset: String, // Empty when the fee is in the quote asset
+}
+
+impl CryptoFill {
+    /// Why the fill's fee is left out, when it was charged in a third asset
+    /// Fees such as BNB at a discount on Binance have no price in the export to convert them at.
+    pub fn unpriced_fee(&self) -> Option<String> {
+        let priced = self.fee_asset.is_empty() || self.fee_asset == self.quote || self.fee_asset == self.base;
+        (!priced && self.fee != 0.0).then(|| {
+            format!(
+                "Fee of {} {} left out: the export has no {} price in {}; add it to the trade's fees",
+                self.fee, self.fee_asset, self.fee_asset, self.quote
+            )
+        })
+    }
+
+    /// Fill matched against the running position of its pair, symbol "BASE/QUOTE"
+    /// Fees are converted to the quote asset at the fill's price when charged in the base asset.
+    /// Fees in a third asset are left out; see unpriced_fee.
+    pub fn into_sided_fill(self, exchange: &str) -> SidedFill {
+        let fee = if self.fee_asset.is_empty() || self.fee_asset == self.quote {
+            self.fee
+        } else if self.fee_asset == self.base {
+            self.fee * self.price
+        } else {
+            0.0
+        };
+
+        let symbol = format!("{}/{}", self.base, self.quote);
+        let quantity = if self.buy { self.quantity } else { -self.quantity };
+        SidedFill {
+            buy: self.buy,
+            short: false, // Spot sells close what is held; a sell beyond it reads as a short
+            execution: TlgExecution {
+                broker_execution_id: String::new(), // Assigned once actions are known
+                symbol: symbol.clone(),
+                name: symbol,
+                exchange: exchange.to_string(),
+                action: if self.buy { TlgAction::BuyToOpen } else { TlgAction::SellToClose },
+                execution_date: self.time.date(),
+                execution_time: self.time.format("%H:%M:%S").to_string(),
+                currency: settlement_currency(&self.quote),
+                quantity,
+                multiplier: 1.0,
+                price: self.price,
+                total: quantity * self.price,
+                fees: -fee, // Negative like TLG fees
+                fx_rate: None,
+                asset_type: TlgAssetType::Crypto,
+                option_details: None,
+                future_details: None,
+            },
+        }
+    }
+}
+
+/// Base and quote assets of a pair such as "BTCUSDT", "BTC/USDT" or "BTC-USD"
+/// Pairs without a separator are split at the longest known quote asset they end with.
+pub(super) fn split_pair(pair: &str) -> Result<(String, String), String> {
+    let pair = pair.trim().to_uppercase();
+    if let Some((base, quote)) = pair.split_once(['/', '-', '_']) {
+        if base.is_empty() || quote.is_empty() {
+            return Err(format!("Invalid pair: {}", pair));
+        }
+        return Ok((base.to_string(), quote.to_string()));
+    }
+
+    QUOTE_ASSETS
+        .iter()
+        .filter(|quote| pair.len() > quote.len() && pair.ends_with(*quote))
+        .max_by_key(|quote| quote.len())
+        .map(|quote| (pair[..pair.len() - quote.len()].to_string(), quote.to_string()))
+        .ok_or_else(|| format!("Unknown quote asset in pair: {}", pair))
+}
+
+/// Currency a fill's quote asset is accounted in, with USD stablecoins counted as US dollars
+pub(super) fn settlement_currency(quote: &str) -> String {
+    if USD_STABLECOINS.contains(&quote) {
+        "USD".to_string()
+    } else {
+        quote.to_string()
+    }
+}
+
+/// Amount followed by its asset, such as "0.00001BTC" or "425.5USDT"
+pub(super) fn parse_asset_amount(s: &str) -> Option<(f64, String)> {
+    let s = s.trim();
+    let asset_start = s.find(|c: char| c.is_ascii_alphabetic())?;
+    let (amount, asset) = s.split_at(asset_start);
+    Some((parse_amount(amount)?, asset.to_uppercase()))
+}
+
+/// Fill time in UTC, as ISO 8601 ("2024-01-15T09:31:02.123Z") or "2024-01-15 09:31:02", with or without " UTC"
+pub(super) fn parse_utc_time(s: &str) -> Result<NaiveDateTime, String> {
+    let s = s.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(s) {
+        return Ok(time.naive_utc());
+    }
+    let local = s.strip_suffix(" UTC").unwrap_or(s);
+    NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M:%S%.f"))
+        .map_err(|_| format!("Invalid time: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_pair() {
+        assert_eq!(split_pair("btcusdt").unwrap(), ("BTC".to_string(), "USDT".to_string()));
+        // FDUSD rather than USD, which the pair also ends with
+        assert_eq!(split_pair("BTCFDUSD").unwrap(), ("BTC".to_string(), "FDUSD".to_string()));
+        assert_eq!(split_pair("ETHBTC").unwrap(), ("ETH".to_string(), "BTC".to_string()));
+        assert_eq!(split_pair("SOL-USD").unwrap(), ("SOL".to_string(), "USD".to_string()));
+        assert_eq!(split_pair("XYZABC").unwrap_err(), "Unknown quote asset in pair: XYZABC");
+    }
+}
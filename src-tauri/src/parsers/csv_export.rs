@@ -0,0 +1,335 @@
+use std::collections::{HashMap, HashSet};
+use chrono::NaiveDate;
+use super::{TlgAction, TlgExecution, TlgParseError};
+
+/// One record of a broker's CSV export
+pub(super) struct CsvRecord {
+    pub line_number: usize, // Line the record starts on
+    pub raw: String,
+    pub fields: Vec<String>,
+}
+
+impl CsvRecord {
+    /// True for empty lines and lines of only commas
+    pub fn is_blank(&self) -> bool {
+        self.raw.trim().trim_matches(',').is_empty()
+    }
+
+    /// Column names of a header record mapped to their index
+    pub fn header_columns(&self) -> HashMap<String, usize> {
+        self.fields
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.trim().to_string(), i))
+            .collect()
+    }
+}
+
+/// Split CSV content into records on commas outside double quotes
+/// Quoted fields may span lines, as Robinhood's descriptions do.
+pub(super) fn csv_records(content: &str) -> Vec<CsvRecord> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut raw = String::new();
+    let mut quoted = false;
+    let mut line_number = 1;
+
+    for (line_idx, line) in content.lines().enumerate() {
+        if quoted {
+            raw.push('\n');
+            field.push('\n');
+        } else {
+            line_number = line_idx + 1;
+        }
+        raw.push_str(line);
+
+        for c in line.chars() {
+            match c {
+                '"' => quoted = !quoted,
+                ',' if !quoted => fields.push(std::mem::take(&mut field)),
+                _ => field.push(c),
+            }
+        }
+
+        if !quoted {
+            fields.push(std::mem::take(&mut field));
+            records.push(CsvRecord {
+                line_number,
+                raw: std::mem::take(&mut raw),
+                fields: std::mem::take(&mut fields),
+            });
+        }
+    }
+
+    // An unterminated quote runs to the end of the content
+    if quoted {
+        fields.push(field);
+        records.push(CsvRecord { line_number, raw, fields });
+    }
+
+    records
+}
+
+/// Lines searched for a header when detecting the format of an export
+const HEADER_SEARCH_LINES: usize = 20;
+
+/// Whether a record near the top of CSV content is a header with all of the given columns
+/// Exports may open with a title, account lines or blank lines before their header.
+pub(super) fn has_columns(content: &str, columns: &[&str]) -> bool {
+    let head = content.lines().take(HEADER_SEARCH_LINES).collect::<Vec<_>>().join("\n");
+    csv_records(&head).iter().any(|record| {
+        let header = record.header_columns();
+        columns.iter().all(|name| header.contains_key(*name))
+    })
+}
+
+/// Number as brokers print amounts, such as "1,250.50", "$470", "-$0.66", "($640.06)", "+5" or
+/// "1 234.56"; parentheses or a minus sign, ASCII or Unicode, make it negative
+pub(super) fn parse_amount(s: &str) -> Option<f64> {
+    let s = s.trim();
+    let (negative, s) = match s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        Some(inner) => (true, inner),
+        None => (false, s),
+    };
+    let value = s
+        .replace(['$', ',', '+', ' ', '\u{a0}'], "")
+        .replace('\u{2212}', "-")
+        .parse::<f64>()
+        .ok()?;
+    Some(if negative { -value } else { value })
+}
+
+/// A fill from an export that does not say whether it opens or closes a position
+pub(super) struct SidedFill {
+    pub buy: bool,
+    pub short: bool, // Explicit short sale, which never closes a long position
+    pub execution: TlgExecution,
+}
+
+/// A position held before the fills of an export, such as a seed position
+#[derive(Debug, Clone)]
+pub struct OpeningPosition {
+    pub symbol: String,
+    pub quantity: f64, // Negative for a short position
+    pub as_of_date: NaiveDate, // Fills on or before this date are already part of the position
+}
+
+/// The opening position of a symbol that a fill on `date` starts from
+fn opening_position<'a>(opening: &'a [OpeningPosition], symbol: &str, date: NaiveDate) -> Option<&'a OpeningPosition> {
+    opening
+        .iter()
+        .find(|o| o.symbol.eq_ignore_ascii_case(symbol) && date > o.as_of_date)
+}
+
+/// Decide which fills open and which close by the running position of their symbol
+/// Positions start flat at the first exported fill, or at their opening position from the first
+/// fill after its as-of date. Buys cover a short position and sells reduce a long one; whatever
+/// is left opens a position, so a fill that flips it is split in two.
+pub(super) fn match_open_close(mut fills: Vec<SidedFill>, opening: &[OpeningPosition]) -> Vec<TlgExecution> {
+    fills.sort_by(|a, b| {
+        a.execution
+            .execution_date
+            .cmp(&b.execution.execution_date)
+            .then_with(|| a.execution.execution_time.cmp(&b.execution.execution_time))
+    });
+
+    let mut executions = Vec::new();
+    let mut positions: HashMap<String, f64> = HashMap::new();
+    let mut seeded: HashSet<String> = HashSet::new();
+    for SidedFill { buy, short, execution } in fills {
+        let position = positions.entry(execution.symbol.clone()).or_insert(0.0);
+        if let Some(open) = opening_position(opening, &execution.symbol, execution.execution_date) {
+            if seeded.insert(execution.symbol.clone()) {
+                *position = open.quantity;
+            }
+        }
+        let quantity = execution.abs_quantity();
+
+        let closable = match (buy, short) {
+            (true, _) => (-*position).max(0.0),
+            (false, false) => position.max(0.0),
+            (false, true) => 0.0,
+        };
+        let mut closing = quantity.min(closable);
+        let mut opening = quantity - closing;
+        // Fractional quantities leave rounding residue that must not open a position of its own
+        if closing > 0.0 && opening < 1e-9 {
+            closing = quantity;
+            opening = 0.0;
+        }
+        *position += if buy { quantity } else { -quantity };
+        if position.abs() < 1e-9 {
+            *position = 0.0;
+        }
+
+        if closing > 0.0 {
+            let action = if buy { TlgAction::BuyToClose } else { TlgAction::SellToClose };
+            executions.push(with_quantity(&execution, closing, action));
+        }
+        if opening > 0.0 {
+            let action = if buy { TlgAction::BuyToOpen } else { TlgAction::SellToOpen };
+            executions.push(with_quantity(&execution, opening, action));
+        }
+    }
+    executions
+}
+
+/// A transaction row of an export that flags some trades as opening or closing and not others
+pub(super) enum Transaction {
+    Sided(SidedFill), // Trade matched against the running position
+    Explicit(TlgExecution), // Trade that says whether it opens or closes
+    Removal(TlgExecution), // Expiration, assignment or exercise; closes whatever is open
+}
+
+/// What a broker's action string says about a trade, whatever words the broker uses for it
+#[derive(Clone, Copy)]
+pub(super) enum BrokerAction {
+    Sided { buy: bool, short: bool }, // Buy or sell without an open/close flag
+    Explicit(TlgAction), // Buy or sell to open or to close
+    Removal, // Expiration, assignment or exercise
+}
+
+impl BrokerAction {
+    /// Whether the trade buys, and its action until the running position settles it
+    pub fn side(self) -> (bool, TlgAction) {
+        match self {
+            BrokerAction::Sided { buy, .. } => (buy, if buy { TlgAction::BuyToOpen } else { TlgAction::SellToClose }),
+            BrokerAction::Explicit(action) => (matches!(action, TlgAction::BuyToOpen | TlgAction::BuyToClose), action),
+            // The side is decided once the open position is known
+            BrokerAction::Removal => (true, TlgAction::SellToClose),
+        }
+    }
+
+    /// Transaction of the trade's execution, built with the side and action of `side`
+    pub fn into_transaction(self, execution: TlgExecution) -> Transaction {
+        match self {
+            BrokerAction::Sided { buy, short } => Transaction::Sided(SidedFill { buy, short, execution }),
+            BrokerAction::Explicit(_) => Transaction::Explicit(execution),
+            BrokerAction::Removal => Transaction::Removal(execution),
+        }
+    }
+}
+
+/// Turn transactions, oldest first, into executions ordered by date
+/// Removals come after the day's trades and close the position of their contract open at that
+/// point, including any opening position, or are reported as errors against their record when
+/// nothing is open.
+pub(super) fn resolve_transactions(
+    transactions: Vec<(Transaction, CsvRecord)>,
+    opening: &[OpeningPosition],
+    errors: &mut Vec<TlgParseError>,
+) -> Vec<TlgExecution> {
+    let mut fills = Vec::new();
+    let mut executions = Vec::new();
+    let mut removals = Vec::new();
+    for (transaction, record) in transactions {
+        match transaction {
+            Transaction::Sided(fill) => fills.push(fill),
+            Transaction::Explicit(execution) => executions.push(execution),
+            Transaction::Removal(execution) => removals.push((execution, record)),
+        }
+    }
+    executions.extend(match_open_close(fills, opening));
+    executions.sort_by_key(|e| e.execution_date);
+
+    removals.sort_by_key(|(e, _)| e.execution_date);
+    for (mut removal, record) in removals {
+        let open = opening_position(opening, &removal.symbol, removal.execution_date);
+        let position: f64 = open.map_or(0.0, |o| o.quantity)
+            + executions
+                .iter()
+                .filter(|e| e.symbol == removal.symbol && e.execution_date <= removal.execution_date)
+                .filter(|e| open.is_none_or(|o| e.execution_date > o.as_of_date))
+                .map(|e| e.quantity)
+                .sum::<f64>();
+        let quantity = removal.abs_quantity().min(position.abs());
+        if quantity < 1e-9 {
+            errors.push(TlgParseError {
+                line_number: record.line_number,
+                line_content: record.raw,
+                error: format!("No open position to close for {}", removal.symbol),
+            });
+            continue;
+        }
+        let (action, quantity) = if position > 0.0 {
+            (TlgAction::SellToClose, -quantity)
+        } else {
+            (TlgAction::BuyToClose, quantity)
+        };
+        removal.action = action;
+        removal.quantity = quantity;
+        removal.total = quantity * removal.price * removal.multiplier;
+        executions.push(removal);
+    }
+    executions.sort_by_key(|e| e.execution_date);
+    executions
+}
+
+/// Copy of an execution for part of its quantity, with fees split pro rata
+fn with_quantity(execution: &TlgExecution, quantity: f64, action: TlgAction) -> TlgExecution {
+    let share = quantity / execution.abs_quantity();
+    let quantity = if execution.quantity < 0.0 { -quantity } else { quantity };
+    TlgExecution {
+        action,
+        quantity,
+        total: quantity * execution.price * execution.multiplier,
+        fees: execution.fees * share,
+        ..execution.clone()
+    }
+}
+
+/// Value of a named column, empty when the record is shorter than the header
+pub(super) fn column<'a>(fields: &'a [String], header: &HashMap<String, usize>, name: &str) -> &'a str {
+    header.get(name).and_then(|&i| fields.get(i)).map(|f| f.trim()).unwrap_or("")
+}
+
+/// Give executions from exports without execution IDs a stable ID derived from their contents,
+/// so re-importing the same export is detected as duplicates
+pub(super) fn assign_content_ids(prefix: &str, executions: &mut [TlgExecution]) {
+    let mut seen_ids: HashMap<String, usize> = HashMap::new();
+    for execution in executions {
+        let base_id = format!(
+            "{}-{}-{}-{}-{}-{}",
+            prefix,
+            execution.execution_date.format("%Y%m%d"),
+            execution.execution_time.replace(':', ""),
+            execution.symbol.split_whitespace().collect::<String>(),
+            execution.quantity,
+            execution.price
+        );
+        // Identical fills at the same time are told apart by their order
+        let occurrence = seen_ids.entry(base_id.clone()).or_insert(0);
+        *occurrence += 1;
+        execution.broker_execution_id = format!("{}-{}", base_id, occurrence);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_records_quoted_line_breaks() {
+        let content = "a,\"b, c\",d\n\"Apple\nCUSIP: 037833100\",Buy\n\n";
+        let records = csv_records(content);
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].fields, vec!["a", "b, c", "d"]);
+        assert_eq!(records[1].line_number, 2);
+        assert_eq!(records[1].fields, vec!["Apple\nCUSIP: 037833100", "Buy"]);
+        assert!(records[2].is_blank());
+    }
+
+    #[test]
+    fn test_parse_amount_formats() {
+        assert_eq!(parse_amount(" $1,250.50 "), Some(1250.5));
+        assert_eq!(parse_amount("-$0.66"), Some(-0.66));
+        assert_eq!(parse_amount("($640.06)"), Some(-640.06));
+        assert_eq!(parse_amount("+5"), Some(5.0));
+        assert_eq!(parse_amount("1 234.56"), Some(1234.56));
+        assert_eq!(parse_amount("\u{2212}20.5"), Some(-20.5));
+        assert_eq!(parse_amount(""), None);
+        assert_eq!(parse_amount("n/a"), None);
+    }
+}
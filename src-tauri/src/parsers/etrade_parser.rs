@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use super::csv_export::{assign_content_ids, column, csv_records, has_columns, parse_amount, resolve_transactions, BrokerAction, Transaction};
+use super::{BrokerParser, OpeningPosition, OptionDetails, OptionType, TlgAction, TlgAssetType, TlgExecution, TlgParseError, TlgParseResult};
+
+/// Parse an E*TRADE transaction history CSV into executions shaped like TLG ones
+/// Bought and Sold trades carry no open/close flag and are matched against the running position of
+/// their symbol; option trades flagged to open or close keep their flag. Expired, assigned and
+/// exercised options close the open position of their contract at a price of zero. The export has
+/// no execution times or IDs, so executions keep its order within a day and get a stable ID from
+/// their contents.
+pub fn parse_etrade_transactions(content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+    let mut transactions = Vec::new();
+    let mut errors = Vec::new();
+    let mut columns: Option<HashMap<String, usize>> = None;
+
+    for record in csv_records(content) {
+        if record.is_blank() {
+            continue;
+        }
+        // The export starts with the account number before the header
+        let Some(ref header) = columns else {
+            let header = record.header_columns();
+            if header.contains_key("Transaction Type") {
+                columns = Some(header);
+            }
+            continue;
+        };
+
+        match parse_transaction(&record.fields, header) {
+            Ok(Some(transaction)) => transactions.push((transaction, record)),
+            Ok(None) => {}
+            Err(e) => errors.push(TlgParseError {
+                line_number: record.line_number,
+                line_content: record.raw,
+                error: e,
+            }),
+        }
+    }
+
+    // The export lists the newest transactions first
+    transactions.reverse();
+    let mut executions = resolve_transactions(transactions, opening, &mut errors);
+    assign_content_ids("etrade", &mut executions);
+    TlgParseResult { executions, errors }
+}
+
+/// E*TRADE transaction histories
+pub struct EtradeParser;
+
+impl BrokerParser for EtradeParser {
+    fn id(&self) -> &'static str {
+        "etrade"
+    }
+
+    fn name(&self) -> &'static str {
+        "E*TRADE transaction history"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        has_columns(content, &["Transaction Date", "Transaction Type", "Security Type"])
+    }
+
+    fn parse(&self, content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+        parse_etrade_transactions(content, opening)
+    }
+}
+
+/// Parse one transaction, or None when it is not a trade
+/// Columns: Transaction Date,Transaction Type,Security Type,Symbol,Quantity,Amount,Price,Commission,Description
+fn parse_transaction(fields: &[String], header: &HashMap<String, usize>) -> Result<Option<Transaction>, String> {
+    let field = |name: &str| column(fields, header, name);
+
+    let broker_action = match field("Transaction Type").to_lowercase().as_str() {
+        "bought" | "bought to cover" => BrokerAction::Sided { buy: true, short: false },
+        "sold" => BrokerAction::Sided { buy: false, short: false },
+        "sold short" => BrokerAction::Sided { buy: false, short: true },
+        "bought to open" => BrokerAction::Explicit(TlgAction::BuyToOpen),
+        "sold to close" => BrokerAction::Explicit(TlgAction::SellToClose),
+        "sold to open" => BrokerAction::Explicit(TlgAction::SellToOpen),
+        "bought to close" => BrokerAction::Explicit(TlgAction::BuyToClose),
+        "option expiration" | "option assignment" | "option exercise" => BrokerAction::Removal,
+        _ => return Ok(None),
+    };
+
+    let execution_date = NaiveDate::parse_from_str(field("Transaction Date"), "%m/%d/%y")
+        .or_else(|_| NaiveDate::parse_from_str(field("Transaction Date"), "%m/%d/%Y"))
+        .map_err(|_| format!("Invalid date: {}", field("Transaction Date")))?;
+
+    let symbol = field("Symbol").to_uppercase();
+    if symbol.is_empty() {
+        return Err("Missing symbol".to_string());
+    }
+    let option_details = match field("Security Type").to_uppercase().as_str() {
+        "OPTN" => Some(
+            parse_etrade_option_symbol(&symbol)
+                .or_else(|| parse_etrade_option_description(field("Description")))
+                .ok_or_else(|| format!("Invalid option symbol: {}", field("Symbol")))?,
+        ),
+        "EQ" => None,
+        // Older exports leave the security type empty, so options are told apart by their symbol
+        "" => parse_etrade_option_symbol(&symbol),
+        other => return Err(format!("Unsupported security type: {}", other)),
+    };
+
+    let quantity = parse_amount(field("Quantity"))
+        .ok_or_else(|| format!("Invalid quantity: {}", field("Quantity")))?
+        .abs();
+    if quantity == 0.0 {
+        return Err("Missing quantity".to_string());
+    }
+    let price = match field("Price") {
+        "" if matches!(broker_action, BrokerAction::Removal) => 0.0,
+        price => parse_amount(price).ok_or_else(|| format!("Invalid price: {}", price))?,
+    };
+    let fees = parse_amount(field("Commission")).unwrap_or(0.0).abs() + parse_amount(field("Fees")).unwrap_or(0.0).abs();
+
+    let (buy, action) = broker_action.side();
+    let (symbol, asset_type, multiplier) = match option_details {
+        Some(ref details) => (details.occ_symbol(), TlgAssetType::Option, 100.0),
+        None => (symbol, TlgAssetType::Stock, 1.0),
+    };
+    let quantity = if buy { quantity } else { -quantity };
+
+    let execution = TlgExecution {
+        broker_execution_id: String::new(), // Assigned once all transactions are parsed
+        symbol,
+        name: field("Description").to_string(),
+        exchange: "ETRADE".to_string(),
+        action,
+        execution_date,
+        execution_time: String::new(),
+        currency: "USD".to_string(),
+        quantity,
+        multiplier,
+        price,
+        total: quantity * price * multiplier,
+        fees: -fees, // Negative like TLG fees
+        fx_rate: None,
+        asset_type,
+        option_details,
+        future_details: None,
+    };
+
+    Ok(Some(broker_action.into_transaction(execution)))
+}
+
+/// Contract of an E*TRADE option symbol such as "SPY Jan 19 '24 $470 Put", or None for a stock
+fn parse_etrade_option_symbol(symbol: &str) -> Option<OptionDetails> {
+    let parts: Vec<&str> = symbol.split_whitespace().collect();
+    let [underlying, month, day, year, strike, option_type] = parts[..] else {
+        return None;
+    };
+
+    let expiration = format!("{} {} {}", month, day, year.trim_start_matches('\''));
+    let expiration_date = NaiveDate::parse_from_str(&expiration, "%b %d %y").ok()?;
+    let strike_price = parse_amount(strike)?;
+    let option_type = match option_type {
+        "CALL" => OptionType::Call,
+        "PUT" => OptionType::Put,
+        _ => return None,
+    };
+
+    Some(OptionDetails {
+        underlying: underlying.to_string(),
+        expiration_date,
+        option_type,
+        strike_price,
+    })
+}
+
+/// Contract of an E*TRADE option description such as "PUT SPY 01/19/24 470.000"
+fn parse_etrade_option_description(description: &str) -> Option<OptionDetails> {
+    let parts: Vec<&str> = description.split_whitespace().collect();
+    let [option_type, underlying, expiration, strike, ..] = parts[..] else {
+        return None;
+    };
+
+    let option_type = match option_type.to_uppercase().as_str() {
+        "CALL" => OptionType::Call,
+        "PUT" => OptionType::Put,
+        _ => return None,
+    };
+    let expiration_date = NaiveDate::parse_from_str(expiration, "%m/%d/%y").ok()?;
+    let strike_price = parse_amount(strike)?;
+
+    Some(OptionDetails {
+        underlying: underlying.to_uppercase(),
+        expiration_date,
+        option_type,
+        strike_price,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSACTIONS: &str = r#"For Account:,#####1234
+
+Transaction Date,Transaction Type,Security Type,Symbol,Quantity,Amount,Price,Commission,Description
+01/19/24,Option Expiration,OPTN,SPY Jan 19 '24 $460 Put,1,0,,0,PUT SPY 01/19/24 460.000
+01/17/24,Bought To Close,OPTN,SPY Jan 19 '24 $470 Put,1,-40.50,0.40,0.50,PUT SPY 01/19/24 470.000
+01/16/24,Sold,EQ,AAPL,-150,28499.95,190.00,0.05,APPLE INC COM
+01/16/24,Dividend,EQ,AAPL,0,24.00,,0,APPLE INC COM
+01/15/24,Sold To Open,OPTN,SPY--240119P00470000,-1,149.50,1.50,0.50,PUT SPY 01/19/24 470.000
+01/15/24,Bought To Open,OPTN,SPY Jan 19 '24 $460 Put,1,-55.50,0.55,0.50,PUT SPY 01/19/24 460.000
+01/12/24,Bought,EQ,AAPL,100,-18550.00,185.50,0,APPLE INC COM
+01/12/24,Bought,MF,VFIAX,10,-4500.00,450.00,0,VANGUARD 500 INDEX ADMIRAL
+"#;
+
+    #[test]
+    fn test_parse_etrade_transactions() {
+        let result = parse_etrade_transactions(TRANSACTIONS, &[]);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line_number, 11);
+        assert_eq!(result.errors[0].error, "Unsupported security type: MF");
+        assert_eq!(result.executions.len(), 7);
+
+        // Without open/close codes, the sell of 150 closes the long 100 and opens a short 50
+        let aapl: Vec<&TlgExecution> = result.executions.iter().filter(|e| e.symbol == "AAPL").collect();
+        assert_eq!(aapl.len(), 3);
+        assert_eq!(aapl[0].action, TlgAction::BuyToOpen);
+        assert_eq!(aapl[1].action, TlgAction::SellToClose);
+        assert_eq!(aapl[1].quantity, -100.0);
+        assert_eq!(aapl[2].action, TlgAction::SellToOpen);
+        assert_eq!(aapl[2].quantity, -50.0);
+
+        // The contract comes from the description when the symbol is not readable
+        let short_put = result.executions.iter().find(|e| e.action == TlgAction::SellToOpen && e.asset_type == TlgAssetType::Option).unwrap();
+        assert_eq!(short_put.symbol, "SPY   240119P00470000");
+        assert_eq!(short_put.price, 1.5);
+        assert_eq!(short_put.fees, -0.5);
+
+        let expired = result.executions.last().unwrap();
+        assert_eq!(expired.symbol, "SPY   240119P00460000");
+        assert_eq!(expired.action, TlgAction::SellToClose);
+        assert_eq!(expired.quantity, -1.0);
+        assert_eq!(expired.price, 0.0);
+    }
+
+    #[test]
+    fn test_parse_etrade_option_symbol() {
+        let details = parse_etrade_option_symbol("TSLA DEC 20 '24 $250.50 CALL").unwrap();
+        assert_eq!(details.underlying, "TSLA");
+        assert_eq!(details.expiration_date, NaiveDate::from_ymd_opt(2024, 12, 20).unwrap());
+        assert_eq!(details.strike_price, 250.5);
+        assert_eq!(details.option_type, OptionType::Call);
+        assert!(parse_etrade_option_symbol("AAPL").is_none());
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use super::csv_export::{assign_content_ids, column, csv_records, has_columns, parse_amount, resolve_transactions, BrokerAction, Transaction};
+use super::{BrokerParser, OpeningPosition, OptionDetails, OptionType, TlgAction, TlgAssetType, TlgExecution, TlgParseError, TlgParseResult};
+
+/// Parse a Fidelity Accounts_History.csv into executions shaped like TLG ones
+/// "YOU BOUGHT" and "YOU SOLD" trades are matched against the running position of their symbol,
+/// unless Fidelity flags them as an opening or closing transaction. Expired, assigned and exercised
+/// options close the open position of their contract at a price of zero. The export has no
+/// execution times or IDs, so executions keep its order within a day and get a stable ID from
+/// their contents.
+pub fn parse_fidelity_history(content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+    let mut transactions = Vec::new();
+    let mut errors = Vec::new();
+    let mut columns: Option<HashMap<String, usize>> = None;
+
+    for record in csv_records(content) {
+        if record.is_blank() {
+            continue;
+        }
+        // The export may start with blank lines or an account title before the header
+        let Some(ref header) = columns else {
+            let header = record.header_columns();
+            if header.contains_key("Run Date") {
+                columns = Some(header);
+            }
+            continue;
+        };
+
+        // The disclaimer after the transactions has no date
+        if !column(&record.fields, header, "Run Date").starts_with(|c: char| c.is_ascii_digit()) {
+            continue;
+        }
+
+        match parse_transaction(&record.fields, header) {
+            Ok(Some(transaction)) => transactions.push((transaction, record)),
+            Ok(None) => {}
+            Err(e) => errors.push(TlgParseError {
+                line_number: record.line_number,
+                line_content: record.raw,
+                error: e,
+            }),
+        }
+    }
+
+    // The export lists the newest transactions first
+    transactions.reverse();
+    let mut executions = resolve_transactions(transactions, opening, &mut errors);
+    assign_content_ids("fidelity", &mut executions);
+    TlgParseResult { executions, errors }
+}
+
+/// Fidelity Accounts_History.csv exports
+pub struct FidelityParser;
+
+impl BrokerParser for FidelityParser {
+    fn id(&self) -> &'static str {
+        "fidelity"
+    }
+
+    fn name(&self) -> &'static str {
+        "Fidelity account history"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        has_columns(content, &["Run Date", "Action", "Security Type"])
+    }
+
+    fn parse(&self, content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+        parse_fidelity_history(content, opening)
+    }
+}
+
+/// Parse one transaction, or None when it is not a trade
+/// Columns: Run Date,Action,Symbol,Security Description,Security Type,Quantity,Price ($),Commission ($),Fees ($),...
+/// Newer exports add an Account column and shorten the description and type column names.
+fn parse_transaction(fields: &[String], header: &HashMap<String, usize>) -> Result<Option<Transaction>, String> {
+    let field = |name: &str| column(fields, header, name);
+
+    let Some(broker_action) = parse_fidelity_action(field("Action")) else {
+        return Ok(None);
+    };
+
+    let execution_date = NaiveDate::parse_from_str(field("Run Date"), "%m/%d/%Y")
+        .map_err(|_| format!("Invalid date: {}", field("Run Date")))?;
+
+    let symbol = field("Symbol").to_uppercase();
+    if symbol.is_empty() {
+        return Err("Missing symbol".to_string());
+    }
+    let option_details = parse_fidelity_option_symbol(&symbol)?;
+
+    let quantity = parse_amount(field("Quantity"))
+        .ok_or_else(|| format!("Invalid quantity: {}", field("Quantity")))?
+        .abs();
+    if quantity == 0.0 {
+        return Err("Missing quantity".to_string());
+    }
+    let price = match field("Price ($)") {
+        "" if matches!(broker_action, BrokerAction::Removal) => 0.0,
+        price => parse_amount(price).ok_or_else(|| format!("Invalid price: {}", price))?,
+    };
+    let fees = parse_amount(field("Commission ($)")).unwrap_or(0.0).abs() + parse_amount(field("Fees ($)")).unwrap_or(0.0).abs();
+
+    let description = match field("Security Description") {
+        "" => field("Description"),
+        description => description,
+    };
+    let (buy, action) = broker_action.side();
+    let (symbol, asset_type, multiplier) = match option_details {
+        Some(ref details) => (details.occ_symbol(), TlgAssetType::Option, 100.0),
+        None => (symbol, TlgAssetType::Stock, 1.0),
+    };
+    let quantity = if buy { quantity } else { -quantity };
+
+    let execution = TlgExecution {
+        broker_execution_id: String::new(), // Assigned once all transactions are parsed
+        symbol,
+        name: description.to_string(),
+        exchange: "FIDELITY".to_string(),
+        action,
+        execution_date,
+        execution_time: String::new(),
+        currency: "USD".to_string(),
+        quantity,
+        multiplier,
+        price,
+        total: quantity * price * multiplier,
+        fees: -fees, // Negative like TLG fees
+        fx_rate: None,
+        asset_type,
+        option_details,
+        future_details: None,
+    };
+
+    Ok(Some(broker_action.into_transaction(execution)))
+}
+
+/// Trade described by a Fidelity action such as "YOU SOLD OPENING TRANSACTION PUT (SPY) ...", or None
+fn parse_fidelity_action(action: &str) -> Option<BrokerAction> {
+    let action = action.to_uppercase();
+
+    if let Some(rest) = action.strip_prefix("YOU BOUGHT ") {
+        Some(if rest.starts_with("OPENING TRANSACTION") {
+            BrokerAction::Explicit(TlgAction::BuyToOpen)
+        } else if rest.starts_with("CLOSING TRANSACTION") {
+            BrokerAction::Explicit(TlgAction::BuyToClose)
+        } else {
+            BrokerAction::Sided { buy: true, short: false }
+        })
+    } else if let Some(rest) = action.strip_prefix("YOU SOLD ") {
+        Some(if rest.starts_with("OPENING TRANSACTION") {
+            BrokerAction::Explicit(TlgAction::SellToOpen)
+        } else if rest.starts_with("CLOSING TRANSACTION") {
+            BrokerAction::Explicit(TlgAction::SellToClose)
+        } else {
+            BrokerAction::Sided { buy: false, short: rest.starts_with("SHORT SALE") }
+        })
+    } else if ["EXPIRED", "ASSIGNED", "EXERCISED"].iter().any(|removal| action.starts_with(removal)) {
+        Some(BrokerAction::Removal)
+    } else {
+        None
+    }
+}
+
+/// Contract of a Fidelity option symbol such as "-SPY240119P470", or None for a stock
+fn parse_fidelity_option_symbol(symbol: &str) -> Result<Option<OptionDetails>, String> {
+    let Some(contract) = symbol.strip_prefix('-') else {
+        return Ok(None);
+    };
+    let invalid = || format!("Invalid option symbol: {}", symbol);
+
+    let date_start = contract.find(|c: char| c.is_ascii_digit()).ok_or_else(invalid)?;
+    let (underlying, rest) = contract.split_at(date_start);
+    if underlying.is_empty() || rest.len() < 8 || !rest.is_ascii() {
+        return Err(invalid());
+    }
+
+    let expiration_date = NaiveDate::parse_from_str(&rest[..6], "%y%m%d").map_err(|_| invalid())?;
+    let option_type = match &rest[6..7] {
+        "C" => OptionType::Call,
+        "P" => OptionType::Put,
+        _ => return Err(invalid()),
+    };
+    let strike_price = parse_amount(&rest[7..]).ok_or_else(|| format!("Invalid strike price: {}", &rest[7..]))?;
+
+    Ok(Some(OptionDetails {
+        underlying: underlying.to_string(),
+        expiration_date,
+        option_type,
+        strike_price,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HISTORY: &str = r#"
+
+Run Date,Action,Symbol,Security Description,Security Type,Quantity,Price ($),Commission ($),Fees ($),Accrued Interest ($),Amount ($),Settlement Date
+01/19/2024,"EXPIRED PUT (SPY) SPDR S&P500 ETF JAN 19 24 $460 (100 SHS) (Margin)", -SPY240119P460,PUT (SPY) SPDR S&P500 ETF JAN 19 24 $460 (100 SHS),Margin,-1,,,,,,
+01/17/2024,"YOU BOUGHT CLOSING TRANSACTION PUT (SPY) SPDR S&P500 ETF JAN 19 24 $470 (100 SHS) (Margin)", -SPY240119P470,PUT (SPY) SPDR S&P500 ETF JAN 19 24 $470 (100 SHS),Margin,1,0.4,0.65,0.01,,-41.06,01/18/2024
+01/16/2024,"YOU SOLD APPLE INC (AAPL) (Cash)",AAPL,APPLE INC,Cash,-150,190,,0.05,,28499.95,01/18/2024
+01/16/2024,"DIVIDEND RECEIVED APPLE INC (AAPL) (Cash)",AAPL,APPLE INC,Cash,0,,,,,24.00,
+01/15/2024,"YOU SOLD OPENING TRANSACTION PUT (SPY) SPDR S&P500 ETF JAN 19 24 $470 (100 SHS) (Margin)", -SPY240119P470,PUT (SPY) SPDR S&P500 ETF JAN 19 24 $470 (100 SHS),Margin,-1,1.5,0.65,0.02,,149.33,01/16/2024
+01/15/2024,"YOU BOUGHT OPENING TRANSACTION PUT (SPY) SPDR S&P500 ETF JAN 19 24 $460 (100 SHS) (Margin)", -SPY240119P460,PUT (SPY) SPDR S&P500 ETF JAN 19 24 $460 (100 SHS),Margin,1,0.55,0.65,0.01,,-55.66,01/16/2024
+01/12/2024,"YOU BOUGHT APPLE INC (AAPL) (Cash)",AAPL,APPLE INC,Cash,100,185.5,,,,-18550.00,01/17/2024
+01/12/2024,"YOU BOUGHT OPENING TRANSACTION CALL (QQQ) INVESCO QQQ TR JAN 12 24 $400 (100 SHS) (Margin)", -QQQ2401X12C400,CALL (QQQ) INVESCO QQQ TR JAN 12 24 $400 (100 SHS),Margin,1,1.1,0.65,0.01,,-110.66,01/16/2024
+
+"The data and information in this spreadsheet is provided to you solely for your use and is not for distribution."
+"Date downloaded 01/20/2024 10:00 am"
+"#;
+
+    #[test]
+    fn test_parse_fidelity_history() {
+        let result = parse_fidelity_history(HISTORY, &[]);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line_number, 11);
+        assert_eq!(result.errors[0].error, "Invalid option symbol: -QQQ2401X12C400");
+        assert_eq!(result.executions.len(), 7);
+
+        // "YOU SOLD" carries no open/close flag, so the sell of 150 closes the long 100 and opens a short 50
+        let aapl: Vec<&TlgExecution> = result.executions.iter().filter(|e| e.symbol == "AAPL").collect();
+        assert_eq!(aapl.len(), 3);
+        assert_eq!(aapl[0].action, TlgAction::BuyToOpen);
+        assert_eq!(aapl[1].action, TlgAction::SellToClose);
+        assert_eq!(aapl[1].quantity, -100.0);
+        assert_eq!(aapl[2].action, TlgAction::SellToOpen);
+        assert_eq!(aapl[2].quantity, -50.0);
+
+        let short_put = result.executions.iter().find(|e| e.action == TlgAction::SellToOpen && e.asset_type == TlgAssetType::Option).unwrap();
+        assert_eq!(short_put.symbol, "SPY   240119P00470000");
+        assert_eq!(short_put.price, 1.5);
+        assert!((short_put.fees + 0.67).abs() < 1e-9);
+
+        // The long put expires worthless, closing with a sell
+        let expired = result.executions.last().unwrap();
+        assert_eq!(expired.symbol, "SPY   240119P00460000");
+        assert_eq!(expired.action, TlgAction::SellToClose);
+        assert_eq!(expired.quantity, -1.0);
+        assert_eq!(expired.price, 0.0);
+    }
+
+    #[test]
+    fn test_parse_fidelity_action() {
+        assert!(matches!(
+            parse_fidelity_action("YOU SOLD SHORT SALE TESLA INC (TSLA) (Short)"),
+            Some(BrokerAction::Sided { buy: false, short: true })
+        ));
+        assert!(matches!(
+            parse_fidelity_action("ASSIGNED as of Jan-19-2024 PUT (SPY) SPDR S&P500 ETF JAN 19 24 $470 (100 SHS) (Margin)"),
+            Some(BrokerAction::Removal)
+        ));
+        assert!(parse_fidelity_action("REINVESTMENT FIDELITY GOVERNMENT MONEY MARKET (SPAXX) (Cash)").is_none());
+    }
+}
@@ -0,0 +1,402 @@
+use chrono::NaiveDateTime;
+use super::csv_export::{csv_records, parse_amount};
+use super::{RoundTripParseResult, RoundTripTrade, TlgAssetType, TlgParseError};
+
+/// ISO codes of the currencies forex pairs are built from
+const CURRENCIES: [&str; 20] = [
+    "USD", "EUR", "GBP", "JPY", "CHF", "CAD", "AUD", "NZD", "SEK", "NOK",
+    "DKK", "SGD", "HKD", "MXN", "ZAR", "TRY", "PLN", "CNH", "HUF", "CZK",
+];
+
+/// One row of a statement table
+struct StatementRow {
+    line_number: usize,
+    raw: String,
+    cells: Vec<String>,
+}
+
+/// Where the fields of a closed ticket are in its table
+struct TicketColumns {
+    ticket: usize,
+    symbol: usize,
+    side: usize,
+    lots: usize,
+    open_time: usize,
+    open_price: usize,
+    close_time: usize,
+    close_price: usize,
+    commission: Option<usize>,
+    taxes: Option<usize>,
+    swap: Option<usize>,
+    profit: Option<usize>,
+}
+
+/// Parse the closed tickets of a MetaTrader 4 or 5 account statement, saved as HTML or CSV
+/// MT4 lists them under Closed Transactions and MT5 under Positions; other tables, balance
+/// operations, cancelled pending orders and tickets that are still open are skipped.
+/// Lots are converted to units with the standard contract size, so prices stay in the quote
+/// currency. The Profit column is in the account currency, so the rate converting the price move
+/// to it is taken from there; pairs quoted in another currency, such as EURGBP in a USD account,
+/// thus keep the statement's PnL. Commission, taxes and swap become the fees of the trade. Times
+/// are the broker's server time, as printed on the statement.
+pub fn parse_metatrader_statement(content: &str) -> RoundTripParseResult {
+    let mut trades = Vec::new();
+    let mut errors = Vec::new();
+    let mut columns: Option<TicketColumns> = None;
+
+    for row in statement_rows(content) {
+        // Every table starts with a header; only those of closed tickets are read
+        if is_header(&row.cells) {
+            columns = ticket_columns(&row.cells);
+            continue;
+        }
+        let Some(ref header) = columns else {
+            continue;
+        };
+
+        match parse_ticket(&row.cells, header) {
+            Ok(Some(trade)) => trades.push(trade),
+            Ok(None) => {}
+            Err(e) => errors.push(TlgParseError {
+                line_number: row.line_number,
+                line_content: row.raw,
+                error: e,
+            }),
+        }
+    }
+
+    RoundTripParseResult { trades, errors }
+}
+
+/// Rows of the statement, from its HTML tables or its CSV lines
+fn statement_rows(content: &str) -> Vec<StatementRow> {
+    if content.to_ascii_lowercase().contains("<tr") {
+        return html_rows(content);
+    }
+    // Statements saved from the terminal are tab separated
+    if content.contains('\t') {
+        return content
+            .lines()
+            .enumerate()
+            .map(|(i, line)| StatementRow {
+                line_number: i + 1,
+                raw: line.to_string(),
+                cells: line.split('\t').map(|c| c.trim().to_string()).collect(),
+            })
+            .collect();
+    }
+    csv_records(content)
+        .into_iter()
+        .map(|record| StatementRow {
+            line_number: record.line_number,
+            cells: record.fields.iter().map(|f| f.trim().to_string()).collect(),
+            raw: record.raw,
+        })
+        .collect()
+}
+
+/// Rows of every table in an HTML statement
+/// Cells spanning several columns fill all of them, so cells line up with their header.
+fn html_rows(content: &str) -> Vec<StatementRow> {
+    // ASCII lowercasing keeps byte offsets, so positions found in it index the content too
+    let lower = content.to_ascii_lowercase();
+    let mut rows = Vec::new();
+    let mut pos = 0;
+
+    while let Some(offset) = lower[pos..].find("<tr") {
+        let start = pos + offset;
+        let end = [lower[start + 3..].find("</tr"), lower[start + 3..].find("<tr")]
+            .into_iter()
+            .flatten()
+            .min()
+            .map_or(lower.len(), |i| start + 3 + i);
+
+        let mut cells = Vec::new();
+        let mut cell_pos = start;
+        while let Some(cell_start) = next_cell(&lower, cell_pos, end) {
+            let tag_end = lower[cell_start..end].find('>').map_or(end, |i| cell_start + i + 1);
+            let text_end = next_cell(&lower, tag_end, end)
+                .into_iter()
+                .chain(lower[tag_end..end].find("</t").map(|i| tag_end + i))
+                .min()
+                .unwrap_or(end);
+
+            cells.push(html_text(&content[tag_end..text_end]));
+            for _ in 1..colspan(&lower[cell_start..tag_end]) {
+                cells.push(String::new());
+            }
+            cell_pos = text_end;
+        }
+
+        rows.push(StatementRow {
+            line_number: content[..start].matches('\n').count() + 1,
+            raw: content[start..end].trim().to_string(),
+            cells,
+        });
+        pos = end;
+    }
+    rows
+}
+
+/// Start of the next td or th tag between from and end
+fn next_cell(lower: &str, from: usize, end: usize) -> Option<usize> {
+    let mut pos = from;
+    while pos < end {
+        let i = pos + lower[pos..end].find("<t")?;
+        let is_cell = matches!(lower.as_bytes().get(i + 2), Some(b'd' | b'h'))
+            && matches!(lower.as_bytes().get(i + 3), Some(b'>' | b' ' | b'\t' | b'\n' | b'\r'));
+        if is_cell {
+            return Some(i);
+        }
+        pos = i + 2;
+    }
+    None
+}
+
+/// Number of columns a cell's opening tag spans
+fn colspan(tag: &str) -> usize {
+    tag.split_once("colspan=")
+        .map(|(_, rest)| {
+            rest.trim_start_matches(['"', '\''])
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect::<String>()
+        })
+        .and_then(|digits| digits.parse().ok())
+        .unwrap_or(1)
+}
+
+/// Text of an HTML fragment without its tags
+fn html_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&nbsp;", " ")
+        .replace("&#160;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+/// True for the header row of a table of tickets, deals or orders
+fn is_header(cells: &[String]) -> bool {
+    let has = |name: &str| cells.iter().any(|c| c.eq_ignore_ascii_case(name));
+    has("type") && (has("item") || has("symbol"))
+}
+
+/// Columns of a header of closed tickets, or None for the header of another table
+/// MT4 names the times Open Time and Close Time; MT5 repeats Time, and both repeat Price.
+fn ticket_columns(cells: &[String]) -> Option<TicketColumns> {
+    let positions = |name: &str| -> Vec<usize> {
+        cells
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.eq_ignore_ascii_case(name))
+            .map(|(i, _)| i)
+            .collect()
+    };
+    let first = |names: &[&str]| names.iter().find_map(|name| positions(name).first().copied());
+    let times = positions("time");
+    let prices = positions("price");
+
+    Some(TicketColumns {
+        ticket: first(&["ticket", "position"])?,
+        symbol: first(&["item", "symbol"])?,
+        side: first(&["type"])?,
+        lots: first(&["size", "volume", "lots"])?,
+        open_time: first(&["open time"]).or(times.first().copied())?,
+        open_price: prices.first().copied()?,
+        close_time: first(&["close time"]).or(times.get(1).copied())?,
+        close_price: prices.get(1).copied()?,
+        commission: first(&["commission"]),
+        taxes: first(&["taxes", "fee"]),
+        swap: first(&["swap"]),
+        profit: first(&["profit"]),
+    })
+}
+
+/// Parse one ticket row, or None for rows that are not a closed buy or sell
+fn parse_ticket(cells: &[String], columns: &TicketColumns) -> Result<Option<RoundTripTrade>, String> {
+    let cell = |i: usize| cells.get(i).map(|c| c.trim()).unwrap_or("");
+
+    let short = match cell(columns.side).to_lowercase().as_str() {
+        "buy" => false,
+        "sell" => true,
+        // Balance operations, pending orders and summary lines
+        _ => return Ok(None),
+    };
+    if cell(columns.close_time).is_empty() {
+        return Ok(None);
+    }
+
+    let ticket = cell(columns.ticket);
+    if ticket.is_empty() {
+        return Err("Missing ticket".to_string());
+    }
+    let broker_symbol = cell(columns.symbol).to_uppercase();
+    if broker_symbol.is_empty() {
+        return Err("Missing symbol".to_string());
+    }
+    let (symbol, asset_type, contract_size) = contract_size(&broker_symbol);
+
+    // MT5 may show the volume as filled / requested
+    let lots_field = cell(columns.lots).split('/').next().unwrap_or_default();
+    let lots = parse_amount(lots_field).ok_or_else(|| format!("Invalid volume: {}", cell(columns.lots)))?;
+    let entry_price = parse_amount(cell(columns.open_price))
+        .ok_or_else(|| format!("Invalid open price: {}", cell(columns.open_price)))?;
+    let exit_price = parse_amount(cell(columns.close_price))
+        .ok_or_else(|| format!("Invalid close price: {}", cell(columns.close_price)))?;
+    let entry_time = parse_statement_time(cell(columns.open_time))?;
+    let exit_time = parse_statement_time(cell(columns.close_time))?;
+
+    // Charges are negative on the statement and swap may be a credit
+    let mut charges = 0.0;
+    for (name, index) in [("commission", columns.commission), ("taxes", columns.taxes), ("swap", columns.swap)] {
+        let value = index.map(cell).unwrap_or("");
+        if !value.is_empty() {
+            charges += parse_amount(value).ok_or_else(|| format!("Invalid {}: {}", name, value))?;
+        }
+    }
+
+    // Profit is the price move in the account currency; a ticket closed at its open price shows none
+    let quantity = lots.abs() * contract_size;
+    let price_move = (if short { entry_price - exit_price } else { exit_price - entry_price }) * quantity;
+    let fx_rate = match columns.profit.map(cell).filter(|value| !value.is_empty()) {
+        Some(value) if price_move != 0.0 => {
+            let profit = parse_amount(value).ok_or_else(|| format!("Invalid profit: {}", value))?;
+            Some(profit / price_move).filter(|rate| *rate > 0.0)
+        }
+        _ => None,
+    };
+
+    Ok(Some(RoundTripTrade {
+        broker_trade_id: format!("metatrader-{}", ticket),
+        symbol,
+        asset_type,
+        multiplier: 1.0,
+        short,
+        quantity,
+        entry_price,
+        exit_price,
+        entry_time,
+        exit_time,
+        commission: -charges,
+        fx_rate,
+    }))
+}
+
+/// Symbol, asset type and units per lot of a MetaTrader symbol
+/// Forex pairs and spot metals are recognised by their currency codes, ignoring broker suffixes
+/// such as EURUSD.m; anything else imports as a stock CFD of one unit per lot.
+fn contract_size(symbol: &str) -> (String, TlgAssetType, f64) {
+    let letters: String = symbol.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+    if letters.len() >= 6 {
+        let (base, quote) = (&letters[..3], &letters[3..6]);
+        let pair = format!("{}{}", base, quote);
+        if CURRENCIES.contains(&quote) {
+            match base {
+                "XAU" => return (pair, TlgAssetType::Forex, 100.0),
+                "XAG" => return (pair, TlgAssetType::Forex, 5000.0),
+                _ if CURRENCIES.contains(&base) => return (pair, TlgAssetType::Forex, 100000.0),
+                _ => {}
+            }
+        }
+    }
+    (symbol.to_string(), TlgAssetType::Stock, 1.0)
+}
+
+/// Statement time as YYYY.MM.DD HH:MM:SS, or without seconds as MT4 prints it
+fn parse_statement_time(s: &str) -> Result<NaiveDateTime, String> {
+    NaiveDateTime::parse_from_str(s, "%Y.%m.%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y.%m.%d %H:%M"))
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S"))
+        .map_err(|_| format!("Invalid time: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    const MT4_STATEMENT: &str = r##"<html><body><table>
+<tr align=left><td colspan=13><b>Closed Transactions:</b></td></tr>
+<tr align=center bgcolor="#C0C0C0"><td>Ticket</td><td nowrap>Open Time</td><td>Type</td><td>Size</td><td>Item</td><td>Price</td><td>S / L</td><td>T / P</td><td nowrap>Close Time</td><td>Price</td><td>Commission</td><td>Taxes</td><td>Swap</td><td>Profit</td></tr>
+<tr align=right><td>50123</td><td class=msdate nowrap>2024.01.15 09:31</td><td>buy</td><td class=mspt>0.50</td><td>eurusd.m</td><td>1.09500</td><td>0.00000</td><td>0.00000</td><td class=msdate nowrap>2024.01.17 15:02</td><td>1.09620</td><td>-3.50</td><td>0.00</td><td>-1.25</td><td>60.00</td></tr>
+<tr bgcolor=#E0E0E0 align=right><td>50124</td><td class=msdate nowrap>2024.01.16 10:00</td><td>sell</td><td>1.00</td><td>xauusd</td><td>2 030.50</td><td>0.00</td><td>0.00</td><td class=msdate nowrap>2024.01.16 11:30</td><td>2 025.50</td><td>-7.00</td><td>0.00</td><td>0.00</td><td>500.00</td></tr>
+<tr align=right><td>50125</td><td class=msdate nowrap>2024.01.16 12:00</td><td>buy limit</td><td>1.00</td><td>gbpusd</td><td>1.27000</td><td>0.00000</td><td>0.00000</td><td class=msdate nowrap>2024.01.16 18:00</td><td>1.27500</td><td colspan=4 style="text-align:center;">cancelled</td></tr>
+<tr align=right><td>50126</td><td class=msdate nowrap>2024.01.17 08:00</td><td>balance</td><td colspan=10 align=left>Deposit</td><td>1 000.00</td></tr>
+<tr align=right><td>50127</td><td class=msdate nowrap>2024.01.17 09:00</td><td>sell</td><td>abc</td><td>usdjpy</td><td>148.100</td><td>0.000</td><td>0.000</td><td class=msdate nowrap>2024.01.17 10:00</td><td>147.900</td><td>0.00</td><td>0.00</td><td>0.00</td><td>13.52</td></tr>
+<tr align=left><td colspan=13><b>Open Trades:</b></td></tr>
+<tr align=center bgcolor="#C0C0C0"><td>Ticket</td><td nowrap>Open Time</td><td>Type</td><td>Size</td><td>Item</td><td>Price</td><td>S / L</td><td>T / P</td><td nowrap>&nbsp;</td><td>Price</td><td>Commission</td><td>Taxes</td><td>Swap</td><td>Profit</td></tr>
+<tr align=right><td>50128</td><td class=msdate nowrap>2024.01.18 09:00</td><td>buy</td><td>0.10</td><td>eurusd</td><td>1.08800</td><td>0.00000</td><td>0.00000</td><td>&nbsp;</td><td>1.08900</td><td>0.00</td><td>0.00</td><td>0.00</td><td>10.00</td></tr>
+</table></body></html>
+"##;
+
+    #[test]
+    fn test_parse_mt4_html_statement() {
+        let result = parse_metatrader_statement(MT4_STATEMENT);
+        assert_eq!(result.trades.len(), 2);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line_number, 8);
+        assert_eq!(result.errors[0].error, "Invalid volume: abc");
+
+        let eurusd = &result.trades[0];
+        assert_eq!(eurusd.broker_trade_id, "metatrader-50123");
+        assert_eq!(eurusd.symbol, "EURUSD");
+        assert_eq!(eurusd.asset_type, TlgAssetType::Forex);
+        assert!(!eurusd.short);
+        assert_eq!(eurusd.quantity, 50000.0);
+        assert_eq!(eurusd.multiplier, 1.0);
+        assert_eq!(eurusd.entry_time, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap().and_hms_opt(9, 31, 0).unwrap());
+        // Commission and swap
+        assert!((eurusd.commission - 4.75).abs() < 1e-9);
+        // Quoted in the account currency, so the profit matches the price move
+        assert!((eurusd.fx_rate.unwrap() - 1.0).abs() < 1e-9);
+
+        let gold = &result.trades[1];
+        assert_eq!(gold.symbol, "XAUUSD");
+        assert!(gold.short);
+        assert_eq!(gold.quantity, 100.0);
+        assert_eq!(gold.entry_price, 2030.5);
+    }
+
+    #[test]
+    fn test_parse_mt5_positions_csv() {
+        let content = r#"Positions
+Time,Position,Symbol,Type,Volume,Price,S / L,T / P,Time,Price,Commission,Swap,Profit
+2024.01.15 09:31:02,7001,GBPJPY,sell,0.2,185.250,,,2024.01.15 11:00:45,184.950,-1.40,0.00,40.80
+2024.01.15 10:00:00,7002,US30,buy,2,37500.0,,,2024.01.16 10:00:00,37600.0,0.00,2.50,200.00
+
+Deals
+Time,Deal,Symbol,Type,Direction,Volume,Price,Order,Commission,Fee,Swap,Profit,Balance,Comment
+2024.01.15 09:31:02,9001,GBPJPY,sell,in,0.2,185.250,8001,-0.70,0.00,0.00,0.00,10000.00,
+"#;
+        let result = parse_metatrader_statement(content);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.trades.len(), 2);
+
+        let pair = &result.trades[0];
+        assert_eq!(pair.broker_trade_id, "metatrader-7001");
+        assert!(pair.short);
+        assert_eq!(pair.quantity, 20000.0);
+        assert_eq!(pair.exit_time, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap().and_hms_opt(11, 0, 45).unwrap());
+        assert!((pair.commission - 1.4).abs() < 1e-9);
+        // 6,000 yen of profit shown as 40.80 in the account currency
+        assert!((pair.fx_rate.unwrap() - 0.0068).abs() < 1e-9);
+
+        // Index CFDs trade one unit per lot, and a swap credit lowers the fees
+        let index = &result.trades[1];
+        assert_eq!(index.asset_type, TlgAssetType::Stock);
+        assert_eq!(index.quantity, 2.0);
+        assert!((index.commission + 2.5).abs() < 1e-9);
+    }
+}
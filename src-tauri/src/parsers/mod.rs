@@ -1,3 +1,34 @@
+pub mod binance_parser;
+pub mod coinbase_parser;
+mod crypto_pairs;
+mod csv_export;
+pub mod etrade_parser;
+pub mod fidelity_parser;
+pub mod metatrader_parser;
+pub mod ninjatrader_parser;
+pub mod registry;
+pub mod robinhood_parser;
+pub mod schwab_parser;
+pub mod tastytrade_parser;
 pub mod tlg_parser;
+pub mod tos_parser;
+pub mod tradingview_parser;
+pub mod tradovate_parser;
+pub mod webull_parser;
 
+pub use binance_parser::*;
+pub use coinbase_parser::*;
+pub use csv_export::OpeningPosition;
+pub use etrade_parser::*;
+pub use fidelity_parser::*;
+pub use metatrader_parser::*;
+pub use ninjatrader_parser::*;
+pub use registry::*;
+pub use robinhood_parser::*;
+pub use schwab_parser::*;
+pub use tastytrade_parser::*;
 pub use tlg_parser::*;
+pub use tos_parser::*;
+pub use tradingview_parser::*;
+pub use tradovate_parser::*;
+pub use webull_parser::*;
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use chrono::NaiveDateTime;
+use super::csv_export::{column, csv_records, parse_amount};
+use super::{contract_multiplier, TlgAssetType, TlgParseError};
+
+/// A completed trade from an export that pairs entries with exits itself
+#[derive(Debug, Clone)]
+pub struct RoundTripTrade {
+    pub broker_trade_id: String, // Stable ID derived from the trade's contents
+    pub symbol: String,
+    pub asset_type: TlgAssetType,
+    pub multiplier: f64,
+    pub short: bool,
+    pub quantity: f64, // Always positive
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub entry_time: NaiveDateTime,
+    pub exit_time: NaiveDateTime,
+    pub commission: f64, // Costs of the whole round trip, negative for a net credit
+    pub fx_rate: Option<f64>, // Converts PnL from the quote currency to the account's; None when the same
+}
+
+/// Result of parsing an export of round-trip trades
+#[derive(Debug, Clone)]
+pub struct RoundTripParseResult {
+    pub trades: Vec<RoundTripTrade>,
+    pub errors: Vec<TlgParseError>,
+}
+
+/// Parse the Trades grid of a NinjaTrader performance report exported as CSV
+/// Each row is a completed round trip, so no open/close matching is needed. Futures are named like
+/// "ES 03-24" and get their multiplier from the product code; anything else imports as a stock.
+/// Trade numbers restart with every report, so trades get a stable ID from their contents instead.
+pub fn parse_ninjatrader_trades(content: &str) -> RoundTripParseResult {
+    let mut trades = Vec::new();
+    let mut errors = Vec::new();
+    let mut columns: Option<HashMap<String, usize>> = None;
+    let mut seen_ids: HashMap<String, usize> = HashMap::new();
+
+    for record in csv_records(content) {
+        if record.is_blank() {
+            continue;
+        }
+        let Some(ref header) = columns else {
+            columns = Some(record.header_columns());
+            continue;
+        };
+
+        match parse_trade(&record.fields, header) {
+            Ok(mut trade) => {
+                // Identical trades in one report are told apart by their order
+                let occurrence = seen_ids.entry(trade.broker_trade_id.clone()).or_insert(0);
+                *occurrence += 1;
+                trade.broker_trade_id = format!("{}-{}", trade.broker_trade_id, occurrence);
+                trades.push(trade);
+            }
+            Err(e) => errors.push(TlgParseError {
+                line_number: record.line_number,
+                line_content: record.raw,
+                error: e,
+            }),
+        }
+    }
+
+    RoundTripParseResult { trades, errors }
+}
+
+/// Parse one row of the Trades grid
+/// Columns: Trade number,Instrument,Account,Strategy,Market pos.,Qty,Entry price,Exit price,
+/// Entry time,Exit time,Entry name,Exit name,Profit,Cum. net profit,Commission,MAE,MFE,ETD,Bars
+fn parse_trade(fields: &[String], header: &HashMap<String, usize>) -> Result<RoundTripTrade, String> {
+    let field = |name: &str| column(fields, header, name);
+
+    let short = match field("Market pos.").to_uppercase().as_str() {
+        "LONG" => false,
+        "SHORT" => true,
+        _ => return Err(format!("Unknown market position: {}", field("Market pos."))),
+    };
+
+    let symbol = field("Instrument").to_uppercase();
+    if symbol.is_empty() {
+        return Err("Missing instrument".to_string());
+    }
+    let (asset_type, multiplier) = match future_product(&symbol) {
+        Some(product) => (
+            TlgAssetType::Future,
+            contract_multiplier(product)
+                .ok_or_else(|| format!("Unknown contract multiplier for product: {}", product))?,
+        ),
+        None => (TlgAssetType::Stock, 1.0),
+    };
+
+    let quantity = parse_amount(field("Qty")).ok_or_else(|| format!("Invalid quantity: {}", field("Qty")))?.abs();
+    let entry_price = parse_amount(field("Entry price"))
+        .ok_or_else(|| format!("Invalid entry price: {}", field("Entry price")))?;
+    let exit_price = parse_amount(field("Exit price"))
+        .ok_or_else(|| format!("Invalid exit price: {}", field("Exit price")))?;
+    let commission = match field("Commission") {
+        "" => 0.0,
+        commission => parse_amount(commission)
+            .ok_or_else(|| format!("Invalid commission: {}", commission))?
+            .abs(),
+    };
+    let entry_time = parse_trade_time(field("Entry time"))?;
+    let exit_time = parse_trade_time(field("Exit time"))?;
+
+    let broker_trade_id = format!(
+        "ninjatrader-{}-{}-{}-{}-{}-{}-{}",
+        field("Account"),
+        entry_time.format("%Y%m%d%H%M%S"),
+        exit_time.format("%Y%m%d%H%M%S"),
+        symbol.split_whitespace().collect::<String>(),
+        if short { -quantity } else { quantity },
+        entry_price,
+        exit_price
+    );
+
+    Ok(RoundTripTrade {
+        broker_trade_id,
+        symbol,
+        asset_type,
+        multiplier,
+        short,
+        quantity,
+        entry_price,
+        exit_price,
+        entry_time,
+        exit_time,
+        commission,
+        fx_rate: None,
+    })
+}
+
+/// Product code of a NinjaTrader futures instrument such as "ES 03-24", or None for other instruments
+fn future_product(instrument: &str) -> Option<&str> {
+    let (product, expiry) = instrument.split_once(' ')?;
+    let (month, year) = expiry.split_once('-')?;
+    let is_digits = |s: &str| s.len() == 2 && s.chars().all(|c| c.is_ascii_digit());
+    (is_digits(month) && is_digits(year)).then_some(product)
+}
+
+/// Trade time as M/D/YYYY h:mm:ss AM/PM, or with a 24-hour clock
+fn parse_trade_time(s: &str) -> Result<NaiveDateTime, String> {
+    NaiveDateTime::parse_from_str(s, "%m/%d/%Y %I:%M:%S %p")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%m/%d/%Y %H:%M:%S"))
+        .map_err(|_| format!("Invalid trade time: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    const TRADES: &str = r#"Trade number,Instrument,Account,Strategy,Market pos.,Qty,Entry price,Exit price,Entry time,Exit time,Entry name,Exit name,Profit,Cum. net profit,Commission,MAE,MFE,ETD,Bars
+1,ES 03-24,Sim101,,Long,2,4800.00,4810.25,1/15/2024 9:31:02 AM,1/15/2024 10:15:00 AM,Entry,Exit,$1025.00,$1019.84,$5.16,$50.00,$1100.00,$75.00,9
+2,MNQ 03-24,Sim101,,Short,1,"16,800.50",16790.50,1/15/2024 1:45:00 PM,1/16/2024 9:30:00 AM,Entry,Exit,$20.00,$1039.32,$0.52,$10.00,$30.00,$10.00,40
+3,AAPL,Sim101,,Long,100,185.50,190.25,1/15/2024 9:31:02 AM,1/15/2024 2:45:00 PM,Buy,Sell,$475.00,$1514.32,$0.00,$20.00,$500.00,$25.00,12
+4,ZZ 03-24,Sim101,,Long,1,10.00,11.00,1/15/2024 9:50:00 AM,1/15/2024 9:55:00 AM,Entry,Exit,$1.00,$1515.32,$0.00,$0.00,$0.00,$0.00,1
+"#;
+
+    #[test]
+    fn test_parse_ninjatrader_trades() {
+        let result = parse_ninjatrader_trades(TRADES);
+        assert_eq!(result.trades.len(), 3);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line_number, 5);
+        assert_eq!(result.errors[0].error, "Unknown contract multiplier for product: ZZ");
+
+        let es = &result.trades[0];
+        assert_eq!(es.symbol, "ES 03-24");
+        assert_eq!(es.asset_type, TlgAssetType::Future);
+        assert_eq!(es.multiplier, 50.0);
+        assert!(!es.short);
+        assert_eq!(es.quantity, 2.0);
+        assert_eq!(es.commission, 5.16);
+        assert_eq!(es.entry_time, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap().and_hms_opt(9, 31, 2).unwrap());
+
+        let mnq = &result.trades[1];
+        assert!(mnq.short);
+        assert_eq!(mnq.entry_price, 16800.5);
+        assert_eq!(mnq.exit_time, NaiveDate::from_ymd_opt(2024, 1, 16).unwrap().and_hms_opt(9, 30, 0).unwrap());
+
+        let aapl = &result.trades[2];
+        assert_eq!(aapl.asset_type, TlgAssetType::Stock);
+        assert_eq!(aapl.multiplier, 1.0);
+
+        // IDs are stable across parses
+        let again = parse_ninjatrader_trades(TRADES);
+        assert_eq!(again.trades[1].broker_trade_id, mnq.broker_trade_id);
+        assert_ne!(es.broker_trade_id, aapl.broker_trade_id);
+    }
+}
@@ -0,0 +1,116 @@
+use serde::Serialize;
+use super::{
+    BinanceParser, CoinbaseParser, EtradeParser, FidelityParser, OpeningPosition, RobinhoodParser, SchwabParser, TastytradeParser,
+    TlgParseResult, TlgParser, TosParser, TradovateParser, WebullParser,
+};
+
+/// A broker export that parses into executions shaped like TLG ones
+/// Exports of whole round-trip trades (NinjaTrader, MetaTrader, TradingView) import without a
+/// preview and are not registered.
+pub trait BrokerParser: Sync {
+    /// Identifier the frontend selects the parser by, such as "webull"
+    fn id(&self) -> &'static str;
+
+    /// Broker and export as shown to users
+    fn name(&self) -> &'static str;
+
+    /// Whether content looks like this parser's export
+    fn detect(&self, content: &str) -> bool;
+
+    /// Parse content into executions, with the lines that failed
+    /// Exports that don't say whether a fill opens or closes match fills against `opening`.
+    fn parse(&self, content: &str, opening: &[OpeningPosition]) -> TlgParseResult;
+}
+
+/// Registered parsers, in the order they are tried when detecting a file's format
+/// Adding a broker takes a parser module with a BrokerParser and an entry here.
+static BROKER_PARSERS: &[&dyn BrokerParser] = &[
+    &TlgParser,
+    &TosParser,
+    &WebullParser,
+    &RobinhoodParser,
+    &SchwabParser,
+    &EtradeParser,
+    &FidelityParser,
+    &TastytradeParser,
+    &TradovateParser,
+    &BinanceParser,
+    &CoinbaseParser,
+];
+
+/// A registered parser as listed to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct SupportedBroker {
+    pub id: String,
+    pub name: String,
+}
+
+pub fn supported_brokers() -> Vec<SupportedBroker> {
+    BROKER_PARSERS
+        .iter()
+        .map(|parser| SupportedBroker {
+            id: parser.id().to_string(),
+            name: parser.name().to_string(),
+        })
+        .collect()
+}
+
+/// The registered parser with an ID
+pub fn broker_parser(id: &str) -> Option<&'static dyn BrokerParser> {
+    BROKER_PARSERS.iter().copied().find(|parser| parser.id() == id)
+}
+
+/// The first registered parser that recognizes the content
+pub fn detect_broker(content: &str) -> Option<&'static dyn BrokerParser> {
+    BROKER_PARSERS.iter().copied().find(|parser| parser.detect(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detected(content: &str) -> Option<&'static str> {
+        detect_broker(content).map(|parser| parser.id())
+    }
+
+    #[test]
+    fn test_detect_broker() {
+        let tlg = "ACCOUNT_INFORMATION\nSTK_TRD|1001|AAPL|APPLE INC|DARK|BUYTOOPEN|O|20260127|09:30:00|USD|100.00|1.00|150.00|15000.00|-1.00|0.85\n";
+        assert_eq!(detected(tlg), Some("tlg"));
+        assert_eq!(detected("ACCOUNT_INFORMATION\nACT_INF|U6498184|Test User|Individual|Address\n"), Some("tlg"));
+        assert_eq!(
+            detected("Account Statement for 123456789\n\nAccount Trade History\n,Exec Time,Spread,Side,Qty\n"),
+            Some("tos")
+        );
+        assert_eq!(
+            detected("\"Activity Date\",\"Process Date\",\"Settle Date\",\"Instrument\",\"Description\",\"Trans Code\",\"Quantity\",\"Price\",\"Amount\"\n"),
+            Some("robinhood")
+        );
+        assert_eq!(
+            detected("DATE,TRANSACTION ID,DESCRIPTION,QUANTITY,SYMBOL,PRICE,COMMISSION,AMOUNT\n"),
+            Some("schwab")
+        );
+        assert_eq!(
+            detected("\n\nRun Date,Action,Symbol,Security Description,Security Type,Quantity,Price ($)\n"),
+            Some("fidelity")
+        );
+
+        // E*TRADE and the Coinbase transaction history both have a Transaction Type column
+        assert_eq!(
+            detected("For Account:,#####1234\n\nTransaction Date,Transaction Type,Security Type,Symbol,Quantity\n"),
+            Some("etrade")
+        );
+        assert_eq!(
+            detected("Transactions\nUser,Jane Doe,a1b2c3\nID,Timestamp,Transaction Type,Asset,Quantity Transacted,Price Currency\n"),
+            Some("coinbase")
+        );
+
+        assert_eq!(detected("Date,Description,Amount\n01/02/2024,Coffee,-4.50\n"), None);
+        assert_eq!(broker_parser("binance").map(|parser| parser.name()), Some("Binance trade history"));
+        assert!(broker_parser("mt4").is_none());
+
+        let ids: Vec<String> = supported_brokers().into_iter().map(|broker| broker.id).collect();
+        let unique: std::collections::HashSet<&String> = ids.iter().collect();
+        assert_eq!(unique.len(), ids.len());
+    }
+}
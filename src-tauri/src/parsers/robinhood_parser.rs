@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use super::csv_export::{assign_content_ids, column, csv_records, has_columns, parse_amount};
+use super::{BrokerParser, OpeningPosition, OptionDetails, OptionType, TlgAction, TlgAssetType, TlgExecution, TlgParseError, TlgParseResult};
+
+/// Description prefix of option expirations, e.g. "Option Expiration for SPY 1/19/2024 Call $475.00"
+const EXPIRATION_PREFIX: &str = "Option Expiration for ";
+
+/// Parse a Robinhood activity report CSV into executions shaped like TLG ones
+/// Only stock and option trades are read; deposits, dividends and other activity are skipped.
+/// The report has no execution times or IDs, so executions keep the report's order within a day
+/// and get a stable ID from their contents. Expired options close at a price of zero.
+pub fn parse_robinhood_report(content: &str) -> TlgParseResult {
+    let mut executions = Vec::new();
+    let mut errors = Vec::new();
+    let mut columns: Option<HashMap<String, usize>> = None;
+
+    for record in csv_records(content) {
+        if record.is_blank() {
+            continue;
+        }
+        let Some(ref header) = columns else {
+            columns = Some(record.header_columns());
+            continue;
+        };
+
+        // The report ends with a disclaimer row without an activity date
+        if column(&record.fields, header, "Activity Date").is_empty() {
+            continue;
+        }
+
+        match parse_activity(&record.fields, header) {
+            Ok(Some(execution)) => executions.push(execution),
+            Ok(None) => {}
+            Err(e) => errors.push(TlgParseError {
+                line_number: record.line_number,
+                line_content: record.raw,
+                error: e,
+            }),
+        }
+    }
+
+    // Reports list the newest activity first
+    executions.reverse();
+    executions.sort_by_key(|e| e.execution_date);
+
+    assign_content_ids("robinhood", &mut executions);
+    TlgParseResult { executions, errors }
+}
+
+/// Robinhood activity reports
+pub struct RobinhoodParser;
+
+impl BrokerParser for RobinhoodParser {
+    fn id(&self) -> &'static str {
+        "robinhood"
+    }
+
+    fn name(&self) -> &'static str {
+        "Robinhood activity report"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        has_columns(content, &["Activity Date", "Instrument", "Trans Code"])
+    }
+
+    fn parse(&self, content: &str, _opening: &[OpeningPosition]) -> TlgParseResult {
+        parse_robinhood_report(content)
+    }
+}
+
+/// Parse one activity row, or None when it is not a trade
+/// Columns: Activity Date,Process Date,Settle Date,Instrument,Description,Trans Code,Quantity,Price,Amount
+fn parse_activity(fields: &[String], header: &HashMap<String, usize>) -> Result<Option<TlgExecution>, String> {
+    let field = |name: &str| column(fields, header, name);
+
+    let trans_code = field("Trans Code").to_uppercase();
+    let (action, is_option) = match trans_code.as_str() {
+        // Robinhood stock trades are always long: buys open and sells close
+        "BUY" => (TlgAction::BuyToOpen, false),
+        "SELL" => (TlgAction::SellToClose, false),
+        "BTO" => (TlgAction::BuyToOpen, true),
+        "STC" => (TlgAction::SellToClose, true),
+        "STO" => (TlgAction::SellToOpen, true),
+        "BTC" => (TlgAction::BuyToClose, true),
+        // Closes either side; only the closing quantity matters for matching
+        "OEXP" => (TlgAction::SellToClose, true),
+        _ => return Ok(None),
+    };
+
+    let execution_date = NaiveDate::parse_from_str(field("Activity Date"), "%m/%d/%Y")
+        .map_err(|_| format!("Invalid activity date: {}", field("Activity Date")))?;
+
+    // Expiration quantities carry a trailing S, e.g. "2S"
+    let quantity = parse_amount(field("Quantity").trim_end_matches('S'))
+        .ok_or_else(|| format!("Invalid quantity: {}", field("Quantity")))?
+        .abs();
+    let price = match field("Price") {
+        "" if trans_code == "OEXP" => 0.0,
+        price => parse_amount(price).ok_or_else(|| format!("Invalid price: {}", price))?,
+    };
+
+    let description = field("Description");
+    let option_details = if is_option {
+        Some(parse_robinhood_option_description(description)?)
+    } else {
+        None
+    };
+    let (symbol, asset_type, multiplier) = match option_details {
+        Some(ref details) => (details.occ_symbol(), TlgAssetType::Option, 100.0),
+        None => match field("Instrument").to_uppercase() {
+            instrument if instrument.is_empty() => return Err("Missing instrument".to_string()),
+            instrument => (instrument, TlgAssetType::Stock, 1.0),
+        },
+    };
+
+    // Fees are the difference between the trade value and the cash amount
+    let buy = matches!(action, TlgAction::BuyToOpen | TlgAction::BuyToClose);
+    let quantity = if buy { quantity } else { -quantity };
+    let total = quantity * price * multiplier;
+    let fees = match parse_amount(field("Amount")) {
+        Some(amount) => ((-total - amount).abs() * 100.0).round() / 100.0,
+        None => 0.0,
+    };
+
+    Ok(Some(TlgExecution {
+        broker_execution_id: String::new(), // Assigned once all activity is parsed
+        symbol,
+        name: description.lines().next().unwrap_or("").to_string(),
+        exchange: "ROBINHOOD".to_string(),
+        action,
+        execution_date,
+        execution_time: String::new(),
+        currency: "USD".to_string(),
+        quantity,
+        multiplier,
+        price,
+        total,
+        fees: -fees, // Negative like TLG fees
+        fx_rate: None,
+        asset_type,
+        option_details,
+        future_details: None,
+    }))
+}
+
+/// Parse a Robinhood option description to extract contract details
+/// Format: "SPY 1/19/2024 Call $475.00", optionally prefixed with "Option Expiration for "
+pub fn parse_robinhood_option_description(description: &str) -> Result<OptionDetails, String> {
+    let contract = description.trim().trim_start_matches(EXPIRATION_PREFIX);
+    let parts: Vec<&str> = contract.split_whitespace().collect();
+    let [underlying, expiration, option_type, strike] = parts[..] else {
+        return Err(format!("Invalid option description: {}", description));
+    };
+
+    let expiration_date = NaiveDate::parse_from_str(expiration, "%m/%d/%Y")
+        .map_err(|_| format!("Invalid expiration in option description: {}", description))?;
+    let option_type = match option_type.to_uppercase().as_str() {
+        "CALL" => OptionType::Call,
+        "PUT" => OptionType::Put,
+        _ => return Err(format!("Invalid option type in option description: {}", description)),
+    };
+    let strike_price = parse_amount(strike)
+        .ok_or_else(|| format!("Invalid strike price: {}", strike))?;
+
+    Ok(OptionDetails {
+        underlying: underlying.to_uppercase(),
+        expiration_date,
+        option_type,
+        strike_price,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = r#""Activity Date","Process Date","Settle Date","Instrument","Description","Trans Code","Quantity","Price","Amount"
+"1/19/2024","1/19/2024","1/19/2024","SPY","Option Expiration for SPY 1/19/2024 Put $470.00","OEXP","1S","",""
+"1/16/2024","1/16/2024","1/18/2024","AAPL","Apple
+CUSIP: 037833100","Sell","100","$190.00","$18,999.95"
+"1/16/2024","1/16/2024","1/16/2024","","ACH Deposit","ACH","","","$1,000.00"
+"1/15/2024","1/15/2024","1/16/2024","SPY","SPY 1/19/2024 Put $470.00","STO","1","$1.50","$149.96"
+"1/15/2024","1/15/2024","1/17/2024","AAPL","Apple
+CUSIP: 037833100","Buy","100","$185.50","($18,550.00)"
+"1/15/2024","1/15/2024","1/16/2024","SPY","SPY 1/19/2024 Put","BTO","1","$1.50","($150.04)"
+"","","","","","","","","","The data provided is for informational purposes only."
+"#;
+
+    #[test]
+    fn test_parse_robinhood_report() {
+        let result = parse_robinhood_report(REPORT);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line_number, 9);
+        assert_eq!(result.executions.len(), 4);
+
+        // Oldest first, keeping the report's order within a day
+        let buy = &result.executions[0];
+        assert_eq!(buy.symbol, "AAPL");
+        assert_eq!(buy.name, "Apple");
+        assert_eq!(buy.action, TlgAction::BuyToOpen);
+        assert_eq!(buy.fees, 0.0);
+
+        let short_put = &result.executions[1];
+        assert_eq!(short_put.symbol, "SPY   240119P00470000");
+        assert_eq!(short_put.action, TlgAction::SellToOpen);
+        assert_eq!(short_put.quantity, -1.0);
+        assert_eq!(short_put.abs_fees(), 0.04);
+
+        let sell = &result.executions[2];
+        assert_eq!(sell.action, TlgAction::SellToClose);
+        assert_eq!(sell.abs_fees(), 0.05);
+
+        let expiration = &result.executions[3];
+        assert_eq!(expiration.symbol, short_put.symbol);
+        assert_eq!(expiration.price, 0.0);
+        assert_eq!(expiration.abs_quantity(), 1.0);
+        assert_ne!(expiration.broker_execution_id, short_put.broker_execution_id);
+    }
+
+    #[test]
+    fn test_parse_robinhood_option_description() {
+        let details = parse_robinhood_option_description("tsla 3/15/2024 Call $1,000.00").unwrap();
+        assert_eq!(details.underlying, "TSLA");
+        assert_eq!(details.expiration_date, NaiveDate::from_ymd_opt(2024, 3, 15).unwrap());
+        assert_eq!(details.option_type, OptionType::Call);
+        assert_eq!(details.strike_price, 1000.0);
+
+        assert!(parse_robinhood_option_description("SPY 1/19/2024 Put").is_err());
+    }
+}
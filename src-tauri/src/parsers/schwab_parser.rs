@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use super::csv_export::{assign_content_ids, column, csv_records, has_columns, parse_amount, resolve_transactions, BrokerAction, Transaction};
+use super::{BrokerParser, OpeningPosition, OptionDetails, OptionType, TlgAction, TlgAssetType, TlgExecution, TlgParseError, TlgParseResult};
+
+/// Export formats read by parse_schwab_transactions
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Schwab,
+    TdAmeritrade,
+}
+
+/// Fields of a trade row shared by both exports
+struct TradeRow<'a> {
+    symbol: String,
+    option_details: Option<OptionDetails>,
+    description: &'a str,
+    execution_date: NaiveDate,
+    buy: bool,
+    quantity: f64, // Always positive
+    price: f64,
+    fees: f64, // Positive
+}
+
+/// Parse a Charles Schwab transactions CSV, or a legacy TD Ameritrade transactions.csv, into
+/// executions shaped like TLG ones
+/// Stock trades carry no open/close flag and are matched against the running position of their
+/// symbol. Expired, assigned and exercised options close the open position of their contract at a
+/// price of zero. Neither export has execution times or IDs, so executions keep the export's order
+/// within a day and get a stable ID from their contents.
+pub fn parse_schwab_transactions(content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+    let mut transactions = Vec::new();
+    let mut errors = Vec::new();
+    let mut columns: Option<(Format, HashMap<String, usize>)> = None;
+
+    for record in csv_records(content) {
+        if record.is_blank() {
+            continue;
+        }
+        // Schwab exports may start with a title line before the header
+        let Some((format, ref header)) = columns else {
+            let header = record.header_columns();
+            if header.contains_key("Action") {
+                columns = Some((Format::Schwab, header));
+            } else if header.contains_key("TRANSACTION ID") {
+                columns = Some((Format::TdAmeritrade, header));
+            }
+            continue;
+        };
+
+        // Totals and end-of-file markers have no date
+        let date_column = if format == Format::Schwab { "Date" } else { "DATE" };
+        if !column(&record.fields, header, date_column).starts_with(|c: char| c.is_ascii_digit()) {
+            continue;
+        }
+
+        let parsed = match format {
+            Format::Schwab => parse_schwab_row(&record.fields, header),
+            Format::TdAmeritrade => parse_tda_row(&record.fields, header),
+        };
+        match parsed {
+            Ok(Some(transaction)) => transactions.push((transaction, record)),
+            Ok(None) => {}
+            Err(e) => errors.push(TlgParseError {
+                line_number: record.line_number,
+                line_content: record.raw,
+                error: e,
+            }),
+        }
+    }
+
+    // Both exports list the newest transactions first
+    transactions.reverse();
+    let mut executions = resolve_transactions(transactions, opening, &mut errors);
+    assign_content_ids("schwab", &mut executions);
+    TlgParseResult { executions, errors }
+}
+
+/// Schwab transaction exports, including legacy TD Ameritrade ones
+pub struct SchwabParser;
+
+impl BrokerParser for SchwabParser {
+    fn id(&self) -> &'static str {
+        "schwab"
+    }
+
+    fn name(&self) -> &'static str {
+        "Schwab or TD Ameritrade transactions"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        has_columns(content, &["Date", "Action", "Fees & Comm"])
+            || has_columns(content, &["DATE", "TRANSACTION ID", "DESCRIPTION"])
+    }
+
+    fn parse(&self, content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+        parse_schwab_transactions(content, opening)
+    }
+}
+
+/// Parse one Schwab transaction, or None when it is not a trade
+/// Columns: Date,Action,Symbol,Description,Quantity,Price,Fees & Comm,Amount
+fn parse_schwab_row(fields: &[String], header: &HashMap<String, usize>) -> Result<Option<Transaction>, String> {
+    let field = |name: &str| column(fields, header, name);
+
+    let broker_action = match field("Action").to_lowercase().as_str() {
+        "buy" | "buy to cover" | "reinvest shares" => BrokerAction::Sided { buy: true, short: false },
+        "sell" => BrokerAction::Sided { buy: false, short: false },
+        "sell short" => BrokerAction::Sided { buy: false, short: true },
+        "buy to open" => BrokerAction::Explicit(TlgAction::BuyToOpen),
+        "sell to close" => BrokerAction::Explicit(TlgAction::SellToClose),
+        "sell to open" => BrokerAction::Explicit(TlgAction::SellToOpen),
+        "buy to close" => BrokerAction::Explicit(TlgAction::BuyToClose),
+        "expired" | "assigned" | "exchange or exercise" => BrokerAction::Removal,
+        _ => return Ok(None),
+    };
+
+    // Corrected transactions show both dates, e.g. "01/16/2024 as of 01/12/2024"
+    let date = field("Date");
+    let trade_date = date.rsplit(" as of ").next().unwrap_or(date);
+    let execution_date = NaiveDate::parse_from_str(trade_date, "%m/%d/%Y")
+        .map_err(|_| format!("Invalid date: {}", date))?;
+
+    let symbol = field("Symbol").to_uppercase();
+    if symbol.is_empty() {
+        return Err("Missing symbol".to_string());
+    }
+    let option_details = parse_schwab_option_symbol(&symbol)?;
+
+    let quantity = parse_amount(field("Quantity"))
+        .ok_or_else(|| format!("Invalid quantity: {}", field("Quantity")))?
+        .abs();
+    let price = match field("Price") {
+        "" if matches!(broker_action, BrokerAction::Removal) => 0.0,
+        price => parse_amount(price).ok_or_else(|| format!("Invalid price: {}", price))?,
+    };
+    let fees = parse_amount(field("Fees & Comm")).unwrap_or(0.0).abs();
+
+    let (buy, action) = broker_action.side();
+    let execution = TradeRow {
+        symbol,
+        option_details,
+        description: field("Description"),
+        execution_date,
+        buy,
+        quantity,
+        price,
+        fees,
+    }
+    .into_execution(action);
+    Ok(Some(broker_action.into_transaction(execution)))
+}
+
+/// Parse one TD Ameritrade transaction, or None when it is not a trade
+/// Columns: DATE,TRANSACTION ID,DESCRIPTION,QUANTITY,SYMBOL,PRICE,COMMISSION,AMOUNT,REG FEE,...
+/// TD Ameritrade flags neither stock nor option trades as opening or closing.
+fn parse_tda_row(fields: &[String], header: &HashMap<String, usize>) -> Result<Option<Transaction>, String> {
+    let field = |name: &str| column(fields, header, name);
+
+    let description = field("DESCRIPTION");
+    let upper = description.to_uppercase();
+    let broker_action = if upper.starts_with("BOUGHT") {
+        BrokerAction::Sided { buy: true, short: false }
+    } else if upper.starts_with("SOLD SHORT") || upper.starts_with("SHORT SALE") {
+        BrokerAction::Sided { buy: false, short: true }
+    } else if upper.starts_with("SOLD") {
+        BrokerAction::Sided { buy: false, short: false }
+    } else if upper.starts_with("REMOVAL OF OPTION") {
+        BrokerAction::Removal
+    } else {
+        return Ok(None);
+    };
+
+    let execution_date = NaiveDate::parse_from_str(field("DATE"), "%m/%d/%Y")
+        .map_err(|_| format!("Invalid date: {}", field("DATE")))?;
+
+    let symbol = field("SYMBOL").to_uppercase();
+    if symbol.is_empty() {
+        return Err("Missing symbol".to_string());
+    }
+    let option_details = parse_tda_option_symbol(&symbol)?;
+
+    let quantity = parse_amount(field("QUANTITY"))
+        .ok_or_else(|| format!("Invalid quantity: {}", field("QUANTITY")))?
+        .abs();
+    let price = match field("PRICE") {
+        "" if matches!(broker_action, BrokerAction::Removal) => 0.0,
+        price => parse_amount(price).ok_or_else(|| format!("Invalid price: {}", price))?,
+    };
+    let fees = parse_amount(field("COMMISSION")).unwrap_or(0.0).abs() + parse_amount(field("REG FEE")).unwrap_or(0.0).abs();
+
+    let (buy, action) = broker_action.side();
+    let execution = TradeRow {
+        symbol,
+        option_details,
+        description,
+        execution_date,
+        buy,
+        quantity,
+        price,
+        fees,
+    }
+    .into_execution(action);
+    Ok(Some(broker_action.into_transaction(execution)))
+}
+
+impl TradeRow<'_> {
+    /// Execution of the row's stock or option trade
+    fn into_execution(self, action: TlgAction) -> TlgExecution {
+        let (symbol, asset_type, multiplier) = match self.option_details {
+            Some(ref details) => (details.occ_symbol(), TlgAssetType::Option, 100.0),
+            None => (self.symbol, TlgAssetType::Stock, 1.0),
+        };
+        let quantity = if self.buy { self.quantity } else { -self.quantity };
+
+        TlgExecution {
+            broker_execution_id: String::new(), // Assigned once all transactions are parsed
+            symbol,
+            name: self.description.to_string(),
+            exchange: "SCHWAB".to_string(),
+            action,
+            execution_date: self.execution_date,
+            execution_time: String::new(),
+            currency: "USD".to_string(),
+            quantity,
+            multiplier,
+            price: self.price,
+            total: quantity * self.price * multiplier,
+            fees: -self.fees, // Negative like TLG fees
+            fx_rate: None,
+            asset_type,
+            option_details: self.option_details,
+            future_details: None,
+        }
+    }
+}
+
+/// Contract of a Schwab option symbol such as "SPY 01/19/2024 470.00 P", or None for a stock
+fn parse_schwab_option_symbol(symbol: &str) -> Result<Option<OptionDetails>, String> {
+    let parts: Vec<&str> = symbol.split_whitespace().collect();
+    let [underlying, expiration, strike, option_type] = parts[..] else {
+        return Ok(None);
+    };
+
+    let expiration_date = NaiveDate::parse_from_str(expiration, "%m/%d/%Y")
+        .map_err(|_| format!("Invalid expiration in option symbol: {}", symbol))?;
+    let strike_price = parse_amount(strike).ok_or_else(|| format!("Invalid strike price: {}", strike))?;
+    let option_type = match option_type {
+        "C" => OptionType::Call,
+        "P" => OptionType::Put,
+        _ => return Err(format!("Invalid option type in option symbol: {}", symbol)),
+    };
+
+    Ok(Some(OptionDetails {
+        underlying: underlying.to_string(),
+        expiration_date,
+        option_type,
+        strike_price,
+    }))
+}
+
+/// Contract of a TD Ameritrade option symbol such as "SPY JAN 19 2024 470.0 PUT", or None for a stock
+fn parse_tda_option_symbol(symbol: &str) -> Result<Option<OptionDetails>, String> {
+    let parts: Vec<&str> = symbol.split_whitespace().collect();
+    let [underlying, month, day, year, strike, option_type] = parts[..] else {
+        return Ok(None);
+    };
+
+    let expiration = format!("{} {} {}", month, day, year);
+    let expiration_date = NaiveDate::parse_from_str(&expiration, "%b %d %Y")
+        .map_err(|_| format!("Invalid expiration in option symbol: {}", symbol))?;
+    let strike_price = parse_amount(strike).ok_or_else(|| format!("Invalid strike price: {}", strike))?;
+    let option_type = match option_type {
+        "CALL" => OptionType::Call,
+        "PUT" => OptionType::Put,
+        _ => return Err(format!("Invalid option type in option symbol: {}", symbol)),
+    };
+
+    Ok(Some(OptionDetails {
+        underlying: underlying.to_string(),
+        expiration_date,
+        option_type,
+        strike_price,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHWAB: &str = r#""Transactions  for account XXXX-1234 as of 01/20/2024 10:00:00 ET"
+"Date","Action","Symbol","Description","Quantity","Price","Fees & Comm","Amount"
+"01/19/2024","Expired","SPY 01/19/2024 460.00 P","PUT SPDR S&P 500 $460 EXP 01/19/24","1","","",""
+"01/17/2024","Buy to Close","SPY 01/19/2024 470.00 P","PUT SPDR S&P 500 $470 EXP 01/19/24","1","$0.40","$0.66","-$40.66"
+"01/16/2024 as of 01/15/2024","Sell","AAPL","APPLE INC","150","$190.00","$0.05","$28,499.95"
+"01/16/2024","Qualified Dividend","AAPL","APPLE INC","","","","$24.00"
+"01/15/2024","Sell to Open","SPY 01/19/2024 470.00 P","PUT SPDR S&P 500 $470 EXP 01/19/24","1","$1.50","$0.66","$149.34"
+"01/15/2024","Buy to Open","SPY 01/19/2024 460.00 P","PUT SPDR S&P 500 $460 EXP 01/19/24","1","$0.55","$0.66","-$55.66"
+"01/12/2024","Buy","AAPL","APPLE INC","100","$185.50","","-$18,550.00"
+"01/12/2024","Expired","QQQ 01/12/2024 400.00 C","CALL INVESCO QQQ $400 EXP 01/12/24","1","","",""
+"Transactions Total","","","","","","","$10,027.03"
+"#;
+
+    #[test]
+    fn test_parse_schwab_transactions() {
+        let result = parse_schwab_transactions(SCHWAB, &[]);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line_number, 10);
+        assert_eq!(result.errors[0].error, "No open position to close for QQQ   240112C00400000");
+        assert_eq!(result.executions.len(), 7);
+
+        // The sell of 150 closes the long 100 and opens a short 50 on the as-of date
+        let aapl: Vec<&TlgExecution> = result.executions.iter().filter(|e| e.symbol == "AAPL").collect();
+        assert_eq!(aapl.len(), 3);
+        assert_eq!(aapl[1].action, TlgAction::SellToClose);
+        assert_eq!(aapl[1].quantity, -100.0);
+        assert_eq!(aapl[1].execution_date, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+        assert_eq!(aapl[2].action, TlgAction::SellToOpen);
+
+        let short_put = result.executions.iter().find(|e| e.action == TlgAction::SellToOpen && e.asset_type == TlgAssetType::Option).unwrap();
+        assert_eq!(short_put.symbol, "SPY   240119P00470000");
+        assert_eq!(short_put.price, 1.5);
+        assert_eq!(short_put.fees, -0.66);
+
+        // The long put expires worthless, closing with a sell
+        let expired = result.executions.last().unwrap();
+        assert_eq!(expired.symbol, "SPY   240119P00460000");
+        assert_eq!(expired.action, TlgAction::SellToClose);
+        assert_eq!(expired.quantity, -1.0);
+        assert_eq!(expired.price, 0.0);
+    }
+
+    #[test]
+    fn test_parse_tda_transactions() {
+        let content = r#"DATE,TRANSACTION ID,DESCRIPTION,QUANTITY,SYMBOL,PRICE,COMMISSION,AMOUNT,REG FEE,SHORT-TERM RDM FEE,FUND REDEMPTION FEE, DEFERRED SALES CHARGE
+01/19/2024,1004,REMOVAL OF OPTION DUE TO EXPIRATION,1,SPY Jan 19 2024 470.0 Put,,,,,,,
+01/16/2024,1003,Bought 100 MSFT @ 395,100,MSFT,395,0.00,-39500.00,,,,
+01/16/2024,1002,Sold Short 100 MSFT @ 400,100,MSFT,400,0.00,39999.95,0.05,,,
+01/15/2024,1001,Sold 1 SPY Jan 19 2024 470.0 Put @ 1.5,1,SPY Jan 19 2024 470.0 Put,1.5,0.65,149.33,0.02,,,
+01/15/2024,1000,ACH DEPOSIT,,,,,1000.00,,,,
+***END OF FILE***
+"#;
+        let result = parse_schwab_transactions(content, &[]);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.executions.len(), 4);
+
+        let put_open = &result.executions[0];
+        assert_eq!(put_open.symbol, "SPY   240119P00470000");
+        assert_eq!(put_open.action, TlgAction::SellToOpen);
+        assert!((put_open.fees + 0.67).abs() < 1e-9);
+
+        assert_eq!(result.executions[1].action, TlgAction::SellToOpen);
+        assert_eq!(result.executions[2].action, TlgAction::BuyToClose);
+
+        // The short put expires, closing with a buy
+        let expired = &result.executions[3];
+        assert_eq!(expired.action, TlgAction::BuyToClose);
+        assert_eq!(expired.quantity, 1.0);
+    }
+}
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use super::csv_export::{assign_content_ids, column, csv_records, has_columns, parse_amount};
+use super::{BrokerParser, OpeningPosition, OptionDetails, OptionType, TlgAction, TlgAssetType, TlgExecution, TlgParseError, TlgParseResult, parse_option_symbol};
+
+/// Sub types of Receive Deliver rows that open or close a position
+/// Splits and symbol changes are left out: they restate a position rather than trade it.
+const POSITION_SUB_TYPES: [&str; 9] = [
+    "expiration",
+    "assignment",
+    "exercise",
+    "cash settled assignment",
+    "cash settled exercise",
+    "buy to open",
+    "sell to open",
+    "buy to close",
+    "sell to close",
+];
+
+/// Parse a tastytrade transaction history CSV into executions shaped like TLG ones
+/// Stock and equity option trades are read, plus the expirations, assignments and exercises that
+/// close or open positions; money movements are skipped. Each leg of a multi-leg order is its own
+/// row, so spreads import as one execution per contract.
+/// Options get their contract from the expiration, strike and call/put columns, falling back to
+/// the OCC symbol. The history has no execution IDs, so executions get a stable ID from their contents.
+pub fn parse_tastytrade_history(content: &str) -> TlgParseResult {
+    let mut executions = Vec::new();
+    let mut errors = Vec::new();
+    let mut columns: Option<HashMap<String, usize>> = None;
+
+    for record in csv_records(content) {
+        if record.is_blank() {
+            continue;
+        }
+        let Some(ref header) = columns else {
+            columns = Some(record.header_columns());
+            continue;
+        };
+
+        match parse_transaction(&record.fields, header) {
+            Ok(Some(execution)) => executions.push(execution),
+            Ok(None) => {}
+            Err(e) => errors.push(TlgParseError {
+                line_number: record.line_number,
+                line_content: record.raw,
+                error: e,
+            }),
+        }
+    }
+
+    // The history lists the newest transactions first
+    executions.reverse();
+    executions.sort_by(|a, b| {
+        a.execution_date
+            .cmp(&b.execution_date)
+            .then_with(|| a.execution_time.cmp(&b.execution_time))
+    });
+
+    assign_content_ids("tastytrade", &mut executions);
+    TlgParseResult { executions, errors }
+}
+
+/// tastytrade transaction histories
+pub struct TastytradeParser;
+
+impl BrokerParser for TastytradeParser {
+    fn id(&self) -> &'static str {
+        "tastytrade"
+    }
+
+    fn name(&self) -> &'static str {
+        "tastytrade transaction history"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        has_columns(content, &["Type", "Sub Type", "Action", "Instrument Type"])
+    }
+
+    fn parse(&self, content: &str, _opening: &[OpeningPosition]) -> TlgParseResult {
+        parse_tastytrade_history(content)
+    }
+}
+
+/// Parse one transaction, or None when it does not trade a position
+/// Columns: Date,Type,Sub Type,Action,Symbol,Instrument Type,Description,Value,Quantity,Average Price,
+/// Commissions,Fees,Multiplier,Root Symbol,Underlying Symbol,Expiration Date,Strike Price,Call or Put,Order #,Currency
+fn parse_transaction(fields: &[String], header: &HashMap<String, usize>) -> Result<Option<TlgExecution>, String> {
+    let field = |name: &str| column(fields, header, name);
+
+    match field("Type").to_lowercase().as_str() {
+        "trade" => {}
+        "receive deliver" if POSITION_SUB_TYPES.contains(&field("Sub Type").to_lowercase().as_str()) => {}
+        _ => return Ok(None),
+    }
+
+    let action = match field("Action").to_uppercase().as_str() {
+        "BUY_TO_OPEN" => TlgAction::BuyToOpen,
+        "SELL_TO_CLOSE" => TlgAction::SellToClose,
+        "SELL_TO_OPEN" => TlgAction::SellToOpen,
+        "BUY_TO_CLOSE" => TlgAction::BuyToClose,
+        _ => return Err(format!("Unknown action: {}", field("Action"))),
+    };
+
+    let (asset_type, default_multiplier) = match field("Instrument Type") {
+        "Equity" => (TlgAssetType::Stock, 1.0),
+        "Equity Option" => (TlgAssetType::Option, 100.0),
+        other => return Err(format!("Unsupported instrument type: {}", other)),
+    };
+    let multiplier = match field("Multiplier") {
+        "" => default_multiplier,
+        multiplier => parse_amount(multiplier).ok_or_else(|| format!("Invalid multiplier: {}", multiplier))?,
+    };
+
+    let executed_at = parse_transaction_time(field("Date"))?;
+    let quantity = parse_amount(field("Quantity"))
+        .ok_or_else(|| format!("Invalid quantity: {}", field("Quantity")))?
+        .abs();
+    if quantity == 0.0 {
+        return Err("Missing quantity".to_string());
+    }
+
+    // Average Price is per contract, so it includes the multiplier; expirations have none
+    let price = match field("Average Price") {
+        "" => parse_amount(field("Value")).unwrap_or(0.0).abs() / (quantity * multiplier),
+        average => parse_amount(average).ok_or_else(|| format!("Invalid average price: {}", average))?.abs() / multiplier,
+    };
+    let fees = parse_amount(field("Commissions")).unwrap_or(0.0) + parse_amount(field("Fees")).unwrap_or(0.0);
+
+    let option_details = match asset_type {
+        TlgAssetType::Option => Some(option_from_columns(fields, header)?),
+        _ => None,
+    };
+    let symbol = match option_details {
+        Some(ref details) => details.occ_symbol(),
+        None => match field("Symbol").to_uppercase() {
+            symbol if symbol.is_empty() => return Err("Missing symbol".to_string()),
+            symbol => symbol,
+        },
+    };
+
+    let buy = matches!(action, TlgAction::BuyToOpen | TlgAction::BuyToClose);
+    let quantity = if buy { quantity } else { -quantity };
+    let currency = match field("Currency") {
+        "" => "USD".to_string(),
+        currency => currency.to_uppercase(),
+    };
+
+    Ok(Some(TlgExecution {
+        broker_execution_id: String::new(), // Assigned once all transactions are parsed
+        symbol,
+        name: field("Description").to_string(),
+        exchange: "TASTYTRADE".to_string(),
+        action,
+        execution_date: executed_at.date(),
+        execution_time: executed_at.format("%H:%M:%S").to_string(),
+        currency,
+        quantity,
+        multiplier,
+        price,
+        total: quantity * price * multiplier,
+        fees: -fees.abs(), // Negative like TLG fees
+        fx_rate: None,
+        asset_type,
+        option_details,
+        future_details: None,
+    }))
+}
+
+/// Option contract from the expiration, strike and call/put columns, or from the OCC symbol
+/// when those are empty
+fn option_from_columns(fields: &[String], header: &HashMap<String, usize>) -> Result<OptionDetails, String> {
+    let field = |name: &str| column(fields, header, name);
+    if field("Expiration Date").is_empty() || field("Strike Price").is_empty() {
+        return parse_option_symbol(field("Symbol"));
+    }
+
+    let underlying = match field("Underlying Symbol") {
+        "" => field("Root Symbol"),
+        underlying => underlying,
+    };
+    if underlying.is_empty() {
+        return Err("Missing underlying symbol".to_string());
+    }
+    let expiration_date = NaiveDate::parse_from_str(field("Expiration Date"), "%m/%d/%y")
+        .or_else(|_| NaiveDate::parse_from_str(field("Expiration Date"), "%m/%d/%Y"))
+        .map_err(|_| format!("Invalid expiration date: {}", field("Expiration Date")))?;
+    let strike_price = parse_amount(field("Strike Price"))
+        .ok_or_else(|| format!("Invalid strike price: {}", field("Strike Price")))?;
+    let option_type = match field("Call or Put").to_uppercase().as_str() {
+        "CALL" => OptionType::Call,
+        "PUT" => OptionType::Put,
+        _ => return Err(format!("Invalid call or put: {}", field("Call or Put"))),
+    };
+
+    Ok(OptionDetails {
+        underlying: underlying.to_uppercase(),
+        expiration_date,
+        option_type,
+        strike_price,
+    })
+}
+
+/// Transaction time as 2024-01-15T10:31:02-0500, kept in the time zone it was exported in
+fn parse_transaction_time(s: &str) -> Result<NaiveDateTime, String> {
+    DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%z")
+        .map(|dt| dt.naive_local())
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S"))
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S"))
+        .map_err(|_| format!("Invalid date: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HISTORY: &str = r#"Date,Type,Sub Type,Action,Symbol,Instrument Type,Description,Value,Quantity,Average Price,Commissions,Fees,Multiplier,Root Symbol,Underlying Symbol,Expiration Date,Strike Price,Call or Put,Order #,Currency
+2024-01-19T16:00:00-0500,Receive Deliver,Expiration,SELL_TO_CLOSE,SPY   240119P00460000,Equity Option,Removal of 1.0 SPY 01/19/24 Put 460.00 due to expiration.,0.00,1,0.00,--,0.00,100,SPY,SPY,1/19/24,460,PUT,,USD
+2024-01-17T11:00:00-0500,Trade,Buy to Close,BUY_TO_CLOSE,SPY   240119P00470000,Equity Option,Bought 1 SPY 01/19/24 Put 470.00 @ 0.40,-40.00,1,-40.00,0.00,-0.13,100,SPY,SPY,1/19/24,470,PUT,1002,USD
+2024-01-16T09:45:00-0500,Money Movement,Deposit,,,,ACH DEPOSIT,"1,000.00",0,,0.00,0.00,,,,,,,,USD
+2024-01-15T10:31:02-0500,Trade,Sell to Open,SELL_TO_OPEN,SPY   240119P00470000,Equity Option,Sold 1 SPY 01/19/24 Put 470.00 @ 1.50,150.00,1,150.00,-1.00,-0.14,100,SPY,SPY,1/19/24,470,PUT,1001,USD
+2024-01-15T10:31:02-0500,Trade,Buy to Open,BUY_TO_OPEN,SPY   240119P00460000,Equity Option,Bought 1 SPY 01/19/24 Put 460.00 @ 0.55,-55.00,1,-55.00,-1.00,-0.13,100,SPY,SPY,1/19/24,460,PUT,1001,USD
+2024-01-15T10:00:00-0500,Trade,Buy to Open,BUY_TO_OPEN,AAPL,Equity,Bought 100 AAPL @ 185.50,"-18,550.00",100,-185.50,0.00,-0.01,1,,AAPL,,,,1000,USD
+2024-01-15T10:05:00-0500,Trade,Buy to Open,BUY_TO_OPEN,/ESH4,Future,Bought 1 /ESH4 @ 4800.00,0.00,1,0.00,-1.25,-1.15,50,/ES,/ES,,,,1003,USD
+"#;
+
+    #[test]
+    fn test_parse_tastytrade_history() {
+        let result = parse_tastytrade_history(HISTORY);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line_number, 8);
+        assert_eq!(result.errors[0].error, "Unsupported instrument type: Future");
+        assert_eq!(result.executions.len(), 5);
+
+        let stock = &result.executions[0];
+        assert_eq!(stock.symbol, "AAPL");
+        assert_eq!(stock.price, 185.5);
+        assert_eq!(stock.execution_time, "10:00:00");
+
+        // Both legs of the put spread, each with its own contract
+        let short_leg = result.executions.iter().find(|e| e.action == TlgAction::SellToOpen).unwrap();
+        assert_eq!(short_leg.symbol, "SPY   240119P00470000");
+        assert_eq!(short_leg.quantity, -1.0);
+        assert_eq!(short_leg.price, 1.5);
+        assert!((short_leg.fees + 1.14).abs() < 1e-9);
+        let details = short_leg.option_details.as_ref().unwrap();
+        assert_eq!(details.strike_price, 470.0);
+        assert_eq!(details.expiration_date, NaiveDate::from_ymd_opt(2024, 1, 19).unwrap());
+        assert_eq!(details.option_type, OptionType::Put);
+
+        let expiration = result.executions.last().unwrap();
+        assert_eq!(expiration.symbol, "SPY   240119P00460000");
+        assert_eq!(expiration.action, TlgAction::SellToClose);
+        assert_eq!(expiration.price, 0.0);
+
+        let again = parse_tastytrade_history(HISTORY);
+        assert_eq!(again.executions[1].broker_execution_id, result.executions[1].broker_execution_id);
+    }
+}
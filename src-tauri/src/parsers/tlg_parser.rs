@@ -1,5 +1,6 @@
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use super::{contract_multiplier, product_code, BrokerParser, OpeningPosition};
 
 /// TLG trade action types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -45,6 +46,9 @@ impl TlgAction {
 pub enum TlgAssetType {
     Stock,
     Option,
+    Future,
+    Forex,
+    Crypto,
 }
 
 /// Option contract details parsed from OCC symbol
@@ -64,6 +68,29 @@ pub enum OptionType {
     Put,
 }
 
+impl OptionDetails {
+    /// OCC symbol of the contract, e.g. "AAPL  250905C00240000"
+    pub fn occ_symbol(&self) -> String {
+        format!(
+            "{:<6}{}{}{:08}",
+            self.underlying,
+            self.expiration_date.format("%y%m%d"),
+            match self.option_type {
+                OptionType::Call => 'C',
+                OptionType::Put => 'P',
+            },
+            (self.strike_price * 1000.0).round() as i64
+        )
+    }
+}
+
+/// Futures contract details parsed from a FUT_TRD line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FutureDetails {
+    pub underlying: String, // Product code, e.g. "ES" for ESH6
+    pub expiration_date: Option<NaiveDate>, // None when the description does not name the expiry day
+}
+
 /// A parsed execution from a TLG file line
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TlgExecution {
@@ -80,9 +107,11 @@ pub struct TlgExecution {
     pub price: f64,
     pub total: f64,
     pub fees: f64, // Stored as negative in TLG
-    pub fx_rate: Option<f64>,
+    pub fx_rate: Option<f64>, // Units of the account's base currency per unit of `currency`
     pub asset_type: TlgAssetType,
     pub option_details: Option<OptionDetails>,
+    #[serde(default)]
+    pub future_details: Option<FutureDetails>,
 }
 
 impl TlgExecution {
@@ -96,11 +125,21 @@ impl TlgExecution {
         self.fees.abs()
     }
 
-    /// Returns the underlying symbol (for stocks: symbol, for options: underlying)
+    /// Returns the underlying symbol (for stocks: symbol, for options and TLG futures: underlying)
     pub fn underlying_symbol(&self) -> &str {
-        match &self.option_details {
-            Some(details) => &details.underlying,
-            None => &self.symbol,
+        match (&self.option_details, &self.future_details) {
+            (Some(details), _) => &details.underlying,
+            (None, Some(details)) => &details.underlying,
+            (None, None) => &self.symbol,
+        }
+    }
+
+    /// Returns the contract expiry of an option or future
+    pub fn expiration_date(&self) -> Option<NaiveDate> {
+        match (&self.option_details, &self.future_details) {
+            (Some(details), _) => Some(details.expiration_date),
+            (None, Some(details)) => details.expiration_date,
+            (None, None) => None,
         }
     }
 }
@@ -152,6 +191,24 @@ pub fn parse_tlg_file(content: &str) -> TlgParseResult {
                     error: e,
                 }),
             }
+        } else if line.starts_with("FUT_TRD|") {
+            match parse_future_transaction(line) {
+                Ok(execution) => executions.push(execution),
+                Err(e) => errors.push(TlgParseError {
+                    line_number,
+                    line_content: line.to_string(),
+                    error: e,
+                }),
+            }
+        } else if line.starts_with("CASH_TRD|") {
+            match parse_cash_transaction(line) {
+                Ok(execution) => executions.push(execution),
+                Err(e) => errors.push(TlgParseError {
+                    line_number,
+                    line_content: line.to_string(),
+                    error: e,
+                }),
+            }
         }
         // Other lines (headers, account info, etc.) are ignored
     }
@@ -159,92 +216,127 @@ pub fn parse_tlg_file(content: &str) -> TlgParseResult {
     TlgParseResult { executions, errors }
 }
 
-/// Parse a stock transaction line
-/// Format: STK_TRD|trade_id|symbol|name|exchange|action|flags|date|time|currency|quantity|multiplier|price|total|fees|fx_rate
-fn parse_stock_transaction(line: &str) -> Result<TlgExecution, String> {
-    let fields: Vec<&str> = line.split('|').collect();
-
-    if fields.len() < 16 {
-        return Err(format!(
-            "Invalid stock transaction: expected 16 fields, got {}",
-            fields.len()
-        ));
+/// Transaction line prefixes of a TLG file
+const TRANSACTION_PREFIXES: [&str; 4] = ["STK_TRD|", "OPT_TRD|", "FUT_TRD|", "CASH_TRD|"];
+/// Lines that mark a TLG file even when it has no transactions, such as a day without trades
+const SECTION_HEADERS: [&str; 5] = [
+    "ACCOUNT_INFORMATION",
+    "STOCK_TRANSACTIONS",
+    "OPTION_TRANSACTIONS",
+    "FUTURE_TRANSACTIONS",
+    "CASH_TRANSACTIONS",
+];
+
+/// TLG files, recognized by their STK_TRD, OPT_TRD, FUT_TRD and CASH_TRD lines or section headers
+pub struct TlgParser;
+
+impl BrokerParser for TlgParser {
+    fn id(&self) -> &'static str {
+        "tlg"
     }
 
-    let broker_execution_id = fields[1].to_string();
-    let symbol = fields[2].to_string();
-    let name = fields[3].to_string();
-    let exchange = fields[4].to_string();
-
-    let action = TlgAction::from_str(fields[5])
-        .ok_or_else(|| format!("Unknown action: {}", fields[5]))?;
+    fn name(&self) -> &'static str {
+        "Interactive Brokers TLG"
+    }
 
-    // fields[6] is flags - we don't need them for now
+    fn detect(&self, content: &str) -> bool {
+        content.lines().any(|line| {
+            let line = line.trim();
+            TRANSACTION_PREFIXES.iter().any(|prefix| line.starts_with(prefix)) || SECTION_HEADERS.contains(&line)
+        })
+    }
 
-    let execution_date = parse_date(fields[7])?;
-    let execution_time = fields[8].to_string();
-    let currency = fields[9].to_string();
+    fn parse(&self, content: &str, _opening: &[OpeningPosition]) -> TlgParseResult {
+        parse_tlg_file(content)
+    }
+}
 
-    let quantity = fields[10]
-        .parse::<f64>()
-        .map_err(|_| format!("Invalid quantity: {}", fields[10]))?;
+/// Format parse errors as a file that can be corrected and re-imported
+/// Each failed line is preceded by a comment with its original line number and
+/// the error; comment lines are ignored when the file is parsed again.
+pub fn format_parse_errors(errors: &[TlgParseError]) -> String {
+    let mut output = String::new();
+    for error in errors {
+        output.push_str(&format!("# Line {}: {}\n", error.line_number, error.error));
+        output.push_str(&error.line_content);
+        output.push('\n');
+    }
+    output
+}
 
-    let multiplier = fields[11]
-        .parse::<f64>()
-        .map_err(|_| format!("Invalid multiplier: {}", fields[11]))?;
+/// Parse a stock transaction line
+/// Format: STK_TRD|trade_id|symbol|name|exchange|action|flags|date|time|currency|quantity|multiplier|price|total|fees|fx_rate
+fn parse_stock_transaction(line: &str) -> Result<TlgExecution, String> {
+    parse_transaction(line, "stock", TlgAssetType::Stock)
+}
 
-    let price = fields[12]
-        .parse::<f64>()
-        .map_err(|_| format!("Invalid price: {}", fields[12]))?;
+/// Parse an option transaction line
+/// Format: OPT_TRD|trade_id|contract_symbol|name|exchange|action|flags|date|time|currency|quantity|multiplier|price|total|fees|fx_rate
+fn parse_option_transaction(line: &str) -> Result<TlgExecution, String> {
+    let mut execution = parse_transaction(line, "option", TlgAssetType::Option)?;
 
-    let total = fields[13]
-        .parse::<f64>()
-        .map_err(|_| format!("Invalid total: {}", fields[13]))?;
+    // Parse option details from contract symbol
+    execution.option_details = Some(parse_option_symbol(&execution.symbol)?);
+    Ok(execution)
+}
 
-    let fees = fields[14]
-        .parse::<f64>()
-        .map_err(|_| format!("Invalid fees: {}", fields[14]))?;
+/// Parse a futures transaction line
+/// Format: FUT_TRD|trade_id|contract_symbol|name|exchange|action|flags|date|time|currency|quantity|multiplier|price|total|fees|fx_rate
+/// The expiry day comes from the name, e.g. "ES 20MAR26". A missing multiplier falls back to
+/// the product's known one, since PnL of a one-point move is multiplier dollars per contract.
+fn parse_future_transaction(line: &str) -> Result<TlgExecution, String> {
+    let mut execution = parse_transaction(line, "futures", TlgAssetType::Future)?;
+
+    let underlying = product_code(&execution.symbol).to_string();
+    if execution.multiplier <= 0.0 {
+        execution.multiplier = contract_multiplier(&underlying)
+            .ok_or_else(|| format!("Unknown contract multiplier for product: {}", underlying))?;
+        execution.total = execution.quantity * execution.price * execution.multiplier;
+    }
+    let expiration_date = execution
+        .name
+        .split_whitespace()
+        .find_map(|token| NaiveDate::parse_from_str(token, "%d%b%y").ok());
 
-    let fx_rate = if fields.len() > 15 && !fields[15].is_empty() {
-        fields[15].parse::<f64>().ok()
-    } else {
-        None
-    };
+    execution.future_details = Some(FutureDetails {
+        underlying,
+        expiration_date,
+    });
+    Ok(execution)
+}
 
-    Ok(TlgExecution {
-        broker_execution_id,
-        symbol,
-        name,
-        exchange,
-        action,
-        execution_date,
-        execution_time,
-        currency,
-        quantity,
-        multiplier,
-        price,
-        total,
-        fees,
-        fx_rate,
-        asset_type: TlgAssetType::Stock,
-        option_details: None,
-    })
+/// Parse a forex transaction line
+/// Format: CASH_TRD|trade_id|pair|name|exchange|action|flags|date|time|quote_currency|quantity|multiplier|price|total|fees|fx_rate
+/// The pair is written BASE.QUOTE, e.g. EUR.USD; quantities are in the base currency and prices
+/// in the quote currency, which fx_rate converts to the account's base currency.
+fn parse_cash_transaction(line: &str) -> Result<TlgExecution, String> {
+    let mut execution = parse_transaction(line, "forex", TlgAssetType::Forex)?;
+
+    let is_currency = |code: &str| code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic());
+    match execution.symbol.split_once('.') {
+        Some((base, quote)) if is_currency(base) && is_currency(quote) => {}
+        _ => return Err(format!("Invalid currency pair: {}", execution.symbol)),
+    }
+    if execution.multiplier <= 0.0 {
+        execution.multiplier = 1.0;
+    }
+    Ok(execution)
 }
 
-/// Parse an option transaction line
-/// Format: OPT_TRD|trade_id|contract_symbol|name|exchange|action|flags|date|time|currency|quantity|multiplier|price|total|fees|fx_rate
-fn parse_option_transaction(line: &str) -> Result<TlgExecution, String> {
+/// Parse the fields shared by all transaction lines
+fn parse_transaction(line: &str, kind: &str, asset_type: TlgAssetType) -> Result<TlgExecution, String> {
     let fields: Vec<&str> = line.split('|').collect();
 
     if fields.len() < 16 {
         return Err(format!(
-            "Invalid option transaction: expected 16 fields, got {}",
+            "Invalid {} transaction: expected 16 fields, got {}",
+            kind,
             fields.len()
         ));
     }
 
     let broker_execution_id = fields[1].to_string();
-    let contract_symbol = fields[2].to_string();
+    let symbol = fields[2].to_string();
     let name = fields[3].to_string();
     let exchange = fields[4].to_string();
 
@@ -283,12 +375,9 @@ fn parse_option_transaction(line: &str) -> Result<TlgExecution, String> {
         None
     };
 
-    // Parse option details from contract symbol
-    let option_details = parse_option_symbol(&contract_symbol)?;
-
     Ok(TlgExecution {
         broker_execution_id,
-        symbol: contract_symbol,
+        symbol,
         name,
         exchange,
         action,
@@ -301,8 +390,9 @@ fn parse_option_transaction(line: &str) -> Result<TlgExecution, String> {
         total,
         fees,
         fx_rate,
-        asset_type: TlgAssetType::Option,
-        option_details: Some(option_details),
+        asset_type,
+        option_details: None,
+        future_details: None,
     })
 }
 
@@ -518,6 +608,42 @@ mod tests {
         assert_eq!(details.strike_price, 240.0);
     }
 
+    #[test]
+    fn test_parse_future_transaction() {
+        let line = "FUT_TRD|5001|MNQH6|MNQ 20MAR26|CME|BUYTOOPEN|O|20260127|09:31:00|USD|3.00|2.00|21000.25|126001.50|-1.86|1.0";
+        let exec = parse_future_transaction(line).unwrap();
+
+        assert_eq!(exec.asset_type, TlgAssetType::Future);
+        assert_eq!(exec.multiplier, 2.0);
+        assert_eq!(exec.underlying_symbol(), "MNQ");
+        assert_eq!(exec.expiration_date(), NaiveDate::from_ymd_opt(2026, 3, 20));
+
+        // Without a multiplier or expiry day, the product's multiplier is used and the expiry is unknown
+        let line = "FUT_TRD|5002|ESM6|E-mini S&P 500 JUN26|CME|SELLTOCLOSE|C|20260128|10:00:00|USD|-1.00|0|6010.00|-6010.00|-2.25|1.0";
+        let exec = parse_future_transaction(line).unwrap();
+        assert_eq!(exec.multiplier, 50.0);
+        assert_eq!(exec.total, -300500.0);
+        assert_eq!(exec.expiration_date(), None);
+
+        let line = "FUT_TRD|5003|ZZH6|ZZ 20MAR26|CME|BUYTOOPEN|O|20260127|09:31:00|USD|1.00|0|10.00|10.00|-1.00|1.0";
+        assert_eq!(parse_future_transaction(line).unwrap_err(), "Unknown contract multiplier for product: ZZ");
+    }
+
+    #[test]
+    fn test_parse_cash_transaction() {
+        let line = "CASH_TRD|7001|USD.JPY|USD.JPY|IDEALFX|BUYTOOPEN|O|20260127|09:30:00|JPY|10000.00|1|155.20|-1552000.00|-2.00|0.00644";
+        let exec = parse_cash_transaction(line).unwrap();
+
+        assert_eq!(exec.asset_type, TlgAssetType::Forex);
+        assert_eq!(exec.symbol, "USD.JPY");
+        assert_eq!(exec.currency, "JPY");
+        assert_eq!(exec.multiplier, 1.0);
+        assert_eq!(exec.fx_rate, Some(0.00644));
+
+        let line = "CASH_TRD|7002|EURUSD|EUR.USD|IDEALFX|BUYTOOPEN|O|20260127|09:30:00|USD|10000.00|1|1.08|-10800.00|-2.00|1";
+        assert_eq!(parse_cash_transaction(line).unwrap_err(), "Invalid currency pair: EURUSD");
+    }
+
     #[test]
     fn test_tlg_action_is_opening() {
         assert!(TlgAction::BuyToOpen.is_opening());
@@ -534,6 +660,22 @@ mod tests {
         assert!(TlgAction::BuyToClose.is_closing());
     }
 
+    #[test]
+    fn test_format_parse_errors_round_trip() {
+        let content = "STK_TRD|1001|AAPL|APPLE INC|DARK|BUYTOOPEN|O|20260127|09:30:00|USD|abc|1.00|150.00|15000.00|-1.00|0.85";
+        let result = parse_tlg_file(content);
+        assert_eq!(result.errors.len(), 1);
+
+        let report = format_parse_errors(&result.errors);
+        assert!(report.starts_with("# Line 1: Invalid quantity: abc\n"));
+
+        // Re-parsing the report yields the same failed line and nothing else
+        let reparsed = parse_tlg_file(&report);
+        assert!(reparsed.executions.is_empty());
+        assert_eq!(reparsed.errors.len(), 1);
+        assert_eq!(reparsed.errors[0].line_content, result.errors[0].line_content);
+    }
+
     #[test]
     fn test_parse_tlg_file() {
         let content = r#"ACCOUNT_INFORMATION
@@ -545,18 +687,22 @@ STK_TRD|1055344297|AAPL|APPLE INC|IBKRATS|SELLTOCLOSE|C|20260127|09:49:27|USD|-7
 
 OPTION_TRANSACTIONS
 OPT_TRD|931660771|AAPL  250905C00240000|AAPL 05SEP25 240 C|MEMX,MIAX|BUYTOOPEN|O|20250904|09:49:58|USD|5.00|100.00|1.45|725.00|-3.96325|0.85835
+
+FUTURE_TRANSACTIONS
+FUT_TRD|3001|ESH6|ES 20MAR26|CME|BUYTOOPEN|O|20260127|09:30:00|USD|1.00|50.00|6000.00|300000.00|-2.25|1.0
 "#;
 
         let result = parse_tlg_file(content);
 
-        assert_eq!(result.executions.len(), 3);
+        assert_eq!(result.executions.len(), 4);
         assert!(result.errors.is_empty());
 
         // First execution is stock buy
         assert_eq!(result.executions[0].symbol, "AAPL");
         assert_eq!(result.executions[0].asset_type, TlgAssetType::Stock);
 
-        // Third execution is option
+        // Third execution is option, fourth is a future
         assert_eq!(result.executions[2].asset_type, TlgAssetType::Option);
+        assert_eq!(result.executions[3].asset_type, TlgAssetType::Future);
     }
 }
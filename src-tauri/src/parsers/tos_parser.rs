@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use chrono::{NaiveDate, NaiveDateTime};
+use super::csv_export::{assign_content_ids, column, csv_records, parse_amount};
+use super::{BrokerParser, OpeningPosition, OptionDetails, OptionType, TlgAction, TlgAssetType, TlgExecution, TlgParseError, TlgParseResult};
+
+/// Title line of the fills section in a thinkorswim Account Statement export
+const TRADE_HISTORY_SECTION: &str = "Account Trade History";
+
+/// Parse the Account Trade History section of a thinkorswim Account Statement CSV
+/// Fills become executions shaped like TLG ones so they aggregate the same way. The statement
+/// has no execution IDs, so each fill gets a stable ID from its contents for duplicate detection.
+/// Fees are reported elsewhere in the statement and are left at zero.
+pub fn parse_tos_statement(content: &str) -> TlgParseResult {
+    let mut executions = Vec::new();
+    let mut errors = Vec::new();
+    let mut columns: Option<HashMap<String, usize>> = None;
+    let mut in_section = false;
+    let mut last_exec_time: Option<String> = None;
+
+    for record in csv_records(content) {
+        if !in_section {
+            in_section = record.raw.trim().trim_matches(',') == TRADE_HISTORY_SECTION;
+            continue;
+        }
+        // The section ends at the first blank line after its rows
+        if record.is_blank() {
+            if columns.is_some() {
+                break;
+            }
+            continue;
+        }
+
+        let Some(ref header) = columns else {
+            columns = Some(record.header_columns());
+            continue;
+        };
+
+        // Later legs of a spread leave the execution time blank
+        let exec_time = match column(&record.fields, header, "Exec Time") {
+            "" => last_exec_time.clone().unwrap_or_default(),
+            time => time.to_string(),
+        };
+        last_exec_time = Some(exec_time.clone());
+
+        match parse_fill(&record.fields, header, &exec_time) {
+            Ok(execution) => executions.push(execution),
+            Err(e) => errors.push(TlgParseError {
+                line_number: record.line_number,
+                line_content: record.raw,
+                error: e,
+            }),
+        }
+    }
+
+    assign_content_ids("tos", &mut executions);
+    TlgParseResult { executions, errors }
+}
+
+/// thinkorswim Account Statements, recognized by their Account Trade History section
+pub struct TosParser;
+
+impl BrokerParser for TosParser {
+    fn id(&self) -> &'static str {
+        "tos"
+    }
+
+    fn name(&self) -> &'static str {
+        "thinkorswim Account Statement"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        content.lines().any(|line| line.trim().trim_matches(',') == TRADE_HISTORY_SECTION)
+    }
+
+    fn parse(&self, content: &str, _opening: &[OpeningPosition]) -> TlgParseResult {
+        parse_tos_statement(content)
+    }
+}
+
+/// Parse one fill of the trade history
+/// Columns: ,Exec Time,Spread,Side,Qty,Pos Effect,Symbol,Exp,Strike,Type,Price,Net Price,Order Type
+fn parse_fill(fields: &[String], header: &HashMap<String, usize>, exec_time: &str) -> Result<TlgExecution, String> {
+    let field = |name: &str| column(fields, header, name);
+    let executed_at = parse_exec_time(exec_time)?;
+
+    let side = field("Side").to_uppercase();
+    let opening = match field("Pos Effect").to_uppercase().as_str() {
+        "TO OPEN" => true,
+        "TO CLOSE" => false,
+        other => return Err(format!("Unknown position effect: {}", other)),
+    };
+    let action = match (side.as_str(), opening) {
+        ("BUY", true) => TlgAction::BuyToOpen,
+        ("SELL", false) => TlgAction::SellToClose,
+        ("SELL", true) => TlgAction::SellToOpen,
+        ("BUY", false) => TlgAction::BuyToClose,
+        _ => return Err(format!("Unknown side: {}", field("Side"))),
+    };
+
+    let quantity = parse_amount(field("Qty"))
+        .ok_or_else(|| format!("Invalid quantity: {}", field("Qty")))?
+        .abs();
+    let quantity = if side == "BUY" { quantity } else { -quantity };
+
+    let price = parse_amount(field("Price")).ok_or_else(|| format!("Invalid price: {}", field("Price")))?;
+
+    let symbol = field("Symbol");
+    if symbol.is_empty() {
+        return Err("Missing symbol".to_string());
+    }
+
+    let option_details = match field("Type").to_uppercase().as_str() {
+        "CALL" => Some(option_from_columns(symbol, field("Exp"), field("Strike"), OptionType::Call)?),
+        "PUT" => Some(option_from_columns(symbol, field("Exp"), field("Strike"), OptionType::Put)?),
+        _ if symbol.starts_with('.') => Some(parse_tos_option_symbol(symbol)?),
+        _ => None,
+    };
+    let (symbol, asset_type, multiplier) = match option_details {
+        Some(ref details) => (details.occ_symbol(), TlgAssetType::Option, 100.0),
+        None => (symbol.to_uppercase(), TlgAssetType::Stock, 1.0),
+    };
+
+    Ok(TlgExecution {
+        broker_execution_id: String::new(), // Assigned once all fills are parsed
+        name: symbol.clone(),
+        symbol,
+        exchange: "TOS".to_string(),
+        action,
+        execution_date: executed_at.date(),
+        execution_time: executed_at.format("%H:%M:%S").to_string(),
+        currency: "USD".to_string(),
+        quantity,
+        multiplier,
+        price,
+        total: quantity * price * multiplier,
+        fees: 0.0,
+        fx_rate: None,
+        asset_type,
+        option_details,
+        future_details: None,
+    })
+}
+
+/// Execution time as M/D/YY HH:MM:SS, or with a four-digit year
+fn parse_exec_time(s: &str) -> Result<NaiveDateTime, String> {
+    NaiveDateTime::parse_from_str(s, "%m/%d/%y %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%m/%d/%Y %H:%M:%S"))
+        .map_err(|_| format!("Invalid execution time: {}", s))
+}
+
+/// Option contract from the trade history's Exp ("19 JAN 24"), Strike and Type columns
+fn option_from_columns(underlying: &str, exp: &str, strike: &str, option_type: OptionType) -> Result<OptionDetails, String> {
+    // Weekly and quarterly expirations carry a suffix such as "19 JAN 24 [WEEKLY]"
+    let exp = exp.split('[').next().unwrap_or("").trim();
+    let expiration_date = NaiveDate::parse_from_str(exp, "%d %b %y")
+        .map_err(|_| format!("Invalid expiration: {}", exp))?;
+    let strike_price = strike
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid strike: {}", strike))?;
+
+    Ok(OptionDetails {
+        underlying: underlying.to_uppercase(),
+        expiration_date,
+        option_type,
+        strike_price,
+    })
+}
+
+/// Parse a thinkorswim option symbol to extract contract details
+/// Format: .SPY240119C475 or .AAPL240119P182.5
+///          │  │     ││
+///          │  │     │└── Strike price, with decimals when fractional
+///          │  │     └─── Option type: C=Call, P=Put
+///          │  └───────── Expiration: YYMMDD
+///          └──────────── Underlying symbol, unpadded
+pub fn parse_tos_option_symbol(symbol: &str) -> Result<OptionDetails, String> {
+    let body = symbol.trim().trim_start_matches('.');
+    let type_pos = body
+        .char_indices()
+        .skip_while(|(_, c)| !c.is_ascii_digit())
+        .find(|(_, c)| matches!(c, 'C' | 'P'))
+        .map(|(i, _)| i)
+        .filter(|i| *i > 6)
+        .ok_or_else(|| format!("Invalid option symbol: {}", symbol))?;
+
+    let (underlying, date) = body[..type_pos].split_at(type_pos - 6);
+    let expiration_date = NaiveDate::parse_from_str(date, "%y%m%d")
+        .map_err(|_| format!("Invalid expiration in option symbol: {}", symbol))?;
+    let option_type = if body.as_bytes()[type_pos] == b'C' { OptionType::Call } else { OptionType::Put };
+    let strike = &body[type_pos + 1..];
+    let strike_price = strike
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid strike price: {}", strike))?;
+
+    Ok(OptionDetails {
+        underlying: underlying.to_string(),
+        expiration_date,
+        option_type,
+        strike_price,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATEMENT: &str = r#"Account Statement for 123456789SCHW (ira) since 1/1/24 through 1/31/24
+
+Cash Balance
+DATE,TIME,TYPE,REF #,DESCRIPTION,Misc Fees,Commissions & Fees,AMOUNT,BALANCE
+1/15/24,09:31:02,TRD,="1234",BOT +100 AAPL @185.50,,,"-18,550.00","81,450.00"
+
+Account Trade History
+,Exec Time,Spread,Side,Qty,Pos Effect,Symbol,Exp,Strike,Type,Price,Net Price,Order Type
+,1/15/24 09:31:02,STOCK,BUY,+100,TO OPEN,AAPL,,,STOCK,185.50,185.50,LMT
+,1/15/24 10:02:11,VERTICAL,SELL,-2,TO OPEN,SPY,19 JAN 24 [WEEKLY],475,CALL,3.20,1.10,LMT
+,,,BUY,+2,TO OPEN,SPY,19 JAN 24 [WEEKLY],480,CALL,2.10,CREDIT,
+,1/16/24 14:45:00,STOCK,SELL,-100,TO CLOSE,AAPL,,,STOCK,"190.25",190.25,MKT
+,1/16/24 15:00:00,SINGLE,BUY,+1,TO CLOSE,.SPY240119P470,,,,0.85,0.85,LMT
+,1/16/24 15:01:00,STOCK,HOLD,+1,TO OPEN,MSFT,,,STOCK,400.00,400.00,LMT
+
+Profits and Losses
+Symbol,Description,P/L Open,P/L %,P/L Day,P/L YTD,P/L Diff,Margin Req,Mark Value
+"#;
+
+    #[test]
+    fn test_parse_tos_statement_trade_history() {
+        let result = parse_tos_statement(STATEMENT);
+        assert_eq!(result.executions.len(), 5);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].error, "Unknown side: HOLD");
+        assert_eq!(result.errors[0].line_number, 14);
+
+        let buy = &result.executions[0];
+        assert_eq!(buy.symbol, "AAPL");
+        assert_eq!(buy.action, TlgAction::BuyToOpen);
+        assert_eq!(buy.execution_date, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+        assert_eq!(buy.execution_time, "09:31:02");
+        assert_eq!(buy.quantity, 100.0);
+        assert_eq!(buy.asset_type, TlgAssetType::Stock);
+
+        // The spread's second leg inherits the execution time of the first
+        let short_leg = &result.executions[1];
+        let long_leg = &result.executions[2];
+        assert_eq!(short_leg.symbol, "SPY   240119C00475000");
+        assert_eq!(short_leg.action, TlgAction::SellToOpen);
+        assert_eq!(short_leg.quantity, -2.0);
+        assert_eq!(short_leg.multiplier, 100.0);
+        assert_eq!(long_leg.execution_time, "10:02:11");
+        assert_eq!(long_leg.option_details.as_ref().unwrap().strike_price, 480.0);
+
+        let sell = &result.executions[3];
+        assert_eq!(sell.action, TlgAction::SellToClose);
+        assert_eq!(sell.price, 190.25);
+
+        let put = result.executions[4].option_details.as_ref().unwrap();
+        assert_eq!(put.underlying, "SPY");
+        assert_eq!(put.option_type, OptionType::Put);
+        assert_eq!(put.strike_price, 470.0);
+        assert_eq!(put.expiration_date, NaiveDate::from_ymd_opt(2024, 1, 19).unwrap());
+        assert_eq!(result.executions[4].action, TlgAction::BuyToClose);
+
+        // IDs are stable across parses
+        let again = parse_tos_statement(STATEMENT);
+        assert_eq!(again.executions[1].broker_execution_id, short_leg.broker_execution_id);
+        assert_ne!(short_leg.broker_execution_id, long_leg.broker_execution_id);
+    }
+
+    #[test]
+    fn test_parse_tos_option_symbol_fractional_strike() {
+        let details = parse_tos_option_symbol(".AAPL240119P182.5").unwrap();
+        assert_eq!(details.underlying, "AAPL");
+        assert_eq!(details.option_type, OptionType::Put);
+        assert_eq!(details.strike_price, 182.5);
+        assert!(parse_tos_option_symbol(".AAPL").is_err());
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::NaiveDateTime;
+use super::csv_export::{column, csv_records, parse_amount, CsvRecord};
+use super::{contract_multiplier, RoundTripParseResult, RoundTripTrade, TlgAssetType, TlgParseError};
+
+/// One side of a trade in the List of Trades
+struct TradeLeg {
+    entry: bool,
+    short: bool,
+    open: bool, // Exit of a trade still open at the last bar
+    time: NaiveDateTime,
+    price: f64,
+    quantity: f64, // Always positive
+    profit: Option<f64>,
+}
+
+/// Parse the List of Trades of a TradingView strategy tester or paper trading export
+/// Each trade is an entry and an exit row under one trade number. The export names no symbol, so
+/// the chart's symbol is passed in; continuous futures such as "ES1!" get their product's
+/// multiplier. Trades still open at the last bar are skipped. Profit is net of the strategy's
+/// commission, which is recovered as the difference from the price move.
+pub fn parse_tradingview_trades(content: &str, symbol: &str) -> Result<RoundTripParseResult, String> {
+    let (symbol, asset_type, multiplier) = tradingview_instrument(symbol)?;
+    let mut errors = Vec::new();
+    let mut columns: Option<HashMap<String, usize>> = None;
+    let mut legs: BTreeMap<u32, Vec<(TradeLeg, CsvRecord)>> = BTreeMap::new();
+
+    for record in csv_records(content) {
+        if record.is_blank() {
+            continue;
+        }
+        let Some(ref header) = columns else {
+            columns = Some(record.header_columns());
+            continue;
+        };
+
+        match parse_leg(&record.fields, header) {
+            Ok((number, leg)) => legs.entry(number).or_default().push((leg, record)),
+            Err(e) => errors.push(TlgParseError {
+                line_number: record.line_number,
+                line_content: record.raw,
+                error: e,
+            }),
+        }
+    }
+
+    let mut trades = Vec::new();
+    let mut seen_ids: HashMap<String, usize> = HashMap::new();
+    for (number, trade_legs) in legs {
+        let (mut entries, mut exits): (Vec<_>, Vec<_>) = trade_legs.into_iter().partition(|(leg, _)| leg.entry);
+        let (entry, exit) = match (entries.pop(), exits.pop()) {
+            (Some((entry, _)), Some((exit, _))) if entries.is_empty() && exits.is_empty() => (entry, exit),
+            (entry, exit) => {
+                for (_, record) in entries.into_iter().chain(exits).chain(entry).chain(exit) {
+                    errors.push(TlgParseError {
+                        line_number: record.line_number,
+                        line_content: record.raw,
+                        error: format!("Trade {} needs one entry and one exit", number),
+                    });
+                }
+                continue;
+            }
+        };
+        if exit.open {
+            continue;
+        }
+
+        let direction = if entry.short { -1.0 } else { 1.0 };
+        let gross = (exit.price - entry.price) * entry.quantity * multiplier * direction;
+        let commission = exit.profit.map_or(0.0, |net| ((gross - net) * 100.0).round() / 100.0);
+
+        let base_id = format!(
+            "tradingview-{}-{}-{}-{}-{}-{}",
+            symbol.split_whitespace().collect::<String>(),
+            entry.time.format("%Y%m%d%H%M%S"),
+            exit.time.format("%Y%m%d%H%M%S"),
+            entry.quantity * direction,
+            entry.price,
+            exit.price
+        );
+        // Identical trades in one export are told apart by their order
+        let occurrence = seen_ids.entry(base_id.clone()).or_insert(0);
+        *occurrence += 1;
+
+        trades.push(RoundTripTrade {
+            broker_trade_id: format!("{}-{}", base_id, occurrence),
+            symbol: symbol.clone(),
+            asset_type,
+            multiplier,
+            short: entry.short,
+            quantity: entry.quantity,
+            entry_price: entry.price,
+            exit_price: exit.price,
+            entry_time: entry.time,
+            exit_time: exit.time,
+            commission,
+            fx_rate: None,
+        });
+    }
+
+    Ok(RoundTripParseResult { trades, errors })
+}
+
+/// Parse one row of the List of Trades into its trade number and leg
+/// Columns: Trade #,Type,Signal,Date/Time,Price USD,Contracts,Profit USD,Profit %,Cum. Profit USD,...
+/// The price and profit columns are named after the chart's currency; newer exports call the
+/// profit "Net P&L" and the quantity "Quantity" or "Position size".
+fn parse_leg(fields: &[String], header: &HashMap<String, usize>) -> Result<(u32, TradeLeg), String> {
+    let field = |name: &str| column(fields, header, name);
+    let field_starting = |prefixes: &[&str]| {
+        header
+            .iter()
+            .filter(|(name, _)| prefixes.iter().any(|p| name.starts_with(p)) && !name.ends_with('%'))
+            .min_by_key(|&(_, &i)| i)
+            .map(|(name, _)| column(fields, header, name))
+            .unwrap_or("")
+    };
+
+    let number = field("Trade #")
+        .parse::<u32>()
+        .map_err(|_| format!("Invalid trade number: {}", field("Trade #")))?;
+    let (entry, short) = match field("Type").to_lowercase().as_str() {
+        "entry long" => (true, false),
+        "entry short" => (true, true),
+        "exit long" => (false, false),
+        "exit short" => (false, true),
+        other => return Err(format!("Unknown trade type: {}", other)),
+    };
+
+    let time = parse_trade_time(field("Date/Time"))?;
+    let price = parse_amount(field_starting(&["Price"]))
+        .ok_or_else(|| format!("Invalid price: {}", field_starting(&["Price"])))?;
+    let quantity = field_starting(&["Contracts", "Quantity", "Position size"]);
+    let quantity = parse_amount(quantity)
+        .filter(|q| *q != 0.0)
+        .ok_or_else(|| format!("Invalid quantity: {}", quantity))?
+        .abs();
+
+    Ok((
+        number,
+        TradeLeg {
+            entry,
+            short,
+            open: field("Signal").eq_ignore_ascii_case("open"),
+            time,
+            price,
+            quantity,
+            profit: parse_amount(field_starting(&["Profit", "Net P&L"])),
+        },
+    ))
+}
+
+/// Symbol, asset type and multiplier of a TradingView symbol such as "NASDAQ:AAPL" or "CME_MINI:ES1!"
+fn tradingview_instrument(symbol: &str) -> Result<(String, TlgAssetType, f64), String> {
+    // The exchange prefix is not part of the journal's symbols
+    let symbol = symbol.rsplit(':').next().unwrap_or(symbol).trim().to_uppercase();
+    if symbol.is_empty() {
+        return Err("Missing symbol".to_string());
+    }
+
+    match symbol.strip_suffix('!') {
+        Some(continuous) => {
+            let product = continuous.trim_end_matches(|c: char| c.is_ascii_digit());
+            let multiplier = contract_multiplier(product)
+                .ok_or_else(|| format!("Unknown contract multiplier for product: {}", product))?;
+            Ok((product.to_string(), TlgAssetType::Future, multiplier))
+        }
+        None => Ok((symbol, TlgAssetType::Stock, 1.0)),
+    }
+}
+
+/// Trade time as YYYY-MM-DD HH:MM, with or without seconds
+fn parse_trade_time(s: &str) -> Result<NaiveDateTime, String> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S"))
+        .map_err(|_| format!("Invalid trade time: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    const TRADES: &str = r#"Trade #,Type,Signal,Date/Time,Price USD,Contracts,Profit USD,Profit %,Cum. Profit USD,Cum. Profit %,Run-up USD,Run-up %,Drawdown USD,Drawdown %
+4,Exit Short,Open,2024-01-19 16:00,4790.00,1,500.00,0.21,1537.50,0.15,600.00,0.25,-100.00,-0.04
+4,Entry Short,Short,2024-01-18 09:30,4800.00,1,500.00,0.21,1537.50,0.15,600.00,0.25,-100.00,-0.04
+3,Exit Long,Close,2024-01-17 11:00,4790.00,2,"−1,004.00",−0.42,1037.50,0.10,250.00,0.1,"−1,100.00",−0.46
+3,Entry Long,Long,2024-01-17 09:30,4800.00,2,"−1,004.00",−0.42,1037.50,0.10,250.00,0.1,"−1,100.00",−0.46
+2,Exit Short,Cover,2024-01-16 10:15,4790.00,1,497.50,0.21,2041.50,0.2,550.00,0.23,-50.00,-0.02
+1,Exit Long,Close,2024-01-15 15:00,4830.00,1,1544.00,0.64,1544.00,0.15,1600.00,0.67,-25.00,-0.01
+1,Entry Long,Long,2024-01-15 09:30,4799.00,1,1544.00,0.64,1544.00,0.15,1600.00,0.67,-25.00,-0.01
+"#;
+
+    #[test]
+    fn test_parse_tradingview_trades() {
+        let result = parse_tradingview_trades(TRADES, "CME_MINI:ES1!").unwrap();
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line_number, 6);
+        assert_eq!(result.errors[0].error, "Trade 2 needs one entry and one exit");
+
+        // The still open trade 4 is skipped
+        assert_eq!(result.trades.len(), 2);
+        let winner = &result.trades[0];
+        assert_eq!(winner.symbol, "ES");
+        assert_eq!(winner.asset_type, TlgAssetType::Future);
+        assert_eq!(winner.multiplier, 50.0);
+        assert_eq!(winner.entry_time, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap().and_hms_opt(9, 30, 0).unwrap());
+        // 31 points × 50 = 1550 gross against 1544 net
+        assert_eq!(winner.commission, 6.0);
+
+        let loser = &result.trades[1];
+        assert!(!loser.short);
+        assert_eq!(loser.quantity, 2.0);
+        assert_eq!(loser.commission, 4.0);
+
+        // IDs are stable across parses
+        let again = parse_tradingview_trades(TRADES, "CME_MINI:ES1!").unwrap();
+        assert_eq!(again.trades[1].broker_trade_id, loser.broker_trade_id);
+    }
+
+    #[test]
+    fn test_tradingview_instrument() {
+        let (symbol, asset_type, multiplier) = tradingview_instrument("NASDAQ:aapl").unwrap();
+        assert_eq!((symbol.as_str(), asset_type, multiplier), ("AAPL", TlgAssetType::Stock, 1.0));
+        assert_eq!(tradingview_instrument("ZZ1!").unwrap_err(), "Unknown contract multiplier for product: ZZ");
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use chrono::NaiveDateTime;
+use super::csv_export::{assign_content_ids, column, csv_records, has_columns, match_open_close, parse_amount, SidedFill};
+use super::{BrokerParser, OpeningPosition, TlgAction, TlgAssetType, TlgExecution, TlgParseError, TlgParseResult};
+
+/// Dollar value of a one-point move per contract, by product code
+const CONTRACT_MULTIPLIERS: [(&str, f64); 24] = [
+    ("ES", 50.0),
+    ("MES", 5.0),
+    ("NQ", 20.0),
+    ("MNQ", 2.0),
+    ("YM", 5.0),
+    ("MYM", 0.5),
+    ("RTY", 50.0),
+    ("M2K", 5.0),
+    ("CL", 1000.0),
+    ("MCL", 100.0),
+    ("NG", 10000.0),
+    ("GC", 100.0),
+    ("MGC", 10.0),
+    ("SI", 5000.0),
+    ("HG", 25000.0),
+    ("ZB", 1000.0),
+    ("ZN", 1000.0),
+    ("ZF", 1000.0),
+    ("ZT", 2000.0),
+    ("6E", 125000.0),
+    ("6J", 12500000.0),
+    ("ZC", 50.0),
+    ("ZS", 50.0),
+    ("ZW", 50.0),
+];
+
+/// Parse a Tradovate fills CSV into futures executions shaped like TLG ones
+/// Tradovate reports sides without an open/close flag, so fills are matched against the running
+/// position of their contract. Each contract's multiplier comes from its product code; fills of
+/// products without a known multiplier are reported as errors rather than imported with wrong PnL.
+pub fn parse_tradovate_fills(content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+    let mut fills = Vec::new();
+    let mut errors = Vec::new();
+    let mut columns: Option<HashMap<String, usize>> = None;
+
+    for record in csv_records(content) {
+        if record.is_blank() {
+            continue;
+        }
+        let Some(ref header) = columns else {
+            columns = Some(record.header_columns());
+            continue;
+        };
+
+        match parse_fill(&record.fields, header) {
+            Ok(fill) => fills.push(fill),
+            Err(e) => errors.push(TlgParseError {
+                line_number: record.line_number,
+                line_content: record.raw,
+                error: e,
+            }),
+        }
+    }
+
+    let mut executions = match_open_close(fills, opening);
+    assign_content_ids("tradovate", &mut executions);
+    TlgParseResult { executions, errors }
+}
+
+/// Tradovate fills exports
+pub struct TradovateParser;
+
+impl BrokerParser for TradovateParser {
+    fn id(&self) -> &'static str {
+        "tradovate"
+    }
+
+    fn name(&self) -> &'static str {
+        "Tradovate fills"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        has_columns(content, &["Contract", "B/S", "Product"])
+    }
+
+    fn parse(&self, content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+        parse_tradovate_fills(content, opening)
+    }
+}
+
+/// Parse one fill
+/// Columns: _id,...,Account,Contract,B/S,Quantity,Price,Timestamp,Date,Product,Product Description,commission
+fn parse_fill(fields: &[String], header: &HashMap<String, usize>) -> Result<SidedFill, String> {
+    let field = |name: &str| column(fields, header, name);
+
+    let buy = match field("B/S").to_uppercase().as_str() {
+        "BUY" => true,
+        "SELL" => false,
+        _ => return Err(format!("Unknown side: {}", field("B/S"))),
+    };
+
+    let contract = field("Contract").to_uppercase();
+    if contract.is_empty() {
+        return Err("Missing contract".to_string());
+    }
+    let product = match field("Product") {
+        "" => product_code(&contract).to_string(),
+        product => product.to_uppercase(),
+    };
+    let multiplier = contract_multiplier(&product)
+        .ok_or_else(|| format!("Unknown contract multiplier for product: {}", product))?;
+
+    let quantity = parse_amount(field("Quantity"))
+        .ok_or_else(|| format!("Invalid quantity: {}", field("Quantity")))?
+        .abs();
+    let price = parse_amount(field("Price")).ok_or_else(|| format!("Invalid price: {}", field("Price")))?;
+    let commission = match field("commission") {
+        "" => 0.0,
+        commission => parse_amount(commission)
+            .ok_or_else(|| format!("Invalid commission: {}", commission))?
+            .abs(),
+    };
+    let executed_at = parse_timestamp(field("Timestamp"))?;
+
+    let quantity = if buy { quantity } else { -quantity };
+    let name = match field("Product Description") {
+        "" => contract.clone(),
+        description => description.to_string(),
+    };
+
+    Ok(SidedFill {
+        buy,
+        short: false,
+        execution: TlgExecution {
+            broker_execution_id: String::new(), // Assigned once actions are known
+            symbol: contract,
+            name,
+            exchange: "TRADOVATE".to_string(),
+            action: if buy { TlgAction::BuyToOpen } else { TlgAction::SellToClose },
+            execution_date: executed_at.date(),
+            execution_time: executed_at.format("%H:%M:%S").to_string(),
+            currency: "USD".to_string(),
+            quantity,
+            multiplier,
+            price,
+            total: quantity * price * multiplier,
+            fees: -commission, // Negative like TLG fees
+            fx_rate: None,
+            asset_type: TlgAssetType::Future,
+            option_details: None,
+            future_details: None,
+        },
+    })
+}
+
+/// Product code of a contract symbol: ESH4 and ESH24 are ES, M2KZ3 is M2K
+pub fn product_code(contract: &str) -> &str {
+    let without_year = contract.trim_end_matches(|c: char| c.is_ascii_digit());
+    // Drop the month code that follows the product
+    match without_year.char_indices().last() {
+        Some((i, _)) if i > 0 => &without_year[..i],
+        _ => contract,
+    }
+}
+
+/// Contract multiplier of a product code
+pub fn contract_multiplier(product: &str) -> Option<f64> {
+    CONTRACT_MULTIPLIERS
+        .iter()
+        .find(|(code, _)| code.eq_ignore_ascii_case(product))
+        .map(|&(_, multiplier)| multiplier)
+}
+
+/// Fill time as MM/DD/YYYY HH:MM:SS or YYYY-MM-DD HH:MM:SS
+fn parse_timestamp(s: &str) -> Result<NaiveDateTime, String> {
+    NaiveDateTime::parse_from_str(s, "%m/%d/%Y %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S"))
+        .map_err(|_| format!("Invalid timestamp: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILLS: &str = r#"_id,Account,Contract,B/S,Quantity,Price,Timestamp,Date,Product,Product Description,commission
+1003,DEMO123,ESH4, Sell,3,4810.25,01/15/2024 10:15:00,1/15/24,ES,E-Mini S&P 500,3.87
+1001,DEMO123,ESH4, Buy,2,4800.00,01/15/2024 09:31:02,1/15/24,ES,E-Mini S&P 500,2.58
+1002,DEMO123,MNQH4, Buy,1,16800.50,01/15/2024 09:45:00,1/15/24,,,0.52
+1004,DEMO123,ZZH4, Buy,1,10.00,01/15/2024 09:50:00,1/15/24,ZZ,Unknown,1.00
+"#;
+
+    #[test]
+    fn test_parse_tradovate_fills() {
+        let result = parse_tradovate_fills(FILLS, &[]);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line_number, 5);
+        assert_eq!(result.errors[0].error, "Unknown contract multiplier for product: ZZ");
+
+        // The sell of 3 closes the long 2 and opens a short 1, splitting the commission
+        let es: Vec<&TlgExecution> = result.executions.iter().filter(|e| e.symbol == "ESH4").collect();
+        assert_eq!(es.len(), 3);
+        assert_eq!(es[0].action, TlgAction::BuyToOpen);
+        assert_eq!(es[0].multiplier, 50.0);
+        assert_eq!(es[0].asset_type, TlgAssetType::Future);
+        assert_eq!(es[1].action, TlgAction::SellToClose);
+        assert_eq!(es[1].quantity, -2.0);
+        assert!((es[1].abs_fees() - 2.58).abs() < 1e-9);
+        assert_eq!(es[2].action, TlgAction::SellToOpen);
+        assert_eq!(es[2].quantity, -1.0);
+
+        // Product derived from the contract when the column is empty
+        let mnq = result.executions.iter().find(|e| e.symbol == "MNQH4").unwrap();
+        assert_eq!(mnq.multiplier, 2.0);
+    }
+
+    #[test]
+    fn test_product_code() {
+        assert_eq!(product_code("ESH4"), "ES");
+        assert_eq!(product_code("MESZ24"), "MES");
+        assert_eq!(product_code("M2KZ3"), "M2K");
+        assert_eq!(product_code("6EM4"), "6E");
+    }
+}
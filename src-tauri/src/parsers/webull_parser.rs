@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use chrono::NaiveDateTime;
+use super::csv_export::{assign_content_ids, column, csv_records, has_columns, match_open_close, parse_amount, SidedFill};
+use super::{BrokerParser, OpeningPosition, TlgAction, TlgAssetType, TlgExecution, TlgParseError, TlgParseResult, parse_option_symbol};
+
+/// Parse a Webull orders export CSV into executions shaped like TLG ones
+/// Each order counts with its filled quantity at the average fill price, so partially filled
+/// orders import what was filled and orders that never filled are skipped.
+/// Webull reports sides without an open/close flag, so fills are matched against the running
+/// position of their symbol.
+pub fn parse_webull_orders(content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+    let mut orders = Vec::new();
+    let mut errors = Vec::new();
+    let mut columns: Option<HashMap<String, usize>> = None;
+
+    for record in csv_records(content) {
+        if record.is_blank() {
+            continue;
+        }
+        let Some(ref header) = columns else {
+            columns = Some(record.header_columns());
+            continue;
+        };
+
+        match parse_order(&record.fields, header) {
+            Ok(Some(order)) => orders.push(order),
+            Ok(None) => {}
+            Err(e) => errors.push(TlgParseError {
+                line_number: record.line_number,
+                line_content: record.raw,
+                error: e,
+            }),
+        }
+    }
+
+    let mut executions = match_open_close(orders, opening);
+    assign_content_ids("webull", &mut executions);
+    TlgParseResult { executions, errors }
+}
+
+/// Webull orders exports
+pub struct WebullParser;
+
+impl BrokerParser for WebullParser {
+    fn id(&self) -> &'static str {
+        "webull"
+    }
+
+    fn name(&self) -> &'static str {
+        "Webull orders"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        has_columns(content, &["Symbol", "Side", "Status", "Filled", "Avg Price", "Filled Time"])
+    }
+
+    fn parse(&self, content: &str, opening: &[OpeningPosition]) -> TlgParseResult {
+        parse_webull_orders(content, opening)
+    }
+}
+
+/// Parse one order row, or None when nothing was filled
+/// Columns: Name,Symbol,Side,Status,Filled,Total Qty,Price,Avg Price,Time-in-Force,Placed Time,Filled Time
+fn parse_order(fields: &[String], header: &HashMap<String, usize>) -> Result<Option<SidedFill>, String> {
+    let field = |name: &str| column(fields, header, name);
+
+    let filled = match field("Filled") {
+        "" => 0.0,
+        filled => parse_amount(filled).ok_or_else(|| format!("Invalid filled quantity: {}", filled))?,
+    };
+    if filled <= 0.0 {
+        return Ok(None);
+    }
+
+    let (buy, short) = match field("Side").to_uppercase().as_str() {
+        "BUY" => (true, false),
+        "SELL" => (false, false),
+        "SHORT" => (false, true),
+        _ => return Err(format!("Unknown side: {}", field("Side"))),
+    };
+
+    let price = parse_amount(field("Avg Price").trim_start_matches('@'))
+        .ok_or_else(|| format!("Invalid average price: {}", field("Avg Price")))?;
+
+    let time = match field("Filled Time") {
+        "" => field("Placed Time"),
+        time => time,
+    };
+    let executed_at = parse_order_time(time)?;
+
+    let symbol = field("Symbol").to_uppercase();
+    if symbol.is_empty() {
+        return Err("Missing symbol".to_string());
+    }
+
+    // Option orders carry the contract symbol without padding, e.g. AAPL240119C00150000
+    let option_details = if symbol.chars().any(|c| c.is_ascii_digit()) {
+        Some(parse_option_symbol(&symbol)?)
+    } else {
+        None
+    };
+    let (symbol, asset_type, multiplier) = match option_details {
+        Some(ref details) => (details.occ_symbol(), TlgAssetType::Option, 100.0),
+        None => (symbol, TlgAssetType::Stock, 1.0),
+    };
+
+    let quantity = if buy { filled } else { -filled };
+    let name = match field("Name") {
+        "" => symbol.clone(),
+        name => name.to_string(),
+    };
+
+    Ok(Some(SidedFill {
+        buy,
+        short,
+        execution: TlgExecution {
+            broker_execution_id: String::new(), // Assigned once actions are known
+            symbol,
+            name,
+            exchange: "WEBULL".to_string(),
+            action: if buy { TlgAction::BuyToOpen } else { TlgAction::SellToClose },
+            execution_date: executed_at.date(),
+            execution_time: executed_at.format("%H:%M:%S").to_string(),
+            currency: "USD".to_string(),
+            quantity,
+            multiplier,
+            price,
+            total: quantity * price * multiplier,
+            fees: 0.0,
+            fx_rate: None,
+            asset_type,
+            option_details,
+            future_details: None,
+        },
+    }))
+}
+
+/// Order time as MM/DD/YYYY HH:MM:SS, followed by a time zone abbreviation such as EST
+fn parse_order_time(s: &str) -> Result<NaiveDateTime, String> {
+    let without_zone = match s.rsplit_once(' ') {
+        Some((rest, zone)) if zone.chars().all(|c| c.is_ascii_alphabetic()) => rest,
+        _ => s,
+    };
+    NaiveDateTime::parse_from_str(without_zone, "%m/%d/%Y %H:%M:%S")
+        .map_err(|_| format!("Invalid order time: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crate::parsers::OptionType;
+
+    const ORDERS: &str = r#"Name,Symbol,Side,Status,Filled,Total Qty,Price,Avg Price,Time-in-Force,Placed Time,Filled Time
+Tesla Inc,TSLA,Buy,Filled,10,10,@250.00,249.90,DAY,01/17/2024 10:00:00 EST,01/17/2024 10:00:02 EST
+Apple Inc,AAPL,Sell,Filled,150,150,MKT,190.00,DAY,01/16/2024 14:00:00 EST,01/16/2024 14:00:01 EST
+Apple Inc,AAPL,Buy,Partially Filled,60,100,@185.00,184.95,DAY,01/15/2024 09:45:00 EST,01/15/2024 09:45:10 EST
+Apple Inc,AAPL,Buy,Cancelled,0,100,@180.00,,DAY,01/15/2024 09:40:00 EST,
+Apple Inc,AAPL,Buy,Filled,40,40,@185.50,185.50,DAY,01/15/2024 09:31:00 EST,01/15/2024 09:31:02 EST
+SPY 240119 Put 470.00,SPY240119P00470000,Buy,Filled,2,2,@1.50,1.50,DAY,01/16/2024 10:00:00 EST,01/16/2024 10:00:01 EST
+Microsoft,MSFT,Hold,Filled,1,1,@400.00,400.00,DAY,01/16/2024 10:00:00 EST,01/16/2024 10:00:01 EST
+"#;
+
+    #[test]
+    fn test_parse_webull_orders() {
+        let result = parse_webull_orders(ORDERS, &[]);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line_number, 8);
+        assert_eq!(result.errors[0].error, "Unknown side: Hold");
+
+        // The cancelled order is skipped and the sell of 150 closes 100 and opens a 50 short
+        let aapl: Vec<&TlgExecution> = result.executions.iter().filter(|e| e.symbol == "AAPL").collect();
+        assert_eq!(aapl.len(), 4);
+        assert_eq!(aapl[0].execution_time, "09:31:02");
+        assert_eq!(aapl[1].quantity, 60.0);
+        assert_eq!(aapl[1].price, 184.95);
+        assert_eq!(aapl[2].action, TlgAction::SellToClose);
+        assert_eq!(aapl[2].quantity, -100.0);
+        assert_eq!(aapl[3].action, TlgAction::SellToOpen);
+        assert_eq!(aapl[3].quantity, -50.0);
+        assert_ne!(aapl[2].broker_execution_id, aapl[3].broker_execution_id);
+
+        let put = result.executions.iter().find(|e| e.asset_type == TlgAssetType::Option).unwrap();
+        assert_eq!(put.symbol, "SPY   240119P00470000");
+        assert_eq!(put.multiplier, 100.0);
+        let details = put.option_details.as_ref().unwrap();
+        assert_eq!(details.option_type, OptionType::Put);
+        assert_eq!(details.expiration_date, NaiveDate::from_ymd_opt(2024, 1, 19).unwrap());
+
+        let again = parse_webull_orders(ORDERS, &[]);
+        assert_eq!(again.executions[0].broker_execution_id, result.executions[0].broker_execution_id);
+    }
+
+    #[test]
+    fn test_parse_webull_orders_from_opening_positions() {
+        // Held 200 AAPL before the export; fills up to the 15th are already part of it
+        let opening = [OpeningPosition {
+            symbol: "aapl".to_string(),
+            quantity: 200.0,
+            as_of_date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+        }];
+        let result = parse_webull_orders(ORDERS, &opening);
+
+        let aapl: Vec<&TlgExecution> = result.executions.iter().filter(|e| e.symbol == "AAPL").collect();
+        assert_eq!(aapl.len(), 3);
+        assert_eq!(aapl[2].action, TlgAction::SellToClose);
+        assert_eq!(aapl[2].quantity, -150.0);
+
+        // A short opening position is covered by the first buy
+        let opening = [OpeningPosition {
+            symbol: "TSLA".to_string(),
+            quantity: -10.0,
+            as_of_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        }];
+        let result = parse_webull_orders(ORDERS, &opening);
+        let tsla = result.executions.iter().find(|e| e.symbol == "TSLA").unwrap();
+        assert_eq!(tsla.action, TlgAction::BuyToClose);
+    }
+}
@@ -1,7 +1,8 @@
-use chrono::Utc;
+use std::collections::HashSet;
+use chrono::{NaiveDate, Utc};
 use sqlx::sqlite::SqlitePool;
 use sqlx::Row;
-use crate::models::Account;
+use crate::models::{Account, CommissionSchedule, PeriodLock};
 
 pub struct AccountRepository;
 
@@ -28,24 +29,37 @@ impl AccountRepository {
         Ok(row.map(|r| Self::row_to_account(&r)))
     }
 
-    /// Create a new account
+    /// Create a new live account
+    #[cfg(test)]
     pub async fn create(
         pool: &SqlitePool,
         user_id: &str,
         name: &str,
         base_currency: Option<&str>,
+    ) -> Result<Account, sqlx::Error> {
+        Self::create_with_paper_flag(pool, user_id, name, base_currency, false).await
+    }
+
+    /// Create a new live or paper account
+    pub async fn create_with_paper_flag(
+        pool: &SqlitePool,
+        user_id: &str,
+        name: &str,
+        base_currency: Option<&str>,
+        is_paper: bool,
     ) -> Result<Account, sqlx::Error> {
         let id = uuid::Uuid::new_v4().to_string();
         let currency = base_currency.unwrap_or("USD");
         let now = Utc::now();
 
         sqlx::query(
-            "INSERT INTO accounts (id, user_id, name, base_currency, created_at) VALUES (?, ?, ?, ?, ?)"
+            "INSERT INTO accounts (id, user_id, name, base_currency, is_paper, created_at) VALUES (?, ?, ?, ?, ?, ?)"
         )
         .bind(&id)
         .bind(user_id)
         .bind(name)
         .bind(currency)
+        .bind(is_paper)
         .bind(now)
         .execute(pool)
         .await?;
@@ -53,12 +67,188 @@ impl AccountRepository {
         Self::get_by_id(pool, &id).await?.ok_or(sqlx::Error::RowNotFound)
     }
 
+    /// Mark an account as paper or live
+    pub async fn set_paper(
+        pool: &SqlitePool,
+        user_id: &str,
+        id: &str,
+        is_paper: bool,
+    ) -> Result<Option<Account>, sqlx::Error> {
+        sqlx::query("UPDATE accounts SET is_paper = ? WHERE id = ? AND user_id = ?")
+            .bind(is_paper)
+            .bind(id)
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+
+        let account = Self::get_by_id(pool, id).await?;
+        Ok(account.filter(|a| a.user_id == user_id))
+    }
+
+    /// Set or clear an account's starting balance
+    pub async fn set_starting_balance(
+        pool: &SqlitePool,
+        user_id: &str,
+        id: &str,
+        starting_balance: Option<f64>,
+    ) -> Result<Option<Account>, sqlx::Error> {
+        sqlx::query("UPDATE accounts SET starting_balance = ? WHERE id = ? AND user_id = ?")
+            .bind(starting_balance)
+            .bind(id)
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+
+        let account = Self::get_by_id(pool, id).await?;
+        Ok(account.filter(|a| a.user_id == user_id))
+    }
+
+    /// Get the IDs of a user's paper accounts
+    pub async fn get_paper_account_ids(
+        pool: &SqlitePool,
+        user_id: &str,
+    ) -> Result<HashSet<String>, sqlx::Error> {
+        let ids: Vec<String> = sqlx::query_scalar(
+            "SELECT id FROM accounts WHERE user_id = ? AND is_paper = 1"
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(ids.into_iter().collect())
+    }
+
+    /// Lock an account's trades through the given date, replacing any existing lock
+    pub async fn lock_period(
+        pool: &SqlitePool,
+        account_id: &str,
+        locked_through: NaiveDate,
+    ) -> Result<PeriodLock, sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO period_locks (account_id, locked_through, locked_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT(account_id) DO UPDATE SET
+                locked_through = excluded.locked_through,
+                locked_at = excluded.locked_at
+            "#
+        )
+        .bind(account_id)
+        .bind(locked_through)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        Self::get_period_lock(pool, account_id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Remove an account's period lock
+    /// Returns false if the account had no lock
+    pub async fn unlock_period(pool: &SqlitePool, account_id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM period_locks WHERE account_id = ?")
+            .bind(account_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Get an account's period lock
+    pub async fn get_period_lock(
+        pool: &SqlitePool,
+        account_id: &str,
+    ) -> Result<Option<PeriodLock>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM period_locks WHERE account_id = ?")
+            .bind(account_id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(row.map(|r| Self::row_to_period_lock(&r)))
+    }
+
+    /// Get period locks for all of a user's accounts
+    pub async fn get_period_locks(pool: &SqlitePool, user_id: &str) -> Result<Vec<PeriodLock>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT pl.*
+            FROM period_locks pl
+            JOIN accounts a ON pl.account_id = a.id
+            WHERE a.user_id = ?
+            ORDER BY a.created_at ASC
+            "#
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_period_lock).collect())
+    }
+
+    /// Create or replace an account's commission schedule
+    pub async fn save_commission_schedule(
+        pool: &SqlitePool,
+        schedule: &CommissionSchedule,
+    ) -> Result<CommissionSchedule, sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO commission_schedules (account_id, per_order, per_share, per_contract, minimum_per_order, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(account_id) DO UPDATE SET
+                per_order = excluded.per_order,
+                per_share = excluded.per_share,
+                per_contract = excluded.per_contract,
+                minimum_per_order = excluded.minimum_per_order,
+                updated_at = excluded.updated_at
+            "#
+        )
+        .bind(&schedule.account_id)
+        .bind(schedule.per_order)
+        .bind(schedule.per_share)
+        .bind(schedule.per_contract)
+        .bind(schedule.minimum_per_order)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        Self::get_commission_schedule(pool, &schedule.account_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Get an account's commission schedule
+    pub async fn get_commission_schedule(
+        pool: &SqlitePool,
+        account_id: &str,
+    ) -> Result<Option<CommissionSchedule>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM commission_schedules WHERE account_id = ?")
+            .bind(account_id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(row.map(|r| CommissionSchedule {
+            account_id: r.get("account_id"),
+            per_order: r.get("per_order"),
+            per_share: r.get("per_share"),
+            per_contract: r.get("per_contract"),
+            minimum_per_order: r.get("minimum_per_order"),
+        }))
+    }
+
+    fn row_to_period_lock(row: &sqlx::sqlite::SqliteRow) -> PeriodLock {
+        PeriodLock {
+            account_id: row.get("account_id"),
+            locked_through: row.get("locked_through"),
+            locked_at: row.get("locked_at"),
+        }
+    }
+
     fn row_to_account(row: &sqlx::sqlite::SqliteRow) -> Account {
         Account {
             id: row.get("id"),
             user_id: row.get("user_id"),
             name: row.get("name"),
             base_currency: row.get("base_currency"),
+            is_paper: row.get("is_paper"),
+            starting_balance: row.get("starting_balance"),
             created_at: row.get("created_at"),
         }
     }
@@ -200,6 +390,39 @@ mod tests {
         assert_eq!(fetched.base_currency, "GBP");
     }
 
+    #[tokio::test]
+    async fn test_paper_accounts() {
+        let pool = create_test_db().await;
+        let user_id = setup_user(&pool).await;
+
+        let live = AccountRepository::create(&pool, &user_id, "Live", None)
+            .await
+            .expect("Failed to create account");
+        let paper = AccountRepository::create_with_paper_flag(&pool, &user_id, "Sim", None, true)
+            .await
+            .expect("Failed to create account");
+
+        assert!(!live.is_paper);
+        assert!(paper.is_paper);
+
+        let paper_ids = AccountRepository::get_paper_account_ids(&pool, &user_id)
+            .await
+            .expect("Query failed");
+        assert_eq!(paper_ids.len(), 1);
+        assert!(paper_ids.contains(&paper.id));
+
+        let updated = AccountRepository::set_paper(&pool, &user_id, &live.id, true)
+            .await
+            .expect("Update failed")
+            .expect("Account not found");
+        assert!(updated.is_paper);
+
+        let other_user = AccountRepository::set_paper(&pool, "other-user", &paper.id, false)
+            .await
+            .expect("Update failed");
+        assert!(other_user.is_none());
+    }
+
     #[tokio::test]
     async fn test_get_by_id_not_found() {
         let pool = create_test_db().await;
@@ -0,0 +1,209 @@
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
+use sqlx::{Connection, Row};
+use crate::models::ArchivedYear;
+use crate::repository::trade_repo::DEPENDENT_TABLES;
+
+/// Name the archive database is attached under on every connection
+pub const ARCHIVE_SCHEMA: &str = "archive";
+
+/// Rows moved with their trade: its dependent rows and the dividends attributed to it
+fn child_tables() -> impl Iterator<Item = &'static str> {
+    DEPENDENT_TABLES.into_iter().chain(["dividends"])
+}
+
+/// Trades and their child tables, which the archive mirrors
+fn archived_tables() -> impl Iterator<Item = &'static str> {
+    std::iter::once("trades").chain(child_tables())
+}
+
+pub struct ArchiveRepository;
+
+impl ArchiveRepository {
+    /// Create the archived tables and their indexes from the main database's own DDL, and add
+    /// columns that later migrations added to the main tables
+    /// The archive's foreign keys name tables that only exist in main, so rows are only moved in
+    /// and out with foreign keys off, together with their trade.
+    pub async fn ensure_tables(conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
+        for table in archived_tables() {
+            let schema = sqlx::query("SELECT type, name, sql FROM main.sqlite_master WHERE tbl_name = ? AND sql IS NOT NULL")
+                .bind(table)
+                .fetch_all(&mut *conn)
+                .await?;
+
+            for row in schema.iter().filter(|r| r.get::<&str, _>("type") == "table") {
+                let sql: &str = row.get("sql");
+                let Some(columns) = sql.find('(').map(|i| &sql[i..]) else {
+                    continue;
+                };
+                sqlx::query(&format!("CREATE TABLE IF NOT EXISTS {}.{} {}", ARCHIVE_SCHEMA, table, columns))
+                    .execute(&mut *conn)
+                    .await?;
+            }
+
+            let archived = Self::columns(conn, ARCHIVE_SCHEMA, table).await?;
+            for row in sqlx::query("SELECT name, type FROM pragma_table_info(?, 'main') ORDER BY cid")
+                .bind(table)
+                .fetch_all(&mut *conn)
+                .await?
+            {
+                let name: String = row.get("name");
+                let column_type: String = row.get("type");
+                if !archived.contains(&name) {
+                    sqlx::query(&format!(
+                        "ALTER TABLE {}.{} ADD COLUMN {} {}",
+                        ARCHIVE_SCHEMA, table, name, column_type
+                    ))
+                    .execute(&mut *conn)
+                    .await?;
+                }
+            }
+
+            // Indexes last, as they may cover columns that were just added
+            for row in schema.iter().filter(|r| r.get::<&str, _>("type") == "index") {
+                let sql: &str = row.get("sql");
+                let name: &str = row.get("name");
+                let Some(on) = sql.to_uppercase().find(" ON ") else {
+                    continue;
+                };
+                let unique = if sql[..on].to_uppercase().contains("UNIQUE") { "UNIQUE " } else { "" };
+                sqlx::query(&format!(
+                    "CREATE {}INDEX IF NOT EXISTS {}.{}{}",
+                    unique, ARCHIVE_SCHEMA, name, &sql[on..]
+                ))
+                .execute(&mut *conn)
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Move a user's closed trades of a calendar year, with their child rows, between main and archive
+    /// Foreign keys are off while moving so rolls keep pointing at a moved trade.
+    /// Returns the number of trades moved; freed pages are only given back by `vacuum_main`.
+    pub async fn move_year(
+        pool: &SqlitePool,
+        user_id: &str,
+        year: i32,
+        to_archive: bool,
+    ) -> Result<u64, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let foreign_keys: i64 = sqlx::query_scalar("PRAGMA foreign_keys")
+            .fetch_one(&mut *conn)
+            .await?;
+        sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await?;
+
+        let moved = Self::move_year_on(&mut conn, user_id, year, to_archive).await;
+
+        sqlx::query(&format!("PRAGMA foreign_keys = {}", foreign_keys))
+            .execute(&mut *conn)
+            .await?;
+        moved
+    }
+
+    /// Give the pages freed by archiving back so the main database file shrinks
+    pub async fn vacuum_main(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+        sqlx::query("VACUUM main").execute(pool).await?;
+        Ok(())
+    }
+
+    async fn move_year_on(
+        conn: &mut SqliteConnection,
+        user_id: &str,
+        year: i32,
+        to_archive: bool,
+    ) -> Result<u64, sqlx::Error> {
+        Self::ensure_tables(conn).await?;
+        let (from, to) = if to_archive { ("main", ARCHIVE_SCHEMA) } else { (ARCHIVE_SCHEMA, "main") };
+
+        let mut tx = conn.begin().await?;
+        sqlx::query("CREATE TEMP TABLE moving_trades (id TEXT PRIMARY KEY)")
+            .execute(&mut *tx)
+            .await?;
+        let moved = sqlx::query(&format!(
+            r#"
+            INSERT INTO temp.moving_trades (id)
+            SELECT id FROM {}.trades
+            WHERE user_id = ? AND status = 'closed' AND strftime('%Y', trade_date) = ?
+            "#,
+            from
+        ))
+        .bind(user_id)
+        .bind(format!("{:04}", year))
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        // Child rows first, then the trades themselves
+        for (table, key) in child_tables().map(|t| (t, "trade_id")).chain([("trades", "id")]) {
+            let columns = Self::columns(&mut tx, "main", table).await?.join(", ");
+            sqlx::query(&format!(
+                "INSERT INTO {to}.{table} ({columns}) SELECT {columns} FROM {from}.{table} WHERE {key} IN (SELECT id FROM temp.moving_trades)",
+            ))
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query(&format!(
+                "DELETE FROM {from}.{table} WHERE {key} IN (SELECT id FROM temp.moving_trades)",
+            ))
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query("DROP TABLE temp.moving_trades").execute(&mut *tx).await?;
+        tx.commit().await?;
+        Ok(moved)
+    }
+
+    /// Years with archived trades, oldest first
+    pub async fn get_archived_years(pool: &SqlitePool, user_id: &str) -> Result<Vec<ArchivedYear>, sqlx::Error> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT CAST(strftime('%Y', trade_date) AS INTEGER) AS year, COUNT(*) AS trade_count
+            FROM {}.trades
+            WHERE user_id = ?
+            GROUP BY year
+            ORDER BY year ASC
+            "#,
+            ARCHIVE_SCHEMA
+        ))
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|r| ArchivedYear {
+                year: r.get::<i64, _>("year") as i32,
+                trade_count: r.get("trade_count"),
+            })
+            .collect())
+    }
+
+    /// Years before `before_year` that still have closed trades in the main database
+    pub async fn get_unarchived_years_before(
+        pool: &SqlitePool,
+        user_id: &str,
+        before_year: i32,
+    ) -> Result<Vec<i32>, sqlx::Error> {
+        let years: Vec<i64> = sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT CAST(strftime('%Y', trade_date) AS INTEGER) AS year
+            FROM main.trades
+            WHERE user_id = ? AND status = 'closed' AND year < ?
+            ORDER BY year ASC
+            "#
+        )
+        .bind(user_id)
+        .bind(before_year)
+        .fetch_all(pool)
+        .await?;
+        Ok(years.into_iter().map(|y| y as i32).collect())
+    }
+
+    async fn columns(conn: &mut SqliteConnection, schema: &str, table: &str) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT name FROM pragma_table_info(?, ?) ORDER BY cid")
+            .bind(table)
+            .bind(schema)
+            .fetch_all(conn)
+            .await
+    }
+}
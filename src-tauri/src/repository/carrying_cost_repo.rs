@@ -0,0 +1,157 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
+use sqlx::Row;
+use crate::models::{CarryingCost, CarryingCostType, CreateCarryingCostInput};
+
+pub struct CarryingCostRepository;
+
+impl CarryingCostRepository {
+    /// Insert a carrying cost entry
+    pub async fn insert(
+        conn: &mut SqliteConnection,
+        user_id: &str,
+        input: &CreateCarryingCostInput,
+    ) -> Result<CarryingCost, sqlx::Error> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO trade_carrying_costs (
+                id, trade_id, cost_type, cost_date, amount, notes, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(&input.trade_id)
+        .bind(input.cost_type.as_str())
+        .bind(input.cost_date)
+        .bind(input.amount)
+        .bind(&input.notes)
+        .bind(now)
+        .execute(&mut *conn)
+        .await?;
+
+        Self::get_by_id_in(conn, user_id, &id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Get one of a user's carrying cost entries by ID
+    pub async fn get_by_id(pool: &SqlitePool, user_id: &str, id: &str) -> Result<Option<CarryingCost>, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        Self::get_by_id_in(&mut conn, user_id, id).await
+    }
+
+    /// Get one of a user's carrying cost entries on a connection, e.g. within a caller's transaction
+    pub async fn get_by_id_in(conn: &mut SqliteConnection, user_id: &str, id: &str) -> Result<Option<CarryingCost>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT c.*, i.symbol
+            FROM trade_carrying_costs c
+            JOIN trades t ON c.trade_id = t.id
+            JOIN instruments i ON t.instrument_id = i.id
+            WHERE c.id = ? AND t.user_id = ?
+            "#
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(row.map(|r| Self::row_to_carrying_cost(&r)))
+    }
+
+    /// Get carrying cost entries for a trade
+    pub async fn get_for_trade(pool: &SqlitePool, trade_id: &str) -> Result<Vec<CarryingCost>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT c.*, i.symbol
+            FROM trade_carrying_costs c
+            JOIN trades t ON c.trade_id = t.id
+            JOIN instruments i ON t.instrument_id = i.id
+            WHERE c.trade_id = ?
+            ORDER BY c.cost_date ASC, c.created_at ASC
+            "#
+        )
+        .bind(trade_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_carrying_cost).collect())
+    }
+
+    /// Get a user's carrying cost entries with optional filters on the cost date
+    pub async fn get_costs(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<CarryingCost>, sqlx::Error> {
+        let mut query = String::from(
+            r#"
+            SELECT c.*, i.symbol
+            FROM trade_carrying_costs c
+            JOIN trades t ON c.trade_id = t.id
+            JOIN instruments i ON t.instrument_id = i.id
+            WHERE t.user_id = ?
+            "#
+        );
+
+        if account_id.is_some() {
+            query.push_str(" AND t.account_id = ?");
+        }
+        if start_date.is_some() {
+            query.push_str(" AND c.cost_date >= ?");
+        }
+        if end_date.is_some() {
+            query.push_str(" AND c.cost_date <= ?");
+        }
+
+        query.push_str(" ORDER BY c.cost_date ASC, c.created_at ASC");
+
+        let mut q = sqlx::query(&query).bind(user_id);
+
+        if let Some(acc) = account_id {
+            q = q.bind(acc);
+        }
+        if let Some(start) = start_date {
+            q = q.bind(start);
+        }
+        if let Some(end) = end_date {
+            q = q.bind(end);
+        }
+
+        let rows = q.fetch_all(pool).await?;
+        Ok(rows.iter().map(Self::row_to_carrying_cost).collect())
+    }
+
+    /// Delete one of a user's carrying cost entries
+    /// Returns the trade the entry belonged to, or None if the user has no entry with the given id
+    pub async fn delete(conn: &mut SqliteConnection, user_id: &str, id: &str) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            DELETE FROM trade_carrying_costs
+            WHERE id = ? AND trade_id IN (SELECT id FROM trades WHERE user_id = ?)
+            RETURNING trade_id
+            "#
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(conn)
+        .await
+    }
+
+    fn row_to_carrying_cost(row: &sqlx::sqlite::SqliteRow) -> CarryingCost {
+        CarryingCost {
+            id: row.get("id"),
+            trade_id: row.get("trade_id"),
+            symbol: row.get("symbol"),
+            cost_type: CarryingCostType::from_str(row.get::<&str, _>("cost_type"))
+                .unwrap_or(CarryingCostType::BorrowFee),
+            cost_date: row.get("cost_date"),
+            amount: row.get("amount"),
+            notes: row.get("notes"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
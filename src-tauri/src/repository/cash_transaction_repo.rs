@@ -0,0 +1,111 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use crate::models::{CashTransaction, CreateCashTransactionInput};
+
+pub struct CashTransactionRepository;
+
+impl CashTransactionRepository {
+    /// Insert a deposit or withdrawal
+    pub async fn insert(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: &CreateCashTransactionInput,
+    ) -> Result<CashTransaction, sqlx::Error> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO cash_transactions (
+                id, user_id, account_id, transaction_date, amount, notes, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(user_id)
+        .bind(&input.account_id)
+        .bind(input.transaction_date)
+        .bind(input.amount)
+        .bind(&input.notes)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        let row = sqlx::query("SELECT * FROM cash_transactions WHERE id = ?")
+            .bind(&id)
+            .fetch_one(pool)
+            .await?;
+        Ok(Self::row_to_transaction(&row))
+    }
+
+    /// Get a user's cash transactions with optional filters
+    pub async fn get_transactions(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<CashTransaction>, sqlx::Error> {
+        let mut query = String::from("SELECT * FROM cash_transactions WHERE user_id = ?");
+
+        if account_id.is_some() {
+            query.push_str(" AND account_id = ?");
+        }
+        if start_date.is_some() {
+            query.push_str(" AND transaction_date >= ?");
+        }
+        if end_date.is_some() {
+            query.push_str(" AND transaction_date <= ?");
+        }
+
+        query.push_str(" ORDER BY transaction_date ASC, created_at ASC");
+
+        let mut q = sqlx::query(&query).bind(user_id);
+
+        if let Some(acc_id) = account_id {
+            q = q.bind(acc_id);
+        }
+        if let Some(start) = start_date {
+            q = q.bind(start);
+        }
+        if let Some(end) = end_date {
+            q = q.bind(end);
+        }
+
+        let rows = q.fetch_all(pool).await?;
+        Ok(rows.iter().map(Self::row_to_transaction).collect())
+    }
+
+    /// Get a cash transaction by id
+    pub async fn get_by_id(pool: &SqlitePool, user_id: &str, id: &str) -> Result<Option<CashTransaction>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM cash_transactions WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(row.as_ref().map(Self::row_to_transaction))
+    }
+
+    /// Delete a cash transaction
+    /// Returns false if the user has no transaction with the given id
+    pub async fn delete(pool: &SqlitePool, user_id: &str, id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM cash_transactions WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn row_to_transaction(row: &sqlx::sqlite::SqliteRow) -> CashTransaction {
+        CashTransaction {
+            id: row.get("id"),
+            account_id: row.get("account_id"),
+            transaction_date: row.get("transaction_date"),
+            amount: row.get("amount"),
+            notes: row.get("notes"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
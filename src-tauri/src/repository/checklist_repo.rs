@@ -0,0 +1,120 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use crate::models::{CheckChecklistItemInput, ChecklistCheck, ChecklistPhase};
+
+pub struct ChecklistRepository;
+
+impl ChecklistRepository {
+    /// Check an item on a trade, replacing any earlier check of the same item
+    pub async fn upsert(
+        pool: &SqlitePool,
+        input: &CheckChecklistItemInput,
+    ) -> Result<ChecklistCheck, sqlx::Error> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let checked_at = input.checked_at.unwrap_or_else(Utc::now);
+
+        sqlx::query(
+            r#"
+            INSERT INTO trade_checklist_items (id, trade_id, item, phase, checked_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(trade_id, item) DO UPDATE SET
+                phase = excluded.phase,
+                checked_at = excluded.checked_at
+            "#
+        )
+        .bind(&id)
+        .bind(&input.trade_id)
+        .bind(&input.item)
+        .bind(input.phase.as_str())
+        .bind(checked_at)
+        .execute(pool)
+        .await?;
+
+        let row = sqlx::query("SELECT * FROM trade_checklist_items WHERE trade_id = ? AND item = ?")
+            .bind(&input.trade_id)
+            .bind(&input.item)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(Self::row_to_check(&row))
+    }
+
+    /// Get checked items for a trade in the order they were checked
+    pub async fn get_for_trade(pool: &SqlitePool, trade_id: &str) -> Result<Vec<ChecklistCheck>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM trade_checklist_items WHERE trade_id = ? ORDER BY checked_at ASC, item ASC"
+        )
+        .bind(trade_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_check).collect())
+    }
+
+    /// Get checked items for a user's trades with optional filters on the trade date
+    pub async fn get_checks(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<ChecklistCheck>, sqlx::Error> {
+        let mut query = String::from(
+            r#"
+            SELECT c.*
+            FROM trade_checklist_items c
+            JOIN trades t ON c.trade_id = t.id
+            WHERE t.user_id = ?
+            "#
+        );
+
+        if account_id.is_some() {
+            query.push_str(" AND t.account_id = ?");
+        }
+        if start_date.is_some() {
+            query.push_str(" AND t.trade_date >= ?");
+        }
+        if end_date.is_some() {
+            query.push_str(" AND t.trade_date <= ?");
+        }
+
+        query.push_str(" ORDER BY c.checked_at ASC");
+
+        let mut q = sqlx::query(&query).bind(user_id);
+
+        if let Some(acc) = account_id {
+            q = q.bind(acc);
+        }
+        if let Some(start) = start_date {
+            q = q.bind(start);
+        }
+        if let Some(end) = end_date {
+            q = q.bind(end);
+        }
+
+        let rows = q.fetch_all(pool).await?;
+        Ok(rows.iter().map(Self::row_to_check).collect())
+    }
+
+    /// Uncheck an item
+    /// Returns false if no check with the given id exists
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM trade_checklist_items WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn row_to_check(row: &sqlx::sqlite::SqliteRow) -> ChecklistCheck {
+        ChecklistCheck {
+            id: row.get("id"),
+            trade_id: row.get("trade_id"),
+            item: row.get("item"),
+            phase: ChecklistPhase::from_str(row.get::<&str, _>("phase"))
+                .unwrap_or(ChecklistPhase::PreEntry),
+            checked_at: row.get("checked_at"),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::NaiveDate;
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use crate::models::DailyClose;
+
+pub struct DailyCloseRepository;
+
+impl DailyCloseRepository {
+    /// Store closing prices, replacing any already stored for the same symbol and day
+    pub async fn upsert_many(pool: &SqlitePool, closes: &[DailyClose]) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        for close in closes {
+            sqlx::query(
+                r#"
+                INSERT INTO daily_closes (symbol, close_date, close, high, low) VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT(symbol, close_date) DO UPDATE SET
+                    close = excluded.close,
+                    high = excluded.high,
+                    low = excluded.low
+                "#
+            )
+            .bind(&close.symbol)
+            .bind(close.date)
+            .bind(close.close)
+            .bind(close.high)
+            .bind(close.low)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+
+    /// Get closes per symbol between two dates, inclusive
+    pub async fn get_closes(
+        pool: &SqlitePool,
+        symbols: &[String],
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<HashMap<String, BTreeMap<NaiveDate, f64>>, sqlx::Error> {
+        let mut closes: HashMap<String, BTreeMap<NaiveDate, f64>> = HashMap::new();
+
+        for symbol in symbols {
+            let rows = sqlx::query(
+                r#"
+                SELECT close_date, close FROM daily_closes
+                WHERE symbol = ? AND close_date >= ? AND close_date <= ?
+                "#
+            )
+            .bind(symbol)
+            .bind(start_date)
+            .bind(end_date)
+            .fetch_all(pool)
+            .await?;
+
+            closes.insert(
+                symbol.clone(),
+                rows.iter().map(|row| (row.get("close_date"), row.get("close"))).collect(),
+            );
+        }
+
+        Ok(closes)
+    }
+
+    /// Get the last (high, low, close) bars of a symbol before a date, oldest first
+    /// Only days stored with both a high and a low are returned.
+    pub async fn get_bars_before(
+        pool: &SqlitePool,
+        symbol: &str,
+        before: NaiveDate,
+        limit: i64,
+    ) -> Result<Vec<(f64, f64, f64)>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT high, low, close FROM daily_closes
+            WHERE symbol = ? AND close_date < ? AND high IS NOT NULL AND low IS NOT NULL
+            ORDER BY close_date DESC
+            LIMIT ?
+            "#
+        )
+        .bind(symbol)
+        .bind(before)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .rev()
+            .map(|row| (row.get("high"), row.get("low"), row.get("close")))
+            .collect())
+    }
+}
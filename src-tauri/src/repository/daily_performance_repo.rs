@@ -0,0 +1,196 @@
+use chrono::NaiveDate;
+use sqlx::sqlite::{SqliteConnection, SqlitePool, SqliteRow};
+use sqlx::Row;
+use crate::models::{DailyPerformance, Status, TradeFilter};
+use crate::repository::{MetricSnapshotRepository, TradeRepository};
+
+/// Stay well below SQLite's limit on bound parameters
+const CHUNK_SIZE: usize = 500;
+
+/// One day of one account in the daily performance summary
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AccountDay {
+    pub user_id: String,
+    pub account_id: String,
+    pub date: NaiveDate,
+}
+
+pub struct DailyPerformanceRepository;
+
+impl DailyPerformanceRepository {
+    /// Get the days trades can count toward: their entry date and the date of their last exit
+    pub async fn get_trade_days(conn: &mut SqliteConnection, trade_ids: &[String]) -> Result<Vec<AccountDay>, sqlx::Error> {
+        let mut days = Vec::new();
+        for chunk in trade_ids.chunks(CHUNK_SIZE) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let sql = format!(
+                r#"
+                SELECT t.user_id, t.account_id, t.trade_date,
+                       (SELECT MAX(e.execution_date) FROM trade_executions e
+                        WHERE e.trade_id = t.id AND e.execution_type = 'exit') AS exit_date
+                FROM trades t
+                WHERE t.id IN ({placeholders})
+                "#
+            );
+            let mut query = sqlx::query(&sql);
+            for trade_id in chunk {
+                query = query.bind(trade_id);
+            }
+
+            for row in query.fetch_all(&mut *conn).await? {
+                let user_id: String = row.get("user_id");
+                let account_id: String = row.get("account_id");
+                let dates = [row.get::<Option<NaiveDate>, _>("trade_date"), row.get("exit_date")];
+                for date in dates.into_iter().flatten() {
+                    days.push(AccountDay {
+                        user_id: user_id.clone(),
+                        account_id: account_id.clone(),
+                        date,
+                    });
+                }
+            }
+        }
+        Ok(days)
+    }
+
+    /// Get each account's summary rows in a date range, ordered by date
+    pub async fn get_days(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<(String, DailyPerformance)>, sqlx::Error> {
+        let mut sql = String::from(
+            "SELECT * FROM daily_performance WHERE user_id = ? AND date >= ? AND date <= ?",
+        );
+        if account_id.is_some() {
+            sql.push_str(" AND account_id = ?");
+        }
+        sql.push_str(" ORDER BY date ASC");
+
+        let mut query = sqlx::query(&sql).bind(user_id).bind(start_date).bind(end_date);
+        if let Some(account_id) = account_id {
+            query = query.bind(account_id);
+        }
+
+        let rows = query.fetch_all(pool).await?;
+        Ok(rows.iter().map(|r| (r.get("account_id"), Self::row_to_day(r))).collect())
+    }
+
+    /// Whether a user has any summary rows
+    pub async fn has_days(conn: &mut SqliteConnection, user_id: &str) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM daily_performance WHERE user_id = ?)")
+            .bind(user_id)
+            .fetch_one(conn)
+            .await
+    }
+
+    /// Replace an account's rows for some days with the sums of the closed trades counting toward them
+    /// Metric snapshots from the earliest day on are built from the old rows, so they are cleared to
+    /// be taken again on the next run.
+    pub async fn refresh_days(
+        conn: &mut SqliteConnection,
+        user_id: &str,
+        account_id: &str,
+        dates: &[NaiveDate],
+    ) -> Result<(), sqlx::Error> {
+        for chunk in dates.chunks(CHUNK_SIZE) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let sql = format!("DELETE FROM daily_performance WHERE account_id = ? AND date IN ({placeholders})");
+            let mut query = sqlx::query(&sql).bind(account_id);
+            for date in chunk {
+                query = query.bind(date);
+            }
+            query.execute(&mut *conn).await?;
+
+            Self::insert_days(&mut *conn, user_id, Some(account_id), Some(chunk)).await?;
+        }
+        MetricSnapshotRepository::delete_from_in(conn, account_id, dates.iter().min().copied()).await
+    }
+
+    /// Replace all of a user's rows with the sums of their closed trades, clearing their metric snapshots
+    /// Returns the number of account days written.
+    pub async fn rebuild(conn: &mut SqliteConnection, user_id: &str) -> Result<u64, sqlx::Error> {
+        sqlx::query("DELETE FROM daily_performance WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
+        let written = Self::insert_days(&mut *conn, user_id, None, None).await?;
+        MetricSnapshotRepository::delete_all(conn, user_id).await?;
+        Ok(written)
+    }
+
+    /// Rebuild every user's rows, after a setting changes how trades count
+    pub async fn rebuild_all(conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
+        let user_ids: Vec<String> = sqlx::query_scalar("SELECT id FROM users").fetch_all(&mut *conn).await?;
+        for user_id in user_ids {
+            Self::rebuild(&mut *conn, &user_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Insert a row per account day from the closed trades counting toward it, in one statement
+    /// The sums mirror calculate_daily_metrics over the PnL and result that trade_repo derives in SQL.
+    async fn insert_days(
+        conn: &mut SqliteConnection,
+        user_id: &str,
+        account_id: Option<&str>,
+        dates: Option<&[NaiveDate]>,
+    ) -> Result<u64, sqlx::Error> {
+        let filter = TradeFilter {
+            account_id: account_id.map(str::to_string),
+            status: Some(Status::Closed),
+            ..Default::default()
+        };
+        let mut sql = format!(
+            r#"
+            INSERT INTO daily_performance (
+                user_id, account_id, date, realized_net_pnl, gross_pnl, total_fees, total_volume,
+                largest_win, largest_loss, trade_count, win_count, loss_count, breakeven_count
+            )
+            SELECT user_id, account_id, attributed_date, TOTAL(net_pnl), TOTAL(gross_pnl), TOTAL(fees),
+                   TOTAL(quantity), MAX(CASE WHEN net_pnl > 0 THEN net_pnl END),
+                   MIN(CASE WHEN net_pnl < 0 THEN net_pnl END), COUNT(*),
+                   SUM(result_pnl > 0), SUM(result_pnl < 0), SUM(result_pnl = 0)
+            FROM ({}) closed
+            WHERE net_pnl IS NOT NULL
+            "#,
+            TradeRepository::filtered_trades_query(&filter)
+        );
+        if let Some(dates) = dates {
+            let placeholders = vec!["?"; dates.len()].join(", ");
+            sql.push_str(&format!(" AND attributed_date IN ({placeholders})"));
+        }
+        sql.push_str(" GROUP BY account_id, attributed_date");
+
+        let mut query = TradeRepository::bind_filter(sqlx::query(&sql), user_id, &filter);
+        for date in dates.unwrap_or_default() {
+            query = query.bind(date);
+        }
+        Ok(query.execute(conn).await?.rows_affected())
+    }
+
+    fn row_to_day(row: &SqliteRow) -> DailyPerformance {
+        let trade_count: i32 = row.get("trade_count");
+        let breakeven_count: i32 = row.get("breakeven_count");
+        let realized_net_pnl: f64 = row.get("realized_net_pnl");
+
+        DailyPerformance {
+            date: row.get("date"),
+            realized_net_pnl,
+            gross_pnl: row.get("gross_pnl"),
+            total_fees: row.get("total_fees"),
+            total_volume: row.get("total_volume"),
+            largest_win: row.get("largest_win"),
+            largest_loss: row.get("largest_loss"),
+            trade_count,
+            win_count: row.get("win_count"),
+            loss_count: row.get("loss_count"),
+            breakeven_count,
+            scratch_rate: breakeven_count as f64 / trade_count as f64,
+            avg_trade_pnl: realized_net_pnl / trade_count as f64,
+            unrealized_pnl_change: None,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+use chrono::{NaiveDate, Utc};
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use crate::models::{DayConditionType, DayConditions};
+
+pub struct DayConditionRepository;
+
+impl DayConditionRepository {
+    /// Replace the conditions tagged on a date; an empty list clears the date
+    pub async fn set_conditions(
+        pool: &SqlitePool,
+        user_id: &str,
+        date: NaiveDate,
+        conditions: &[DayConditionType],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("DELETE FROM day_conditions WHERE user_id = ? AND condition_date = ?")
+            .bind(user_id)
+            .bind(date)
+            .execute(&mut *tx)
+            .await?;
+
+        let now = Utc::now();
+        for condition in conditions {
+            sqlx::query(
+                "INSERT OR IGNORE INTO day_conditions (user_id, condition_date, condition, created_at) VALUES (?, ?, ?, ?)"
+            )
+            .bind(user_id)
+            .bind(date)
+            .bind(condition.as_str())
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+
+    /// Get tagged days with optional filters on the date
+    pub async fn get_conditions(
+        pool: &SqlitePool,
+        user_id: &str,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<DayConditions>, sqlx::Error> {
+        let mut query = String::from("SELECT condition_date, condition FROM day_conditions WHERE user_id = ?");
+
+        if start_date.is_some() {
+            query.push_str(" AND condition_date >= ?");
+        }
+        if end_date.is_some() {
+            query.push_str(" AND condition_date <= ?");
+        }
+
+        let mut q = sqlx::query(&query).bind(user_id);
+
+        if let Some(start) = start_date {
+            q = q.bind(start);
+        }
+        if let Some(end) = end_date {
+            q = q.bind(end);
+        }
+
+        let rows = q.fetch_all(pool).await?;
+
+        let mut by_date: BTreeMap<NaiveDate, Vec<DayConditionType>> = BTreeMap::new();
+        for row in &rows {
+            if let Some(condition) = DayConditionType::from_str(row.get::<&str, _>("condition")) {
+                by_date.entry(row.get("condition_date")).or_default().push(condition);
+            }
+        }
+
+        Ok(by_date
+            .into_iter()
+            .map(|(date, mut conditions)| {
+                conditions.sort();
+                DayConditions { date, conditions }
+            })
+            .collect())
+    }
+}
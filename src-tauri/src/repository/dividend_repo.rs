@@ -0,0 +1,157 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
+use sqlx::Row;
+use crate::models::{CreateDividendInput, Dividend};
+
+pub struct DividendRepository;
+
+impl DividendRepository {
+    /// Insert a dividend with the amount already signed (received > 0, paid < 0)
+    pub async fn insert(
+        conn: &mut SqliteConnection,
+        user_id: &str,
+        instrument_id: &str,
+        input: &CreateDividendInput,
+        amount: f64,
+    ) -> Result<Dividend, sqlx::Error> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO dividends (
+                id, user_id, account_id, instrument_id, trade_id,
+                ex_date, pay_date, amount, notes, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(user_id)
+        .bind(&input.account_id)
+        .bind(instrument_id)
+        .bind(&input.trade_id)
+        .bind(input.ex_date)
+        .bind(input.pay_date)
+        .bind(amount)
+        .bind(&input.notes)
+        .bind(now)
+        .execute(&mut *conn)
+        .await?;
+
+        Self::get_by_id_in(conn, user_id, &id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Get one of a user's dividends by ID
+    pub async fn get_by_id(pool: &SqlitePool, user_id: &str, id: &str) -> Result<Option<Dividend>, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        Self::get_by_id_in(&mut conn, user_id, id).await
+    }
+
+    /// Get one of a user's dividends on a connection, e.g. within a caller's transaction
+    pub async fn get_by_id_in(conn: &mut SqliteConnection, user_id: &str, id: &str) -> Result<Option<Dividend>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT d.*, i.symbol
+            FROM dividends d
+            JOIN instruments i ON d.instrument_id = i.id
+            WHERE d.id = ? AND d.user_id = ?
+            "#
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(row.map(|r| Self::row_to_dividend(&r)))
+    }
+
+    /// Get dividends with optional filters on the ex-date
+    pub async fn get_dividends(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<Dividend>, sqlx::Error> {
+        let mut query = String::from(
+            r#"
+            SELECT d.*, i.symbol
+            FROM dividends d
+            JOIN instruments i ON d.instrument_id = i.id
+            WHERE d.user_id = ?
+            "#
+        );
+
+        if account_id.is_some() {
+            query.push_str(" AND d.account_id = ?");
+        }
+        if start_date.is_some() {
+            query.push_str(" AND d.ex_date >= ?");
+        }
+        if end_date.is_some() {
+            query.push_str(" AND d.ex_date <= ?");
+        }
+
+        query.push_str(" ORDER BY d.ex_date DESC, d.created_at DESC");
+
+        let mut q = sqlx::query(&query).bind(user_id);
+
+        if let Some(acc) = account_id {
+            q = q.bind(acc);
+        }
+        if let Some(start) = start_date {
+            q = q.bind(start);
+        }
+        if let Some(end) = end_date {
+            q = q.bind(end);
+        }
+
+        let rows = q.fetch_all(pool).await?;
+        Ok(rows.iter().map(Self::row_to_dividend).collect())
+    }
+
+    /// Get a user's dividends linked to a trade
+    pub async fn get_for_trade(pool: &SqlitePool, user_id: &str, trade_id: &str) -> Result<Vec<Dividend>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT d.*, i.symbol
+            FROM dividends d
+            JOIN instruments i ON d.instrument_id = i.id
+            WHERE d.trade_id = ? AND d.user_id = ?
+            ORDER BY d.ex_date ASC
+            "#
+        )
+        .bind(trade_id)
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_dividend).collect())
+    }
+
+    /// Delete a dividend
+    /// Returns the trade it was linked to, if any, or None if no dividend with the given id exists for the user
+    pub async fn delete(conn: &mut SqliteConnection, user_id: &str, id: &str) -> Result<Option<Option<String>>, sqlx::Error> {
+        sqlx::query_scalar("DELETE FROM dividends WHERE id = ? AND user_id = ? RETURNING trade_id")
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    fn row_to_dividend(row: &sqlx::sqlite::SqliteRow) -> Dividend {
+        Dividend {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            account_id: row.get("account_id"),
+            instrument_id: row.get("instrument_id"),
+            symbol: row.get("symbol"),
+            trade_id: row.get("trade_id"),
+            ex_date: row.get("ex_date"),
+            pay_date: row.get("pay_date"),
+            amount: row.get("amount"),
+            notes: row.get("notes"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
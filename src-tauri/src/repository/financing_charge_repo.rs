@@ -0,0 +1,147 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use crate::models::{CreateFinancingChargeInput, FinancingCharge, FinancingChargeSource, FinancingChargeType};
+
+pub struct FinancingChargeRepository;
+
+impl FinancingChargeRepository {
+    /// Insert a financing charge, stored against the first day of its month
+    pub async fn insert(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: &CreateFinancingChargeInput,
+        source: FinancingChargeSource,
+    ) -> Result<FinancingCharge, sqlx::Error> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO financing_charges (
+                id, user_id, account_id, charge_month, charge_type, amount, source, notes, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(user_id)
+        .bind(&input.account_id)
+        .bind(month_start(input.charge_month))
+        .bind(input.charge_type.as_str())
+        .bind(input.amount)
+        .bind(source.as_str())
+        .bind(&input.notes)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        let row = sqlx::query("SELECT * FROM financing_charges WHERE id = ?")
+            .bind(&id)
+            .fetch_one(pool)
+            .await?;
+        Ok(Self::row_to_charge(&row))
+    }
+
+    /// Insert an imported charge, replacing an earlier import for the same account, month and type
+    /// Returns the charge and whether an earlier import was replaced
+    pub async fn upsert_imported(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: &CreateFinancingChargeInput,
+    ) -> Result<(FinancingCharge, bool), sqlx::Error> {
+        let deleted = sqlx::query(
+            r#"
+            DELETE FROM financing_charges
+            WHERE user_id = ? AND account_id = ? AND charge_month = ? AND charge_type = ? AND source = 'import'
+            "#
+        )
+        .bind(user_id)
+        .bind(&input.account_id)
+        .bind(month_start(input.charge_month))
+        .bind(input.charge_type.as_str())
+        .execute(pool)
+        .await?;
+
+        let charge = Self::insert(pool, user_id, input, FinancingChargeSource::Import).await?;
+        Ok((charge, deleted.rows_affected() > 0))
+    }
+
+    /// Get a user's financing charges with optional filters
+    /// Date filters match on the charge month, so a start date mid-month includes that month
+    pub async fn get_charges(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<FinancingCharge>, sqlx::Error> {
+        let mut query = String::from("SELECT * FROM financing_charges WHERE user_id = ?");
+
+        if account_id.is_some() {
+            query.push_str(" AND account_id = ?");
+        }
+        if start_date.is_some() {
+            query.push_str(" AND charge_month >= ?");
+        }
+        if end_date.is_some() {
+            query.push_str(" AND charge_month <= ?");
+        }
+
+        query.push_str(" ORDER BY charge_month ASC, account_id ASC, charge_type ASC");
+
+        let mut q = sqlx::query(&query).bind(user_id);
+
+        if let Some(acc_id) = account_id {
+            q = q.bind(acc_id);
+        }
+        if let Some(start) = start_date {
+            q = q.bind(month_start(start));
+        }
+        if let Some(end) = end_date {
+            q = q.bind(end);
+        }
+
+        let rows = q.fetch_all(pool).await?;
+        Ok(rows.iter().map(Self::row_to_charge).collect())
+    }
+
+    /// Get a financing charge by id
+    pub async fn get_by_id(pool: &SqlitePool, user_id: &str, id: &str) -> Result<Option<FinancingCharge>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM financing_charges WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(row.as_ref().map(Self::row_to_charge))
+    }
+
+    /// Delete a financing charge
+    /// Returns false if the user has no charge with the given id
+    pub async fn delete(pool: &SqlitePool, user_id: &str, id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM financing_charges WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn row_to_charge(row: &sqlx::sqlite::SqliteRow) -> FinancingCharge {
+        FinancingCharge {
+            id: row.get("id"),
+            account_id: row.get("account_id"),
+            charge_month: row.get("charge_month"),
+            charge_type: FinancingChargeType::from_str(row.get::<&str, _>("charge_type"))
+                .unwrap_or(FinancingChargeType::Financing),
+            amount: row.get("amount"),
+            source: FinancingChargeSource::from_str(row.get::<&str, _>("source"))
+                .unwrap_or(FinancingChargeSource::Manual),
+            notes: row.get("notes"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
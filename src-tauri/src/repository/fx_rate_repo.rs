@@ -0,0 +1,96 @@
+use chrono::NaiveDate;
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use crate::models::{FxRate, FxRateSource};
+
+pub struct FxRateRepository;
+
+impl FxRateRepository {
+    /// Store FX rates, replacing any already stored for the same pair and day
+    /// Fetched rates never replace a manual rate; manual rates replace anything.
+    /// Returns the number of rates stored.
+    pub async fn upsert_many(pool: &SqlitePool, rates: &[FxRate]) -> Result<u64, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        let mut stored = 0;
+
+        for rate in rates {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO fx_rates (base_currency, quote_currency, rate_date, rate, source) VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT(base_currency, quote_currency, rate_date) DO UPDATE SET
+                    rate = excluded.rate,
+                    source = excluded.source
+                WHERE excluded.source = 'manual' OR fx_rates.source != 'manual'
+                "#
+            )
+            .bind(&rate.base_currency)
+            .bind(&rate.quote_currency)
+            .bind(rate.date)
+            .bind(rate.rate)
+            .bind(rate.source.as_str())
+            .execute(&mut *tx)
+            .await?;
+            stored += result.rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(stored)
+    }
+
+    /// Get all stored FX rates, ordered by pair and date
+    pub async fn get_rates(pool: &SqlitePool) -> Result<Vec<FxRate>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM fx_rates ORDER BY base_currency ASC, quote_currency ASC, rate_date ASC"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| FxRate {
+                base_currency: row.get("base_currency"),
+                quote_currency: row.get("quote_currency"),
+                date: row.get("rate_date"),
+                rate: row.get("rate"),
+                source: FxRateSource::from_str(row.get::<&str, _>("source")).unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// Date of the latest fetched rate of a pair
+    pub async fn get_last_fetched_date(
+        pool: &SqlitePool,
+        base_currency: &str,
+        quote_currency: &str,
+    ) -> Result<Option<NaiveDate>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT MAX(rate_date) FROM fx_rates
+            WHERE base_currency = ? AND quote_currency = ? AND source = 'ecb'
+            "#
+        )
+        .bind(base_currency)
+        .bind(quote_currency)
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Delete a rate, e.g. to drop a manual override
+    /// Returns false if no rate is stored for the pair on that day
+    pub async fn delete(
+        pool: &SqlitePool,
+        base_currency: &str,
+        quote_currency: &str,
+        date: NaiveDate,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM fx_rates WHERE base_currency = ? AND quote_currency = ? AND rate_date = ?"
+        )
+        .bind(base_currency)
+        .bind(quote_currency)
+        .bind(date)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,133 @@
+use chrono::{Duration, NaiveDate};
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use crate::models::{GoalScorecard, OutcomeScore, ProcessScore};
+
+pub struct GoalScorecardRepository;
+
+impl GoalScorecardRepository {
+    /// Store a week's scorecard, replacing an earlier evaluation of the same week
+    pub async fn upsert(
+        pool: &SqlitePool,
+        user_id: &str,
+        scorecard: &GoalScorecard,
+    ) -> Result<(), sqlx::Error> {
+        let process = &scorecard.process;
+        let outcome = &scorecard.outcome;
+
+        sqlx::query(
+            r#"
+            INSERT INTO goal_scorecards (
+                user_id, week_start, max_trades_per_day, days_traded, days_over_trade_limit,
+                journal_every_day, days_journaled, max_risk_per_trade, trades_over_risk,
+                trades_without_stop, goals_met, goals_evaluated, trade_count, net_pnl,
+                win_rate, evaluated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(user_id, week_start) DO UPDATE SET
+                max_trades_per_day = excluded.max_trades_per_day,
+                days_traded = excluded.days_traded,
+                days_over_trade_limit = excluded.days_over_trade_limit,
+                journal_every_day = excluded.journal_every_day,
+                days_journaled = excluded.days_journaled,
+                max_risk_per_trade = excluded.max_risk_per_trade,
+                trades_over_risk = excluded.trades_over_risk,
+                trades_without_stop = excluded.trades_without_stop,
+                goals_met = excluded.goals_met,
+                goals_evaluated = excluded.goals_evaluated,
+                trade_count = excluded.trade_count,
+                net_pnl = excluded.net_pnl,
+                win_rate = excluded.win_rate,
+                evaluated_at = excluded.evaluated_at
+            "#
+        )
+        .bind(user_id)
+        .bind(scorecard.week_start)
+        .bind(process.max_trades_per_day)
+        .bind(process.days_traded)
+        .bind(process.days_over_trade_limit)
+        .bind(process.journal_every_day)
+        .bind(process.days_journaled)
+        .bind(process.max_risk_per_trade)
+        .bind(process.trades_over_risk)
+        .bind(process.trades_without_stop)
+        .bind(process.goals_met)
+        .bind(process.goals_evaluated)
+        .bind(outcome.trade_count)
+        .bind(outcome.net_pnl)
+        .bind(outcome.win_rate)
+        .bind(scorecard.evaluated_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get stored scorecards by week, oldest first, with optional filters on the week start
+    pub async fn get_scorecards(
+        pool: &SqlitePool,
+        user_id: &str,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<GoalScorecard>, sqlx::Error> {
+        let mut query = String::from("SELECT * FROM goal_scorecards WHERE user_id = ?");
+
+        if start_date.is_some() {
+            query.push_str(" AND week_start >= ?");
+        }
+        if end_date.is_some() {
+            query.push_str(" AND week_start <= ?");
+        }
+
+        query.push_str(" ORDER BY week_start ASC");
+
+        let mut q = sqlx::query(&query).bind(user_id);
+
+        if let Some(start) = start_date {
+            q = q.bind(start);
+        }
+        if let Some(end) = end_date {
+            q = q.bind(end);
+        }
+
+        let rows = q.fetch_all(pool).await?;
+        Ok(rows.iter().map(Self::row_to_scorecard).collect())
+    }
+
+    /// Start of the most recent stored week
+    pub async fn get_latest_week_start(
+        pool: &SqlitePool,
+        user_id: &str,
+    ) -> Result<Option<NaiveDate>, sqlx::Error> {
+        sqlx::query_scalar("SELECT MAX(week_start) FROM goal_scorecards WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await
+    }
+
+    fn row_to_scorecard(row: &sqlx::sqlite::SqliteRow) -> GoalScorecard {
+        let week_start: NaiveDate = row.get("week_start");
+
+        GoalScorecard {
+            week_start,
+            week_end: week_start + Duration::days(6),
+            process: ProcessScore {
+                max_trades_per_day: row.get("max_trades_per_day"),
+                days_traded: row.get("days_traded"),
+                days_over_trade_limit: row.get("days_over_trade_limit"),
+                journal_every_day: row.get("journal_every_day"),
+                days_journaled: row.get("days_journaled"),
+                max_risk_per_trade: row.get("max_risk_per_trade"),
+                trades_over_risk: row.get("trades_over_risk"),
+                trades_without_stop: row.get("trades_without_stop"),
+                goals_met: row.get("goals_met"),
+                goals_evaluated: row.get("goals_evaluated"),
+            },
+            outcome: OutcomeScore {
+                trade_count: row.get("trade_count"),
+                net_pnl: row.get("net_pnl"),
+                win_rate: row.get("win_rate"),
+            },
+            evaluated_at: row.get("evaluated_at"),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::sqlite::{SqliteConnection, SqlitePool, SqliteRow};
+use sqlx::Row;
+use crate::models::{ImportBatch, MAX_BATCH_ERRORS};
+use crate::repository::TradeRepository;
+
+pub struct ImportBatchRepository;
+
+impl ImportBatchRepository {
+    /// Start a batch for an import into an account, before its trades are created
+    pub async fn insert(
+        conn: &mut SqliteConnection,
+        user_id: &str,
+        account_id: &str,
+        file_name: Option<&str>,
+    ) -> Result<ImportBatch, sqlx::Error> {
+        let batch = ImportBatch {
+            id: uuid::Uuid::new_v4().to_string(),
+            account_id: account_id.to_string(),
+            file_name: file_name.map(str::to_string),
+            trade_count: 0,
+            execution_count: 0,
+            skipped_duplicates: 0,
+            error_count: 0,
+            errors: Vec::new(),
+            created_at: Utc::now(),
+            undone_at: None,
+        };
+
+        sqlx::query("INSERT INTO import_batches (id, user_id, account_id, file_name, created_at) VALUES (?, ?, ?, ?, ?)")
+            .bind(&batch.id)
+            .bind(user_id)
+            .bind(account_id)
+            .bind(&batch.file_name)
+            .bind(batch.created_at)
+            .execute(conn)
+            .await?;
+        Ok(batch)
+    }
+
+    /// Record what a batch created and how many duplicates it skipped
+    pub async fn set_counts(
+        conn: &mut SqliteConnection,
+        id: &str,
+        trade_count: i32,
+        execution_count: i32,
+        skipped_duplicates: i32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE import_batches SET trade_count = ?, execution_count = ?, skipped_duplicates = ? WHERE id = ?")
+            .bind(trade_count)
+            .bind(execution_count)
+            .bind(skipped_duplicates)
+            .bind(id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Record a batch's errors, keeping the first MAX_BATCH_ERRORS messages
+    pub async fn set_errors(pool: &SqlitePool, id: &str, errors: &[String]) -> Result<(), sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        Self::set_errors_in(&mut conn, id, errors).await
+    }
+
+    /// Record a batch's errors on a connection, e.g. within the import's transaction
+    pub async fn set_errors_in(conn: &mut SqliteConnection, id: &str, errors: &[String]) -> Result<(), sqlx::Error> {
+        let kept = &errors[..errors.len().min(MAX_BATCH_ERRORS)];
+        let json = serde_json::to_string(kept).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+        sqlx::query("UPDATE import_batches SET error_count = ?, errors = ? WHERE id = ?")
+            .bind(errors.len() as i32)
+            .bind(json)
+            .bind(id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Get a user's batch
+    pub async fn get_by_id(pool: &SqlitePool, user_id: &str, id: &str) -> Result<Option<ImportBatch>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM import_batches WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(row.as_ref().map(Self::row_to_batch))
+    }
+
+    /// Get a user's batches, most recent first
+    pub async fn get_batches(pool: &SqlitePool, user_id: &str) -> Result<Vec<ImportBatch>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM import_batches WHERE user_id = ? ORDER BY created_at DESC, rowid DESC")
+            .bind(user_id)
+            .fetch_all(pool)
+            .await?;
+        Ok(rows.iter().map(Self::row_to_batch).collect())
+    }
+
+    /// Get the IDs, trade dates and first exit dates of the trades a batch created that are still in the journal
+    pub async fn get_trades(pool: &SqlitePool, id: &str) -> Result<Vec<(String, NaiveDate, Option<NaiveDate>)>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT t.id, t.trade_date,
+                   (SELECT MIN(e.execution_date) FROM trade_executions e
+                    WHERE e.trade_id = t.id AND e.execution_type = 'exit') AS first_exit_date
+            FROM trades t
+            WHERE t.import_batch_id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.iter().map(|r| (r.get("id"), r.get("trade_date"), r.get("first_exit_date"))).collect())
+    }
+
+    /// Delete a batch's trades with their dependent rows and mark it undone
+    /// Run within a transaction so the batch is undone all or nothing. Returns the number of trades deleted.
+    pub async fn undo(conn: &mut SqliteConnection, id: &str, undone_at: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let trade_ids: Vec<String> = sqlx::query_scalar("SELECT id FROM trades WHERE import_batch_id = ?")
+            .bind(id)
+            .fetch_all(&mut *conn)
+            .await?;
+        let mut deleted = 0;
+        for trade_id in &trade_ids {
+            deleted += TradeRepository::delete_in(&mut *conn, trade_id).await?;
+        }
+
+        sqlx::query("UPDATE import_batches SET undone_at = ? WHERE id = ?")
+            .bind(undone_at)
+            .bind(id)
+            .execute(conn)
+            .await?;
+        Ok(deleted)
+    }
+
+    fn row_to_batch(row: &SqliteRow) -> ImportBatch {
+        ImportBatch {
+            id: row.get("id"),
+            account_id: row.get("account_id"),
+            file_name: row.get("file_name"),
+            trade_count: row.get("trade_count"),
+            execution_count: row.get("execution_count"),
+            skipped_duplicates: row.get("skipped_duplicates"),
+            error_count: row.get("error_count"),
+            errors: row
+                .get::<Option<String>, _>("errors")
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            created_at: row.get("created_at"),
+            undone_at: row.get("undone_at"),
+        }
+    }
+}
@@ -1,7 +1,9 @@
+use std::collections::HashMap;
 use chrono::Utc;
-use sqlx::sqlite::SqlitePool;
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
 use sqlx::Row;
 use crate::models::{AssetClass, Instrument};
+use crate::parsers::{parse_option_symbol, OptionType};
 
 pub struct InstrumentRepository;
 
@@ -19,19 +21,29 @@ impl InstrumentRepository {
         pool: &SqlitePool,
         symbol: &str,
         asset_class: Option<AssetClass>,
+    ) -> Result<Instrument, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        Self::get_or_create_with_asset_class_in(&mut conn, symbol, asset_class).await
+    }
+
+    /// Get or create an instrument on a connection, e.g. within a caller's transaction
+    pub async fn get_or_create_with_asset_class_in(
+        conn: &mut SqliteConnection,
+        symbol: &str,
+        asset_class: Option<AssetClass>,
     ) -> Result<Instrument, sqlx::Error> {
         // Try to find existing instrument
-        if let Some(existing) = Self::get_by_symbol(pool, symbol).await? {
+        if let Some(existing) = Self::get_by_symbol_in(&mut *conn, symbol).await? {
             if let Some(requested_asset_class) = asset_class {
                 let requested = requested_asset_class.as_str();
                 if existing.asset_class != requested {
                     sqlx::query("UPDATE instruments SET asset_class = ? WHERE id = ?")
                         .bind(requested)
                         .bind(&existing.id)
-                        .execute(pool)
+                        .execute(&mut *conn)
                         .await?;
 
-                    return Self::get_by_id(pool, &existing.id)
+                    return Self::get_by_id_in(&mut *conn, &existing.id)
                         .await?
                         .ok_or(sqlx::Error::RowNotFound);
                 }
@@ -45,19 +57,57 @@ impl InstrumentRepository {
         let id = uuid::Uuid::new_v4().to_string();
         let now = Utc::now();
         let symbol_upper = symbol.to_uppercase();
-        let asset_class_str = asset_class.unwrap_or(AssetClass::Stock).as_str();
+        let asset_class = asset_class.unwrap_or(AssetClass::Stock);
+
+        // Options get their contract details when the symbol is in OCC format
+        let option_details = match asset_class {
+            AssetClass::Option => parse_option_symbol(&symbol_upper).ok(),
+            _ => None,
+        };
+        let underlying_symbol = match asset_class {
+            AssetClass::Option => option_details.as_ref().map(|d| d.underlying.clone()),
+            AssetClass::Stock | AssetClass::Future | AssetClass::Forex | AssetClass::Crypto => Some(symbol_upper.clone()),
+        };
 
         sqlx::query(
-            "INSERT INTO instruments (id, symbol, asset_class, created_at) VALUES (?, ?, ?, ?)"
+            r#"
+            INSERT INTO instruments (id, symbol, asset_class, underlying_symbol, option_type, strike_price, expiration_date, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
         )
         .bind(&id)
         .bind(&symbol_upper)
-        .bind(asset_class_str)
+        .bind(asset_class.as_str())
+        .bind(underlying_symbol)
+        .bind(option_details.as_ref().map(|d| match d.option_type {
+            OptionType::Call => "call",
+            OptionType::Put => "put",
+        }))
+        .bind(option_details.as_ref().map(|d| d.strike_price))
+        .bind(option_details.as_ref().map(|d| d.expiration_date))
         .bind(now)
-        .execute(pool)
+        .execute(&mut *conn)
         .await?;
 
-        Self::get_by_id(pool, &id).await?.ok_or(sqlx::Error::RowNotFound)
+        Self::get_by_id_in(conn, &id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    async fn get_by_symbol_in(conn: &mut SqliteConnection, symbol: &str) -> Result<Option<Instrument>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM instruments WHERE symbol = ?")
+            .bind(symbol.to_uppercase())
+            .fetch_optional(conn)
+            .await?;
+
+        Ok(row.map(|r| Self::row_to_instrument(&r)))
+    }
+
+    async fn get_by_id_in(conn: &mut SqliteConnection, id: &str) -> Result<Option<Instrument>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM instruments WHERE id = ?")
+            .bind(id)
+            .fetch_optional(conn)
+            .await?;
+
+        Ok(row.map(|r| Self::row_to_instrument(&r)))
     }
 
     /// Get an instrument by symbol
@@ -80,12 +130,61 @@ impl InstrumentRepository {
         Ok(row.map(|r| Self::row_to_instrument(&r)))
     }
 
+    /// Set or clear the tick size of an instrument
+    pub async fn set_tick_size(
+        pool: &SqlitePool,
+        id: &str,
+        tick_size: Option<f64>,
+    ) -> Result<Option<Instrument>, sqlx::Error> {
+        sqlx::query("UPDATE instruments SET tick_size = ? WHERE id = ?")
+            .bind(tick_size)
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Set or clear the margin requirement of an instrument
+    pub async fn set_margin_requirement(
+        pool: &SqlitePool,
+        id: &str,
+        margin_requirement: Option<f64>,
+    ) -> Result<Option<Instrument>, sqlx::Error> {
+        sqlx::query("UPDATE instruments SET margin_requirement = ? WHERE id = ?")
+            .bind(margin_requirement)
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Margin requirement by instrument ID, for instruments that have one
+    pub async fn get_margin_requirements(pool: &SqlitePool) -> Result<HashMap<String, f64>, sqlx::Error> {
+        let rows = sqlx::query("SELECT id, margin_requirement FROM instruments WHERE margin_requirement IS NOT NULL")
+            .fetch_all(pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| (row.get("id"), row.get("margin_requirement")))
+            .collect())
+    }
+
     fn row_to_instrument(row: &sqlx::sqlite::SqliteRow) -> Instrument {
         Instrument {
             id: row.get("id"),
             symbol: row.get("symbol"),
             asset_class: row.get("asset_class"),
             exchange: row.get("exchange"),
+            tick_size: row.get("tick_size"),
+            margin_requirement: row.get("margin_requirement"),
+            underlying_symbol: row.get("underlying_symbol"),
+            option_type: row.get("option_type"),
+            strike_price: row.get("strike_price"),
+            expiration_date: row.get("expiration_date"),
+            multiplier: row.get("multiplier"),
             created_at: row.get("created_at"),
         }
     }
@@ -94,6 +193,7 @@ impl InstrumentRepository {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::NaiveDate;
     use crate::test_utils::create_test_db;
 
     #[tokio::test]
@@ -196,6 +296,10 @@ mod tests {
 
         assert_eq!(instrument.symbol, "AAPL240315C00150000");
         assert_eq!(instrument.asset_class, "option");
+        assert_eq!(instrument.underlying_symbol, Some("AAPL".to_string()));
+        assert_eq!(instrument.option_type, Some("call".to_string()));
+        assert_eq!(instrument.strike_price, Some(150.0));
+        assert_eq!(instrument.expiration_date, NaiveDate::from_ymd_opt(2024, 3, 15));
     }
 
     #[tokio::test]
@@ -212,6 +316,8 @@ mod tests {
 
         assert_eq!(instrument.symbol, "NVDA");
         assert_eq!(instrument.asset_class, "stock");
+        assert_eq!(instrument.underlying_symbol, Some("NVDA".to_string()));
+        assert_eq!(instrument.option_type, None);
     }
 
     #[tokio::test]
@@ -254,4 +360,26 @@ mod tests {
         assert_eq!(stock.id, option.id);
         assert_eq!(option.asset_class, "option");
     }
+
+    #[tokio::test]
+    async fn test_set_tick_size() {
+        let pool = create_test_db().await;
+
+        let created = InstrumentRepository::get_or_create(&pool, "ES")
+            .await
+            .expect("Failed to create instrument");
+        assert_eq!(created.tick_size, None);
+
+        let updated = InstrumentRepository::set_tick_size(&pool, &created.id, Some(0.25))
+            .await
+            .expect("Query failed")
+            .expect("Instrument not found");
+        assert_eq!(updated.tick_size, Some(0.25));
+
+        let cleared = InstrumentRepository::set_tick_size(&pool, &created.id, None)
+            .await
+            .expect("Query failed")
+            .expect("Instrument not found");
+        assert_eq!(cleared.tick_size, None);
+    }
 }
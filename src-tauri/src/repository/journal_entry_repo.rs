@@ -0,0 +1,116 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use crate::models::{JournalEntry, JournalEntryStatus};
+
+pub struct JournalEntryRepository;
+
+impl JournalEntryRepository {
+    /// Store a draft for the day unless the day already has an entry, which is left untouched
+    pub async fn insert_draft(
+        pool: &SqlitePool,
+        user_id: &str,
+        entry_date: NaiveDate,
+        content: &str,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO journal_entries (id, user_id, entry_date, content, status, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(user_id, entry_date) DO NOTHING
+            "#
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(user_id)
+        .bind(entry_date)
+        .bind(content)
+        .bind(JournalEntryStatus::Draft.as_str())
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Replace the content of a day's entry and mark it reviewed
+    /// Returns false when the day has no entry.
+    pub async fn update_content(
+        pool: &SqlitePool,
+        user_id: &str,
+        entry_date: NaiveDate,
+        content: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE journal_entries SET content = ?, status = ?, updated_at = ?
+            WHERE user_id = ? AND entry_date = ?
+            "#
+        )
+        .bind(content)
+        .bind(JournalEntryStatus::Reviewed.as_str())
+        .bind(Utc::now())
+        .bind(user_id)
+        .bind(entry_date)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_by_date(
+        pool: &SqlitePool,
+        user_id: &str,
+        entry_date: NaiveDate,
+    ) -> Result<Option<JournalEntry>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM journal_entries WHERE user_id = ? AND entry_date = ?")
+            .bind(user_id)
+            .bind(entry_date)
+            .fetch_optional(pool)
+            .await?;
+        Ok(row.as_ref().map(Self::row_to_entry))
+    }
+
+    /// Get a user's journal entries with optional date filters
+    pub async fn get_entries(
+        pool: &SqlitePool,
+        user_id: &str,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<JournalEntry>, sqlx::Error> {
+        let mut query = String::from("SELECT * FROM journal_entries WHERE user_id = ?");
+
+        if start_date.is_some() {
+            query.push_str(" AND entry_date >= ?");
+        }
+        if end_date.is_some() {
+            query.push_str(" AND entry_date <= ?");
+        }
+
+        query.push_str(" ORDER BY entry_date ASC");
+
+        let mut q = sqlx::query(&query).bind(user_id);
+
+        if let Some(start) = start_date {
+            q = q.bind(start);
+        }
+        if let Some(end) = end_date {
+            q = q.bind(end);
+        }
+
+        let rows = q.fetch_all(pool).await?;
+        Ok(rows.iter().map(Self::row_to_entry).collect())
+    }
+
+    fn row_to_entry(row: &sqlx::sqlite::SqliteRow) -> JournalEntry {
+        JournalEntry {
+            id: row.get("id"),
+            entry_date: row.get("entry_date"),
+            content: row.get("content"),
+            status: JournalEntryStatus::from_str(row.get::<&str, _>("status"))
+                .unwrap_or(JournalEntryStatus::Draft),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use crate::models::{CreateMarketEventInput, MarketEvent, MarketEventType};
+
+pub struct MarketEventRepository;
+
+impl MarketEventRepository {
+    /// Insert a market event
+    /// Returns None if the same event is already recorded
+    pub async fn insert(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: &CreateMarketEventInput,
+    ) -> Result<Option<MarketEvent>, sqlx::Error> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO market_events (
+                id, user_id, event_date, event_type, symbol, description, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(user_id)
+        .bind(input.event_date)
+        .bind(input.event_type.as_str())
+        .bind(&input.symbol)
+        .bind(&input.description)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        let row = sqlx::query("SELECT * FROM market_events WHERE id = ?")
+            .bind(&id)
+            .fetch_one(pool)
+            .await?;
+        Ok(Some(Self::row_to_event(&row)))
+    }
+
+    /// Get a user's market events with optional filters on the event date
+    pub async fn get_events(
+        pool: &SqlitePool,
+        user_id: &str,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<MarketEvent>, sqlx::Error> {
+        let mut query = String::from("SELECT * FROM market_events WHERE user_id = ?");
+
+        if start_date.is_some() {
+            query.push_str(" AND event_date >= ?");
+        }
+        if end_date.is_some() {
+            query.push_str(" AND event_date <= ?");
+        }
+
+        query.push_str(" ORDER BY event_date ASC, event_type ASC, symbol ASC");
+
+        let mut q = sqlx::query(&query).bind(user_id);
+
+        if let Some(start) = start_date {
+            q = q.bind(start);
+        }
+        if let Some(end) = end_date {
+            q = q.bind(end);
+        }
+
+        let rows = q.fetch_all(pool).await?;
+        Ok(rows.iter().map(Self::row_to_event).collect())
+    }
+
+    /// Delete a market event
+    /// Returns false if the user has no event with the given id
+    pub async fn delete(pool: &SqlitePool, user_id: &str, id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM market_events WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn row_to_event(row: &sqlx::sqlite::SqliteRow) -> MarketEvent {
+        MarketEvent {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            event_date: row.get("event_date"),
+            event_type: MarketEventType::from_str(row.get::<&str, _>("event_type"))
+                .unwrap_or(MarketEventType::Other),
+            symbol: row.get("symbol"),
+            description: row.get("description"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use chrono::NaiveDate;
+use sqlx::sqlite::{SqliteConnection, SqlitePool, SqliteRow};
+use sqlx::Row;
+use crate::models::MetricSnapshot;
+
+pub struct MetricSnapshotRepository;
+
+impl MetricSnapshotRepository {
+    /// Get an account's most recent snapshot
+    pub async fn get_latest(pool: &SqlitePool, account_id: &str) -> Result<Option<MetricSnapshot>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM metric_snapshots WHERE account_id = ? ORDER BY date DESC LIMIT 1")
+            .bind(account_id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(row.as_ref().map(Self::row_to_snapshot))
+    }
+
+    /// Get a user's snapshots with optional filters, ordered by date
+    pub async fn get_snapshots(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<MetricSnapshot>, sqlx::Error> {
+        let mut sql = String::from("SELECT * FROM metric_snapshots WHERE user_id = ?");
+        if account_id.is_some() {
+            sql.push_str(" AND account_id = ?");
+        }
+        if start_date.is_some() {
+            sql.push_str(" AND date >= ?");
+        }
+        if end_date.is_some() {
+            sql.push_str(" AND date <= ?");
+        }
+        sql.push_str(" ORDER BY date ASC, account_id ASC");
+
+        let mut query = sqlx::query(&sql).bind(user_id);
+        if let Some(account_id) = account_id {
+            query = query.bind(account_id);
+        }
+        if let Some(start_date) = start_date {
+            query = query.bind(start_date);
+        }
+        if let Some(end_date) = end_date {
+            query = query.bind(end_date);
+        }
+
+        let rows = query.fetch_all(pool).await?;
+        Ok(rows.iter().map(Self::row_to_snapshot).collect())
+    }
+
+    /// Insert snapshots in one transaction
+    pub async fn insert_snapshots(
+        pool: &SqlitePool,
+        user_id: &str,
+        snapshots: &[MetricSnapshot],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        for snapshot in snapshots {
+            sqlx::query(
+                r#"
+                INSERT OR REPLACE INTO metric_snapshots (
+                    user_id, account_id, date, balance, cumulative_pnl, drawdown, max_drawdown,
+                    trade_count, win_count, loss_count
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#
+            )
+            .bind(user_id)
+            .bind(&snapshot.account_id)
+            .bind(snapshot.date)
+            .bind(snapshot.balance)
+            .bind(snapshot.cumulative_pnl)
+            .bind(snapshot.drawdown)
+            .bind(snapshot.max_drawdown)
+            .bind(snapshot.trade_count)
+            .bind(snapshot.win_count)
+            .bind(snapshot.loss_count)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+
+    /// Delete an account's snapshots from a date on, or all of them without a date
+    pub async fn delete_from(
+        pool: &SqlitePool,
+        account_id: &str,
+        from_date: Option<NaiveDate>,
+    ) -> Result<(), sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        Self::delete_from_in(&mut conn, account_id, from_date).await
+    }
+
+    /// Delete an account's snapshots from a date on, on a connection, e.g. within a caller's transaction
+    pub async fn delete_from_in(
+        conn: &mut SqliteConnection,
+        account_id: &str,
+        from_date: Option<NaiveDate>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM metric_snapshots WHERE account_id = ? AND (? IS NULL OR date >= ?)")
+            .bind(account_id)
+            .bind(from_date)
+            .bind(from_date)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Delete all of a user's snapshots
+    pub async fn delete_all(conn: &mut SqliteConnection, user_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM metric_snapshots WHERE user_id = ?")
+            .bind(user_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    fn row_to_snapshot(row: &SqliteRow) -> MetricSnapshot {
+        let win_count: i32 = row.get("win_count");
+        let loss_count: i32 = row.get("loss_count");
+
+        MetricSnapshot {
+            account_id: row.get("account_id"),
+            date: row.get("date"),
+            balance: row.get("balance"),
+            cumulative_pnl: row.get("cumulative_pnl"),
+            drawdown: row.get("drawdown"),
+            max_drawdown: row.get("max_drawdown"),
+            trade_count: row.get("trade_count"),
+            win_count,
+            loss_count,
+            win_rate: (win_count + loss_count > 0).then(|| win_count as f64 / (win_count + loss_count) as f64),
+        }
+    }
+}
@@ -1,24 +1,75 @@
 pub mod trade_repo;
 pub mod account_repo;
 pub mod instrument_repo;
+pub mod carrying_cost_repo;
+pub mod dividend_repo;
+pub mod seed_position_repo;
+pub mod checklist_repo;
+pub mod market_event_repo;
+pub mod day_condition_repo;
+pub mod goal_repo;
+pub mod strategy_rule_repo;
+pub mod daily_close_repo;
+pub mod stop_adjustment_repo;
+pub mod financing_charge_repo;
+pub mod cash_transaction_repo;
+pub mod fx_rate_repo;
+pub mod journal_entry_repo;
+pub mod archive_repo;
+pub mod daily_performance_repo;
+pub mod metric_snapshot_repo;
+pub mod import_batch_repo;
+pub mod watched_file_repo;
 
 use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
 use std::path::PathBuf;
 
-pub use trade_repo::TradeRepository;
+pub use trade_repo::{SqlDerivedFields, TradeRepository};
 pub use account_repo::AccountRepository;
 pub use instrument_repo::InstrumentRepository;
+pub use carrying_cost_repo::CarryingCostRepository;
+pub use dividend_repo::DividendRepository;
+pub use seed_position_repo::SeedPositionRepository;
+pub use checklist_repo::ChecklistRepository;
+pub use market_event_repo::MarketEventRepository;
+pub use day_condition_repo::DayConditionRepository;
+pub use goal_repo::GoalScorecardRepository;
+pub use strategy_rule_repo::StrategyRuleRepository;
+pub use daily_close_repo::DailyCloseRepository;
+pub use stop_adjustment_repo::StopAdjustmentRepository;
+pub use financing_charge_repo::FinancingChargeRepository;
+pub use cash_transaction_repo::CashTransactionRepository;
+pub use fx_rate_repo::FxRateRepository;
+pub use journal_entry_repo::JournalEntryRepository;
+pub use archive_repo::ArchiveRepository;
+pub use daily_performance_repo::{AccountDay, DailyPerformanceRepository};
+pub use metric_snapshot_repo::MetricSnapshotRepository;
+pub use import_batch_repo::ImportBatchRepository;
+pub use watched_file_repo::WatchedFileRepository;
 
 /// Initialize the database connection pool
-pub async fn init_db(app_data_dir: PathBuf) -> Result<SqlitePool, sqlx::Error> {
+pub async fn init_db(db_path: PathBuf) -> Result<SqlitePool, sqlx::Error> {
     // Ensure the directory exists
-    std::fs::create_dir_all(&app_data_dir).ok();
+    if let Some(dir) = db_path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
 
-    let db_path = app_data_dir.join("trades.db");
     let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+    // Archived years live in a separate file next to the database, attached to every connection
+    let archive_path = db_path.with_file_name("archive.db").display().to_string();
 
     let pool = SqlitePoolOptions::new()
         .max_connections(5)
+        .after_connect(move |conn, _meta| {
+            let archive_path = archive_path.clone();
+            Box::pin(async move {
+                sqlx::query(&format!("ATTACH DATABASE ? AS {}", archive_repo::ARCHIVE_SCHEMA))
+                    .bind(archive_path)
+                    .execute(conn)
+                    .await?;
+                Ok(())
+            })
+        })
         .connect(&db_url)
         .await?;
 
@@ -29,12 +80,106 @@ pub async fn init_db(app_data_dir: PathBuf) -> Result<SqlitePool, sqlx::Error> {
 
     // Run migrations
     run_migrations(&pool).await?;
+    ArchiveRepository::ensure_tables(&mut *pool.acquire().await?).await?;
 
     Ok(pool)
 }
 
+/// Migrations in the order they are applied, by the name they are tracked under
+/// Add new migrations at the end; both the app and the test database run this list.
+const MIGRATIONS: &[(&str, &str)] = &[
+    // Migration 001: Initial schema
+    ("001_initial_schema", include_str!("../../migrations/001_initial_schema.sql")),
+    // Migration 002: Executions and options support
+    ("002_executions_options", include_str!("../../migrations/002_executions_options.sql")),
+    // Migration 003: Screenshot URL on trades
+    ("003_trade_screenshot_url", include_str!("../../migrations/003_trade_screenshot_url.sql")),
+    // Migration 004: Market candle cache
+    ("004_market_candles", include_str!("../../migrations/004_market_candles.sql")),
+    // Migration 005: Application settings
+    ("005_settings", include_str!("../../migrations/005_settings.sql")),
+    // Migration 006: Execution quality (intended price, order type)
+    ("006_execution_quality", include_str!("../../migrations/006_execution_quality.sql")),
+    // Migration 007: Carrying costs per trade
+    ("007_carrying_costs", include_str!("../../migrations/007_carrying_costs.sql")),
+    // Migration 008: Dividends
+    ("008_dividends", include_str!("../../migrations/008_dividends.sql")),
+    // Migration 009: Paper account flag
+    ("009_paper_accounts", include_str!("../../migrations/009_paper_accounts.sql")),
+    // Migration 010: Period locks
+    ("010_period_locks", include_str!("../../migrations/010_period_locks.sql")),
+    // Migration 011: Commission schedules
+    ("011_commission_schedules", include_str!("../../migrations/011_commission_schedules.sql")),
+    // Migration 012: Seed positions
+    ("012_seed_positions", include_str!("../../migrations/012_seed_positions.sql")),
+    // Migration 013: Trade reference codes
+    ("013_trade_refs", include_str!("../../migrations/013_trade_refs.sql")),
+    // Migration 014: Account starting balances
+    ("014_account_balances", include_str!("../../migrations/014_account_balances.sql")),
+    // Migration 015: Trade targets and best price reached
+    ("015_trade_targets", include_str!("../../migrations/015_trade_targets.sql")),
+    // Migration 016: Manual trade result overrides
+    ("016_result_overrides", include_str!("../../migrations/016_result_overrides.sql")),
+    // Migration 017: Per-trade checklist completion
+    ("017_trade_checklists", include_str!("../../migrations/017_trade_checklists.sql")),
+    // Migration 018: Market events and catalysts
+    ("018_market_events", include_str!("../../migrations/018_market_events.sql")),
+    // Migration 019: Market condition tags per trading day
+    ("019_day_conditions", include_str!("../../migrations/019_day_conditions.sql")),
+    // Migration 020: Weekly process goal scorecards
+    ("020_goal_scorecards", include_str!("../../migrations/020_goal_scorecards.sql")),
+    // Migration 021: Strategy suggestion rules and watchlists
+    ("021_strategy_rules", include_str!("../../migrations/021_strategy_rules.sql")),
+    // Migration 022: Worst price reached while a trade was open
+    ("022_trade_excursions", include_str!("../../migrations/022_trade_excursions.sql")),
+    // Migration 023: Daily closing prices for marking open positions to market
+    ("023_daily_closes", include_str!("../../migrations/023_daily_closes.sql")),
+    // Migration 024: Stop-loss adjustment history
+    ("024_stop_adjustments", include_str!("../../migrations/024_stop_adjustments.sql")),
+    // Migration 025: Daily bar ranges and ATR at entry
+    ("025_entry_atr", include_str!("../../migrations/025_entry_atr.sql")),
+    // Migration 026: Per-instrument tick sizes
+    ("026_instrument_tick_sizes", include_str!("../../migrations/026_instrument_tick_sizes.sql")),
+    // Migration 027: Rolled and transferred open positions
+    ("027_position_rolls", include_str!("../../migrations/027_position_rolls.sql")),
+    // Migration 028: Option expiration outcomes
+    ("028_option_outcomes", include_str!("../../migrations/028_option_outcomes.sql")),
+    // Migration 029: Margin requirement per instrument
+    ("029_instrument_margin_requirements", include_str!("../../migrations/029_instrument_margin_requirements.sql")),
+    // Migration 030: Account financing ledger
+    ("030_financing_charges", include_str!("../../migrations/030_financing_charges.sql")),
+    // Migration 031: Account deposits and withdrawals
+    ("031_cash_transactions", include_str!("../../migrations/031_cash_transactions.sql")),
+    // Migration 032: FX rates for the reporting currency
+    ("032_fx_rates", include_str!("../../migrations/032_fx_rates.sql")),
+    // Migration 033: FX rate sources for fetched ECB rates
+    ("033_fx_rate_sources", include_str!("../../migrations/033_fx_rate_sources.sql")),
+    // Migration 034: Borrow availability of short trades
+    ("034_borrow_availability", include_str!("../../migrations/034_borrow_availability.sql")),
+    // Migration 035: Daily journal entries
+    ("035_journal_entries", include_str!("../../migrations/035_journal_entries.sql")),
+    // Migration 036: Backfill instrument option metadata
+    ("036_instrument_option_metadata", include_str!("../../migrations/036_instrument_option_metadata.sql")),
+    // Migration 037: Contract multiplier per instrument
+    ("037_instrument_multipliers", include_str!("../../migrations/037_instrument_multipliers.sql")),
+    // Migration 038: Daily performance summary per account
+    ("038_daily_performance", include_str!("../../migrations/038_daily_performance.sql")),
+    // Migration 039: Nightly metric snapshots per account
+    ("039_metric_snapshots", include_str!("../../migrations/039_metric_snapshots.sql")),
+    // Migration 040: Import batches stamped on imported trades and executions
+    ("040_import_batches", include_str!("../../migrations/040_import_batches.sql")),
+    // Migration 041: File name, skipped duplicates and errors of import batches
+    ("041_import_history", include_str!("../../migrations/041_import_history.sql")),
+    // Migration 042: Files processed from the watched import folder
+    ("042_watched_files", include_str!("../../migrations/042_watched_files.sql")),
+    // Migration 043: Rate converting a trade's PnL to the account's base currency
+    ("043_trade_fx_rate", include_str!("../../migrations/043_trade_fx_rate.sql")),
+    // Migration 044: Strategy rules for futures, forex and crypto
+    ("044_strategy_rule_asset_classes", include_str!("../../migrations/044_strategy_rule_asset_classes.sql")),
+];
+
 /// Run database migrations with tracking to avoid re-running
-async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+pub async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     // Create migrations tracking table if it doesn't exist
     sqlx::raw_sql(
         "CREATE TABLE IF NOT EXISTS _migrations (
@@ -110,39 +255,11 @@ async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
         }
     }
 
-    // Migration 001: Initial schema
-    if !migration_applied(pool, "001_initial_schema").await? {
-        let migration_001 = include_str!("../../migrations/001_initial_schema.sql");
-        sqlx::raw_sql(migration_001).execute(pool).await?;
-        mark_migration_applied(pool, "001_initial_schema").await?;
-    }
-
-    // Migration 002: Executions and options support
-    if !migration_applied(pool, "002_executions_options").await? {
-        let migration_002 = include_str!("../../migrations/002_executions_options.sql");
-        sqlx::raw_sql(migration_002).execute(pool).await?;
-        mark_migration_applied(pool, "002_executions_options").await?;
-    }
-
-    // Migration 003: Screenshot URL on trades
-    if !migration_applied(pool, "003_trade_screenshot_url").await? {
-        let migration_003 = include_str!("../../migrations/003_trade_screenshot_url.sql");
-        sqlx::raw_sql(migration_003).execute(pool).await?;
-        mark_migration_applied(pool, "003_trade_screenshot_url").await?;
-    }
-
-    // Migration 004: Market candle cache
-    if !migration_applied(pool, "004_market_candles").await? {
-        let migration_004 = include_str!("../../migrations/004_market_candles.sql");
-        sqlx::raw_sql(migration_004).execute(pool).await?;
-        mark_migration_applied(pool, "004_market_candles").await?;
-    }
-
-    // Migration 005: Application settings
-    if !migration_applied(pool, "005_settings").await? {
-        let migration_005 = include_str!("../../migrations/005_settings.sql");
-        sqlx::raw_sql(migration_005).execute(pool).await?;
-        mark_migration_applied(pool, "005_settings").await?;
+    for &(name, sql) in MIGRATIONS {
+        if !migration_applied(pool, name).await? {
+            sqlx::raw_sql(sql).execute(pool).await?;
+            mark_migration_applied(pool, name).await?;
+        }
     }
 
     Ok(())
@@ -278,4 +395,39 @@ mod tests {
 
         assert_eq!(account_count, 1);
     }
+
+    #[tokio::test]
+    async fn test_option_metadata_backfill_decodes_padded_and_unpadded_symbols() {
+        let pool = create_test_db().await;
+        for (id, symbol) in [("padded", "AAPL  250905C00240000"), ("unpadded", "AAPL240315P00150500"), ("bad", "AAPL240315X0015")] {
+            sqlx::query("INSERT INTO instruments (id, symbol, asset_class) VALUES (?, ?, 'option')")
+                .bind(id)
+                .bind(symbol)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let (_, sql) = MIGRATIONS.iter().find(|(name, _)| *name == "036_instrument_option_metadata").unwrap();
+        sqlx::raw_sql(sql).execute(&pool).await.unwrap();
+
+        type OptionColumns = (Option<String>, Option<String>, Option<String>, Option<f64>);
+        let decoded: Vec<OptionColumns> = sqlx::query_as(
+            "SELECT underlying_symbol, CAST(expiration_date AS TEXT), option_type, strike_price FROM instruments ORDER BY id",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        let some = |underlying: &str, expiration: &str, option_type: &str, strike: f64| {
+            (Some(underlying.to_string()), Some(expiration.to_string()), Some(option_type.to_string()), Some(strike))
+        };
+        assert_eq!(
+            decoded,
+            vec![
+                (None, None, None, None), // bad
+                some("AAPL", "2025-09-05", "call", 240.0), // padded
+                some("AAPL", "2024-03-15", "put", 150.5), // unpadded
+            ]
+        );
+    }
 }
@@ -0,0 +1,100 @@
+use chrono::Utc;
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use crate::models::{CreateSeedPositionInput, Direction, SeedPosition};
+
+pub struct SeedPositionRepository;
+
+impl SeedPositionRepository {
+    /// Insert a seed position, replacing any existing seed for the same account and symbol
+    pub async fn insert(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: &CreateSeedPositionInput,
+    ) -> Result<SeedPosition, sqlx::Error> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let symbol = input.symbol.trim().to_uppercase();
+
+        sqlx::query(
+            r#"
+            INSERT INTO seed_positions (
+                id, user_id, account_id, symbol, direction,
+                quantity, avg_cost, open_date, as_of_date, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(account_id, symbol) DO UPDATE SET
+                id = excluded.id,
+                direction = excluded.direction,
+                quantity = excluded.quantity,
+                avg_cost = excluded.avg_cost,
+                open_date = excluded.open_date,
+                as_of_date = excluded.as_of_date,
+                created_at = excluded.created_at
+            "#
+        )
+        .bind(&id)
+        .bind(user_id)
+        .bind(&input.account_id)
+        .bind(&symbol)
+        .bind(input.direction.as_str())
+        .bind(input.quantity)
+        .bind(input.avg_cost)
+        .bind(input.open_date)
+        .bind(input.as_of_date)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        let row = sqlx::query("SELECT * FROM seed_positions WHERE id = ?")
+            .bind(&id)
+            .fetch_one(pool)
+            .await?;
+        Ok(Self::row_to_seed_position(&row))
+    }
+
+    /// Get a user's seed positions, optionally for a single account
+    pub async fn get_seed_positions(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+    ) -> Result<Vec<SeedPosition>, sqlx::Error> {
+        let mut query = String::from("SELECT * FROM seed_positions WHERE user_id = ?");
+        if account_id.is_some() {
+            query.push_str(" AND account_id = ?");
+        }
+        query.push_str(" ORDER BY symbol ASC");
+
+        let mut q = sqlx::query(&query).bind(user_id);
+        if let Some(acc) = account_id {
+            q = q.bind(acc);
+        }
+
+        let rows = q.fetch_all(pool).await?;
+        Ok(rows.iter().map(Self::row_to_seed_position).collect())
+    }
+
+    /// Delete a seed position
+    /// Returns false if no seed position with the given id exists for the user
+    pub async fn delete(pool: &SqlitePool, user_id: &str, id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM seed_positions WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn row_to_seed_position(row: &sqlx::sqlite::SqliteRow) -> SeedPosition {
+        SeedPosition {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            account_id: row.get("account_id"),
+            symbol: row.get("symbol"),
+            direction: Direction::from_str(row.get::<&str, _>("direction")).unwrap_or(Direction::Long),
+            quantity: row.get("quantity"),
+            avg_cost: row.get("avg_cost"),
+            open_date: row.get("open_date"),
+            as_of_date: row.get("as_of_date"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use chrono::Utc;
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use crate::models::{RecordStopAdjustmentInput, StopAdjustment};
+
+pub struct StopAdjustmentRepository;
+
+impl StopAdjustmentRepository {
+    /// Record a stop-loss change on a trade
+    pub async fn insert(
+        pool: &SqlitePool,
+        input: &RecordStopAdjustmentInput,
+    ) -> Result<StopAdjustment, sqlx::Error> {
+        let adjustment = StopAdjustment {
+            id: uuid::Uuid::new_v4().to_string(),
+            trade_id: input.trade_id.clone(),
+            stop_price: input.stop_price,
+            adjusted_at: input.adjusted_at.unwrap_or_else(Utc::now),
+        };
+
+        sqlx::query(
+            "INSERT INTO stop_adjustments (id, trade_id, stop_price, adjusted_at) VALUES (?, ?, ?, ?)"
+        )
+        .bind(&adjustment.id)
+        .bind(&adjustment.trade_id)
+        .bind(adjustment.stop_price)
+        .bind(adjustment.adjusted_at)
+        .execute(pool)
+        .await?;
+
+        Ok(adjustment)
+    }
+
+    /// Get one of a user's stop changes by ID
+    pub async fn get_by_id(pool: &SqlitePool, user_id: &str, id: &str) -> Result<Option<StopAdjustment>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT s.*
+            FROM stop_adjustments s
+            JOIN trades t ON s.trade_id = t.id
+            WHERE s.id = ? AND t.user_id = ?
+            "#
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.as_ref().map(Self::row_to_adjustment))
+    }
+
+    /// Get stop changes for one of a user's trades, oldest first
+    pub async fn get_for_trade(
+        pool: &SqlitePool,
+        user_id: &str,
+        trade_id: &str,
+    ) -> Result<Vec<StopAdjustment>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT s.*
+            FROM stop_adjustments s
+            JOIN trades t ON s.trade_id = t.id
+            WHERE s.trade_id = ? AND t.user_id = ?
+            ORDER BY s.adjusted_at ASC
+            "#
+        )
+        .bind(trade_id)
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_adjustment).collect())
+    }
+
+    /// Get stop changes for all of a user's trades keyed by trade ID, oldest first
+    pub async fn get_by_trade(
+        pool: &SqlitePool,
+        user_id: &str,
+    ) -> Result<HashMap<String, Vec<StopAdjustment>>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT s.*
+            FROM stop_adjustments s
+            JOIN trades t ON s.trade_id = t.id
+            WHERE t.user_id = ?
+            ORDER BY s.adjusted_at ASC
+            "#
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        let mut by_trade: HashMap<String, Vec<StopAdjustment>> = HashMap::new();
+        for adjustment in rows.iter().map(Self::row_to_adjustment) {
+            by_trade.entry(adjustment.trade_id.clone()).or_default().push(adjustment);
+        }
+        Ok(by_trade)
+    }
+
+    /// Delete a stop change on one of a user's trades
+    /// Returns false if the user has no adjustment with the given id
+    pub async fn delete(pool: &SqlitePool, user_id: &str, id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM stop_adjustments
+            WHERE id = ? AND trade_id IN (SELECT id FROM trades WHERE user_id = ?)
+            "#
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn row_to_adjustment(row: &sqlx::sqlite::SqliteRow) -> StopAdjustment {
+        StopAdjustment {
+            id: row.get("id"),
+            trade_id: row.get("trade_id"),
+            stop_price: row.get("stop_price"),
+            adjusted_at: row.get("adjusted_at"),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use chrono::Utc;
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use crate::models::{AssetClass, CreateStrategyRuleInput, Direction, StrategyRule, Watchlist};
+
+pub struct StrategyRuleRepository;
+
+impl StrategyRuleRepository {
+    /// Insert a new strategy rule
+    pub async fn insert(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: &CreateStrategyRuleInput,
+    ) -> Result<StrategyRule, sqlx::Error> {
+        let rule = StrategyRule {
+            id: uuid::Uuid::new_v4().to_string(),
+            strategy: input.strategy.clone(),
+            priority: input.priority.unwrap_or(0),
+            min_hold_minutes: input.min_hold_minutes,
+            max_hold_minutes: input.max_hold_minutes,
+            watchlist: input.watchlist.clone(),
+            direction: input.direction,
+            asset_class: input.asset_class,
+            created_at: Utc::now(),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO strategy_rules (
+                id, user_id, strategy, priority, min_hold_minutes, max_hold_minutes,
+                watchlist, direction, asset_class, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&rule.id)
+        .bind(user_id)
+        .bind(&rule.strategy)
+        .bind(rule.priority)
+        .bind(rule.min_hold_minutes)
+        .bind(rule.max_hold_minutes)
+        .bind(&rule.watchlist)
+        .bind(rule.direction.map(|d| d.as_str()))
+        .bind(rule.asset_class.map(|a| a.as_str()))
+        .bind(rule.created_at)
+        .execute(pool)
+        .await?;
+
+        Ok(rule)
+    }
+
+    /// Get a user's rules in the order they are tried
+    pub async fn get_rules(
+        pool: &SqlitePool,
+        user_id: &str,
+    ) -> Result<Vec<StrategyRule>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM strategy_rules WHERE user_id = ? ORDER BY priority ASC, created_at ASC"
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| StrategyRule {
+                id: row.get("id"),
+                strategy: row.get("strategy"),
+                priority: row.get("priority"),
+                min_hold_minutes: row.get("min_hold_minutes"),
+                max_hold_minutes: row.get("max_hold_minutes"),
+                watchlist: row.get("watchlist"),
+                direction: row.get::<Option<&str>, _>("direction").and_then(Direction::from_str),
+                asset_class: row.get::<Option<&str>, _>("asset_class").and_then(AssetClass::from_str),
+                created_at: row.get("created_at"),
+            })
+            .collect())
+    }
+
+    /// Delete a strategy rule
+    pub async fn delete(pool: &SqlitePool, user_id: &str, id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM strategy_rules WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Replace the symbols on a watchlist; an empty list removes the watchlist
+    pub async fn set_watchlist(
+        pool: &SqlitePool,
+        user_id: &str,
+        name: &str,
+        symbols: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("DELETE FROM watchlist_symbols WHERE user_id = ? AND watchlist = ?")
+            .bind(user_id)
+            .bind(name)
+            .execute(&mut *tx)
+            .await?;
+
+        for symbol in symbols {
+            sqlx::query("INSERT OR IGNORE INTO watchlist_symbols (user_id, watchlist, symbol) VALUES (?, ?, ?)")
+                .bind(user_id)
+                .bind(name)
+                .bind(symbol)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await
+    }
+
+    /// Get a user's watchlists by name
+    pub async fn get_watchlists(
+        pool: &SqlitePool,
+        user_id: &str,
+    ) -> Result<Vec<Watchlist>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT watchlist, symbol FROM watchlist_symbols WHERE user_id = ? ORDER BY watchlist, symbol"
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        let mut by_name: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for row in &rows {
+            by_name.entry(row.get("watchlist")).or_default().push(row.get("symbol"));
+        }
+
+        Ok(by_name
+            .into_iter()
+            .map(|(name, symbols)| Watchlist { name, symbols })
+            .collect())
+    }
+}
@@ -1,33 +1,71 @@
-use chrono::{NaiveDate, Utc};
-use sqlx::sqlite::SqlitePool;
+use std::collections::HashMap;
+use chrono::{Datelike, NaiveDate, Utc};
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
 use sqlx::Row;
-use crate::models::{Direction, Status, Trade, CreateTradeInput, UpdateTradeInput, AssetClass};
-#[cfg(test)]
+use crate::models::{BorrowAvailability, Direction, Status, Trade, CreateTradeInput, UpdateTradeInput, AssetClass, ExecutionFill, OptionOutcome, OrderType, RollType, TradeFilter, TradeResult, TradeStats, TradeTypeThresholds};
 use crate::models::trade::TradeExecutionRecord;
+use crate::repository::archive_repo::ARCHIVE_SCHEMA;
+
+type SqliteQuery<'q> = sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>;
+
+/// Tables whose rows belong to a single trade and go away with it
+pub(crate) const DEPENDENT_TABLES: [&str; 6] = [
+    "trade_executions",
+    "trade_tags",
+    "trade_links",
+    "trade_carrying_costs",
+    "trade_checklist_items",
+    "stop_adjustments",
+];
+
+/// Derived fields of a trade as computed in SQL, null where calculate_derived_fields gives None
+#[derive(Debug, Clone)]
+pub struct SqlDerivedFields {
+    pub trade_id: String,
+    pub gross_pnl: Option<f64>,
+    pub net_pnl: Option<f64>,
+    pub result_pnl: Option<f64>, // Gross or net PnL, by the result basis setting
+    pub r_multiple: Option<f64>,
+    pub trade_type: Option<String>,
+}
 
+// Settings read in SQL below use the keys of the matching KEY_ constants in settings_service
 pub struct TradeRepository;
 
 impl TradeRepository {
     /// Insert a new trade
+    #[cfg(test)]
     pub async fn insert(
         pool: &SqlitePool,
         user_id: &str,
         instrument_id: &str,
         input: &CreateTradeInput,
+    ) -> Result<Trade, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        Self::insert_in(&mut conn, user_id, instrument_id, input).await
+    }
+
+    /// Insert a new trade on a connection, e.g. within a caller's transaction
+    pub async fn insert_in(
+        conn: &mut SqliteConnection,
+        user_id: &str,
+        instrument_id: &str,
+        input: &CreateTradeInput,
     ) -> Result<Trade, sqlx::Error> {
         let id = uuid::Uuid::new_v4().to_string();
         let now = Utc::now();
         let status = input.status.unwrap_or(Status::Closed);
         let fees = input.fees.unwrap_or(0.0);
+        let ref_code = Self::next_ref_code(&mut *conn, user_id, input.trade_date).await?;
 
         sqlx::query(
             r#"
             INSERT INTO trades (
-                id, user_id, account_id, instrument_id, trade_number,
+                id, user_id, account_id, instrument_id, trade_number, ref_code,
                 trade_date, direction, quantity, entry_price, exit_price,
-                stop_loss_price, entry_time, exit_time, fees, strategy,
-                notes, screenshot_url, status, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                stop_loss_price, target_price, max_favorable_price, entry_time, exit_time,
+                fees, strategy, notes, screenshot_url, status, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#
         )
         .bind(&id)
@@ -35,12 +73,15 @@ impl TradeRepository {
         .bind(&input.account_id)
         .bind(instrument_id)
         .bind(input.trade_number)
+        .bind(&ref_code)
         .bind(input.trade_date)
         .bind(input.direction.as_str())
         .bind(input.quantity)
         .bind(input.entry_price)
         .bind(input.exit_price)
         .bind(input.stop_loss_price)
+        .bind(input.target_price)
+        .bind(input.max_favorable_price)
         .bind(&input.entry_time)
         .bind(&input.exit_time)
         .bind(fees)
@@ -50,93 +91,385 @@ impl TradeRepository {
         .bind(status.as_str())
         .bind(now)
         .bind(now)
-        .execute(pool)
+        .execute(&mut *conn)
         .await?;
 
         // Fetch the inserted trade
-        Self::get_by_id(pool, &id).await?.ok_or_else(|| {
+        Self::get_by_id_in(conn, &id).await?.ok_or_else(|| {
             sqlx::Error::RowNotFound
         })
     }
 
+    /// Next free reference code for a user's trades in the year of the trade date, e.g. T-2024-0193
+    /// Codes are assigned once and don't change when the trade date is edited.
+    pub async fn next_ref_code(
+        conn: &mut SqliteConnection,
+        user_id: &str,
+        trade_date: NaiveDate,
+    ) -> Result<String, sqlx::Error> {
+        let prefix = format!("T-{}-", trade_date.year());
+        let last: Option<i64> = sqlx::query_scalar(
+            "SELECT MAX(CAST(substr(ref_code, ?) AS INTEGER)) FROM trades WHERE user_id = ? AND ref_code LIKE ?"
+        )
+        .bind(prefix.len() as i64 + 1)
+        .bind(user_id)
+        .bind(format!("{}%", prefix))
+        .fetch_one(conn)
+        .await?;
+
+        Ok(format!("{}{:04}", prefix, last.unwrap_or(0) + 1))
+    }
+
+    /// Get the ID of a user's trade by its reference code
+    pub async fn get_id_by_ref_code(
+        pool: &SqlitePool,
+        user_id: &str,
+        ref_code: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT id FROM trades WHERE user_id = ? AND ref_code = ?")
+            .bind(user_id)
+            .bind(ref_code)
+            .fetch_optional(pool)
+            .await
+    }
+
     /// Get a trade by ID
     pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Trade>, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        Self::get_by_id_in(&mut conn, id).await
+    }
+
+    /// Get a trade by ID on a connection, e.g. within a caller's transaction
+    pub async fn get_by_id_in(conn: &mut SqliteConnection, id: &str) -> Result<Option<Trade>, sqlx::Error> {
         let row = sqlx::query(
             r#"
-            SELECT t.*, i.symbol, i.asset_class
+            SELECT t.*, i.symbol, i.asset_class, i.multiplier AS contract_multiplier,
+                   (SELECT TOTAL(c.amount) FROM trade_carrying_costs c
+                    WHERE c.trade_id = t.id) AS carrying_costs,
+                   (SELECT MAX(e.execution_date) FROM trade_executions e
+                    WHERE e.trade_id = t.id AND e.execution_type = 'exit') AS exit_date,
+                   CASE WHEN (SELECT s.value FROM settings s
+                              WHERE s.key = 'include_dividends_in_pnl') = 'true'
+                        THEN (SELECT TOTAL(d.amount) FROM dividends d WHERE d.trade_id = t.id)
+                        ELSE 0.0 END AS dividends,
+                   EXISTS(SELECT 1 FROM period_locks pl
+                          WHERE pl.account_id = t.account_id
+                            AND t.trade_date <= pl.locked_through) AS is_locked
             FROM trades t
             JOIN instruments i ON t.instrument_id = i.id
             WHERE t.id = ?
             "#
         )
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(conn)
         .await?;
 
         Ok(row.map(|r| Self::row_to_trade(&r)))
     }
 
-    /// Get trades with optional filters
+    /// Get trades matching a filter, newest first
     pub async fn get_trades(
         pool: &SqlitePool,
         user_id: &str,
-        account_id: Option<&str>,
-        start_date: Option<NaiveDate>,
-        end_date: Option<NaiveDate>,
-        status_filter: Option<Status>,
+        filter: &TradeFilter,
     ) -> Result<Vec<Trade>, sqlx::Error> {
-        let mut query = String::from(
+        let mut query = Self::filtered_trades_query(filter);
+        query.push_str(" ORDER BY trade_date DESC, created_at DESC");
+
+        let rows = Self::bind_filter(sqlx::query(&query), user_id, filter)
+            .fetch_all(pool)
+            .await?;
+        Ok(rows.iter().map(|r| Self::row_to_trade(r)).collect())
+    }
+
+    /// Count and summarize the trades matching a filter without loading them
+    pub async fn get_trade_stats(
+        pool: &SqlitePool,
+        user_id: &str,
+        filter: &TradeFilter,
+    ) -> Result<TradeStats, sqlx::Error> {
+        let query = format!(
             r#"
-            SELECT t.*, i.symbol, i.asset_class
-            FROM trades t
+            SELECT COUNT(*) AS trade_count,
+                   COUNT(net_pnl) AS closed_count,
+                   TOTAL(net_pnl) AS total_pnl,
+                   COUNT(CASE WHEN result_pnl > 0 THEN 1 END) AS win_count,
+                   COUNT(CASE WHEN result_pnl < 0 THEN 1 END) AS loss_count,
+                   MIN(trade_date) AS first_trade_date,
+                   MAX(trade_date) AS last_trade_date
+            FROM ({}) filtered
+            "#,
+            Self::filtered_trades_query(filter)
+        );
+
+        let row = Self::bind_filter(sqlx::query(&query), user_id, filter)
+            .fetch_one(pool)
+            .await?;
+        let win_count: i64 = row.get("win_count");
+        let loss_count: i64 = row.get("loss_count");
+        let decisive_count = win_count + loss_count;
+
+        Ok(TradeStats {
+            trade_count: row.get("trade_count"),
+            closed_count: row.get("closed_count"),
+            total_pnl: row.get("total_pnl"),
+            win_count,
+            loss_count,
+            win_rate: (decisive_count > 0).then(|| win_count as f64 / decisive_count as f64),
+            first_trade_date: row.get("first_trade_date"),
+            last_trade_date: row.get("last_trade_date"),
+        })
+    }
+
+    /// Get the PnL, R-multiple and trade type of the trades matching a filter as the SQL of
+    /// filtered_trades_query derives them, to check against calculate_derived_fields
+    pub async fn get_sql_derived_fields(
+        pool: &SqlitePool,
+        user_id: &str,
+        filter: &TradeFilter,
+    ) -> Result<Vec<SqlDerivedFields>, sqlx::Error> {
+        let query = format!(
+            "SELECT id, gross_pnl, net_pnl, result_pnl, r_multiple, trade_type FROM ({}) filtered",
+            Self::filtered_trades_query(filter)
+        );
+
+        let rows = Self::bind_filter(sqlx::query(&query), user_id, filter)
+            .fetch_all(pool)
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| SqlDerivedFields {
+                trade_id: row.get("id"),
+                gross_pnl: row.get("gross_pnl"),
+                net_pnl: row.get("net_pnl"),
+                result_pnl: row.get("result_pnl"),
+                r_multiple: row.get("r_multiple"),
+                trade_type: row.get("trade_type"),
+            })
+            .collect())
+    }
+
+    /// Build the SELECT for trades matching a filter, with net_pnl, r_multiple and
+    /// duration_minutes computed in SQL so they can be filtered on.
+    /// Net PnL mirrors calculate_derived_fields: gross × multiplier × fx rate − fees − carrying costs + dividends.
+    /// result_pnl is the gross or net PnL that wins and losses are classified by.
+    /// Duration runs from the entry time on the trade date to the exit time on the last exit date;
+    /// trades without both times never match.
+    /// attributed_date is the day the trade counts toward, which start and end dates match on.
+    pub(crate) fn filtered_trades_query(filter: &TradeFilter) -> String {
+        let schema = if filter.archived { ARCHIVE_SCHEMA } else { "main" };
+        let date = Self::attributed_date(filter);
+        let mut base = format!(
+            r#"
+            SELECT t.*, i.symbol, i.asset_class, i.multiplier AS contract_multiplier,
+                   {date} AS attributed_date,
+                   (SELECT TOTAL(c.amount) FROM {schema}.trade_carrying_costs c
+                    WHERE c.trade_id = t.id) AS carrying_costs,
+                   (SELECT MAX(e.execution_date) FROM {schema}.trade_executions e
+                    WHERE e.trade_id = t.id AND e.execution_type = 'exit') AS exit_date,
+                   CASE WHEN (SELECT s.value FROM settings s
+                              WHERE s.key = 'include_dividends_in_pnl') = 'true'
+                        THEN (SELECT TOTAL(d.amount) FROM {schema}.dividends d WHERE d.trade_id = t.id)
+                        ELSE 0.0 END AS dividends,
+                   EXISTS(SELECT 1 FROM period_locks pl
+                          WHERE pl.account_id = t.account_id
+                            AND t.trade_date <= pl.locked_through) AS is_locked
+            FROM {schema}.trades t
             JOIN instruments i ON t.instrument_id = i.id
             WHERE t.user_id = ?
             "#
         );
 
-        if account_id.is_some() {
-            query.push_str(" AND t.account_id = ?");
+        if filter.account_id.is_some() {
+            base.push_str(" AND t.account_id = ?");
         }
-        if start_date.is_some() {
-            query.push_str(" AND t.trade_date >= ?");
+        // With both attribution, a closed trade also matches on its trade date
+        if filter.start_date.is_some() || filter.end_date.is_some() {
+            let range = |column: &str| {
+                let mut bounds = Vec::new();
+                if filter.start_date.is_some() {
+                    bounds.push(format!("{} >= ?", column));
+                }
+                if filter.end_date.is_some() {
+                    bounds.push(format!("{} <= ?", column));
+                }
+                bounds.join(" AND ")
+            };
+            base.push_str(&format!(
+                " AND (({}) OR ({} = 'both' AND {}))",
+                range(&date),
+                Self::date_attribution(filter),
+                range("t.trade_date"),
+            ));
         }
-        if end_date.is_some() {
-            query.push_str(" AND t.trade_date <= ?");
+        if filter.symbol.is_some() {
+            base.push_str(" AND UPPER(i.symbol) = UPPER(?)");
         }
-        if status_filter.is_some() {
-            query.push_str(" AND t.status = ?");
+        if filter.direction.is_some() {
+            base.push_str(" AND t.direction = ?");
+        }
+        if filter.status.is_some() {
+            base.push_str(" AND t.status = ?");
         }
 
-        query.push_str(" ORDER BY t.trade_date DESC, t.created_at DESC");
+        // Null exit price or quantity leaves PnL and R null, as in calculate_derived_fields
+        let mut query = format!(
+            r#"
+            SELECT * FROM (
+                SELECT g.*,
+                       g.gross_pnl - g.fees - g.carrying_costs + g.dividends AS net_pnl,
+                       CASE WHEN (SELECT LOWER(s.value) FROM settings s WHERE s.key = 'result_basis') = 'gross'
+                            THEN g.gross_pnl
+                            ELSE g.gross_pnl - g.fees - g.carrying_costs + g.dividends END AS result_pnl,
+                       {} AS trade_type
+                FROM (
+                    SELECT b.*,
+                           (CASE WHEN b.direction = 'short' THEN b.entry_price - b.exit_price
+                                 ELSE b.exit_price - b.entry_price END)
+                               * b.quantity
+                               * COALESCE(b.contract_multiplier,
+                                          CASE WHEN b.asset_class = 'option' THEN 100.0 ELSE 1.0 END)
+                               * COALESCE(b.fx_rate, 1.0) AS gross_pnl,
+                           CASE WHEN b.quantity IS NULL THEN NULL
+                           ELSE (CASE WHEN b.direction = 'short' THEN b.entry_price - b.exit_price
+                                      ELSE b.exit_price - b.entry_price END)
+                                / NULLIF(ABS(b.entry_price - b.stop_loss_price), 0)
+                           END AS r_multiple,
+                           (strftime('%s', COALESCE(b.exit_date, b.trade_date) || ' ' || b.exit_time)
+                            - strftime('%s', b.trade_date || ' ' || b.entry_time)) / 60.0 AS duration_minutes
+                    FROM ({}) b
+                ) g
+            ) derived
+            WHERE 1 = 1
+            "#,
+            Self::trade_type_case(),
+            base
+        );
 
-        let mut q = sqlx::query(&query).bind(user_id);
+        match filter.result {
+            Some(TradeResult::Win) => query.push_str(" AND result_pnl > 0"),
+            Some(TradeResult::Loss) => query.push_str(" AND result_pnl < 0"),
+            Some(TradeResult::Breakeven) => query.push_str(" AND result_pnl = 0"),
+            None => {}
+        }
+        if filter.min_r_multiple.is_some() {
+            query.push_str(" AND r_multiple >= ?");
+        }
+        if filter.max_duration_minutes.is_some() {
+            query.push_str(" AND duration_minutes >= 0 AND duration_minutes <= ?");
+        }
+        if filter.trade_type.is_some() {
+            query.push_str(" AND trade_type = ?");
+        }
 
-        if let Some(acc) = account_id {
+        query
+    }
+
+    /// SQL classifying a row of the derived query by trade type, mirroring calculate_trade_type
+    /// Thresholds come from settings, with the defaults of TradeTypeThresholds when unset.
+    fn trade_type_case() -> String {
+        let defaults = TradeTypeThresholds::default();
+        format!(
+            r#"CASE WHEN g.status <> 'closed' THEN NULL
+                    WHEN g.exit_date > g.trade_date THEN
+                        CASE WHEN julianday(g.exit_date) - julianday(g.trade_date)
+                                  <= COALESCE((SELECT CAST(s.value AS INTEGER) FROM settings s
+                                               WHERE s.key = 'trade_type_swing_max_days'), {swing})
+                             THEN 'swing' ELSE 'position' END
+                    WHEN g.duration_minutes <= COALESCE((SELECT CAST(s.value AS REAL) FROM settings s
+                                                         WHERE s.key = 'trade_type_scalp_max_minutes'), {scalp})
+                    THEN 'scalp'
+                    ELSE 'day' END"#,
+            swing = defaults.swing_max_days,
+            scalp = defaults.scalp_max_minutes,
+        )
+    }
+
+    /// SQL for the date attribution of a filter, or the setting when the filter has none
+    fn date_attribution(filter: &TradeFilter) -> String {
+        match filter.date_attribution {
+            Some(attribution) => format!("'{}'", attribution.as_str()),
+            None => "(SELECT LOWER(s.value) FROM settings s WHERE s.key = 'date_attribution')".to_string(),
+        }
+    }
+
+    /// SQL for the date a trade counts toward, which start and end dates match on
+    /// With exit or both attribution, closed trades count toward the date of their last exit execution.
+    fn attributed_date(filter: &TradeFilter) -> String {
+        let schema = if filter.archived { ARCHIVE_SCHEMA } else { "main" };
+        let attribution = Self::date_attribution(filter);
+        format!(
+            r#"(CASE WHEN t.status = 'closed' AND {attribution} IN ('exit', 'both')
+                 THEN COALESCE((SELECT MAX(e.execution_date) FROM {schema}.trade_executions e
+                                WHERE e.trade_id = t.id AND e.execution_type = 'exit'),
+                               t.trade_date)
+                 ELSE t.trade_date END)"#
+        )
+    }
+
+    /// Bind the parameters of filtered_trades_query in placeholder order
+    pub(crate) fn bind_filter<'q>(
+        query: SqliteQuery<'q>,
+        user_id: &'q str,
+        filter: &'q TradeFilter,
+    ) -> SqliteQuery<'q> {
+        let mut q = query.bind(user_id);
+
+        if let Some(ref acc) = filter.account_id {
             q = q.bind(acc);
         }
-        if let Some(start) = start_date {
-            q = q.bind(start);
+        // The range is matched on the attributed date and again on the trade date
+        for _ in 0..2 {
+            if let Some(start) = filter.start_date {
+                q = q.bind(start);
+            }
+            if let Some(end) = filter.end_date {
+                q = q.bind(end);
+            }
         }
-        if let Some(end) = end_date {
-            q = q.bind(end);
+        if let Some(ref symbol) = filter.symbol {
+            q = q.bind(symbol.trim());
         }
-        if let Some(status) = status_filter {
+        if let Some(direction) = filter.direction {
+            q = q.bind(direction.as_str());
+        }
+        if let Some(status) = filter.status {
             q = q.bind(status.as_str());
         }
+        if let Some(min_r) = filter.min_r_multiple {
+            q = q.bind(min_r);
+        }
+        if let Some(max_minutes) = filter.max_duration_minutes {
+            q = q.bind(max_minutes);
+        }
+        if let Some(trade_type) = filter.trade_type {
+            q = q.bind(trade_type.as_str());
+        }
 
-        let rows = q.fetch_all(pool).await?;
-        Ok(rows.iter().map(|r| Self::row_to_trade(r)).collect())
+        q
     }
 
     /// Update a trade
+    #[cfg(test)]
     pub async fn update(
         pool: &SqlitePool,
         id: &str,
         instrument_id: Option<&str>,
         input: &UpdateTradeInput,
+    ) -> Result<Trade, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        Self::update_in(&mut conn, id, instrument_id, input).await
+    }
+
+    /// Update a trade on a connection, e.g. within a caller's transaction
+    pub async fn update_in(
+        conn: &mut SqliteConnection,
+        id: &str,
+        instrument_id: Option<&str>,
+        input: &UpdateTradeInput,
     ) -> Result<Trade, sqlx::Error> {
         let now = Utc::now();
-        let existing = Self::get_by_id(pool, id).await?.ok_or(sqlx::Error::RowNotFound)?;
+        let existing = Self::get_by_id_in(&mut *conn, id).await?.ok_or(sqlx::Error::RowNotFound)?;
 
         let account_id = input.account_id.as_ref().unwrap_or(&existing.account_id);
         let trade_number = input.trade_number.or(existing.trade_number);
@@ -146,6 +479,8 @@ impl TradeRepository {
         let entry_price = input.entry_price.unwrap_or(existing.entry_price);
         let exit_price = input.exit_price.or(existing.exit_price);
         let stop_loss_price = input.stop_loss_price.or(existing.stop_loss_price);
+        let target_price = input.target_price.or(existing.target_price);
+        let max_favorable_price = input.max_favorable_price.or(existing.max_favorable_price);
         let entry_time = input.entry_time.clone().or(existing.entry_time);
         let exit_time = input.exit_time.clone().or(existing.exit_time);
         let fees = input.fees.unwrap_or(existing.fees);
@@ -167,6 +502,8 @@ impl TradeRepository {
                 entry_price = ?,
                 exit_price = ?,
                 stop_loss_price = ?,
+                target_price = ?,
+                max_favorable_price = ?,
                 entry_time = ?,
                 exit_time = ?,
                 fees = ?,
@@ -187,6 +524,8 @@ impl TradeRepository {
         .bind(entry_price)
         .bind(exit_price)
         .bind(stop_loss_price)
+        .bind(target_price)
+        .bind(max_favorable_price)
         .bind(&entry_time)
         .bind(&exit_time)
         .bind(fees)
@@ -196,23 +535,379 @@ impl TradeRepository {
         .bind(status.as_str())
         .bind(now)
         .bind(id)
+        .execute(&mut *conn)
+        .await?;
+
+        Self::get_by_id_in(conn, id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Closed trades in unlocked periods still missing a best or worst price
+    pub async fn get_ids_missing_excursions(
+        pool: &SqlitePool,
+        user_id: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT t.id FROM trades t
+            WHERE t.user_id = ? AND t.status = 'closed'
+              AND (t.max_favorable_price IS NULL OR t.max_adverse_price IS NULL)
+              AND NOT EXISTS(SELECT 1 FROM period_locks pl
+                             WHERE pl.account_id = t.account_id
+                               AND t.trade_date <= pl.locked_through)
+            ORDER BY t.trade_date ASC
+            "#
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Fill in the best and worst prices reached, keeping values that were already recorded
+    pub async fn fill_excursions(
+        pool: &SqlitePool,
+        id: &str,
+        max_favorable_price: f64,
+        max_adverse_price: f64,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE trades SET
+                max_favorable_price = COALESCE(max_favorable_price, ?),
+                max_adverse_price = COALESCE(max_adverse_price, ?),
+                updated_at = ?
+            WHERE id = ?
+            "#
+        )
+        .bind(max_favorable_price)
+        .bind(max_adverse_price)
+        .bind(Utc::now())
+        .bind(id)
         .execute(pool)
         .await?;
 
-        Self::get_by_id(pool, id).await?.ok_or(sqlx::Error::RowNotFound)
+        Ok(result.rows_affected() > 0)
     }
 
-    /// Delete a trade
-    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM trades WHERE id = ?")
+    /// Get IDs of unlocked trades without an ATR at entry, oldest first
+    pub async fn get_ids_missing_entry_atr(
+        pool: &SqlitePool,
+        user_id: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT t.id FROM trades t
+            WHERE t.user_id = ? AND t.entry_atr IS NULL
+              AND NOT EXISTS(SELECT 1 FROM period_locks pl
+                             WHERE pl.account_id = t.account_id
+                               AND t.trade_date <= pl.locked_through)
+            ORDER BY t.trade_date ASC
+            "#
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Fill in the ATR at entry, keeping a value that was already recorded
+    pub async fn fill_entry_atr(pool: &SqlitePool, id: &str, entry_atr: f64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE trades SET entry_atr = COALESCE(entry_atr, ?), updated_at = ? WHERE id = ?"
+        )
+        .bind(entry_atr)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Set or clear the manual result override of a trade
+    /// Returns false if no trade with the given id exists
+    pub async fn set_result_override(
+        conn: &mut SqliteConnection,
+        id: &str,
+        result_override: Option<TradeResult>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE trades SET result_override = ?, updated_at = ? WHERE id = ?")
+            .bind(result_override.map(|r| r.as_str()))
+            .bind(Utc::now())
             .bind(id)
-            .execute(pool)
+            .execute(conn)
             .await?;
-        Ok(())
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Set or clear how an option trade ended at expiration
+    /// Returns false if no trade with the given id exists
+    pub async fn set_option_outcome(
+        conn: &mut SqliteConnection,
+        id: &str,
+        option_outcome: Option<OptionOutcome>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE trades SET option_outcome = ?, updated_at = ? WHERE id = ?")
+            .bind(option_outcome.map(|o| o.as_str()))
+            .bind(Utc::now())
+            .bind(id)
+            .execute(conn)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Set or clear the borrow availability and note of a short trade
+    /// Returns false if no trade with the given id exists
+    pub async fn set_borrow_availability(
+        pool: &SqlitePool,
+        id: &str,
+        availability: Option<BorrowAvailability>,
+        note: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE trades SET borrow_availability = ?, borrow_note = ?, updated_at = ? WHERE id = ?"
+        )
+        .bind(availability.map(|a| a.as_str()))
+        .bind(note)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark or unmark a position as carried into a continuation trade, setting the status it ends up with
+    pub async fn set_roll(
+        conn: &mut SqliteConnection,
+        id: &str,
+        roll_type: Option<RollType>,
+        rolled_on: Option<NaiveDate>,
+        continuation_trade_id: Option<&str>,
+        status: Status,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE trades
+            SET roll_type = ?, rolled_on = ?, continuation_trade_id = ?, status = ?, updated_at = ?
+            WHERE id = ?
+            "#
+        )
+        .bind(roll_type.map(|r| r.as_str()))
+        .bind(rolled_on)
+        .bind(continuation_trade_id)
+        .bind(status.as_str())
+        .bind(Utc::now())
+        .bind(id)
+        .execute(conn)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
     }
 
-    /// Get executions for a trade
+    /// Delete a trade
     #[cfg(test)]
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<u64, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        let deleted = Self::delete_in(&mut tx, id).await?;
+        tx.commit().await?;
+        Ok(deleted)
+    }
+
+    /// Delete a trade and its dependent rows on a connection, e.g. within a caller's transaction
+    pub async fn delete_in(conn: &mut SqliteConnection, id: &str) -> Result<u64, sqlx::Error> {
+        // Remove dependent rows explicitly so nothing is orphaned on a connection
+        // without foreign key enforcement
+        for table in DEPENDENT_TABLES {
+            sqlx::query(&format!("DELETE FROM {} WHERE trade_id = ?", table))
+                .bind(id)
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        // A rolled position whose continuation is deleted keeps its roll without the link
+        sqlx::query("UPDATE trades SET continuation_trade_id = NULL WHERE continuation_trade_id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        // Dividends outlive the trade they were attributed to
+        sqlx::query("UPDATE dividends SET trade_id = NULL WHERE trade_id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        let result = sqlx::query("DELETE FROM trades WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Get the date of the last exit execution for each of a user's trades
+    pub async fn get_last_exit_dates(
+        pool: &SqlitePool,
+        user_id: &str,
+    ) -> Result<HashMap<String, NaiveDate>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT e.trade_id, MAX(e.execution_date) AS last_exit_date
+            FROM trade_executions e
+            JOIN trades t ON e.trade_id = t.id
+            WHERE t.user_id = ? AND e.execution_type = 'exit'
+            GROUP BY e.trade_id
+            "#
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| (row.get("trade_id"), row.get("last_exit_date")))
+            .collect())
+    }
+
+    /// Get the tag names of each of a user's tagged trades, sorted
+    pub async fn get_tag_names(
+        pool: &SqlitePool,
+        user_id: &str,
+    ) -> Result<HashMap<String, Vec<String>>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT tt.trade_id, tg.name
+            FROM trade_tags tt
+            JOIN tags tg ON tt.tag_id = tg.id
+            JOIN trades t ON tt.trade_id = t.id
+            WHERE t.user_id = ?
+            ORDER BY tg.name
+            "#
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        let mut by_trade: HashMap<String, Vec<String>> = HashMap::new();
+        for row in &rows {
+            by_trade.entry(row.get("trade_id")).or_default().push(row.get("name"));
+        }
+        Ok(by_trade)
+    }
+
+    /// Get executions with their trade context, filtered by the execution date
+    pub async fn get_execution_fills(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<ExecutionFill>, sqlx::Error> {
+        let mut query = String::from(
+            r#"
+            SELECT e.trade_id, t.account_id, i.symbol, i.asset_class, i.multiplier AS contract_multiplier, i.tick_size, t.fx_rate, t.direction, t.entry_price, e.execution_type,
+                   e.execution_date, e.execution_time, e.quantity, e.price,
+                   e.fees, e.exchange, e.intended_price, e.order_type
+            FROM trade_executions e
+            JOIN trades t ON e.trade_id = t.id
+            JOIN instruments i ON t.instrument_id = i.id
+            WHERE t.user_id = ?
+            "#
+        );
+
+        if account_id.is_some() {
+            query.push_str(" AND t.account_id = ?");
+        }
+        if start_date.is_some() {
+            query.push_str(" AND e.execution_date >= ?");
+        }
+        if end_date.is_some() {
+            query.push_str(" AND e.execution_date <= ?");
+        }
+
+        query.push_str(" ORDER BY e.execution_date ASC, e.execution_time ASC, e.execution_type ASC");
+
+        let mut q = sqlx::query(&query).bind(user_id);
+
+        if let Some(acc) = account_id {
+            q = q.bind(acc);
+        }
+        if let Some(start) = start_date {
+            q = q.bind(start);
+        }
+        if let Some(end) = end_date {
+            q = q.bind(end);
+        }
+
+        let rows = q.fetch_all(pool).await?;
+        Ok(rows.iter().map(|row| ExecutionFill {
+            trade_id: row.get("trade_id"),
+            account_id: row.get("account_id"),
+            symbol: row.get("symbol"),
+            asset_class: row.get::<Option<&str>, _>("asset_class")
+                .and_then(AssetClass::from_str)
+                .unwrap_or(AssetClass::Stock),
+            contract_multiplier: row.get("contract_multiplier"),
+            tick_size: row.get("tick_size"),
+            fx_rate: row.get("fx_rate"),
+            direction: Direction::from_str(row.get::<&str, _>("direction")).unwrap_or(Direction::Long),
+            trade_entry_price: row.get("entry_price"),
+            execution_type: row.get("execution_type"),
+            execution_date: row.get("execution_date"),
+            execution_time: row.get("execution_time"),
+            quantity: row.get("quantity"),
+            price: row.get("price"),
+            fees: row.get::<Option<f64>, _>("fees").unwrap_or(0.0),
+            exchange: row.get("exchange"),
+            intended_price: row.get("intended_price"),
+            order_type: row.get::<Option<&str>, _>("order_type").and_then(OrderType::from_str),
+        }).collect())
+    }
+
+    /// Get the trade one of a user's executions belongs to
+    pub async fn get_execution_trade_id(
+        pool: &SqlitePool,
+        user_id: &str,
+        execution_id: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT e.trade_id FROM trade_executions e
+            JOIN trades t ON e.trade_id = t.id
+            WHERE e.id = ? AND t.user_id = ?
+            "#
+        )
+        .bind(execution_id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Set the intended price and order type of an execution
+    /// Returns false if the user has no execution with the given id
+    pub async fn update_execution_quality(
+        pool: &SqlitePool,
+        user_id: &str,
+        execution_id: &str,
+        intended_price: Option<f64>,
+        order_type: Option<OrderType>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE trade_executions SET intended_price = ?, order_type = ?
+            WHERE id = ? AND trade_id IN (SELECT id FROM trades WHERE user_id = ?)
+            "#
+        )
+        .bind(intended_price)
+        .bind(order_type.map(|o| o.as_str()))
+        .bind(execution_id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Get executions for a trade, oldest first
     pub async fn get_executions(pool: &SqlitePool, trade_id: &str) -> Result<Vec<TradeExecutionRecord>, sqlx::Error> {
         let rows = sqlx::query(
             r#"
@@ -227,7 +922,49 @@ impl TradeRepository {
         .fetch_all(pool)
         .await?;
 
-        Ok(rows.iter().map(|row| TradeExecutionRecord {
+        Ok(rows.iter().map(Self::row_to_execution_record).collect())
+    }
+
+    /// Get executions of several trades by trade ID, oldest first
+    /// Archived trades keep their executions in the archive database.
+    pub async fn get_executions_for_trades(
+        pool: &SqlitePool,
+        trade_ids: &[String],
+        archived: bool,
+    ) -> Result<HashMap<String, Vec<TradeExecutionRecord>>, sqlx::Error> {
+        // Stay well below SQLite's limit on bound parameters
+        const CHUNK_SIZE: usize = 500;
+
+        let schema = if archived { ARCHIVE_SCHEMA } else { "main" };
+        let mut executions: HashMap<String, Vec<TradeExecutionRecord>> = HashMap::new();
+
+        for chunk in trade_ids.chunks(CHUNK_SIZE) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let sql = format!(
+                r#"
+                SELECT id, trade_id, execution_type, execution_date, execution_time,
+                       quantity, price, fees
+                FROM {schema}.trade_executions
+                WHERE trade_id IN ({placeholders})
+                ORDER BY execution_date ASC, execution_time ASC
+                "#
+            );
+            let mut query = sqlx::query(&sql);
+            for trade_id in chunk {
+                query = query.bind(trade_id);
+            }
+
+            for row in query.fetch_all(pool).await? {
+                let record = Self::row_to_execution_record(&row);
+                executions.entry(record.trade_id.clone()).or_default().push(record);
+            }
+        }
+
+        Ok(executions)
+    }
+
+    fn row_to_execution_record(row: &sqlx::sqlite::SqliteRow) -> TradeExecutionRecord {
+        TradeExecutionRecord {
             id: row.get("id"),
             trade_id: row.get("trade_id"),
             execution_type: row.get("execution_type"),
@@ -236,7 +973,34 @@ impl TradeRepository {
             quantity: row.get("quantity"),
             price: row.get("price"),
             fees: row.get("fees"),
-        }).collect())
+        }
+    }
+
+    /// Overwrite an entry execution with the trade's entry fields
+    pub async fn sync_entry_execution(
+        conn: &mut SqliteConnection,
+        execution_id: &str,
+        execution_date: NaiveDate,
+        execution_time: Option<&str>,
+        quantity: f64,
+        price: f64,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE trade_executions
+            SET execution_date = ?, execution_time = ?, quantity = ?, price = ?
+            WHERE id = ? AND execution_type = 'entry'
+            "#
+        )
+        .bind(execution_date)
+        .bind(execution_time)
+        .bind(quantity)
+        .bind(price)
+        .bind(execution_id)
+        .execute(conn)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
     }
 
     /// Convert a database row to Trade struct
@@ -250,20 +1014,40 @@ impl TradeRepository {
             asset_class: row.get::<Option<&str>, _>("asset_class")
                 .and_then(AssetClass::from_str)
                 .unwrap_or(AssetClass::Stock),
+            contract_multiplier: row.get("contract_multiplier"),
+            fx_rate: row.get("fx_rate"),
             trade_number: row.get("trade_number"),
+            ref_code: row.get("ref_code"),
             trade_date: row.get("trade_date"),
             direction: Direction::from_str(row.get::<&str, _>("direction")).unwrap_or(Direction::Long),
             quantity: row.get("quantity"),
             entry_price: row.get("entry_price"),
             exit_price: row.get("exit_price"),
             stop_loss_price: row.get("stop_loss_price"),
+            target_price: row.get("target_price"),
+            max_favorable_price: row.get("max_favorable_price"),
+            max_adverse_price: row.get("max_adverse_price"),
+            entry_atr: row.get("entry_atr"),
             entry_time: row.get("entry_time"),
             exit_time: row.get("exit_time"),
+            exit_date: row.get("exit_date"),
             fees: row.get::<f64, _>("fees"),
+            carrying_costs: row.get("carrying_costs"),
+            dividends: row.get("dividends"),
             strategy: row.get("strategy"),
             notes: row.get("notes"),
             screenshot_url: row.get("screenshot_url"),
             status: Status::from_str(row.get::<&str, _>("status")).unwrap_or(Status::Closed),
+            result_override: row.get::<Option<&str>, _>("result_override").and_then(TradeResult::from_str),
+            roll_type: row.get::<Option<&str>, _>("roll_type").and_then(RollType::from_str),
+            rolled_on: row.get("rolled_on"),
+            continuation_trade_id: row.get("continuation_trade_id"),
+            option_outcome: row.get::<Option<&str>, _>("option_outcome").and_then(OptionOutcome::from_str),
+            borrow_availability: row
+                .get::<Option<&str>, _>("borrow_availability")
+                .and_then(BorrowAvailability::from_str),
+            borrow_note: row.get("borrow_note"),
+            is_locked: row.get("is_locked"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
         }
@@ -324,6 +1108,8 @@ mod tests {
             entry_price: 400.0,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -397,7 +1183,7 @@ mod tests {
                 .unwrap();
         }
 
-        let trades = TradeRepository::get_trades(&pool, &user_id, None, None, None, None)
+        let trades = TradeRepository::get_trades(&pool, &user_id, &TradeFilter::default())
             .await
             .expect("Failed to get trades");
 
@@ -437,7 +1223,11 @@ mod tests {
             .unwrap();
 
         // Filter by first account
-        let trades = TradeRepository::get_trades(&pool, &user_id, Some(&account_id), None, None, None)
+        let filter = TradeFilter {
+            account_id: Some(account_id.clone()),
+            ..Default::default()
+        };
+        let trades = TradeRepository::get_trades(&pool, &user_id, &filter)
             .await
             .expect("Failed to get trades");
 
@@ -468,7 +1258,12 @@ mod tests {
         let start = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
         let end = NaiveDate::from_ymd_opt(2024, 1, 20).unwrap();
 
-        let trades = TradeRepository::get_trades(&pool, &user_id, None, Some(start), Some(end), None)
+        let filter = TradeFilter {
+            start_date: Some(start),
+            end_date: Some(end),
+            ..Default::default()
+        };
+        let trades = TradeRepository::get_trades(&pool, &user_id, &filter)
             .await
             .expect("Failed to get trades");
 
@@ -500,7 +1295,11 @@ mod tests {
             .unwrap();
 
         // Filter by closed status
-        let closed_trades = TradeRepository::get_trades(&pool, &user_id, None, None, None, Some(Status::Closed))
+        let filter = TradeFilter {
+            status: Some(Status::Closed),
+            ..Default::default()
+        };
+        let closed_trades = TradeRepository::get_trades(&pool, &user_id, &filter)
             .await
             .expect("Failed to get trades");
 
@@ -508,7 +1307,11 @@ mod tests {
         assert_eq!(closed_trades[0].status, Status::Closed);
 
         // Filter by open status
-        let open_trades = TradeRepository::get_trades(&pool, &user_id, None, None, None, Some(Status::Open))
+        let filter = TradeFilter {
+            status: Some(Status::Open),
+            ..Default::default()
+        };
+        let open_trades = TradeRepository::get_trades(&pool, &user_id, &filter)
             .await
             .expect("Failed to get trades");
 
@@ -516,6 +1319,71 @@ mod tests {
         assert_eq!(open_trades[0].status, Status::Open);
     }
 
+    #[tokio::test]
+    async fn test_get_trades_filter_by_derived_fields() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let instrument = InstrumentRepository::get_or_create(&pool, "MSFT")
+            .await
+            .unwrap();
+
+        // Default input: 1R win held 09:30-10:45
+        let one_r = create_test_trade_input(&account_id, "MSFT");
+
+        let mut three_r_scalp = create_test_trade_input(&account_id, "MSFT");
+        three_r_scalp.exit_price = Some(165.0);
+        three_r_scalp.exit_time = Some("09:50".to_string());
+
+        let mut stopped_out = create_test_trade_input(&account_id, "MSFT");
+        stopped_out.exit_price = Some(145.0);
+        stopped_out.exit_time = Some("10:00".to_string());
+
+        let mut no_stop_or_times = create_test_trade_input(&account_id, "MSFT");
+        no_stop_or_times.exit_price = Some(160.0);
+        no_stop_or_times.stop_loss_price = None;
+        no_stop_or_times.entry_time = None;
+        no_stop_or_times.exit_time = None;
+
+        for (i, input) in [one_r, three_r_scalp, stopped_out, no_stop_or_times].iter_mut().enumerate() {
+            input.trade_number = Some(i as i32 + 1);
+            TradeRepository::insert(&pool, &user_id, &instrument.id, input)
+                .await
+                .unwrap();
+        }
+
+        let wins = TradeFilter {
+            result: Some(TradeResult::Win),
+            ..Default::default()
+        };
+        let trades = TradeRepository::get_trades(&pool, &user_id, &wins).await.unwrap();
+        assert_eq!(trades.len(), 3);
+
+        let big_winners = TradeFilter {
+            min_r_multiple: Some(2.0),
+            ..Default::default()
+        };
+        let trades = TradeRepository::get_trades(&pool, &user_id, &big_winners).await.unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].exit_price, Some(165.0));
+
+        let quick = TradeFilter {
+            max_duration_minutes: Some(30.0),
+            ..Default::default()
+        };
+        let trades = TradeRepository::get_trades(&pool, &user_id, &quick).await.unwrap();
+        assert_eq!(trades.len(), 2); // The 20 and 30 minute trades
+
+        let quick_wins = TradeFilter {
+            result: Some(TradeResult::Win),
+            max_duration_minutes: Some(30.0),
+            ..Default::default()
+        };
+        let stats = TradeRepository::get_trade_stats(&pool, &user_id, &quick_wins).await.unwrap();
+        assert_eq!(stats.trade_count, 1);
+        assert!((stats.total_pnl - 1490.0).abs() < 0.01); // 15 × 100 − 10 fees
+    }
+
     #[tokio::test]
     async fn test_get_trades_ordering() {
         let pool = create_test_db().await;
@@ -535,7 +1403,7 @@ mod tests {
                 .unwrap();
         }
 
-        let trades = TradeRepository::get_trades(&pool, &user_id, None, None, None, None)
+        let trades = TradeRepository::get_trades(&pool, &user_id, &TradeFilter::default())
             .await
             .expect("Failed to get trades");
 
@@ -572,6 +1440,8 @@ mod tests {
             entry_price: None,
             exit_price: Some(160.0), // Changed
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(15.0), // Changed
@@ -621,6 +1491,8 @@ mod tests {
             entry_price: None,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -656,14 +1528,72 @@ mod tests {
         assert!(TradeRepository::get_by_id(&pool, &trade.id).await.unwrap().is_some());
 
         // Delete trade
-        TradeRepository::delete(&pool, &trade.id)
+        let deleted = TradeRepository::delete(&pool, &trade.id)
             .await
             .expect("Failed to delete trade");
+        assert_eq!(deleted, 1);
 
         // Verify trade is deleted
         assert!(TradeRepository::get_by_id(&pool, &trade.id).await.unwrap().is_none());
     }
 
+    #[tokio::test]
+    async fn test_delete_trade_removes_dependents_without_foreign_keys() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let instrument = InstrumentRepository::get_or_create(&pool, "AAPL")
+            .await
+            .unwrap();
+        let input = create_test_trade_input(&account_id, "AAPL");
+        let trade = TradeRepository::insert(&pool, &user_id, &instrument.id, &input)
+            .await
+            .unwrap();
+
+        // Cascades must not depend on the connection's pragma
+        sqlx::query("PRAGMA foreign_keys = OFF").execute(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO trade_executions (id, trade_id, execution_type, execution_date, quantity, price)
+             VALUES ('exec-1', ?, 'entry', '2024-01-15', 100, 150)"
+        )
+        .bind(&trade.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO tags (id, user_id, name) VALUES ('tag-1', ?, 'breakout')")
+            .bind(&user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO trade_tags (trade_id, tag_id) VALUES (?, 'tag-1')")
+            .bind(&trade.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO trade_links (id, trade_id, type, url) VALUES ('link-1', ?, 'tradingview', 'https://example.com')")
+            .bind(&trade.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let deleted = TradeRepository::delete(&pool, &trade.id).await.unwrap();
+        assert_eq!(deleted, 1);
+
+        for table in DEPENDENT_TABLES {
+            let remaining: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {} WHERE trade_id = ?", table))
+                .bind(&trade.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(remaining, 0, "orphaned rows in {}", table);
+        }
+
+        // The tag itself is kept for other trades
+        let tags: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tags").fetch_one(&pool).await.unwrap();
+        assert_eq!(tags, 1);
+    }
+
     #[tokio::test]
     async fn test_get_trades_user_isolation() {
         let pool = create_test_db().await;
@@ -719,10 +1649,10 @@ mod tests {
             .unwrap();
 
         // Verify user isolation
-        let user1_trades = TradeRepository::get_trades(&pool, "user1", None, None, None, None)
+        let user1_trades = TradeRepository::get_trades(&pool, "user1", &TradeFilter::default())
             .await
             .unwrap();
-        let user2_trades = TradeRepository::get_trades(&pool, "user2", None, None, None, None)
+        let user2_trades = TradeRepository::get_trades(&pool, "user2", &TradeFilter::default())
             .await
             .unwrap();
 
@@ -752,6 +1682,8 @@ mod tests {
             entry_price: 200.0,
             exit_price: Some(180.0),
             stop_loss_price: Some(210.0),
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(5.0),
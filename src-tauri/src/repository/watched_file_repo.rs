@@ -0,0 +1,45 @@
+use chrono::Utc;
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+
+pub struct WatchedFileRepository;
+
+impl WatchedFileRepository {
+    /// Get the size and modification time a file had when it was last processed, with the error
+    /// importing it failed with
+    pub async fn get_version(pool: &SqlitePool, path: &str) -> Result<Option<(i64, i64, Option<String>)>, sqlx::Error> {
+        let row = sqlx::query("SELECT size, modified_ms, error FROM watched_files WHERE path = ?")
+            .bind(path)
+            .fetch_optional(pool)
+            .await?;
+        Ok(row.map(|r| (r.get("size"), r.get("modified_ms"), r.get("error"))))
+    }
+
+    /// Record that a version of a file was processed, replacing an earlier version's record
+    pub async fn record(
+        pool: &SqlitePool,
+        user_id: &str,
+        path: &str,
+        size: i64,
+        modified_ms: i64,
+        import_batch_id: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO watched_files (path, user_id, size, modified_ms, import_batch_id, error, processed_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(path)
+        .bind(user_id)
+        .bind(size)
+        .bind(modified_ms)
+        .bind(import_batch_id)
+        .bind(error)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use std::time::{Duration, SystemTime};
+use chrono::{Local, Utc};
+use sqlx::sqlite::SqlitePool;
+use tauri::{AppHandle, Emitter};
+use crate::services::{JournalService, MaintenanceService, MetricSnapshotService, ReminderService, WatchFolderService};
+use crate::write_queue::WriteQueue;
+
+/// Event carrying a `DueReminder`; the frontend shows it as a notification
+pub const REMINDER_EVENT: &str = "reminder-due";
+/// Event carrying a day's `EndOfDaySummary`, prompting a review of its draft journal entry
+pub const END_OF_DAY_SUMMARY_EVENT: &str = "end-of-day-summary";
+/// Event carrying the `MaintenanceReport` of a scheduled maintenance run
+pub const MAINTENANCE_EVENT: &str = "maintenance-completed";
+/// Event carrying the `WatchedImport` of each file imported from the watched folder
+pub const WATCHED_IMPORT_EVENT: &str = "watched-file-imported";
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Check reminders, the end-of-day summary, metric snapshots, scheduled maintenance and the watched import folder on app start and then every minute while the app runs
+/// Each check writes what it took or ran, so it waits its turn behind mutating commands.
+pub async fn run(app: AppHandle, pool: SqlitePool, user_id: String, writes: WriteQueue) {
+    loop {
+        let now = Local::now().naive_local();
+
+        // A failed check is logged and retried on the next tick
+        match writes.run(ReminderService::take_due(&pool, now)).await {
+            Ok(due) => {
+                for reminder in due {
+                    let _ = app.emit(REMINDER_EVENT, reminder);
+                }
+            }
+            Err(e) => log::warn!("Reminder check failed: {}", e),
+        }
+        match writes.run(JournalService::take_due_summaries(&pool, &user_id, now)).await {
+            Ok(summaries) => {
+                for summary in summaries {
+                    let _ = app.emit(END_OF_DAY_SUMMARY_EVENT, summary);
+                }
+            }
+            Err(e) => log::warn!("End-of-day summary check failed: {}", e),
+        }
+        if let Err(e) = writes.run(MetricSnapshotService::take_due(&pool, &user_id, now.date())).await {
+            log::warn!("Metric snapshots failed: {}", e);
+        }
+        match writes.run(MaintenanceService::run_if_due(&pool, Utc::now())).await {
+            Ok(Some(report)) => {
+                let _ = app.emit(MAINTENANCE_EVENT, report);
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("Scheduled maintenance failed: {}", e),
+        }
+        match writes.run(WatchFolderService::scan(&pool, &user_id, SystemTime::now())).await {
+            Ok(imports) => {
+                for import in imports {
+                    let _ = app.emit(WATCHED_IMPORT_EVENT, import);
+                }
+            }
+            Err(e) => log::warn!("Watched folder import failed: {}", e),
+        }
+
+        tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+}
@@ -0,0 +1,378 @@
+use chrono::{Datelike, NaiveDate};
+use sqlx::sqlite::SqlitePool;
+use crate::models::{
+    Account, CashTransaction, CommissionSchedule, CreateCashTransactionInput, CreateFinancingChargeInput, CreateSeedPositionInput, FinancingCharge,
+    FinancingChargeSource, FinancingChargeType, FinancingImportResult, PeriodLock, SeedPosition,
+};
+use crate::repository::{AccountRepository, CashTransactionRepository, FinancingChargeRepository, MetricSnapshotRepository, SeedPositionRepository};
+
+pub struct AccountService;
+
+impl AccountService {
+    /// Lock an account's trades through the given date
+    pub async fn lock_period(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: &str,
+        through_date: NaiveDate,
+    ) -> Result<PeriodLock, String> {
+        Self::get_owned_account(pool, user_id, account_id).await?;
+
+        AccountRepository::lock_period(pool, account_id, through_date)
+            .await
+            .map_err(|e| format!("Failed to lock period: {}", e))
+    }
+
+    /// Unlock an account's finalized period so its trades can be edited again
+    pub async fn unlock_period(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: &str,
+    ) -> Result<(), String> {
+        Self::get_owned_account(pool, user_id, account_id).await?;
+
+        AccountRepository::unlock_period(pool, account_id)
+            .await
+            .map_err(|e| format!("Failed to unlock period: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Get period locks for all of a user's accounts
+    pub async fn get_period_locks(pool: &SqlitePool, user_id: &str) -> Result<Vec<PeriodLock>, String> {
+        AccountRepository::get_period_locks(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get period locks: {}", e))
+    }
+
+    /// Get an account's commission schedule, or an empty one if none is set
+    pub async fn get_commission_schedule(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: &str,
+    ) -> Result<CommissionSchedule, String> {
+        Self::get_owned_account(pool, user_id, account_id).await?;
+
+        let schedule = AccountRepository::get_commission_schedule(pool, account_id)
+            .await
+            .map_err(|e| format!("Failed to get commission schedule: {}", e))?;
+
+        Ok(schedule.unwrap_or_else(|| CommissionSchedule {
+            account_id: account_id.to_string(),
+            ..Default::default()
+        }))
+    }
+
+    /// Save an account's commission schedule
+    pub async fn save_commission_schedule(
+        pool: &SqlitePool,
+        user_id: &str,
+        schedule: CommissionSchedule,
+    ) -> Result<CommissionSchedule, String> {
+        Self::get_owned_account(pool, user_id, &schedule.account_id).await?;
+
+        let rates = [
+            schedule.per_order,
+            schedule.per_share,
+            schedule.per_contract,
+            schedule.minimum_per_order,
+        ];
+        if rates.iter().any(|r| !r.is_finite() || *r < 0.0) {
+            return Err("Commission rates must be zero or positive".to_string());
+        }
+
+        AccountRepository::save_commission_schedule(pool, &schedule)
+            .await
+            .map_err(|e| format!("Failed to save commission schedule: {}", e))
+    }
+
+    /// Declare a position that was already open when the imported history starts
+    pub async fn add_seed_position(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: CreateSeedPositionInput,
+    ) -> Result<SeedPosition, String> {
+        Self::get_owned_account(pool, user_id, &input.account_id).await?;
+
+        if input.symbol.trim().is_empty() {
+            return Err("Symbol is required".to_string());
+        }
+        if input.quantity <= 0.0 || !input.quantity.is_finite() {
+            return Err("Quantity must be positive".to_string());
+        }
+        if input.avg_cost < 0.0 || !input.avg_cost.is_finite() {
+            return Err("Average cost cannot be negative".to_string());
+        }
+        if input.open_date > input.as_of_date {
+            return Err("Open date cannot be after the as-of date".to_string());
+        }
+
+        SeedPositionRepository::insert(pool, user_id, &input)
+            .await
+            .map_err(|e| format!("Failed to add seed position: {}", e))
+    }
+
+    /// Get seed positions, optionally for a single account
+    pub async fn get_seed_positions(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+    ) -> Result<Vec<SeedPosition>, String> {
+        SeedPositionRepository::get_seed_positions(pool, user_id, account_id)
+            .await
+            .map_err(|e| format!("Failed to get seed positions: {}", e))
+    }
+
+    /// Delete a seed position
+    pub async fn delete_seed_position(pool: &SqlitePool, user_id: &str, id: &str) -> Result<(), String> {
+        let deleted = SeedPositionRepository::delete(pool, user_id, id)
+            .await
+            .map_err(|e| format!("Failed to delete seed position: {}", e))?;
+
+        if !deleted {
+            return Err("Seed position not found".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Record a margin interest or financing charge for an account month
+    pub async fn add_financing_charge(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: CreateFinancingChargeInput,
+    ) -> Result<FinancingCharge, String> {
+        Self::get_owned_account(pool, user_id, &input.account_id).await?;
+        Self::validate_financing_charge(&input)?;
+        Self::ensure_month_unlocked(pool, &input.account_id, input.charge_month).await?;
+
+        FinancingChargeRepository::insert(pool, user_id, &input, FinancingChargeSource::Manual)
+            .await
+            .map_err(|e| format!("Failed to add financing charge: {}", e))
+    }
+
+    /// Get financing charges, optionally for a single account and month range
+    pub async fn get_financing_charges(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<FinancingCharge>, String> {
+        FinancingChargeRepository::get_charges(pool, user_id, account_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get financing charges: {}", e))
+    }
+
+    /// Delete a financing charge
+    pub async fn delete_financing_charge(pool: &SqlitePool, user_id: &str, id: &str) -> Result<(), String> {
+        let charge = FinancingChargeRepository::get_by_id(pool, user_id, id)
+            .await
+            .map_err(|e| format!("Failed to get financing charge: {}", e))?
+            .ok_or_else(|| "Financing charge not found".to_string())?;
+        Self::ensure_month_unlocked(pool, &charge.account_id, charge.charge_month).await?;
+
+        FinancingChargeRepository::delete(pool, user_id, id)
+            .await
+            .map_err(|e| format!("Failed to delete financing charge: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Import an account's financing charges from CSV lines: month,type,amount,notes
+    /// Month is YYYY-MM or a date in the month; a header line is skipped and notes may be empty.
+    /// Re-importing a month replaces the earlier import of the same type; manual charges are kept.
+    pub async fn import_financing_csv(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: &str,
+        content: &str,
+    ) -> Result<FinancingImportResult, String> {
+        Self::get_owned_account(pool, user_id, account_id).await?;
+        let mut result = FinancingImportResult::default();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (index == 0 && line.to_lowercase().starts_with("month")) {
+                continue;
+            }
+
+            let input = match Self::parse_financing_line(account_id, line)
+                .and_then(|input| Self::validate_financing_charge(&input).map(|_| input))
+            {
+                Ok(input) => input,
+                Err(e) => {
+                    result.errors.push(format!("Line {}: {}", index + 1, e));
+                    continue;
+                }
+            };
+            if let Err(e) = Self::ensure_month_unlocked(pool, account_id, input.charge_month).await {
+                result.errors.push(format!("Line {}: {}", index + 1, e));
+                continue;
+            }
+
+            let (_, replaced) = FinancingChargeRepository::upsert_imported(pool, user_id, &input)
+                .await
+                .map_err(|e| format!("Failed to import financing charges: {}", e))?;
+            result.imported += 1;
+            if replaced {
+                result.replaced += 1;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Record a deposit (positive amount) or withdrawal (negative amount)
+    pub async fn add_cash_transaction(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: CreateCashTransactionInput,
+    ) -> Result<CashTransaction, String> {
+        Self::get_owned_account(pool, user_id, &input.account_id).await?;
+
+        if input.amount == 0.0 || !input.amount.is_finite() {
+            return Err("Amount must be a non-zero number".to_string());
+        }
+        Self::ensure_date_unlocked(pool, &input.account_id, input.transaction_date).await?;
+
+        let transaction = CashTransactionRepository::insert(pool, user_id, &input)
+            .await
+            .map_err(|e| format!("Failed to add cash transaction: {}", e))?;
+        Self::clear_snapshots(pool, &transaction.account_id, Some(transaction.transaction_date)).await?;
+        Ok(transaction)
+    }
+
+    /// Get deposits and withdrawals, optionally for a single account and date range
+    pub async fn get_cash_transactions(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<CashTransaction>, String> {
+        CashTransactionRepository::get_transactions(pool, user_id, account_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get cash transactions: {}", e))
+    }
+
+    /// Delete a deposit or withdrawal
+    pub async fn delete_cash_transaction(pool: &SqlitePool, user_id: &str, id: &str) -> Result<(), String> {
+        let transaction = CashTransactionRepository::get_by_id(pool, user_id, id)
+            .await
+            .map_err(|e| format!("Failed to get cash transaction: {}", e))?
+            .ok_or_else(|| "Cash transaction not found".to_string())?;
+        Self::ensure_date_unlocked(pool, &transaction.account_id, transaction.transaction_date).await?;
+
+        CashTransactionRepository::delete(pool, user_id, id)
+            .await
+            .map_err(|e| format!("Failed to delete cash transaction: {}", e))?;
+        Self::clear_snapshots(pool, &transaction.account_id, Some(transaction.transaction_date)).await?;
+
+        Ok(())
+    }
+
+    /// Set or clear an account's starting balance
+    pub async fn set_starting_balance(
+        pool: &SqlitePool,
+        user_id: &str,
+        id: &str,
+        starting_balance: Option<f64>,
+    ) -> Result<Account, String> {
+        let account = AccountRepository::set_starting_balance(pool, user_id, id, starting_balance)
+            .await
+            .map_err(|e| format!("Failed to update account: {}", e))?
+            .ok_or_else(|| format!("Account not found: {}", id))?;
+        // Every snapshot balance builds on it
+        Self::clear_snapshots(pool, id, None).await?;
+        Ok(account)
+    }
+
+    /// Delete an account's metric snapshots from a date on, whose balances a change made stale
+    async fn clear_snapshots(pool: &SqlitePool, account_id: &str, from_date: Option<NaiveDate>) -> Result<(), String> {
+        MetricSnapshotRepository::delete_from(pool, account_id, from_date)
+            .await
+            .map_err(|e| format!("Failed to clear metric snapshots: {}", e))
+    }
+
+    fn parse_financing_line(account_id: &str, line: &str) -> Result<CreateFinancingChargeInput, String> {
+        let fields: Vec<&str> = line.splitn(4, ',').map(str::trim).collect();
+        if fields.len() < 3 {
+            return Err("Expected month,type,amount,notes".to_string());
+        }
+
+        let charge_month = NaiveDate::parse_from_str(fields[0], "%Y-%m-%d")
+            .or_else(|_| NaiveDate::parse_from_str(&format!("{}-01", fields[0]), "%Y-%m-%d"))
+            .map_err(|e| format!("Invalid month '{}': {}", fields[0], e))?;
+        let charge_type = FinancingChargeType::from_str(fields[1])
+            .ok_or_else(|| format!("Invalid charge type: {}", fields[1]))?;
+        let amount: f64 = fields[2]
+            .parse()
+            .map_err(|_| format!("Invalid amount: {}", fields[2]))?;
+
+        Ok(CreateFinancingChargeInput {
+            account_id: account_id.to_string(),
+            charge_month,
+            charge_type,
+            amount,
+            notes: fields.get(3).filter(|s| !s.is_empty()).map(|s| s.to_string()),
+        })
+    }
+
+    fn validate_financing_charge(input: &CreateFinancingChargeInput) -> Result<(), String> {
+        if input.amount == 0.0 || !input.amount.is_finite() {
+            return Err("Amount must be a non-zero number".to_string());
+        }
+        Ok(())
+    }
+
+    /// A charge belongs to the whole month, so any locked day of it locks the charge
+    async fn ensure_month_unlocked(
+        pool: &SqlitePool,
+        account_id: &str,
+        charge_month: NaiveDate,
+    ) -> Result<(), String> {
+        let lock = AccountRepository::get_period_lock(pool, account_id)
+            .await
+            .map_err(|e| format!("Failed to check period lock: {}", e))?;
+        let month = charge_month.with_day(1).unwrap_or(charge_month);
+
+        match lock {
+            Some(lock) if month <= lock.locked_through => Err(format!(
+                "Month {} is in a locked period (locked through {}). Unlock the period to make changes.",
+                month.format("%Y-%m"), lock.locked_through
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    async fn ensure_date_unlocked(
+        pool: &SqlitePool,
+        account_id: &str,
+        date: NaiveDate,
+    ) -> Result<(), String> {
+        let lock = AccountRepository::get_period_lock(pool, account_id)
+            .await
+            .map_err(|e| format!("Failed to check period lock: {}", e))?;
+
+        match lock {
+            Some(lock) if date <= lock.locked_through => Err(format!(
+                "Date {} is in a locked period (locked through {}). Unlock the period to make changes.",
+                date, lock.locked_through
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    async fn get_owned_account(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: &str,
+    ) -> Result<Account, String> {
+        AccountRepository::get_by_id(pool, account_id)
+            .await
+            .map_err(|e| format!("Failed to get account: {}", e))?
+            .filter(|a| a.user_id == user_id)
+            .ok_or_else(|| format!("Account not found: {}", account_id))
+    }
+}
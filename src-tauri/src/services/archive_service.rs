@@ -0,0 +1,200 @@
+use chrono::{Datelike, NaiveDate};
+use sqlx::sqlite::SqlitePool;
+use crate::models::{ArchiveResult, ArchivedYear, TradeFilter, TradeWithDerived};
+use crate::repository::ArchiveRepository;
+use crate::services::{DailyPerformanceService, TradeService};
+
+pub struct ArchiveService;
+
+impl ArchiveService {
+    /// Move a year's closed trades to the archive database; open trades stay in the main one
+    /// Archived trades leave the daily performance summary, like every other metric. The main
+    /// database file only shrinks when `compact` is set, as VACUUM rewrites all of it.
+    pub async fn archive_year(pool: &SqlitePool, user_id: &str, year: i32, compact: bool) -> Result<u64, String> {
+        let moved = ArchiveRepository::move_year(pool, user_id, year, true)
+            .await
+            .map_err(|e| format!("Failed to archive trades: {}", e))?;
+        DailyPerformanceService::rebuild(pool, user_id).await?;
+        if compact && moved > 0 {
+            Self::compact(pool).await?;
+        }
+        Ok(moved)
+    }
+
+    /// Move an archived year back into the main database
+    pub async fn unarchive_year(pool: &SqlitePool, user_id: &str, year: i32) -> Result<u64, String> {
+        let moved = ArchiveRepository::move_year(pool, user_id, year, false)
+            .await
+            .map_err(|e| format!("Failed to unarchive trades: {}", e))?;
+        DailyPerformanceService::rebuild(pool, user_id).await?;
+        Ok(moved)
+    }
+
+    /// Archive every calendar year that ended more than `years` years before today
+    pub async fn archive_older_than(
+        pool: &SqlitePool,
+        user_id: &str,
+        years: i32,
+        today: NaiveDate,
+        compact: bool,
+    ) -> Result<ArchiveResult, String> {
+        if years < 1 {
+            return Err("Retention must be at least 1 year".to_string());
+        }
+
+        let archivable = ArchiveRepository::get_unarchived_years_before(pool, user_id, today.year() - years)
+            .await
+            .map_err(|e| format!("Failed to get trade years: {}", e))?;
+
+        let mut result = ArchiveResult { years: Vec::new(), trades_archived: 0 };
+        for year in archivable {
+            result.trades_archived += Self::archive_year(pool, user_id, year, false).await?;
+            result.years.push(year);
+        }
+        if compact && result.trades_archived > 0 {
+            Self::compact(pool).await?;
+        }
+        Ok(result)
+    }
+
+    async fn compact(pool: &SqlitePool) -> Result<(), String> {
+        ArchiveRepository::vacuum_main(pool)
+            .await
+            .map_err(|e| format!("Failed to compact database: {}", e))
+    }
+
+    pub async fn get_archived_years(pool: &SqlitePool, user_id: &str) -> Result<Vec<ArchivedYear>, String> {
+        ArchiveRepository::get_archived_years(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get archived years: {}", e))
+    }
+
+    /// Archived trades of a year, read from the archive database without moving them back
+    pub async fn get_archived_trades(
+        pool: &SqlitePool,
+        user_id: &str,
+        year: i32,
+    ) -> Result<Vec<TradeWithDerived>, String> {
+        let filter = TradeFilter {
+            start_date: NaiveDate::from_ymd_opt(year, 1, 1),
+            end_date: NaiveDate::from_ymd_opt(year, 12, 31),
+            archived: true,
+            ..Default::default()
+        };
+        TradeService::find_trades(pool, user_id, &filter).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreateCarryingCostInput, CreateDividendInput, CarryingCostType, Status};
+    use crate::services::settings_service::SettingsService;
+    use crate::services::{CarryingCostService, DividendService};
+    use crate::test_utils::{create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    #[tokio::test]
+    async fn test_archive_and_unarchive_year() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let mut old = create_test_trade_input(&account_id, "AAPL");
+        old.trade_date = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+        let old = TradeService::create_trade(&pool, &user_id, old).await.unwrap();
+        CarryingCostService::add_cost(&pool, &user_id, CreateCarryingCostInput {
+            trade_id: old.trade.id.clone(),
+            cost_type: CarryingCostType::BorrowFee,
+            cost_date: NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(),
+            amount: 10.0,
+            notes: None,
+        })
+        .await
+        .unwrap();
+        SettingsService::save_include_dividends_in_pnl(&pool, true).await.unwrap();
+        let dividend = DividendService::add_dividend(&pool, &user_id, CreateDividendInput {
+            account_id: account_id.clone(),
+            symbol: "AAPL".to_string(),
+            trade_id: Some(old.trade.id.clone()),
+            ex_date: NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(),
+            pay_date: None,
+            amount: 4.0,
+            notes: None,
+        })
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO tags (id, user_id, name) VALUES ('tag-1', ?, 'breakout')")
+            .bind(&user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO trade_tags (trade_id, tag_id) VALUES (?, 'tag-1')")
+            .bind(&old.trade.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let mut still_open = create_test_trade_input(&account_id, "MSFT");
+        still_open.trade_date = NaiveDate::from_ymd_opt(2021, 6, 1).unwrap();
+        still_open.exit_price = None;
+        still_open.exit_time = None;
+        still_open.status = Some(Status::Open);
+        TradeService::create_trade(&pool, &user_id, still_open).await.unwrap();
+
+        let recent = create_test_trade_input(&account_id, "NVDA"); // 2024
+        TradeService::create_trade(&pool, &user_id, recent).await.unwrap();
+
+        let today = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let result = ArchiveService::archive_older_than(&pool, &user_id, 2, today, true).await.unwrap();
+        assert_eq!(result.years, vec![2021]);
+        assert_eq!(result.trades_archived, 1);
+
+        // The open trade and the recent one stay in the main database
+        let hot = TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(hot.len(), 2);
+        assert!(hot.iter().all(|t| t.trade.id != old.trade.id));
+
+        // Archived trades are still queryable, carrying costs and dividends included
+        let archived = ArchiveService::get_archived_trades(&pool, &user_id, 2021).await.unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].trade.id, old.trade.id);
+        assert!((archived[0].net_pnl.unwrap() - (old.net_pnl.unwrap() - 10.0 + 4.0)).abs() < 0.01);
+
+        // Child rows moved with the trade, into tables built from the main schema
+        for table in ["main.trade_executions", "main.trade_tags", "main.trade_carrying_costs", "main.dividends"] {
+            let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {} WHERE trade_id = ?", table))
+                .bind(&old.trade.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(count, 0, "{} still has rows of the archived trade", table);
+        }
+        assert!(DividendService::get_trade_dividends(&pool, &user_id, &old.trade.id).await.unwrap().is_empty());
+        let archived_dividend: String = sqlx::query_scalar("SELECT id FROM archive.dividends WHERE trade_id = ?")
+            .bind(&old.trade.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(archived_dividend, dividend.id);
+        let ddl: String = sqlx::query_scalar("SELECT sql FROM archive.sqlite_master WHERE name = 'trades'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert!(ddl.contains("PRIMARY KEY"));
+        let indexes: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM archive.sqlite_master WHERE name = 'idx_trades_user_date'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(indexes, 1);
+        let years = ArchiveService::get_archived_years(&pool, &user_id).await.unwrap();
+        assert_eq!((years[0].year, years[0].trade_count), (2021, 1));
+
+        // Unarchiving restores the trade and its dependent rows
+        assert_eq!(ArchiveService::unarchive_year(&pool, &user_id, 2021).await.unwrap(), 1);
+        let restored = TradeService::get_trade(&pool, &user_id, &old.trade.id).await.unwrap().unwrap();
+        assert!((restored.net_pnl.unwrap() - archived[0].net_pnl.unwrap()).abs() < 0.01);
+        assert_eq!(DividendService::get_trade_dividends(&pool, &user_id, &old.trade.id).await.unwrap().len(), 1);
+        assert!(ArchiveService::get_archived_years(&pool, &user_id).await.unwrap().is_empty());
+
+        assert!(ArchiveService::archive_older_than(&pool, &user_id, 0, today, false).await.is_err());
+    }
+}
@@ -0,0 +1,205 @@
+use chrono::NaiveDate;
+use sqlx::sqlite::SqlitePool;
+use crate::calculations::calculate_carrying_cost_report;
+use crate::models::{CarryingCost, CarryingCostReport, CreateCarryingCostInput};
+use crate::repository::{CarryingCostRepository, TradeRepository};
+use crate::services::{DailyPerformanceService, TradeService};
+
+pub struct CarryingCostService;
+
+impl CarryingCostService {
+    /// Add a carrying cost entry to one of the user's trades
+    pub async fn add_cost(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: CreateCarryingCostInput,
+    ) -> Result<CarryingCost, String> {
+        if input.amount == 0.0 || !input.amount.is_finite() {
+            return Err("Amount must be a non-zero number".to_string());
+        }
+
+        let trade = TradeRepository::get_by_id(pool, &input.trade_id)
+            .await
+            .map_err(|e| format!("Failed to get trade: {}", e))?
+            .filter(|t| t.user_id == user_id)
+            .ok_or_else(|| "Trade not found".to_string())?;
+        // The cost changes the trade's net PnL
+        TradeService::ensure_period_unlocked(pool, &trade.account_id, trade.trade_date).await?;
+
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        let cost = CarryingCostRepository::insert(&mut tx, user_id, &input)
+            .await
+            .map_err(|e| format!("Failed to add carrying cost: {}", e))?;
+        DailyPerformanceService::refresh_trades(&mut tx, &[input.trade_id], Vec::new()).await?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        Ok(cost)
+    }
+
+    /// Get carrying cost entries for a trade
+    pub async fn get_trade_costs(
+        pool: &SqlitePool,
+        trade_id: &str,
+    ) -> Result<Vec<CarryingCost>, String> {
+        CarryingCostRepository::get_for_trade(pool, trade_id)
+            .await
+            .map_err(|e| format!("Failed to get carrying costs: {}", e))
+    }
+
+    /// Delete one of the user's carrying cost entries
+    pub async fn delete_cost(pool: &SqlitePool, user_id: &str, id: &str) -> Result<(), String> {
+        let cost = CarryingCostRepository::get_by_id(pool, user_id, id)
+            .await
+            .map_err(|e| format!("Failed to get carrying cost: {}", e))?
+            .ok_or_else(|| "Carrying cost not found".to_string())?;
+        let trade = TradeRepository::get_by_id(pool, &cost.trade_id)
+            .await
+            .map_err(|e| format!("Failed to get trade: {}", e))?
+            .ok_or_else(|| "Trade not found".to_string())?;
+        TradeService::ensure_period_unlocked(pool, &trade.account_id, trade.trade_date).await?;
+
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        let trade_id = CarryingCostRepository::delete(&mut tx, user_id, id)
+            .await
+            .map_err(|e| format!("Failed to delete carrying cost: {}", e))?
+            .ok_or_else(|| "Carrying cost not found".to_string())?;
+        DailyPerformanceService::refresh_trades(&mut tx, &[trade_id], Vec::new()).await?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))
+    }
+
+    /// Get carrying costs aggregated by type and symbol
+    pub async fn get_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<CarryingCostReport, String> {
+        let costs = CarryingCostRepository::get_costs(pool, user_id, account_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get carrying costs: {}", e))?;
+
+        Ok(calculate_carrying_cost_report(&costs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CarryingCostType, Direction, Status};
+    use crate::services::{AccountService, TradeService};
+    use crate::test_utils::{create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    fn borrow_fee(trade_id: &str, day: u32, amount: f64) -> CreateCarryingCostInput {
+        CreateCarryingCostInput {
+            trade_id: trade_id.to_string(),
+            cost_type: CarryingCostType::BorrowFee,
+            cost_date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            amount,
+            notes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_carrying_costs_reduce_net_pnl() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let mut input = create_test_trade_input(&account_id, "GME");
+        input.direction = Direction::Short;
+        input.entry_price = 155.0;
+        input.exit_price = Some(150.0);
+        input.status = Some(Status::Closed);
+        let trade = TradeService::create_trade(&pool, &user_id, input)
+            .await
+            .expect("Failed to create trade");
+        let net_before = trade.net_pnl.unwrap();
+
+        CarryingCostService::add_cost(&pool, &user_id, borrow_fee(&trade.trade.id, 16, 12.5))
+            .await
+            .expect("Failed to add cost");
+        CarryingCostService::add_cost(&pool, &user_id, borrow_fee(&trade.trade.id, 17, 7.5))
+            .await
+            .expect("Failed to add cost");
+
+        let updated = TradeService::get_trade(&pool, &user_id, &trade.trade.id)
+            .await
+            .expect("Failed to get trade")
+            .expect("Trade not found");
+        assert_eq!(updated.trade.carrying_costs, 20.0);
+        assert!((updated.net_pnl.unwrap() - (net_before - 20.0)).abs() < 0.01);
+
+        let report = CarryingCostService::get_report(&pool, &user_id, None, None, None)
+            .await
+            .expect("Failed to get report");
+        assert_eq!(report.entry_count, 2);
+        assert_eq!(report.by_symbol[0].key, "GME");
+    }
+
+    #[tokio::test]
+    async fn test_add_cost_validation() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let trade = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .expect("Failed to create trade");
+
+        let zero = CarryingCostService::add_cost(&pool, &user_id, borrow_fee(&trade.trade.id, 16, 0.0)).await;
+        assert!(zero.is_err());
+
+        let missing = CarryingCostService::add_cost(&pool, &user_id, borrow_fee("missing", 16, 1.0)).await;
+        assert_eq!(missing.unwrap_err(), "Trade not found");
+
+        let other_user = CarryingCostService::add_cost(&pool, "other-user", borrow_fee(&trade.trade.id, 16, 1.0)).await;
+        assert_eq!(other_user.unwrap_err(), "Trade not found");
+    }
+
+    #[tokio::test]
+    async fn test_delete_cost() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let trade = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .expect("Failed to create trade");
+        let cost = CarryingCostService::add_cost(&pool, &user_id, borrow_fee(&trade.trade.id, 16, 4.0))
+            .await
+            .expect("Failed to add cost");
+
+        let other_user = CarryingCostService::delete_cost(&pool, "other-user", &cost.id).await;
+        assert_eq!(other_user.unwrap_err(), "Carrying cost not found");
+
+        CarryingCostService::delete_cost(&pool, &user_id, &cost.id)
+            .await
+            .expect("Failed to delete cost");
+
+        let costs = CarryingCostService::get_trade_costs(&pool, &trade.trade.id)
+            .await
+            .expect("Failed to get costs");
+        assert!(costs.is_empty());
+        assert!(CarryingCostService::delete_cost(&pool, &user_id, &cost.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_locked_period_blocks_adding_and_deleting_costs() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // Trade date is 2024-01-15
+        let trade = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .expect("Failed to create trade");
+        let cost = CarryingCostService::add_cost(&pool, &user_id, borrow_fee(&trade.trade.id, 16, 4.0))
+            .await
+            .expect("Failed to add cost");
+        AccountService::lock_period(&pool, &user_id, &account_id, NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
+            .await
+            .expect("Failed to lock period");
+
+        let added = CarryingCostService::add_cost(&pool, &user_id, borrow_fee(&trade.trade.id, 17, 1.0)).await;
+        assert!(added.unwrap_err().contains("locked period"));
+        let deleted = CarryingCostService::delete_cost(&pool, &user_id, &cost.id).await;
+        assert!(deleted.unwrap_err().contains("locked period"));
+        assert_eq!(CarryingCostService::get_trade_costs(&pool, &trade.trade.id).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,137 @@
+use chrono::NaiveDate;
+use sqlx::sqlite::SqlitePool;
+use crate::calculations::calculate_checklist_report;
+use crate::models::{CheckChecklistItemInput, ChecklistCheck, ChecklistReport};
+use crate::repository::{ChecklistRepository, TradeRepository};
+use crate::services::TradeService;
+
+pub struct ChecklistService;
+
+impl ChecklistService {
+    /// Check a checklist item on one of the user's trades
+    pub async fn check_item(
+        pool: &SqlitePool,
+        user_id: &str,
+        mut input: CheckChecklistItemInput,
+    ) -> Result<ChecklistCheck, String> {
+        input.item = input.item.trim().to_string();
+        if input.item.is_empty() {
+            return Err("Checklist item cannot be empty".to_string());
+        }
+
+        let trade = TradeRepository::get_by_id(pool, &input.trade_id)
+            .await
+            .map_err(|e| format!("Failed to get trade: {}", e))?;
+        if !matches!(trade, Some(ref t) if t.user_id == user_id) {
+            return Err("Trade not found".to_string());
+        }
+
+        ChecklistRepository::upsert(pool, &input)
+            .await
+            .map_err(|e| format!("Failed to check checklist item: {}", e))
+    }
+
+    /// Get checked items for a trade
+    pub async fn get_trade_checklist(
+        pool: &SqlitePool,
+        trade_id: &str,
+    ) -> Result<Vec<ChecklistCheck>, String> {
+        ChecklistRepository::get_for_trade(pool, trade_id)
+            .await
+            .map_err(|e| format!("Failed to get checklist: {}", e))
+    }
+
+    /// Uncheck a checklist item
+    pub async fn uncheck_item(pool: &SqlitePool, id: &str) -> Result<(), String> {
+        let deleted = ChecklistRepository::delete(pool, id)
+            .await
+            .map_err(|e| format!("Failed to uncheck checklist item: {}", e))?;
+
+        if !deleted {
+            return Err("Checklist item not found".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Get how often trades were entered before their checklist was confirmed
+    pub async fn get_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<ChecklistReport, String> {
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let checks = ChecklistRepository::get_checks(pool, user_id, account_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get checklists: {}", e))?;
+
+        Ok(calculate_checklist_report(&trades, &checks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ChecklistPhase;
+    use crate::test_utils::{create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    fn check(trade_id: &str, item: &str, phase: ChecklistPhase) -> CheckChecklistItemInput {
+        CheckChecklistItemInput {
+            trade_id: trade_id.to_string(),
+            item: item.to_string(),
+            phase,
+            checked_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checklist_report_flags_entries_before_confirmation() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let confirmed = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+        let early = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "MSFT"))
+            .await
+            .unwrap();
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "NVDA"))
+            .await
+            .unwrap();
+
+        ChecklistService::check_item(&pool, &user_id, check(&confirmed.trade.id, "Trend aligned", ChecklistPhase::PreEntry))
+            .await
+            .unwrap();
+        ChecklistService::check_item(&pool, &user_id, check(&early.trade.id, "Trend aligned", ChecklistPhase::PreEntry))
+            .await
+            .unwrap();
+        ChecklistService::check_item(&pool, &user_id, check(&early.trade.id, "Volume confirmation", ChecklistPhase::PostEntry))
+            .await
+            .unwrap();
+
+        // Re-checking an item replaces the earlier check
+        let rechecked = ChecklistService::check_item(&pool, &user_id, check(&early.trade.id, " Trend aligned ", ChecklistPhase::PostEntry))
+            .await
+            .unwrap();
+        assert_eq!(rechecked.item, "Trend aligned");
+        let items = ChecklistService::get_trade_checklist(&pool, &early.trade.id).await.unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().all(|c| c.phase == ChecklistPhase::PostEntry));
+
+        let report = ChecklistService::get_report(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(report.checklist_trade_count, 2);
+        assert_eq!(report.confirmed_count, 1);
+        assert_eq!(report.entered_before_confirmation_count, 1);
+        assert_eq!(report.entered_before_confirmation_trade_ids, vec![early.trade.id.clone()]);
+        assert_eq!(report.entered_before_confirmation_rate, Some(0.5));
+
+        ChecklistService::uncheck_item(&pool, &rechecked.id).await.unwrap();
+        let result = ChecklistService::uncheck_item(&pool, &rechecked.id).await;
+        assert!(result.unwrap_err().contains("not found"));
+
+        let result = ChecklistService::check_item(&pool, &user_id, check(&early.trade.id, "  ", ChecklistPhase::PreEntry)).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use chrono::NaiveDate;
+use sqlx::sqlite::SqlitePool;
+use sqlx::{Sqlite, Transaction};
+use crate::models::DateAttribution;
+use crate::repository::{AccountDay, DailyPerformanceRepository, TradeRepository};
+use crate::services::settings_service::SettingsService;
+
+/// Keeps the daily_performance summary in step with the trades it is computed from
+/// Each account day holds the sums calculate_daily_metrics would give for the closed trades counting
+/// toward it, so the calendar reads one row per day instead of every trade. Writes refresh the days
+/// they touch in their own transaction; a rebuild recomputes everything, e.g. after a setting
+/// changes how trades count.
+pub struct DailyPerformanceService;
+
+impl DailyPerformanceService {
+    /// Get the days trades count toward now, to refresh along with their new days after a change
+    pub async fn trade_days(tx: &mut Transaction<'_, Sqlite>, trade_ids: &[String]) -> Result<Vec<AccountDay>, String> {
+        DailyPerformanceRepository::get_trade_days(tx, trade_ids)
+            .await
+            .map_err(|e| format!("Failed to get trade days: {}", e))
+    }
+
+    /// Refresh the days trades count toward, plus the days they counted toward before a change
+    pub async fn refresh_trades(
+        tx: &mut Transaction<'_, Sqlite>,
+        trade_ids: &[String],
+        previous_days: Vec<AccountDay>,
+    ) -> Result<(), String> {
+        let mut days = previous_days;
+        days.extend(Self::trade_days(tx, trade_ids).await?);
+        Self::refresh_days(tx, days).await
+    }
+
+    /// Recompute account days from their closed trades
+    pub async fn refresh_days(tx: &mut Transaction<'_, Sqlite>, days: Vec<AccountDay>) -> Result<(), String> {
+        let mut by_account: BTreeMap<(String, String), BTreeSet<NaiveDate>> = BTreeMap::new();
+        for day in days {
+            by_account.entry((day.user_id, day.account_id)).or_default().insert(day.date);
+        }
+
+        for ((user_id, account_id), dates) in by_account {
+            let dates: Vec<NaiveDate> = dates.into_iter().collect();
+            DailyPerformanceRepository::refresh_days(tx, &user_id, &account_id, &dates)
+                .await
+                .map_err(|e| format!("Failed to update daily performance: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Recompute a user's whole summary from their closed trades
+    /// Returns the number of account days written.
+    pub async fn rebuild(pool: &SqlitePool, user_id: &str) -> Result<usize, String> {
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        let written = DailyPerformanceRepository::rebuild(&mut tx, user_id)
+            .await
+            .map_err(|e| format!("Failed to rebuild daily performance: {}", e))?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        Ok(written as usize)
+    }
+
+    /// Build the summary of a journal kept before it existed
+    pub async fn ensure_built(pool: &SqlitePool, user_id: &str) -> Result<(), String> {
+        let mut conn = pool.acquire().await.map_err(|e| format!("Database error: {}", e))?;
+        let built = DailyPerformanceRepository::has_days(&mut conn, user_id)
+            .await
+            .map_err(|e| format!("Failed to get daily performance: {}", e))?;
+        drop(conn);
+        if !built {
+            Self::rebuild(pool, user_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Last exit dates when realized PnL lands on the day of the last exit, else none
+    pub(crate) async fn attribution_exit_dates(pool: &SqlitePool, user_id: &str) -> Result<HashMap<String, NaiveDate>, String> {
+        match SettingsService::get_date_attribution(pool).await? {
+            DateAttribution::Entry => Ok(HashMap::new()),
+            DateAttribution::Exit | DateAttribution::Both => TradeRepository::get_last_exit_dates(pool, user_id)
+                .await
+                .map_err(|e| format!("Failed to get exit dates: {}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculations::calculate_daily_metrics;
+    use crate::models::{CreateTradeInput, ResultBasis, UpdateTradeInput};
+    use crate::services::TradeService;
+    use crate::test_utils::{create_losing_long_trade, create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    async fn summary(pool: &SqlitePool, user_id: &str) -> Vec<(NaiveDate, f64, i32)> {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        DailyPerformanceRepository::get_days(pool, user_id, None, start, end)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(_, day)| (day.date, day.realized_net_pnl, day.trade_count))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_summary_follows_trade_changes() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let jan_15 = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let jan_16 = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+
+        // (155 - 150) × 100 - 10 in fees each
+        let first = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL")).await.unwrap();
+        let second = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "MSFT")).await.unwrap();
+        assert_eq!(summary(&pool, &user_id).await, vec![(jan_15, 980.0, 2)]);
+
+        // Moving a trade refreshes the day it left as well as the day it joined
+        let update = UpdateTradeInput {
+            account_id: None,
+            symbol: None,
+            trade_number: None,
+            trade_date: Some(jan_16),
+            direction: None,
+            quantity: None,
+            entry_price: None,
+            exit_price: None,
+            stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
+            entry_time: None,
+            exit_time: None,
+            fees: None,
+            strategy: None,
+            notes: None,
+            screenshot_url: None,
+            status: None,
+        };
+        TradeService::update_trade(&pool, &user_id, &first.trade.id, update).await.unwrap();
+        assert_eq!(summary(&pool, &user_id).await, vec![(jan_15, 490.0, 1), (jan_16, 490.0, 1)]);
+
+        TradeService::delete_trade(&pool, &user_id, &second.trade.id).await.unwrap();
+        let refreshed = summary(&pool, &user_id).await;
+        assert_eq!(refreshed, vec![(jan_16, 490.0, 1)]);
+
+        // A rebuild from scratch agrees with the incremental updates
+        assert_eq!(DailyPerformanceService::rebuild(&pool, &user_id).await.unwrap(), 1);
+        assert_eq!(summary(&pool, &user_id).await, refreshed);
+    }
+
+    /// Stored and calculated days, compared by their debug output since the floats are summed alike
+    async fn stored_and_calculated(pool: &SqlitePool, user_id: &str) -> (Vec<String>, Vec<String>) {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let stored = DailyPerformanceRepository::get_days(pool, user_id, None, start, end).await.unwrap();
+        let trades = TradeService::get_trades(pool, user_id, None, None, None).await.unwrap();
+        let exit_dates = DailyPerformanceService::attribution_exit_dates(pool, user_id).await.unwrap();
+        (
+            stored.iter().map(|(_, day)| format!("{:?}", day)).collect(),
+            calculate_daily_metrics(&trades, &exit_dates).iter().map(|day| format!("{:?}", day)).collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_summary_matches_calculated_metrics_after_setting_changes() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let jan_15 = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        // A winner, and a scratch that only its fees turn into a loss
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL")).await.unwrap();
+        let scratch = CreateTradeInput {
+            fees: Some(2.0),
+            ..create_losing_long_trade(&account_id, "MSFT", jan_15, 50.0, 50.0, 10.0)
+        };
+        TradeService::create_trade(&pool, &user_id, scratch).await.unwrap();
+        let (stored, calculated) = stored_and_calculated(&pool, &user_id).await;
+        assert_eq!(stored, calculated);
+
+        // Classifying by gross PnL makes the scratch breakeven, and saving the setting rebuilds the summary
+        SettingsService::save_result_basis(&pool, ResultBasis::Gross).await.unwrap();
+        let days = DailyPerformanceRepository::get_days(&pool, &user_id, None, jan_15, jan_15).await.unwrap();
+        assert_eq!((days[0].1.win_count, days[0].1.loss_count, days[0].1.breakeven_count), (1, 0, 1));
+        let (stored, calculated) = stored_and_calculated(&pool, &user_id).await;
+        assert_eq!(stored, calculated);
+
+        SettingsService::save_result_basis(&pool, ResultBasis::Net).await.unwrap();
+        let days = DailyPerformanceRepository::get_days(&pool, &user_id, None, jan_15, jan_15).await.unwrap();
+        assert_eq!((days[0].1.win_count, days[0].1.loss_count, days[0].1.breakeven_count), (1, 1, 0));
+    }
+
+    #[tokio::test]
+    async fn test_failed_refresh_rolls_back_the_write() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        sqlx::query(
+            "CREATE TRIGGER fail_summary BEFORE INSERT ON daily_performance BEGIN SELECT RAISE(ABORT, 'disk full'); END",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let result = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL")).await;
+        assert!(result.unwrap_err().contains("disk full"));
+
+        // The trade went with the summary it could not be counted in
+        assert!(TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap().is_empty());
+        sqlx::query("DROP TRIGGER fail_summary").execute(&pool).await.unwrap();
+    }
+}
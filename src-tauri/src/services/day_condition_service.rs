@@ -0,0 +1,100 @@
+use chrono::NaiveDate;
+use sqlx::sqlite::SqlitePool;
+use crate::calculations::calculate_day_condition_report;
+use crate::models::{DayConditionType, DayConditions, PerformanceBucket};
+use crate::repository::DayConditionRepository;
+use crate::services::{MetricsService, TradeService};
+
+pub struct DayConditionService;
+
+impl DayConditionService {
+    /// Tag a trading day with market conditions, replacing earlier tags
+    pub async fn set_conditions(
+        pool: &SqlitePool,
+        user_id: &str,
+        date: NaiveDate,
+        conditions: Vec<String>,
+    ) -> Result<DayConditions, String> {
+        let mut parsed = conditions
+            .iter()
+            .map(|c| DayConditionType::from_str(c.trim()).ok_or_else(|| format!("Invalid day condition: {}", c)))
+            .collect::<Result<Vec<_>, _>>()?;
+        parsed.sort();
+        parsed.dedup();
+
+        if parsed.contains(&DayConditionType::GapUp) && parsed.contains(&DayConditionType::GapDown) {
+            return Err("A day cannot gap both up and down".to_string());
+        }
+
+        DayConditionRepository::set_conditions(pool, user_id, date, &parsed)
+            .await
+            .map_err(|e| format!("Failed to save day conditions: {}", e))?;
+
+        Ok(DayConditions { date, conditions: parsed })
+    }
+
+    /// Get tagged days in a date range
+    pub async fn get_conditions(
+        pool: &SqlitePool,
+        user_id: &str,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<DayConditions>, String> {
+        DayConditionRepository::get_conditions(pool, user_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get day conditions: {}", e))
+    }
+
+    /// Get performance per day condition
+    pub async fn get_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<PerformanceBucket>, String> {
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = MetricsService::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let days = Self::get_conditions(pool, user_id, start_date, end_date).await?;
+
+        Ok(calculate_day_condition_report(&trades, &days))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculations::UNTAGGED_DAY_KEY;
+    use crate::test_utils::{create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    #[tokio::test]
+    async fn test_day_condition_report_groups_trades_by_condition() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let gap_day = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+        let mut untagged = create_test_trade_input(&account_id, "MSFT");
+        untagged.trade_date = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+        TradeService::create_trade(&pool, &user_id, untagged).await.unwrap();
+
+        let result = DayConditionService::set_conditions(&pool, &user_id, gap_day, vec!["gap_up".into(), "gap_down".into()]).await;
+        assert!(result.is_err());
+
+        DayConditionService::set_conditions(&pool, &user_id, gap_day, vec!["chop".into()]).await.unwrap();
+        let tagged = DayConditionService::set_conditions(&pool, &user_id, gap_day, vec!["trend".into(), "gap_up".into(), "trend".into()])
+            .await
+            .unwrap();
+        assert_eq!(tagged.conditions, vec![DayConditionType::Trend, DayConditionType::GapUp]);
+
+        let days = DayConditionService::get_conditions(&pool, &user_id, None, None).await.unwrap();
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].conditions, vec![DayConditionType::Trend, DayConditionType::GapUp]);
+
+        let report = DayConditionService::get_report(&pool, &user_id, None, None, None).await.unwrap();
+        let keys: Vec<(&str, i32)> = report.iter().map(|b| (b.key.as_str(), b.trade_count)).collect();
+        assert_eq!(keys, vec![("trend", 1), ("gap_up", 1), (UNTAGGED_DAY_KEY, 1)]);
+    }
+}
@@ -0,0 +1,290 @@
+use chrono::NaiveDate;
+use sqlx::sqlite::SqlitePool;
+use crate::calculations::calculate_dividend_income;
+use crate::models::{CreateDividendInput, Direction, Dividend, DividendIncomeReport};
+use crate::repository::{AccountRepository, DividendRepository, InstrumentRepository, TradeRepository};
+use crate::services::{DailyPerformanceService, TradeService};
+
+pub struct DividendService;
+
+impl DividendService {
+    /// Record a dividend, optionally linked to the trade held through the ex-date
+    pub async fn add_dividend(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: CreateDividendInput,
+    ) -> Result<Dividend, String> {
+        if input.amount == 0.0 || !input.amount.is_finite() {
+            return Err("Amount must be a non-zero number".to_string());
+        }
+        if input.symbol.trim().is_empty() {
+            return Err("Symbol is required".to_string());
+        }
+
+        let account = AccountRepository::get_by_id(pool, &input.account_id)
+            .await
+            .map_err(|e| format!("Failed to get account: {}", e))?;
+        if !matches!(account, Some(ref a) if a.user_id == user_id) {
+            return Err(format!("Account not found: {}", input.account_id));
+        }
+
+        let mut amount = input.amount;
+        if let Some(ref trade_id) = input.trade_id {
+            let trade = TradeRepository::get_by_id(pool, trade_id)
+                .await
+                .map_err(|e| format!("Failed to get trade: {}", e))?
+                .filter(|t| t.user_id == user_id)
+                .ok_or_else(|| "Trade not found".to_string())?;
+
+            if trade.account_id != input.account_id {
+                return Err(format!("Trade {} is not in account {}", trade_id, input.account_id));
+            }
+            if !trade.symbol.eq_ignore_ascii_case(input.symbol.trim()) {
+                return Err(format!("Trade {} is not a {} trade", trade_id, input.symbol.trim()));
+            }
+
+            // Longs receive the dividend, shorts pay it
+            amount = match trade.direction {
+                Direction::Long => amount.abs(),
+                Direction::Short => -amount.abs(),
+            };
+        }
+
+        Self::ensure_period_unlocked(pool, &input.account_id, input.trade_id.as_deref(), input.ex_date).await?;
+
+        let instrument = InstrumentRepository::get_or_create(pool, input.symbol.trim())
+            .await
+            .map_err(|e| format!("Failed to get/create instrument: {}", e))?;
+
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        let dividend = DividendRepository::insert(&mut tx, user_id, &instrument.id, &input, amount)
+            .await
+            .map_err(|e| format!("Failed to add dividend: {}", e))?;
+
+        // Linked dividends count toward the trade's PnL when enabled in settings
+        if let Some(trade_id) = input.trade_id {
+            DailyPerformanceService::refresh_trades(&mut tx, &[trade_id], Vec::new()).await?;
+        }
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        Ok(dividend)
+    }
+
+    /// Get dividends with optional filters on the ex-date
+    pub async fn get_dividends(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<Dividend>, String> {
+        DividendRepository::get_dividends(pool, user_id, account_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get dividends: {}", e))
+    }
+
+    /// Get the user's dividends linked to a trade
+    pub async fn get_trade_dividends(
+        pool: &SqlitePool,
+        user_id: &str,
+        trade_id: &str,
+    ) -> Result<Vec<Dividend>, String> {
+        DividendRepository::get_for_trade(pool, user_id, trade_id)
+            .await
+            .map_err(|e| format!("Failed to get dividends: {}", e))
+    }
+
+    /// Delete a dividend
+    pub async fn delete_dividend(pool: &SqlitePool, user_id: &str, id: &str) -> Result<(), String> {
+        let dividend = DividendRepository::get_by_id(pool, user_id, id)
+            .await
+            .map_err(|e| format!("Failed to get dividend: {}", e))?
+            .ok_or_else(|| "Dividend not found".to_string())?;
+        Self::ensure_period_unlocked(pool, &dividend.account_id, dividend.trade_id.as_deref(), dividend.ex_date).await?;
+
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        let trade_id = DividendRepository::delete(&mut tx, user_id, id)
+            .await
+            .map_err(|e| format!("Failed to delete dividend: {}", e))?
+            .ok_or_else(|| "Dividend not found".to_string())?;
+
+        if let Some(trade_id) = trade_id {
+            DailyPerformanceService::refresh_trades(&mut tx, &[trade_id], Vec::new()).await?;
+        }
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))
+    }
+
+    /// Get dividend income aggregated by symbol and month
+    pub async fn get_income_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<DividendIncomeReport, String> {
+        let dividends = Self::get_dividends(pool, user_id, account_id, start_date, end_date).await?;
+        Ok(calculate_dividend_income(&dividends))
+    }
+
+    /// Fail if the dividend falls in its account's locked period
+    /// A linked dividend counts toward its trade's PnL, so the trade date is checked; otherwise the ex-date.
+    async fn ensure_period_unlocked(
+        pool: &SqlitePool,
+        account_id: &str,
+        trade_id: Option<&str>,
+        ex_date: NaiveDate,
+    ) -> Result<(), String> {
+        let date = match trade_id {
+            Some(trade_id) => TradeRepository::get_by_id(pool, trade_id)
+                .await
+                .map_err(|e| format!("Failed to get trade: {}", e))?
+                .map_or(ex_date, |t| t.trade_date),
+            None => ex_date,
+        };
+        TradeService::ensure_period_unlocked(pool, account_id, date).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::settings_service::SettingsService;
+    use crate::services::{AccountService, TradeService};
+    use crate::test_utils::{
+        create_open_trade, create_test_db, create_test_trade_input, setup_test_user_and_account,
+    };
+
+    fn dividend_input(account_id: &str, symbol: &str, trade_id: Option<&str>, amount: f64) -> CreateDividendInput {
+        CreateDividendInput {
+            account_id: account_id.to_string(),
+            symbol: symbol.to_string(),
+            trade_id: trade_id.map(|t| t.to_string()),
+            ex_date: NaiveDate::from_ymd_opt(2024, 2, 9).unwrap(),
+            pay_date: Some(NaiveDate::from_ymd_opt(2024, 2, 15).unwrap()),
+            amount,
+            notes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dividend_sign_follows_trade_direction() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let mut input = create_open_trade(&account_id, "KO", NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(), 60.0, 100.0);
+        input.direction = Direction::Short;
+        let short = TradeService::create_trade(&pool, &user_id, input)
+            .await
+            .expect("Failed to create trade");
+
+        let paid = DividendService::add_dividend(&pool, &user_id, dividend_input(&account_id, "KO", Some(&short.trade.id), 46.0))
+            .await
+            .expect("Failed to add dividend");
+        assert_eq!(paid.amount, -46.0);
+
+        let unlinked = DividendService::add_dividend(&pool, &user_id, dividend_input(&account_id, "T", None, 27.75))
+            .await
+            .expect("Failed to add dividend");
+        assert_eq!(unlinked.amount, 27.75);
+        assert!(unlinked.trade_id.is_none());
+
+        let report = DividendService::get_income_report(&pool, &user_id, None, None, None)
+            .await
+            .expect("Failed to get report");
+        assert!((report.net_income - (27.75 - 46.0)).abs() < 0.001);
+    }
+
+    #[tokio::test]
+    async fn test_dividends_in_net_pnl_only_when_enabled() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let trade = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "KO"))
+            .await
+            .expect("Failed to create trade");
+        let net_without = trade.net_pnl.unwrap();
+
+        DividendService::add_dividend(&pool, &user_id, dividend_input(&account_id, "KO", Some(&trade.trade.id), 46.0))
+            .await
+            .expect("Failed to add dividend");
+
+        let fetched = TradeService::get_trade(&pool, &user_id, &trade.trade.id).await.unwrap().unwrap();
+        assert!((fetched.net_pnl.unwrap() - net_without).abs() < 0.001);
+
+        SettingsService::save_include_dividends_in_pnl(&pool, true).await.unwrap();
+
+        let fetched = TradeService::get_trade(&pool, &user_id, &trade.trade.id).await.unwrap().unwrap();
+        assert_eq!(fetched.trade.dividends, 46.0);
+        assert!((fetched.net_pnl.unwrap() - (net_without + 46.0)).abs() < 0.001);
+    }
+
+    #[tokio::test]
+    async fn test_add_dividend_validation() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let trade = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .expect("Failed to create trade");
+
+        let zero = DividendService::add_dividend(&pool, &user_id, dividend_input(&account_id, "AAPL", None, 0.0)).await;
+        assert!(zero.is_err());
+
+        let wrong_symbol = DividendService::add_dividend(&pool, &user_id, dividend_input(&account_id, "KO", Some(&trade.trade.id), 5.0)).await;
+        assert!(wrong_symbol.is_err());
+
+        let bad_account = DividendService::add_dividend(&pool, &user_id, dividend_input("missing", "AAPL", None, 5.0)).await;
+        assert!(bad_account.is_err());
+
+        // The linked trade must belong to the user and sit in the dividend's account
+        let other_account = AccountRepository::create(&pool, &user_id, "Other", Some("USD")).await.unwrap();
+        let wrong_account =
+            DividendService::add_dividend(&pool, &user_id, dividend_input(&other_account.id, "AAPL", Some(&trade.trade.id), 5.0)).await;
+        assert_eq!(wrong_account.unwrap_err(), format!("Trade {} is not in account {}", trade.trade.id, other_account.id));
+
+        sqlx::query("INSERT INTO users (id, email) VALUES (?, ?)")
+            .bind("other-user")
+            .bind("other@example.com")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let other_user_account = AccountRepository::create(&pool, "other-user", "Other", Some("USD")).await.unwrap();
+        let other_user =
+            DividendService::add_dividend(&pool, "other-user", dividend_input(&other_user_account.id, "AAPL", Some(&trade.trade.id), 5.0)).await;
+        assert_eq!(other_user.unwrap_err(), "Trade not found");
+    }
+
+    #[tokio::test]
+    async fn test_locked_period_blocks_adding_and_deleting_dividends() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // Trade date is 2024-01-15, the ex-date 2024-02-09
+        let trade = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "KO"))
+            .await
+            .expect("Failed to create trade");
+        let linked = DividendService::add_dividend(&pool, &user_id, dividend_input(&account_id, "KO", Some(&trade.trade.id), 46.0))
+            .await
+            .expect("Failed to add dividend");
+        AccountService::lock_period(&pool, &user_id, &account_id, NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
+            .await
+            .expect("Failed to lock period");
+
+        // A linked dividend counts toward its trade's date
+        let added = DividendService::add_dividend(&pool, &user_id, dividend_input(&account_id, "KO", Some(&trade.trade.id), 10.0)).await;
+        assert!(added.unwrap_err().contains("locked period"));
+        let deleted = DividendService::delete_dividend(&pool, &user_id, &linked.id).await;
+        assert!(deleted.unwrap_err().contains("locked period"));
+
+        // An unlinked one toward its ex-date
+        let unlinked = DividendService::add_dividend(&pool, &user_id, dividend_input(&account_id, "T", None, 27.75))
+            .await
+            .expect("Failed to add dividend");
+        AccountService::lock_period(&pool, &user_id, &account_id, NaiveDate::from_ymd_opt(2024, 2, 29).unwrap())
+            .await
+            .expect("Failed to lock period");
+        let deleted = DividendService::delete_dividend(&pool, &user_id, &unlinked.id).await;
+        assert!(deleted.unwrap_err().contains("locked period"));
+        let added = DividendService::add_dividend(&pool, &user_id, dividend_input(&account_id, "T", None, 27.75)).await;
+        assert!(added.unwrap_err().contains("locked period"));
+    }
+}
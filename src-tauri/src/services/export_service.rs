@@ -0,0 +1,292 @@
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use sqlx::sqlite::SqlitePool;
+use crate::calculations::{calculate_period_metrics, symbol_month_matrix_csv};
+use crate::models::{AnonymizedExport, AnonymizedTrade, DailyPerformance, Status, SymbolMonthMatrix, TradeWithDerived};
+use crate::services::{MetricsService, TradeService};
+
+/// Range of the random factor applied to dollar amounts and quantities
+const MIN_SCALE_FACTOR: f64 = 0.5;
+const MAX_SCALE_FACTOR: f64 = 2.0;
+/// Longest iCalendar content line in octets, excluding the line break
+const ICS_LINE_OCTETS: usize = 75;
+
+pub struct ExportService;
+
+impl ExportService {
+    /// Build an anonymized export of trades, optionally writing it to a JSON file
+    pub async fn export_anonymized(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        file_path: Option<&str>,
+    ) -> Result<AnonymizedExport, String> {
+        let mut trades = TradeService::get_all_trades(pool, user_id, account_id, start_date, end_date)
+            .await?;
+        trades.sort_by_key(|t| (t.trade.trade_date, t.trade.created_at));
+
+        let export = anonymize_trades(&trades, random_scale_factor());
+
+        if let Some(path) = file_path {
+            let json = serde_json::to_string_pretty(&export)
+                .map_err(|e| format!("Failed to serialize export: {}", e))?;
+            std::fs::write(path, json)
+                .map_err(|e| format!("Failed to write file: {}", e))?;
+        }
+
+        Ok(export)
+    }
+
+    /// Write the symbol by month matrix to a CSV file
+    pub async fn export_symbol_month_matrix(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        file_path: &str,
+    ) -> Result<SymbolMonthMatrix, String> {
+        let matrix = MetricsService::get_symbol_month_matrix(pool, user_id, account_id, start_date, end_date).await?;
+        std::fs::write(file_path, symbol_month_matrix_csv(&matrix))
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+        Ok(matrix)
+    }
+
+    /// Write each trading day's net PnL and trade count to an iCalendar (.ics) file of all-day events
+    /// The range defaults to all days through today. Returns the number of days written.
+    pub async fn export_calendar(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        file_path: &str,
+    ) -> Result<usize, String> {
+        let start = start_date.unwrap_or(NaiveDate::from_ymd_opt(1970, 1, 1).unwrap());
+        let end = end_date.unwrap_or_else(|| Local::now().date_naive());
+        let days = MetricsService::get_daily_performance(pool, user_id, account_id, start, end, false).await?;
+
+        std::fs::write(file_path, trading_days_ics(&days, account_id, Utc::now()))
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+        Ok(days.len())
+    }
+}
+
+/// Calendar with an all-day event per trading day
+/// Event UIDs are stable per day and account, so importing a newer export updates the events.
+fn trading_days_ics(days: &[DailyPerformance], account_id: Option<&str>, generated_at: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//FreeTradingJournal//Trading Days//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Trading results".to_string(),
+    ];
+    let stamp = generated_at.format("%Y%m%dT%H%M%SZ");
+    for day in days {
+        let trades = if day.trade_count == 1 { "trade" } else { "trades" };
+        let summary = format!("{:+.2} ({} {})", day.realized_net_pnl, day.trade_count, trades);
+        let description = format!(
+            "Net PnL: {:.2}\nWins: {}, losses: {}, breakeven: {}\nFees: {:.2}",
+            day.realized_net_pnl, day.win_count, day.loss_count, day.breakeven_count, day.total_fees
+        );
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}-{}@freetradingjournal", day.date.format("%Y%m%d"), account_id.unwrap_or("all")),
+            format!("DTSTAMP:{}", stamp),
+            format!("DTSTART;VALUE=DATE:{}", day.date.format("%Y%m%d")),
+            format!("DTEND;VALUE=DATE:{}", (day.date + chrono::Duration::days(1)).format("%Y%m%d")),
+            format!("SUMMARY:{}", ics_text(&summary)),
+            format!("DESCRIPTION:{}", ics_text(&description)),
+            "TRANSP:TRANSPARENT".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    // iCalendar lines end with CRLF
+    lines.iter().map(|line| fold_ics_line(line)).collect::<Vec<_>>().join("\r\n") + "\r\n"
+}
+
+/// Fold a content line longer than 75 octets onto continuation lines that start with a space
+/// (RFC 5545 section 3.1), without splitting a UTF-8 character.
+fn fold_ics_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut line_octets = 0;
+    for c in line.chars() {
+        if line_octets + c.len_utf8() > ICS_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            line_octets = 1;
+        }
+        folded.push(c);
+        line_octets += c.len_utf8();
+    }
+    folded
+}
+
+/// Escape an iCalendar TEXT value
+fn ics_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Random factor between MIN_SCALE_FACTOR and MAX_SCALE_FACTOR
+fn random_scale_factor() -> f64 {
+    let fraction = (uuid::Uuid::new_v4().as_u128() % 1_000_000) as f64 / 1_000_000.0;
+    MIN_SCALE_FACTOR + fraction * (MAX_SCALE_FACTOR - MIN_SCALE_FACTOR)
+}
+
+/// Strip identifying details and scale dollar amounts and quantities
+/// Prices, R-multiples and percentages are unchanged.
+fn anonymize_trades(trades: &[TradeWithDerived], scale: f64) -> AnonymizedExport {
+    let scaled: Vec<TradeWithDerived> = trades
+        .iter()
+        .map(|t| {
+            let mut s = t.clone();
+            s.trade.quantity = t.trade.quantity.map(|q| q * scale);
+            s.trade.fees = t.trade.fees * scale;
+            s.trade.carrying_costs = t.trade.carrying_costs * scale;
+            s.trade.dividends = t.trade.dividends * scale;
+            s.gross_pnl = t.gross_pnl.map(|v| v * scale);
+            s.net_pnl = t.net_pnl.map(|v| v * scale);
+            s
+        })
+        .collect();
+
+    let closed: Vec<TradeWithDerived> = scaled
+        .iter()
+        .filter(|t| t.trade.status == Status::Closed)
+        .cloned()
+        .collect();
+
+    let anonymized: Vec<AnonymizedTrade> = scaled
+        .into_iter()
+        .enumerate()
+        .map(|(i, t)| AnonymizedTrade {
+            trade_number: i as i32 + 1,
+            return_pct: t.pnl_per_share.map(|pps| pps / t.trade.entry_price * 100.0),
+            symbol: t.trade.symbol,
+            asset_class: t.trade.asset_class,
+            trade_date: t.trade.trade_date,
+            direction: t.trade.direction,
+            status: t.trade.status,
+            quantity: t.trade.quantity,
+            entry_price: t.trade.entry_price,
+            exit_price: t.trade.exit_price,
+            stop_loss_price: t.trade.stop_loss_price,
+            entry_time: t.trade.entry_time,
+            exit_time: t.trade.exit_time,
+            fees: t.trade.fees,
+            gross_pnl: t.gross_pnl,
+            net_pnl: t.net_pnl,
+            pnl_per_share: t.pnl_per_share,
+            r_multiple: t.r_multiple,
+            result: t.result,
+            strategy: t.trade.strategy,
+        })
+        .collect();
+
+    AnonymizedExport {
+        generated_at: Utc::now(),
+        trade_count: anonymized.len() as i32,
+        metrics: calculate_period_metrics(&closed),
+        trades: anonymized,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    #[test]
+    fn test_random_scale_factor_in_range() {
+        for _ in 0..100 {
+            let scale = random_scale_factor();
+            assert!((MIN_SCALE_FACTOR..MAX_SCALE_FACTOR).contains(&scale));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_anonymize_scales_dollars_and_keeps_ratios() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .expect("Failed to create trade");
+        let trades = TradeService::get_all_trades(&pool, &user_id, None, None, None)
+            .await
+            .unwrap();
+
+        let export = anonymize_trades(&trades, 1.5);
+        let original = &trades[0];
+        let shared = &export.trades[0];
+
+        assert_eq!(export.trade_count, 1);
+        assert_eq!(shared.trade_number, 1);
+        assert_eq!(shared.quantity, Some(150.0));
+        assert!((shared.net_pnl.unwrap() - original.net_pnl.unwrap() * 1.5).abs() < 0.001);
+        assert!((shared.fees - 15.0).abs() < 0.001);
+        assert!((export.metrics.total_net_pnl - shared.net_pnl.unwrap()).abs() < 0.001);
+
+        // Prices, R-multiple and percentages are unchanged
+        assert_eq!(shared.entry_price, original.trade.entry_price);
+        assert_eq!(shared.r_multiple, original.r_multiple);
+        // (155 - 150) / 150 = 3.33%
+        assert!((shared.return_pct.unwrap() - 3.3333).abs() < 0.001);
+
+        // Notes and account details are not part of the export
+        let json = serde_json::to_string(&export).unwrap();
+        assert!(!json.contains("Test trade"));
+        assert!(!json.contains(&account_id));
+    }
+
+    #[tokio::test]
+    async fn test_export_calendar_writes_a_day_per_event() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let trade = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+        let date = trade.trade.trade_date;
+
+        let path = std::env::temp_dir().join(format!("ftj-calendar-{}.ics", uuid::Uuid::new_v4()));
+        let path = path.to_string_lossy().into_owned();
+        let written = ExportService::export_calendar(&pool, &user_id, None, None, Some(date), &path).await.unwrap();
+        assert_eq!(written, 1);
+
+        let ics = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        // Long lines are folded to 75 octets
+        assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+        let ics = ics.replace("\r\n ", "");
+        let lines: Vec<&str> = ics.split("\r\n").collect();
+        assert_eq!(lines[0], "BEGIN:VCALENDAR");
+        let day = date.format("%Y%m%d").to_string();
+        assert!(lines.contains(&format!("DTSTART;VALUE=DATE:{}", day).as_str()));
+        assert!(lines.contains(&format!("UID:{}-all@freetradingjournal", day).as_str()));
+        let pnl = trade.net_pnl.unwrap();
+        assert!(lines.contains(&format!("SUMMARY:{:+.2} (1 trade)", pnl).as_str()));
+        // Commas in text values are escaped
+        assert!(lines.iter().any(|line| line.starts_with("DESCRIPTION:") && line.contains("Wins: 1\\, losses: 0")));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn test_fold_ics_line() {
+        assert_eq!(fold_ics_line("SUMMARY:short"), "SUMMARY:short");
+
+        let line = format!("DESCRIPTION:{}", "é".repeat(40));
+        let folded = fold_ics_line(&line);
+        let parts: Vec<&str> = folded.split("\r\n").collect();
+        assert_eq!(parts.len(), 2);
+        assert!(parts.iter().all(|part| part.len() <= 75));
+        assert!(parts[1].starts_with(' '));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::{Duration, NaiveDate, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use sqlx::sqlite::SqlitePool;
+use crate::models::{FxRate, FxRateSource, FxRefreshResult, TradeFilter};
+use crate::repository::{AccountRepository, FxRateRepository, TradeRepository};
+use crate::services::settings_service::SettingsService;
+
+/// ECB reference rates, served without an API key
+const FX_PROVIDER_BASE_URL: &str = "https://api.frankfurter.app";
+
+#[derive(Debug, Deserialize)]
+struct FrankfurterRangeResponse {
+    rates: BTreeMap<String, HashMap<String, f64>>,
+}
+
+pub struct FxService;
+
+impl FxService {
+    /// Store manual FX rates, overriding fetched rates of the same day
+    pub async fn save_rates(pool: &SqlitePool, rates: Vec<FxRate>) -> Result<usize, String> {
+        let rates = rates
+            .into_iter()
+            .map(|r| {
+                let base_currency = r.base_currency.trim().to_uppercase();
+                let quote_currency = r.quote_currency.trim().to_uppercase();
+                if base_currency.is_empty() || quote_currency.is_empty() || base_currency == quote_currency {
+                    return Err("Rates need two different currencies".to_string());
+                }
+                if r.rate <= 0.0 || !r.rate.is_finite() {
+                    return Err(format!(
+                        "Rate for {}/{} on {} must be greater than 0",
+                        base_currency, quote_currency, r.date
+                    ));
+                }
+                Ok(FxRate {
+                    base_currency,
+                    quote_currency,
+                    source: FxRateSource::Manual,
+                    ..r
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        FxRateRepository::upsert_many(pool, &rates)
+            .await
+            .map_err(|e| format!("Failed to save FX rates: {}", e))?;
+
+        Ok(rates.len())
+    }
+
+    /// Get all stored FX rates, manual and fetched
+    pub async fn get_rates(pool: &SqlitePool) -> Result<Vec<FxRate>, String> {
+        FxRateRepository::get_rates(pool)
+            .await
+            .map_err(|e| format!("Failed to get FX rates: {}", e))
+    }
+
+    /// Delete a stored rate; removing a manual override lets the next refresh fetch that day again
+    pub async fn delete_rate(
+        pool: &SqlitePool,
+        base_currency: &str,
+        quote_currency: &str,
+        date: NaiveDate,
+    ) -> Result<(), String> {
+        let deleted = FxRateRepository::delete(
+            pool,
+            &base_currency.trim().to_uppercase(),
+            &quote_currency.trim().to_uppercase(),
+            date,
+        )
+        .await
+        .map_err(|e| format!("Failed to delete FX rate: {}", e))?;
+
+        if !deleted {
+            return Err("FX rate not found".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Fetch daily rates from each account currency to the reporting currency
+    /// Only days after the last fetched rate of a pair are requested, starting at the first trade.
+    pub async fn refresh_rates(pool: &SqlitePool, user_id: &str) -> Result<FxRefreshResult, String> {
+        let mut result = FxRefreshResult::default();
+        let Some(reporting_currency) = SettingsService::get_reporting_currency(pool).await? else {
+            return Ok(result);
+        };
+
+        let mut currencies: Vec<String> = AccountRepository::get_accounts(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get accounts: {}", e))?
+            .into_iter()
+            .map(|a| a.base_currency.trim().to_uppercase())
+            .filter(|c| !c.is_empty() && *c != reporting_currency)
+            .collect();
+        currencies.sort();
+        currencies.dedup();
+        if currencies.is_empty() {
+            return Ok(result);
+        }
+
+        let today = Utc::now().date_naive();
+        let first_trade_date = TradeRepository::get_trade_stats(pool, user_id, &TradeFilter::default())
+            .await
+            .map_err(|e| format!("Failed to get trade dates: {}", e))?
+            .first_trade_date
+            .unwrap_or(today);
+
+        let client = Client::builder()
+            .user_agent("TradingJournal/0.1")
+            .build()
+            .map_err(|e| format!("HTTP client error: {}", e))?;
+
+        for currency in currencies {
+            let last_fetched = FxRateRepository::get_last_fetched_date(pool, &currency, &reporting_currency)
+                .await
+                .map_err(|e| format!("Failed to get FX rates: {}", e))?;
+            // Rates are published on business days, so look back a few days for the first one
+            let start = match last_fetched {
+                Some(date) => date + Duration::days(1),
+                None => first_trade_date - Duration::days(7),
+            };
+            result.pairs.push(format!("{}/{}", currency, reporting_currency));
+            if start > today {
+                continue;
+            }
+
+            let rates = fetch_daily_rates(&client, &currency, &reporting_currency, start, today).await?;
+            let stored = FxRateRepository::upsert_many(pool, &rates)
+                .await
+                .map_err(|e| format!("Failed to save FX rates: {}", e))?;
+            result.fetched += stored as i32;
+        }
+
+        Ok(result)
+    }
+}
+
+async fn fetch_daily_rates(
+    client: &Client,
+    base_currency: &str,
+    quote_currency: &str,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Vec<FxRate>, String> {
+    let endpoint = format!(
+        "{}/{}..{}",
+        FX_PROVIDER_BASE_URL,
+        start_date.format("%Y-%m-%d"),
+        end_date.format("%Y-%m-%d")
+    );
+
+    let response = client
+        .get(&endpoint)
+        .query(&[("from", base_currency), ("to", quote_currency)])
+        .send()
+        .await
+        .map_err(|e| format!("FX rate request failed: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("FX rate request failed: HTTP {} {}", status, body));
+    }
+
+    let payload: FrankfurterRangeResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse FX rate response: {}", e))?;
+
+    Ok(rates_from_response(payload, base_currency, quote_currency))
+}
+
+fn rates_from_response(
+    payload: FrankfurterRangeResponse,
+    base_currency: &str,
+    quote_currency: &str,
+) -> Vec<FxRate> {
+    payload
+        .rates
+        .into_iter()
+        .filter_map(|(date, rates)| {
+            let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok()?;
+            let rate = *rates.get(quote_currency)?;
+            Some(FxRate {
+                base_currency: base_currency.to_string(),
+                quote_currency: quote_currency.to_string(),
+                date,
+                rate,
+                source: FxRateSource::Ecb,
+            })
+        })
+        .filter(|r| r.rate > 0.0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_db;
+
+    #[tokio::test]
+    async fn test_fetched_rates_are_cached_without_replacing_manual_overrides() {
+        let pool = create_test_db().await;
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+
+        FxService::save_rates(
+            &pool,
+            vec![FxRate {
+                base_currency: "eur".to_string(),
+                quote_currency: "usd".to_string(),
+                date: day(3),
+                rate: 1.20,
+                source: FxRateSource::Ecb, // Saved rates are always manual
+            }],
+        )
+        .await
+        .unwrap();
+
+        let payload: FrankfurterRangeResponse = serde_json::from_str(
+            r#"{"amount":1.0,"base":"EUR","start_date":"2024-01-02","end_date":"2024-01-04",
+                "rates":{"2024-01-02":{"USD":1.0956},"2024-01-03":{"USD":1.0919},"2024-01-04":{"USD":1.0953}}}"#,
+        )
+        .unwrap();
+        let fetched = rates_from_response(payload, "EUR", "USD");
+        assert_eq!(fetched.len(), 3);
+
+        let stored = FxRateRepository::upsert_many(&pool, &fetched).await.unwrap();
+        assert_eq!(stored, 2);
+        assert_eq!(
+            FxRateRepository::get_last_fetched_date(&pool, "EUR", "USD").await.unwrap(),
+            Some(day(4))
+        );
+
+        let rates = FxService::get_rates(&pool).await.unwrap();
+        let jan_3 = rates.iter().find(|r| r.date == day(3)).unwrap();
+        assert_eq!((jan_3.rate, jan_3.source), (1.20, FxRateSource::Manual));
+
+        // Dropping the override leaves the day to be fetched again
+        FxService::delete_rate(&pool, "EUR", "USD", day(3)).await.unwrap();
+        FxRateRepository::upsert_many(&pool, &fetched).await.unwrap();
+        let rates = FxService::get_rates(&pool).await.unwrap();
+        assert_eq!(rates.len(), 3);
+        assert!(rates.iter().all(|r| r.source == FxRateSource::Ecb));
+    }
+}
@@ -0,0 +1,190 @@
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use sqlx::sqlite::SqlitePool;
+use crate::calculations::{calculate_outcome_score, calculate_process_score, calculate_process_streaks};
+use crate::models::{GoalScorecard, ProcessStreaks, TradeFilter};
+use crate::repository::GoalScorecardRepository;
+use crate::services::settings_service::SettingsService;
+use crate::services::{MetricsService, TradeService};
+
+pub struct GoalService;
+
+impl GoalService {
+    /// Score the Monday-Sunday week containing `date` against the current goals and store it
+    pub async fn evaluate_week(
+        pool: &SqlitePool,
+        user_id: &str,
+        date: NaiveDate,
+    ) -> Result<GoalScorecard, String> {
+        let week_start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+        let week_end = week_start + Duration::days(6);
+
+        let goals = SettingsService::get_process_goals(pool).await?;
+        let trades = TradeService::get_trades(pool, user_id, None, Some(week_start), Some(week_end)).await?;
+        let trades = MetricsService::exclude_paper_trades(pool, user_id, None, trades).await?;
+
+        let scorecard = GoalScorecard {
+            week_start,
+            week_end,
+            process: calculate_process_score(&trades, &goals),
+            outcome: calculate_outcome_score(&trades),
+            evaluated_at: Utc::now(),
+        };
+
+        GoalScorecardRepository::upsert(pool, user_id, &scorecard)
+            .await
+            .map_err(|e| format!("Failed to save goal scorecard: {}", e))?;
+
+        Ok(scorecard)
+    }
+
+    /// Score every completed week since the last stored scorecard, or since the first trade
+    /// Returns the number of weeks evaluated.
+    pub async fn evaluate_completed_weeks(
+        pool: &SqlitePool,
+        user_id: &str,
+        today: NaiveDate,
+    ) -> Result<usize, String> {
+        let latest = GoalScorecardRepository::get_latest_week_start(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get goal scorecards: {}", e))?;
+
+        let mut week = match latest {
+            Some(week_start) => week_start + Duration::days(7),
+            None => {
+                let stats = TradeService::get_trade_stats(pool, user_id, &TradeFilter::default()).await?;
+                let Some(first) = stats.first_trade_date else {
+                    return Ok(0);
+                };
+                first - Duration::days(first.weekday().num_days_from_monday() as i64)
+            }
+        };
+
+        let current_week = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+        let mut evaluated = 0;
+        while week < current_week {
+            Self::evaluate_week(pool, user_id, week).await?;
+            week += Duration::days(7);
+            evaluated += 1;
+        }
+
+        Ok(evaluated)
+    }
+
+    /// Get stored scorecards, first scoring any week completed since the last one
+    pub async fn get_scorecards(
+        pool: &SqlitePool,
+        user_id: &str,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<GoalScorecard>, String> {
+        Self::evaluate_completed_weeks(pool, user_id, Utc::now().date_naive()).await?;
+
+        GoalScorecardRepository::get_scorecards(pool, user_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get goal scorecards: {}", e))
+    }
+
+    /// Journaling and daily loss limit streaks over all closed trades
+    pub async fn get_process_streaks(pool: &SqlitePool, user_id: &str) -> Result<ProcessStreaks, String> {
+        let daily_loss_limit = SettingsService::get_daily_loss_limit(pool).await?;
+        let trades = TradeService::get_trades(pool, user_id, None, None, None).await?;
+        let trades = MetricsService::exclude_paper_trades(pool, user_id, None, trades).await?;
+
+        Ok(calculate_process_streaks(&trades, daily_loss_limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProcessGoals;
+    use crate::test_utils::{create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    #[tokio::test]
+    async fn test_weekly_scorecard_separates_process_from_outcome() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let goals = ProcessGoals {
+            max_trades_per_day: Some(1),
+            journal_every_day: true,
+            max_risk_per_trade: Some(600.0),
+        };
+        SettingsService::save_process_goals(&pool, &goals).await.unwrap();
+
+        // Monday Jan 15 2024: two winning trades risking 500 each, one without notes
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+        let mut unjournaled = create_test_trade_input(&account_id, "MSFT");
+        unjournaled.notes = None;
+        TradeService::create_trade(&pool, &user_id, unjournaled).await.unwrap();
+
+        // Wednesday: a journaled loss without a stop
+        let mut no_stop = create_test_trade_input(&account_id, "NVDA");
+        no_stop.trade_date = NaiveDate::from_ymd_opt(2024, 1, 17).unwrap();
+        no_stop.exit_price = Some(140.0);
+        no_stop.stop_loss_price = None;
+        TradeService::create_trade(&pool, &user_id, no_stop).await.unwrap();
+
+        let scorecard = GoalService::evaluate_week(&pool, &user_id, NaiveDate::from_ymd_opt(2024, 1, 19).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(scorecard.week_start, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+        assert_eq!(scorecard.process.days_traded, 2);
+        assert_eq!(scorecard.process.days_over_trade_limit, 1);
+        assert_eq!(scorecard.process.days_journaled, 1);
+        assert_eq!(scorecard.process.trades_over_risk, 0);
+        assert_eq!(scorecard.process.trades_without_stop, 1);
+        assert_eq!((scorecard.process.goals_met, scorecard.process.goals_evaluated), (0, 3));
+        assert_eq!(scorecard.outcome.trade_count, 3);
+        assert!((scorecard.outcome.net_pnl - (490.0 * 2.0 - 1010.0)).abs() < 0.01);
+
+        // Later weeks are filled in up to, but not including, the current week
+        let evaluated = GoalService::evaluate_completed_weeks(&pool, &user_id, NaiveDate::from_ymd_opt(2024, 2, 1).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(evaluated, 1); // Week of Jan 22; the week of Jan 29 is still running
+
+        let stored = GoalScorecardRepository::get_scorecards(&pool, &user_id, None, None).await.unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].process.trades_without_stop, 1);
+        assert_eq!(stored[1].outcome.trade_count, 0);
+        assert_eq!((stored[1].process.goals_met, stored[1].process.goals_evaluated), (3, 3));
+    }
+
+    #[tokio::test]
+    async fn test_process_streaks_skip_untraded_days() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        SettingsService::save_daily_loss_limit(&pool, Some(500.0)).await.unwrap();
+
+        // Jan 15 journaled win, Jan 16 unjournaled win, Jan 17 journaled loss of 1010, Jan 19 journaled win
+        let days = [(15, "AAPL", true, 155.0), (16, "MSFT", false, 155.0), (17, "NVDA", true, 140.0), (19, "AMD", true, 155.0)];
+        for (day, symbol, journaled, exit_price) in days {
+            let mut input = create_test_trade_input(&account_id, symbol);
+            input.trade_date = NaiveDate::from_ymd_opt(2024, 1, day).unwrap();
+            input.exit_price = Some(exit_price);
+            if !journaled {
+                input.notes = None;
+            }
+            TradeService::create_trade(&pool, &user_id, input).await.unwrap();
+        }
+
+        let streaks = GoalService::get_process_streaks(&pool, &user_id).await.unwrap();
+        assert_eq!(streaks.days_traded, 4);
+        assert_eq!(streaks.last_traded, NaiveDate::from_ymd_opt(2024, 1, 19));
+        assert_eq!((streaks.journaled.current, streaks.journaled.longest, streaks.journaled.days_met), (2, 2, 3));
+        assert_eq!(streaks.journaled.last_broken, NaiveDate::from_ymd_opt(2024, 1, 16));
+
+        let within = streaks.within_loss_limit.unwrap();
+        assert_eq!((within.current, within.longest, within.days_met), (1, 2, 3));
+        assert_eq!(within.last_broken, NaiveDate::from_ymd_opt(2024, 1, 17));
+
+        // Without a limit only the journaling streak is tracked
+        SettingsService::save_daily_loss_limit(&pool, None).await.unwrap();
+        let streaks = GoalService::get_process_streaks(&pool, &user_id).await.unwrap();
+        assert!(streaks.within_loss_limit.is_none());
+        assert!(SettingsService::save_daily_loss_limit(&pool, Some(-1.0)).await.is_err());
+    }
+}
@@ -1,18 +1,29 @@
 use std::collections::HashMap;
-use chrono::{NaiveDate, Utc};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::sqlite::SqlitePool;
-use sqlx::Row;
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
+use sqlx::{Acquire, Row};
 
-use crate::models::Direction;
+use crate::calculations::{calculate_hold_minutes, suggest_strategy, FxTable};
+use crate::models::{AssetClass, CommissionSchedule, Direction, ImportBatch, RollType, SeedPosition, Status, TradeTraits};
+use crate::repository::{AccountRepository, ImportBatchRepository, TradeRepository};
+use crate::services::{DailyPerformanceService, FxService, StrategyRuleService, TradeService};
 use crate::parsers::{
-    parse_tlg_file, OptionDetails, OptionType, TlgAction, TlgAssetType, TlgExecution,
+    broker_parser, detect_broker, format_parse_errors, parse_metatrader_statement, OpeningPosition, parse_ninjatrader_trades, parse_tradingview_trades, supported_brokers, OptionDetails, OptionType, RoundTripParseResult, RoundTripTrade, TlgAction, TlgAssetType, TlgExecution,
     TlgParseError, TlgParseResult,
 };
 
+/// Broker execution ID prefix of the synthetic opening fill created from a seed position
+const SEED_EXECUTION_PREFIX: &str = "seed-";
+
+/// Longest gap between closing one option leg and opening the next for the pair to count as a roll
+const ROLL_WINDOW_MINUTES: f64 = 15.0;
+
 /// An individual execution within a trade
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Execution {
+    #[serde(default)]
+    pub id: Option<String>, // Set when loaded from the database
     pub execution_type: String, // "entry" or "exit"
     pub execution_date: NaiveDate,
     pub execution_time: Option<String>,
@@ -21,6 +32,12 @@ pub struct Execution {
     pub fees: f64,
     pub exchange: Option<String>,
     pub broker_execution_id: String,
+    #[serde(default)]
+    pub intended_price: Option<f64>,
+    #[serde(default)]
+    pub order_type: Option<String>,
+    #[serde(default)]
+    pub fees_estimated: bool, // Fees missing from broker data, computed from the commission schedule
 }
 
 /// An aggregated trade ready for import
@@ -29,10 +46,16 @@ pub struct AggregatedTrade {
     pub key: String, // Unique key for selection (symbol + first entry date)
     pub symbol: String,
     pub underlying_symbol: String,
-    pub asset_class: String, // "stock" or "option"
+    pub asset_class: String, // "stock", "option" or "future"
     pub option_type: Option<String>, // "call" or "put"
     pub strike_price: Option<f64>,
     pub expiration_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub multiplier: Option<f64>, // Contract multiplier; None uses the asset class default
+    #[serde(default)]
+    pub fx_rate: Option<f64>, // Converts PnL from the quote currency to the account's base currency
+    #[serde(default)]
+    pub currency: Option<String>, // Quote currency of a crypto pair, converted to the account's on import
     pub direction: String, // "long" or "short"
     pub trade_date: NaiveDate,
     pub entries: Vec<Execution>,
@@ -44,6 +67,8 @@ pub struct AggregatedTrade {
     pub avg_exit_price: Option<f64>,
     pub total_fees: f64,
     pub net_pnl: Option<f64>,
+    #[serde(default)]
+    pub suggested_strategy: Option<String>, // From the user's strategy rules, saved as the strategy on import
 }
 
 impl AggregatedTrade {
@@ -69,8 +94,10 @@ impl AggregatedTrade {
             + self.exits.iter().map(|e| e.fees).sum::<f64>();
 
         // Calculate exit price and PnL if position is closed
+        // Fractional quantities, such as crypto ones, may sum with rounding residue
+        const EPSILON: f64 = 1e-9;
         let exit_qty: f64 = self.exits.iter().map(|e| e.quantity).sum();
-        if exit_qty >= self.total_quantity && !self.exits.is_empty() {
+        if exit_qty >= self.total_quantity - EPSILON && !self.exits.is_empty() {
             self.status = "closed".to_string();
 
             let total_exit_value: f64 = self
@@ -87,9 +114,11 @@ impl AggregatedTrade {
                 (self.avg_entry_price - self.avg_exit_price.unwrap()) * self.total_quantity
             };
 
-            // For options, multiply by contract multiplier (usually 100)
-            let multiplier = if self.asset_class == "option" { 100.0 } else { 1.0 };
-            let gross_pnl = gross_pnl * multiplier;
+            // Options are usually 100 per contract, futures differ per contract
+            let multiplier = self.multiplier.unwrap_or_else(|| {
+                AssetClass::from_str(&self.asset_class).unwrap_or(AssetClass::Stock).multiplier()
+            });
+            let gross_pnl = gross_pnl * multiplier * self.fx_rate.unwrap_or(1.0);
 
             self.net_pnl = Some(gross_pnl - self.total_fees);
         } else {
@@ -98,6 +127,76 @@ impl AggregatedTrade {
             self.net_pnl = None;
         }
     }
+
+    /// What strategy rules look at; hold time runs from the first entry to the last exit
+    pub fn traits(&self) -> TradeTraits {
+        let first_entry = self.entries.first();
+        let last_exit = self.exits.last();
+        let hold_minutes = match (first_entry, last_exit) {
+            (Some(entry), Some(exit)) => calculate_hold_minutes(
+                entry.execution_date,
+                entry.execution_time.as_deref(),
+                exit.execution_date,
+                exit.execution_time.as_deref(),
+            ),
+            _ => None,
+        };
+
+        TradeTraits {
+            symbol: self.underlying_symbol.clone(),
+            direction: Direction::from_str(&self.direction).unwrap_or(Direction::Long),
+            asset_class: AssetClass::from_str(&self.asset_class).unwrap_or(AssetClass::Stock),
+            hold_minutes,
+        }
+    }
+
+    /// Estimate fees for executions the broker reported without any, using the commission schedule
+    pub fn apply_commission_schedule(&mut self, schedule: &CommissionSchedule) {
+        if schedule.is_empty() {
+            return;
+        }
+
+        let is_option = self.asset_class == "option";
+        for execution in self.entries.iter_mut().chain(self.exits.iter_mut()) {
+            let is_seed = execution.broker_execution_id.starts_with(SEED_EXECUTION_PREFIX);
+            if execution.fees == 0.0 && !is_seed {
+                execution.fees = schedule.estimate_fees(execution.quantity, is_option);
+                execution.fees_estimated = true;
+            }
+        }
+        self.calculate_derived();
+    }
+
+    /// Convert a trade quoted in another currency than the account's base currency
+    /// PnL converts at the rate of the last exit, or of the last entry while open, and so do the
+    /// fees, which the exchange charged in the quote currency. Fails when no rate of the pair is stored.
+    pub fn convert_to_currency(&mut self, base_currency: &str, rates: &FxTable) -> Result<(), String> {
+        let Some(currency) = self.currency.as_deref() else {
+            return Ok(());
+        };
+        if self.fx_rate.is_some() || currency.eq_ignore_ascii_case(base_currency) {
+            return Ok(());
+        }
+
+        let date = self.exits.last().or(self.entries.last()).map_or(self.trade_date, |e| e.execution_date);
+        let rate = rates.rate(currency, base_currency, date).ok_or_else(|| {
+            format!("No FX rate from {} to {}; add one under FX rates", currency, base_currency.to_uppercase())
+        })?;
+        for execution in self.entries.iter_mut().chain(self.exits.iter_mut()) {
+            execution.fees *= rate;
+        }
+        self.fx_rate = Some(rate);
+        self.calculate_derived();
+        Ok(())
+    }
+}
+
+/// A previously imported trade whose fills differ from the broker's current data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedTrade {
+    pub existing_trade_id: String,
+    pub trade: AggregatedTrade,
+    pub changes: Vec<String>, // Human-readable description of each difference
 }
 
 /// Preview of what will be imported
@@ -105,16 +204,33 @@ impl AggregatedTrade {
 pub struct ImportPreview {
     pub trades_to_import: Vec<AggregatedTrade>,
     pub open_positions: Vec<AggregatedTrade>,
-    pub duplicate_count: i32,
+    pub duplicate_count: i32, // Already imported and unchanged
+    pub changed_trades: Vec<ChangedTrade>,
     pub parse_errors: Vec<TlgParseError>,
+    #[serde(default)]
+    pub roll_chains: Vec<RollChainCandidate>, // Option legs that look like rolls of one position
+}
+
+/// Option legs closed and reopened at a different strike or expiry, in roll order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollChainCandidate {
+    pub underlying_symbol: String,
+    pub option_type: Option<String>,
+    pub keys: Vec<String>, // Aggregated trade keys, earliest leg first
+    pub combined_net_pnl: f64, // Realized PnL summed across the closed legs
 }
 
 /// Result of executing an import
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportResult {
     pub imported_count: i32,
+    pub updated_count: i32,
     pub skipped_duplicates: i32,
     pub errors: Vec<String>,
+    #[serde(default)]
+    pub roll_links: i32, // Imported legs linked to the next leg of their roll chain
+    #[serde(default)]
+    pub batch_id: Option<String>, // Undoes the import with undo_import; None when nothing was imported
 }
 
 /// Position tracker for aggregating executions into trades
@@ -169,14 +285,19 @@ impl PositionTracker {
             .entries
             .iter()
             .map(|e| Execution {
+                id: None,
                 execution_type: "entry".to_string(),
                 execution_date: e.execution_date,
-                execution_time: Some(e.execution_time.clone()),
+                // Reports without execution times leave it empty
+                execution_time: Some(e.execution_time.clone()).filter(|t| !t.is_empty()),
                 quantity: e.abs_quantity(),
                 price: e.price,
                 fees: e.abs_fees(),
                 exchange: Some(e.exchange.clone()),
                 broker_execution_id: e.broker_execution_id.clone(),
+                intended_price: None,
+                order_type: None,
+                fees_estimated: false,
             })
             .collect();
 
@@ -184,14 +305,18 @@ impl PositionTracker {
             .exits
             .iter()
             .map(|e| Execution {
+                id: None,
                 execution_type: "exit".to_string(),
                 execution_date: e.execution_date,
-                execution_time: Some(e.execution_time.clone()),
+                execution_time: Some(e.execution_time.clone()).filter(|t| !t.is_empty()),
                 quantity: e.abs_quantity(),
                 price: e.price,
                 fees: e.abs_fees(),
                 exchange: Some(e.exchange.clone()),
                 broker_execution_id: e.broker_execution_id.clone(),
+                intended_price: None,
+                order_type: None,
+                fees_estimated: false,
             })
             .collect();
 
@@ -211,7 +336,8 @@ impl PositionTracker {
                 Some(details.strike_price),
                 Some(details.expiration_date),
             ),
-            None => (None, None, None),
+            // Futures carry only an expiry
+            None => (None, None, self.entries.iter().chain(&self.exits).find_map(|e| e.expiration_date())),
         };
 
         let mut trade = AggregatedTrade {
@@ -221,10 +347,23 @@ impl PositionTracker {
             asset_class: match self.asset_class {
                 TlgAssetType::Stock => "stock".to_string(),
                 TlgAssetType::Option => "option".to_string(),
+                TlgAssetType::Future => "future".to_string(),
+                TlgAssetType::Forex => "forex".to_string(),
+                TlgAssetType::Crypto => "crypto".to_string(),
             },
             option_type,
             strike_price,
             expiration_date,
+            multiplier: self.entries.iter().chain(&self.exits).next().map(|e| e.multiplier),
+            // Realized PnL converts at the rate of the last exit, open PnL at that of the last entry
+            fx_rate: match self.asset_class {
+                TlgAssetType::Forex => self.exits.last().or(self.entries.last()).and_then(|e| e.fx_rate),
+                _ => None,
+            },
+            currency: match self.asset_class {
+                TlgAssetType::Crypto => self.entries.iter().chain(&self.exits).next().map(|e| e.currency.clone()),
+                _ => None,
+            },
             direction: match self.direction {
                 Some(Direction::Long) => "long".to_string(),
                 Some(Direction::Short) => "short".to_string(),
@@ -239,6 +378,7 @@ impl PositionTracker {
             avg_exit_price: None,
             total_fees: 0.0,
             net_pnl: None,
+            suggested_strategy: None,
         };
 
         trade.calculate_derived();
@@ -246,12 +386,242 @@ impl PositionTracker {
     }
 }
 
+/// Synthetic opening fill for a seed position, shaped like the symbol's first imported execution
+fn seed_execution(seed: &SeedPosition, first: &TlgExecution) -> TlgExecution {
+    let (action, quantity) = match seed.direction {
+        Direction::Long => (TlgAction::BuyToOpen, seed.quantity),
+        Direction::Short => (TlgAction::SellToOpen, -seed.quantity),
+    };
+
+    TlgExecution {
+        broker_execution_id: format!("{}{}", SEED_EXECUTION_PREFIX, seed.id),
+        symbol: first.symbol.clone(),
+        name: first.name.clone(),
+        exchange: "SEED".to_string(),
+        action,
+        execution_date: seed.open_date,
+        execution_time: "00:00:00".to_string(),
+        currency: first.currency.clone(),
+        quantity,
+        multiplier: first.multiplier,
+        price: seed.avg_cost,
+        total: quantity * seed.avg_cost * first.multiplier,
+        fees: 0.0,
+        fx_rate: None,
+        asset_type: first.asset_type,
+        option_details: first.option_details.clone(),
+        future_details: first.future_details.clone(),
+    }
+}
+
+/// Closed trade with one entry and one exit for a round trip the broker already matched
+/// The commission is split evenly between the two fills.
+fn roundtrip_to_aggregated(roundtrip: &RoundTripTrade, exchange: &str) -> AggregatedTrade {
+    let fill = |execution_type: &str, at: NaiveDateTime, price: f64| Execution {
+        id: None,
+        execution_type: execution_type.to_string(),
+        execution_date: at.date(),
+        execution_time: Some(at.format("%H:%M:%S").to_string()),
+        quantity: roundtrip.quantity,
+        price,
+        fees: roundtrip.commission / 2.0,
+        exchange: Some(exchange.to_string()),
+        broker_execution_id: format!("{}-{}", roundtrip.broker_trade_id, execution_type),
+        intended_price: None,
+        order_type: None,
+        fees_estimated: false,
+    };
+
+    let mut trade = AggregatedTrade {
+        key: roundtrip.broker_trade_id.clone(),
+        symbol: roundtrip.symbol.clone(),
+        underlying_symbol: roundtrip.symbol.split_whitespace().next().unwrap_or_default().to_string(),
+        asset_class: match roundtrip.asset_type {
+            TlgAssetType::Stock => "stock".to_string(),
+            TlgAssetType::Option => "option".to_string(),
+            TlgAssetType::Future => "future".to_string(),
+            TlgAssetType::Forex => "forex".to_string(),
+            TlgAssetType::Crypto => "crypto".to_string(),
+        },
+        option_type: None,
+        strike_price: None,
+        expiration_date: None,
+        multiplier: Some(roundtrip.multiplier),
+        fx_rate: roundtrip.fx_rate,
+        currency: None,
+        direction: if roundtrip.short { "short" } else { "long" }.to_string(),
+        trade_date: roundtrip.entry_time.date(),
+        entries: vec![fill("entry", roundtrip.entry_time, roundtrip.entry_price)],
+        exits: vec![fill("exit", roundtrip.exit_time, roundtrip.exit_price)],
+        status: "closed".to_string(),
+        total_quantity: 0.0,
+        avg_entry_price: 0.0,
+        avg_exit_price: None,
+        total_fees: 0.0,
+        net_pnl: None,
+        suggested_strategy: None,
+    };
+    trade.calculate_derived();
+    trade
+}
+
+/// Describe differences between stored executions and the broker's current fills
+fn diff_executions(stored: &[Execution], trade: &AggregatedTrade) -> Vec<String> {
+    const EPSILON: f64 = 1e-9;
+    let mut changes = Vec::new();
+    let stored_by_id: HashMap<&str, &Execution> = stored
+        .iter()
+        .map(|e| (e.broker_execution_id.as_str(), e))
+        .collect();
+
+    let incoming: Vec<&Execution> = trade.entries.iter().chain(trade.exits.iter()).collect();
+    for execution in &incoming {
+        let id = execution.broker_execution_id.as_str();
+        let Some(previous) = stored_by_id.get(id) else {
+            changes.push(format!(
+                "New fill {}: {} @ {:.2}",
+                id, execution.quantity, execution.price
+            ));
+            continue;
+        };
+
+        if (previous.quantity - execution.quantity).abs() > EPSILON {
+            changes.push(format!(
+                "Fill {}: quantity {} -> {}",
+                id, previous.quantity, execution.quantity
+            ));
+        }
+        if (previous.price - execution.price).abs() > EPSILON {
+            changes.push(format!(
+                "Fill {}: price {:.4} -> {:.4}",
+                id, previous.price, execution.price
+            ));
+        }
+        // An estimate stands in for fees the broker still doesn't report
+        let fees_still_missing = previous.fees_estimated && execution.fees == 0.0;
+        if !fees_still_missing && (previous.fees - execution.fees).abs() > EPSILON {
+            changes.push(format!(
+                "Fill {}: fees {:.2} -> {:.2}",
+                id, previous.fees, execution.fees
+            ));
+        }
+    }
+
+    for previous in stored {
+        if !incoming.iter().any(|e| e.broker_execution_id == previous.broker_execution_id) {
+            changes.push(format!("Fill {} is no longer reported", previous.broker_execution_id));
+        }
+    }
+
+    changes
+}
+
+/// Whether `next` reopens the position `prev` closed, and the minutes between the two
+/// Without execution times both fills must fall on the same day.
+fn roll_gap_minutes(prev: &AggregatedTrade, next: &AggregatedTrade) -> Option<f64> {
+    if prev.asset_class != "option"
+        || next.asset_class != "option"
+        || prev.status != "closed"
+        || prev.underlying_symbol != next.underlying_symbol
+        || prev.option_type != next.option_type
+        || prev.direction != next.direction
+        || (prev.strike_price == next.strike_price && prev.expiration_date == next.expiration_date)
+    {
+        return None;
+    }
+
+    let close = prev.exits.last()?;
+    let open = next.entries.first()?;
+    let first_open = prev.entries.first()?;
+    let opened_before = (first_open.execution_date, first_open.execution_time.as_deref())
+        < (open.execution_date, open.execution_time.as_deref());
+    if !opened_before {
+        return None;
+    }
+
+    let gap = calculate_hold_minutes(
+        close.execution_date,
+        close.execution_time.as_deref(),
+        open.execution_date,
+        open.execution_time.as_deref(),
+    )
+    .map(f64::abs)
+    .or_else(|| (close.execution_date == open.execution_date).then_some(0.0))?;
+
+    (gap <= ROLL_WINDOW_MINUTES).then_some(gap)
+}
+
+/// Group option legs into roll chains
+/// Each closed leg links to the closest reopening within the roll window that no other leg claimed.
+fn detect_roll_chains(trades: &[&AggregatedTrade]) -> Vec<RollChainCandidate> {
+    let mut order: Vec<usize> = (0..trades.len()).collect();
+    order.sort_by(|&a, &b| {
+        let first = |i: usize| {
+            trades[i]
+                .entries
+                .first()
+                .map(|e| (e.execution_date, e.execution_time.clone()))
+        };
+        first(a).cmp(&first(b))
+    });
+
+    let mut next_leg: HashMap<usize, usize> = HashMap::new();
+    let mut has_prev = vec![false; trades.len()];
+    for &prev in &order {
+        let best = order
+            .iter()
+            .filter(|&&next| next != prev && !has_prev[next])
+            .filter_map(|&next| roll_gap_minutes(trades[prev], trades[next]).map(|gap| (next, gap)))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((next, _)) = best {
+            next_leg.insert(prev, next);
+            has_prev[next] = true;
+        }
+    }
+
+    order
+        .iter()
+        .filter(|&&start| !has_prev[start] && next_leg.contains_key(&start))
+        .map(|&start| {
+            let mut legs = vec![start];
+            while let Some(&next) = next_leg.get(legs.last().unwrap()) {
+                legs.push(next);
+            }
+            RollChainCandidate {
+                underlying_symbol: trades[start].underlying_symbol.clone(),
+                option_type: trades[start].option_type.clone(),
+                keys: legs.iter().map(|&i| trades[i].key.clone()).collect(),
+                combined_net_pnl: legs.iter().filter_map(|&i| trades[i].net_pnl).sum(),
+            }
+        })
+        .collect()
+}
+
 pub struct ImportService;
 
 impl ImportService {
     /// Parse a TLG file and aggregate executions into trades
+    #[cfg(test)]
     pub fn parse_and_aggregate(content: &str) -> (Vec<AggregatedTrade>, Vec<AggregatedTrade>, Vec<TlgParseError>) {
-        let TlgParseResult { executions, errors } = parse_tlg_file(content);
+        Self::parse_and_aggregate_with_seeds(content, &[])
+    }
+
+    /// Parse a TLG file and aggregate executions into trades, starting from seeded open positions
+    #[cfg(test)]
+    pub fn parse_and_aggregate_with_seeds(
+        content: &str,
+        seeds: &[SeedPosition],
+    ) -> (Vec<AggregatedTrade>, Vec<AggregatedTrade>, Vec<TlgParseError>) {
+        Self::aggregate_with_seeds(crate::parsers::parse_tlg_file(content), seeds)
+    }
+
+    /// Aggregate parsed executions into trades, starting from seeded open positions
+    /// Executions of a seeded symbol on or before the seed's as-of date are already part of the seed.
+    fn aggregate_with_seeds(
+        parsed: TlgParseResult,
+        seeds: &[SeedPosition],
+    ) -> (Vec<AggregatedTrade>, Vec<AggregatedTrade>, Vec<TlgParseError>) {
+        let TlgParseResult { executions, errors } = parsed;
 
         // Group executions by symbol
         let mut trackers: HashMap<String, PositionTracker> = HashMap::new();
@@ -270,9 +640,18 @@ impl ImportService {
             let asset_type = exec.asset_type;
             let option_details = exec.option_details.clone();
 
-            let tracker = trackers
-                .entry(symbol.clone())
-                .or_insert_with(|| PositionTracker::new(&symbol, &underlying, asset_type, option_details));
+            let seed = seeds.iter().find(|s| s.symbol.eq_ignore_ascii_case(&symbol));
+            if matches!(seed, Some(s) if exec.execution_date <= s.as_of_date) {
+                continue;
+            }
+
+            let tracker = trackers.entry(symbol.clone()).or_insert_with(|| {
+                let mut tracker = PositionTracker::new(&symbol, &underlying, asset_type, option_details);
+                if let Some(seed) = seed {
+                    tracker.add_execution(seed_execution(seed, &exec));
+                }
+                tracker
+            });
 
             tracker.add_execution(exec);
         }
@@ -297,70 +676,308 @@ impl ImportService {
         (closed_trades, open_positions, errors)
     }
 
-    /// Generate a preview of the import
+    /// Write failed lines with their error reasons to a file for correction and re-import
+    pub fn export_parse_errors(file_path: &str, errors: &[TlgParseError]) -> Result<(), String> {
+        std::fs::write(file_path, format_parse_errors(errors))
+            .map_err(|e| format!("Failed to write file: {}", e))
+    }
+
+    /// Generate a preview of importing a TLG file or any other registered broker export,
+    /// with the format detected from the content
     pub async fn preview_import(
         pool: &SqlitePool,
+        user_id: &str,
         content: &str,
+        seeds: &[SeedPosition],
     ) -> Result<ImportPreview, String> {
-        let (closed_trades, open_positions, errors) = Self::parse_and_aggregate(content);
+        let parser = detect_broker(content).ok_or_else(|| {
+            let names: Vec<String> = supported_brokers().into_iter().map(|broker| broker.name).collect();
+            format!("Unrecognized file format. Supported formats: {}", names.join(", "))
+        })?;
+        let parsed = parser.parse(content, &Self::opening_positions(seeds));
+        Self::preview_parsed(pool, user_id, parsed, seeds).await
+    }
 
-        // Check for duplicates
+    /// Generate a preview of importing the export of a registered broker, by its ID
+    pub async fn preview_broker_import(
+        pool: &SqlitePool,
+        user_id: &str,
+        broker_id: &str,
+        content: &str,
+        seeds: &[SeedPosition],
+    ) -> Result<ImportPreview, String> {
+        let parser = broker_parser(broker_id).ok_or_else(|| format!("Unknown broker: {}", broker_id))?;
+        let parsed = parser.parse(content, &Self::opening_positions(seeds));
+        Self::preview_parsed(pool, user_id, parsed, seeds).await
+    }
+
+    /// Seed positions as the opening positions an export's fills are matched against
+    fn opening_positions(seeds: &[SeedPosition]) -> Vec<OpeningPosition> {
+        seeds
+            .iter()
+            .map(|seed| OpeningPosition {
+                symbol: seed.symbol.clone(),
+                quantity: match seed.direction {
+                    Direction::Long => seed.quantity,
+                    Direction::Short => -seed.quantity,
+                },
+                as_of_date: seed.as_of_date,
+            })
+            .collect()
+    }
+
+    async fn preview_parsed(
+        pool: &SqlitePool,
+        user_id: &str,
+        parsed: TlgParseResult,
+        seeds: &[SeedPosition],
+    ) -> Result<ImportPreview, String> {
+        let (closed_trades, mut open_positions, errors) = Self::aggregate_with_seeds(parsed, seeds);
+        let (rules, watchlists) = StrategyRuleService::get_rule_set(pool, user_id).await?;
+
+        // Split into new trades, unchanged duplicates and trades changed at the broker
         let mut duplicate_count = 0;
         let mut trades_to_import = Vec::new();
+        let mut changed_trades = Vec::new();
 
-        for trade in closed_trades {
-            let mut has_duplicate = false;
-            for entry in &trade.entries {
-                if Self::execution_exists(pool, &entry.broker_execution_id).await? {
-                    has_duplicate = true;
-                    duplicate_count += 1;
-                    break;
-                }
-            }
-            if !has_duplicate {
+        for mut trade in closed_trades {
+            let Some(existing_trade_id) = Self::find_imported_trade_id(pool, &trade).await? else {
+                trade.suggested_strategy = suggest_strategy(&rules, &watchlists, &trade.traits());
                 trades_to_import.push(trade);
+                continue;
+            };
+
+            let stored = Self::get_trade_executions(pool, &existing_trade_id).await?;
+            let changes = diff_executions(&stored, &trade);
+            if changes.is_empty() {
+                duplicate_count += 1;
+            } else {
+                changed_trades.push(ChangedTrade {
+                    existing_trade_id,
+                    trade,
+                    changes,
+                });
             }
         }
 
+        for position in &mut open_positions {
+            position.suggested_strategy = suggest_strategy(&rules, &watchlists, &position.traits());
+        }
+
+        let new_legs: Vec<&AggregatedTrade> = trades_to_import.iter().chain(open_positions.iter()).collect();
+        let roll_chains = detect_roll_chains(&new_legs);
+
         Ok(ImportPreview {
             trades_to_import,
             open_positions,
             duplicate_count,
+            changed_trades,
             parse_errors: errors,
+            roll_chains,
         })
     }
 
     /// Check if an execution already exists by broker ID
-    async fn execution_exists(pool: &SqlitePool, broker_execution_id: &str) -> Result<bool, String> {
+    async fn execution_exists(conn: &mut SqliteConnection, broker_execution_id: &str) -> Result<bool, String> {
         let exists: bool = sqlx::query_scalar(
             "SELECT EXISTS(SELECT 1 FROM trade_executions WHERE broker_execution_id = ?)",
         )
         .bind(broker_execution_id)
-        .fetch_one(pool)
+        .fetch_one(conn)
         .await
         .map_err(|e| format!("Database error: {}", e))?;
 
         Ok(exists)
     }
 
-    /// Execute the import for selected trades
+    /// Find the trade that an earlier import created from any of these executions
+    async fn find_imported_trade_id(
+        pool: &SqlitePool,
+        trade: &AggregatedTrade,
+    ) -> Result<Option<String>, String> {
+        for execution in trade.entries.iter().chain(trade.exits.iter()) {
+            let trade_id: Option<String> = sqlx::query_scalar(
+                "SELECT trade_id FROM trade_executions WHERE broker_execution_id = ? LIMIT 1",
+            )
+            .bind(&execution.broker_execution_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+            if trade_id.is_some() {
+                return Ok(trade_id);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Replace previously imported trades with the broker's corrected fills
+    /// Intended prices and order types recorded on matching fills are kept. The updates and the
+    /// daily performance refresh run in one transaction; a trade that fails is rolled back alone.
+    pub async fn update_changed_trades(
+        pool: &SqlitePool,
+        user_id: &str,
+        changed_trades: Vec<ChangedTrade>,
+    ) -> Result<ImportResult, String> {
+        let mut updated_count = 0;
+        let mut errors = Vec::new();
+        let trade_ids: Vec<String> = changed_trades.iter().map(|c| c.existing_trade_id.clone()).collect();
+
+        let mut prepared = Vec::with_capacity(changed_trades.len());
+        for changed in changed_trades {
+            match Self::prepare_update(pool, user_id, &changed).await {
+                Ok(update) => prepared.push(update),
+                Err(e) => errors.push(format!("Failed to update {}: {}", changed.trade.symbol, e)),
+            }
+        }
+
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        let previous_days = DailyPerformanceService::trade_days(&mut tx, &trade_ids).await?;
+        for (trade_id, stored, trade) in &prepared {
+            let mut savepoint = tx.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+            match Self::update_single_trade(&mut savepoint, trade_id, stored, trade).await {
+                Ok(()) => {
+                    savepoint.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+                    updated_count += 1;
+                }
+                Err(e) => errors.push(format!("Failed to update {}: {}", trade.symbol, e)),
+            }
+        }
+        DailyPerformanceService::refresh_trades(&mut tx, &trade_ids, previous_days).await?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+
+        Ok(ImportResult {
+            imported_count: 0,
+            updated_count,
+            skipped_duplicates: 0,
+            errors,
+            roll_links: 0,
+            batch_id: None,
+        })
+    }
+
+    /// Check a previously imported trade can be updated and load what the update keeps
+    /// Returns the trade's ID, its stored fills by broker execution ID and the corrected trade
+    /// with the account's commission schedule applied.
+    async fn prepare_update(
+        pool: &SqlitePool,
+        user_id: &str,
+        changed: &ChangedTrade,
+    ) -> Result<(String, HashMap<String, Execution>, AggregatedTrade), String> {
+        let existing = TradeRepository::get_by_id(pool, &changed.existing_trade_id)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?
+            .filter(|t| t.user_id == user_id)
+            .ok_or_else(|| "Trade not found".to_string())?;
+        if existing.is_locked {
+            return Err("Trade is in a locked period".to_string());
+        }
+        // Nor can the broker's correction move it into one
+        TradeService::ensure_period_unlocked(pool, &existing.account_id, changed.trade.trade_date).await?;
+
+        let stored: HashMap<String, Execution> = Self::get_trade_executions(pool, &existing.id)
+            .await?
+            .into_iter()
+            .map(|e| (e.broker_execution_id.clone(), e))
+            .collect();
+
+        let mut trade = changed.trade.clone();
+        let (base_currency, rates) = Self::get_account_rates(pool, &existing.account_id).await?;
+        trade.convert_to_currency(&base_currency, &rates)?;
+        if let Some(schedule) = Self::get_commission_schedule(pool, &existing.account_id).await? {
+            trade.apply_commission_schedule(&schedule);
+        }
+        Ok((existing.id, stored, trade))
+    }
+
+    /// Update a single previously imported trade and replace its executions
+    async fn update_single_trade(
+        conn: &mut SqliteConnection,
+        trade_id: &str,
+        stored: &HashMap<String, Execution>,
+        trade: &AggregatedTrade,
+    ) -> Result<(), String> {
+        let entry_time = trade.entries.first().and_then(|e| e.execution_time.clone());
+        let exit_time = trade.exits.last().and_then(|e| e.execution_time.clone());
+        let status = if trade.status == "closed" { "closed" } else { "open" };
+
+        sqlx::query(
+            r#"
+            UPDATE trades SET
+                trade_date = ?, direction = ?, quantity = ?, entry_price = ?, exit_price = ?,
+                entry_time = ?, exit_time = ?, fees = ?, fx_rate = ?, status = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(trade.trade_date)
+        .bind(&trade.direction)
+        .bind(trade.total_quantity)
+        .bind(trade.avg_entry_price)
+        .bind(trade.avg_exit_price)
+        .bind(&entry_time)
+        .bind(&exit_time)
+        .bind(trade.total_fees)
+        .bind(trade.fx_rate)
+        .bind(status)
+        .bind(Utc::now())
+        .bind(trade_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to update trade: {}", e))?;
+
+        sqlx::query("DELETE FROM trade_executions WHERE trade_id = ?")
+            .bind(trade_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to delete executions: {}", e))?;
+
+        for execution in trade.entries.iter().chain(trade.exits.iter()) {
+            let mut execution = execution.clone();
+            if let Some(previous) = stored.get(&execution.broker_execution_id) {
+                execution.intended_price = previous.intended_price;
+                execution.order_type = previous.order_type.clone();
+            }
+            Self::insert_execution(conn, trade_id, &execution).await?;
+        }
+        Ok(())
+    }
+
+    /// Execute the import for selected trades, recording it as an import batch
+    /// Imported legs of an accepted roll chain are linked to the next leg as its continuation.
+    /// Lines of the file that failed to parse are recorded with the batch's own errors.
+    #[allow(clippy::too_many_arguments)]
     pub async fn execute_import(
         pool: &SqlitePool,
         user_id: &str,
         account_id: &str,
         trades: Vec<AggregatedTrade>,
         skip_duplicates: bool,
+        roll_chains: &[RollChainCandidate],
+        parse_errors: &[TlgParseError],
+        file_name: Option<&str>,
     ) -> Result<ImportResult, String> {
         let mut imported_count = 0;
         let mut skipped_duplicates = 0;
         let mut errors = Vec::new();
-
-        for trade in trades {
+        let mut imported: HashMap<String, (String, NaiveDate)> = HashMap::new();
+        let mut execution_count = 0;
+        let schedule = Self::get_commission_schedule(pool, account_id).await?;
+        let lock = AccountRepository::get_period_lock(pool, account_id)
+            .await
+            .map_err(|e| format!("Failed to check period lock: {}", e))?;
+        let (base_currency, rates) = Self::get_account_rates(pool, account_id).await?;
+
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        let batch = ImportBatchRepository::insert(&mut tx, user_id, account_id, file_name)
+            .await
+            .map_err(|e| format!("Failed to record import batch: {}", e))?;
+
+        for mut trade in trades {
             // Check for duplicates if requested
             if skip_duplicates {
                 let mut has_duplicate = false;
                 for entry in &trade.entries {
-                    if Self::execution_exists(pool, &entry.broker_execution_id).await? {
+                    if Self::execution_exists(&mut tx, &entry.broker_execution_id).await? {
                         has_duplicate = true;
                         break;
                     }
@@ -371,39 +988,242 @@ impl ImportService {
                 }
             }
 
-            // Import the trade
-            match Self::import_single_trade(pool, user_id, account_id, &trade).await {
-                Ok(_) => imported_count += 1,
+            // Trades dated in the account's locked period are reported instead of imported
+            if let Some(Err(e)) = lock.as_ref().map(|lock| lock.ensure_unlocked(trade.trade_date)) {
+                errors.push(format!("Failed to import {}: {}", trade.symbol, e));
+                continue;
+            }
+
+            // Crypto pairs quoted in another currency are converted to the account's
+            if let Err(e) = trade.convert_to_currency(&base_currency, &rates) {
+                errors.push(format!("Failed to import {}: {}", trade.symbol, e));
+                continue;
+            }
+            if let Some(schedule) = &schedule {
+                trade.apply_commission_schedule(schedule);
+            }
+
+            // Import the trade, rolling back only its own rows if it fails
+            let mut savepoint = tx.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+            match Self::import_single_trade(&mut savepoint, user_id, account_id, &batch.id, &trade).await {
+                Ok(trade_id) => {
+                    savepoint.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+                    imported_count += 1;
+                    execution_count += (trade.entries.len() + trade.exits.len()) as i32;
+                    imported.insert(trade.key.clone(), (trade_id, trade.trade_date));
+                }
                 Err(e) => errors.push(format!("Failed to import {}: {}", trade.symbol, e)),
             }
         }
 
+        let mut roll_links = 0;
+        for chain in roll_chains {
+            for pair in chain.keys.windows(2) {
+                let (Some((from_id, _)), Some((to_id, rolled_on))) = (imported.get(&pair[0]), imported.get(&pair[1])) else {
+                    continue;
+                };
+                match TradeRepository::set_roll(&mut tx, from_id, Some(RollType::Rolled), Some(*rolled_on), Some(to_id), Status::Closed).await {
+                    Ok(_) => roll_links += 1,
+                    Err(e) => errors.push(format!("Failed to link roll of {}: {}", chain.underlying_symbol, e)),
+                }
+            }
+        }
+
+        errors.extend(Self::parse_error_messages(parse_errors));
+
+        let trade_ids: Vec<String> = imported.values().map(|(id, _)| id.clone()).collect();
+        DailyPerformanceService::refresh_trades(&mut tx, &trade_ids, Vec::new()).await?;
+
+        ImportBatchRepository::set_counts(&mut tx, &batch.id, imported_count, execution_count, skipped_duplicates)
+            .await
+            .map_err(|e| format!("Failed to record import batch: {}", e))?;
+        ImportBatchRepository::set_errors_in(&mut tx, &batch.id, &errors)
+            .await
+            .map_err(|e| format!("Failed to record import batch: {}", e))?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+
         Ok(ImportResult {
             imported_count,
+            updated_count: 0,
             skipped_duplicates,
             errors,
+            roll_links,
+            batch_id: Some(batch.id),
         })
     }
 
+    /// Import a NinjaTrader Trades grid export
+    pub async fn import_ninjatrader_trades(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: &str,
+        content: &str,
+        skip_duplicates: bool,
+        file_name: Option<&str>,
+    ) -> Result<ImportResult, String> {
+        let parsed = parse_ninjatrader_trades(content);
+        Self::import_roundtrips(pool, user_id, account_id, parsed, "NINJATRADER", skip_duplicates, file_name).await
+    }
+
+    /// Import the closed tickets of a MetaTrader 4 or 5 account statement
+    pub async fn import_metatrader_statement(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: &str,
+        content: &str,
+        skip_duplicates: bool,
+        file_name: Option<&str>,
+    ) -> Result<ImportResult, String> {
+        let parsed = parse_metatrader_statement(content);
+        Self::import_roundtrips(pool, user_id, account_id, parsed, "METATRADER", skip_duplicates, file_name).await
+    }
+
+    /// Import the List of Trades of a TradingView strategy tester or paper trading export
+    /// The trades are simulated, so they only go into a paper account, where they stay out of
+    /// headline metrics. The export names no symbol, so the chart's symbol is given.
+    pub async fn import_tradingview_trades(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: &str,
+        content: &str,
+        symbol: &str,
+        skip_duplicates: bool,
+        file_name: Option<&str>,
+    ) -> Result<ImportResult, String> {
+        let account = AccountRepository::get_by_id(pool, account_id)
+            .await
+            .map_err(|e| format!("Failed to get account: {}", e))?
+            .filter(|account| account.user_id == user_id)
+            .ok_or_else(|| format!("Account not found: {}", account_id))?;
+        if !account.is_paper {
+            return Err(format!("TradingView trades are simulated; {} is not a paper account", account.name));
+        }
+
+        let parsed = parse_tradingview_trades(content, symbol)?;
+        Self::import_roundtrips(pool, user_id, account_id, parsed, "TRADINGVIEW", skip_duplicates, file_name).await
+    }
+
+    /// Import round trips the broker already matched directly as closed trades
+    /// Unlike execute_import there is no preview or execution matching: each round trip becomes one
+    /// trade with a single entry and exit fill. Unparseable rows are reported as errors.
+    pub async fn import_roundtrips(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: &str,
+        parsed: RoundTripParseResult,
+        exchange: &str,
+        skip_duplicates: bool,
+        file_name: Option<&str>,
+    ) -> Result<ImportResult, String> {
+        let (rules, watchlists) = StrategyRuleService::get_rule_set(pool, user_id).await?;
+        let trades = parsed
+            .trades
+            .iter()
+            .map(|roundtrip| {
+                let mut trade = roundtrip_to_aggregated(roundtrip, exchange);
+                trade.suggested_strategy = suggest_strategy(&rules, &watchlists, &trade.traits());
+                trade
+            })
+            .collect();
+
+        Self::execute_import(pool, user_id, account_id, trades, skip_duplicates, &[], &parsed.errors, file_name).await
+    }
+
+    /// Import errors for lines of a file that failed to parse
+    pub(crate) fn parse_error_messages(errors: &[TlgParseError]) -> Vec<String> {
+        errors.iter().map(|e| format!("Line {}: {}", e.line_number, e.error)).collect()
+    }
+
+    /// Get a user's past imports, most recent first, with what each loaded and the errors it hit
+    pub async fn get_import_history(pool: &SqlitePool, user_id: &str) -> Result<Vec<ImportBatch>, String> {
+        ImportBatchRepository::get_batches(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get import history: {}", e))
+    }
+
+    /// Delete every trade an import created, with its executions, and mark the import undone
+    /// Instruments the import added stay, as other trades may use them. Nothing is deleted when a
+    /// trade falls in the account's locked period. Returns the number of trades deleted.
+    pub async fn undo_import(pool: &SqlitePool, user_id: &str, batch_id: &str) -> Result<u64, String> {
+        let batch = ImportBatchRepository::get_by_id(pool, user_id, batch_id)
+            .await
+            .map_err(|e| format!("Failed to get import batch: {}", e))?
+            .ok_or_else(|| format!("Import batch not found: {}", batch_id))?;
+        if batch.undone_at.is_some() {
+            return Err("Import was already undone".to_string());
+        }
+
+        let trades = ImportBatchRepository::get_trades(pool, batch_id)
+            .await
+            .map_err(|e| format!("Failed to get imported trades: {}", e))?;
+        let lock = AccountRepository::get_period_lock(pool, &batch.account_id)
+            .await
+            .map_err(|e| format!("Failed to check period lock: {}", e))?;
+        // A trade changes the locked period when it was entered or exited within it
+        let earliest = trades
+            .iter()
+            .flat_map(|(_, entry_date, first_exit_date)| std::iter::once(*entry_date).chain(*first_exit_date))
+            .min();
+        if let (Some(lock), Some(date)) = (lock, earliest) {
+            if date <= lock.locked_through {
+                return Err(format!(
+                    "Trade date {} is in a locked period (locked through {}). Unlock the period to undo the import.",
+                    date, lock.locked_through
+                ));
+            }
+        }
+
+        let trade_ids: Vec<String> = trades.into_iter().map(|(id, _, _)| id).collect();
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        let previous_days = DailyPerformanceService::trade_days(&mut tx, &trade_ids).await?;
+        let deleted = ImportBatchRepository::undo(&mut tx, batch_id, Utc::now())
+            .await
+            .map_err(|e| format!("Failed to undo import: {}", e))?;
+        DailyPerformanceService::refresh_days(&mut tx, previous_days).await?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+
+        Ok(deleted)
+    }
+
+    async fn get_commission_schedule(
+        pool: &SqlitePool,
+        account_id: &str,
+    ) -> Result<Option<CommissionSchedule>, String> {
+        AccountRepository::get_commission_schedule(pool, account_id)
+            .await
+            .map_err(|e| format!("Database error: {}", e))
+    }
+
+    /// Get an account's base currency with the stored FX rates to convert to it
+    async fn get_account_rates(pool: &SqlitePool, account_id: &str) -> Result<(String, FxTable), String> {
+        let account = AccountRepository::get_by_id(pool, account_id)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?
+            .ok_or_else(|| "Account not found".to_string())?;
+        let rates = FxService::get_rates(pool).await?;
+        Ok((account.base_currency, FxTable::new(&rates)))
+    }
+
     /// Import a single aggregated trade
     async fn import_single_trade(
-        pool: &SqlitePool,
+        conn: &mut SqliteConnection,
         user_id: &str,
         account_id: &str,
+        batch_id: &str,
         trade: &AggregatedTrade,
     ) -> Result<String, String> {
         // Get or create instrument
-        let instrument_id = Self::get_or_create_instrument(pool, trade).await?;
+        let instrument_id = Self::get_or_create_instrument(conn, trade).await?;
 
         // Create the trade record
-        let trade_id = Self::create_trade_record(pool, user_id, account_id, &instrument_id, trade).await?;
+        let trade_id = Self::create_trade_record(conn, user_id, account_id, &instrument_id, batch_id, trade).await?;
 
         // Insert executions
         for entry in &trade.entries {
-            Self::insert_execution(pool, &trade_id, entry).await?;
+            Self::insert_execution(conn, &trade_id, entry).await?;
         }
         for exit in &trade.exits {
-            Self::insert_execution(pool, &trade_id, exit).await?;
+            Self::insert_execution(conn, &trade_id, exit).await?;
         }
 
         Ok(trade_id)
@@ -411,7 +1231,7 @@ impl ImportService {
 
     /// Get or create an instrument for the trade
     async fn get_or_create_instrument(
-        pool: &SqlitePool,
+        conn: &mut SqliteConnection,
         trade: &AggregatedTrade,
     ) -> Result<String, String> {
         // Check if instrument exists
@@ -419,7 +1239,7 @@ impl ImportService {
             "SELECT id FROM instruments WHERE symbol = ?",
         )
         .bind(&trade.symbol)
-        .fetch_optional(pool)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(|e| format!("Database error: {}", e))?;
 
@@ -433,8 +1253,8 @@ impl ImportService {
 
         sqlx::query(
             r#"
-            INSERT INTO instruments (id, symbol, asset_class, underlying_symbol, option_type, strike_price, expiration_date, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO instruments (id, symbol, asset_class, underlying_symbol, option_type, strike_price, expiration_date, multiplier, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&id)
@@ -444,8 +1264,9 @@ impl ImportService {
         .bind(&trade.option_type)
         .bind(trade.strike_price)
         .bind(trade.expiration_date)
+        .bind(trade.multiplier)
         .bind(now)
-        .execute(pool)
+        .execute(conn)
         .await
         .map_err(|e| format!("Failed to create instrument: {}", e))?;
 
@@ -454,14 +1275,18 @@ impl ImportService {
 
     /// Create the trade record in the database
     async fn create_trade_record(
-        pool: &SqlitePool,
+        conn: &mut SqliteConnection,
         user_id: &str,
         account_id: &str,
         instrument_id: &str,
+        batch_id: &str,
         trade: &AggregatedTrade,
     ) -> Result<String, String> {
         let trade_id = uuid::Uuid::new_v4().to_string();
         let now = Utc::now();
+        let ref_code = TradeRepository::next_ref_code(&mut *conn, user_id, trade.trade_date)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
 
         // Get the entry and exit times from the first/last executions
         let entry_time = trade.entries.first().and_then(|e| e.execution_time.clone());
@@ -473,16 +1298,17 @@ impl ImportService {
         sqlx::query(
             r#"
             INSERT INTO trades (
-                id, user_id, account_id, instrument_id,
+                id, user_id, account_id, instrument_id, ref_code,
                 trade_date, direction, quantity, entry_price, exit_price,
-                entry_time, exit_time, fees, status, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                entry_time, exit_time, fees, fx_rate, strategy, status, import_batch_id, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&trade_id)
         .bind(user_id)
         .bind(account_id)
         .bind(instrument_id)
+        .bind(&ref_code)
         .bind(trade.trade_date)
         .bind(&trade.direction)
         .bind(trade.total_quantity)
@@ -491,19 +1317,22 @@ impl ImportService {
         .bind(&entry_time)
         .bind(&exit_time)
         .bind(trade.total_fees)
+        .bind(trade.fx_rate)
+        .bind(&trade.suggested_strategy)
         .bind(status)
+        .bind(batch_id)
         .bind(now)
         .bind(now)
-        .execute(pool)
+        .execute(conn)
         .await
         .map_err(|e| format!("Failed to create trade: {}", e))?;
 
         Ok(trade_id)
     }
 
-    /// Insert an execution record
+    /// Insert an execution record, stamped with the import batch of its trade
     async fn insert_execution(
-        pool: &SqlitePool,
+        conn: &mut SqliteConnection,
         trade_id: &str,
         execution: &Execution,
     ) -> Result<(), String> {
@@ -514,8 +1343,9 @@ impl ImportService {
             r#"
             INSERT INTO trade_executions (
                 id, trade_id, execution_type, execution_date, execution_time,
-                quantity, price, fees, exchange, broker_execution_id, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                quantity, price, fees, exchange, broker_execution_id,
+                intended_price, order_type, fees_estimated, import_batch_id, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, (SELECT import_batch_id FROM trades WHERE id = ?), ?)
             "#,
         )
         .bind(&id)
@@ -528,8 +1358,12 @@ impl ImportService {
         .bind(execution.fees)
         .bind(&execution.exchange)
         .bind(&execution.broker_execution_id)
+        .bind(execution.intended_price)
+        .bind(&execution.order_type)
+        .bind(execution.fees_estimated)
+        .bind(trade_id)
         .bind(now)
-        .execute(pool)
+        .execute(conn)
         .await
         .map_err(|e| format!("Failed to insert execution: {}", e))?;
 
@@ -556,6 +1390,7 @@ impl ImportService {
         Ok(rows
             .iter()
             .map(|row| Execution {
+                id: row.get("id"),
                 execution_type: row.get("execution_type"),
                 execution_date: row.get("execution_date"),
                 execution_time: row.get("execution_time"),
@@ -563,7 +1398,12 @@ impl ImportService {
                 price: row.get("price"),
                 fees: row.get("fees"),
                 exchange: row.get("exchange"),
-                broker_execution_id: row.get("broker_execution_id"),
+                broker_execution_id: row
+                    .get::<Option<String>, _>("broker_execution_id")
+                    .unwrap_or_default(),
+                intended_price: row.get("intended_price"),
+                order_type: row.get("order_type"),
+                fees_estimated: row.get("fees_estimated"),
             })
             .collect())
     }
@@ -688,6 +1528,64 @@ OPT_TRD|1002|AAPL  250905C00240000|AAPL 05SEP25 240 C|MEMX|SELLTOCLOSE|C|2025090
         assert!((trade.net_pnl.unwrap() - 242.0).abs() < 0.01);
     }
 
+    #[tokio::test]
+    async fn test_import_links_option_roll_chain() {
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // 240 call rolled up to the 250 call two minutes later; the put is unrelated
+        let content = r#"
+OPTION_TRANSACTIONS
+OPT_TRD|1001|AAPL  250905C00240000|AAPL 05SEP25 240 C|MEMX|BUYTOOPEN|O|20250904|09:30:00|USD|5.00|100.00|1.50|750.00|-4.00|0.85
+OPT_TRD|1002|AAPL  250905C00240000|AAPL 05SEP25 240 C|MEMX|SELLTOCLOSE|C|20250904|10:00:00|USD|-5.00|100.00|2.00|-1000.00|-4.00|0.85
+OPT_TRD|2001|AAPL  250905P00230000|AAPL 05SEP25 230 P|MEMX|BUYTOOPEN|O|20250904|10:01:00|USD|5.00|100.00|1.00|500.00|-4.00|0.85
+OPT_TRD|2002|AAPL  250905P00230000|AAPL 05SEP25 230 P|MEMX|SELLTOCLOSE|C|20250904|10:30:00|USD|-5.00|100.00|0.80|-400.00|-4.00|0.85
+OPT_TRD|3001|AAPL  250905C00250000|AAPL 05SEP25 250 C|MEMX|BUYTOOPEN|O|20250904|10:02:00|USD|5.00|100.00|1.00|500.00|-4.00|0.85
+OPT_TRD|3002|AAPL  250905C00250000|AAPL 05SEP25 250 C|MEMX|SELLTOCLOSE|C|20250904|11:00:00|USD|-5.00|100.00|1.20|-600.00|-4.00|0.85
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+
+        assert_eq!(preview.roll_chains.len(), 1);
+        let chain = &preview.roll_chains[0];
+        assert_eq!(chain.option_type, Some("call".to_string()));
+        let strikes: Vec<f64> = chain
+            .keys
+            .iter()
+            .map(|key| {
+                let leg = preview.trades_to_import.iter().find(|t| &t.key == key).unwrap();
+                leg.strike_price.unwrap()
+            })
+            .collect();
+        assert_eq!(strikes, vec![240.0, 250.0]);
+        // 242 on the 240 call plus (1.20 - 1.00) * 500 - 8 = 92 on the 250 call
+        assert!((chain.combined_net_pnl - 334.0).abs() < 0.01);
+
+        let roll_chains = preview.roll_chains.clone();
+        let result = ImportService::execute_import(&pool, &user_id, &account_id, preview.trades_to_import, true, &roll_chains, &[], None)
+            .await
+            .unwrap();
+        assert_eq!(result.imported_count, 3);
+        assert_eq!(result.roll_links, 1);
+
+        let trade_id_for = |execution_id: &'static str| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_scalar::<_, String>("SELECT trade_id FROM trade_executions WHERE broker_execution_id = ?")
+                    .bind(execution_id)
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap()
+            }
+        };
+        let first = TradeRepository::get_by_id(&pool, &trade_id_for("1001").await).await.unwrap().unwrap();
+        let put = TradeRepository::get_by_id(&pool, &trade_id_for("2001").await).await.unwrap().unwrap();
+        assert_eq!(first.roll_type, Some(RollType::Rolled));
+        assert_eq!(first.continuation_trade_id, Some(trade_id_for("3001").await));
+        assert_eq!(put.continuation_trade_id, None);
+    }
+
     #[test]
     fn test_parse_and_aggregate_multiple_symbols() {
         let content = r#"
@@ -711,6 +1609,411 @@ STK_TRD|1004|MSFT|MICROSOFT|DARK|SELLTOCLOSE|C|20260127|10:00:00|USD|-50.00|1.00
         assert_eq!(msft.total_quantity, 50.0);
     }
 
+    #[tokio::test]
+    async fn test_preview_tos_import() {
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, _) = setup_test_user_and_account(&pool).await;
+
+        let content = r#"Account Statement for 123456789SCHW since 1/1/24 through 1/31/24
+
+Account Trade History
+,Exec Time,Spread,Side,Qty,Pos Effect,Symbol,Exp,Strike,Type,Price,Net Price,Order Type
+,1/15/24 09:31:02,STOCK,BUY,+100,TO OPEN,AAPL,,,STOCK,185.50,185.50,LMT
+,1/15/24 10:02:11,SINGLE,BUY,+2,TO OPEN,SPY,19 JAN 24,475,CALL,3.20,3.20,LMT
+,1/16/24 14:45:00,STOCK,SELL,-100,TO CLOSE,AAPL,,,STOCK,190.25,190.25,MKT
+,1/16/24 15:00:00,SINGLE,SELL,-2,TO CLOSE,.SPY240119C475,,,,4.00,4.00,LMT
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+
+        assert!(preview.parse_errors.is_empty());
+        assert!(preview.open_positions.is_empty());
+        assert_eq!(preview.trades_to_import.len(), 2);
+
+        let aapl = preview.trades_to_import.iter().find(|t| t.symbol == "AAPL").unwrap();
+        assert!((aapl.net_pnl.unwrap() - 475.0).abs() < 0.01);
+
+        // Both the column form and the dot symbol decode to the same contract
+        let spy = preview.trades_to_import.iter().find(|t| t.underlying_symbol == "SPY").unwrap();
+        assert_eq!(spy.asset_class, "option");
+        assert_eq!(spy.option_type, Some("call".to_string()));
+        assert!((spy.net_pnl.unwrap() - 160.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_preview_binance_import_closes_fractional_position() {
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, _) = setup_test_user_and_account(&pool).await;
+
+        // 0.1 + 0.2 sums to a hair over 0.3, which the sell still closes
+        let content = r#"Date(UTC),Pair,Side,Price,Executed,Amount,Fee
+2024-01-16 14:00:05,BTCUSDT,SELL,43000,0.3BTC,12900USDT,12.9USDT
+2024-01-15 10:02:11,BTCUSDT,BUY,42000,0.2BTC,8400USDT,8.4USDT
+2024-01-15 09:31:02,BTCUSDT,BUY,42000,0.1BTC,4200USDT,4.2USDT
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+
+        assert!(preview.parse_errors.is_empty());
+        assert!(preview.open_positions.is_empty());
+        assert_eq!(preview.trades_to_import.len(), 1);
+
+        let trade = &preview.trades_to_import[0];
+        assert_eq!(trade.symbol, "BTC/USDT");
+        assert_eq!(trade.asset_class, "crypto");
+        assert_eq!(trade.status, "closed");
+        // 1000 × 0.3 less 25.5 in fees
+        assert!((trade.net_pnl.unwrap() - 274.5).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_crypto_import_converts_quote_currency_pnl() {
+        use crate::models::{FxRate, FxRateSource};
+        use crate::services::TradeService;
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // 0.1 BTC bought and sold 1,000 EUR higher, in a USD account, and an ETH/BTC round trip
+        let content = r#"Date(UTC),Pair,Side,Price,Executed,Amount,Fee
+2024-01-16 14:00:05,BTCEUR,SELL,40000,0.1BTC,4000EUR,4EUR
+2024-01-15 09:31:02,BTCEUR,BUY,39000,0.1BTC,3900EUR,3.9EUR
+2024-01-16 16:20:00,ETHBTC,SELL,0.056,1ETH,0.056BTC,0.00005BTC
+2024-01-15 16:20:00,ETHBTC,BUY,0.055,1ETH,0.055BTC,0.00005BTC
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+        assert_eq!(preview.trades_to_import.len(), 2);
+        let btc = preview.trades_to_import.iter().find(|t| t.symbol == "BTC/EUR").unwrap();
+        assert_eq!(btc.currency.as_deref(), Some("EUR"));
+
+        FxService::save_rates(
+            &pool,
+            vec![FxRate {
+                base_currency: "EUR".to_string(),
+                quote_currency: "USD".to_string(),
+                date: NaiveDate::from_ymd_opt(2024, 1, 16).unwrap(),
+                rate: 1.1,
+                source: FxRateSource::Manual,
+            }],
+        )
+        .await
+        .unwrap();
+        let result = ImportService::execute_import(&pool, &user_id, &account_id, preview.trades_to_import, true, &[], &[], None)
+            .await
+            .unwrap();
+
+        // No BTC rate is stored, so the pair quoted in bitcoin is reported rather than imported in BTC
+        assert_eq!(result.imported_count, 1);
+        assert_eq!(result.errors, vec!["Failed to import ETH/BTC: No FX rate from BTC to USD; add one under FX rates"]);
+
+        // (100 EUR less 7.9 EUR in fees) at 1.1
+        let trades = TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(trades[0].trade.fx_rate, Some(1.1));
+        assert!((trades[0].trade.fees - 8.69).abs() < 0.01);
+        assert!((trades[0].net_pnl.unwrap() - 101.31).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_preview_webull_import_aggregates_partial_fills() {
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, _) = setup_test_user_and_account(&pool).await;
+
+        let content = r#"Name,Symbol,Side,Status,Filled,Total Qty,Price,Avg Price,Time-in-Force,Placed Time,Filled Time
+Apple Inc,AAPL,Sell,Filled,100,100,MKT,190.00,DAY,01/16/2024 14:00:00 EST,01/16/2024 14:00:01 EST
+Apple Inc,AAPL,Buy,Partially Filled,60,100,@185.00,185.00,DAY,01/15/2024 09:45:00 EST,01/15/2024 09:45:10 EST
+Apple Inc,AAPL,Buy,Filled,40,40,@180.00,180.00,DAY,01/15/2024 09:31:00 EST,01/15/2024 09:31:02 EST
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+
+        assert!(preview.parse_errors.is_empty());
+        assert!(preview.open_positions.is_empty());
+        assert_eq!(preview.trades_to_import.len(), 1);
+
+        let trade = &preview.trades_to_import[0];
+        assert_eq!(trade.entries.len(), 2);
+        assert_eq!(trade.exits.len(), 1);
+        assert_eq!(trade.total_quantity, 100.0);
+        // (190 - 183) * 100 on an average entry of 183
+        assert!((trade.net_pnl.unwrap() - 700.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_preview_robinhood_import_closes_expired_options() {
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, _) = setup_test_user_and_account(&pool).await;
+
+        let content = r#""Activity Date","Process Date","Settle Date","Instrument","Description","Trans Code","Quantity","Price","Amount"
+"1/19/2024","1/19/2024","1/19/2024","SPY","Option Expiration for SPY 1/19/2024 Put $470.00","OEXP","2S","",""
+"1/15/2024","1/15/2024","1/16/2024","SPY","SPY 1/19/2024 Put $470.00","STO","2","$1.50","$299.92"
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+
+        assert!(preview.parse_errors.is_empty());
+        assert!(preview.open_positions.is_empty());
+        assert_eq!(preview.trades_to_import.len(), 1);
+
+        let trade = &preview.trades_to_import[0];
+        assert_eq!(trade.direction, "short");
+        assert_eq!(trade.option_type, Some("put".to_string()));
+        assert_eq!(trade.entries[0].execution_time, None);
+        // Premium kept: 1.50 * 2 * 100 - 0.08 fees
+        assert!((trade.net_pnl.unwrap() - 299.92).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_tradovate_import_uses_contract_multiplier() {
+        use crate::services::TradeService;
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let content = r#"_id,Account,Contract,B/S,Quantity,Price,Timestamp,Date,Product,Product Description,commission
+1002,DEMO123,ESH4,Sell,2,4805.00,01/15/2024 10:15:00,1/15/24,ES,E-Mini S&P 500,2.58
+1001,DEMO123,ESH4,Buy,2,4800.00,01/15/2024 09:31:02,1/15/24,ES,E-Mini S&P 500,2.58
+"#;
+        let preview = ImportService::preview_broker_import(&pool, &user_id, "tradovate", content, &[]).await.unwrap();
+
+        assert!(preview.parse_errors.is_empty());
+        assert_eq!(preview.trades_to_import.len(), 1);
+        let trade = &preview.trades_to_import[0];
+        assert_eq!(trade.asset_class, "future");
+        assert_eq!(trade.multiplier, Some(50.0));
+        // 5 points × 2 contracts × 50 − 5.16 commissions
+        assert!((trade.net_pnl.unwrap() - 494.84).abs() < 0.01);
+
+        ImportService::execute_import(&pool, &user_id, &account_id, preview.trades_to_import, true, &[], &[], None)
+            .await
+            .unwrap();
+        let trades = TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(trades[0].trade.asset_class, AssetClass::Future);
+        assert_eq!(trades[0].trade.contract_multiplier, Some(50.0));
+        assert!((trades[0].net_pnl.unwrap() - 494.84).abs() < 0.01);
+
+        let unknown = ImportService::preview_broker_import(&pool, &user_id, "tradestation", content, &[]).await;
+        assert_eq!(unknown.unwrap_err(), "Unknown broker: tradestation");
+        let unrecognized = ImportService::preview_import(&pool, &user_id, "Date,Description,Amount\n", &[]).await;
+        assert!(unrecognized.unwrap_err().starts_with("Unrecognized file format"));
+
+        // A TLG export of a day without trades previews nothing to import
+        let no_trades = "ACCOUNT_INFORMATION\nACT_INF|U6498184|Test User|Individual|Address\n\nSTOCK_TRANSACTIONS\n";
+        let preview = ImportService::preview_import(&pool, &user_id, no_trades, &[]).await.unwrap();
+        assert!(preview.trades_to_import.is_empty());
+        assert!(preview.parse_errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tlg_futures_import_uses_contract_multiplier() {
+        use crate::services::TradeService;
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let content = r#"FUTURE_TRANSACTIONS
+FUT_TRD|3001|ESH6|ES 20MAR26|CME|SELLTOOPEN|O|20260127|09:30:00|USD|-2.00|50.00|6000.00|-600000.00|-2.25|1.0
+FUT_TRD|3002|ESH6|ES 20MAR26|CME|BUYTOCLOSE|C|20260127|10:15:00|USD|2.00|50.00|5990.00|599000.00|-2.25|1.0
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+
+        assert!(preview.parse_errors.is_empty());
+        let trade = &preview.trades_to_import[0];
+        assert_eq!((trade.asset_class.as_str(), trade.direction.as_str()), ("future", "short"));
+        assert_eq!(trade.underlying_symbol, "ES");
+        assert_eq!(trade.expiration_date, NaiveDate::from_ymd_opt(2026, 3, 20));
+        // 10 points × 2 contracts × 50 − 4.50 commissions
+        assert!((trade.net_pnl.unwrap() - 995.5).abs() < 0.01);
+
+        ImportService::execute_import(&pool, &user_id, &account_id, preview.trades_to_import, true, &[], &[], None)
+            .await
+            .unwrap();
+        let trades = TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(trades[0].trade.contract_multiplier, Some(50.0));
+        assert!((trades[0].net_pnl.unwrap() - 995.5).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_tlg_forex_import_converts_pnl_to_base_currency() {
+        use crate::services::TradeService;
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // Long 10,000 USD against JPY, closed 0.80 yen higher at 0.00641 USD per yen
+        let content = r#"CASH_TRD|7001|USD.JPY|USD.JPY|IDEALFX|BUYTOOPEN|O|20260127|09:30:00|JPY|10000.00|1|155.20|-1552000.00|-2.00|0.00644
+CASH_TRD|7002|USD.JPY|USD.JPY|IDEALFX|SELLTOCLOSE|C|20260128|11:00:00|JPY|-10000.00|1|156.00|1560000.00|-2.00|0.00641
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+
+        assert!(preview.parse_errors.is_empty());
+        let trade = &preview.trades_to_import[0];
+        assert_eq!((trade.symbol.as_str(), trade.asset_class.as_str()), ("USD.JPY", "forex"));
+        assert_eq!(trade.fx_rate, Some(0.00641));
+        // 8,000 yen at 0.00641 less 4.00 commissions
+        assert!((trade.net_pnl.unwrap() - 47.28).abs() < 0.01);
+
+        ImportService::execute_import(&pool, &user_id, &account_id, preview.trades_to_import, true, &[], &[], None)
+            .await
+            .unwrap();
+        let trades = TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(trades[0].trade.asset_class, AssetClass::Forex);
+        assert_eq!(trades[0].trade.entry_price, 155.2);
+        assert_eq!((trades[0].trade.contract_multiplier, trades[0].trade.fx_rate), (Some(1.0), Some(0.00641)));
+        assert!((trades[0].net_pnl.unwrap() - 47.28).abs() < 0.01);
+
+        // Trade stats compute PnL in SQL at the trade's fx rate too
+        let stats = TradeService::get_trade_stats(&pool, &user_id, &crate::models::TradeFilter::default()).await.unwrap();
+        assert!((stats.total_pnl - 47.28).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_tastytrade_preview_aggregates_spread_legs() {
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, _account_id) = setup_test_user_and_account(&pool).await;
+
+        let content = r#"Date,Type,Sub Type,Action,Symbol,Instrument Type,Description,Value,Quantity,Average Price,Commissions,Fees,Multiplier,Root Symbol,Underlying Symbol,Expiration Date,Strike Price,Call or Put,Order #,Currency
+2024-01-19T16:00:00-0500,Receive Deliver,Expiration,SELL_TO_CLOSE,SPY   240119P00460000,Equity Option,Removal of 1.0 SPY 01/19/24 Put 460.00 due to expiration.,0.00,1,0.00,--,0.00,100,SPY,SPY,1/19/24,460,PUT,,USD
+2024-01-17T11:00:00-0500,Trade,Buy to Close,BUY_TO_CLOSE,SPY   240119P00470000,Equity Option,Bought 1 SPY 01/19/24 Put 470.00 @ 0.40,-40.00,1,-40.00,0.00,-0.13,100,SPY,SPY,1/19/24,470,PUT,1002,USD
+2024-01-15T10:31:02-0500,Trade,Sell to Open,SELL_TO_OPEN,SPY   240119P00470000,Equity Option,Sold 1 SPY 01/19/24 Put 470.00 @ 1.50,150.00,1,150.00,-1.00,-0.14,100,SPY,SPY,1/19/24,470,PUT,1001,USD
+2024-01-15T10:31:02-0500,Trade,Buy to Open,BUY_TO_OPEN,SPY   240119P00460000,Equity Option,Bought 1 SPY 01/19/24 Put 460.00 @ 0.55,-55.00,1,-55.00,-1.00,-0.13,100,SPY,SPY,1/19/24,460,PUT,1001,USD
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+        assert!(preview.parse_errors.is_empty());
+        assert_eq!(preview.trades_to_import.len(), 2);
+
+        let short_put = preview.trades_to_import.iter().find(|t| t.direction == "short").unwrap();
+        assert_eq!(short_put.asset_class, "option");
+        assert_eq!(short_put.strike_price, Some(470.0));
+        // (1.50 − 0.40) × 100 − 1.27 fees
+        assert!((short_put.net_pnl.unwrap() - 108.73).abs() < 0.01);
+
+        let expired = preview.trades_to_import.iter().find(|t| t.direction == "long").unwrap();
+        assert_eq!(expired.strike_price, Some(460.0));
+        assert!((expired.net_pnl.unwrap() + 56.13).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_import_ninjatrader_round_trips() {
+        use crate::services::TradeService;
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let content = r#"Trade number,Instrument,Account,Strategy,Market pos.,Qty,Entry price,Exit price,Entry time,Exit time,Entry name,Exit name,Profit,Cum. net profit,Commission,MAE,MFE,ETD,Bars
+1,ES 03-24,Sim101,,Short,2,4805.00,4800.00,1/15/2024 9:31:02 AM,1/15/2024 10:15:00 AM,Entry,Exit,$500.00,$494.84,$5.16,$50.00,$600.00,$100.00,9
+2,ES 03-24,Sim101,,Long,1,4790.00,4800.00,1/15/2024 11:00:00 AM,1/16/2024 9:45:00 AM,Entry,Exit,$500.00,$992.26,$2.58,$50.00,$600.00,$100.00,40
+3,AAPL,Sim101,,Flat,100,185.50,190.25,1/15/2024 9:31:02 AM,1/15/2024 2:45:00 PM,Buy,Sell,$475.00,$1467.26,$0.00,$20.00,$500.00,$25.00,12
+"#;
+        let result = ImportService::import_ninjatrader_trades(&pool, &user_id, &account_id, content, true, Some("Trades.csv"))
+            .await
+            .unwrap();
+        assert_eq!(result.imported_count, 2);
+        assert_eq!(result.errors, vec!["Line 4: Unknown market position: Flat".to_string()]);
+
+        let trades = TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(trades.len(), 2);
+        let short = trades.iter().find(|t| t.trade.direction == Direction::Short).unwrap();
+        assert_eq!(short.trade.contract_multiplier, Some(50.0));
+        assert_eq!(short.trade.entry_time.as_deref(), Some("09:31:02"));
+        // 5 points × 2 contracts × 50 − 5.16 commission
+        assert!((short.net_pnl.unwrap() - 494.84).abs() < 0.01);
+        let overnight = trades.iter().find(|t| t.trade.direction == Direction::Long).unwrap();
+        assert_eq!(overnight.trade.exit_date, NaiveDate::from_ymd_opt(2024, 1, 16));
+
+        // Re-importing the same report skips every trade
+        let again = ImportService::import_ninjatrader_trades(&pool, &user_id, &account_id, content, true, None)
+            .await
+            .unwrap();
+        assert_eq!(again.imported_count, 0);
+        assert_eq!(again.skipped_duplicates, 2);
+
+        // Both imports are in the history, the one that loaded nothing included
+        let history = ImportService::get_import_history(&pool, &user_id).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!((history[0].trade_count, history[0].skipped_duplicates), (0, 2));
+        assert_eq!(history[1].file_name.as_deref(), Some("Trades.csv"));
+        assert_eq!((history[1].trade_count, history[1].execution_count), (2, 4));
+        assert_eq!(history[1].error_count, 1);
+        assert_eq!(history[1].errors, result.errors);
+    }
+
+    #[tokio::test]
+    async fn test_import_metatrader_statement() {
+        use crate::services::TradeService;
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let content = r#"Time,Position,Symbol,Type,Volume,Price,S / L,T / P,Time,Price,Commission,Swap,Profit
+2024.01.15 09:31:02,7001,EURUSD,buy,0.5,1.09500,,,2024.01.17 15:02:00,1.09620,-3.50,-1.25,60.00
+2024.01.18 10:00:00,7002,EURGBP,sell,1,0.86000,,,2024.01.18 12:00:00,0.85900,-7.00,0.00,127.00
+"#;
+        let result = ImportService::import_metatrader_statement(&pool, &user_id, &account_id, content, true, None)
+            .await
+            .unwrap();
+        assert_eq!(result.imported_count, 2);
+
+        let trades = TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap();
+        let eurusd = trades.iter().find(|t| t.trade.symbol == "EURUSD").unwrap();
+        assert_eq!(eurusd.trade.asset_class, AssetClass::Forex);
+        assert_eq!(eurusd.trade.quantity, Some(50000.0));
+        // 12 pips on 50,000 units less commission and swap
+        assert!((eurusd.net_pnl.unwrap() - 55.25).abs() < 0.01);
+
+        // 100 GBP of price move is the 127.00 USD of profit on the statement, less commission
+        let eurgbp = trades.iter().find(|t| t.trade.symbol == "EURGBP").unwrap();
+        assert!((eurgbp.trade.fx_rate.unwrap() - 1.27).abs() < 1e-9);
+        assert!((eurgbp.net_pnl.unwrap() - 120.0).abs() < 0.01);
+
+        let again = ImportService::import_metatrader_statement(&pool, &user_id, &account_id, content, true, None)
+            .await
+            .unwrap();
+        assert_eq!(again.skipped_duplicates, 2);
+    }
+
+    #[tokio::test]
+    async fn test_import_tradingview_trades_into_paper_account() {
+        use crate::repository::AccountRepository;
+        use crate::services::TradeService;
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let content = r#"Trade #,Type,Signal,Date/Time,Price USD,Contracts,Profit USD,Profit %,Cum. Profit USD,Cum. Profit %,Run-up USD,Run-up %,Drawdown USD,Drawdown %
+1,Exit Long,Close,2024-01-15 15:00,190.25,100,474.00,2.56,474.00,0.05,500.00,2.7,-20.00,-0.11
+1,Entry Long,Long,2024-01-15 09:30,185.50,100,474.00,2.56,474.00,0.05,500.00,2.7,-20.00,-0.11
+"#;
+        // Simulated trades stay out of live accounts
+        let err = ImportService::import_tradingview_trades(&pool, &user_id, &account_id, content, "NASDAQ:AAPL", true, None)
+            .await
+            .unwrap_err();
+        assert!(err.ends_with("is not a paper account"));
+
+        AccountRepository::set_paper(&pool, &user_id, &account_id, true).await.unwrap();
+        let result = ImportService::import_tradingview_trades(&pool, &user_id, &account_id, content, "NASDAQ:AAPL", true, None)
+            .await
+            .unwrap();
+        assert_eq!(result.imported_count, 1);
+
+        let trades = TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(trades[0].trade.symbol, "AAPL");
+        // The strategy's 1.00 commission comes back out of the net profit
+        assert!((trades[0].net_pnl.unwrap() - 474.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_aggregated_trade_key_uniqueness() {
         let content = r#"
@@ -725,4 +2028,304 @@ STK_TRD|1002|AAPL|APPLE INC|DARK|SELLTOCLOSE|C|20260127|10:00:00|USD|-100.00|1.0
         assert!(trade.key.starts_with("AAPL_"));
         assert!(trade.key.contains("2026-01-27"));
     }
+
+    #[tokio::test]
+    async fn test_reimport_preview_detects_changed_fees_and_updates() {
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let original = r#"
+STK_TRD|1001|AAPL|APPLE INC|DARK|BUYTOOPEN|O|20260127|09:30:00|USD|100.00|1.00|150.00|15000.00|-1.00|0.85
+STK_TRD|1002|AAPL|APPLE INC|DARK|SELLTOCLOSE|C|20260127|10:00:00|USD|-100.00|1.00|155.00|-15500.00|-1.00|0.85
+STK_TRD|2001|MSFT|MICROSOFT|DARK|BUYTOOPEN|O|20260128|09:30:00|USD|10.00|1.00|400.00|4000.00|-1.00|0.85
+STK_TRD|2002|MSFT|MICROSOFT|DARK|SELLTOCLOSE|C|20260128|10:00:00|USD|-10.00|1.00|410.00|-4100.00|-1.00|0.85
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, original, &[]).await.unwrap();
+        ImportService::execute_import(&pool, &user_id, &account_id, preview.trades_to_import, true, &[], &[], None)
+            .await
+            .unwrap();
+
+        // Broker corrected the AAPL exit fee and a new TSLA trade appeared
+        let corrected = r#"
+STK_TRD|1001|AAPL|APPLE INC|DARK|BUYTOOPEN|O|20260127|09:30:00|USD|100.00|1.00|150.00|15000.00|-1.00|0.85
+STK_TRD|1002|AAPL|APPLE INC|DARK|SELLTOCLOSE|C|20260127|10:00:00|USD|-100.00|1.00|155.00|-15500.00|-2.50|0.85
+STK_TRD|2001|MSFT|MICROSOFT|DARK|BUYTOOPEN|O|20260128|09:30:00|USD|10.00|1.00|400.00|4000.00|-1.00|0.85
+STK_TRD|2002|MSFT|MICROSOFT|DARK|SELLTOCLOSE|C|20260128|10:00:00|USD|-10.00|1.00|410.00|-4100.00|-1.00|0.85
+STK_TRD|3001|TSLA|TESLA INC|DARK|BUYTOOPEN|O|20260129|09:30:00|USD|5.00|1.00|200.00|1000.00|-1.00|0.85
+STK_TRD|3002|TSLA|TESLA INC|DARK|SELLTOCLOSE|C|20260129|10:00:00|USD|-5.00|1.00|210.00|-1050.00|-1.00|0.85
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, corrected, &[]).await.unwrap();
+
+        assert_eq!(preview.duplicate_count, 1); // MSFT unchanged
+        assert_eq!(preview.trades_to_import.len(), 1);
+        assert_eq!(preview.trades_to_import[0].symbol, "TSLA");
+        assert_eq!(preview.changed_trades.len(), 1);
+        assert_eq!(preview.changed_trades[0].changes, vec!["Fill 1002: fees 1.00 -> 2.50"]);
+
+        // A failure while replacing the fills leaves the trade and its fills as they were
+        let aapl_id = preview.changed_trades[0].existing_trade_id.clone();
+        let fees_before = TradeRepository::get_by_id(&pool, &aapl_id).await.unwrap().unwrap().fees;
+        sqlx::query(
+            "CREATE TRIGGER fail_fill BEFORE INSERT ON trade_executions \
+             WHEN NEW.broker_execution_id = '1002' BEGIN SELECT RAISE(ABORT, 'disk full'); END",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let result = ImportService::update_changed_trades(&pool, &user_id, preview.changed_trades.clone())
+            .await
+            .unwrap();
+        assert_eq!(result.updated_count, 0);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(TradeRepository::get_by_id(&pool, &aapl_id).await.unwrap().unwrap().fees, fees_before);
+        assert_eq!(ImportService::get_trade_executions(&pool, &aapl_id).await.unwrap().len(), 2);
+        sqlx::query("DROP TRIGGER fail_fill").execute(&pool).await.unwrap();
+
+        let result = ImportService::update_changed_trades(&pool, &user_id, preview.changed_trades)
+            .await
+            .unwrap();
+        assert_eq!(result.updated_count, 1);
+        assert!(result.errors.is_empty());
+
+        // Preview again: nothing changed anymore
+        let preview = ImportService::preview_import(&pool, &user_id, corrected, &[]).await.unwrap();
+        assert_eq!(preview.duplicate_count, 2);
+        assert!(preview.changed_trades.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_locked_period_blocks_importing_and_updating_trades() {
+        use crate::services::AccountService;
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        AccountService::lock_period(&pool, &user_id, &account_id, NaiveDate::from_ymd_opt(2026, 1, 27).unwrap())
+            .await
+            .unwrap();
+
+        let content = r#"
+STK_TRD|1001|AAPL|APPLE INC|DARK|BUYTOOPEN|O|20260127|09:30:00|USD|100.00|1.00|150.00|15000.00|-1.00|0.85
+STK_TRD|1002|AAPL|APPLE INC|DARK|SELLTOCLOSE|C|20260127|10:00:00|USD|-100.00|1.00|155.00|-15500.00|-1.00|0.85
+STK_TRD|2001|MSFT|MICROSOFT|DARK|BUYTOOPEN|O|20260128|09:30:00|USD|10.00|1.00|400.00|4000.00|-1.00|0.85
+STK_TRD|2002|MSFT|MICROSOFT|DARK|SELLTOCLOSE|C|20260128|10:00:00|USD|-10.00|1.00|410.00|-4100.00|-1.00|0.85
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+        let result = ImportService::execute_import(&pool, &user_id, &account_id, preview.trades_to_import, true, &[], &[], None)
+            .await
+            .unwrap();
+        assert_eq!(result.imported_count, 1);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].starts_with("Failed to import AAPL"));
+        assert!(result.errors[0].contains("locked period"));
+
+        // Nor can a broker correction change the trade once its period is locked
+        AccountService::lock_period(&pool, &user_id, &account_id, NaiveDate::from_ymd_opt(2026, 1, 28).unwrap())
+            .await
+            .unwrap();
+        let corrected = content.replace("-4100.00|-1.00", "-4100.00|-2.50");
+        let preview = ImportService::preview_import(&pool, &user_id, &corrected, &[]).await.unwrap();
+        assert_eq!(preview.changed_trades.len(), 1);
+        let result = ImportService::update_changed_trades(&pool, &user_id, preview.changed_trades).await.unwrap();
+        assert_eq!(result.updated_count, 0);
+        assert!(result.errors[0].contains("locked period"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_import_records_parse_errors() {
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let content = r#"
+STK_TRD|1001|AAPL|APPLE INC|DARK|BUYTOOPEN|O|20260127|09:30:00|USD|100.00|1.00|150.00|15000.00|-1.00|0.85
+STK_TRD|1002|AAPL|APPLE INC|DARK|SELLTOCLOSE|C|20260127|10:00:00|USD|abc|1.00|155.00|-15500.00|-1.00|0.85
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+        assert_eq!(preview.parse_errors.len(), 1);
+        let result = ImportService::execute_import(
+            &pool,
+            &user_id,
+            &account_id,
+            preview.trades_to_import,
+            true,
+            &[],
+            &preview.parse_errors,
+            Some("trades.tlg"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.errors, vec!["Line 3: Invalid quantity: abc".to_string()]);
+
+        let history = ImportService::get_import_history(&pool, &user_id).await.unwrap();
+        assert_eq!(history[0].error_count, 1);
+        assert_eq!(history[0].errors, result.errors);
+    }
+
+    #[tokio::test]
+    async fn test_undo_import_deletes_batch_trades() {
+        use crate::services::{AccountService, TradeService};
+        use crate::test_utils::{create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let manual = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "NVDA")).await.unwrap();
+
+        let content = r#"
+STK_TRD|1001|AAPL|APPLE INC|DARK|BUYTOOPEN|O|20260127|09:30:00|USD|100.00|1.00|150.00|15000.00|-1.00|0.85
+STK_TRD|1002|AAPL|APPLE INC|DARK|SELLTOCLOSE|C|20260127|10:00:00|USD|-100.00|1.00|155.00|-15500.00|-1.00|0.85
+STK_TRD|2001|MSFT|MICROSOFT|DARK|BUYTOOPEN|O|20260128|09:30:00|USD|10.00|1.00|400.00|4000.00|-1.00|0.85
+STK_TRD|2002|MSFT|MICROSOFT|DARK|SELLTOCLOSE|C|20260128|10:00:00|USD|-10.00|1.00|410.00|-4100.00|-1.00|0.85
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+        let result = ImportService::execute_import(&pool, &user_id, &account_id, preview.trades_to_import, true, &[], &[], None)
+            .await
+            .unwrap();
+        let batch_id = result.batch_id.unwrap();
+
+        let batches = ImportService::get_import_history(&pool, &user_id).await.unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!((batches[0].trade_count, batches[0].execution_count), (2, 4));
+
+        // Nothing is deleted while a trade is in a locked period, whether by its entry or an exit
+        let through = NaiveDate::from_ymd_opt(2026, 1, 26).unwrap();
+        AccountService::lock_period(&pool, &user_id, &account_id, through).await.unwrap();
+        sqlx::query(
+            "UPDATE trade_executions SET execution_date = '2026-01-20' \
+             WHERE import_batch_id = ? AND execution_type = 'exit' AND price = 410.0",
+        )
+        .bind(&batch_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let err = ImportService::undo_import(&pool, &user_id, &batch_id).await.unwrap_err();
+        assert!(err.contains("Trade date 2026-01-20 is in a locked period"));
+        AccountService::unlock_period(&pool, &user_id, &account_id).await.unwrap();
+
+        let through = NaiveDate::from_ymd_opt(2026, 1, 27).unwrap();
+        AccountService::lock_period(&pool, &user_id, &account_id, through).await.unwrap();
+        let err = ImportService::undo_import(&pool, &user_id, &batch_id).await.unwrap_err();
+        assert!(err.contains("locked period"));
+        AccountService::unlock_period(&pool, &user_id, &account_id).await.unwrap();
+
+        assert_eq!(ImportService::undo_import(&pool, &user_id, &batch_id).await.unwrap(), 2);
+        let executions: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM trade_executions WHERE import_batch_id = ?")
+            .bind(&batch_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(executions, 0);
+        assert!(TradeRepository::get_by_id(&pool, &manual.trade.id).await.unwrap().is_some());
+
+        let batches = ImportService::get_import_history(&pool, &user_id).await.unwrap();
+        assert!(batches[0].undone_at.is_some());
+        assert!(ImportService::undo_import(&pool, &user_id, &batch_id).await.is_err());
+
+        // The file imports again as new trades
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+        assert_eq!(preview.duplicate_count, 0);
+        assert_eq!(preview.trades_to_import.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_import_estimates_missing_fees_from_commission_schedule() {
+        use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        AccountRepository::save_commission_schedule(
+            &pool,
+            &CommissionSchedule {
+                account_id: account_id.clone(),
+                per_order: 0.0,
+                per_share: 0.005,
+                per_contract: 0.65,
+                minimum_per_order: 1.0,
+            },
+        )
+        .await
+        .unwrap();
+
+        // Entry fee reported by the broker, exit fee missing
+        let content = r#"
+STK_TRD|1001|AAPL|APPLE INC|DARK|BUYTOOPEN|O|20260127|09:30:00|USD|400.00|1.00|150.00|60000.00|-1.50|0.85
+STK_TRD|1002|AAPL|APPLE INC|DARK|SELLTOCLOSE|C|20260127|10:00:00|USD|-400.00|1.00|155.00|-62000.00|0.00|0.85
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+        let result = ImportService::execute_import(&pool, &user_id, &account_id, preview.trades_to_import, true, &[], &[], None)
+            .await
+            .unwrap();
+        assert_eq!(result.imported_count, 1);
+
+        let (closed, _, _) = ImportService::parse_and_aggregate(content);
+        let trade_id = ImportService::find_imported_trade_id(&pool, &closed[0])
+            .await
+            .unwrap()
+            .unwrap();
+        let executions = ImportService::get_trade_executions(&pool, &trade_id).await.unwrap();
+        let entry = executions.iter().find(|e| e.broker_execution_id == "1001").unwrap();
+        let exit = executions.iter().find(|e| e.broker_execution_id == "1002").unwrap();
+        assert!(!entry.fees_estimated);
+        assert!((entry.fees - 1.5).abs() < 0.001);
+        assert!(exit.fees_estimated);
+        assert!((exit.fees - 2.0).abs() < 0.001); // 400 shares * 0.005
+
+        let trade = TradeRepository::get_by_id(&pool, &trade_id).await.unwrap().unwrap();
+        assert!((trade.fees - 3.5).abs() < 0.001);
+
+        // Re-importing the same data doesn't flag the estimate as a broker change
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+        assert_eq!(preview.duplicate_count, 1);
+        assert!(preview.changed_trades.is_empty());
+    }
+
+    #[test]
+    fn test_commission_schedule_applies_minimum() {
+        let schedule = CommissionSchedule {
+            per_share: 0.005,
+            minimum_per_order: 1.0,
+            per_contract: 0.65,
+            ..Default::default()
+        };
+        assert!((schedule.estimate_fees(100.0, false) - 1.0).abs() < 0.001);
+        assert!((schedule.estimate_fees(3.0, true) - 1.95).abs() < 0.001);
+        assert!(CommissionSchedule::default().is_empty());
+    }
+
+    #[test]
+    fn test_parse_and_aggregate_matches_closing_execution_against_seed() {
+        let seed = SeedPosition {
+            id: "s1".to_string(),
+            user_id: "u1".to_string(),
+            account_id: "a1".to_string(),
+            symbol: "AAPL".to_string(),
+            direction: Direction::Long,
+            quantity: 100.0,
+            avg_cost: 140.0,
+            open_date: NaiveDate::from_ymd_opt(2025, 12, 15).unwrap(),
+            as_of_date: NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+            created_at: Utc::now(),
+        };
+        let content = r#"
+STK_TRD|999|AAPL|APPLE INC|DARK|BUYTOOPEN|O|20251220|09:30:00|USD|100.00|1.00|140.00|14000.00|-1.00|0.85
+STK_TRD|1002|AAPL|APPLE INC|DARK|SELLTOCLOSE|C|20260127|10:00:00|USD|-100.00|1.00|155.00|-15500.00|-1.00|0.85
+"#;
+
+        let (closed, open, errors) = ImportService::parse_and_aggregate_with_seeds(content, &[seed]);
+
+        assert!(errors.is_empty());
+        assert!(open.is_empty());
+        assert_eq!(closed.len(), 1);
+
+        let trade = &closed[0];
+        assert_eq!(trade.direction, "long");
+        assert_eq!(trade.trade_date, NaiveDate::from_ymd_opt(2025, 12, 15).unwrap());
+        assert_eq!(trade.entries.len(), 1); // The pre-seed buy is already part of the seed
+        assert_eq!(trade.entries[0].broker_execution_id, "seed-s1");
+        assert!((trade.avg_entry_price - 140.0).abs() < 0.001);
+        assert!((trade.net_pnl.unwrap() - 1499.0).abs() < 0.001);
+    }
 }
@@ -0,0 +1,212 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use sqlx::sqlite::SqlitePool;
+use crate::calculations::draft_day_journal;
+use crate::models::{EndOfDaySummary, JournalEntry, Status, TradeFilter};
+use crate::repository::JournalEntryRepository;
+use crate::services::settings_service::SettingsService;
+use crate::services::{MetricsService, TradeService};
+
+pub struct JournalService;
+
+impl JournalService {
+    /// Summarize a day and store a draft journal entry for it, keeping any entry the day already has
+    /// Returns None when no trade was entered that day.
+    pub async fn summarize_day(
+        pool: &SqlitePool,
+        user_id: &str,
+        date: NaiveDate,
+    ) -> Result<Option<EndOfDaySummary>, String> {
+        let day_trades = TradeService::get_all_trades(pool, user_id, None, Some(date), Some(date)).await?;
+        let day_trades = MetricsService::exclude_paper_trades(pool, user_id, None, day_trades).await?;
+        if day_trades.is_empty() {
+            return Ok(None);
+        }
+
+        let performance = MetricsService::get_daily_performance(pool, user_id, None, date, date, false)
+            .await?
+            .into_iter()
+            .next();
+
+        let filter = TradeFilter {
+            end_date: Some(date),
+            status: Some(Status::Open),
+            ..Default::default()
+        };
+        let open_trades = TradeService::find_trades(pool, user_id, &filter).await?;
+        let open_trades = MetricsService::exclude_paper_trades(pool, user_id, None, open_trades).await?;
+
+        let mut symbols: Vec<String> = day_trades.iter().map(|t| t.trade.symbol.clone()).collect();
+        symbols.sort();
+        symbols.dedup();
+
+        let trades_opened = day_trades.len() as i32;
+        let open_positions = open_trades.len() as i32;
+        let content = draft_day_journal(performance.as_ref(), trades_opened, open_positions, &symbols);
+        JournalEntryRepository::insert_draft(pool, user_id, date, &content)
+            .await
+            .map_err(|e| format!("Failed to save journal entry: {}", e))?;
+        let journal_entry = JournalEntryRepository::get_by_date(pool, user_id, date)
+            .await
+            .map_err(|e| format!("Failed to get journal entry: {}", e))?
+            .ok_or_else(|| "Journal entry not found".to_string())?;
+
+        Ok(Some(EndOfDaySummary {
+            date,
+            performance,
+            trades_opened,
+            open_positions,
+            symbols,
+            journal_entry,
+        }))
+    }
+
+    /// Summaries of every day whose summary time has passed since the last run, oldest first
+    /// Checked on app start as well, so days missed while the app was closed are caught up on the
+    /// next launch. The first run only summarizes the latest due day. Days without trades are
+    /// marked done without a summary.
+    pub async fn take_due_summaries(
+        pool: &SqlitePool,
+        user_id: &str,
+        now: NaiveDateTime,
+    ) -> Result<Vec<EndOfDaySummary>, String> {
+        let time = SettingsService::get_end_of_day_summary_time(pool).await?;
+        let time = NaiveTime::parse_from_str(&time, "%H:%M")
+            .map_err(|e| format!("Invalid summary time: {}", e))?;
+        let Some(last_due) = (if now.time() >= time { Some(now.date()) } else { now.date().pred_opt() }) else {
+            return Ok(Vec::new());
+        };
+        let first_due = match SettingsService::get_end_of_day_summary_last_date(pool).await? {
+            Some(last_run) => match last_run.succ_opt() {
+                Some(next) => next,
+                None => return Ok(Vec::new()),
+            },
+            None => last_due,
+        };
+
+        let mut summaries = Vec::new();
+        for date in first_due.iter_days().take_while(|d| *d <= last_due) {
+            if let Some(summary) = Self::summarize_day(pool, user_id, date).await? {
+                summaries.push(summary);
+            }
+            SettingsService::save_end_of_day_summary_last_date(pool, date).await?;
+        }
+        Ok(summaries)
+    }
+
+    pub async fn get_entries(
+        pool: &SqlitePool,
+        user_id: &str,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<JournalEntry>, String> {
+        JournalEntryRepository::get_entries(pool, user_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get journal entries: {}", e))
+    }
+
+    /// Save the reviewed text of a day's entry, creating the entry if the day has none
+    pub async fn save_entry(
+        pool: &SqlitePool,
+        user_id: &str,
+        date: NaiveDate,
+        content: &str,
+    ) -> Result<JournalEntry, String> {
+        JournalEntryRepository::insert_draft(pool, user_id, date, content)
+            .await
+            .map_err(|e| format!("Failed to save journal entry: {}", e))?;
+        JournalEntryRepository::update_content(pool, user_id, date, content)
+            .await
+            .map_err(|e| format!("Failed to save journal entry: {}", e))?;
+
+        JournalEntryRepository::get_by_date(pool, user_id, date)
+            .await
+            .map_err(|e| format!("Failed to get journal entry: {}", e))?
+            .ok_or_else(|| "Journal entry not found".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::JournalEntryStatus;
+    use crate::test_utils::{create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    #[tokio::test]
+    async fn test_end_of_day_summary_drafts_journal_once() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        SettingsService::save_end_of_day_summary_time(&pool, "16:30").await.unwrap();
+
+        // Monday Jan 15 2024: a closed winner and a position left open
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+        let mut open = create_test_trade_input(&account_id, "MSFT");
+        open.exit_price = None;
+        open.exit_time = None;
+        open.status = Some(Status::Open);
+        TradeService::create_trade(&pool, &user_id, open).await.unwrap();
+
+        let day = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let before = JournalService::take_due_summaries(&pool, &user_id, day.and_hms_opt(16, 0, 0).unwrap())
+            .await
+            .unwrap();
+        assert!(before.is_empty());
+
+        let mut summaries = JournalService::take_due_summaries(&pool, &user_id, day.and_hms_opt(18, 0, 0).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(summaries.len(), 1);
+        let summary = summaries.remove(0);
+        assert_eq!(summary.trades_opened, 2);
+        assert_eq!(summary.open_positions, 1);
+        assert_eq!(summary.symbols, vec!["AAPL".to_string(), "MSFT".to_string()]);
+        assert_eq!(summary.performance.as_ref().unwrap().trade_count, 1);
+        assert_eq!(summary.journal_entry.status, JournalEntryStatus::Draft);
+        assert!(summary.journal_entry.content.starts_with("Net PnL 490.00 over 1 closed trade (1 won"));
+
+        // Already summarized today
+        let again = JournalService::take_due_summaries(&pool, &user_id, day.and_hms_opt(19, 0, 0).unwrap())
+            .await
+            .unwrap();
+        assert!(again.is_empty());
+
+        // A reviewed entry is not replaced by a later summary of the same day
+        JournalService::save_entry(&pool, &user_id, day, "Followed the plan").await.unwrap();
+        let summary = JournalService::summarize_day(&pool, &user_id, day).await.unwrap().unwrap();
+        assert_eq!(summary.journal_entry.content, "Followed the plan");
+        assert_eq!(summary.journal_entry.status, JournalEntryStatus::Reviewed);
+
+        // Days without trades get no summary
+        let quiet = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+        assert!(JournalService::summarize_day(&pool, &user_id, quiet).await.unwrap().is_none());
+        assert_eq!(JournalService::get_entries(&pool, &user_id, None, None).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_end_of_day_summaries_catch_up_missed_days() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        SettingsService::save_end_of_day_summary_time(&pool, "16:30").await.unwrap();
+        let date = |day| NaiveDate::from_ymd_opt(2024, 1, day).unwrap();
+
+        for (symbol, day) in [("AAPL", 15), ("MSFT", 16), ("NVDA", 18)] {
+            let mut input = create_test_trade_input(&account_id, symbol);
+            input.trade_date = date(day);
+            TradeService::create_trade(&pool, &user_id, input).await.unwrap();
+        }
+
+        // The first run only summarizes the latest due day
+        let first = JournalService::take_due_summaries(&pool, &user_id, date(15).and_hms_opt(17, 0, 0).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(first.iter().map(|s| s.date).collect::<Vec<_>>(), vec![date(15)]);
+
+        // The app was closed until the morning of the 19th, before that day's summary time
+        let missed = JournalService::take_due_summaries(&pool, &user_id, date(19).and_hms_opt(9, 0, 0).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(missed.iter().map(|s| s.date).collect::<Vec<_>>(), vec![date(16), date(18)]);
+        assert_eq!(SettingsService::get_end_of_day_summary_last_date(&pool).await.unwrap(), Some(date(18)));
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use crate::calculations::classify_result;
+use crate::models::{CalculationDiscrepancy, CalculationReport, MaintenanceReport, TradeFilter, TradeResult};
+use crate::repository::archive_repo::ARCHIVE_SCHEMA;
+use crate::repository::{AccountRepository, SqlDerivedFields, TradeRepository};
+use crate::services::settings_service::SettingsService;
+use crate::services::TradeService;
+
+pub struct MaintenanceService;
+
+impl MaintenanceService {
+    /// Check integrity, checkpoint the WAL, then VACUUM and ANALYZE the main and archive databases
+    pub async fn run_maintenance(pool: &SqlitePool) -> Result<MaintenanceReport, String> {
+        let bytes_before = SettingsService::get_storage_usage(pool).await?.database_bytes;
+
+        let integrity_issues: Vec<String> = sqlx::query_scalar("PRAGMA integrity_check")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| format!("Failed to check database integrity: {}", e))?
+            .into_iter()
+            .filter(|line: &String| line != "ok")
+            .collect();
+
+        // Columns are (busy, log frames, checkpointed frames); log is -1 outside WAL mode
+        let checkpoint = sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .fetch_one(pool)
+            .await
+            .map_err(|e| format!("Failed to checkpoint database: {}", e))?;
+        let log_frames: i64 = checkpoint.get(1);
+        let wal_frames_checkpointed = (log_frames >= 0).then(|| checkpoint.get::<i64, _>(2));
+
+        // A corrupt database is reported as is rather than rewritten
+        if integrity_issues.is_empty() {
+            for statement in ["VACUUM main".to_string(), format!("VACUUM {}", ARCHIVE_SCHEMA), "ANALYZE".to_string()] {
+                sqlx::query(&statement)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| format!("Failed to run {}: {}", statement, e))?;
+            }
+        }
+
+        let bytes_after = SettingsService::get_storage_usage(pool).await?.database_bytes;
+        let report = MaintenanceReport {
+            ran_at: Utc::now(),
+            bytes_before,
+            bytes_after,
+            bytes_reclaimed: (bytes_before - bytes_after).max(0),
+            integrity_issues,
+            wal_frames_checkpointed,
+        };
+        SettingsService::save_last_maintenance_report(pool, &report).await?;
+        Ok(report)
+    }
+
+    /// Run maintenance when a schedule is set and its interval has passed since the last run
+    pub async fn run_if_due(pool: &SqlitePool, now: DateTime<Utc>) -> Result<Option<MaintenanceReport>, String> {
+        let Some(interval) = SettingsService::get_maintenance_interval_days(pool).await? else {
+            return Ok(None);
+        };
+        let last = SettingsService::get_last_maintenance_report(pool).await?;
+        if last.is_some_and(|r| now - r.ran_at < Duration::days(interval as i64)) {
+            return Ok(None);
+        }
+
+        Self::run_maintenance(pool).await.map(Some)
+    }
+
+    /// Recompute a user's derived metrics per trade and in SQL, and report where they disagree
+    /// The SQL that filters and counts trades mirrors calculate_derived_fields, so a change to one
+    /// and not the other shows up here. Each trade is compared field by field, and the journal and
+    /// each account by the totals of their trade stats.
+    pub async fn verify_calculations(pool: &SqlitePool, user_id: &str) -> Result<CalculationReport, String> {
+        let all = TradeFilter::default();
+        let trades = TradeService::find_trades(pool, user_id, &all).await?;
+        let sql_fields = TradeRepository::get_sql_derived_fields(pool, user_id, &all)
+            .await
+            .map_err(|e| format!("Failed to get derived trade fields: {}", e))?;
+        let sql_by_id: HashMap<&str, &SqlDerivedFields> = sql_fields.iter().map(|f| (f.trade_id.as_str(), f)).collect();
+
+        let mut discrepancies = Vec::new();
+        for trade in &trades {
+            let scope = trade.trade.ref_code.clone().unwrap_or_else(|| trade.trade.id.clone());
+            let Some(sql) = sql_by_id.get(trade.trade.id.as_str()) else {
+                compare(&mut discrepancies, &scope, "trade", Some("present"), Some("missing"));
+                continue;
+            };
+
+            compare_amount(&mut discrepancies, &scope, "gross_pnl", trade.gross_pnl, sql.gross_pnl);
+            compare_amount(&mut discrepancies, &scope, "net_pnl", trade.net_pnl, sql.net_pnl);
+            compare_amount(&mut discrepancies, &scope, "r_multiple", trade.r_multiple, sql.r_multiple);
+            let sql_result = sql.result_pnl.map(classify_result);
+            compare(&mut discrepancies, &scope, "result", trade.result.map(|r| r.as_str()), sql_result.map(|r| r.as_str()));
+            compare(
+                &mut discrepancies,
+                &scope,
+                "trade_type",
+                trade.trade_type.map(|t| t.as_str()),
+                sql.trade_type.as_deref(),
+            );
+        }
+
+        let accounts = AccountRepository::get_accounts(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get accounts: {}", e))?;
+        let scopes = std::iter::once(("Journal".to_string(), None)).chain(accounts.into_iter().map(|a| (a.name, Some(a.id))));
+        for (scope, account_id) in scopes {
+            let filter = TradeFilter {
+                account_id: account_id.clone(),
+                ..Default::default()
+            };
+            let stats = TradeRepository::get_trade_stats(pool, user_id, &filter)
+                .await
+                .map_err(|e| format!("Failed to get trade stats: {}", e))?;
+
+            let scoped: Vec<_> = trades
+                .iter()
+                .filter(|t| account_id.is_none() || account_id.as_deref() == Some(t.trade.account_id.as_str()))
+                .collect();
+            let count = |result: TradeResult| scoped.iter().filter(|t| t.result == Some(result)).count() as i64;
+            let closed_count = scoped.iter().filter(|t| t.net_pnl.is_some()).count() as i64;
+            let total_pnl: f64 = scoped.iter().filter_map(|t| t.net_pnl).sum();
+
+            compare(&mut discrepancies, &scope, "trade_count", Some(scoped.len() as i64), Some(stats.trade_count));
+            compare(&mut discrepancies, &scope, "closed_count", Some(closed_count), Some(stats.closed_count));
+            compare(&mut discrepancies, &scope, "win_count", Some(count(TradeResult::Win)), Some(stats.win_count));
+            compare(&mut discrepancies, &scope, "loss_count", Some(count(TradeResult::Loss)), Some(stats.loss_count));
+            compare_amount(&mut discrepancies, &scope, "total_pnl", Some(total_pnl), Some(stats.total_pnl));
+        }
+
+        Ok(CalculationReport {
+            checked_at: Utc::now(),
+            trade_count: trades.len() as i64,
+            discrepancies,
+        })
+    }
+}
+
+/// Record a discrepancy when the two computations of a metric differ
+fn compare<T: PartialEq + Display>(
+    discrepancies: &mut Vec<CalculationDiscrepancy>,
+    scope: &str,
+    metric: &str,
+    per_trade: Option<T>,
+    sql: Option<T>,
+) {
+    if per_trade != sql {
+        discrepancies.push(CalculationDiscrepancy {
+            scope: scope.to_string(),
+            metric: metric.to_string(),
+            per_trade: per_trade.map_or("none".to_string(), |v| v.to_string()),
+            sql: sql.map_or("none".to_string(), |v| v.to_string()),
+        });
+    }
+}
+
+/// Record a discrepancy when two computations of an amount differ by more than rounding,
+/// which grows with the amount since sums are added up in a different order
+fn compare_amount(
+    discrepancies: &mut Vec<CalculationDiscrepancy>,
+    scope: &str,
+    metric: &str,
+    per_trade: Option<f64>,
+    sql: Option<f64>,
+) {
+    let agree = match (per_trade, sql) {
+        (Some(a), Some(b)) => (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.0),
+        (a, b) => a.is_none() && b.is_none(),
+    };
+    if !agree {
+        compare(discrepancies, scope, metric, per_trade, sql);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crate::models::{Direction, TradeResult};
+    use crate::test_utils::{create_open_trade, create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    #[tokio::test]
+    async fn test_maintenance_reclaims_space_and_follows_schedule() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let mut ids = Vec::new();
+        for _ in 0..50 {
+            let mut input = create_test_trade_input(&account_id, "AAPL");
+            input.notes = Some("x".repeat(2000));
+            ids.push(TradeService::create_trade(&pool, &user_id, input).await.unwrap().trade.id);
+        }
+        for id in &ids {
+            TradeService::delete_trade(&pool, &user_id, id).await.unwrap();
+        }
+
+        let report = MaintenanceService::run_maintenance(&pool).await.unwrap();
+        assert!(report.integrity_issues.is_empty());
+        assert!(report.bytes_reclaimed > 0);
+        assert_eq!(report.bytes_after, report.bytes_before - report.bytes_reclaimed);
+        assert!(report.wal_frames_checkpointed.is_none()); // In-memory databases do not use WAL
+
+        // Without a schedule nothing runs; with one it waits for the interval
+        assert!(MaintenanceService::run_if_due(&pool, Utc::now()).await.unwrap().is_none());
+        SettingsService::save_maintenance_interval_days(&pool, Some(7)).await.unwrap();
+        assert!(MaintenanceService::run_if_due(&pool, Utc::now()).await.unwrap().is_none());
+        let later = Utc::now() + Duration::days(8);
+        assert!(MaintenanceService::run_if_due(&pool, later).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_verify_calculations() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL")).await.unwrap();
+        let mut short = create_test_trade_input(&account_id, "TSLA");
+        short.direction = Direction::Short;
+        short.stop_loss_price = Some(160.0);
+        TradeService::create_trade(&pool, &user_id, short).await.unwrap();
+        let open = create_open_trade(&account_id, "MSFT", NaiveDate::from_ymd_opt(2024, 1, 16).unwrap(), 400.0, 10.0);
+        TradeService::create_trade(&pool, &user_id, open).await.unwrap();
+        // A 5 point gain that fees turn into a loss
+        let mut scratch = create_test_trade_input(&account_id, "NVDA");
+        scratch.exit_price = Some(150.05);
+        TradeService::create_trade(&pool, &user_id, scratch).await.unwrap();
+
+        let report = MaintenanceService::verify_calculations(&pool, &user_id).await.unwrap();
+        assert_eq!(report.trade_count, 4);
+        assert!(report.discrepancies.is_empty(), "{:?}", report.discrepancies);
+
+        // Setting values are read case-insensitively by both, so the two agree on the gross result
+        sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES ('result_basis', 'GROSS', CURRENT_TIMESTAMP)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let report = MaintenanceService::verify_calculations(&pool, &user_id).await.unwrap();
+        assert!(report.discrepancies.is_empty(), "{:?}", report.discrepancies);
+        let trades = TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap();
+        let nvda = trades.iter().find(|t| t.trade.symbol == "NVDA").unwrap();
+        assert_eq!(nvda.result, Some(TradeResult::Win));
+    }
+}
@@ -6,9 +6,14 @@ use serde::{Deserialize, Serialize};
 use sqlx::sqlite::SqlitePool;
 use sqlx::Row;
 
+use crate::calculations::{calculate_atr, calculate_excursion_prices};
+use crate::config;
+use crate::repository::{DailyCloseRepository, TradeRepository};
+
 const ALPACA_DATA_BASE_URL: &str = "https://data.alpaca.markets";
 const ALPACA_FETCH_LIMIT: i64 = 10_000;
 const MAX_CHART_1M_BARS: i64 = 4_000;
+const ENTRY_ATR_PERIOD: usize = 14;
 
 const KEY_ALPACA_API_KEY_ID: &str = "alpaca_api_key_id";
 const KEY_ALPACA_API_SECRET_KEY: &str = "alpaca_api_secret_key";
@@ -23,6 +28,21 @@ pub struct Candle {
     pub volume: Option<f64>,
 }
 
+/// Outcome of backfilling MAE/MFE prices from cached candles
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExcursionBackfillResult {
+    pub updated_count: i32,
+    pub skipped_no_times: i32,   // Entry or exit time unknown
+    pub skipped_no_candles: i32, // No cached candles in the trade's window
+}
+
+/// Outcome of backfilling ATR at entry from stored daily bars
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntryAtrBackfillResult {
+    pub updated_count: i32,
+    pub skipped_no_bars: i32, // Fewer than ENTRY_ATR_PERIOD + 1 daily bars before the trade date
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketTapeQuote {
     pub symbol: String,
@@ -60,6 +80,7 @@ struct TradeMarketContext {
     start_ts: i64,
     end_ts: i64,
     estimated_1m_bars: i64,
+    execution_window: Option<(i64, i64)>, // First and last fill; None unless both have a time
 }
 
 #[derive(Debug, Deserialize)]
@@ -193,6 +214,100 @@ impl MarketDataService {
         Ok(aggregate_candles(&one_minute, bucket_minutes))
     }
 
+    /// Fill in missing MAE/MFE prices of closed trades from cached 1-minute candles
+    /// Uses the bars from the minute of the first fill through the last fill; nothing is fetched,
+    /// so trades whose window isn't cached (e.g. never charted) are skipped.
+    pub async fn backfill_excursions(
+        pool: &SqlitePool,
+        user_id: &str,
+    ) -> Result<ExcursionBackfillResult, String> {
+        let trade_ids = TradeRepository::get_ids_missing_excursions(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get trades: {}", e))?;
+
+        let mut result = ExcursionBackfillResult::default();
+        for trade_id in trade_ids {
+            let Some(trade) = TradeRepository::get_by_id(pool, &trade_id)
+                .await
+                .map_err(|e| format!("Failed to get trade: {}", e))?
+            else {
+                continue;
+            };
+
+            let context = get_trade_market_context(pool, &trade_id, CandleKind::Primary).await?;
+            let Some((entry_ts, exit_ts)) = context.execution_window else {
+                result.skipped_no_times += 1;
+                continue;
+            };
+
+            let bars: Vec<(f64, f64)> = get_cached_candles(
+                pool,
+                &context.symbol,
+                "1m",
+                entry_ts - entry_ts.rem_euclid(60),
+                exit_ts,
+            )
+            .await?
+            .iter()
+            .map(|c| (c.high, c.low))
+            .collect();
+
+            let Some((favorable, adverse)) = calculate_excursion_prices(trade.direction, trade.entry_price, &bars) else {
+                result.skipped_no_candles += 1;
+                continue;
+            };
+
+            TradeRepository::fill_excursions(pool, &trade_id, favorable, adverse)
+                .await
+                .map_err(|e| format!("Failed to save excursions: {}", e))?;
+            result.updated_count += 1;
+        }
+
+        Ok(result)
+    }
+
+    /// Fill in missing ATR at entry from the daily bars stored before each trade's date
+    /// Bars are keyed by the traded symbol like daily closes; nothing is fetched.
+    pub async fn backfill_entry_atr(
+        pool: &SqlitePool,
+        user_id: &str,
+    ) -> Result<EntryAtrBackfillResult, String> {
+        let trade_ids = TradeRepository::get_ids_missing_entry_atr(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get trades: {}", e))?;
+
+        let mut result = EntryAtrBackfillResult::default();
+        for trade_id in trade_ids {
+            let Some(trade) = TradeRepository::get_by_id(pool, &trade_id)
+                .await
+                .map_err(|e| format!("Failed to get trade: {}", e))?
+            else {
+                continue;
+            };
+
+            let bars = DailyCloseRepository::get_bars_before(
+                pool,
+                &trade.symbol,
+                trade.trade_date,
+                ENTRY_ATR_PERIOD as i64 + 1,
+            )
+            .await
+            .map_err(|e| format!("Failed to get daily bars: {}", e))?;
+
+            let Some(atr) = calculate_atr(&bars, ENTRY_ATR_PERIOD).filter(|atr| *atr > 0.0) else {
+                result.skipped_no_bars += 1;
+                continue;
+            };
+
+            TradeRepository::fill_entry_atr(pool, &trade_id, atr)
+                .await
+                .map_err(|e| format!("Failed to save entry ATR: {}", e))?;
+            result.updated_count += 1;
+        }
+
+        Ok(result)
+    }
+
     pub async fn get_market_tape(
         pool: &SqlitePool,
         symbols: Option<&[String]>,
@@ -285,7 +400,7 @@ async fn get_trade_market_context(
 
     let execution_rows = sqlx::query(
         r#"
-        SELECT execution_date, execution_time
+        SELECT execution_type, execution_date, execution_time
         FROM trade_executions
         WHERE trade_id = ?
         ORDER BY execution_date ASC, execution_time ASC
@@ -317,6 +432,33 @@ async fn get_trade_market_context(
         }
     }
 
+    // Window the trade was actually open: first entry fill to last exit fill, falling back to
+    // the trade's own times for manual trades recorded without fills
+    let trade_date: NaiveDate = trade_row.get("trade_date");
+    let fills_of = |execution_type: &'static str| {
+        execution_rows
+            .iter()
+            .filter(move |row| row.get::<&str, _>("execution_type") == execution_type)
+            .map(|row| (row.get::<NaiveDate, _>("execution_date"), row.get::<Option<String>, _>("execution_time")))
+    };
+    let first_entry = fills_of("entry")
+        .next()
+        .unwrap_or((trade_date, trade_row.get("entry_time")));
+    let last_exit = fills_of("exit")
+        .next_back()
+        .unwrap_or((trade_date, trade_row.get("exit_time")));
+    let execution_window = match (&first_entry, &last_exit) {
+        ((entry_date, Some(entry_time)), (exit_date, Some(exit_time)))
+            if parse_trade_time(entry_time).is_some() && parse_trade_time(exit_time).is_some() =>
+        {
+            Some((
+                parse_date_time(*entry_date, Some(entry_time))?,
+                parse_date_time(*exit_date, Some(exit_time))?,
+            ))
+        }
+        _ => None,
+    };
+
     let trade_date: NaiveDate = trade_row.get("trade_date");
     let fallback_ts = parse_date_time(trade_date, Some("09:30:00"))?;
     let start_anchor = execution_times.first().copied().unwrap_or(fallback_ts);
@@ -339,6 +481,7 @@ async fn get_trade_market_context(
         start_ts,
         end_ts,
         estimated_1m_bars,
+        execution_window,
     })
 }
 
@@ -563,6 +706,11 @@ fn convert_ibkr_like_to_occ(symbol: &str) -> Option<String> {
 }
 
 async fn get_alpaca_keys(pool: &SqlitePool) -> Result<(String, String), String> {
+    // Keys from config.toml or the environment take precedence over those saved in Settings
+    if let Some(keys) = config::current().and_then(|c| c.alpaca_keys()) {
+        return Ok(keys);
+    }
+
     let key_id = sqlx::query("SELECT value FROM settings WHERE key = ?")
         .bind(KEY_ALPACA_API_KEY_ID)
         .fetch_optional(pool)
@@ -970,3 +1118,102 @@ async fn cache_candles(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DailyClose;
+    use crate::services::{MetricsService, TradeService};
+    use crate::test_utils::{create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    fn candle(time: i64, high: f64, low: f64) -> Candle {
+        Candle { time, open: low, high, low, close: high, volume: None }
+    }
+
+    #[tokio::test]
+    async fn test_backfill_excursions_from_cached_candles() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let traded = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap()
+            .trade;
+        let mut untimed = create_test_trade_input(&account_id, "AAPL");
+        untimed.entry_time = None;
+        TradeService::create_trade(&pool, &user_id, untimed).await.unwrap();
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "MSFT"))
+            .await
+            .unwrap();
+
+        // Stored times are UTC; bars outside the entry minute..exit are ignored
+        let entry_ts = parse_date_time(traded.trade_date, traded.entry_time.as_deref()).unwrap();
+        let exit_ts = parse_date_time(traded.trade_date, traded.exit_time.as_deref()).unwrap();
+        let candles = vec![
+            candle(entry_ts - 60, 200.0, 100.0),
+            candle(entry_ts, 151.0, 149.0),
+            candle(entry_ts + 1800, 157.0, 148.5),
+            candle(exit_ts, 155.5, 154.0),
+            candle(exit_ts + 60, 300.0, 50.0),
+        ];
+        cache_candles(&pool, "AAPL", "1m", "alpaca", &candles, 0).await.unwrap();
+
+        let result = MarketDataService::backfill_excursions(&pool, &user_id).await.unwrap();
+        assert_eq!(result.updated_count, 1);
+        assert_eq!(result.skipped_no_times, 1);
+        assert_eq!(result.skipped_no_candles, 1);
+
+        let trade = TradeRepository::get_by_id(&pool, &traded.id).await.unwrap().unwrap();
+        assert_eq!(trade.max_favorable_price, Some(157.0));
+        assert_eq!(trade.max_adverse_price, Some(148.5));
+
+        let again = MarketDataService::backfill_excursions(&pool, &user_id).await.unwrap();
+        assert_eq!(again.updated_count, 0);
+    }
+    #[tokio::test]
+    async fn test_backfill_entry_atr_from_daily_bars() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // Entry 150, exit 155, stop 145 on 2024-01-15
+        let traded = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap()
+            .trade;
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "MSFT"))
+            .await
+            .unwrap();
+
+        // Twenty flat days with a range of 2 before the trade date; the trade day itself is ignored
+        let mut bars: Vec<DailyClose> = (0..20)
+            .map(|i| DailyClose {
+                symbol: "AAPL".to_string(),
+                date: traded.trade_date - chrono::Duration::days(20 - i),
+                close: 150.0,
+                high: Some(151.0),
+                low: Some(149.0),
+            })
+            .collect();
+        bars.push(DailyClose {
+            symbol: "AAPL".to_string(),
+            date: traded.trade_date,
+            close: 150.0,
+            high: Some(170.0),
+            low: Some(130.0),
+        });
+        MetricsService::save_daily_closes(&pool, bars).await.unwrap();
+
+        let result = MarketDataService::backfill_entry_atr(&pool, &user_id).await.unwrap();
+        assert_eq!(result.updated_count, 1);
+        assert_eq!(result.skipped_no_bars, 1);
+
+        let trade = TradeService::get_trade(&pool, &user_id, &traded.id).await.unwrap().unwrap();
+        assert_eq!(trade.trade.entry_atr, Some(2.0));
+        assert_eq!(trade.atr_multiple, Some(2.5));
+        assert_eq!(trade.r_multiple, Some(1.0));
+
+        let report = MetricsService::get_r_expectancy(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(report.atr_trade_count, 1);
+        assert_eq!(report.expectancy_atr, Some(2.5));
+    }
+}
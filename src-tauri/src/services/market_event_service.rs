@@ -0,0 +1,273 @@
+use chrono::NaiveDate;
+use sqlx::sqlite::SqlitePool;
+use crate::calculations::{calculate_earnings_window_report, calculate_event_day_report, events_for_trade};
+use crate::models::{
+    CreateMarketEventInput, EarningsWindowReport, EventDayReport, MarketEvent, MarketEventImportResult, MarketEventType,
+};
+use crate::repository::{MarketEventRepository, TradeRepository};
+use crate::services::{MetricsService, TradeService};
+
+/// Days before earnings in which a closed trade counts as closed before earnings
+pub const DEFAULT_EARNINGS_WINDOW_DAYS: i64 = 5;
+
+pub struct MarketEventService;
+
+impl MarketEventService {
+    /// Record a market event such as FOMC, CPI or earnings for a symbol
+    pub async fn add_event(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: CreateMarketEventInput,
+    ) -> Result<MarketEvent, String> {
+        let input = Self::normalize(input)?;
+
+        MarketEventRepository::insert(pool, user_id, &input)
+            .await
+            .map_err(|e| format!("Failed to add market event: {}", e))?
+            .ok_or_else(|| "Market event already exists".to_string())
+    }
+
+    /// Get a user's market events
+    pub async fn get_events(
+        pool: &SqlitePool,
+        user_id: &str,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<MarketEvent>, String> {
+        MarketEventRepository::get_events(pool, user_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get market events: {}", e))
+    }
+
+    /// Delete a market event
+    pub async fn delete_event(pool: &SqlitePool, user_id: &str, id: &str) -> Result<(), String> {
+        let deleted = MarketEventRepository::delete(pool, user_id, id)
+            .await
+            .map_err(|e| format!("Failed to delete market event: {}", e))?;
+
+        if !deleted {
+            return Err("Market event not found".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Import market events from CSV lines: date,type,symbol,description
+    /// A header line is skipped; symbol and description may be empty. Duplicates are skipped.
+    pub async fn import_csv(
+        pool: &SqlitePool,
+        user_id: &str,
+        content: &str,
+    ) -> Result<MarketEventImportResult, String> {
+        let mut result = MarketEventImportResult::default();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (index == 0 && line.to_lowercase().starts_with("date")) {
+                continue;
+            }
+
+            let input = match Self::parse_csv_line(line).and_then(Self::normalize) {
+                Ok(input) => input,
+                Err(e) => {
+                    result.errors.push(format!("Line {}: {}", index + 1, e));
+                    continue;
+                }
+            };
+
+            let inserted = MarketEventRepository::insert(pool, user_id, &input)
+                .await
+                .map_err(|e| format!("Failed to import market events: {}", e))?;
+            if inserted.is_some() {
+                result.imported += 1;
+            } else {
+                result.duplicates += 1;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Get the market events on a trade's date
+    pub async fn get_trade_events(
+        pool: &SqlitePool,
+        user_id: &str,
+        trade_id: &str,
+    ) -> Result<Vec<MarketEvent>, String> {
+        let trade = TradeService::get_trade(pool, user_id, trade_id)
+            .await?
+            .ok_or_else(|| "Trade not found".to_string())?;
+        let events = Self::get_events(pool, user_id, Some(trade.trade.trade_date), Some(trade.trade.trade_date)).await?;
+
+        Ok(events_for_trade(&trade, &events).into_iter().cloned().collect())
+    }
+
+    /// Get performance on event days vs normal days
+    pub async fn get_event_day_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<EventDayReport, String> {
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = MetricsService::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let events = Self::get_events(pool, user_id, start_date, end_date).await?;
+
+        Ok(calculate_event_day_report(&trades, &events))
+    }
+
+    /// Get performance of trades held through earnings vs closed before
+    pub async fn get_earnings_window_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        window_days: Option<i64>,
+    ) -> Result<EarningsWindowReport, String> {
+        let window_days = window_days.unwrap_or(DEFAULT_EARNINGS_WINDOW_DAYS);
+        if window_days < 0 {
+            return Err("Window days cannot be negative".to_string());
+        }
+
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = MetricsService::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        // Earnings after the range can still fall within a trade opened in it
+        let events = Self::get_events(pool, user_id, start_date, None).await?;
+        let exit_dates = TradeRepository::get_last_exit_dates(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get exit dates: {}", e))?;
+
+        Ok(calculate_earnings_window_report(&trades, &events, &exit_dates, window_days))
+    }
+
+    fn parse_csv_line(line: &str) -> Result<CreateMarketEventInput, String> {
+        let fields: Vec<&str> = line.splitn(4, ',').map(str::trim).collect();
+        if fields.len() < 2 {
+            return Err("Expected date,type,symbol,description".to_string());
+        }
+
+        let event_date = NaiveDate::parse_from_str(fields[0], "%Y-%m-%d")
+            .map_err(|e| format!("Invalid date '{}': {}", fields[0], e))?;
+        let event_type = MarketEventType::from_str(fields[1])
+            .ok_or_else(|| format!("Invalid event type: {}", fields[1]))?;
+        let optional = |i: usize| fields.get(i).filter(|s| !s.is_empty()).map(|s| s.to_string());
+
+        Ok(CreateMarketEventInput {
+            event_date,
+            event_type,
+            symbol: optional(2),
+            description: optional(3),
+        })
+    }
+
+    /// Uppercase the symbol and require one for earnings
+    fn normalize(mut input: CreateMarketEventInput) -> Result<CreateMarketEventInput, String> {
+        input.symbol = input
+            .symbol
+            .map(|s| s.trim().to_uppercase())
+            .filter(|s| !s.is_empty());
+
+        if input.event_type == MarketEventType::Earnings && input.symbol.is_none() {
+            return Err("Earnings events require a symbol".to_string());
+        }
+
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ExitExecution;
+    use crate::test_utils::{create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    #[tokio::test]
+    async fn test_event_day_report_tags_trades_on_event_dates() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // Trades on 2024-01-15 (+50 each, see create_test_trade_input) and one on a normal day
+        let aapl = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "MSFT"))
+            .await
+            .unwrap();
+        let mut normal = create_test_trade_input(&account_id, "NVDA");
+        normal.trade_date = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+        TradeService::create_trade(&pool, &user_id, normal).await.unwrap();
+
+        let csv = "date,type,symbol,description\n\
+                   2024-01-15,cpi,,December CPI\n\
+                   2024-01-15,earnings,aapl,Q4 earnings\n\
+                   2024-01-15,cpi,,Duplicate\n\
+                   2024-01-17,earnings,,Missing symbol\n\
+                   not-a-date,fomc,,\n";
+        let result = MarketEventService::import_csv(&pool, &user_id, csv).await.unwrap();
+        assert_eq!(result.imported, 2);
+        assert_eq!(result.duplicates, 1);
+        assert_eq!(result.errors.len(), 2);
+
+        let events = MarketEventService::get_trade_events(&pool, &user_id, &aapl.trade.id).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events.iter().find(|e| e.event_type == MarketEventType::Earnings).unwrap().symbol.as_deref(), Some("AAPL"));
+
+        let report = MarketEventService::get_event_day_report(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(report.event_days.trade_count, 2);
+        assert_eq!(report.normal_days.trade_count, 1);
+        let by_type: Vec<(&str, i32)> = report.by_event_type.iter().map(|b| (b.key.as_str(), b.trade_count)).collect();
+        assert_eq!(by_type, vec![("cpi", 2), ("earnings", 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_earnings_window_report_splits_held_through_and_closed_before() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let earnings = NaiveDate::from_ymd_opt(2024, 1, 25).unwrap();
+
+        // Held overnight into earnings via a later exit execution
+        let mut swing = create_test_trade_input(&account_id, "AAPL");
+        swing.exit_price = None;
+        swing.exits = Some(vec![ExitExecution {
+            id: None,
+            exit_date: earnings,
+            exit_time: None,
+            quantity: 100.0,
+            price: 140.0,
+            fees: None,
+        }]);
+        let swing = TradeService::create_trade(&pool, &user_id, swing).await.unwrap();
+
+        // Closed on 2024-01-22, three days before
+        let mut early = create_test_trade_input(&account_id, "AAPL");
+        early.trade_date = NaiveDate::from_ymd_opt(2024, 1, 22).unwrap();
+        TradeService::create_trade(&pool, &user_id, early).await.unwrap();
+
+        // Well before the window, and a different symbol
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+        let mut other = create_test_trade_input(&account_id, "MSFT");
+        other.trade_date = NaiveDate::from_ymd_opt(2024, 1, 24).unwrap();
+        TradeService::create_trade(&pool, &user_id, other).await.unwrap();
+
+        MarketEventService::add_event(&pool, &user_id, CreateMarketEventInput {
+            event_date: earnings,
+            event_type: MarketEventType::Earnings,
+            symbol: Some("aapl".to_string()),
+            description: None,
+        })
+        .await
+        .unwrap();
+
+        let report = MarketEventService::get_earnings_window_report(&pool, &user_id, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(report.window_days, 5);
+        assert_eq!(report.held_through.trade_count, 1);
+        assert_eq!(report.held_through_trade_ids, vec![swing.trade.id.clone()]);
+        assert_eq!(report.closed_before.trade_count, 1);
+    }
+}
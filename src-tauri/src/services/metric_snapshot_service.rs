@@ -0,0 +1,128 @@
+use chrono::NaiveDate;
+use sqlx::sqlite::SqlitePool;
+use crate::calculations::calculate_metric_snapshots;
+use crate::models::MetricSnapshot;
+use crate::repository::{AccountRepository, CashTransactionRepository, DailyPerformanceRepository, MetricSnapshotRepository};
+
+/// Takes the nightly metric snapshots that long-term trend charts read
+/// Each run extends an account's snapshots from its latest one with the daily performance rows since,
+/// so it never goes back over the whole trade history. Changing a past day deletes the snapshots
+/// from that day on, and the next run takes them again.
+pub struct MetricSnapshotService;
+
+impl MetricSnapshotService {
+    /// Snapshot each of a user's accounts through the day before `today`, catching up on nights
+    /// the app was not running
+    /// Returns the number of snapshots written.
+    pub async fn take_due(pool: &SqlitePool, user_id: &str, today: NaiveDate) -> Result<usize, String> {
+        let Some(end_date) = today.pred_opt() else {
+            return Ok(0);
+        };
+        let accounts = AccountRepository::get_accounts(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get accounts: {}", e))?;
+
+        let mut written = 0;
+        for account in accounts {
+            let previous = MetricSnapshotRepository::get_latest(pool, &account.id)
+                .await
+                .map_err(|e| format!("Failed to get metric snapshots: {}", e))?;
+            let start_date = match previous {
+                Some(ref previous) if previous.date >= end_date => continue,
+                Some(ref previous) => previous.date + chrono::Duration::days(1),
+                None => NaiveDate::from_ymd_opt(1970, 1, 1).unwrap(),
+            };
+
+            let days: Vec<_> = DailyPerformanceRepository::get_days(pool, user_id, Some(&account.id), start_date, end_date)
+                .await
+                .map_err(|e| format!("Failed to get daily performance: {}", e))?
+                .into_iter()
+                .map(|(_, day)| day)
+                .collect();
+            let cash_transactions =
+                CashTransactionRepository::get_transactions(pool, user_id, Some(&account.id), None, Some(end_date))
+                    .await
+                    .map_err(|e| format!("Failed to get cash transactions: {}", e))?;
+
+            let snapshots = calculate_metric_snapshots(
+                &account.id,
+                previous.as_ref(),
+                &days,
+                &cash_transactions,
+                account.starting_balance,
+                end_date,
+            );
+            MetricSnapshotRepository::insert_snapshots(pool, user_id, &snapshots)
+                .await
+                .map_err(|e| format!("Failed to save metric snapshots: {}", e))?;
+            written += snapshots.len();
+        }
+        Ok(written)
+    }
+
+    /// Get snapshots, optionally for a single account and date range
+    pub async fn get_snapshots(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<MetricSnapshot>, String> {
+        MetricSnapshotRepository::get_snapshots(pool, user_id, account_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get metric snapshots: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreateCashTransactionInput;
+    use crate::services::{AccountService, TradeService};
+    use crate::test_utils::{create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    #[tokio::test]
+    async fn test_take_due_snapshots() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        AccountService::set_starting_balance(&pool, &user_id, &account_id, Some(10000.0)).await.unwrap();
+
+        let trade = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL")).await.unwrap();
+        let trade_date = trade.trade.trade_date;
+        let today = trade_date + chrono::Duration::days(3);
+
+        assert_eq!(MetricSnapshotService::take_due(&pool, &user_id, today).await.unwrap(), 3);
+        // Already taken through yesterday
+        assert_eq!(MetricSnapshotService::take_due(&pool, &user_id, today).await.unwrap(), 0);
+
+        let snapshots = MetricSnapshotService::get_snapshots(&pool, &user_id, Some(&account_id), None, None).await.unwrap();
+        assert_eq!(snapshots[0].date, trade_date);
+        assert_eq!(snapshots[0].trade_count, 1);
+        let pnl = trade.net_pnl.unwrap();
+        assert_eq!(snapshots[2].balance, Some(10000.0 + pnl));
+
+        // A deposit on a snapshotted day retakes the snapshots from that day on
+        AccountService::add_cash_transaction(
+            &pool,
+            &user_id,
+            CreateCashTransactionInput {
+                account_id: account_id.clone(),
+                transaction_date: trade_date + chrono::Duration::days(1),
+                amount: 500.0,
+                notes: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(MetricSnapshotService::take_due(&pool, &user_id, today).await.unwrap(), 2);
+        let snapshots = MetricSnapshotService::get_snapshots(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(snapshots.len(), 3);
+        assert_eq!(snapshots[0].balance, Some(10000.0 + pnl));
+        assert_eq!(snapshots[2].balance, Some(10500.0 + pnl));
+
+        // So does deleting the trade, which leaves nothing to snapshot
+        TradeService::delete_trade(&pool, &user_id, &trade.trade.id).await.unwrap();
+        assert_eq!(MetricSnapshotService::take_due(&pool, &user_id, today).await.unwrap(), 0);
+        assert!(MetricSnapshotService::get_snapshots(&pool, &user_id, None, None, None).await.unwrap().is_empty());
+    }
+}
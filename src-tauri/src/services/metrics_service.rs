@@ -1,20 +1,157 @@
+use std::collections::{HashMap, HashSet};
 use chrono::NaiveDate;
 use sqlx::sqlite::SqlitePool;
-use crate::calculations::{calculate_daily_metrics, calculate_equity_curve_owned, calculate_period_metrics};
-use crate::models::{DailyPerformance, EquityPoint, PeriodMetrics};
-use crate::services::TradeService;
+use crate::calculations::{
+    add_unrealized_changes, calculate_average_risk, merge_account_days, calculate_equity_curve_owned, calculate_equity_curves_by, calculate_period_metrics,
+    calculate_exchange_report, calculate_execution_quality, calculate_max_drawdown_pct, calculate_portfolio_heat, calculate_position_sizing_audit, calculate_r_expectancy,
+    calculate_entry_cohort_report, calculate_strategy_trends, calculate_target_calibration, calculate_trade_sequence_report, calculate_trade_type_report,
+    calculate_overtrading_report, calculate_day_timeline, calculate_options_premium_report, calculate_margin_utilization, calculate_short_side_report, calculate_futures_session_report, calculate_crypto_tax_report,
+    calculate_account_performance, calculate_account_returns, calculate_skill_progression, calculate_symbol_month_matrix, calculate_trade_clusters, reconcile_trades, FxTable, ReportingConverter,
+};
+use crate::models::{
+    AccountPerformance, AccountReturns, CryptoTaxReport, DailyClose, DailyPerformance, EquityCurveGrouping, EquityPoint, EquitySeries, ExchangeBucket, ExecutionQualityReport, FuturesSessionReport, MarginUtilizationReport, PeriodMetrics, PortfolioHeatPoint, PositionSizingReport,
+    OptionsPremiumReport, OvertradingReport, PerformanceBucket, EntryCohort, ShortSideReport, SkillProgressionReport, StrategyTrend, RExpectancyReport, ReconciliationReport, StatementTotals, Status, SymbolMonthMatrix, TargetCalibrationReport, TimelineEvent, TradeClusterReport, TradeFilter, TradeSequenceBucket, TradeWithDerived,
+};
+use crate::repository::{AccountRepository, CashTransactionRepository, ChecklistRepository, DailyCloseRepository, DailyPerformanceRepository, FinancingChargeRepository, InstrumentRepository, StopAdjustmentRepository, TradeRepository};
+use crate::services::settings_service::SettingsService;
+use crate::services::{DailyPerformanceService, FxService, TradeService};
+
+/// Reward-to-risk checked by the target calibration report when none is given
+pub const DEFAULT_CALIBRATION_R: f64 = 2.0;
+
+/// Calendar days in each window of the strategy trends when none is given
+pub const DEFAULT_STRATEGY_TREND_DAYS: i64 = 30;
+
+/// Trades in each sample of the skill progression report when none is given
+pub const DEFAULT_SKILL_SAMPLE_SIZE: i32 = 50;
 
 pub struct MetricsService;
 
 impl MetricsService {
     /// Get daily performance for a date range
+    /// Realized PnL is read from the daily performance summary, one row per account and day.
     pub async fn get_daily_performance(
         pool: &SqlitePool,
         user_id: &str,
         account_id: Option<&str>,
         start_date: NaiveDate,
         end_date: NaiveDate,
+        include_unrealized: bool,
     ) -> Result<Vec<DailyPerformance>, String> {
+        let mut account_days = DailyPerformanceRepository::get_days(pool, user_id, account_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get daily performance: {}", e))?;
+        let paper_account_ids = Self::excluded_paper_account_ids(pool, user_id, account_id).await?;
+        account_days.retain(|(account, _)| !paper_account_ids.contains(account));
+
+        // Each account's day is converted at that day's rate
+        let converter = Self::reporting_converter(pool, user_id, account_id).await?;
+        if let Some(ref converter) = converter {
+            converter.convert_account_days(&mut account_days)?;
+        }
+        let days = merge_account_days(account_days);
+
+        if !include_unrealized {
+            return Ok(days);
+        }
+
+        // Positions still open, marked to market from their entry through the end of the range, and
+        // positions closed by a roll without an exit, marked until the roll
+        let filter = TradeFilter {
+            account_id: account_id.map(str::to_string),
+            end_date: Some(end_date),
+            ..Default::default()
+        };
+        let open_trades: Vec<TradeWithDerived> = TradeService::find_trades(pool, user_id, &filter)
+            .await?
+            .into_iter()
+            .filter(|t| t.trade.status == Status::Open || (t.trade.roll_type.is_some() && t.trade.exit_date.is_none()))
+            .collect();
+        let mut open_trades = Self::exclude_paper_trades(pool, user_id, account_id, open_trades).await?;
+        let Some(first_entry) = open_trades.iter().map(|t| t.trade.trade_date).min() else {
+            return Ok(days);
+        };
+
+        let mut symbols: Vec<String> = open_trades.iter().map(|t| t.trade.symbol.clone()).collect();
+        symbols.sort();
+        symbols.dedup();
+        let closes = DailyCloseRepository::get_closes(pool, &symbols, first_entry, end_date)
+            .await
+            .map_err(|e| format!("Failed to get daily closes: {}", e))?;
+
+        let Some(converter) = converter else {
+            return Ok(add_unrealized_changes(days, &open_trades, &closes, start_date, end_date));
+        };
+
+        // Closes are in the currency of the account holding the position, so positions are marked
+        // per account currency with the closes converted at that currency's rate of each day
+        converter.convert_trades(&mut open_trades)?;
+        let mut by_currency: HashMap<Option<&str>, Vec<TradeWithDerived>> = HashMap::new();
+        for trade in open_trades {
+            by_currency
+                .entry(converter.account_currency(&trade.trade.account_id))
+                .or_default()
+                .push(trade);
+        }
+
+        let mut days = days;
+        for (currency, trades) in by_currency {
+            let mut currency_closes = HashMap::new();
+            for trade in &trades {
+                let symbol = &trade.trade.symbol;
+                if currency_closes.contains_key(symbol) {
+                    continue;
+                }
+                let Some(symbol_closes) = closes.get(symbol) else {
+                    continue;
+                };
+                let mut converted = symbol_closes.clone();
+                for (date, close) in converted.iter_mut() {
+                    *close *= converter.currency_rate(currency, *date)?;
+                }
+                currency_closes.insert(symbol.clone(), converted);
+            }
+            days = add_unrealized_changes(days, &trades, &currency_closes, start_date, end_date);
+        }
+        Ok(days)
+    }
+
+    /// Store daily closing prices used to mark open positions to market
+    pub async fn save_daily_closes(pool: &SqlitePool, closes: Vec<DailyClose>) -> Result<usize, String> {
+        let closes = closes
+            .into_iter()
+            .map(|c| {
+                let symbol = c.symbol.trim().to_uppercase();
+                if symbol.is_empty() {
+                    return Err("Symbol is required".to_string());
+                }
+                if c.close <= 0.0 || !c.close.is_finite() {
+                    return Err(format!("Close for {} on {} must be greater than 0", symbol, c.date));
+                }
+                if let (Some(high), Some(low)) = (c.high, c.low) {
+                    if low <= 0.0 || high < low || c.close > high || c.close < low {
+                        return Err(format!("High and low for {} on {} must bracket the close", symbol, c.date));
+                    }
+                }
+                Ok(DailyClose { symbol, ..c })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        DailyCloseRepository::upsert_many(pool, &closes)
+            .await
+            .map_err(|e| format!("Failed to save daily closes: {}", e))?;
+
+        Ok(closes.len())
+    }
+
+    /// Get period metrics for a date range
+    pub async fn get_period_metrics(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<PeriodMetrics, String> {
         let trades = TradeService::get_trades(
             pool,
             user_id,
@@ -23,18 +160,37 @@ impl MetricsService {
             Some(end_date),
         )
         .await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
 
-        Ok(calculate_daily_metrics(&trades))
+        let mut metrics = calculate_period_metrics(&trades);
+        Self::fill_relative_drawdowns(pool, user_id, account_id, &trades, &mut metrics).await?;
+        Ok(metrics)
     }
 
-    /// Get period metrics for a date range
-    pub async fn get_period_metrics(
+    /// Get all-time period metrics
+    pub async fn get_all_time_metrics(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+    ) -> Result<PeriodMetrics, String> {
+        let trades = TradeService::get_trades(pool, user_id, account_id, None, None).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
+        let mut metrics = calculate_period_metrics(&trades);
+        Self::fill_relative_drawdowns(pool, user_id, account_id, &trades, &mut metrics).await?;
+        Ok(metrics)
+    }
+
+    /// Get one equity curve per strategy or account for a date range
+    pub async fn get_equity_curve_series(
         pool: &SqlitePool,
         user_id: &str,
         account_id: Option<&str>,
         start_date: NaiveDate,
         end_date: NaiveDate,
-    ) -> Result<PeriodMetrics, String> {
+        grouping: EquityCurveGrouping,
+    ) -> Result<Vec<EquitySeries>, String> {
         let trades = TradeService::get_trades(
             pool,
             user_id,
@@ -43,18 +199,312 @@ impl MetricsService {
             Some(end_date),
         )
         .await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
 
-        Ok(calculate_period_metrics(&trades))
+        Ok(calculate_equity_curves_by(&trades, grouping))
     }
 
-    /// Get all-time period metrics
-    pub async fn get_all_time_metrics(
+    /// Get each strategy's rolling expectancy and trade count over windows of window_days
+    /// Trades up to a window before start_date are loaded so the first points have full windows.
+    pub async fn get_strategy_trends(
         pool: &SqlitePool,
         user_id: &str,
         account_id: Option<&str>,
-    ) -> Result<PeriodMetrics, String> {
-        let trades = TradeService::get_trades(pool, user_id, account_id, None, None).await?;
-        Ok(calculate_period_metrics(&trades))
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        window_days: Option<i64>,
+    ) -> Result<Vec<StrategyTrend>, String> {
+        let window_days = window_days.unwrap_or(DEFAULT_STRATEGY_TREND_DAYS);
+        if window_days < 1 {
+            return Err("Trend window must be at least 1 day".to_string());
+        }
+
+        let load_from = start_date.map(|start| start - chrono::Duration::days(window_days - 1));
+        let trades = TradeService::get_trades(pool, user_id, account_id, load_from, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
+
+        Ok(calculate_strategy_trends(&trades, window_days, start_date))
+    }
+
+    /// Get expectancy in R for a date range
+    /// Trades without a stop loss use the default risk per trade from settings when set.
+    pub async fn get_r_expectancy(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<RExpectancyReport, String> {
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let default_risk = SettingsService::get_default_risk_per_trade(pool).await?;
+
+        Ok(calculate_r_expectancy(&trades, default_risk))
+    }
+
+    /// Audit dollar risk per closed trade against the account risk percent setting
+    /// Earlier trades are loaded too so account equity at each trade includes all prior PnL.
+    pub async fn get_position_sizing_audit(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<PositionSizingReport, String> {
+        let risk_pct = SettingsService::get_account_risk_pct(pool)
+            .await?
+            .ok_or("Set an account risk percent to audit position sizing")?;
+
+        let trades = TradeService::get_trades(pool, user_id, account_id, None, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+
+        let starting_balances: HashMap<String, f64> = AccountRepository::get_accounts(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get accounts: {}", e))?
+            .into_iter()
+            .filter_map(|a| a.starting_balance.map(|balance| (a.id, balance)))
+            .collect();
+
+        Ok(calculate_position_sizing_audit(&trades, &starting_balances, risk_pct, start_date))
+    }
+
+    /// Get how often trades planned at min_planned_r or more reached it (defaults to 2R)
+    pub async fn get_target_calibration(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        min_planned_r: Option<f64>,
+    ) -> Result<TargetCalibrationReport, String> {
+        let min_planned_r = min_planned_r.unwrap_or(DEFAULT_CALIBRATION_R);
+        if min_planned_r <= 0.0 {
+            return Err("Minimum planned R must be greater than 0".to_string());
+        }
+
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+
+        Ok(calculate_target_calibration(&trades, min_planned_r))
+    }
+
+    /// Get performance by trade sequence within the day
+    pub async fn get_trade_sequence_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<TradeSequenceBucket>, String> {
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
+
+        Ok(calculate_trade_sequence_report(&trades))
+    }
+
+    /// Get performance by trade type, from scalps to positions
+    pub async fn get_trade_type_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<PerformanceBucket>, String> {
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
+
+        Ok(calculate_trade_type_report(&trades))
+    }
+
+    /// Get performance by entry month, to see whether recent trades do better than older ones
+    pub async fn get_entry_cohort_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<EntryCohort>, String> {
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
+
+        Ok(calculate_entry_cohort_report(&trades))
+    }
+
+    /// Compare the first and most recent closed trades for win rate, R and mistakes per trade
+    /// Uses the last sample_size trades (50 unless given) against the first as many.
+    pub async fn get_skill_progression(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        sample_size: Option<i32>,
+    ) -> Result<SkillProgressionReport, String> {
+        let sample_size = sample_size.unwrap_or(DEFAULT_SKILL_SAMPLE_SIZE);
+        if sample_size < 1 {
+            return Err("Sample size must be at least 1 trade".to_string());
+        }
+
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let default_risk = SettingsService::get_default_risk_per_trade(pool).await?;
+        let stop_adjustments = StopAdjustmentRepository::get_by_trade(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get stop adjustments: {}", e))?;
+        let checks = ChecklistRepository::get_checks(pool, user_id, account_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get checklists: {}", e))?;
+
+        Ok(calculate_skill_progression(&trades, sample_size as usize, default_risk, &stop_adjustments, &checks))
+    }
+
+    /// Cluster closed trades by time of day, hold time, R, symbol volatility and tags
+    /// Forms one cluster per 15 trades, from 2 to 6, unless cluster_count is given.
+    pub async fn get_trade_clusters(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        cluster_count: Option<i32>,
+    ) -> Result<TradeClusterReport, String> {
+        if cluster_count.is_some_and(|count| count < 1) {
+            return Err("Cluster count must be at least 1".to_string());
+        }
+
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
+        let default_risk = SettingsService::get_default_risk_per_trade(pool).await?;
+        let tags = TradeRepository::get_tag_names(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get trade tags: {}", e))?;
+
+        Ok(calculate_trade_clusters(&trades, &tags, cluster_count.map(|count| count as usize), default_risk))
+    }
+
+    /// Get net PnL and trade count by symbol and month for the heatmap
+    /// Trades land in the month of their exit when PnL is attributed to the exit date.
+    pub async fn get_symbol_month_matrix(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<SymbolMonthMatrix, String> {
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
+        let exit_dates = DailyPerformanceService::attribution_exit_dates(pool, user_id).await?;
+
+        Ok(calculate_symbol_month_matrix(&trades, &exit_dates))
+    }
+
+    /// Get days where a symbol was traded more often than the configured threshold
+    pub async fn get_overtrading_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<OvertradingReport, String> {
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
+        let max_trades = SettingsService::get_max_trades_per_symbol_per_day(pool).await?;
+
+        Ok(calculate_overtrading_report(&trades, max_trades))
+    }
+
+    /// Get win rate, PnL and assignment frequency of premium-selling vs premium-buying option trades
+    pub async fn get_options_premium_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<OptionsPremiumReport, String> {
+        let trades = TradeService::get_all_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
+
+        Ok(calculate_options_premium_report(&trades))
+    }
+
+    /// Get short-side costs vs long, days held short and performance by borrow availability
+    pub async fn get_short_side_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<ShortSideReport, String> {
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
+        let exit_dates = TradeRepository::get_last_exit_dates(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get exit dates: {}", e))?;
+
+        Ok(calculate_short_side_report(&trades, &exit_dates))
+    }
+
+    /// Get futures performance by contract month and by RTH vs overnight (Globex) session
+    pub async fn get_futures_session_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<FuturesSessionReport, String> {
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
+
+        Ok(calculate_futures_session_report(&trades))
+    }
+
+    /// Get realized crypto gains with disposals matched to lots by the configured cost-basis method
+    /// Lots acquired before the start date still count, and paper accounts are never taxed.
+    pub async fn get_crypto_tax_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<CryptoTaxReport, String> {
+        let method = SettingsService::get_crypto_cost_basis_method(pool).await?;
+        let paper_account_ids = AccountRepository::get_paper_account_ids(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get paper accounts: {}", e))?;
+        let fills: Vec<_> = TradeRepository::get_execution_fills(pool, user_id, account_id, None, end_date)
+            .await
+            .map_err(|e| format!("Failed to get executions: {}", e))?
+            .into_iter()
+            .filter(|f| !paper_account_ids.contains(&f.account_id))
+            .collect();
+
+        Ok(calculate_crypto_tax_report(&fills, method, start_date))
+    }
+
+    /// Get a day's executions in time order with running position and PnL per symbol
+    pub async fn get_day_timeline(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        date: NaiveDate,
+    ) -> Result<Vec<TimelineEvent>, String> {
+        // Earlier fills give the positions carried into the day
+        let fills = TradeRepository::get_execution_fills(pool, user_id, account_id, None, Some(date))
+            .await
+            .map_err(|e| format!("Failed to get executions: {}", e))?;
+
+        Ok(calculate_day_timeline(&fills, date))
     }
 
     /// Get equity curve for a date range
@@ -65,7 +515,7 @@ impl MetricsService {
         start_date: NaiveDate,
         end_date: NaiveDate,
     ) -> Result<Vec<EquityPoint>, String> {
-        let mut trades = TradeService::get_trades(
+        let trades = TradeService::get_trades(
             pool,
             user_id,
             account_id,
@@ -73,6 +523,8 @@ impl MetricsService {
             Some(end_date),
         )
         .await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let mut trades = Self::to_reporting_currency(pool, user_id, account_id, trades).await?;
 
         // Sort by date for correct equity curve
         trades.sort_by_key(|t| t.trade.trade_date);
@@ -82,7 +534,7 @@ impl MetricsService {
         // Check if there are any trades BEFORE start_date
         // If so, we're viewing a filtered subset and should start from $0
         // If not, we're viewing "all time" and should start from first trade
-        let has_trades_before_start = TradeService::get_trades(
+        let trades_before_start = TradeService::get_trades(
             pool,
             user_id,
             account_id,
@@ -90,8 +542,11 @@ impl MetricsService {
             Some(start_date - chrono::Duration::days(1)),
         )
         .await
-        .map(|t| !t.is_empty())
-        .unwrap_or(false);
+        .unwrap_or_default();
+        let has_trades_before_start =
+            !Self::exclude_paper_trades(pool, user_id, account_id, trades_before_start)
+                .await?
+                .is_empty();
 
         // Add $0 starting point only when viewing a filtered range (trades exist before start_date)
         // and the first trade in range is after start_date
@@ -112,12 +567,299 @@ impl MetricsService {
 
         Ok(curve)
     }
+
+    /// Get total open stop-based risk per day for a date range
+    pub async fn get_portfolio_heat(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<PortfolioHeatPoint>, String> {
+        // Positions opened before the range may still be open during it
+        let trades = TradeService::get_all_trades(pool, user_id, account_id, None, Some(end_date))
+            .await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+
+        let exit_dates = TradeRepository::get_last_exit_dates(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get exit dates: {}", e))?;
+        let stop_adjustments = StopAdjustmentRepository::get_by_trade(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get stop adjustments: {}", e))?;
+
+        Ok(calculate_portfolio_heat(&trades, &exit_dates, &stop_adjustments, start_date, end_date))
+    }
+
+    /// Get peak margin utilization and leverage per account per day, flagging days over the configured maximum
+    /// Positions opened before the range and trades closed before it still count toward the range.
+    pub async fn get_margin_utilization(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<MarginUtilizationReport, String> {
+        let max_utilization_pct = SettingsService::get_max_margin_utilization_pct(pool).await?;
+
+        let trades = TradeService::get_all_trades(pool, user_id, account_id, None, Some(end_date))
+            .await?;
+        let trades = Self::exclude_paper_trades(pool, user_id, account_id, trades).await?;
+        let exit_dates = TradeRepository::get_last_exit_dates(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get exit dates: {}", e))?;
+        let margin_requirements = InstrumentRepository::get_margin_requirements(pool)
+            .await
+            .map_err(|e| format!("Failed to get margin requirements: {}", e))?;
+        let starting_balances: HashMap<String, f64> = AccountRepository::get_accounts(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get accounts: {}", e))?
+            .into_iter()
+            .filter_map(|a| a.starting_balance.map(|balance| (a.id, balance)))
+            .collect();
+
+        Ok(calculate_margin_utilization(
+            &trades,
+            &exit_dates,
+            &margin_requirements,
+            &starting_balances,
+            max_utilization_pct,
+            start_date,
+            end_date,
+        ))
+    }
+
+    /// Get slippage of fills versus their intended price
+    pub async fn get_execution_quality(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<ExecutionQualityReport, String> {
+        let fills = TradeRepository::get_execution_fills(pool, user_id, account_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get executions: {}", e))?;
+
+        Ok(calculate_execution_quality(&fills))
+    }
+
+    /// Get fill counts, fees and slippage per exchange/route
+    pub async fn get_exchange_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<ExchangeBucket>, String> {
+        let fills = TradeRepository::get_execution_fills(pool, user_id, account_id, start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get executions: {}", e))?;
+
+        Ok(calculate_exchange_report(&fills))
+    }
+
+    /// Compare an account's journal totals against broker statement totals
+    pub async fn reconcile(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        statement_totals: StatementTotals,
+    ) -> Result<ReconciliationReport, String> {
+        let trades = TradeService::get_all_trades(
+            pool,
+            user_id,
+            Some(account_id),
+            Some(start_date),
+            Some(end_date),
+        )
+        .await?;
+        let financing_costs: f64 = FinancingChargeRepository::get_charges(
+            pool,
+            user_id,
+            Some(account_id),
+            Some(start_date),
+            Some(end_date),
+        )
+        .await
+        .map_err(|e| format!("Failed to get financing charges: {}", e))?
+        .iter()
+        .map(|c| c.amount)
+        .sum();
+
+        Ok(reconcile_trades(&trades, financing_costs, &statement_totals))
+    }
+
+    /// Get an account's net PnL and return including margin interest and financing charges
+    pub async fn get_account_performance(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: &str,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<AccountPerformance, String> {
+        let account = AccountRepository::get_by_id(pool, account_id)
+            .await
+            .map_err(|e| format!("Failed to get account: {}", e))?
+            .filter(|a| a.user_id == user_id)
+            .ok_or_else(|| format!("Account not found: {}", account_id))?;
+
+        let trades = TradeService::get_trades(pool, user_id, Some(account_id), start_date, end_date).await?;
+        let charges = FinancingChargeRepository::get_charges(pool, user_id, Some(account_id), start_date, end_date)
+            .await
+            .map_err(|e| format!("Failed to get financing charges: {}", e))?;
+
+        Ok(calculate_account_performance(account_id, &trades, &charges, account.starting_balance))
+    }
+
+    /// Get an account's time-weighted and money-weighted returns, net of deposits and withdrawals
+    pub async fn get_account_returns(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<AccountReturns, String> {
+        let account = AccountRepository::get_by_id(pool, account_id)
+            .await
+            .map_err(|e| format!("Failed to get account: {}", e))?
+            .filter(|a| a.user_id == user_id)
+            .ok_or_else(|| format!("Account not found: {}", account_id))?;
+
+        // History before the period sets its starting value
+        let trades = TradeService::get_trades(pool, user_id, Some(account_id), None, Some(end_date)).await?;
+        let charges = FinancingChargeRepository::get_charges(pool, user_id, Some(account_id), None, Some(end_date))
+            .await
+            .map_err(|e| format!("Failed to get financing charges: {}", e))?;
+        let cash_transactions = CashTransactionRepository::get_transactions(pool, user_id, Some(account_id), None, Some(end_date))
+            .await
+            .map_err(|e| format!("Failed to get cash transactions: {}", e))?;
+
+        Ok(calculate_account_returns(
+            account_id,
+            &trades,
+            &charges,
+            &cash_transactions,
+            account.starting_balance,
+            start_date,
+            end_date,
+        ))
+    }
+
+    /// Express max drawdown in R (average risk per trade) and as a percent of peak equity
+    /// R falls back to the default risk setting when no trade has a stop; percent needs a starting balance.
+    async fn fill_relative_drawdowns(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        trades: &[TradeWithDerived],
+        metrics: &mut PeriodMetrics,
+    ) -> Result<(), String> {
+        let average_risk = match calculate_average_risk(trades) {
+            Some(risk) => Some(risk),
+            None => SettingsService::get_default_risk_per_trade(pool).await?,
+        };
+        metrics.max_drawdown_r = average_risk
+            .filter(|risk| *risk > 0.0)
+            .map(|risk| metrics.max_drawdown / risk);
+
+        let accounts = AccountRepository::get_accounts(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get accounts: {}", e))?;
+        let include_paper = account_id.is_some() || SettingsService::get_include_paper_trades(pool).await?;
+        // Balances are converted at the latest rate, as they stand for the accounts' current size
+        let converter = Self::reporting_converter(pool, user_id, account_id).await?;
+        let today = chrono::Utc::now().date_naive();
+        let balances: Vec<f64> = accounts
+            .iter()
+            .filter(|a| account_id.map_or(include_paper || !a.is_paper, |id| a.id == id))
+            .filter_map(|a| a.starting_balance.map(|balance| (a, balance)))
+            .map(|(a, balance)| match converter {
+                Some(ref converter) => converter.account_rate(&a.id, today).map(|rate| balance * rate),
+                None => Ok(balance),
+            })
+            .collect::<Result<_, String>>()?;
+
+        if !balances.is_empty() {
+            metrics.max_drawdown_pct = calculate_max_drawdown_pct(trades, balances.iter().sum());
+        }
+        Ok(())
+    }
+
+    /// Convert trades across accounts to the reporting currency, when one is set
+    /// A selected account is reported in its own currency.
+    pub(crate) async fn to_reporting_currency(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        mut trades: Vec<TradeWithDerived>,
+    ) -> Result<Vec<TradeWithDerived>, String> {
+        if let Some(converter) = Self::reporting_converter(pool, user_id, account_id).await? {
+            converter.convert_trades(&mut trades)?;
+        }
+        Ok(trades)
+    }
+
+    async fn reporting_converter(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+    ) -> Result<Option<ReportingConverter>, String> {
+        if account_id.is_some() {
+            return Ok(None);
+        }
+        let Some(currency) = SettingsService::get_reporting_currency(pool).await? else {
+            return Ok(None);
+        };
+
+        let account_currencies: HashMap<String, String> = AccountRepository::get_accounts(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get accounts: {}", e))?
+            .into_iter()
+            .map(|a| (a.id, a.base_currency))
+            .collect();
+        let rates = FxService::get_rates(pool).await?;
+
+        Ok(Some(ReportingConverter::new(&currency, account_currencies, FxTable::new(&rates))))
+    }
+
+    /// Drop paper account trades from metrics across all accounts
+    /// Paper trades are kept when their account is selected or when enabled in settings.
+    pub(crate) async fn exclude_paper_trades(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        trades: Vec<TradeWithDerived>,
+    ) -> Result<Vec<TradeWithDerived>, String> {
+        let paper_account_ids = Self::excluded_paper_account_ids(pool, user_id, account_id).await?;
+        Ok(trades
+            .into_iter()
+            .filter(|t| !paper_account_ids.contains(&t.trade.account_id))
+            .collect())
+    }
+
+    /// Paper accounts left out of metrics, none when an account is selected or paper trades are included
+    async fn excluded_paper_account_ids(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+    ) -> Result<HashSet<String>, String> {
+        if account_id.is_some() || SettingsService::get_include_paper_trades(pool).await? {
+            return Ok(HashSet::new());
+        }
+
+        AccountRepository::get_paper_account_ids(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get paper accounts: {}", e))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{CreateTradeInput, Direction, Status};
+    use crate::models::{CreateTradeInput, DateAttribution, Direction, Status};
     use crate::services::TradeService;
     use crate::test_utils::{create_test_db, setup_test_user_and_account};
 
@@ -140,6 +882,8 @@ mod tests {
             entry_price: entry,
             exit_price: Some(exit),
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(fees),
@@ -175,7 +919,7 @@ mod tests {
         .await
         .unwrap();
 
-        let daily = MetricsService::get_daily_performance(&pool, &user_id, None, date, date)
+        let daily = MetricsService::get_daily_performance(&pool, &user_id, None, date, date, false)
             .await
             .expect("Failed to get daily performance");
 
@@ -185,6 +929,133 @@ mod tests {
         assert_eq!(daily[0].trade_count, 2);
         assert_eq!(daily[0].win_count, 2);
         assert_eq!(daily[0].loss_count, 0);
+
+        assert!((daily[0].gross_pnl - 1500.0).abs() < 0.01);
+        assert_eq!(daily[0].total_volume, 200.0);
+        assert_eq!(daily[0].largest_win, Some(1000.0));
+        assert_eq!(daily[0].largest_loss, None);
+    }
+
+    #[tokio::test]
+    async fn test_daily_performance_gross_fees_and_scratches() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        for (exit, fees) in [(110.0, 5.0), (95.0, 5.0), (90.0, 2.0), (100.0, 0.0)] {
+            TradeService::create_trade(
+                &pool,
+                &user_id,
+                create_trade_input(&account_id, date, 100.0, exit, 10.0, fees),
+            )
+            .await
+            .unwrap();
+        }
+
+        let daily = MetricsService::get_daily_performance(&pool, &user_id, None, date, date, false)
+            .await
+            .expect("Failed to get daily performance");
+
+        // Gross: 100 - 50 - 100 = -50, fees 12, net -62
+        assert!((daily[0].gross_pnl - (-50.0)).abs() < 0.01);
+        assert!((daily[0].total_fees - 12.0).abs() < 0.01);
+        assert!((daily[0].realized_net_pnl - (-62.0)).abs() < 0.01);
+        assert_eq!(daily[0].largest_win, Some(95.0));
+        assert_eq!(daily[0].largest_loss, Some(-102.0));
+
+        // Scratch trade at entry price
+        assert_eq!(daily[0].trade_count, 4);
+        assert_eq!(daily[0].breakeven_count, 1);
+        assert!((daily[0].scratch_rate - 0.25).abs() < 0.001);
+        assert!((daily[0].avg_trade_pnl - (-15.5)).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_daily_performance_attributes_swing_trades_to_exit_date() {
+        use crate::models::ExitExecution;
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // Opened at the end of January and closed in February
+        let mut input = create_trade_input(&account_id, NaiveDate::from_ymd_opt(2024, 1, 30).unwrap(), 100.0, 110.0, 10.0, 0.0);
+        input.exit_price = None;
+        input.exits = Some(vec![ExitExecution {
+            id: None,
+            exit_date: NaiveDate::from_ymd_opt(2024, 2, 2).unwrap(),
+            exit_time: None,
+            quantity: 10.0,
+            price: 110.0,
+            fees: None,
+        }]);
+        TradeService::create_trade(&pool, &user_id, input).await.unwrap();
+
+        let feb_start = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+        let feb_end = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        let days = MetricsService::get_daily_performance(&pool, &user_id, None, feb_start, feb_end, false)
+            .await
+            .unwrap();
+        assert!(days.is_empty());
+
+        SettingsService::save_date_attribution(&pool, DateAttribution::Exit).await.unwrap();
+        let days = MetricsService::get_daily_performance(&pool, &user_id, None, feb_start, feb_end, false)
+            .await
+            .unwrap();
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].date, NaiveDate::from_ymd_opt(2024, 2, 2).unwrap());
+        assert!((days[0].realized_net_pnl - 100.0).abs() < 0.001);
+
+        // A filter can still ask for entry dates
+        let filter = TradeFilter {
+            start_date: Some(feb_start),
+            end_date: Some(feb_end),
+            date_attribution: Some(DateAttribution::Entry),
+            ..Default::default()
+        };
+        assert!(TradeService::find_trades(&pool, &user_id, &filter).await.unwrap().is_empty());
+
+        // Both attribution finds the trade in the month of either date and still reports its PnL once
+        SettingsService::save_date_attribution(&pool, DateAttribution::Both).await.unwrap();
+        let jan_filter = TradeFilter {
+            start_date: Some(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
+            end_date: Some(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap()),
+            ..Default::default()
+        };
+        let feb_filter = TradeFilter { date_attribution: None, ..filter };
+        assert_eq!(TradeService::find_trades(&pool, &user_id, &jan_filter).await.unwrap().len(), 1);
+        assert_eq!(TradeService::find_trades(&pool, &user_id, &feb_filter).await.unwrap().len(), 1);
+        let days = MetricsService::get_daily_performance(&pool, &user_id, None, feb_start, feb_end, false)
+            .await
+            .unwrap();
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].date, NaiveDate::from_ymd_opt(2024, 2, 2).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_all_time_metrics_drawdown_in_r_and_percent() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let day1 = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+        let day2 = NaiveDate::from_ymd_opt(2024, 1, 11).unwrap();
+
+        let mut win = create_trade_input(&account_id, day1, 100.0, 110.0, 100.0, 0.0); // +1000
+        win.stop_loss_price = Some(98.0); // 200 risk
+        let mut loss = create_trade_input(&account_id, day2, 100.0, 95.0, 100.0, 0.0); // -500
+        loss.stop_loss_price = Some(98.0);
+        TradeService::create_trade(&pool, &user_id, win).await.unwrap();
+        TradeService::create_trade(&pool, &user_id, loss).await.unwrap();
+
+        let metrics = MetricsService::get_all_time_metrics(&pool, &user_id, None).await.unwrap();
+        assert!((metrics.max_drawdown - 500.0).abs() < 0.01);
+        assert!((metrics.max_drawdown_r.unwrap() - 2.5).abs() < 0.001);
+        assert!(metrics.max_drawdown_pct.is_none());
+
+        AccountRepository::set_starting_balance(&pool, &user_id, &account_id, Some(9000.0))
+            .await
+            .unwrap();
+        let metrics = MetricsService::get_all_time_metrics(&pool, &user_id, Some(&account_id)).await.unwrap();
+        // Peak equity 10,000, drawdown 500 = 5%
+        assert!((metrics.max_drawdown_pct.unwrap() - 0.05).abs() < 0.0001);
     }
 
     #[tokio::test]
@@ -223,7 +1094,7 @@ mod tests {
         .await
         .unwrap();
 
-        let daily = MetricsService::get_daily_performance(&pool, &user_id, None, day1, day3)
+        let daily = MetricsService::get_daily_performance(&pool, &user_id, None, day1, day3, false)
             .await
             .expect("Failed to get daily performance");
 
@@ -429,8 +1300,48 @@ mod tests {
             .expect("Failed to get metrics");
 
         assert_eq!(metrics.trade_count, 2);
-        // +1000 + +500 = +1500
-        assert!((metrics.total_net_pnl - 1500.0).abs() < 0.01);
+        // +1000 + +500 = +1500
+        assert!((metrics.total_net_pnl - 1500.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_all_time_metrics_excludes_paper_accounts() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let paper = AccountRepository::create_with_paper_flag(&pool, &user_id, "Sim", None, true)
+            .await
+            .unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        TradeService::create_trade(
+            &pool,
+            &user_id,
+            create_trade_input(&account_id, date, 100.0, 110.0, 100.0, 0.0), // +1000 live
+        )
+        .await
+        .unwrap();
+
+        TradeService::create_trade(
+            &pool,
+            &user_id,
+            create_trade_input(&paper.id, date, 100.0, 150.0, 100.0, 0.0), // +5000 paper
+        )
+        .await
+        .unwrap();
+
+        // Headline metrics exclude paper trades by default
+        let metrics = MetricsService::get_all_time_metrics(&pool, &user_id, None).await.unwrap();
+        assert_eq!(metrics.trade_count, 1);
+        assert!((metrics.total_net_pnl - 1000.0).abs() < 0.01);
+
+        // Selecting the paper account analyzes it separately
+        let metrics = MetricsService::get_all_time_metrics(&pool, &user_id, Some(&paper.id)).await.unwrap();
+        assert_eq!(metrics.trade_count, 1);
+        assert!((metrics.total_net_pnl - 5000.0).abs() < 0.01);
+
+        SettingsService::save_include_paper_trades(&pool, true).await.unwrap();
+        let metrics = MetricsService::get_all_time_metrics(&pool, &user_id, None).await.unwrap();
+        assert_eq!(metrics.trade_count, 2);
     }
 
     #[tokio::test]
@@ -660,6 +1571,8 @@ mod tests {
             entry_price: 100.0,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -682,6 +1595,62 @@ mod tests {
         assert!((metrics.total_net_pnl - 1000.0).abs() < 0.01);
     }
 
+    #[tokio::test]
+    async fn test_daily_performance_marks_open_positions_to_closes() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let day1 = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let day2 = NaiveDate::from_ymd_opt(2024, 1, 3).unwrap();
+
+        TradeService::create_trade(
+            &pool,
+            &user_id,
+            create_trade_input(&account_id, day2, 100.0, 110.0, 10.0, 0.0), // +100
+        )
+        .await
+        .unwrap();
+
+        let open_input = CreateTradeInput {
+            exit_price: None,
+            status: Some(Status::Open),
+            ..create_trade_input(&account_id, day1, 100.0, 0.0, 10.0, 0.0)
+        };
+        TradeService::create_trade(&pool, &user_id, open_input)
+            .await
+            .unwrap();
+
+        let saved = MetricsService::save_daily_closes(
+            &pool,
+            vec![
+                DailyClose { symbol: "aapl".to_string(), date: day1, close: 102.0, high: None, low: None },
+                DailyClose { symbol: "AAPL".to_string(), date: day2, close: 99.0, high: None, low: None },
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(saved, 2);
+
+        let realized_only = MetricsService::get_daily_performance(&pool, &user_id, None, day1, day2, false)
+            .await
+            .unwrap();
+        assert_eq!(realized_only.len(), 1);
+        assert_eq!(realized_only[0].unrealized_pnl_change, None);
+
+        let daily = MetricsService::get_daily_performance(&pool, &user_id, None, day1, day2, true)
+            .await
+            .unwrap();
+
+        // The open position adds a day with no closed trades
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily[0].date, day1);
+        assert_eq!(daily[0].trade_count, 0);
+        assert!((daily[0].unrealized_pnl_change.unwrap() - 20.0).abs() < 0.01);
+        assert_eq!(daily[1].date, day2);
+        assert!((daily[1].realized_net_pnl - 100.0).abs() < 0.01);
+        assert!((daily[1].unrealized_pnl_change.unwrap() + 30.0).abs() < 0.01);
+    }
+
     #[tokio::test]
     async fn test_empty_metrics() {
         let pool = create_test_db().await;
@@ -761,4 +1730,709 @@ mod tests {
         assert_eq!(metrics.max_win_streak, 3);
         assert_eq!(metrics.max_loss_streak, 2);
     }
+
+    #[tokio::test]
+    async fn test_target_calibration_counts_trades_reaching_planned_r() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        // All risk 5 per share with a 3R target at 115
+        let planned = |exit: f64, best: Option<f64>| {
+            let mut input = create_trade_input(&account_id, date, 100.0, exit, 10.0, 0.0);
+            input.stop_loss_price = Some(95.0);
+            input.target_price = Some(115.0);
+            input.max_favorable_price = best;
+            input
+        };
+        let inputs = vec![
+            planned(115.0, None),        // Hit target
+            planned(95.0, Some(111.0)),  // Reached 2R, then stopped
+            planned(95.0, Some(104.0)),  // Stopped before 2R
+            planned(102.0, None),        // Closed early
+        ];
+        for input in inputs {
+            TradeService::create_trade(&pool, &user_id, input).await.unwrap();
+        }
+
+        // 1R target is below the threshold
+        let mut small = create_trade_input(&account_id, date, 100.0, 105.0, 10.0, 0.0);
+        small.stop_loss_price = Some(95.0);
+        small.target_price = Some(105.0);
+        TradeService::create_trade(&pool, &user_id, small).await.unwrap();
+
+        let report = MetricsService::get_target_calibration(&pool, &user_id, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(report.min_planned_r, 2.0);
+        assert_eq!(report.planned_count, 4);
+        assert_eq!(report.reached_count, 2);
+        assert_eq!(report.stopped_count, 1);
+        assert!((report.reach_rate.unwrap() - 0.5).abs() < 0.001);
+        assert!((report.avg_planned_r.unwrap() - 3.0).abs() < 0.001);
+
+        let report = MetricsService::get_target_calibration(&pool, &user_id, None, None, None, Some(1.0))
+            .await
+            .unwrap();
+        assert_eq!(report.planned_count, 5);
+        assert_eq!(report.reached_count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_overtrading_report_uses_configured_threshold() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        // Three AAPL round trips (+100, -50, -50) and one MSFT trade
+        for exit in [110.0, 95.0, 95.0] {
+            TradeService::create_trade(&pool, &user_id, create_trade_input(&account_id, date, 100.0, exit, 10.0, 0.0))
+                .await
+                .unwrap();
+        }
+        let mut msft = create_trade_input(&account_id, date, 100.0, 101.0, 10.0, 0.0);
+        msft.symbol = "MSFT".to_string();
+        TradeService::create_trade(&pool, &user_id, msft).await.unwrap();
+
+        let report = MetricsService::get_overtrading_report(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(report.max_trades_per_symbol_per_day, 5);
+        assert!(report.breaches.is_empty());
+
+        SettingsService::save_max_trades_per_symbol_per_day(&pool, 2).await.unwrap();
+        assert!(SettingsService::save_max_trades_per_symbol_per_day(&pool, 0).await.is_err());
+
+        let report = MetricsService::get_overtrading_report(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(report.breaches.len(), 1);
+        assert_eq!(report.breaches[0].symbol, "AAPL");
+        assert_eq!(report.breaches[0].trade_count, 3);
+        assert!((report.breach_net_pnl - 0.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_r_expectancy_uses_default_risk_without_stop() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        // Stop-based: +2 per share on 1 per share risk = 2R
+        let mut with_stop = create_trade_input(&account_id, date, 100.0, 102.0, 100.0, 0.0);
+        with_stop.stop_loss_price = Some(99.0);
+        TradeService::create_trade(&pool, &user_id, with_stop).await.unwrap();
+        // No stop: -100 net
+        TradeService::create_trade(&pool, &user_id, create_trade_input(&account_id, date, 100.0, 99.0, 100.0, 0.0))
+            .await
+            .unwrap();
+
+        let report = MetricsService::get_r_expectancy(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(report.trade_count, 2);
+        assert_eq!(report.stop_based_count, 1);
+        assert_eq!(report.skipped_count, 1);
+        assert!((report.expectancy_r.unwrap() - 2.0).abs() < 0.001);
+
+        SettingsService::save_default_risk_per_trade(&pool, Some(200.0)).await.unwrap();
+
+        let report = MetricsService::get_r_expectancy(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(report.assumed_risk_count, 1);
+        assert_eq!(report.skipped_count, 0);
+        // (2 + -0.5) / 2
+        assert!((report.expectancy_r.unwrap() - 0.75).abs() < 0.001);
+        assert!((report.avg_loss_r.unwrap() + 0.5).abs() < 0.001);
+    }
+    #[tokio::test]
+    async fn test_position_sizing_audit_flags_trades_over_account_risk() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+
+        // Risk 2 × 50 = 100, exactly 1% of 10,000; +200 raises equity to 10,200
+        let mut sized = create_trade_input(&account_id, day(1), 100.0, 104.0, 50.0, 0.0);
+        sized.stop_loss_price = Some(98.0);
+        TradeService::create_trade(&pool, &user_id, sized).await.unwrap();
+        // Risk 5 × 40 = 200 against 102 allowed; -400
+        let mut oversized = create_trade_input(&account_id, day(2), 100.0, 90.0, 40.0, 0.0);
+        oversized.stop_loss_price = Some(95.0);
+        let oversized = TradeService::create_trade(&pool, &user_id, oversized).await.unwrap();
+        TradeService::create_trade(&pool, &user_id, create_trade_input(&account_id, day(3), 100.0, 101.0, 10.0, 0.0))
+            .await
+            .unwrap();
+
+        let result = MetricsService::get_position_sizing_audit(&pool, &user_id, None, None, None).await;
+        assert!(result.is_err());
+
+        SettingsService::save_account_risk_pct(&pool, Some(1.0)).await.unwrap();
+        let report = MetricsService::get_position_sizing_audit(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(report.skipped_no_balance, 2);
+        assert_eq!(report.skipped_no_stop, 1);
+
+        AccountRepository::set_starting_balance(&pool, &user_id, &account_id, Some(10000.0))
+            .await
+            .unwrap();
+        let report = MetricsService::get_position_sizing_audit(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(report.points.len(), 2);
+        assert!(!report.points[0].oversized);
+        assert_eq!(report.oversized_count, 1);
+        assert!((report.oversized_net_pnl + 400.0).abs() < 0.01);
+        assert!((report.total_net_pnl + 200.0).abs() < 0.01);
+
+        // Trades before the range still count toward equity
+        let report = MetricsService::get_position_sizing_audit(&pool, &user_id, None, Some(day(2)), None)
+            .await
+            .unwrap();
+        assert_eq!(report.points.len(), 1);
+        let point = &report.points[0];
+        assert_eq!(point.trade_id, oversized.trade.id);
+        assert!((point.account_equity - 10200.0).abs() < 0.01);
+        assert!((point.allowed_risk - 102.0).abs() < 0.01);
+        assert!((point.actual_risk - 200.0).abs() < 0.01);
+        assert!(point.oversized);
+    }
+
+    #[tokio::test]
+    async fn test_options_premium_report_splits_sold_and_bought() {
+        use crate::models::{AssetClass, OptionOutcome};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let option = |direction, entry, exit: Option<f64>, qty| {
+            let mut input = create_trade_input(&account_id, day(2), entry, exit.unwrap_or(entry), qty, 0.0);
+            input.symbol = "AAPL240119P00150000".to_string();
+            input.asset_class = Some(AssetClass::Option);
+            input.direction = direction;
+            if exit.is_none() {
+                input.exit_price = None;
+                input.status = Some(Status::Open);
+            }
+            input
+        };
+
+        // Sold: +390 expired, -200 assigned, one still open; bought: +100
+        let expired = TradeService::create_trade(&pool, &user_id, option(Direction::Short, 2.0, Some(0.05), 2.0)).await.unwrap();
+        let assigned = TradeService::create_trade(&pool, &user_id, option(Direction::Short, 3.0, Some(5.0), 1.0)).await.unwrap();
+        TradeService::create_trade(&pool, &user_id, option(Direction::Short, 1.5, None, 1.0)).await.unwrap();
+        TradeService::create_trade(&pool, &user_id, option(Direction::Long, 1.0, Some(2.0), 1.0)).await.unwrap();
+        let stock = TradeService::create_trade(&pool, &user_id, create_trade_input(&account_id, day(3), 100.0, 110.0, 10.0, 0.0))
+            .await
+            .unwrap();
+
+        TradeService::set_option_outcome(&pool, &user_id, &expired.trade.id, Some("expired".to_string())).await.unwrap();
+        let assigned = TradeService::set_option_outcome(&pool, &user_id, &assigned.trade.id, Some("assigned".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(assigned.trade.option_outcome, Some(OptionOutcome::Assigned));
+        let result = TradeService::set_option_outcome(&pool, &user_id, &stock.trade.id, Some("assigned".to_string())).await;
+        assert!(result.is_err());
+
+        let report = MetricsService::get_options_premium_report(&pool, &user_id, None, None, None).await.unwrap();
+
+        let sold = &report.sold;
+        assert_eq!(sold.performance.trade_count, 2);
+        assert_eq!(sold.performance.win_rate, Some(0.5));
+        assert!((sold.performance.net_pnl - 190.0).abs() < 0.01);
+        assert_eq!(sold.open_count, 1);
+        assert_eq!(sold.expired_count, 1);
+        assert_eq!(sold.assigned_count, 1);
+        assert_eq!(sold.assignment_rate, Some(0.5));
+        assert!((sold.premium - 850.0).abs() < 0.01);
+
+        let bought = &report.bought;
+        assert_eq!(bought.performance.trade_count, 1);
+        assert!((bought.performance.net_pnl - 100.0).abs() < 0.01);
+        assert_eq!(bought.assignment_rate, Some(0.0));
+        assert!((report.net_premium - 750.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_margin_utilization_uses_peak_of_overlapping_positions() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        SettingsService::save_manual_trade_timezone(&pool, "UTC").await.unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let trade = |date, symbol: &str, entry, exit, qty, entry_time: &str, exit_time: &str| {
+            let mut input = create_trade_input(&account_id, date, entry, exit, qty, 0.0);
+            input.symbol = symbol.to_string();
+            input.entry_time = Some(entry_time.to_string());
+            input.exit_time = Some(exit_time.to_string());
+            input
+        };
+
+        // Day 1 overlaps from 10:00 to 10:30: 10,000 + 12,000 notional at 25% margin
+        TradeService::create_trade(&pool, &user_id, trade(day(1), "AAPL", 100.0, 104.0, 100.0, "09:30", "10:30")).await.unwrap();
+        TradeService::create_trade(&pool, &user_id, trade(day(1), "MSFT", 200.0, 200.0, 60.0, "10:00", "11:00")).await.unwrap();
+        // Day 2 closes AAPL at the minute MSFT opens, so they never overlap
+        TradeService::create_trade(&pool, &user_id, trade(day(2), "AAPL", 100.0, 100.0, 100.0, "09:30", "10:00")).await.unwrap();
+        TradeService::create_trade(&pool, &user_id, trade(day(2), "MSFT", 200.0, 200.0, 60.0, "10:00", "11:00")).await.unwrap();
+        TradeService::set_instrument_margin_requirement(&pool, "AAPL", Some(0.25)).await.unwrap();
+        TradeService::set_instrument_margin_requirement(&pool, "MSFT", Some(0.25)).await.unwrap();
+        assert!(TradeService::set_instrument_margin_requirement(&pool, "MSFT", Some(1.5)).await.is_err());
+
+        let report = MetricsService::get_margin_utilization(&pool, &user_id, None, day(1), day(3)).await.unwrap();
+        assert!(report.days.is_empty());
+        assert_eq!(report.skipped_no_balance, 2);
+
+        AccountRepository::set_starting_balance(&pool, &user_id, &account_id, Some(10000.0))
+            .await
+            .unwrap();
+        let report = MetricsService::get_margin_utilization(&pool, &user_id, None, day(1), day(3)).await.unwrap();
+        assert_eq!(report.max_utilization_pct, 50.0);
+        assert_eq!(report.days.len(), 2);
+        assert_eq!(report.over_threshold_count, 1);
+
+        let first = &report.days[0];
+        assert!((first.peak_margin - 5500.0).abs() < 0.01);
+        assert!((first.peak_notional - 22000.0).abs() < 0.01);
+        assert!((first.utilization_pct - 55.0).abs() < 0.01);
+        assert!((first.leverage - 2.2).abs() < 0.01);
+        assert!(first.over_threshold);
+
+        // Day 1 AAPL made +400
+        let second = &report.days[1];
+        assert!((second.account_equity - 10400.0).abs() < 0.01);
+        assert!((second.peak_margin - 3000.0).abs() < 0.01);
+        assert!(!second.over_threshold);
+
+        SettingsService::save_max_margin_utilization_pct(&pool, 25.0).await.unwrap();
+        assert!(SettingsService::save_max_margin_utilization_pct(&pool, 0.0).await.is_err());
+        let report = MetricsService::get_margin_utilization(&pool, &user_id, None, day(1), day(3)).await.unwrap();
+        assert_eq!(report.over_threshold_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_account_performance_and_reconciliation_include_financing_charges() {
+        use crate::models::{CreateFinancingChargeInput, FinancingChargeType};
+        use crate::services::AccountService;
+        use crate::test_utils::create_test_trade_input;
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        AccountRepository::set_starting_balance(&pool, &user_id, &account_id, Some(10000.0))
+            .await
+            .unwrap();
+
+        // +490 net on 2024-01-15
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+
+        let manual = AccountService::add_financing_charge(
+            &pool,
+            &user_id,
+            CreateFinancingChargeInput {
+                account_id: account_id.clone(),
+                charge_month: NaiveDate::from_ymd_opt(2024, 1, 20).unwrap(),
+                charge_type: FinancingChargeType::MarginInterest,
+                amount: 20.0,
+                notes: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(manual.charge_month, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+
+        let csv = "month,type,amount,notes\n\
+                   2024-01,financing,15,Borrow fee\n\
+                   2024-02-29,margin_interest,12.5,\n\
+                   2024-13,financing,1,\n\
+                   2024-02,dividend,1,\n";
+        let result = AccountService::import_financing_csv(&pool, &user_id, &account_id, csv).await.unwrap();
+        assert_eq!((result.imported, result.replaced, result.errors.len()), (2, 0, 2));
+
+        // The corrected statement replaces the earlier import but keeps the manual charge
+        let result = AccountService::import_financing_csv(&pool, &user_id, &account_id, "2024-01,financing,10")
+            .await
+            .unwrap();
+        assert_eq!((result.imported, result.replaced), (1, 1));
+        let charges = AccountService::get_financing_charges(&pool, &user_id, Some(&account_id), None, None)
+            .await
+            .unwrap();
+        assert_eq!(charges.len(), 3);
+
+        let performance = MetricsService::get_account_performance(&pool, &user_id, &account_id, None, None)
+            .await
+            .unwrap();
+        assert!((performance.trade_net_pnl - 490.0).abs() < 0.001);
+        assert!((performance.financing_costs - 42.5).abs() < 0.001);
+        assert!((performance.net_pnl - 447.5).abs() < 0.001);
+        assert!((performance.return_pct.unwrap() - 4.475).abs() < 0.001);
+        assert_eq!(performance.months.len(), 2);
+        assert!((performance.months[0].net_pnl - 460.0).abs() < 0.001);
+
+        // Per-trade stats are unaffected
+        let metrics = MetricsService::get_all_time_metrics(&pool, &user_id, Some(&account_id)).await.unwrap();
+        assert!((metrics.total_net_pnl - 490.0).abs() < 0.001);
+
+        let report = MetricsService::reconcile(
+            &pool,
+            &user_id,
+            &account_id,
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            StatementTotals { net_pnl: 460.0, fees: None, trade_count: Some(1) },
+        )
+        .await
+        .unwrap();
+        assert!(report.is_reconciled);
+        assert!((report.journal_financing - 30.0).abs() < 0.001);
+
+        AccountService::lock_period(&pool, &user_id, &account_id, NaiveDate::from_ymd_opt(2024, 1, 10).unwrap())
+            .await
+            .unwrap();
+        assert!(AccountService::delete_financing_charge(&pool, &user_id, &manual.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_account_returns_separate_deposits_from_performance() {
+        use crate::models::CreateCashTransactionInput;
+        use crate::services::AccountService;
+        use crate::test_utils::create_test_trade_input;
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        AccountRepository::set_starting_balance(&pool, &user_id, &account_id, Some(10000.0))
+            .await
+            .unwrap();
+
+        // +490 before and after a deposit that doubles the account
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+        AccountService::add_cash_transaction(
+            &pool,
+            &user_id,
+            CreateCashTransactionInput {
+                account_id: account_id.clone(),
+                transaction_date: day(16),
+                amount: 10000.0,
+                notes: Some("Top-up".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        let mut later = create_test_trade_input(&account_id, "MSFT");
+        later.trade_date = day(17);
+        TradeService::create_trade(&pool, &user_id, later).await.unwrap();
+
+        let returns = MetricsService::get_account_returns(&pool, &user_id, &account_id, day(1), day(31))
+            .await
+            .unwrap();
+        assert!((returns.start_value - 10000.0).abs() < 0.001);
+        assert!((returns.end_value - 20980.0).abs() < 0.001);
+        assert!((returns.net_flows - 10000.0).abs() < 0.001);
+        assert_eq!(returns.days.len(), 3);
+
+        let expected_twr = (1.049 * (1.0 + 490.0 / 20490.0) - 1.0) * 100.0;
+        assert!((returns.twr_pct.unwrap() - expected_twr).abs() < 0.0001);
+        // The deposit missed the better half of the month
+        let mwr = returns.mwr_pct.unwrap();
+        assert!(mwr > 0.0 && mwr < expected_twr);
+
+        // Later periods start from the value including earlier flows
+        let returns = MetricsService::get_account_returns(&pool, &user_id, &account_id, day(17), day(31))
+            .await
+            .unwrap();
+        assert!((returns.start_value - 20490.0).abs() < 0.001);
+        assert!((returns.net_flows).abs() < 0.001);
+
+        AccountService::lock_period(&pool, &user_id, &account_id, day(31)).await.unwrap();
+        let locked = AccountService::add_cash_transaction(
+            &pool,
+            &user_id,
+            CreateCashTransactionInput {
+                account_id: account_id.clone(),
+                transaction_date: day(20),
+                amount: -500.0,
+                notes: None,
+            },
+        )
+        .await;
+        assert!(locked.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_across_accounts_use_reporting_currency() {
+        use crate::models::{FxRate, FxRateSource};
+        use crate::test_utils::create_test_trade_input;
+
+        let pool = create_test_db().await;
+        let (user_id, usd_account) = setup_test_user_and_account(&pool).await;
+        let eur_account = AccountRepository::create(&pool, &user_id, "EUR Account", Some("EUR"))
+            .await
+            .unwrap()
+            .id;
+
+        // +490 in each account's own currency
+        for account_id in [&usd_account, &eur_account] {
+            TradeService::create_trade(&pool, &user_id, create_test_trade_input(account_id, "AAPL"))
+                .await
+                .unwrap();
+        }
+
+        let metrics = MetricsService::get_all_time_metrics(&pool, &user_id, None).await.unwrap();
+        assert!((metrics.total_net_pnl - 980.0).abs() < 0.001);
+
+        FxService::save_rates(
+            &pool,
+            vec![
+                FxRate {
+                    base_currency: "eur".to_string(),
+                    quote_currency: "usd".to_string(),
+                    date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+                    rate: 1.10,
+                    source: FxRateSource::Manual,
+                },
+                FxRate {
+                    base_currency: "EUR".to_string(),
+                    quote_currency: "USD".to_string(),
+                    date: NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+                    rate: 1.50,
+                    source: FxRateSource::Manual,
+                },
+            ],
+        )
+        .await
+        .unwrap();
+        SettingsService::save_reporting_currency(&pool, Some("usd")).await.unwrap();
+        assert_eq!(SettingsService::get_reporting_currency(&pool).await.unwrap().as_deref(), Some("USD"));
+
+        // The EUR trade of 2024-01-15 converts at the rate of 2024-01-02
+        let metrics = MetricsService::get_all_time_metrics(&pool, &user_id, None).await.unwrap();
+        assert!((metrics.total_net_pnl - 1029.0).abs() < 0.001);
+        let curve = MetricsService::get_equity_curve(
+            &pool,
+            &user_id,
+            None,
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+        )
+        .await
+        .unwrap();
+        assert!((curve.last().unwrap().cumulative_pnl - 1029.0).abs() < 0.001);
+
+        // A single account stays in its own currency
+        let metrics = MetricsService::get_all_time_metrics(&pool, &user_id, Some(&eur_account)).await.unwrap();
+        assert!((metrics.total_net_pnl - 490.0).abs() < 0.001);
+
+        SettingsService::save_reporting_currency(&pool, Some("GBP")).await.unwrap();
+        let err = MetricsService::get_all_time_metrics(&pool, &user_id, None).await.unwrap_err();
+        assert!(err.contains("No FX rate from USD to GBP") || err.contains("No FX rate from EUR to GBP"));
+
+        SettingsService::save_reporting_currency(&pool, None).await.unwrap();
+        assert!(SettingsService::save_reporting_currency(&pool, Some("dollars")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unrealized_pnl_converts_closes_per_account_currency() {
+        use crate::models::{FxRate, FxRateSource};
+
+        let pool = create_test_db().await;
+        let (user_id, usd_account) = setup_test_user_and_account(&pool).await;
+        let eur_account = AccountRepository::create(&pool, &user_id, "EUR Account", Some("EUR"))
+            .await
+            .unwrap()
+            .id;
+        let day = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        // 10 AAPL held at 100 in each account, closing at 102
+        for account_id in [&usd_account, &eur_account] {
+            let input = CreateTradeInput {
+                exit_price: None,
+                status: Some(Status::Open),
+                ..create_trade_input(account_id, day, 100.0, 0.0, 10.0, 0.0)
+            };
+            TradeService::create_trade(&pool, &user_id, input).await.unwrap();
+        }
+        MetricsService::save_daily_closes(
+            &pool,
+            vec![DailyClose { symbol: "AAPL".to_string(), date: day, close: 102.0, high: None, low: None }],
+        )
+        .await
+        .unwrap();
+        FxService::save_rates(
+            &pool,
+            vec![FxRate {
+                base_currency: "EUR".to_string(),
+                quote_currency: "USD".to_string(),
+                date: day,
+                rate: 1.10,
+                source: FxRateSource::Manual,
+            }],
+        )
+        .await
+        .unwrap();
+        SettingsService::save_reporting_currency(&pool, Some("USD")).await.unwrap();
+
+        // 20 USD plus 20 EUR at 1.10
+        let daily = MetricsService::get_daily_performance(&pool, &user_id, None, day, day, true)
+            .await
+            .unwrap();
+        assert_eq!(daily.len(), 1);
+        assert!((daily[0].unrealized_pnl_change.unwrap() - 42.0).abs() < 0.001);
+    }
+
+    #[tokio::test]
+    async fn test_short_side_report_compares_costs_and_days_held() {
+        use crate::models::{CarryingCostType, CreateCarryingCostInput, ExitExecution};
+        use crate::services::CarryingCostService;
+        use crate::test_utils::create_test_trade_input;
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // Long: $15,000 notional, $10 fees
+        let long = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+
+        // Short: $2,000 notional, $10 fees plus a $25 borrow fee, covered three days later
+        let mut short = create_test_trade_input(&account_id, "GME");
+        short.direction = Direction::Short;
+        short.entry_price = 20.0;
+        short.stop_loss_price = Some(22.0);
+        short.exit_price = None;
+        short.exits = Some(vec![ExitExecution {
+            id: None,
+            exit_date: NaiveDate::from_ymd_opt(2024, 1, 18).unwrap(),
+            exit_time: None,
+            quantity: 100.0,
+            price: 18.0,
+            fees: None,
+        }]);
+        let short = TradeService::create_trade(&pool, &user_id, short).await.unwrap();
+        CarryingCostService::add_cost(
+            &pool,
+            &user_id,
+            CreateCarryingCostInput {
+                trade_id: short.trade.id.clone(),
+                cost_type: CarryingCostType::BorrowFee,
+                cost_date: NaiveDate::from_ymd_opt(2024, 1, 16).unwrap(),
+                amount: 25.0,
+                notes: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let short = TradeService::set_borrow_availability(
+            &pool,
+            &user_id,
+            &short.trade.id,
+            Some("hard_to_borrow".to_string()),
+            Some("Locate at 35% annualized".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(short.trade.borrow_note.as_deref(), Some("Locate at 35% annualized"));
+        let result = TradeService::set_borrow_availability(&pool, &user_id, &long.trade.id, Some("easy".to_string()), None).await;
+        assert!(result.is_err());
+
+        let report = MetricsService::get_short_side_report(&pool, &user_id, None, None, None).await.unwrap();
+        assert!((report.short.performance.net_pnl - 165.0).abs() < 0.001);
+        assert!((report.short.total_carrying_costs - 25.0).abs() < 0.001);
+        assert!((report.short.cost_pct_of_notional.unwrap() - 1.75).abs() < 0.001);
+        assert!((report.long.cost_per_trade.unwrap() - 10.0).abs() < 0.001);
+        let premium = 1.75 - 10.0 / 15000.0 * 100.0;
+        assert!((report.short_cost_premium_pct.unwrap() - premium).abs() < 0.001);
+
+        let held: Vec<(&str, i32)> = report
+            .short_holding_days
+            .iter()
+            .filter(|b| b.trade_count > 0)
+            .map(|b| (b.label.as_str(), b.trade_count))
+            .collect();
+        assert_eq!(held, vec![("3-5 days", 1)]);
+        assert_eq!(report.by_borrow_availability.len(), 1);
+        assert_eq!(report.by_borrow_availability[0].key, "hard_to_borrow");
+    }
+
+    #[tokio::test]
+    async fn test_futures_session_report_splits_contract_months_and_sessions() {
+        use crate::models::{AssetClass, CreateTradeInput};
+        use crate::test_utils::create_test_trade_input;
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        SettingsService::save_manual_trade_timezone(&pool, "America/Chicago").await.unwrap();
+
+        let future = |symbol: &str, entry_time: Option<&str>| CreateTradeInput {
+            asset_class: Some(AssetClass::Future),
+            quantity: Some(1.0),
+            entry_time: entry_time.map(str::to_string),
+            exit_time: None,
+            ..create_test_trade_input(&account_id, symbol)
+        };
+        // 09:00 CT is within ES regular hours; 19:00 CT is Globex overnight, the next day in UTC
+        // 07:30 CT is before the CL open
+        for input in [
+            future("ESH4", Some("09:00")),
+            future("ESH4", Some("19:00")),
+            future("CLG4", Some("07:30")),
+            future("ES", None),
+            create_test_trade_input(&account_id, "AAPL"),
+        ] {
+            TradeService::create_trade(&pool, &user_id, input).await.unwrap();
+        }
+
+        let report = MetricsService::get_futures_session_report(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(report.trade_count, 4);
+        let months: Vec<(&str, i32)> = report.by_contract_month.iter().map(|b| (b.key.as_str(), b.trade_count)).collect();
+        assert_eq!(months, vec![("2024-02", 1), ("2024-03", 2), ("unknown", 1)]);
+        let sessions: Vec<(&str, i32)> = report.by_session.iter().map(|b| (b.key.as_str(), b.trade_count)).collect();
+        assert_eq!(sessions, vec![("overnight", 2), ("rth", 1), ("unknown", 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_crypto_tax_report_matches_lots_by_the_configured_method() {
+        use crate::models::{AssetClass, CostBasisMethod, CreateTradeInput, ExitExecution};
+        use crate::test_utils::create_open_trade;
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let day = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        // 1 BTC held since 2023 at 100, another bought at 300 and sold at 250 in 2024
+        let crypto = |date, price| CreateTradeInput {
+            asset_class: Some(AssetClass::Crypto),
+            ..create_open_trade(&account_id, "BTC", date, price, 1.0)
+        };
+        let held = TradeService::create_trade(&pool, &user_id, crypto(day(2023, 1, 2), 100.0)).await.unwrap();
+        let sold = TradeService::create_trade(
+            &pool,
+            &user_id,
+            CreateTradeInput {
+                exits: Some(vec![ExitExecution {
+                    id: None,
+                    exit_date: day(2024, 6, 3),
+                    exit_time: None,
+                    quantity: 1.0,
+                    price: 250.0,
+                    fees: None,
+                }]),
+                ..crypto(day(2024, 3, 1), 300.0)
+            },
+        )
+        .await
+        .unwrap();
+
+        // FIFO by default: the disposal uses the 2023 lot, held for over a year
+        assert_eq!(SettingsService::get_crypto_cost_basis_method(&pool).await.unwrap(), CostBasisMethod::Fifo);
+        let report = MetricsService::get_crypto_tax_report(&pool, &user_id, None, Some(day(2024, 1, 1)), None).await.unwrap();
+        assert_eq!(report.disposals.len(), 1);
+        assert_eq!(report.disposals[0].trade_id, sold.trade.id);
+        assert_eq!(report.disposals[0].lot_trade_id, held.trade.id);
+        assert!((report.long_term_gain - 150.0).abs() < 1e-9);
+        assert_eq!(report.short_term_gain, 0.0);
+
+        // LIFO uses the lot the trade bought, at a short-term loss
+        SettingsService::save_crypto_cost_basis_method(&pool, CostBasisMethod::Lifo).await.unwrap();
+        let report = MetricsService::get_crypto_tax_report(&pool, &user_id, None, Some(day(2024, 1, 1)), None).await.unwrap();
+        assert_eq!(report.method, CostBasisMethod::Lifo);
+        assert_eq!(report.disposals[0].lot_trade_id, sold.trade.id);
+        assert!((report.short_term_gain + 50.0).abs() < 1e-9);
+        assert!((report.proceeds - 250.0).abs() < 1e-9);
+
+        // Disposals before the start date are left out
+        let report = MetricsService::get_crypto_tax_report(&pool, &user_id, None, Some(day(2025, 1, 1)), None).await.unwrap();
+        assert!(report.disposals.is_empty());
+    }
 }
@@ -3,6 +3,44 @@ pub mod metrics_service;
 pub mod import_service;
 pub mod market_data_service;
 pub mod settings_service;
+pub mod carrying_cost_service;
+pub mod dividend_service;
+pub mod account_service;
+pub mod export_service;
+pub mod checklist_service;
+pub mod market_event_service;
+pub mod day_condition_service;
+pub mod goal_service;
+pub mod strategy_rule_service;
+pub mod stop_adjustment_service;
+pub mod position_roll_service;
+pub mod fx_service;
+pub mod reminder_service;
+pub mod journal_service;
+pub mod archive_service;
+pub mod maintenance_service;
+pub mod daily_performance_service;
+pub mod metric_snapshot_service;
+pub mod watch_folder_service;
 
 pub use trade_service::TradeService;
 pub use metrics_service::MetricsService;
+pub use carrying_cost_service::CarryingCostService;
+pub use dividend_service::DividendService;
+pub use account_service::AccountService;
+pub use export_service::ExportService;
+pub use checklist_service::ChecklistService;
+pub use market_event_service::MarketEventService;
+pub use day_condition_service::DayConditionService;
+pub use goal_service::GoalService;
+pub use strategy_rule_service::StrategyRuleService;
+pub use stop_adjustment_service::StopAdjustmentService;
+pub use position_roll_service::PositionRollService;
+pub use fx_service::FxService;
+pub use reminder_service::ReminderService;
+pub use journal_service::JournalService;
+pub use archive_service::ArchiveService;
+pub use maintenance_service::MaintenanceService;
+pub use daily_performance_service::DailyPerformanceService;
+pub use metric_snapshot_service::MetricSnapshotService;
+pub use watch_folder_service::WatchFolderService;
@@ -0,0 +1,206 @@
+use chrono::NaiveDate;
+use sqlx::sqlite::SqlitePool;
+use crate::models::{RollPositionInput, Status, TradeFilter, TradeWithDerived};
+use crate::repository::{AccountRepository, TradeRepository};
+use crate::services::{DailyPerformanceService, TradeService};
+
+pub struct PositionRollService;
+
+impl PositionRollService {
+    /// Open positions as of a period end that have not been rolled or transferred
+    pub async fn get_unresolved_positions(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        period_end: NaiveDate,
+    ) -> Result<Vec<TradeWithDerived>, String> {
+        let filter = TradeFilter {
+            account_id: account_id.map(str::to_string),
+            end_date: Some(period_end),
+            status: Some(Status::Open),
+            ..Default::default()
+        };
+        let trades = TradeService::find_trades(pool, user_id, &filter).await?;
+
+        Ok(trades.into_iter().filter(|t| t.trade.roll_type.is_none()).collect())
+    }
+
+    /// Mark an open position as rolled or transferred into a continuation trade
+    /// The position is closed without a realized exit; its exposure ends the day before the roll.
+    pub async fn roll_position(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: RollPositionInput,
+    ) -> Result<TradeWithDerived, String> {
+        let trade = TradeService::get_trade(pool, user_id, &input.trade_id)
+            .await?
+            .ok_or_else(|| format!("Trade not found: {}", input.trade_id))?
+            .trade;
+
+        if trade.status != Status::Open {
+            return Err("Only open positions can be rolled or transferred".to_string());
+        }
+        if input.rolled_on < trade.trade_date {
+            return Err(format!("Roll date cannot be before the trade date {}", trade.trade_date));
+        }
+        Self::ensure_roll_unlocked(pool, &trade.account_id, input.rolled_on).await?;
+
+        if let Some(ref continuation_id) = input.continuation_trade_id {
+            if continuation_id == &trade.id {
+                return Err("A position cannot continue into itself".to_string());
+            }
+            TradeService::get_trade(pool, user_id, continuation_id)
+                .await?
+                .ok_or_else(|| format!("Continuation trade not found: {}", continuation_id))?;
+        }
+
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        TradeRepository::set_roll(
+            &mut tx,
+            &trade.id,
+            Some(input.roll_type),
+            Some(input.rolled_on),
+            input.continuation_trade_id.as_deref(),
+            Status::Closed,
+        )
+        .await
+        .map_err(|e| format!("Failed to roll position: {}", e))?;
+        DailyPerformanceService::refresh_trades(&mut tx, std::slice::from_ref(&trade.id), Vec::new()).await?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+
+        TradeService::get_trade(pool, user_id, &trade.id)
+            .await?
+            .ok_or_else(|| format!("Trade not found: {}", trade.id))
+    }
+
+    /// Undo a roll, reopening the position unless its exits cover its quantity
+    pub async fn clear_roll(
+        pool: &SqlitePool,
+        user_id: &str,
+        trade_id: &str,
+    ) -> Result<TradeWithDerived, String> {
+        let trade = TradeService::get_trade(pool, user_id, trade_id)
+            .await?
+            .ok_or_else(|| format!("Trade not found: {}", trade_id))?
+            .trade;
+
+        if let Some(rolled_on) = trade.rolled_on {
+            Self::ensure_roll_unlocked(pool, &trade.account_id, rolled_on).await?;
+        }
+        let exited: f64 = TradeRepository::get_executions(pool, trade_id)
+            .await
+            .map_err(|e| format!("Failed to get trade executions: {}", e))?
+            .iter()
+            .filter(|e| e.execution_type == "exit")
+            .map(|e| e.quantity)
+            .sum();
+        let status = match trade.quantity {
+            Some(quantity) if exited + 1e-9 >= quantity => Status::Closed,
+            _ => Status::Open,
+        };
+
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        TradeRepository::set_roll(&mut tx, trade_id, None, None, None, status)
+            .await
+            .map_err(|e| format!("Failed to clear roll: {}", e))?;
+        DailyPerformanceService::refresh_trades(&mut tx, &[trade_id.to_string()], Vec::new()).await?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+
+        TradeService::get_trade(pool, user_id, trade_id)
+            .await?
+            .ok_or_else(|| format!("Trade not found: {}", trade_id))
+    }
+
+    /// A roll changes exposure from its date on, so that date must be outside the locked period
+    async fn ensure_roll_unlocked(
+        pool: &SqlitePool,
+        account_id: &str,
+        rolled_on: NaiveDate,
+    ) -> Result<(), String> {
+        let lock = AccountRepository::get_period_lock(pool, account_id)
+            .await
+            .map_err(|e| format!("Failed to check period lock: {}", e))?;
+
+        match lock {
+            Some(lock) if rolled_on <= lock.locked_through => Err(format!(
+                "Roll date {} is in a locked period (locked through {}). Unlock the period to make changes.",
+                rolled_on, lock.locked_through
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RollType;
+    use crate::services::AccountService;
+    use crate::test_utils::{create_open_trade, create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    #[tokio::test]
+    async fn test_roll_position_links_continuation_and_resolves_it() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+
+        let front = TradeService::create_trade(&pool, &user_id, create_open_trade(&account_id, "ESH4", day(2), 4800.0, 1.0))
+            .await
+            .unwrap();
+        let closed = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+
+        let unresolved = PositionRollService::get_unresolved_positions(&pool, &user_id, None, day(31))
+            .await
+            .unwrap();
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].trade.id, front.trade.id);
+
+        let back = TradeService::create_trade(&pool, &user_id, create_open_trade(&account_id, "ESM4", day(29), 4850.0, 1.0))
+            .await
+            .unwrap();
+        let roll = |trade_id: &str, rolled_on| RollPositionInput {
+            trade_id: trade_id.to_string(),
+            roll_type: RollType::Rolled,
+            rolled_on,
+            continuation_trade_id: Some(back.trade.id.clone()),
+        };
+
+        // Closed trades and dates before entry are rejected
+        assert!(PositionRollService::roll_position(&pool, &user_id, roll(&closed.trade.id, day(29))).await.is_err());
+        assert!(PositionRollService::roll_position(&pool, &user_id, roll(&front.trade.id, day(1))).await.is_err());
+
+        let rolled = PositionRollService::roll_position(&pool, &user_id, roll(&front.trade.id, day(29)))
+            .await
+            .unwrap();
+        assert_eq!(rolled.trade.roll_type, Some(RollType::Rolled));
+        assert_eq!(rolled.trade.rolled_on, Some(day(29)));
+        assert_eq!(rolled.trade.continuation_trade_id.as_deref(), Some(back.trade.id.as_str()));
+        assert_eq!(rolled.trade.status, Status::Closed);
+        assert!(PositionRollService::roll_position(&pool, &user_id, roll(&front.trade.id, day(30))).await.is_err());
+
+        // Only the continuation is left to close out
+        let unresolved = PositionRollService::get_unresolved_positions(&pool, &user_id, None, day(31))
+            .await
+            .unwrap();
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].trade.id, back.trade.id);
+
+        // Deleting the continuation keeps the roll but drops the link
+        TradeService::delete_trade(&pool, &user_id, &back.trade.id).await.unwrap();
+        let orphaned = TradeService::get_trade(&pool, &user_id, &front.trade.id).await.unwrap().unwrap();
+        assert_eq!(orphaned.trade.roll_type, Some(RollType::Rolled));
+        assert_eq!(orphaned.trade.continuation_trade_id, None);
+
+        // Rolls inside a locked period cannot be undone
+        AccountService::lock_period(&pool, &user_id, &account_id, day(30)).await.unwrap();
+        assert!(PositionRollService::clear_roll(&pool, &user_id, &front.trade.id).await.is_err());
+        AccountService::unlock_period(&pool, &user_id, &account_id).await.unwrap();
+
+        let cleared = PositionRollService::clear_roll(&pool, &user_id, &front.trade.id).await.unwrap();
+        assert_eq!(cleared.trade.roll_type, None);
+        assert_eq!(cleared.trade.rolled_on, None);
+        assert_eq!(cleared.trade.status, Status::Open);
+    }
+}
@@ -0,0 +1,67 @@
+use chrono::NaiveDateTime;
+use sqlx::sqlite::SqlitePool;
+use crate::calculations::due_reminders;
+use crate::models::DueReminder;
+use crate::services::settings_service::SettingsService;
+
+pub struct ReminderService;
+
+impl ReminderService {
+    /// Reminders due at `now`, marked as fired so the next check does not repeat them
+    pub async fn take_due(pool: &SqlitePool, now: NaiveDateTime) -> Result<Vec<DueReminder>, String> {
+        let reminders = SettingsService::get_reminders(pool).await?;
+        let mut last_fired = SettingsService::get_reminders_last_fired(pool).await?;
+
+        let due = due_reminders(&reminders, &last_fired, now);
+        if !due.is_empty() {
+            for reminder in &due {
+                last_fired.insert(reminder.kind, now);
+            }
+            SettingsService::save_reminders_last_fired(pool, &last_fired).await?;
+        }
+
+        Ok(due)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crate::models::ReminderKind;
+    use crate::test_utils::create_test_db;
+
+    #[tokio::test]
+    async fn test_take_due_persists_schedule_and_fires_once() {
+        let pool = create_test_db().await;
+
+        // Nothing is enabled out of the box
+        let mut reminders = SettingsService::get_reminders(&pool).await.unwrap();
+        assert_eq!(reminders.len(), 3);
+        assert!(reminders.iter().all(|r| !r.enabled));
+
+        let review = reminders.iter_mut().find(|r| r.kind == ReminderKind::WeeklyReview).unwrap();
+        review.enabled = true;
+        review.time = "09:30".to_string();
+        SettingsService::save_reminders(&pool, &reminders).await.unwrap();
+
+        // Sunday Jan 21 2024
+        let sunday = NaiveDate::from_ymd_opt(2024, 1, 21).unwrap();
+        let due = ReminderService::take_due(&pool, sunday.and_hms_opt(9, 0, 0).unwrap()).await.unwrap();
+        assert!(due.is_empty());
+
+        let due = ReminderService::take_due(&pool, sunday.and_hms_opt(10, 0, 0).unwrap()).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].kind, ReminderKind::WeeklyReview);
+        assert_eq!(due[0].scheduled_for, sunday.and_hms_opt(9, 30, 0).unwrap());
+
+        let due = ReminderService::take_due(&pool, sunday.and_hms_opt(10, 1, 0).unwrap()).await.unwrap();
+        assert!(due.is_empty());
+
+        reminders[0].time = "25:00".to_string();
+        assert!(SettingsService::save_reminders(&pool, &reminders).await.is_err());
+        reminders[0].time = "20:00".to_string();
+        reminders[0].weekdays = vec![7];
+        assert!(SettingsService::save_reminders(&pool, &reminders).await.is_err());
+    }
+}
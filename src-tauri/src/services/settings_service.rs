@@ -1,13 +1,44 @@
 use serde::Serialize;
-use sqlx::sqlite::SqlitePool;
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
 use sqlx::Row;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use chrono_tz::Tz;
+use std::collections::HashMap;
 use std::str::FromStr;
+use crate::models::{CostBasisMethod, DateAttribution, MaintenanceReport, ProcessGoals, Reminder, ReminderKind, ResultBasis, TradeTypeThresholds, WatchFolder};
+use crate::repository::DailyPerformanceRepository;
 
 const KEY_ALPACA_API_KEY_ID: &str = "alpaca_api_key_id";
 const KEY_ALPACA_API_SECRET_KEY: &str = "alpaca_api_secret_key";
 const KEY_MANUAL_TRADE_TIMEZONE: &str = "manual_trade_timezone";
 const DEFAULT_MANUAL_TRADE_TIMEZONE: &str = "Europe/Amsterdam";
+// Also read directly by the trade queries in trade_repo
+const KEY_INCLUDE_DIVIDENDS_IN_PNL: &str = "include_dividends_in_pnl";
+const KEY_INCLUDE_PAPER_TRADES: &str = "include_paper_trades";
+const KEY_RESULT_BASIS: &str = "result_basis";
+const KEY_DATE_ATTRIBUTION: &str = "date_attribution";
+const KEY_CRYPTO_COST_BASIS_METHOD: &str = "crypto_cost_basis_method";
+const KEY_TRADE_TYPE_SCALP_MAX_MINUTES: &str = "trade_type_scalp_max_minutes";
+const KEY_TRADE_TYPE_SWING_MAX_DAYS: &str = "trade_type_swing_max_days";
+const KEY_DEFAULT_RISK_PER_TRADE: &str = "default_risk_per_trade";
+const KEY_ACCOUNT_RISK_PCT: &str = "account_risk_pct";
+const KEY_MAX_TRADES_PER_SYMBOL_PER_DAY: &str = "max_trades_per_symbol_per_day";
+const DEFAULT_MAX_TRADES_PER_SYMBOL_PER_DAY: i32 = 5;
+const KEY_MAX_MARGIN_UTILIZATION_PCT: &str = "max_margin_utilization_pct";
+const DEFAULT_MAX_MARGIN_UTILIZATION_PCT: f64 = 50.0;
+const KEY_REPORTING_CURRENCY: &str = "reporting_currency";
+const KEY_GOAL_MAX_TRADES_PER_DAY: &str = "goal_max_trades_per_day";
+const KEY_GOAL_JOURNAL_EVERY_DAY: &str = "goal_journal_every_day";
+const KEY_GOAL_MAX_RISK_PER_TRADE: &str = "goal_max_risk_per_trade";
+const KEY_DAILY_LOSS_LIMIT: &str = "daily_loss_limit";
+const KEY_REMINDERS: &str = "reminders";
+const KEY_REMINDERS_LAST_FIRED: &str = "reminders_last_fired";
+const KEY_END_OF_DAY_SUMMARY_TIME: &str = "end_of_day_summary_time";
+const DEFAULT_END_OF_DAY_SUMMARY_TIME: &str = "16:00";
+const KEY_END_OF_DAY_SUMMARY_LAST_DATE: &str = "end_of_day_summary_last_date";
+const KEY_MAINTENANCE_INTERVAL_DAYS: &str = "maintenance_interval_days";
+const KEY_LAST_MAINTENANCE_REPORT: &str = "last_maintenance_report";
+const KEY_WATCH_FOLDER: &str = "watch_folder";
 
 #[derive(Debug, Clone, Serialize)]
 pub struct AlpacaKeysStatus {
@@ -16,6 +47,15 @@ pub struct AlpacaKeysStatus {
     pub masked_key_id: Option<String>,
 }
 
+/// Disk usage of the journal database
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageUsage {
+    pub database_bytes: i64,
+    pub trade_count: i64,
+    pub execution_count: i64,
+    pub cached_candle_count: i64,
+}
+
 pub struct SettingsService;
 
 impl SettingsService {
@@ -66,9 +106,366 @@ impl SettingsService {
         Tz::from_str(trimmed).map_err(|_| format!("Invalid IANA timezone: {}", trimmed))?;
         upsert_setting(pool, KEY_MANUAL_TRADE_TIMEZONE, trimmed).await
     }
+
+    pub async fn get_storage_usage(pool: &SqlitePool) -> Result<StorageUsage, String> {
+        let page_count: i64 = sqlx::query_scalar("PRAGMA page_count")
+            .fetch_one(pool)
+            .await
+            .map_err(|e| format!("Failed to read database size: {}", e))?;
+        let page_size: i64 = sqlx::query_scalar("PRAGMA page_size")
+            .fetch_one(pool)
+            .await
+            .map_err(|e| format!("Failed to read database size: {}", e))?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM trades) AS trade_count,
+                (SELECT COUNT(*) FROM trade_executions) AS execution_count,
+                (SELECT COUNT(*) FROM market_candles) AS cached_candle_count
+            "#,
+        )
+        .fetch_one(pool)
+        .await
+        .map_err(|e| format!("Failed to read storage usage: {}", e))?;
+
+        Ok(StorageUsage {
+            database_bytes: page_count * page_size,
+            trade_count: row.get("trade_count"),
+            execution_count: row.get("execution_count"),
+            cached_candle_count: row.get("cached_candle_count"),
+        })
+    }
+
+    pub async fn get_include_dividends_in_pnl(pool: &SqlitePool) -> Result<bool, String> {
+        let value = get_setting(pool, KEY_INCLUDE_DIVIDENDS_IN_PNL).await?;
+        Ok(value.as_deref() == Some("true"))
+    }
+
+    pub async fn save_include_dividends_in_pnl(pool: &SqlitePool, include: bool) -> Result<(), String> {
+        upsert_setting_rebuilding(pool, KEY_INCLUDE_DIVIDENDS_IN_PNL, if include { "true" } else { "false" }).await
+    }
+
+    pub async fn get_include_paper_trades(pool: &SqlitePool) -> Result<bool, String> {
+        let value = get_setting(pool, KEY_INCLUDE_PAPER_TRADES).await?;
+        Ok(value.as_deref() == Some("true"))
+    }
+
+    pub async fn save_include_paper_trades(pool: &SqlitePool, include: bool) -> Result<(), String> {
+        upsert_setting(pool, KEY_INCLUDE_PAPER_TRADES, if include { "true" } else { "false" }).await
+    }
+
+    /// Whether wins and losses are classified by gross or net PnL
+    pub async fn get_result_basis(pool: &SqlitePool) -> Result<ResultBasis, String> {
+        let value = get_setting(pool, KEY_RESULT_BASIS).await?;
+        Ok(value.as_deref().and_then(ResultBasis::from_str).unwrap_or_default())
+    }
+
+    pub async fn save_result_basis(pool: &SqlitePool, basis: ResultBasis) -> Result<(), String> {
+        upsert_setting_rebuilding(pool, KEY_RESULT_BASIS, basis.as_str()).await
+    }
+
+    /// Whether closed trades count toward the day they were entered or exited
+    pub async fn get_date_attribution(pool: &SqlitePool) -> Result<DateAttribution, String> {
+        let value = get_setting(pool, KEY_DATE_ATTRIBUTION).await?;
+        Ok(value.as_deref().and_then(DateAttribution::from_str).unwrap_or_default())
+    }
+
+    pub async fn save_date_attribution(pool: &SqlitePool, attribution: DateAttribution) -> Result<(), String> {
+        upsert_setting_rebuilding(pool, KEY_DATE_ATTRIBUTION, attribution.as_str()).await
+    }
+
+    /// Order in which crypto disposals use up acquired lots in the tax report
+    pub async fn get_crypto_cost_basis_method(pool: &SqlitePool) -> Result<CostBasisMethod, String> {
+        let value = get_setting(pool, KEY_CRYPTO_COST_BASIS_METHOD).await?;
+        Ok(value.as_deref().and_then(CostBasisMethod::from_str).unwrap_or_default())
+    }
+
+    pub async fn save_crypto_cost_basis_method(pool: &SqlitePool, method: CostBasisMethod) -> Result<(), String> {
+        upsert_setting(pool, KEY_CRYPTO_COST_BASIS_METHOD, method.as_str()).await
+    }
+
+    /// Holding durations separating scalps, day trades, swings and positions
+    pub async fn get_trade_type_thresholds(pool: &SqlitePool) -> Result<TradeTypeThresholds, String> {
+        let defaults = TradeTypeThresholds::default();
+        let scalp = get_setting(pool, KEY_TRADE_TYPE_SCALP_MAX_MINUTES).await?;
+        let swing = get_setting(pool, KEY_TRADE_TYPE_SWING_MAX_DAYS).await?;
+
+        Ok(TradeTypeThresholds {
+            scalp_max_minutes: scalp
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(defaults.scalp_max_minutes),
+            swing_max_days: swing
+                .and_then(|v| v.parse::<i64>().ok())
+                .unwrap_or(defaults.swing_max_days),
+        })
+    }
+
+    pub async fn save_trade_type_thresholds(pool: &SqlitePool, thresholds: &TradeTypeThresholds) -> Result<(), String> {
+        if thresholds.scalp_max_minutes <= 0.0 || !thresholds.scalp_max_minutes.is_finite() {
+            return Err("Scalp duration must be positive".to_string());
+        }
+        if thresholds.swing_max_days < 1 {
+            return Err("Swing duration must be at least 1 day".to_string());
+        }
+
+        upsert_setting(pool, KEY_TRADE_TYPE_SCALP_MAX_MINUTES, &thresholds.scalp_max_minutes.to_string()).await?;
+        upsert_setting(pool, KEY_TRADE_TYPE_SWING_MAX_DAYS, &thresholds.swing_max_days.to_string()).await
+    }
+
+    /// Dollar risk assumed for trades without a stop loss in R-based analytics
+    pub async fn get_default_risk_per_trade(pool: &SqlitePool) -> Result<Option<f64>, String> {
+        let value = get_setting(pool, KEY_DEFAULT_RISK_PER_TRADE).await?;
+        Ok(value.and_then(|v| v.parse::<f64>().ok()).filter(|r| *r > 0.0))
+    }
+
+    /// Save the assumed risk per trade; None clears it
+    pub async fn save_default_risk_per_trade(pool: &SqlitePool, risk: Option<f64>) -> Result<(), String> {
+        match risk {
+            Some(r) if r > 0.0 && r.is_finite() => {
+                upsert_setting(pool, KEY_DEFAULT_RISK_PER_TRADE, &r.to_string()).await
+            }
+            Some(_) => Err("Default risk per trade must be positive".to_string()),
+            None => delete_setting(pool, KEY_DEFAULT_RISK_PER_TRADE).await,
+        }
+    }
+
+    /// Percent of account equity a single trade may risk, used by the position sizing audit
+    pub async fn get_account_risk_pct(pool: &SqlitePool) -> Result<Option<f64>, String> {
+        let value = get_setting(pool, KEY_ACCOUNT_RISK_PCT).await?;
+        Ok(value.and_then(|v| v.parse::<f64>().ok()).filter(|p| *p > 0.0))
+    }
+
+    /// Save the account risk percent; None clears it
+    pub async fn save_account_risk_pct(pool: &SqlitePool, risk_pct: Option<f64>) -> Result<(), String> {
+        match risk_pct {
+            Some(p) if p > 0.0 && p <= 100.0 => {
+                upsert_setting(pool, KEY_ACCOUNT_RISK_PCT, &p.to_string()).await
+            }
+            Some(_) => Err("Account risk percent must be greater than 0 and at most 100".to_string()),
+            None => delete_setting(pool, KEY_ACCOUNT_RISK_PCT).await,
+        }
+    }
+
+    /// Round trips in one symbol per day above which a day counts as overtrading
+    pub async fn get_max_trades_per_symbol_per_day(pool: &SqlitePool) -> Result<i32, String> {
+        let value = get_setting(pool, KEY_MAX_TRADES_PER_SYMBOL_PER_DAY).await?;
+        Ok(value
+            .and_then(|v| v.parse::<i32>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_MAX_TRADES_PER_SYMBOL_PER_DAY))
+    }
+
+    pub async fn save_max_trades_per_symbol_per_day(pool: &SqlitePool, max_trades: i32) -> Result<(), String> {
+        if max_trades < 1 {
+            return Err("Max trades per symbol per day must be at least 1".to_string());
+        }
+        upsert_setting(pool, KEY_MAX_TRADES_PER_SYMBOL_PER_DAY, &max_trades.to_string()).await
+    }
+
+    /// Percent of account equity tied up as margin above which a day is flagged
+    pub async fn get_max_margin_utilization_pct(pool: &SqlitePool) -> Result<f64, String> {
+        let value = get_setting(pool, KEY_MAX_MARGIN_UTILIZATION_PCT).await?;
+        Ok(value
+            .and_then(|v| v.parse::<f64>().ok())
+            .filter(|p| *p > 0.0)
+            .unwrap_or(DEFAULT_MAX_MARGIN_UTILIZATION_PCT))
+    }
+
+    pub async fn save_max_margin_utilization_pct(pool: &SqlitePool, max_pct: f64) -> Result<(), String> {
+        if !(max_pct > 0.0 && max_pct <= 100.0) {
+            return Err("Max margin utilization must be greater than 0 and at most 100 percent".to_string());
+        }
+        upsert_setting(pool, KEY_MAX_MARGIN_UTILIZATION_PCT, &max_pct.to_string()).await
+    }
+
+    /// Currency that metrics across accounts are converted to; None keeps each account's own currency
+    pub async fn get_reporting_currency(pool: &SqlitePool) -> Result<Option<String>, String> {
+        get_setting(pool, KEY_REPORTING_CURRENCY).await
+    }
+
+    pub async fn save_reporting_currency(pool: &SqlitePool, currency: Option<&str>) -> Result<(), String> {
+        match currency.map(str::trim).filter(|c| !c.is_empty()) {
+            Some(c) => {
+                if c.len() != 3 || !c.chars().all(|ch| ch.is_ascii_alphabetic()) {
+                    return Err(format!("Invalid currency code: {}", c));
+                }
+                upsert_setting(pool, KEY_REPORTING_CURRENCY, &c.to_uppercase()).await
+            }
+            None => delete_setting(pool, KEY_REPORTING_CURRENCY).await,
+        }
+    }
+
+    /// Net loss per day the trader aims to stay within, tracked as a process streak
+    pub async fn get_daily_loss_limit(pool: &SqlitePool) -> Result<Option<f64>, String> {
+        let value = get_setting(pool, KEY_DAILY_LOSS_LIMIT).await?;
+        Ok(value.and_then(|v| v.parse::<f64>().ok()).filter(|l| *l > 0.0))
+    }
+
+    /// Save the daily loss limit as a positive amount; None clears it
+    pub async fn save_daily_loss_limit(pool: &SqlitePool, limit: Option<f64>) -> Result<(), String> {
+        match limit {
+            Some(l) if l > 0.0 && l.is_finite() => {
+                upsert_setting(pool, KEY_DAILY_LOSS_LIMIT, &l.to_string()).await
+            }
+            Some(_) => Err("Daily loss limit must be positive".to_string()),
+            None => delete_setting(pool, KEY_DAILY_LOSS_LIMIT).await,
+        }
+    }
+
+    /// Process goals used for the weekly scorecards
+    pub async fn get_process_goals(pool: &SqlitePool) -> Result<ProcessGoals, String> {
+        let max_trades = get_setting(pool, KEY_GOAL_MAX_TRADES_PER_DAY).await?;
+        let journal = get_setting(pool, KEY_GOAL_JOURNAL_EVERY_DAY).await?;
+        let max_risk = get_setting(pool, KEY_GOAL_MAX_RISK_PER_TRADE).await?;
+
+        Ok(ProcessGoals {
+            max_trades_per_day: max_trades.and_then(|v| v.parse::<i32>().ok()),
+            journal_every_day: journal.as_deref() == Some("true"),
+            max_risk_per_trade: max_risk.and_then(|v| v.parse::<f64>().ok()),
+        })
+    }
+
+    pub async fn save_process_goals(pool: &SqlitePool, goals: &ProcessGoals) -> Result<(), String> {
+        if goals.max_trades_per_day.is_some_and(|n| n < 1) {
+            return Err("Max trades per day must be at least 1".to_string());
+        }
+        if goals.max_risk_per_trade.is_some_and(|r| r <= 0.0 || !r.is_finite()) {
+            return Err("Max risk per trade must be positive".to_string());
+        }
+
+        match goals.max_trades_per_day {
+            Some(n) => upsert_setting(pool, KEY_GOAL_MAX_TRADES_PER_DAY, &n.to_string()).await?,
+            None => delete_setting(pool, KEY_GOAL_MAX_TRADES_PER_DAY).await?,
+        }
+        upsert_setting(pool, KEY_GOAL_JOURNAL_EVERY_DAY, if goals.journal_every_day { "true" } else { "false" }).await?;
+        match goals.max_risk_per_trade {
+            Some(r) => upsert_setting(pool, KEY_GOAL_MAX_RISK_PER_TRADE, &r.to_string()).await,
+            None => delete_setting(pool, KEY_GOAL_MAX_RISK_PER_TRADE).await,
+        }
+    }
+
+    /// Reminder schedules; kinds never saved keep their default schedule
+    pub async fn get_reminders(pool: &SqlitePool) -> Result<Vec<Reminder>, String> {
+        let saved: Vec<Reminder> = get_setting(pool, KEY_REMINDERS)
+            .await?
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default();
+
+        Ok(Reminder::defaults()
+            .into_iter()
+            .map(|default| saved.iter().find(|r| r.kind == default.kind).cloned().unwrap_or(default))
+            .collect())
+    }
+
+    pub async fn save_reminders(pool: &SqlitePool, reminders: &[Reminder]) -> Result<(), String> {
+        for (i, reminder) in reminders.iter().enumerate() {
+            if reminders[..i].iter().any(|r| r.kind == reminder.kind) {
+                return Err(format!("Duplicate reminder: {}", reminder.kind.as_str()));
+            }
+            if NaiveTime::parse_from_str(&reminder.time, "%H:%M").is_err() {
+                return Err(format!("Invalid reminder time: {}", reminder.time));
+            }
+            if reminder.weekdays.iter().any(|d| *d > 6) {
+                return Err("Reminder weekdays must be 0 (Monday) to 6 (Sunday)".to_string());
+            }
+        }
+
+        let json = serde_json::to_string(reminders)
+            .map_err(|e| format!("Failed to serialize reminders: {}", e))?;
+        upsert_setting(pool, KEY_REMINDERS, &json).await
+    }
+
+    /// When each reminder last fired, so a reminder fires once per scheduled day
+    pub async fn get_reminders_last_fired(pool: &SqlitePool) -> Result<HashMap<ReminderKind, NaiveDateTime>, String> {
+        Ok(get_setting(pool, KEY_REMINDERS_LAST_FIRED)
+            .await?
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default())
+    }
+
+    pub async fn save_reminders_last_fired(
+        pool: &SqlitePool,
+        last_fired: &HashMap<ReminderKind, NaiveDateTime>,
+    ) -> Result<(), String> {
+        let json = serde_json::to_string(last_fired)
+            .map_err(|e| format!("Failed to serialize reminders: {}", e))?;
+        upsert_setting(pool, KEY_REMINDERS_LAST_FIRED, &json).await
+    }
+
+    /// Local time (HH:MM) after which the day is summarized, by default the US market close
+    pub async fn get_end_of_day_summary_time(pool: &SqlitePool) -> Result<String, String> {
+        let value = get_setting(pool, KEY_END_OF_DAY_SUMMARY_TIME).await?;
+        Ok(value.unwrap_or_else(|| DEFAULT_END_OF_DAY_SUMMARY_TIME.to_string()))
+    }
+
+    pub async fn save_end_of_day_summary_time(pool: &SqlitePool, time: &str) -> Result<(), String> {
+        let trimmed = time.trim();
+        if NaiveTime::parse_from_str(trimmed, "%H:%M").is_err() {
+            return Err(format!("Invalid summary time: {}", time));
+        }
+        upsert_setting(pool, KEY_END_OF_DAY_SUMMARY_TIME, trimmed).await
+    }
+
+    /// Last day an end-of-day summary was emitted for
+    pub async fn get_end_of_day_summary_last_date(pool: &SqlitePool) -> Result<Option<NaiveDate>, String> {
+        let value = get_setting(pool, KEY_END_OF_DAY_SUMMARY_LAST_DATE).await?;
+        Ok(value.and_then(|v| NaiveDate::parse_from_str(&v, "%Y-%m-%d").ok()))
+    }
+
+    pub async fn save_end_of_day_summary_last_date(pool: &SqlitePool, date: NaiveDate) -> Result<(), String> {
+        upsert_setting(pool, KEY_END_OF_DAY_SUMMARY_LAST_DATE, &date.format("%Y-%m-%d").to_string()).await
+    }
+
+    /// Days between scheduled maintenance runs; None runs maintenance only on demand
+    pub async fn get_maintenance_interval_days(pool: &SqlitePool) -> Result<Option<i32>, String> {
+        let value = get_setting(pool, KEY_MAINTENANCE_INTERVAL_DAYS).await?;
+        Ok(value.and_then(|v| v.parse::<i32>().ok()).filter(|d| *d > 0))
+    }
+
+    pub async fn save_maintenance_interval_days(pool: &SqlitePool, days: Option<i32>) -> Result<(), String> {
+        match days {
+            Some(d) if d > 0 => upsert_setting(pool, KEY_MAINTENANCE_INTERVAL_DAYS, &d.to_string()).await,
+            Some(_) => Err("Maintenance interval must be at least 1 day".to_string()),
+            None => delete_setting(pool, KEY_MAINTENANCE_INTERVAL_DAYS).await,
+        }
+    }
+
+    pub async fn get_last_maintenance_report(pool: &SqlitePool) -> Result<Option<MaintenanceReport>, String> {
+        Ok(get_setting(pool, KEY_LAST_MAINTENANCE_REPORT)
+            .await?
+            .and_then(|v| serde_json::from_str(&v).ok()))
+    }
+
+    pub async fn save_last_maintenance_report(pool: &SqlitePool, report: &MaintenanceReport) -> Result<(), String> {
+        let json = serde_json::to_string(report)
+            .map_err(|e| format!("Failed to serialize maintenance report: {}", e))?;
+        upsert_setting(pool, KEY_LAST_MAINTENANCE_REPORT, &json).await
+    }
+
+    /// Folder imported from automatically; None when no folder is watched
+    pub async fn get_watch_folder(pool: &SqlitePool) -> Result<Option<WatchFolder>, String> {
+        Ok(get_setting(pool, KEY_WATCH_FOLDER)
+            .await?
+            .and_then(|v| serde_json::from_str(&v).ok()))
+    }
+
+    pub async fn save_watch_folder(pool: &SqlitePool, folder: Option<&WatchFolder>) -> Result<(), String> {
+        let Some(folder) = folder else {
+            return delete_setting(pool, KEY_WATCH_FOLDER).await;
+        };
+        if !std::path::Path::new(&folder.directory).is_dir() {
+            return Err(format!("Not a folder: {}", folder.directory));
+        }
+
+        let json = serde_json::to_string(folder)
+            .map_err(|e| format!("Failed to serialize watch folder: {}", e))?;
+        upsert_setting(pool, KEY_WATCH_FOLDER, &json).await
+    }
 }
 
-fn mask_key_id(value: &str) -> String {
+/// Key ID showing only its first and last four characters
+pub(crate) fn mask_key_id(value: &str) -> String {
     let len = value.chars().count();
     if len <= 8 {
         return "••••".to_string();
@@ -96,6 +493,22 @@ async fn get_setting(pool: &SqlitePool, key: &str) -> Result<Option<String>, Str
 }
 
 async fn upsert_setting(pool: &SqlitePool, key: &str, value: &str) -> Result<(), String> {
+    let mut conn = pool.acquire().await.map_err(|e| format!("Database error: {}", e))?;
+    upsert_setting_in(&mut conn, key, value).await
+}
+
+/// Save a setting that changes how trades count, rebuilding every user's daily performance in the
+/// same transaction so the summary never disagrees with it
+async fn upsert_setting_rebuilding(pool: &SqlitePool, key: &str, value: &str) -> Result<(), String> {
+    let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+    upsert_setting_in(&mut tx, key, value).await?;
+    DailyPerformanceRepository::rebuild_all(&mut tx)
+        .await
+        .map_err(|e| format!("Failed to rebuild daily performance: {}", e))?;
+    tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))
+}
+
+async fn upsert_setting_in(conn: &mut SqliteConnection, key: &str, value: &str) -> Result<(), String> {
     sqlx::query(
         r#"
         INSERT INTO settings (key, value, updated_at)
@@ -107,7 +520,7 @@ async fn upsert_setting(pool: &SqlitePool, key: &str, value: &str) -> Result<(),
     )
     .bind(key)
     .bind(value)
-    .execute(pool)
+    .execute(conn)
     .await
     .map_err(|e| format!("Failed to save settings: {}", e))?;
     Ok(())
@@ -121,3 +534,36 @@ async fn delete_setting(pool: &SqlitePool, key: &str) -> Result<(), String> {
         .map_err(|e| format!("Failed to clear settings: {}", e))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_db;
+
+    #[tokio::test]
+    async fn test_get_storage_usage_empty_db() {
+        let pool = create_test_db().await;
+
+        let usage = SettingsService::get_storage_usage(&pool).await.unwrap();
+
+        assert!(usage.database_bytes > 0);
+        assert_eq!(usage.trade_count, 0);
+        assert_eq!(usage.execution_count, 0);
+        assert_eq!(usage.cached_candle_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_default_risk_per_trade_roundtrip() {
+        let pool = create_test_db().await;
+
+        assert_eq!(SettingsService::get_default_risk_per_trade(&pool).await.unwrap(), None);
+
+        SettingsService::save_default_risk_per_trade(&pool, Some(250.0)).await.unwrap();
+        assert_eq!(SettingsService::get_default_risk_per_trade(&pool).await.unwrap(), Some(250.0));
+
+        assert!(SettingsService::save_default_risk_per_trade(&pool, Some(-1.0)).await.is_err());
+
+        SettingsService::save_default_risk_per_trade(&pool, None).await.unwrap();
+        assert_eq!(SettingsService::get_default_risk_per_trade(&pool).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,221 @@
+use chrono::NaiveDate;
+use sqlx::sqlite::SqlitePool;
+use crate::calculations::calculate_stop_adjustment_report;
+use crate::models::{RecordStopAdjustmentInput, StopAdjustment, StopAdjustmentReport};
+use crate::repository::{StopAdjustmentRepository, TradeRepository};
+use crate::services::TradeService;
+
+pub struct StopAdjustmentService;
+
+impl StopAdjustmentService {
+    /// Record a stop-loss change on one of the user's trades
+    /// The trade's initial stop is kept for R; the change only moves its current stop.
+    pub async fn record_adjustment(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: RecordStopAdjustmentInput,
+    ) -> Result<StopAdjustment, String> {
+        if !input.stop_price.is_finite() || input.stop_price <= 0.0 {
+            return Err("Stop price must be greater than 0".to_string());
+        }
+
+        let trade = TradeRepository::get_by_id(pool, &input.trade_id)
+            .await
+            .map_err(|e| format!("Failed to get trade: {}", e))?;
+        let trade = match trade {
+            Some(t) if t.user_id == user_id => t,
+            _ => return Err("Trade not found".to_string()),
+        };
+        if trade.stop_loss_price.is_none() {
+            return Err("Set the trade's initial stop loss before recording adjustments".to_string());
+        }
+        TradeService::ensure_period_unlocked(pool, &trade.account_id, trade.trade_date).await?;
+
+        StopAdjustmentRepository::insert(pool, &input)
+            .await
+            .map_err(|e| format!("Failed to record stop adjustment: {}", e))
+    }
+
+    /// Get stop changes for one of the user's trades, oldest first
+    pub async fn get_trade_stops(
+        pool: &SqlitePool,
+        user_id: &str,
+        trade_id: &str,
+    ) -> Result<Vec<StopAdjustment>, String> {
+        StopAdjustmentRepository::get_for_trade(pool, user_id, trade_id)
+            .await
+            .map_err(|e| format!("Failed to get stop adjustments: {}", e))
+    }
+
+    /// Delete a stop change on one of the user's trades
+    pub async fn delete_adjustment(pool: &SqlitePool, user_id: &str, id: &str) -> Result<(), String> {
+        let adjustment = StopAdjustmentRepository::get_by_id(pool, user_id, id)
+            .await
+            .map_err(|e| format!("Failed to get stop adjustment: {}", e))?
+            .ok_or_else(|| "Stop adjustment not found".to_string())?;
+        let trade = TradeRepository::get_by_id(pool, &adjustment.trade_id)
+            .await
+            .map_err(|e| format!("Failed to get trade: {}", e))?
+            .ok_or_else(|| "Trade not found".to_string())?;
+        TradeService::ensure_period_unlocked(pool, &trade.account_id, trade.trade_date).await?;
+
+        let deleted = StopAdjustmentRepository::delete(pool, user_id, id)
+            .await
+            .map_err(|e| format!("Failed to delete stop adjustment: {}", e))?;
+
+        if !deleted {
+            return Err("Stop adjustment not found".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Get how often widening a stop preceded a loss larger than the initial risk
+    pub async fn get_report(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<StopAdjustmentReport, String> {
+        let trades = TradeService::get_trades(pool, user_id, account_id, start_date, end_date).await?;
+        let adjustments = StopAdjustmentRepository::get_by_trade(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get stop adjustments: {}", e))?;
+
+        Ok(calculate_stop_adjustment_report(&trades, &adjustments))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use crate::models::CreateTradeInput;
+    use crate::services::{AccountService, MetricsService};
+    use crate::test_utils::{create_open_trade, create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    fn adjust(trade_id: &str, stop_price: f64, day: u32) -> RecordStopAdjustmentInput {
+        RecordStopAdjustmentInput {
+            trade_id: trade_id.to_string(),
+            stop_price,
+            adjusted_at: Some(Utc.with_ymd_and_hms(2024, 1, day, 15, 0, 0).unwrap()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_widened_stops_and_open_risk_use_adjustment_history() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // Entry 150, initial stop 145 (5 risk); widened to 140 and exited at 138 for -2.4R
+        let widened = TradeService::create_trade(
+            &pool,
+            &user_id,
+            CreateTradeInput {
+                exit_price: Some(138.0),
+                ..create_test_trade_input(&account_id, "AAPL")
+            },
+        )
+        .await
+        .unwrap();
+        // Trailed up to 148 and exited at 155 for +1R
+        let trailed = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "MSFT"))
+            .await
+            .unwrap();
+
+        StopAdjustmentService::record_adjustment(&pool, &user_id, adjust(&widened.trade.id, 140.0, 15))
+            .await
+            .unwrap();
+        StopAdjustmentService::record_adjustment(&pool, &user_id, adjust(&trailed.trade.id, 148.0, 15))
+            .await
+            .unwrap();
+
+        // R still uses the initial stop
+        let fetched = TradeService::get_trade(&pool, &user_id, &widened.trade.id).await.unwrap().unwrap();
+        assert_eq!(fetched.risk_per_share, Some(5.0));
+
+        let report = StopAdjustmentService::get_report(&pool, &user_id, None, None, None).await.unwrap();
+        assert_eq!(report.trade_count, 2);
+        assert_eq!(report.widened_count, 1);
+        assert_eq!(report.widened_larger_loss_count, 1);
+        assert_eq!(report.widened_larger_loss_rate, Some(1.0));
+        assert_eq!(report.widened_trade_ids, vec![widened.trade.id.clone()]);
+        assert!((report.widened_avg_r.unwrap() + 2.4).abs() < 1e-9);
+        assert_eq!(report.kept_count, 1);
+        assert_eq!(report.kept_larger_loss_rate, Some(0.0));
+
+        // Open long from 100 with a 95 stop, trailed to 98 on the 17th and past entry on the 18th
+        let open = TradeService::create_trade(
+            &pool,
+            &user_id,
+            CreateTradeInput {
+                stop_loss_price: Some(95.0),
+                ..create_open_trade(&account_id, "NVDA", NaiveDate::from_ymd_opt(2024, 1, 16).unwrap(), 100.0, 10.0)
+            },
+        )
+        .await
+        .unwrap();
+        StopAdjustmentService::record_adjustment(&pool, &user_id, adjust(&open.trade.id, 98.0, 17))
+            .await
+            .unwrap();
+        let passed_entry = StopAdjustmentService::record_adjustment(&pool, &user_id, adjust(&open.trade.id, 102.0, 18))
+            .await
+            .unwrap();
+        assert_eq!(StopAdjustmentService::get_trade_stops(&pool, &user_id, &open.trade.id).await.unwrap().len(), 2);
+
+        // Other users can neither see nor delete the changes
+        assert!(StopAdjustmentService::get_trade_stops(&pool, "other-user", &open.trade.id).await.unwrap().is_empty());
+        let result = StopAdjustmentService::delete_adjustment(&pool, "other-user", &passed_entry.id).await;
+        assert_eq!(result.unwrap_err(), "Stop adjustment not found");
+
+        let heat = MetricsService::get_portfolio_heat(
+            &pool,
+            &user_id,
+            None,
+            NaiveDate::from_ymd_opt(2024, 1, 16).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 1, 18).unwrap(),
+        )
+        .await
+        .unwrap();
+        let risks: Vec<f64> = heat.iter().map(|p| p.open_risk).collect();
+        assert_eq!(risks, vec![50.0, 20.0, 0.0]);
+        assert_eq!(heat[2].unprotected_positions, 0);
+
+        // Adjustments need a valid price and an initial stop to compare against
+        let result = StopAdjustmentService::record_adjustment(&pool, &user_id, adjust(&open.trade.id, 0.0, 18)).await;
+        assert!(result.is_err());
+        let no_stop = TradeService::create_trade(
+            &pool,
+            &user_id,
+            create_open_trade(&account_id, "TSLA", NaiveDate::from_ymd_opt(2024, 1, 16).unwrap(), 100.0, 10.0),
+        )
+        .await
+        .unwrap();
+        let result = StopAdjustmentService::record_adjustment(&pool, &user_id, adjust(&no_stop.trade.id, 95.0, 17)).await;
+        assert!(result.unwrap_err().contains("initial stop"));
+    }
+
+    #[tokio::test]
+    async fn test_locked_period_blocks_recording_and_deleting_stops() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // Trade date is 2024-01-15
+        let trade = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+        let adjustment = StopAdjustmentService::record_adjustment(&pool, &user_id, adjust(&trade.trade.id, 148.0, 15))
+            .await
+            .unwrap();
+        AccountService::lock_period(&pool, &user_id, &account_id, NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
+            .await
+            .unwrap();
+
+        let recorded = StopAdjustmentService::record_adjustment(&pool, &user_id, adjust(&trade.trade.id, 149.0, 15)).await;
+        assert!(recorded.unwrap_err().contains("locked period"));
+        let deleted = StopAdjustmentService::delete_adjustment(&pool, &user_id, &adjustment.id).await;
+        assert!(deleted.unwrap_err().contains("locked period"));
+        assert_eq!(StopAdjustmentService::get_trade_stops(&pool, &user_id, &trade.trade.id).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,166 @@
+use sqlx::sqlite::SqlitePool;
+use crate::models::{CreateStrategyRuleInput, StrategyRule, Watchlist};
+use crate::repository::StrategyRuleRepository;
+
+pub struct StrategyRuleService;
+
+impl StrategyRuleService {
+    /// Add a rule suggesting a strategy for matching trades
+    pub async fn create_rule(
+        pool: &SqlitePool,
+        user_id: &str,
+        mut input: CreateStrategyRuleInput,
+    ) -> Result<StrategyRule, String> {
+        input.strategy = input.strategy.trim().to_string();
+        if input.strategy.is_empty() {
+            return Err("Strategy is required".to_string());
+        }
+        input.watchlist = input
+            .watchlist
+            .map(|w| w.trim().to_string())
+            .filter(|w| !w.is_empty());
+
+        for minutes in [input.min_hold_minutes, input.max_hold_minutes].into_iter().flatten() {
+            if minutes < 0.0 || !minutes.is_finite() {
+                return Err("Hold time must be zero or more minutes".to_string());
+            }
+        }
+        if let (Some(min), Some(max)) = (input.min_hold_minutes, input.max_hold_minutes) {
+            if min >= max {
+                return Err("Minimum hold time must be less than the maximum".to_string());
+            }
+        }
+
+        StrategyRuleRepository::insert(pool, user_id, &input)
+            .await
+            .map_err(|e| format!("Failed to create strategy rule: {}", e))
+    }
+
+    /// Get rules in the order they are tried
+    pub async fn get_rules(pool: &SqlitePool, user_id: &str) -> Result<Vec<StrategyRule>, String> {
+        StrategyRuleRepository::get_rules(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get strategy rules: {}", e))
+    }
+
+    pub async fn delete_rule(pool: &SqlitePool, user_id: &str, id: &str) -> Result<bool, String> {
+        StrategyRuleRepository::delete(pool, user_id, id)
+            .await
+            .map_err(|e| format!("Failed to delete strategy rule: {}", e))
+    }
+
+    /// Replace the symbols on a watchlist; an empty list removes it
+    pub async fn set_watchlist(
+        pool: &SqlitePool,
+        user_id: &str,
+        name: &str,
+        symbols: Vec<String>,
+    ) -> Result<Watchlist, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Watchlist name is required".to_string());
+        }
+
+        let mut symbols: Vec<String> = symbols
+            .iter()
+            .map(|s| s.trim().to_uppercase())
+            .filter(|s| !s.is_empty())
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+
+        StrategyRuleRepository::set_watchlist(pool, user_id, name, &symbols)
+            .await
+            .map_err(|e| format!("Failed to save watchlist: {}", e))?;
+
+        Ok(Watchlist { name: name.to_string(), symbols })
+    }
+
+    pub async fn get_watchlists(pool: &SqlitePool, user_id: &str) -> Result<Vec<Watchlist>, String> {
+        StrategyRuleRepository::get_watchlists(pool, user_id)
+            .await
+            .map_err(|e| format!("Failed to get watchlists: {}", e))
+    }
+
+    /// Load the rules and watchlists once for suggesting strategies on many trades
+    pub async fn get_rule_set(
+        pool: &SqlitePool,
+        user_id: &str,
+    ) -> Result<(Vec<StrategyRule>, Vec<Watchlist>), String> {
+        Ok((Self::get_rules(pool, user_id).await?, Self::get_watchlists(pool, user_id).await?))
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AssetClass;
+    use crate::repository::TradeRepository;
+    use crate::services::import_service::ImportService;
+    use crate::services::TradeService;
+    use crate::test_utils::{create_test_db, create_test_trade_input, setup_test_user_and_account};
+
+    fn rule_input(strategy: &str, priority: i32) -> CreateStrategyRuleInput {
+        CreateStrategyRuleInput {
+            strategy: strategy.to_string(),
+            priority: Some(priority),
+            min_hold_minutes: None,
+            max_hold_minutes: None,
+            watchlist: None,
+            direction: None,
+            asset_class: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rules_suggest_strategy_for_manual_and_imported_trades() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        StrategyRuleService::set_watchlist(&pool, &user_id, "momo", vec![" aapl".into(), "AAPL".into()])
+            .await
+            .unwrap();
+        let mut scalp = rule_input("Scalp", 1);
+        scalp.max_hold_minutes = Some(45.0);
+        scalp.watchlist = Some("momo".to_string());
+        StrategyRuleService::create_rule(&pool, &user_id, scalp).await.unwrap();
+        let mut day_trade = rule_input("Day trade", 2);
+        day_trade.asset_class = Some(AssetClass::Stock);
+        StrategyRuleService::create_rule(&pool, &user_id, day_trade).await.unwrap();
+
+        // Rules can target every asset class trades are imported as
+        let mut crypto = rule_input("Crypto swing", 4);
+        crypto.asset_class = Some(AssetClass::Crypto);
+        StrategyRuleService::create_rule(&pool, &user_id, crypto).await.unwrap();
+
+        let mut invalid = rule_input("Swing", 3);
+        invalid.min_hold_minutes = Some(60.0);
+        invalid.max_hold_minutes = Some(30.0);
+        assert!(StrategyRuleService::create_rule(&pool, &user_id, invalid).await.is_err());
+
+        // Held 75 minutes, so only the catch-all stock rule matches; an explicit strategy is kept
+        let mut manual = create_test_trade_input(&account_id, "AAPL");
+        manual.strategy = None;
+        let created = TradeService::create_trade(&pool, &user_id, manual).await.unwrap();
+        assert_eq!(created.trade.strategy.as_deref(), Some("Day trade"));
+        let explicit = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+        assert_eq!(explicit.trade.strategy.as_deref(), Some("momentum"));
+
+        // Imported round trip held 30 minutes in a watchlist symbol
+        let content = r#"
+STK_TRD|1001|AAPL|APPLE INC|DARK|BUYTOOPEN|O|20260127|09:30:00|USD|400.00|1.00|150.00|60000.00|-1.50|0.85
+STK_TRD|1002|AAPL|APPLE INC|DARK|SELLTOCLOSE|C|20260127|10:00:00|USD|-400.00|1.00|155.00|-62000.00|-1.50|0.85
+"#;
+        let preview = ImportService::preview_import(&pool, &user_id, content, &[]).await.unwrap();
+        assert_eq!(preview.trades_to_import[0].suggested_strategy.as_deref(), Some("Scalp"));
+
+        ImportService::execute_import(&pool, &user_id, &account_id, preview.trades_to_import, true, &[], &[], None)
+            .await
+            .unwrap();
+        let trades = TradeRepository::get_trades(&pool, &user_id, &Default::default()).await.unwrap();
+        assert!(trades.iter().any(|t| t.strategy.as_deref() == Some("Scalp")));
+    }
+}
@@ -1,12 +1,16 @@
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 use chrono_tz::Tz;
-use sqlx::sqlite::SqlitePool;
-use crate::calculations::calculate_derived_fields;
-use crate::models::{CreateTradeInput, Status, Trade, TradeWithDerived, UpdateTradeInput};
-#[cfg(test)]
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
+use crate::calculations::{calculate_derived_fields, calculate_hold_minutes, calculate_trade_type, snap_to_tick, suggest_strategy};
+use crate::models::{AssetClass, BorrowAvailability, BulkCreateResult, BulkRowError, CreateTradeInput, Direction, Instrument, OptionOutcome, OrderType, ResultBasis, Status, StrategyRule, Trade, TradeFilter, TradeResult, TradeStats, TradeTraits, TradeTypeThresholds, TradeWithDerived, UpdateTradeInput, Watchlist};
 use crate::models::trade::TradeExecutionRecord;
-use crate::repository::{InstrumentRepository, TradeRepository};
+use crate::repository::{AccountRepository, InstrumentRepository, TradeRepository};
 use crate::services::settings_service::SettingsService;
+use crate::services::{DailyPerformanceService, StrategyRuleService};
+
+const MAX_SYMBOL_LENGTH: usize = 32;
+const MAX_STRATEGY_LENGTH: usize = 100;
+const MAX_NOTES_LENGTH: usize = 10_000;
 
 pub struct TradeService;
 
@@ -18,22 +22,103 @@ impl TradeService {
         input: CreateTradeInput,
     ) -> Result<TradeWithDerived, String> {
         let manual_timezone = SettingsService::get_manual_trade_timezone(pool).await?;
-        let normalized_input = Self::normalize_manual_times_to_utc(input, &manual_timezone)?;
+        let (rules, watchlists) = StrategyRuleService::get_rule_set(pool, user_id).await?;
+        let (normalized_input, processed_input) =
+            Self::prepare_input(pool, user_id, input, &manual_timezone, &rules, &watchlists).await?;
+        let result_basis = SettingsService::get_result_basis(pool).await?;
+        let thresholds = SettingsService::get_trade_type_thresholds(pool).await?;
+
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        let instrument = Self::get_or_create_instrument(&mut tx, &processed_input).await?;
+        let trade = Self::insert_prepared(&mut tx, user_id, &instrument.id, &normalized_input, &processed_input).await?;
+        DailyPerformanceService::refresh_trades(&mut tx, std::slice::from_ref(&trade.id), Vec::new()).await?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+
+        Ok(Self::with_derived_fields(trade, result_basis, &thresholds))
+    }
+
+    /// Validate and insert many manual trades, all or nothing
+    /// Every row is validated first; if any row fails, nothing is inserted and each failure is reported.
+    /// The trades are inserted in one transaction, so a failed insert leaves none of them behind.
+    pub async fn create_trades(
+        pool: &SqlitePool,
+        user_id: &str,
+        inputs: Vec<CreateTradeInput>,
+    ) -> Result<BulkCreateResult, String> {
+        let manual_timezone = SettingsService::get_manual_trade_timezone(pool).await?;
+        let (rules, watchlists) = StrategyRuleService::get_rule_set(pool, user_id).await?;
+
+        let mut prepared = Vec::with_capacity(inputs.len());
+        let mut errors = Vec::new();
+        for (index, input) in inputs.into_iter().enumerate() {
+            match Self::prepare_input(pool, user_id, input, &manual_timezone, &rules, &watchlists).await {
+                Ok(pair) => prepared.push(pair),
+                Err(message) => errors.push(BulkRowError { row: index + 1, message }),
+            }
+        }
 
-        // Validate input (including exits)
-        Self::validate_input(&normalized_input)?;
+        if !errors.is_empty() {
+            return Ok(BulkCreateResult { created: Vec::new(), errors });
+        }
 
-        // Validate account exists
-        let account_exists: bool = sqlx::query_scalar(
-            "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = ?)"
-        )
-        .bind(&normalized_input.account_id)
-        .fetch_one(pool)
-        .await
-        .map_err(|e| format!("Failed to check account: {}", e))?;
+        let result_basis = SettingsService::get_result_basis(pool).await?;
+        let thresholds = SettingsService::get_trade_type_thresholds(pool).await?;
 
-        if !account_exists {
-            return Err(format!("Account not found: {}", normalized_input.account_id));
+        // Instruments are created in the transaction too, so a failed batch leaves none behind
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        let mut created = Vec::with_capacity(prepared.len());
+        for (index, (normalized_input, processed_input)) in prepared.iter().enumerate() {
+            let inserted = match Self::get_or_create_instrument(&mut tx, processed_input).await {
+                Ok(instrument) => {
+                    Self::insert_prepared(&mut tx, user_id, &instrument.id, normalized_input, processed_input).await
+                }
+                Err(message) => Err(message),
+            };
+            match inserted {
+                Ok(trade) => created.push(trade),
+                Err(message) => {
+                    // Dropping the transaction rolls back the rows inserted so far
+                    return Ok(BulkCreateResult {
+                        created: Vec::new(),
+                        errors: vec![BulkRowError { row: index + 1, message }],
+                    });
+                }
+            }
+        }
+        let created_ids: Vec<String> = created.iter().map(|t| t.id.clone()).collect();
+        DailyPerformanceService::refresh_trades(&mut tx, &created_ids, Vec::new()).await?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+
+        let created = created.into_iter().map(|t| Self::with_derived_fields(t, result_basis, &thresholds)).collect();
+        Ok(BulkCreateResult { created, errors })
+    }
+
+    /// Normalize times, validate and aggregate exits of a manual trade
+    /// Returns the normalized input (for executions) and the input to store on the trade row.
+    async fn prepare_input(
+        pool: &SqlitePool,
+        user_id: &str,
+        input: CreateTradeInput,
+        manual_timezone: &str,
+        rules: &[StrategyRule],
+        watchlists: &[Watchlist],
+    ) -> Result<(CreateTradeInput, CreateTradeInput), String> {
+        // Validate input (including exits) before times are converted
+        Self::validate_input(&input)?;
+
+        let normalized_input = Self::normalize_manual_times_to_utc(input, manual_timezone)?;
+
+        // Prices must sit on the instrument's tick grid
+        let instrument = InstrumentRepository::get_by_symbol(pool, normalized_input.symbol.trim())
+            .await
+            .map_err(|e| format!("Failed to get instrument: {}", e))?;
+        let normalized_input = Self::snap_input_prices(normalized_input, instrument.as_ref())?;
+
+        // Validate the account exists and belongs to the user, and the entry and exits land outside its locked period
+        Self::ensure_account_owned(pool, user_id, &normalized_input.account_id).await?;
+        let exit_dates = normalized_input.exits.iter().flatten().map(|exit| exit.exit_date);
+        for date in std::iter::once(normalized_input.trade_date).chain(exit_dates) {
+            Self::ensure_period_unlocked(pool, &normalized_input.account_id, date).await?;
         }
 
         // Process exits if provided
@@ -57,20 +142,57 @@ impl TradeService {
             processed_input.status = Some(status);
         }
 
-        // Get or create instrument with asset class
-        let instrument = InstrumentRepository::get_or_create_with_asset_class(
-            pool,
+        // Fill in the strategy from the user's rules when none was given
+        if processed_input.strategy.as_deref().is_none_or(|s| s.trim().is_empty()) {
+            let exit_date = normalized_input
+                .exits
+                .as_ref()
+                .and_then(|exits| exits.iter().map(|e| e.exit_date).max())
+                .unwrap_or(processed_input.trade_date);
+            let traits = TradeTraits {
+                symbol: processed_input.symbol.trim().to_string(),
+                direction: processed_input.direction,
+                asset_class: processed_input.asset_class.unwrap_or(AssetClass::Stock),
+                hold_minutes: calculate_hold_minutes(
+                    processed_input.trade_date,
+                    processed_input.entry_time.as_deref(),
+                    exit_date,
+                    processed_input.exit_time.as_deref(),
+                ),
+            };
+            processed_input.strategy = suggest_strategy(rules, watchlists, &traits);
+        }
+
+        Ok((normalized_input, processed_input))
+    }
+
+    /// Get or create the instrument of a prepared manual trade with its asset class
+    async fn get_or_create_instrument(
+        conn: &mut SqliteConnection,
+        processed_input: &CreateTradeInput,
+    ) -> Result<Instrument, String> {
+        InstrumentRepository::get_or_create_with_asset_class_in(
+            conn,
             &processed_input.symbol,
             processed_input.asset_class,
         )
         .await
-        .map_err(|e| format!("Failed to get/create instrument: {}", e))?;
+        .map_err(|e| format!("Failed to get/create instrument: {}", e))
+    }
 
+    /// Insert a prepared manual trade with its entry and exit executions
+    async fn insert_prepared(
+        conn: &mut SqliteConnection,
+        user_id: &str,
+        instrument_id: &str,
+        normalized_input: &CreateTradeInput,
+        processed_input: &CreateTradeInput,
+    ) -> Result<Trade, String> {
         // Insert trade
-        let trade = TradeRepository::insert(pool, user_id, &instrument.id, &processed_input)
+        let mut trade = TradeRepository::insert_in(&mut *conn, user_id, instrument_id, processed_input)
             .await
             .map_err(|e| format!("Failed to create trade (user={}, account={}, instrument={}): {}",
-                user_id, normalized_input.account_id, instrument.id, e))?;
+                user_id, normalized_input.account_id, instrument_id, e))?;
 
         // Insert entry execution record for manual trades.
         let entry_quantity = normalized_input.quantity.unwrap_or_else(|| {
@@ -80,7 +202,7 @@ impl TradeService {
         });
         if entry_quantity > 0.0 {
             Self::insert_execution(
-                pool,
+                &mut *conn,
                 &trade.id,
                 "entry",
                 normalized_input.trade_date,
@@ -97,7 +219,7 @@ impl TradeService {
         if let Some(ref exits) = normalized_input.exits {
             for (i, exit) in exits.iter().enumerate() {
                 Self::insert_execution(
-                    pool,
+                    &mut *conn,
                     &trade.id,
                     "exit",
                     exit.exit_date,
@@ -109,10 +231,10 @@ impl TradeService {
                     .await
                     .map_err(|e| format!("Failed to insert exit execution #{}: {}", i + 1, e))?;
             }
+            trade.exit_date = exits.iter().map(|e| e.exit_date).max();
         }
 
-        // Calculate derived fields
-        Ok(Self::with_derived_fields(trade))
+        Ok(trade)
     }
 
     fn normalize_manual_times_to_utc(
@@ -200,7 +322,7 @@ impl TradeService {
 
     /// Insert an execution into the database
     async fn insert_execution(
-        pool: &SqlitePool,
+        conn: &mut SqliteConnection,
         trade_id: &str,
         execution_type: &str,
         execution_date: NaiveDate,
@@ -227,22 +349,54 @@ impl TradeService {
         .bind(quantity)
         .bind(price)
         .bind(fees)
-        .execute(pool)
+        .execute(conn)
         .await?;
 
         Ok(())
     }
 
-    /// Get a trade by ID with derived fields
+    /// Get one of the user's trades by ID with derived fields
     pub async fn get_trade(
         pool: &SqlitePool,
+        user_id: &str,
         id: &str,
     ) -> Result<Option<TradeWithDerived>, String> {
         let trade = TradeRepository::get_by_id(pool, id)
             .await
-            .map_err(|e| format!("Failed to get trade: {}", e))?;
+            .map_err(|e| format!("Failed to get trade: {}", e))?
+            .filter(|t| t.user_id == user_id);
+
+        let result_basis = SettingsService::get_result_basis(pool).await?;
+        let thresholds = SettingsService::get_trade_type_thresholds(pool).await?;
+        Ok(trade.map(|t| Self::with_derived_fields(t, result_basis, &thresholds)))
+    }
+
+    /// Count and summary stats for the trades matching a filter
+    pub async fn get_trade_stats(
+        pool: &SqlitePool,
+        user_id: &str,
+        filter: &TradeFilter,
+    ) -> Result<TradeStats, String> {
+        TradeRepository::get_trade_stats(pool, user_id, filter)
+            .await
+            .map_err(|e| format!("Failed to get trade stats: {}", e))
+    }
+
+    /// Resolve a trade reference code such as T-2024-0193 to the user's trade
+    pub async fn resolve_trade_ref(
+        pool: &SqlitePool,
+        user_id: &str,
+        ref_code: &str,
+    ) -> Result<Option<TradeWithDerived>, String> {
+        let ref_code = ref_code.trim().to_uppercase();
+        let Some(id) = TradeRepository::get_id_by_ref_code(pool, user_id, &ref_code)
+            .await
+            .map_err(|e| format!("Failed to resolve trade reference: {}", e))?
+        else {
+            return Ok(None);
+        };
 
-        Ok(trade.map(Self::with_derived_fields))
+        Self::get_trade(pool, user_id, &id).await
     }
 
     /// Get trades with optional filters
@@ -254,18 +408,14 @@ impl TradeService {
         end_date: Option<NaiveDate>,
     ) -> Result<Vec<TradeWithDerived>, String> {
         // Only get closed trades for metrics
-        let trades = TradeRepository::get_trades(
-            pool,
-            user_id,
-            account_id,
+        let filter = TradeFilter {
+            account_id: account_id.map(str::to_string),
             start_date,
             end_date,
-            Some(Status::Closed),
-        )
-        .await
-        .map_err(|e| format!("Failed to get trades: {}", e))?;
-
-        Ok(trades.into_iter().map(Self::with_derived_fields).collect())
+            status: Some(Status::Closed),
+            ..Default::default()
+        };
+        Self::find_trades(pool, user_id, &filter).await
     }
 
     /// Get all trades including open ones
@@ -276,26 +426,103 @@ impl TradeService {
         start_date: Option<NaiveDate>,
         end_date: Option<NaiveDate>,
     ) -> Result<Vec<TradeWithDerived>, String> {
-        let trades = TradeRepository::get_trades(
-            pool,
-            user_id,
-            account_id,
+        let filter = TradeFilter {
+            account_id: account_id.map(str::to_string),
             start_date,
             end_date,
-            None,
-        )
-        .await
-        .map_err(|e| format!("Failed to get trades: {}", e))?;
+            ..Default::default()
+        };
+        Self::find_trades(pool, user_id, &filter).await
+    }
+
+    /// Get trades matching a review filter such as result, minimum R or maximum duration
+    pub async fn find_trades(
+        pool: &SqlitePool,
+        user_id: &str,
+        filter: &TradeFilter,
+    ) -> Result<Vec<TradeWithDerived>, String> {
+        let trades = TradeRepository::get_trades(pool, user_id, filter)
+            .await
+            .map_err(|e| format!("Failed to get trades: {}", e))?;
+
+        let result_basis = SettingsService::get_result_basis(pool).await?;
+        let thresholds = SettingsService::get_trade_type_thresholds(pool).await?;
+        Ok(trades.into_iter().map(|t| Self::with_derived_fields(t, result_basis, &thresholds)).collect())
+    }
+
+    /// Load the executions of each trade into it, e.g. for editing partial exits
+    pub async fn attach_executions(
+        pool: &SqlitePool,
+        trades: &mut [TradeWithDerived],
+        archived: bool,
+    ) -> Result<(), String> {
+        let trade_ids: Vec<String> = trades.iter().map(|t| t.trade.id.clone()).collect();
+        let mut executions = TradeRepository::get_executions_for_trades(pool, &trade_ids, archived)
+            .await
+            .map_err(|e| format!("Failed to get trade executions: {}", e))?;
 
-        Ok(trades.into_iter().map(Self::with_derived_fields).collect())
+        for trade in trades {
+            trade.executions = Some(executions.remove(&trade.trade.id).unwrap_or_default());
+        }
+        Ok(())
     }
 
     /// Update a trade
     pub async fn update_trade(
         pool: &SqlitePool,
+        user_id: &str,
         id: &str,
         input: UpdateTradeInput,
     ) -> Result<TradeWithDerived, String> {
+        Self::validate_update_input(&input)?;
+
+        let existing = Self::get_owned_trade(pool, user_id, id).await?;
+        Self::ensure_period_unlocked(pool, &existing.account_id, existing.trade_date).await?;
+
+        // A trade can only move to another of the user's own accounts
+        if let Some(ref account_id) = input.account_id {
+            Self::ensure_account_owned(pool, user_id, account_id).await?;
+        }
+
+        // Moving a trade into a locked period is not allowed either
+        let account_id = input.account_id.as_deref().unwrap_or(&existing.account_id);
+        let trade_date = input.trade_date.unwrap_or(existing.trade_date);
+        Self::ensure_period_unlocked(pool, account_id, trade_date).await?;
+
+        // Status and exit data follow the recorded executions
+        let mut input = input;
+        let executions = TradeRepository::get_executions(pool, id)
+            .await
+            .map_err(|e| format!("Failed to get executions: {}", e))?;
+        let entry_execution_id = Self::reconcile_with_executions(&existing, &mut input, &executions)?;
+        let exit_price_derived = executions.iter().any(|e| e.execution_type == "exit");
+
+        // Prices must sit on the tick grid of the (possibly new) instrument
+        let symbol = input.symbol.as_deref().unwrap_or(&existing.symbol);
+        let instrument = InstrumentRepository::get_by_symbol(pool, symbol.trim())
+            .await
+            .map_err(|e| format!("Failed to get instrument: {}", e))?;
+        let tick_size = instrument.as_ref().and_then(|i| i.tick_size);
+        let asset_class = instrument
+            .as_ref()
+            .and_then(|i| AssetClass::from_str(&i.asset_class))
+            .unwrap_or(existing.asset_class);
+
+        input.entry_price = input
+            .entry_price
+            .map(|p| Self::snap_price("Entry price", p, tick_size, asset_class))
+            .transpose()?;
+        if !exit_price_derived {
+            input.exit_price = input
+                .exit_price
+                .map(|p| Self::snap_price("Exit price", p, tick_size, asset_class))
+                .transpose()?;
+        }
+        input.stop_loss_price = input
+            .stop_loss_price
+            .map(|p| Self::snap_price("Stop loss price", p, tick_size, asset_class))
+            .transpose()?;
+
         // Get new instrument ID if symbol changed
         let instrument_id = if let Some(ref symbol) = input.symbol {
             let instrument = InstrumentRepository::get_or_create(pool, symbol)
@@ -306,18 +533,231 @@ impl TradeService {
             None
         };
 
-        let trade = TradeRepository::update(pool, id, instrument_id.as_deref(), &input)
+        let result_basis = SettingsService::get_result_basis(pool).await?;
+        let thresholds = SettingsService::get_trade_type_thresholds(pool).await?;
+
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        // The trade may move off the days it counted toward
+        let previous_days = DailyPerformanceService::trade_days(&mut tx, &[id.to_string()]).await?;
+        let trade = TradeRepository::update_in(&mut tx, id, instrument_id.as_deref(), &input)
             .await
             .map_err(|e| format!("Failed to update trade: {}", e))?;
 
-        Ok(Self::with_derived_fields(trade))
+        // Keep a single entry execution in step with the trade's entry fields
+        if let (Some(execution_id), Some(quantity)) = (entry_execution_id, trade.quantity) {
+            TradeRepository::sync_entry_execution(
+                &mut tx,
+                &execution_id,
+                trade.trade_date,
+                trade.entry_time.as_deref(),
+                quantity,
+                trade.entry_price,
+            )
+            .await
+            .map_err(|e| format!("Failed to update entry execution: {}", e))?;
+        }
+        DailyPerformanceService::refresh_trades(&mut tx, &[id.to_string()], previous_days).await?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+
+        Ok(Self::with_derived_fields(trade, result_basis, &thresholds))
     }
 
-    /// Delete a trade
-    pub async fn delete_trade(pool: &SqlitePool, id: &str) -> Result<(), String> {
-        TradeRepository::delete(pool, id)
+    /// Set or clear a manual result override, e.g. to count a rule-breaking win as a loss
+    pub async fn set_result_override(
+        pool: &SqlitePool,
+        user_id: &str,
+        id: &str,
+        result_override: Option<String>,
+    ) -> Result<TradeWithDerived, String> {
+        let result_override = match result_override {
+            Some(value) => Some(
+                TradeResult::from_str(&value)
+                    .ok_or_else(|| format!("Invalid trade result: {}", value))?,
+            ),
+            None => None,
+        };
+
+        let trade = Self::get_owned_trade(pool, user_id, id).await?;
+        Self::ensure_period_unlocked(pool, &trade.account_id, trade.trade_date).await?;
+
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        TradeRepository::set_result_override(&mut tx, id, result_override)
             .await
-            .map_err(|e| format!("Failed to delete trade: {}", e))
+            .map_err(|e| format!("Failed to update trade: {}", e))?;
+        DailyPerformanceService::refresh_trades(&mut tx, &[id.to_string()], Vec::new()).await?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+
+        Self::get_trade(pool, user_id, id)
+            .await?
+            .ok_or_else(|| format!("Trade not found: {}", id))
+    }
+
+    /// Record how hard a short trade's shares were to borrow, or clear it
+    pub async fn set_borrow_availability(
+        pool: &SqlitePool,
+        user_id: &str,
+        id: &str,
+        availability: Option<String>,
+        note: Option<String>,
+    ) -> Result<TradeWithDerived, String> {
+        let availability = match availability {
+            Some(value) => Some(
+                BorrowAvailability::from_str(&value)
+                    .ok_or_else(|| format!("Invalid borrow availability: {}", value))?,
+            ),
+            None => None,
+        };
+        let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+
+        let trade = Self::get_owned_trade(pool, user_id, id).await?;
+        if (availability.is_some() || note.is_some()) && trade.direction != Direction::Short {
+            return Err("Borrow availability only applies to short trades".to_string());
+        }
+        Self::ensure_period_unlocked(pool, &trade.account_id, trade.trade_date).await?;
+
+        TradeRepository::set_borrow_availability(pool, id, availability, note.as_deref())
+            .await
+            .map_err(|e| format!("Failed to update trade: {}", e))?;
+
+        Self::get_trade(pool, user_id, id)
+            .await?
+            .ok_or_else(|| format!("Trade not found: {}", id))
+    }
+
+    /// Record that an option trade expired, was assigned or was exercised, or clear it
+    pub async fn set_option_outcome(
+        pool: &SqlitePool,
+        user_id: &str,
+        id: &str,
+        option_outcome: Option<String>,
+    ) -> Result<TradeWithDerived, String> {
+        let option_outcome = match option_outcome {
+            Some(value) => Some(
+                OptionOutcome::from_str(&value)
+                    .ok_or_else(|| format!("Invalid option outcome: {}", value))?,
+            ),
+            None => None,
+        };
+
+        let trade = Self::get_owned_trade(pool, user_id, id).await?;
+        if option_outcome.is_some() && trade.asset_class != AssetClass::Option {
+            return Err("Only option trades can expire, be assigned or be exercised".to_string());
+        }
+        Self::ensure_period_unlocked(pool, &trade.account_id, trade.trade_date).await?;
+
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        TradeRepository::set_option_outcome(&mut tx, id, option_outcome)
+            .await
+            .map_err(|e| format!("Failed to update trade: {}", e))?;
+        DailyPerformanceService::refresh_trades(&mut tx, &[id.to_string()], Vec::new()).await?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))?;
+
+        Self::get_trade(pool, user_id, id)
+            .await?
+            .ok_or_else(|| format!("Trade not found: {}", id))
+    }
+
+    /// Delete a trade; a missing or stale ID is reported as not found
+    pub async fn delete_trade(pool: &SqlitePool, user_id: &str, id: &str) -> Result<(), String> {
+        let trade = Self::get_owned_trade(pool, user_id, id).await?;
+        Self::ensure_period_unlocked(pool, &trade.account_id, trade.trade_date).await?;
+
+        let mut tx = pool.begin().await.map_err(|e| format!("Database transaction error: {}", e))?;
+        let previous_days = DailyPerformanceService::trade_days(&mut tx, &[id.to_string()]).await?;
+        let deleted = TradeRepository::delete_in(&mut tx, id)
+            .await
+            .map_err(|e| format!("Failed to delete trade: {}", e))?;
+
+        if deleted == 0 {
+            return Err(format!("Trade not found: {}", id));
+        }
+
+        DailyPerformanceService::refresh_days(&mut tx, previous_days).await?;
+        tx.commit().await.map_err(|e| format!("Database transaction error: {}", e))
+    }
+
+    /// Set or clear the minimum price increment of an instrument
+    pub async fn set_instrument_tick_size(
+        pool: &SqlitePool,
+        symbol: &str,
+        tick_size: Option<f64>,
+    ) -> Result<Instrument, String> {
+        if let Some(tick) = tick_size {
+            if !tick.is_finite() || tick <= 0.0 {
+                return Err("Tick size must be greater than 0".to_string());
+            }
+        }
+
+        let instrument = InstrumentRepository::get_or_create(pool, symbol.trim())
+            .await
+            .map_err(|e| format!("Failed to get/create instrument: {}", e))?;
+
+        InstrumentRepository::set_tick_size(pool, &instrument.id, tick_size)
+            .await
+            .map_err(|e| format!("Failed to update instrument: {}", e))?
+            .ok_or_else(|| format!("Instrument not found: {}", symbol))
+    }
+
+    /// Set or clear the fraction of position value an instrument requires as margin
+    pub async fn set_instrument_margin_requirement(
+        pool: &SqlitePool,
+        symbol: &str,
+        margin_requirement: Option<f64>,
+    ) -> Result<Instrument, String> {
+        if let Some(requirement) = margin_requirement {
+            if !requirement.is_finite() || requirement <= 0.0 || requirement > 1.0 {
+                return Err("Margin requirement must be greater than 0 and at most 1".to_string());
+            }
+        }
+
+        let instrument = InstrumentRepository::get_or_create(pool, symbol.trim())
+            .await
+            .map_err(|e| format!("Failed to get/create instrument: {}", e))?;
+
+        InstrumentRepository::set_margin_requirement(pool, &instrument.id, margin_requirement)
+            .await
+            .map_err(|e| format!("Failed to update instrument: {}", e))?
+            .ok_or_else(|| format!("Instrument not found: {}", symbol))
+    }
+
+    /// Record the intended price and order type of one of the user's executions
+    pub async fn update_execution_quality(
+        pool: &SqlitePool,
+        user_id: &str,
+        execution_id: &str,
+        intended_price: Option<f64>,
+        order_type: Option<String>,
+    ) -> Result<(), String> {
+        if let Some(price) = intended_price {
+            if price <= 0.0 {
+                return Err("Intended price must be greater than 0".to_string());
+            }
+        }
+
+        let order_type = match order_type {
+            Some(value) => Some(
+                OrderType::from_str(&value)
+                    .ok_or_else(|| format!("Invalid order type: {}", value))?,
+            ),
+            None => None,
+        };
+
+        let trade_id = TradeRepository::get_execution_trade_id(pool, user_id, execution_id)
+            .await
+            .map_err(|e| format!("Failed to get execution: {}", e))?
+            .ok_or_else(|| "Execution not found".to_string())?;
+        let trade = Self::get_owned_trade(pool, user_id, &trade_id).await?;
+        Self::ensure_period_unlocked(pool, &trade.account_id, trade.trade_date).await?;
+
+        let updated = TradeRepository::update_execution_quality(pool, user_id, execution_id, intended_price, order_type)
+            .await
+            .map_err(|e| format!("Failed to update execution: {}", e))?;
+
+        if !updated {
+            return Err("Execution not found".to_string());
+        }
+
+        Ok(())
     }
 
     /// Get executions for a trade
@@ -331,14 +771,133 @@ impl TradeService {
             .map_err(|e| format!("Failed to get trade executions: {}", e))
     }
 
-    /// Add derived fields to a trade
-    fn with_derived_fields(trade: Trade) -> TradeWithDerived {
-        let derived = calculate_derived_fields(&trade);
-        TradeWithDerived::from_trade(trade, derived)
+    /// Check an update against the trade's executions and derive status and exit data from them
+    /// Returns the entry execution to keep in sync when the trade has exactly one.
+    fn reconcile_with_executions(
+        existing: &Trade,
+        input: &mut UpdateTradeInput,
+        executions: &[TradeExecutionRecord],
+    ) -> Result<Option<String>, String> {
+        const EPSILON: f64 = 1e-6;
+
+        let entries: Vec<&TradeExecutionRecord> =
+            executions.iter().filter(|e| e.execution_type == "entry").collect();
+        let exits: Vec<&TradeExecutionRecord> =
+            executions.iter().filter(|e| e.execution_type == "exit").collect();
+
+        let quantity_changed = match (input.quantity, existing.quantity) {
+            (Some(new), Some(old)) => (new - old).abs() > EPSILON,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if entries.len() > 1 && quantity_changed {
+            return Err(format!(
+                "Quantity cannot be edited on a trade with {} entry executions",
+                entries.len()
+            ));
+        }
+
+        if !exits.is_empty() {
+            let exited: f64 = exits.iter().map(|e| e.quantity).sum();
+            let quantity = input.quantity.or(existing.quantity);
+
+            if let Some(qty) = quantity {
+                if qty + EPSILON < exited {
+                    return Err(format!(
+                        "Quantity {} cannot be less than the {} already exited",
+                        qty, exited
+                    ));
+                }
+            }
+
+            let status = if quantity.is_none_or(|qty| exited + EPSILON >= qty) {
+                Status::Closed
+            } else {
+                Status::Open
+            };
+            if let Some(requested) = input.status {
+                if requested != status {
+                    return Err(format!(
+                        "Status cannot be {} while exit executions cover {} of {}",
+                        requested.as_str(),
+                        exited,
+                        quantity.unwrap_or(exited)
+                    ));
+                }
+            }
+
+            let exit_price = exits.iter().map(|e| e.price * e.quantity).sum::<f64>() / exited;
+            if let Some(requested) = input.exit_price {
+                if (requested - exit_price).abs() > EPSILON {
+                    return Err(format!(
+                        "Exit price {} does not match the {} average of the recorded exit executions",
+                        requested, exit_price
+                    ));
+                }
+            }
+
+            input.status = Some(status);
+            input.exit_price = Some(exit_price);
+            if let Some(last_exit) = exits.last() {
+                input.exit_time = last_exit.execution_time.clone();
+            }
+        }
+
+        Ok(match entries.as_slice() {
+            [entry] => Some(entry.id.clone()),
+            _ => None,
+        })
+    }
+
+    /// Get a trade, treating trades of other users as missing
+    async fn get_owned_trade(pool: &SqlitePool, user_id: &str, id: &str) -> Result<Trade, String> {
+        TradeRepository::get_by_id(pool, id)
+            .await
+            .map_err(|e| format!("Failed to get trade: {}", e))?
+            .filter(|t| t.user_id == user_id)
+            .ok_or_else(|| format!("Trade not found: {}", id))
+    }
+
+    /// Fail unless the account exists and belongs to the user
+    async fn ensure_account_owned(pool: &SqlitePool, user_id: &str, account_id: &str) -> Result<(), String> {
+        let owned = AccountRepository::get_by_id(pool, account_id)
+            .await
+            .map_err(|e| format!("Failed to check account: {}", e))?
+            .is_some_and(|a| a.user_id == user_id);
+
+        if !owned {
+            return Err(format!("Account not found: {}", account_id));
+        }
+        Ok(())
+    }
+
+    /// Fail if the date falls within the account's locked period
+    pub(crate) async fn ensure_period_unlocked(
+        pool: &SqlitePool,
+        account_id: &str,
+        trade_date: NaiveDate,
+    ) -> Result<(), String> {
+        let lock = AccountRepository::get_period_lock(pool, account_id)
+            .await
+            .map_err(|e| format!("Failed to check period lock: {}", e))?;
+
+        lock.map_or(Ok(()), |lock| lock.ensure_unlocked(trade_date))
+    }
+
+    /// Add derived fields to a trade, classifying its result on the given basis and its type by the thresholds
+    fn with_derived_fields(trade: Trade, result_basis: ResultBasis, thresholds: &TradeTypeThresholds) -> TradeWithDerived {
+        let derived = calculate_derived_fields(&trade, result_basis);
+        let trade_type = calculate_trade_type(&trade, thresholds);
+        TradeWithDerived {
+            trade_type,
+            ..TradeWithDerived::from_trade(trade, derived)
+        }
     }
 
     /// Validate trade input
     fn validate_input(input: &CreateTradeInput) -> Result<(), String> {
+        validate_symbol(&input.symbol)?;
+
         if input.entry_price <= 0.0 {
             return Err("Entry price must be greater than 0".to_string());
         }
@@ -361,12 +920,34 @@ impl TradeService {
             }
         }
 
+        if let Some(target) = input.target_price {
+            if target <= 0.0 {
+                return Err("Target price must be greater than 0".to_string());
+            }
+        }
+
         if let Some(fees) = input.fees {
             if fees < 0.0 {
                 return Err("Fees cannot be negative".to_string());
             }
         }
 
+        if let Some(ref strategy) = input.strategy {
+            validate_text_length("Strategy", strategy, MAX_STRATEGY_LENGTH)?;
+        }
+
+        if let Some(ref notes) = input.notes {
+            validate_text_length("Notes", notes, MAX_NOTES_LENGTH)?;
+        }
+
+        if let Some(ref entry_time) = input.entry_time {
+            validate_time_format("Entry time", entry_time)?;
+        }
+
+        if let Some(ref exit_time) = input.exit_time {
+            validate_time_format("Exit time", exit_time)?;
+        }
+
         // Validate exits if provided
         if let Some(ref exits) = input.exits {
             for (i, exit) in exits.iter().enumerate() {
@@ -381,11 +962,121 @@ impl TradeService {
                         return Err(format!("Exit {} fees cannot be negative", i + 1));
                     }
                 }
+                if let Some(ref exit_time) = exit.exit_time {
+                    validate_time_format(&format!("Exit {} time", i + 1), exit_time)?;
+                }
             }
         }
 
         Ok(())
     }
+
+    /// Validate the text fields of an update; unset fields are left alone
+    fn validate_update_input(input: &UpdateTradeInput) -> Result<(), String> {
+        if let Some(ref symbol) = input.symbol {
+            validate_symbol(symbol)?;
+        }
+
+        if let Some(ref strategy) = input.strategy {
+            validate_text_length("Strategy", strategy, MAX_STRATEGY_LENGTH)?;
+        }
+
+        if let Some(ref notes) = input.notes {
+            validate_text_length("Notes", notes, MAX_NOTES_LENGTH)?;
+        }
+
+        if let Some(ref entry_time) = input.entry_time {
+            validate_time_format("Entry time", entry_time)?;
+        }
+
+        if let Some(ref exit_time) = input.exit_time {
+            validate_time_format("Exit time", exit_time)?;
+        }
+
+        Ok(())
+    }
+
+    /// Snap entry, exit and stop prices onto the instrument's tick grid
+    fn snap_input_prices(
+        mut input: CreateTradeInput,
+        instrument: Option<&Instrument>,
+    ) -> Result<CreateTradeInput, String> {
+        let tick_size = instrument.and_then(|i| i.tick_size);
+        let asset_class = input
+            .asset_class
+            .or_else(|| instrument.and_then(|i| AssetClass::from_str(&i.asset_class)))
+            .unwrap_or(AssetClass::Stock);
+
+        input.entry_price = Self::snap_price("Entry price", input.entry_price, tick_size, asset_class)?;
+        input.exit_price = input
+            .exit_price
+            .map(|p| Self::snap_price("Exit price", p, tick_size, asset_class))
+            .transpose()?;
+        input.stop_loss_price = input
+            .stop_loss_price
+            .map(|p| Self::snap_price("Stop loss price", p, tick_size, asset_class))
+            .transpose()?;
+
+        if let Some(ref mut exits) = input.exits {
+            for (i, exit) in exits.iter_mut().enumerate() {
+                let label = format!("Exit {} price", i + 1);
+                exit.price = Self::snap_price(&label, exit.price, tick_size, asset_class)?;
+            }
+        }
+
+        Ok(input)
+    }
+
+    /// Snap one price, falling back to the asset class tick when the instrument has none
+    fn snap_price(
+        label: &str,
+        price: f64,
+        tick_size: Option<f64>,
+        asset_class: AssetClass,
+    ) -> Result<f64, String> {
+        let tick = tick_size.unwrap_or_else(|| asset_class.default_tick_size(price));
+        snap_to_tick(price, tick)
+            .ok_or_else(|| format!("{} {} is not a multiple of the {} tick size", label, price, tick))
+    }
+}
+
+/// Check a symbol is non-empty, bounded and made of ticker characters
+/// Allows option descriptions like "NVDA FEB13'26 190 PUT" and OCC codes
+fn validate_symbol(symbol: &str) -> Result<(), String> {
+    let symbol = symbol.trim();
+    if symbol.is_empty() {
+        return Err("Symbol is required".to_string());
+    }
+    if symbol.chars().count() > MAX_SYMBOL_LENGTH {
+        return Err(format!("Symbol cannot exceed {} characters", MAX_SYMBOL_LENGTH));
+    }
+    if let Some(c) = symbol
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && !" ./-_'^:=".contains(*c))
+    {
+        return Err(format!("Symbol contains invalid character '{}'", c));
+    }
+    Ok(())
+}
+
+fn validate_text_length(field: &str, value: &str, max_length: usize) -> Result<(), String> {
+    if value.chars().count() > max_length {
+        return Err(format!("{} cannot exceed {} characters", field, max_length));
+    }
+    Ok(())
+}
+
+/// Times must be zero-padded "HH:MM" or "HH:MM:SS"
+fn validate_time_format(field: &str, value: &str) -> Result<(), String> {
+    let well_formed = matches!(value.len(), 5 | 8)
+        && value
+            .bytes()
+            .enumerate()
+            .all(|(i, b)| if i % 3 == 2 { b == b':' } else { b.is_ascii_digit() });
+    if !well_formed || parse_time_value(value).is_err() {
+        return Err(format!("{} must be in HH:MM or HH:MM:SS format: {}", field, value));
+    }
+    Ok(())
 }
 
 fn parse_time_value(value: &str) -> Result<NaiveTime, String> {
@@ -432,6 +1123,8 @@ mod tests {
             entry_price: 150.0,
             exit_price: Some(155.0),
             stop_loss_price: Some(145.0),
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(10.0),
@@ -565,6 +1258,79 @@ mod tests {
         assert!(TradeService::validate_input(&input).is_ok());
     }
 
+    #[test]
+    fn test_validate_input_symbol_format() {
+        let mut input = valid_input();
+        input.symbol = "NVDA FEB13'26 190 PUT".to_string();
+        assert!(TradeService::validate_input(&input).is_ok());
+
+        input.symbol = "   ".to_string();
+        assert_eq!(TradeService::validate_input(&input).unwrap_err(), "Symbol is required");
+
+        input.symbol = "AAPL; DROP".to_string();
+        assert!(TradeService::validate_input(&input).unwrap_err().contains("invalid character ';'"));
+
+        input.symbol = "A".repeat(MAX_SYMBOL_LENGTH + 1);
+        assert!(TradeService::validate_input(&input).unwrap_err().starts_with("Symbol cannot exceed"));
+    }
+
+    #[test]
+    fn test_validate_input_text_lengths() {
+        let mut input = valid_input();
+        input.strategy = Some("s".repeat(MAX_STRATEGY_LENGTH + 1));
+        assert!(TradeService::validate_input(&input).unwrap_err().starts_with("Strategy cannot exceed"));
+
+        let mut input = valid_input();
+        input.notes = Some("n".repeat(MAX_NOTES_LENGTH + 1));
+        assert!(TradeService::validate_input(&input).unwrap_err().starts_with("Notes cannot exceed"));
+    }
+
+    #[test]
+    fn test_validate_input_time_format() {
+        let mut input = valid_input();
+        input.entry_time = Some("09:30".to_string());
+        input.exit_time = Some("15:59:59".to_string());
+        assert!(TradeService::validate_input(&input).is_ok());
+
+        input.entry_time = Some("9:30".to_string());
+        assert!(TradeService::validate_input(&input).unwrap_err().starts_with("Entry time must be"));
+
+        input.entry_time = Some("09:30".to_string());
+        input.exit_time = Some("25:00".to_string());
+        assert!(TradeService::validate_input(&input).unwrap_err().starts_with("Exit time must be"));
+    }
+
+    #[test]
+    fn test_validate_update_input() {
+        let update = UpdateTradeInput {
+            account_id: None,
+            symbol: Some("BRK.B".to_string()),
+            trade_number: None,
+            trade_date: None,
+            direction: None,
+            quantity: None,
+            entry_price: None,
+            exit_price: None,
+            stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
+            entry_time: Some("09:30:00".to_string()),
+            exit_time: None,
+            fees: None,
+            strategy: None,
+            notes: None,
+            screenshot_url: None,
+            status: None,
+        };
+        assert!(TradeService::validate_update_input(&update).is_ok());
+
+        let bad_time = UpdateTradeInput { exit_time: Some("noon".to_string()), ..update.clone() };
+        assert!(TradeService::validate_update_input(&bad_time).unwrap_err().starts_with("Exit time must be"));
+
+        let bad_symbol = UpdateTradeInput { symbol: Some(String::new()), ..update };
+        assert_eq!(TradeService::validate_update_input(&bad_symbol).unwrap_err(), "Symbol is required");
+    }
+
     #[test]
     fn test_validate_input_minimal_valid() {
         let input = CreateTradeInput {
@@ -578,6 +1344,8 @@ mod tests {
             entry_price: 200.0,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -594,10 +1362,12 @@ mod tests {
 #[cfg(test)]
 mod integration_tests {
     use super::*;
+    use crate::services::AccountService;
     use crate::models::{Direction, ExitExecution, TradeResult};
     use crate::test_utils::{
         create_test_db, setup_test_user_and_account, create_test_trade_input,
-        create_losing_long_trade, create_open_trade,
+        create_losing_long_trade, create_open_trade, create_test_trade_with_exits,
+        create_test_trade_with_executions,
     };
 
     #[tokio::test]
@@ -621,19 +1391,312 @@ mod integration_tests {
         assert!(trade.gross_pnl.is_some());
         assert!((trade.gross_pnl.unwrap() - 500.0).abs() < 0.01);
 
-        // Net: 500 - 10 = 490
-        assert!(trade.net_pnl.is_some());
-        assert!((trade.net_pnl.unwrap() - 490.0).abs() < 0.01);
+        // Net: 500 - 10 = 490
+        assert!(trade.net_pnl.is_some());
+        assert!((trade.net_pnl.unwrap() - 490.0).abs() < 0.01);
+
+        // Result should be Win
+        assert_eq!(trade.result, Some(TradeResult::Win));
+
+        // Manual trade creation should create an entry execution row.
+        let executions = TradeService::get_trade_executions(&pool, &trade.trade.id)
+            .await
+            .expect("Failed to get executions");
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0].execution_type, "entry");
+    }
+
+    #[tokio::test]
+    async fn test_trade_prices_validated_against_tick_size() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // Fat-fingered stock price is off the default penny tick
+        let mut input = create_test_trade_input(&account_id, "AAPL");
+        input.entry_price = 15.003;
+        let err = TradeService::create_trade(&pool, &user_id, input)
+            .await
+            .expect_err("Off-tick entry should be rejected");
+        assert!(err.contains("Entry price 15.003"));
+
+        // Float noise is snapped onto the grid
+        let mut input = create_test_trade_input(&account_id, "AAPL");
+        input.entry_price = 150.10000000000002;
+        let trade = TradeService::create_trade(&pool, &user_id, input)
+            .await
+            .expect("Failed to create trade");
+        assert_eq!(trade.trade.entry_price, 150.1);
+
+        // A custom tick size applies to updates as well
+        TradeService::set_instrument_tick_size(&pool, "AAPL", Some(0.25))
+            .await
+            .expect("Failed to set tick size");
+        let update = UpdateTradeInput {
+            account_id: None,
+            symbol: None,
+            trade_number: None,
+            trade_date: None,
+            direction: None,
+            quantity: None,
+            entry_price: None,
+            exit_price: None,
+            stop_loss_price: Some(145.1),
+            target_price: None,
+            max_favorable_price: None,
+            entry_time: None,
+            exit_time: None,
+            fees: None,
+            strategy: None,
+            notes: None,
+            screenshot_url: None,
+            status: None,
+        };
+        let err = TradeService::update_trade(&pool, &user_id, &trade.trade.id, update)
+            .await
+            .expect_err("Off-tick stop should be rejected");
+        assert!(err.contains("Stop loss price"));
+    }
+
+    #[tokio::test]
+    async fn test_trade_operations_enforce_account_ownership() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        sqlx::query("INSERT INTO users (id, email) VALUES (?, ?)")
+            .bind("other-user")
+            .bind("other@example.com")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO accounts (id, user_id, name, base_currency) VALUES (?, ?, ?, ?)")
+            .bind("other-account")
+            .bind("other-user")
+            .bind("Other Account")
+            .bind("USD")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Cannot create a trade in someone else's account
+        let result = TradeService::create_trade(&pool, &user_id, create_test_trade_input("other-account", "AAPL")).await;
+        assert_eq!(result.unwrap_err(), "Account not found: other-account");
+
+        let trade = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .expect("Failed to create trade");
+        let id = trade.trade.id;
+
+        // Other users see the trade as missing
+        assert!(TradeService::get_trade(&pool, "other-user", &id).await.unwrap().is_none());
+        let err = TradeService::set_result_override(&pool, "other-user", &id, None).await.unwrap_err();
+        assert_eq!(err, format!("Trade not found: {}", id));
+        let err = TradeService::delete_trade(&pool, "other-user", &id).await.unwrap_err();
+        assert_eq!(err, format!("Trade not found: {}", id));
+
+        // The owner cannot move the trade into someone else's account
+        let update = UpdateTradeInput {
+            account_id: Some("other-account".to_string()),
+            symbol: None,
+            trade_number: None,
+            trade_date: None,
+            direction: None,
+            quantity: None,
+            entry_price: None,
+            exit_price: None,
+            stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
+            entry_time: None,
+            exit_time: None,
+            fees: None,
+            strategy: None,
+            notes: None,
+            screenshot_url: None,
+            status: None,
+        };
+        let err = TradeService::update_trade(&pool, &user_id, &id, update.clone()).await.unwrap_err();
+        assert_eq!(err, "Account not found: other-account");
+        let err = TradeService::update_trade(&pool, "other-user", &id, update).await.unwrap_err();
+        assert_eq!(err, format!("Trade not found: {}", id));
+
+        assert!(TradeService::get_trade(&pool, &user_id, &id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_trade_ref_codes_sequence_per_year_and_resolve() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let first = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .expect("Failed to create trade");
+        let second = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "MSFT"))
+            .await
+            .expect("Failed to create trade");
+        let next_year = TradeService::create_trade(
+            &pool,
+            &user_id,
+            create_open_trade(&account_id, "TSLA", NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(), 200.0, 10.0),
+        )
+        .await
+        .expect("Failed to create trade");
+
+        assert_eq!(first.trade.ref_code.as_deref(), Some("T-2024-0001"));
+        assert_eq!(second.trade.ref_code.as_deref(), Some("T-2024-0002"));
+        assert_eq!(next_year.trade.ref_code.as_deref(), Some("T-2025-0001"));
+
+        let resolved = TradeService::resolve_trade_ref(&pool, &user_id, " t-2024-0002 ")
+            .await
+            .expect("Failed to resolve")
+            .expect("Trade not found");
+        assert_eq!(resolved.trade.id, second.trade.id);
+
+        let other_user = TradeService::resolve_trade_ref(&pool, "other-user", "T-2024-0002")
+            .await
+            .expect("Failed to resolve");
+        assert!(other_user.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_trade_stats_match_filter() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // Win: +490 on 2024-01-15
+        TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .expect("Failed to create trade");
+        // Loss: -200 on 2024-02-01
+        TradeService::create_trade(
+            &pool,
+            &user_id,
+            create_losing_long_trade(&account_id, "MSFT", NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(), 100.0, 98.0, 100.0),
+        )
+        .await
+        .expect("Failed to create trade");
+        // Open, no PnL
+        TradeService::create_trade(
+            &pool,
+            &user_id,
+            create_open_trade(&account_id, "aapl", NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(), 160.0, 10.0),
+        )
+        .await
+        .expect("Failed to create trade");
+
+        let all = TradeService::get_trade_stats(&pool, &user_id, &TradeFilter::default())
+            .await
+            .expect("Failed to get stats");
+        assert_eq!(all.trade_count, 3);
+        assert_eq!(all.closed_count, 2);
+        assert!((all.total_pnl - 290.0).abs() < 0.01);
+        assert_eq!(all.win_rate, Some(0.5));
+        assert_eq!(all.first_trade_date, NaiveDate::from_ymd_opt(2024, 1, 15));
+        assert_eq!(all.last_trade_date, NaiveDate::from_ymd_opt(2024, 3, 1));
+
+        let aapl = TradeFilter {
+            symbol: Some("AAPL".to_string()),
+            ..Default::default()
+        };
+        let stats = TradeService::get_trade_stats(&pool, &user_id, &aapl).await.unwrap();
+        assert_eq!(stats.trade_count, 2);
+
+        let losses = TradeFilter {
+            result: Some(TradeResult::Loss),
+            ..Default::default()
+        };
+        let stats = TradeService::get_trade_stats(&pool, &user_id, &losses).await.unwrap();
+        assert_eq!(stats.trade_count, 1);
+        assert!((stats.total_pnl + 200.0).abs() < 0.01);
+        assert_eq!(stats.win_rate, Some(0.0));
+
+        let none = TradeFilter {
+            start_date: NaiveDate::from_ymd_opt(2025, 1, 1),
+            ..Default::default()
+        };
+        let stats = TradeService::get_trade_stats(&pool, &user_id, &none).await.unwrap();
+        assert_eq!(stats.trade_count, 0);
+        assert!(stats.win_rate.is_none());
+        assert!(stats.first_trade_date.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_trade_type_classification_and_filter() {
+        use crate::models::{TradeType, TradeTypeThresholds};
+
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // Held 09:30 to 10:45 on the same day
+        let day_trade = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .unwrap();
+        assert_eq!(day_trade.trade_type, Some(TradeType::Day));
+
+        // Held over three nights
+        let mut input = create_test_trade_input(&account_id, "MSFT");
+        input.exit_price = None;
+        input.exits = Some(vec![ExitExecution {
+            id: None,
+            exit_date: NaiveDate::from_ymd_opt(2024, 1, 18).unwrap(),
+            exit_time: None,
+            quantity: 100.0,
+            price: 155.0,
+            fees: None,
+        }]);
+        let swing = TradeService::create_trade(&pool, &user_id, input).await.unwrap();
+        assert_eq!(swing.trade.exit_date, NaiveDate::from_ymd_opt(2024, 1, 18));
+        assert_eq!(swing.trade_type, Some(TradeType::Swing));
+
+        let swings = TradeFilter {
+            trade_type: Some(TradeType::Swing),
+            ..Default::default()
+        };
+        let trades = TradeService::find_trades(&pool, &user_id, &swings).await.unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].trade.id, swing.trade.id);
+
+        // The SQL filter follows the configured thresholds like the derived type does
+        let thresholds = TradeTypeThresholds {
+            scalp_max_minutes: 180.0,
+            swing_max_days: 2,
+        };
+        SettingsService::save_trade_type_thresholds(&pool, &thresholds).await.unwrap();
+        for (trade_type, trade_id) in [(TradeType::Scalp, &day_trade.trade.id), (TradeType::Position, &swing.trade.id)] {
+            let filter = TradeFilter {
+                trade_type: Some(trade_type),
+                ..Default::default()
+            };
+            let trades = TradeService::find_trades(&pool, &user_id, &filter).await.unwrap();
+            assert_eq!(trades.len(), 1);
+            assert_eq!(&trades[0].trade.id, trade_id);
+            assert_eq!(trades[0].trade_type, Some(trade_type));
+        }
+    }
 
-        // Result should be Win
-        assert_eq!(trade.result, Some(TradeResult::Win));
+    #[tokio::test]
+    async fn test_duration_filter_counts_nights_held() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
 
-        // Manual trade creation should create an entry execution row.
-        let executions = TradeService::get_trade_executions(&pool, &trade.trade.id)
-            .await
-            .expect("Failed to get executions");
-        assert_eq!(executions.len(), 1);
-        assert_eq!(executions[0].execution_type, "entry");
+        // Entered at 09:30 and exited ten minutes later in clock time, but on the next day
+        let mut input = create_test_trade_input(&account_id, "AAPL");
+        input.exit_price = None;
+        input.exits = Some(vec![ExitExecution {
+            id: None,
+            exit_date: NaiveDate::from_ymd_opt(2024, 1, 16).unwrap(),
+            exit_time: Some("09:40".to_string()),
+            quantity: 100.0,
+            price: 155.0,
+            fees: None,
+        }]);
+        TradeService::create_trade(&pool, &user_id, input).await.unwrap();
+
+        let within = |minutes| TradeFilter {
+            max_duration_minutes: Some(minutes),
+            ..Default::default()
+        };
+        assert!(TradeService::find_trades(&pool, &user_id, &within(30.0)).await.unwrap().is_empty());
+        assert_eq!(TradeService::find_trades(&pool, &user_id, &within(1450.0)).await.unwrap().len(), 1);
     }
 
     #[tokio::test]
@@ -652,6 +1715,8 @@ mod integration_tests {
             entry_price: 100.0,
             exit_price: Some(110.0),  // +10 per share
             stop_loss_price: Some(95.0), // -5 risk per share
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(0.0),
@@ -693,6 +1758,8 @@ mod integration_tests {
             entry_price: 200.0,
             exit_price: Some(180.0), // Short wins when price goes down
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(0.0),
@@ -751,6 +1818,8 @@ mod integration_tests {
             entry_price: 100.0,
             exit_price: Some(100.0), // Same as entry
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(0.0),
@@ -789,6 +1858,8 @@ mod integration_tests {
             entry_price: 1.50,
             exit_price: Some(2.00),
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(8.0),
@@ -822,7 +1893,7 @@ mod integration_tests {
             .await
             .expect("Failed to create trade");
 
-        let fetched = TradeService::get_trade(&pool, &created.trade.id)
+        let fetched = TradeService::get_trade(&pool, &user_id, &created.trade.id)
             .await
             .expect("Failed to get trade")
             .expect("Trade not found");
@@ -917,6 +1988,8 @@ mod integration_tests {
             entry_price: None,
             exit_price: Some(160.0), // Changed from 155.0
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -926,7 +1999,7 @@ mod integration_tests {
             status: None,
         };
 
-        let updated = TradeService::update_trade(&pool, &trade.trade.id, update)
+        let updated = TradeService::update_trade(&pool, &user_id, &trade.trade.id, update)
             .await
             .expect("Failed to update trade");
 
@@ -958,6 +2031,8 @@ mod integration_tests {
             entry_price: None,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -967,7 +2042,7 @@ mod integration_tests {
             status: None,
         };
 
-        let updated = TradeService::update_trade(&pool, &trade.trade.id, update)
+        let updated = TradeService::update_trade(&pool, &user_id, &trade.trade.id, update)
             .await
             .expect("Failed to update trade");
 
@@ -985,18 +2060,287 @@ mod integration_tests {
             .expect("Failed to create trade");
 
         // Delete trade
-        TradeService::delete_trade(&pool, &trade.trade.id)
+        TradeService::delete_trade(&pool, &user_id, &trade.trade.id)
             .await
             .expect("Failed to delete trade");
 
         // Verify trade is gone
-        let result = TradeService::get_trade(&pool, &trade.trade.id)
+        let result = TradeService::get_trade(&pool, &user_id, &trade.trade.id)
             .await
             .expect("Query failed");
 
         assert!(result.is_none());
     }
 
+    #[tokio::test]
+    async fn test_create_trades_is_all_or_nothing_with_row_errors() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let mut bad_price = create_test_trade_input(&account_id, "MSFT");
+        bad_price.entry_price = 0.0;
+        let bad_account = create_test_trade_input("missing-account", "NVDA");
+        let inputs = vec![create_test_trade_input(&account_id, "AAPL"), bad_price, bad_account];
+
+        let result = TradeService::create_trades(&pool, &user_id, inputs).await.unwrap();
+        assert!(result.created.is_empty());
+        assert_eq!(result.errors.len(), 2);
+        assert_eq!(result.errors[0].row, 2);
+        assert!(result.errors[0].message.contains("Entry price"));
+        assert_eq!(result.errors[1].row, 3);
+        assert!(result.errors[1].message.contains("Account not found"));
+
+        let trades = TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap();
+        assert!(trades.is_empty());
+
+        // An insert failing after earlier rows went in leaves none of them behind
+        sqlx::query(
+            "CREATE TRIGGER fail_msft BEFORE INSERT ON trades \
+             WHEN NEW.instrument_id = (SELECT id FROM instruments WHERE symbol = 'MSFT') \
+             BEGIN SELECT RAISE(ABORT, 'disk full'); END",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let inputs = vec![
+            create_test_trade_input(&account_id, "AAPL"),
+            create_test_trade_input(&account_id, "MSFT"),
+        ];
+        let result = TradeService::create_trades(&pool, &user_id, inputs).await.unwrap();
+        assert!(result.created.is_empty());
+        assert_eq!(result.errors[0].row, 2);
+        assert!(result.errors[0].message.contains("disk full"));
+        let trades = TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap();
+        assert!(trades.is_empty());
+        // Instruments created for the batch are rolled back with it
+        assert!(InstrumentRepository::get_by_symbol(&pool, "AAPL").await.unwrap().is_none());
+        sqlx::query("DROP TRIGGER fail_msft").execute(&pool).await.unwrap();
+
+        let inputs = vec![
+            create_test_trade_input(&account_id, "AAPL"),
+            create_test_trade_input(&account_id, "MSFT"),
+        ];
+        let result = TradeService::create_trades(&pool, &user_id, inputs).await.unwrap();
+        assert!(result.errors.is_empty());
+        assert_eq!(result.created.len(), 2);
+        assert_eq!(result.created[1].trade.symbol, "MSFT");
+    }
+
+    #[tokio::test]
+    async fn test_result_override_kept_apart_from_computed_result() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let input = create_test_trade_input(&account_id, "AAPL");
+        let trade = TradeService::create_trade(&pool, &user_id, input)
+            .await
+            .expect("Failed to create trade");
+        assert_eq!(trade.result, Some(TradeResult::Win));
+
+        let overridden = TradeService::set_result_override(&pool, &user_id, &trade.trade.id, Some("loss".to_string()))
+            .await
+            .expect("Failed to set override");
+        assert_eq!(overridden.result, Some(TradeResult::Win));
+        assert_eq!(overridden.trade.result_override, Some(TradeResult::Loss));
+        assert_eq!(overridden.effective_result(), Some(TradeResult::Loss));
+
+        let result = TradeService::set_result_override(&pool, &user_id, &trade.trade.id, Some("scratch".to_string())).await;
+        assert!(result.unwrap_err().contains("Invalid trade result"));
+
+        let cleared = TradeService::set_result_override(&pool, &user_id, &trade.trade.id, None)
+            .await
+            .expect("Failed to clear override");
+        assert_eq!(cleared.trade.result_override, None);
+        assert_eq!(cleared.effective_result(), Some(TradeResult::Win));
+    }
+
+    #[tokio::test]
+    async fn test_result_basis_setting_classifies_by_gross_or_net() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // +5 before commissions, -5 after the 10 in fees
+        let input = CreateTradeInput {
+            exit_price: Some(150.05),
+            ..create_test_trade_input(&account_id, "AAPL")
+        };
+        let trade = TradeService::create_trade(&pool, &user_id, input).await.unwrap();
+        assert_eq!(trade.result, Some(TradeResult::Loss));
+
+        let wins = TradeFilter {
+            result: Some(TradeResult::Win),
+            ..Default::default()
+        };
+        assert!(TradeService::find_trades(&pool, &user_id, &wins).await.unwrap().is_empty());
+
+        SettingsService::save_result_basis(&pool, ResultBasis::Gross).await.unwrap();
+
+        let fetched = TradeService::get_trade(&pool, &user_id, &trade.trade.id).await.unwrap().unwrap();
+        assert_eq!(fetched.result, Some(TradeResult::Win));
+        assert!((fetched.net_pnl.unwrap() + 5.0).abs() < 0.01);
+        assert!((fetched.gross_pnl.unwrap() - 5.0).abs() < 0.01);
+        assert_eq!(TradeService::find_trades(&pool, &user_id, &wins).await.unwrap().len(), 1);
+
+        let stats = TradeService::get_trade_stats(&pool, &user_id, &TradeFilter::default()).await.unwrap();
+        assert_eq!(stats.win_count, 1);
+        assert_eq!(stats.loss_count, 0);
+        assert!((stats.total_pnl + 5.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_locked_period_blocks_update_and_delete() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        // Trade date is 2024-01-15
+        let input = create_test_trade_input(&account_id, "AAPL");
+        let trade = TradeService::create_trade(&pool, &user_id, input)
+            .await
+            .expect("Failed to create trade");
+
+        AccountService::lock_period(&pool, &user_id, &account_id, NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
+            .await
+            .expect("Failed to lock period");
+
+        let fetched = TradeService::get_trade(&pool, &user_id, &trade.trade.id).await.unwrap().unwrap();
+        assert!(fetched.trade.is_locked);
+
+        let update = UpdateTradeInput {
+            account_id: None,
+            symbol: None,
+            trade_number: None,
+            trade_date: None,
+            direction: None,
+            quantity: None,
+            entry_price: None,
+            exit_price: None,
+            stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
+            entry_time: None,
+            exit_time: None,
+            fees: None,
+            strategy: None,
+            notes: Some("Edited".to_string()),
+            screenshot_url: None,
+            status: None,
+        };
+
+        let result = TradeService::update_trade(&pool, &user_id, &trade.trade.id, update.clone()).await;
+        assert!(result.unwrap_err().contains("locked period"));
+
+        let result = TradeService::delete_trade(&pool, &user_id, &trade.trade.id).await;
+        assert!(result.unwrap_err().contains("locked period"));
+
+        AccountService::unlock_period(&pool, &user_id, &account_id)
+            .await
+            .expect("Failed to unlock period");
+
+        let updated = TradeService::update_trade(&pool, &user_id, &trade.trade.id, update)
+            .await
+            .expect("Failed to update trade");
+        assert_eq!(updated.trade.notes, Some("Edited".to_string()));
+        assert!(!updated.trade.is_locked);
+    }
+
+    #[tokio::test]
+    async fn test_locked_period_blocks_creating_trades() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        AccountService::lock_period(&pool, &user_id, &account_id, NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
+            .await
+            .expect("Failed to lock period");
+
+        // Trade date is 2024-01-15
+        let result = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL")).await;
+        assert!(result.unwrap_err().contains("locked period"));
+
+        let mut later = create_test_trade_input(&account_id, "MSFT");
+        later.trade_date = NaiveDate::from_ymd_opt(2024, 2, 5).unwrap();
+        let inputs = vec![later, create_test_trade_input(&account_id, "AAPL")];
+        let result = TradeService::create_trades(&pool, &user_id, inputs).await.unwrap();
+        assert!(result.created.is_empty());
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].row, 2);
+        assert!(result.errors[0].message.contains("locked period"));
+        assert!(TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap().is_empty());
+
+        // Exits dated in the locked period are blocked too
+        let mut exited_in_lock = create_test_trade_input(&account_id, "NVDA");
+        exited_in_lock.trade_date = NaiveDate::from_ymd_opt(2024, 2, 5).unwrap();
+        exited_in_lock.exit_price = None;
+        exited_in_lock.exit_time = None;
+        exited_in_lock.exits = Some(vec![ExitExecution {
+            id: None,
+            exit_date: NaiveDate::from_ymd_opt(2024, 1, 20).unwrap(),
+            exit_time: None,
+            quantity: 100.0,
+            price: 155.0,
+            fees: None,
+        }]);
+        let result = TradeService::create_trade(&pool, &user_id, exited_in_lock).await;
+        assert!(result.unwrap_err().contains("Trade date 2024-01-20 is in a locked period"));
+    }
+
+    #[tokio::test]
+    async fn test_locked_period_blocks_execution_quality() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let trade = TradeService::create_trade(&pool, &user_id, create_test_trade_input(&account_id, "AAPL"))
+            .await
+            .expect("Failed to create trade");
+        let executions = TradeService::get_trade_executions(&pool, &trade.trade.id)
+            .await
+            .expect("Failed to get executions");
+
+        AccountService::lock_period(&pool, &user_id, &account_id, NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
+            .await
+            .expect("Failed to lock period");
+        let result =
+            TradeService::update_execution_quality(&pool, &user_id, &executions[0].id, Some(149.95), Some("limit".to_string())).await;
+        assert!(result.unwrap_err().contains("locked period"));
+    }
+
+    #[tokio::test]
+    async fn test_update_cannot_move_trade_into_locked_period() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let mut input = create_test_trade_input(&account_id, "AAPL");
+        input.trade_date = NaiveDate::from_ymd_opt(2024, 2, 5).unwrap();
+        let trade = TradeService::create_trade(&pool, &user_id, input)
+            .await
+            .expect("Failed to create trade");
+
+        AccountService::lock_period(&pool, &user_id, &account_id, NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
+            .await
+            .expect("Failed to lock period");
+
+        let update = UpdateTradeInput {
+            account_id: None,
+            symbol: None,
+            trade_number: None,
+            trade_date: Some(NaiveDate::from_ymd_opt(2024, 1, 20).unwrap()),
+            direction: None,
+            quantity: None,
+            entry_price: None,
+            exit_price: None,
+            stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
+            entry_time: None,
+            exit_time: None,
+            fees: None,
+            strategy: None,
+            notes: None,
+            screenshot_url: None,
+            status: None,
+        };
+
+        let result = TradeService::update_trade(&pool, &user_id, &trade.trade.id, update).await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_create_trade_validation_error() {
         let pool = create_test_db().await;
@@ -1067,6 +2411,8 @@ mod integration_tests {
             entry_price: 100.0,
             exit_price: None, // Will be set by exits
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(5.0), // Entry fees
@@ -1126,6 +2472,8 @@ mod integration_tests {
             entry_price: 100.0,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(0.0),
@@ -1163,6 +2511,152 @@ mod integration_tests {
         assert_eq!(trade.trade.status, Status::Closed);
         // Gross PnL: (112 - 100) * 100 = 1200
         assert!((trade.gross_pnl.unwrap() - 1200.0).abs() < 0.01);
+        assert!(trade.executions.is_none());
+
+        // Partial exits can be loaded with the trade for editing
+        let mut trades = vec![trade];
+        TradeService::attach_executions(&pool, &mut trades, false).await.unwrap();
+        let executions = trades[0].executions.as_ref().unwrap();
+        let exit_quantities: Vec<f64> = executions
+            .iter()
+            .filter(|e| e.execution_type == "exit")
+            .map(|e| e.quantity)
+            .collect();
+        assert_eq!(exit_quantities, vec![60.0, 40.0]);
+    }
+
+    #[tokio::test]
+    async fn test_trade_with_exits_fixture_stores_executions() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let jan = |day| NaiveDate::from_ymd_opt(2024, 1, day).unwrap();
+        let input = create_test_trade_with_exits(&account_id, "MSFT", jan(1), 100.0, &[(jan(2), 60.0, 110.0), (jan(3), 40.0, 115.0)]);
+        let trade = create_test_trade_with_executions(&pool, &user_id, input).await;
+
+        assert_eq!(trade.trade.quantity, Some(100.0));
+        assert_eq!(trade.trade.status, Status::Closed);
+        assert!((trade.gross_pnl.unwrap() - 1200.0).abs() < 0.01);
+        let executions: Vec<(&str, f64, f64)> = trade
+            .executions
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|e| (e.execution_type.as_str(), e.quantity, e.price))
+            .collect();
+        assert_eq!(executions, vec![("entry", 100.0, 100.0), ("exit", 60.0, 110.0), ("exit", 40.0, 115.0)]);
+    }
+
+    #[tokio::test]
+    async fn test_update_trade_stays_consistent_with_exit_executions() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let input = CreateTradeInput {
+            account_id: account_id.clone(),
+            symbol: "MSFT".to_string(),
+            asset_class: None,
+            trade_number: None,
+            trade_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            direction: Direction::Long,
+            quantity: Some(100.0),
+            entry_price: 100.0,
+            exit_price: None,
+            stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
+            entry_time: None,
+            exit_time: None,
+            fees: Some(0.0),
+            strategy: None,
+            notes: None,
+            screenshot_url: None,
+            status: None,
+            exits: Some(vec![
+                ExitExecution {
+                    id: None,
+                    exit_date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+                    exit_time: None,
+                    quantity: 60.0,
+                    price: 110.0,
+                    fees: None,
+                },
+                ExitExecution {
+                    id: None,
+                    exit_date: NaiveDate::from_ymd_opt(2024, 1, 3).unwrap(),
+                    exit_time: None,
+                    quantity: 40.0,
+                    price: 115.0,
+                    fees: None,
+                },
+            ]),
+        };
+        let trade = TradeService::create_trade(&pool, &user_id, input)
+            .await
+            .expect("Failed to create trade");
+        let id = trade.trade.id;
+
+        let no_changes = UpdateTradeInput {
+            account_id: None,
+            symbol: None,
+            trade_number: None,
+            trade_date: None,
+            direction: None,
+            quantity: None,
+            entry_price: None,
+            exit_price: None,
+            stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
+            entry_time: None,
+            exit_time: None,
+            fees: None,
+            strategy: None,
+            notes: None,
+            screenshot_url: None,
+            status: None,
+        };
+
+        // Edits that contradict the executions are blocked
+        let shrink = UpdateTradeInput { quantity: Some(50.0), ..no_changes.clone() };
+        let err = TradeService::update_trade(&pool, &user_id, &id, shrink).await.unwrap_err();
+        assert!(err.contains("cannot be less than the 100 already exited"));
+
+        let reopen = UpdateTradeInput { status: Some(Status::Open), ..no_changes.clone() };
+        let err = TradeService::update_trade(&pool, &user_id, &id, reopen).await.unwrap_err();
+        assert!(err.starts_with("Status cannot be open"));
+
+        let new_exit = UpdateTradeInput { exit_price: Some(120.0), ..no_changes.clone() };
+        let err = TradeService::update_trade(&pool, &user_id, &id, new_exit).await.unwrap_err();
+        assert!(err.starts_with("Exit price 120 does not match"));
+
+        // A failed entry execution update leaves the trade as it was
+        sqlx::query(
+            "CREATE TRIGGER fail_entry BEFORE UPDATE ON trade_executions \
+             BEGIN SELECT RAISE(ABORT, 'disk full'); END",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let grow = UpdateTradeInput { quantity: Some(150.0), ..no_changes.clone() };
+        let err = TradeService::update_trade(&pool, &user_id, &id, grow).await.unwrap_err();
+        assert!(err.contains("disk full"));
+        let unchanged = TradeService::get_trade(&pool, &user_id, &id).await.unwrap().unwrap();
+        assert_eq!(unchanged.trade.quantity, Some(100.0));
+        assert_eq!(unchanged.trade.status, Status::Closed);
+        sqlx::query("DROP TRIGGER fail_entry").execute(&pool).await.unwrap();
+
+        // Growing the position reopens it and resizes the entry execution
+        let grow = UpdateTradeInput { quantity: Some(150.0), ..no_changes };
+        let updated = TradeService::update_trade(&pool, &user_id, &id, grow)
+            .await
+            .expect("Failed to update trade");
+        assert_eq!(updated.trade.status, Status::Open);
+        assert!((updated.trade.exit_price.unwrap() - 112.0).abs() < 1e-9);
+
+        let executions = TradeService::get_trade_executions(&pool, &id).await.unwrap();
+        let entry = executions.iter().find(|e| e.execution_type == "entry").unwrap();
+        assert_eq!(entry.quantity, 150.0);
     }
 
     #[tokio::test]
@@ -1181,6 +2675,8 @@ mod integration_tests {
             entry_price: 200.0,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -1223,6 +2719,8 @@ mod integration_tests {
             entry_price: 500.0,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -1261,6 +2759,8 @@ mod integration_tests {
             entry_price: 150.0,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -1299,6 +2799,8 @@ mod integration_tests {
             entry_price: 300.0,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: None,
@@ -1337,6 +2839,8 @@ mod integration_tests {
             entry_price: 100.0,
             exit_price: None,
             stop_loss_price: None,
+            target_price: None,
+            max_favorable_price: None,
             entry_time: None,
             exit_time: None,
             fees: Some(5.0), // Entry fees
@@ -1375,4 +2879,40 @@ mod integration_tests {
         // Net PnL: 1000 - 10 = 990
         assert!((trade.net_pnl.unwrap() - 990.0).abs() < 0.01);
     }
+
+    #[tokio::test]
+    async fn test_update_execution_quality() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+
+        let input = create_test_trade_input(&account_id, "AAPL");
+        let trade = TradeService::create_trade(&pool, &user_id, input)
+            .await
+            .expect("Failed to create trade");
+
+        let executions = TradeService::get_trade_executions(&pool, &trade.trade.id)
+            .await
+            .expect("Failed to get executions");
+        let entry = executions.iter().find(|e| e.execution_type == "entry").unwrap();
+
+        TradeService::update_execution_quality(&pool, &user_id, &entry.id, Some(149.95), Some("limit".to_string()))
+            .await
+            .expect("Failed to update execution");
+
+        let fills = TradeRepository::get_execution_fills(&pool, &user_id, None, None, None)
+            .await
+            .expect("Failed to get fills");
+        let fill = fills.iter().find(|f| f.execution_type == "entry").unwrap();
+        assert_eq!(fill.intended_price, Some(149.95));
+        assert_eq!(fill.order_type, Some(OrderType::Limit));
+
+        let invalid = TradeService::update_execution_quality(&pool, &user_id, &entry.id, None, Some("iceberg".to_string())).await;
+        assert!(invalid.is_err());
+
+        let missing = TradeService::update_execution_quality(&pool, &user_id, "missing", Some(1.0), None).await;
+        assert_eq!(missing.unwrap_err(), "Execution not found");
+
+        let other_user = TradeService::update_execution_quality(&pool, "other-user", &entry.id, Some(1.0), None).await;
+        assert_eq!(other_user.unwrap_err(), "Execution not found");
+    }
 }
@@ -0,0 +1,256 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use crate::models::WatchFolder;
+use crate::repository::{AccountRepository, ImportBatchRepository, WatchedFileRepository};
+use crate::services::import_service::{ImportResult, ImportService};
+use crate::services::settings_service::SettingsService;
+use crate::services::AccountService;
+
+/// Extensions of the files imported from the watched folder
+const IMPORT_EXTENSIONS: [&str; 2] = ["tlg", "csv"];
+/// How long a file must go unmodified before it is imported, so one still being written is not
+const SETTLE_TIME: Duration = Duration::from_secs(10);
+
+/// Outcome of importing one file of the watched folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchedImport {
+    pub file_name: String,
+    pub result: Option<ImportResult>, // None when the file could not be imported
+    pub error: Option<String>,
+}
+
+/// Imports broker exports dropped into a watched folder without a preview
+/// The scheduler scans the folder every minute. Each new or changed TLG or CSV file is imported
+/// like a previewed one with every trade selected and duplicates skipped, so a file that grows
+/// over the day only adds its new trades, and the broker's corrected fills replace those of the
+/// trades imported earlier.
+pub struct WatchFolderService;
+
+impl WatchFolderService {
+    pub async fn get_watch_folder(pool: &SqlitePool) -> Result<Option<WatchFolder>, String> {
+        SettingsService::get_watch_folder(pool).await
+    }
+
+    /// Watch a folder for exports to import into one of the user's accounts, or stop watching
+    pub async fn save_watch_folder(pool: &SqlitePool, user_id: &str, folder: Option<WatchFolder>) -> Result<(), String> {
+        if let Some(ref folder) = folder {
+            AccountRepository::get_by_id(pool, &folder.account_id)
+                .await
+                .map_err(|e| format!("Failed to get account: {}", e))?
+                .filter(|account| account.user_id == user_id)
+                .ok_or_else(|| format!("Account not found: {}", folder.account_id))?;
+        }
+        SettingsService::save_watch_folder(pool, folder.as_ref()).await
+    }
+
+    /// Import the watched folder's files that are new or changed since they were last imported
+    /// Files modified within SETTLE_TIME of `now` wait for the next scan. A file that fails is
+    /// retried on every scan, e.g. once a missing FX rate is added, but reported again only when it
+    /// fails with another error.
+    pub async fn scan(pool: &SqlitePool, user_id: &str, now: SystemTime) -> Result<Vec<WatchedImport>, String> {
+        let Some(folder) = SettingsService::get_watch_folder(pool).await? else {
+            return Ok(Vec::new());
+        };
+        let entries = fs::read_dir(&folder.directory)
+            .map_err(|e| format!("Failed to read watch folder {}: {}", folder.directory, e))?;
+
+        let mut files: Vec<(PathBuf, i64, i64)> = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let Ok(modified) = metadata.modified() else {
+                continue;
+            };
+            let settled = now.duration_since(modified).is_ok_and(|age| age >= SETTLE_TIME);
+            if metadata.is_file() && settled && is_import_file(&path) {
+                let modified_ms = modified.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as i64);
+                files.push((path, metadata.len() as i64, modified_ms));
+            }
+        }
+        files.sort();
+
+        let mut imports = Vec::new();
+        for (path, size, modified_ms) in files {
+            let path_key = path.to_string_lossy().into_owned();
+            let processed = WatchedFileRepository::get_version(pool, &path_key)
+                .await
+                .map_err(|e| format!("Failed to get watched files: {}", e))?;
+            let previous_error = match processed {
+                Some((processed_size, processed_ms, error)) if (processed_size, processed_ms) == (size, modified_ms) => {
+                    match error {
+                        Some(error) => Some(error),
+                        None => continue,
+                    }
+                }
+                _ => None,
+            };
+
+            let file_name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            let outcome = Self::import_file(pool, user_id, &folder.account_id, &path, &file_name).await;
+            let (batch_id, error) = match &outcome {
+                Ok(result) => (result.batch_id.as_deref(), None),
+                Err(e) => (None, Some(e.as_str())),
+            };
+            WatchedFileRepository::record(pool, user_id, &path_key, size, modified_ms, batch_id, error)
+                .await
+                .map_err(|e| format!("Failed to record watched file: {}", e))?;
+            if error.is_some() && error == previous_error.as_deref() {
+                continue;
+            }
+
+            let (result, error) = match outcome {
+                Ok(result) => (Some(result), None),
+                Err(e) => (None, Some(e)),
+            };
+            imports.push(WatchedImport { file_name, result, error });
+        }
+        Ok(imports)
+    }
+
+    async fn import_file(
+        pool: &SqlitePool,
+        user_id: &str,
+        account_id: &str,
+        path: &Path,
+        file_name: &str,
+    ) -> Result<ImportResult, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let seeds = AccountService::get_seed_positions(pool, user_id, Some(account_id)).await?;
+        let preview = ImportService::preview_import(pool, user_id, &content, &seeds).await?;
+
+        // Nothing new to import records no batch
+        let mut result = if preview.trades_to_import.is_empty() {
+            ImportResult {
+                imported_count: 0,
+                updated_count: 0,
+                skipped_duplicates: 0,
+                errors: ImportService::parse_error_messages(&preview.parse_errors),
+                roll_links: 0,
+                batch_id: None,
+            }
+        } else {
+            ImportService::execute_import(
+                pool,
+                user_id,
+                account_id,
+                preview.trades_to_import,
+                true,
+                &[],
+                &preview.parse_errors,
+                Some(file_name),
+            )
+            .await?
+        };
+        result.skipped_duplicates += preview.duplicate_count;
+
+        // Corrected fills of earlier imports, applied as if accepted in a preview
+        if !preview.changed_trades.is_empty() {
+            let updated = ImportService::update_changed_trades(pool, user_id, preview.changed_trades).await?;
+            result.updated_count = updated.updated_count;
+            if !updated.errors.is_empty() {
+                result.errors.extend(updated.errors);
+                if let Some(batch_id) = &result.batch_id {
+                    ImportBatchRepository::set_errors(pool, batch_id, &result.errors)
+                        .await
+                        .map_err(|e| format!("Failed to record import batch: {}", e))?;
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn is_import_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMPORT_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_db, setup_test_user_and_account};
+
+    #[tokio::test]
+    async fn test_scan_imports_new_and_changed_files() {
+        let pool = create_test_db().await;
+        let (user_id, account_id) = setup_test_user_and_account(&pool).await;
+        let dir = std::env::temp_dir().join(format!("ftj-watch-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let later = || SystemTime::now() + Duration::from_secs(60);
+
+        // Nothing happens until a folder is watched
+        assert!(WatchFolderService::scan(&pool, &user_id, later()).await.unwrap().is_empty());
+        let folder = WatchFolder {
+            directory: dir.to_string_lossy().into_owned(),
+            account_id: "missing".to_string(),
+        };
+        assert!(WatchFolderService::save_watch_folder(&pool, &user_id, Some(folder.clone())).await.is_err());
+        WatchFolderService::save_watch_folder(&pool, &user_id, Some(WatchFolder { account_id: account_id.clone(), ..folder }))
+            .await
+            .unwrap();
+
+        let aapl = "STOCK_TRANSACTIONS\n\
+                    STK_TRD|1001|AAPL|APPLE INC|DARK|BUYTOOPEN|O|20260127|09:30:00|USD|100.00|1.00|150.00|15000.00|-1.00|0.85\n\
+                    STK_TRD|1002|AAPL|APPLE INC|DARK|SELLTOCLOSE|C|20260127|10:00:00|USD|-100.00|1.00|155.00|-15500.00|-1.00|0.85\n";
+        fs::write(dir.join("trades.tlg"), aapl).unwrap();
+        fs::write(dir.join("notes.pdf"), "not an export").unwrap();
+        // Padded to the size of the export it is replaced with below
+        let nvda = "STK_TRD|3001|NVDA|NVIDIA|DARK|BUYTOOPEN|O|20260129|09:30:00|USD|10.00|1.00|600.00|6000.00|-1.00|0.85\n\
+                    STK_TRD|3002|NVDA|NVIDIA|DARK|SELLTOCLOSE|C|20260129|10:00:00|USD|-10.00|1.00|610.00|-6100.00|-1.00|0.85\n";
+        let budget = format!("{:<width$}\n", "Date,Description,Amount", width = nvda.len() - 1);
+        fs::write(dir.join("budget.csv"), &budget).unwrap();
+
+        // Files are only imported once they settle
+        assert!(WatchFolderService::scan(&pool, &user_id, SystemTime::now()).await.unwrap().is_empty());
+
+        let imports = WatchFolderService::scan(&pool, &user_id, later()).await.unwrap();
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].file_name, "budget.csv");
+        assert!(imports[0].error.as_deref().unwrap().starts_with("Unrecognized file format"));
+        let result = imports[1].result.as_ref().unwrap();
+        assert_eq!(result.imported_count, 1);
+        let history = ImportService::get_import_history(&pool, &user_id).await.unwrap();
+        assert_eq!(history[0].file_name.as_deref(), Some("trades.tlg"));
+
+        // Imported files are not imported again; failed ones are retried without being reported again
+        assert!(WatchFolderService::scan(&pool, &user_id, later()).await.unwrap().is_empty());
+
+        // A failed file is retried even when its size and modification time stay the same
+        let budget_path = dir.join("budget.csv");
+        let modified = fs::metadata(&budget_path).unwrap().modified().unwrap();
+        fs::write(&budget_path, nvda).unwrap();
+        fs::File::options().write(true).open(&budget_path).unwrap().set_modified(modified).unwrap();
+        let imports = WatchFolderService::scan(&pool, &user_id, later()).await.unwrap();
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0].result.as_ref().unwrap().imported_count, 1);
+
+        // A grown file only adds its new trades
+        let msft = "STK_TRD|2001|MSFT|MICROSOFT|DARK|BUYTOOPEN|O|20260128|09:30:00|USD|10.00|1.00|400.00|4000.00|-1.00|0.85\n\
+                    STK_TRD|2002|MSFT|MICROSOFT|DARK|SELLTOCLOSE|C|20260128|10:00:00|USD|-10.00|1.00|410.00|-4100.00|-1.00|0.85\n";
+        fs::write(dir.join("trades.tlg"), format!("{}{}", aapl, msft)).unwrap();
+        let imports = WatchFolderService::scan(&pool, &user_id, later()).await.unwrap();
+        assert_eq!(imports.len(), 1);
+        let result = imports[0].result.as_ref().unwrap();
+        assert_eq!((result.imported_count, result.skipped_duplicates), (1, 1));
+
+        // Corrected fills replace those of the trades imported before
+        let corrected = format!("{}{}", aapl, msft).replace("-4100.00|-1.00", "-4100.00|-2.50");
+        fs::write(dir.join("trades.tlg"), corrected).unwrap();
+        let imports = WatchFolderService::scan(&pool, &user_id, later()).await.unwrap();
+        let result = imports[0].result.as_ref().unwrap();
+        assert_eq!((result.imported_count, result.updated_count), (0, 1));
+        let trades = crate::services::TradeService::get_all_trades(&pool, &user_id, None, None, None).await.unwrap();
+        let msft = trades.iter().find(|t| t.trade.symbol == "MSFT").unwrap();
+        assert_eq!(msft.trade.fees, 3.5);
+
+        WatchFolderService::save_watch_folder(&pool, &user_id, None).await.unwrap();
+        assert!(WatchFolderService::get_watch_folder(&pool).await.unwrap().is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,9 +1,11 @@
 //! Test utilities for setting up in-memory database and test fixtures
 
-use chrono::NaiveDate;
+use chrono::{NaiveDate, Utc};
 use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
 
-use crate::models::{CreateTradeInput, Direction, Status};
+use crate::calculations::calculate_derived_fields;
+use crate::models::{AssetClass, CreateTradeInput, Direction, ExitExecution, ResultBasis, Status, Trade, TradeWithDerived};
+use crate::services::TradeService;
 
 /// Create an in-memory SQLite database for testing
 pub async fn create_test_db() -> SqlitePool {
@@ -13,24 +15,18 @@ pub async fn create_test_db() -> SqlitePool {
         .await
         .expect("Failed to create test database");
 
-    // Run migrations
-    let migration_001 = include_str!("../migrations/001_initial_schema.sql");
-    sqlx::raw_sql(migration_001)
-        .execute(&pool)
+    // The same migrations the app runs, so tests see the production schema
+    crate::repository::run_migrations(&pool)
         .await
-        .expect("Failed to run migration 001");
+        .expect("Failed to run migrations");
 
-    let migration_002 = include_str!("../migrations/002_executions_options.sql");
-    sqlx::raw_sql(migration_002)
+    sqlx::query("ATTACH DATABASE ':memory:' AS archive")
         .execute(&pool)
         .await
-        .expect("Failed to run migration 002");
-
-    let migration_003 = include_str!("../migrations/003_trade_screenshot_url.sql");
-    sqlx::raw_sql(migration_003)
-        .execute(&pool)
+        .expect("Failed to attach archive database");
+    crate::repository::ArchiveRepository::ensure_tables(&mut pool.acquire().await.unwrap())
         .await
-        .expect("Failed to run migration 003");
+        .expect("Failed to create archive tables");
 
     pool
 }
@@ -72,6 +68,8 @@ pub fn create_test_trade_input(account_id: &str, symbol: &str) -> CreateTradeInp
         entry_price: 150.0,
         exit_price: Some(155.0),
         stop_loss_price: Some(145.0),
+        target_price: None,
+        max_favorable_price: None,
         entry_time: Some("09:30".to_string()),
         exit_time: Some("10:45".to_string()),
         fees: Some(10.0),
@@ -103,6 +101,8 @@ pub fn create_losing_long_trade(
         entry_price: entry,
         exit_price: Some(exit),
         stop_loss_price: None,
+        target_price: None,
+        max_favorable_price: None,
         entry_time: None,
         exit_time: None,
         fees: Some(0.0),
@@ -133,6 +133,8 @@ pub fn create_open_trade(
         entry_price: entry,
         exit_price: None,
         stop_loss_price: None,
+        target_price: None,
+        max_favorable_price: None,
         entry_time: None,
         exit_time: None,
         fees: None,
@@ -143,3 +145,100 @@ pub fn create_open_trade(
         exits: None,
     }
 }
+
+/// Create a long trade input entered at `entry` and closed by partial exits of (date, quantity, price)
+/// The trade's quantity is the total exited.
+pub fn create_test_trade_with_exits(
+    account_id: &str,
+    symbol: &str,
+    date: NaiveDate,
+    entry: f64,
+    exits: &[(NaiveDate, f64, f64)],
+) -> CreateTradeInput {
+    CreateTradeInput {
+        quantity: Some(exits.iter().map(|&(_, quantity, _)| quantity).sum()),
+        exits: Some(
+            exits
+                .iter()
+                .map(|&(exit_date, quantity, price)| ExitExecution {
+                    id: None,
+                    exit_date,
+                    exit_time: None,
+                    quantity,
+                    price,
+                    fees: None,
+                })
+                .collect(),
+        ),
+        status: None, // Closed once the exits cover the quantity
+        ..create_open_trade(account_id, symbol, date, entry, 0.0)
+    }
+}
+
+/// Build the stock trade an input would be stored as, with its derived fields, without a database
+/// Results are classified by net PnL; partial exits are ignored.
+pub fn create_test_trade(id: &str, input: CreateTradeInput) -> TradeWithDerived {
+    let trade = Trade {
+        id: id.to_string(),
+        user_id: "test-user".to_string(),
+        instrument_id: format!("instrument-{}", input.symbol),
+        symbol: input.symbol,
+        account_id: input.account_id,
+        asset_class: input.asset_class.unwrap_or(AssetClass::Stock),
+        contract_multiplier: None,
+        fx_rate: None,
+        trade_number: input.trade_number,
+        ref_code: None,
+        trade_date: input.trade_date,
+        direction: input.direction,
+        quantity: input.quantity,
+        entry_price: input.entry_price,
+        exit_price: input.exit_price,
+        stop_loss_price: input.stop_loss_price,
+        target_price: input.target_price,
+        max_favorable_price: input.max_favorable_price,
+        max_adverse_price: None,
+        entry_atr: None,
+        entry_time: input.entry_time,
+        exit_time: input.exit_time,
+        exit_date: None,
+        fees: input.fees.unwrap_or(0.0),
+        carrying_costs: 0.0,
+        dividends: 0.0,
+        strategy: input.strategy,
+        notes: input.notes,
+        screenshot_url: input.screenshot_url,
+        status: input
+            .status
+            .unwrap_or(if input.exit_price.is_some() { Status::Closed } else { Status::Open }),
+        result_override: None,
+        roll_type: None,
+        rolled_on: None,
+        continuation_trade_id: None,
+        option_outcome: None,
+        borrow_availability: None,
+        borrow_note: None,
+        is_locked: false,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+    let derived = calculate_derived_fields(&trade, ResultBasis::Net);
+    TradeWithDerived::from_trade(trade, derived)
+}
+
+/// Create a trade and return it with its stored executions attached
+pub async fn create_test_trade_with_executions(
+    pool: &SqlitePool,
+    user_id: &str,
+    input: CreateTradeInput,
+) -> TradeWithDerived {
+    let trade = TradeService::create_trade(pool, user_id, input)
+        .await
+        .expect("Failed to create test trade");
+
+    let mut trades = vec![trade];
+    TradeService::attach_executions(pool, &mut trades, false)
+        .await
+        .expect("Failed to load test trade executions");
+    trades.remove(0)
+}
@@ -0,0 +1,55 @@
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Runs mutating operations one at a time, in the order they were queued
+/// Parallel commands would otherwise interleave their statements and transactions on the
+/// database. Writers wait for their turn on a fair lock; reads go straight to the pool and never wait.
+/// Clones share the same queue, so the scheduler and the commands take turns.
+#[derive(Clone, Default)]
+pub struct WriteQueue {
+    turn: Arc<Mutex<()>>,
+}
+
+impl WriteQueue {
+    /// Run a write once every write queued before it has finished
+    /// Writes must not queue other writes while running, or they wait on themselves.
+    pub async fn run<F: Future>(&self, write: F) -> F::Output {
+        let _turn = self.turn.lock().await;
+        write.await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_writes_do_not_interleave() {
+        let queue = WriteQueue::default();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let write = |name: &'static str, pause_ms: u64| {
+            let (queue, log) = (queue.clone(), log.clone());
+            tokio::spawn(async move {
+                queue
+                    .run(async {
+                        log.lock().await.push(format!("{} start", name));
+                        tokio::time::sleep(Duration::from_millis(pause_ms)).await;
+                        log.lock().await.push(format!("{} end", name));
+                    })
+                    .await
+            })
+        };
+
+        // The slow first write still finishes before the second starts
+        let first = write("first", 50);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let second = write("second", 0);
+        first.await.unwrap();
+        second.await.unwrap();
+
+        assert_eq!(*log.lock().await, vec!["first start", "first end", "second start", "second end"]);
+    }
+}
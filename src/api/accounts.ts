@@ -1,5 +1,14 @@
 import { invoke } from '@/mocks/invoke';
-import type { Account } from '@/types';
+import type {
+  Account,
+  AccountPerformance,
+  AccountReturns,
+  CashTransaction,
+  CreateCashTransactionInput,
+  CreateFinancingChargeInput,
+  FinancingCharge,
+  FinancingImportResult,
+} from '@/types';
 
 export async function getAccounts(): Promise<Account[]> {
   return invoke('get_accounts');
@@ -11,3 +20,62 @@ export async function createAccount(
 ): Promise<Account> {
   return invoke('create_account', { name, baseCurrency });
 }
+
+export async function addFinancingCharge(
+  input: CreateFinancingChargeInput
+): Promise<FinancingCharge> {
+  return invoke('add_financing_charge', { input });
+}
+
+export async function getFinancingCharges(
+  accountId?: string,
+  startDate?: string,
+  endDate?: string
+): Promise<FinancingCharge[]> {
+  return invoke('get_financing_charges', { accountId, startDate, endDate });
+}
+
+export async function deleteFinancingCharge(id: string): Promise<void> {
+  return invoke('delete_financing_charge', { id });
+}
+
+export async function importFinancingCharges(
+  accountId: string,
+  filePath: string
+): Promise<FinancingImportResult> {
+  return invoke('import_financing_charges', { accountId, filePath });
+}
+
+export async function getAccountPerformance(
+  accountId: string,
+  startDate?: string,
+  endDate?: string
+): Promise<AccountPerformance> {
+  return invoke('get_account_performance', { accountId, startDate, endDate });
+}
+
+export async function addCashTransaction(
+  input: CreateCashTransactionInput
+): Promise<CashTransaction> {
+  return invoke('add_cash_transaction', { input });
+}
+
+export async function getCashTransactions(
+  accountId?: string,
+  startDate?: string,
+  endDate?: string
+): Promise<CashTransaction[]> {
+  return invoke('get_cash_transactions', { accountId, startDate, endDate });
+}
+
+export async function deleteCashTransaction(id: string): Promise<void> {
+  return invoke('delete_cash_transaction', { id });
+}
+
+export async function getAccountReturns(
+  accountId: string,
+  startDate: string,
+  endDate: string
+): Promise<AccountReturns> {
+  return invoke('get_account_returns', { accountId, startDate, endDate });
+}
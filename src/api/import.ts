@@ -1,5 +1,5 @@
 import { invoke } from '@/mocks/invoke';
-import type { ImportPreview, ImportResult, AggregatedTrade, Execution } from '@/types';
+import type { ImportPreview, ImportResult, AggregatedTrade, Execution, RollChainCandidate, SupportedBroker, ImportBatch, TlgParseError, WatchFolder } from '@/types';
 
 /**
  * Open a file picker dialog to select a TLG file
@@ -9,27 +9,127 @@ export async function selectTlgFile(): Promise<string | null> {
 }
 
 /**
- * Preview importing trades from a TLG file
+ * Preview importing trades from a TLG file or any other supported broker export, detected from its contents
+ * With an account, closing executions are matched against its seed positions
  */
-export async function previewTlgImport(filePath: string): Promise<ImportPreview> {
-  return invoke('preview_tlg_import', { filePath });
+export async function previewTlgImport(filePath: string, accountId?: string): Promise<ImportPreview> {
+  return invoke('preview_tlg_import', { filePath, accountId });
+}
+
+/**
+ * Open a file picker dialog to select a CSV export, such as a thinkorswim Account Statement
+ */
+export async function selectCsvFile(): Promise<string | null> {
+  return invoke('select_csv_file', {});
+}
+
+/**
+ * List the brokers whose exports can be previewed
+ */
+export async function listSupportedBrokers(): Promise<SupportedBroker[]> {
+  return invoke('list_supported_brokers', {});
+}
+
+/**
+ * Preview importing the export of a supported broker, detecting the format when no broker is given
+ * Previewed trades are imported with executeTlgImport
+ */
+export async function previewBrokerImport(
+  filePath: string,
+  brokerId?: string,
+  accountId?: string
+): Promise<ImportPreview> {
+  return invoke('preview_broker_import', { filePath, brokerId, accountId });
+}
+
+/**
+ * Import the round trips of a NinjaTrader Trades grid export directly as closed trades
+ */
+export async function importNinjaTraderTrades(
+  filePath: string,
+  accountId: string,
+  skipDuplicates: boolean = true
+): Promise<ImportResult> {
+  return invoke('import_ninjatrader_trades', { filePath, accountId, skipDuplicates });
+}
+
+/**
+ * Import the closed tickets of a MetaTrader 4 or 5 statement (HTML or CSV) directly as closed trades
+ */
+export async function importMetaTraderStatement(
+  filePath: string,
+  accountId: string,
+  skipDuplicates: boolean = true
+): Promise<ImportResult> {
+  return invoke('import_metatrader_statement', { filePath, accountId, skipDuplicates });
+}
+
+/**
+ * Import the List of Trades of a TradingView strategy tester or paper trading export into a paper account
+ * The export names no symbol, so the chart's symbol (e.g. "NASDAQ:AAPL" or "ES1!") is given
+ */
+export async function importTradingViewTrades(
+  filePath: string,
+  accountId: string,
+  symbol: string,
+  skipDuplicates: boolean = true
+): Promise<ImportResult> {
+  return invoke('import_tradingview_trades', { filePath, accountId, symbol, skipDuplicates });
 }
 
 /**
  * Execute the import for selected trades
+ * The previewed file's path and the lines that failed to parse in its preview are recorded in
+ * the import history
  */
 export async function executeTlgImport(
   accountId: string,
   trades: AggregatedTrade[],
-  skipDuplicates: boolean = true
+  skipDuplicates: boolean = true,
+  rollChains: RollChainCandidate[] = [],
+  filePath?: string,
+  parseErrors: TlgParseError[] = []
 ): Promise<ImportResult> {
   return invoke('execute_tlg_import', {
     accountId,
     trades,
     skipDuplicates,
+    rollChains,
+    filePath,
+    parseErrors,
   });
 }
 
+/**
+ * List past imports, most recent first, with their file names, counts and errors
+ */
+export async function getImportHistory(): Promise<ImportBatch[]> {
+  return invoke('get_import_history', {});
+}
+
+/**
+ * Delete the trades and executions an import created, returning how many trades were deleted
+ * Fails without deleting anything when a trade is in a locked period
+ */
+export async function undoImport(batchId: string): Promise<number> {
+  return invoke('undo_import', { batchId });
+}
+
+/**
+ * Get the folder new exports are imported from automatically, if any
+ */
+export async function getWatchFolder(): Promise<WatchFolder | null> {
+  return invoke('get_watch_folder', {});
+}
+
+/**
+ * Watch a folder for new TLG and CSV exports to import into an account, or stop watching with null
+ * Each imported file is reported with a 'watched-file-imported' event
+ */
+export async function saveWatchFolder(folder: WatchFolder | null): Promise<void> {
+  return invoke('save_watch_folder', { folder });
+}
+
 /**
  * Get executions for a specific trade
  */
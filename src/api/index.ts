@@ -4,3 +4,4 @@ export * from './metrics';
 export * from './import';
 export * from './market';
 export * from './settings';
+export * from './journal';
@@ -0,0 +1,23 @@
+import { invoke, isTauri } from '@/mocks/invoke';
+import type { EndOfDaySummary, JournalEntry } from '@/types';
+
+export async function getJournalEntries(startDate?: string, endDate?: string): Promise<JournalEntry[]> {
+  return invoke('get_journal_entries', { startDate, endDate });
+}
+
+export async function saveJournalEntry(date: string, content: string): Promise<JournalEntry> {
+  return invoke('save_journal_entry', { date, content });
+}
+
+export async function summarizeDay(date: string): Promise<EndOfDaySummary | null> {
+  return invoke('summarize_day', { date });
+}
+
+// Subscribe to end-of-day summaries emitted by the backend scheduler; returns an unsubscribe function
+export async function onEndOfDaySummary(handler: (summary: EndOfDaySummary) => void): Promise<() => void> {
+  if (!isTauri()) {
+    return () => {};
+  }
+  const { listen } = await import('@tauri-apps/api/event');
+  return listen<EndOfDaySummary>('end-of-day-summary', (event) => handler(event.payload));
+}
@@ -1,5 +1,12 @@
 import { invoke } from '@/mocks/invoke';
-import type { Candle, CandleRequestKind, CandleTimeframe, MarketTapeQuote } from '@/types';
+import type {
+  Candle,
+  CandleRequestKind,
+  CandleTimeframe,
+  EntryAtrBackfillResult,
+  ExcursionBackfillResult,
+  MarketTapeQuote,
+} from '@/types';
 
 export async function getTradeCandles(
   tradeId: string,
@@ -13,3 +20,11 @@ export async function getTradeCandles(
 export async function getMarketTape(symbols?: string[]): Promise<MarketTapeQuote[]> {
   return invoke('get_market_tape', { symbols });
 }
+
+export async function backfillExcursions(): Promise<ExcursionBackfillResult> {
+  return invoke('backfill_excursions');
+}
+
+export async function backfillEntryAtr(): Promise<EntryAtrBackfillResult> {
+  return invoke('backfill_entry_atr');
+}
@@ -1,12 +1,32 @@
 import { invoke } from '@/mocks/invoke';
-import type { DailyPerformance, PeriodMetrics, EquityPoint } from '@/types';
+import type {
+  DailyPerformance,
+  PeriodMetrics,
+  EquityPoint,
+  MetricSnapshot,
+  EntryCohort,
+  SymbolMonthMatrix,
+  EquitySeries,
+  MarginUtilizationReport,
+  OptionsPremiumReport,
+  PerformanceBucket,
+  ProcessStreaks,
+  ShortSideReport,
+  FuturesSessionReport,
+  CryptoTaxReport,
+  SkillProgressionReport,
+  StrategyTrend,
+  TimelineEvent,
+  TradeClusterReport,
+} from '@/types';
 
 export async function getDailyPerformance(
   startDate: string,
   endDate: string,
-  accountId?: string
+  accountId?: string,
+  includeUnrealized?: boolean
 ): Promise<DailyPerformance[]> {
-  return invoke('get_daily_performance', { startDate, endDate, accountId });
+  return invoke('get_daily_performance', { startDate, endDate, accountId, includeUnrealized });
 }
 
 export async function getPeriodMetrics(
@@ -28,3 +48,175 @@ export async function getEquityCurve(
 ): Promise<EquityPoint[]> {
   return invoke('get_equity_curve', { startDate, endDate, accountId });
 }
+
+/**
+ * Get the nightly snapshots of account metrics (balance, cumulative PnL, drawdown, win rate)
+ * Snapshots run through yesterday; today's metrics come from the live queries
+ */
+export async function getMetricSnapshots(
+  startDate: string,
+  endDate: string,
+  accountId?: string
+): Promise<MetricSnapshot[]> {
+  return invoke('get_metric_snapshots', { startDate, endDate, accountId });
+}
+
+export async function getEquityCurveSeries(
+  startDate: string,
+  endDate: string,
+  groupBy: 'strategy' | 'account',
+  accountId?: string
+): Promise<EquitySeries[]> {
+  return invoke('get_equity_curve_series', { startDate, endDate, accountId, groupBy });
+}
+
+export async function getMarginUtilization(
+  startDate: string,
+  endDate: string,
+  accountId?: string
+): Promise<MarginUtilizationReport> {
+  return invoke('get_margin_utilization', { startDate, endDate, accountId });
+}
+
+export async function getOptionsPremiumReport(
+  startDate?: string,
+  endDate?: string,
+  accountId?: string
+): Promise<OptionsPremiumReport> {
+  return invoke('get_options_premium_report', { startDate, endDate, accountId });
+}
+
+export async function getShortSideReport(
+  startDate?: string,
+  endDate?: string,
+  accountId?: string
+): Promise<ShortSideReport> {
+  return invoke('get_short_side_report', { startDate, endDate, accountId });
+}
+
+export async function getFuturesSessionReport(
+  startDate?: string,
+  endDate?: string,
+  accountId?: string
+): Promise<FuturesSessionReport> {
+  return invoke('get_futures_session_report', { startDate, endDate, accountId });
+}
+
+export async function getCryptoTaxReport(
+  startDate?: string,
+  endDate?: string,
+  accountId?: string
+): Promise<CryptoTaxReport> {
+  return invoke('get_crypto_tax_report', { startDate, endDate, accountId });
+}
+
+export async function getTradeTypeReport(
+  startDate?: string,
+  endDate?: string,
+  accountId?: string
+): Promise<PerformanceBucket[]> {
+  return invoke('get_trade_type_report', { startDate, endDate, accountId });
+}
+
+export async function getEntryCohortReport(
+  startDate?: string,
+  endDate?: string,
+  accountId?: string
+): Promise<EntryCohort[]> {
+  return invoke('get_entry_cohort_report', { startDate, endDate, accountId });
+}
+
+/**
+ * Compare the first and most recent closed trades (50 each unless sampleSize is given)
+ */
+export async function getSkillProgression(
+  startDate?: string,
+  endDate?: string,
+  accountId?: string,
+  sampleSize?: number
+): Promise<SkillProgressionReport> {
+  return invoke('get_skill_progression', { startDate, endDate, accountId, sampleSize });
+}
+
+/**
+ * Cluster closed trades by time of day, hold time, R, symbol volatility and tags
+ * One cluster per 15 trades, from 2 to 6, unless clusterCount is given
+ */
+export async function getTradeClusters(
+  startDate?: string,
+  endDate?: string,
+  accountId?: string,
+  clusterCount?: number
+): Promise<TradeClusterReport> {
+  return invoke('get_trade_clusters', { startDate, endDate, accountId, clusterCount });
+}
+
+/**
+ * Get net PnL and trade count by symbol and month for a heatmap
+ */
+export async function getSymbolMonthMatrix(
+  startDate?: string,
+  endDate?: string,
+  accountId?: string
+): Promise<SymbolMonthMatrix> {
+  return invoke('get_symbol_month_matrix', { startDate, endDate, accountId });
+}
+
+/**
+ * Open a save dialog for a CSV export, suggesting fileName
+ */
+export async function selectCsvExportFile(fileName: string): Promise<string | null> {
+  return invoke('select_csv_export_file', { fileName });
+}
+
+/**
+ * Write the symbol by month matrix to a CSV file, with a net PnL and a trade count column per month
+ */
+export async function exportSymbolMonthMatrix(
+  filePath: string,
+  accountId?: string,
+  startDate?: string,
+  endDate?: string
+): Promise<SymbolMonthMatrix> {
+  return invoke('export_symbol_month_matrix', { filePath, accountId, startDate, endDate });
+}
+
+/**
+ * Open a save dialog for a calendar (.ics) export
+ */
+export async function selectCalendarExportFile(): Promise<string | null> {
+  return invoke('select_calendar_export_file', {});
+}
+
+/**
+ * Write each trading day's net PnL and trade count to an .ics file of all-day events, returning the day count
+ * Re-importing a newer export into a calendar app updates the same events
+ */
+export async function exportTradingCalendar(
+  filePath: string,
+  accountId?: string,
+  startDate?: string,
+  endDate?: string
+): Promise<number> {
+  return invoke('export_trading_calendar', { filePath, accountId, startDate, endDate });
+}
+
+/**
+ * Get each strategy's rolling expectancy and trade count (30-day windows unless windowDays is given)
+ */
+export async function getStrategyTrends(
+  startDate?: string,
+  endDate?: string,
+  accountId?: string,
+  windowDays?: number
+): Promise<StrategyTrend[]> {
+  return invoke('get_strategy_trends', { startDate, endDate, accountId, windowDays });
+}
+
+export async function getDayTimeline(date: string, accountId?: string): Promise<TimelineEvent[]> {
+  return invoke('get_day_timeline', { date, accountId });
+}
+
+export async function getProcessStreaks(): Promise<ProcessStreaks> {
+  return invoke('get_process_streaks', {});
+}
@@ -1,5 +1,17 @@
-import { invoke } from '@/mocks/invoke';
-import type { AlpacaKeysStatus } from '@/types';
+import { invoke, isTauri } from '@/mocks/invoke';
+import type {
+  AlpacaKeysStatus,
+  CalculationReport,
+  ConfigView,
+  CostBasisMethod,
+  DateAttribution,
+  DueReminder,
+  FxRate,
+  FxRefreshResult,
+  MaintenanceReport,
+  Reminder,
+  TradeTypeThresholds,
+} from '@/types';
 
 export async function getAlpacaKeysStatus(): Promise<AlpacaKeysStatus> {
   return invoke('get_alpaca_keys_status', {});
@@ -23,3 +35,135 @@ export async function getManualTradeTimezone(): Promise<string> {
 export async function saveManualTradeTimezone(timezone: string): Promise<void> {
   return invoke('save_manual_trade_timezone', { timezone });
 }
+
+export async function getDateAttribution(): Promise<DateAttribution> {
+  return invoke('get_date_attribution', {});
+}
+
+export async function saveDateAttribution(attribution: DateAttribution): Promise<void> {
+  return invoke('save_date_attribution', { attribution });
+}
+
+export async function getCryptoCostBasisMethod(): Promise<CostBasisMethod> {
+  return invoke('get_crypto_cost_basis_method', {});
+}
+
+export async function saveCryptoCostBasisMethod(method: CostBasisMethod): Promise<void> {
+  return invoke('save_crypto_cost_basis_method', { method });
+}
+
+export async function getTradeTypeThresholds(): Promise<TradeTypeThresholds> {
+  return invoke('get_trade_type_thresholds', {});
+}
+
+export async function saveTradeTypeThresholds(thresholds: TradeTypeThresholds): Promise<void> {
+  return invoke('save_trade_type_thresholds', { thresholds });
+}
+
+export async function getMaxMarginUtilizationPct(): Promise<number> {
+  return invoke('get_max_margin_utilization_pct', {});
+}
+
+export async function saveMaxMarginUtilizationPct(maxPct: number): Promise<void> {
+  return invoke('save_max_margin_utilization_pct', { maxPct });
+}
+
+export async function getDailyLossLimit(): Promise<number | null> {
+  return invoke('get_daily_loss_limit', {});
+}
+
+export async function saveDailyLossLimit(limit: number | null): Promise<void> {
+  return invoke('save_daily_loss_limit', { limit });
+}
+
+export async function getReportingCurrency(): Promise<string | null> {
+  return invoke('get_reporting_currency', {});
+}
+
+export async function saveReportingCurrency(currency: string | null): Promise<void> {
+  return invoke('save_reporting_currency', { currency });
+}
+
+export async function getReminders(): Promise<Reminder[]> {
+  return invoke('get_reminders', {});
+}
+
+export async function saveReminders(reminders: Reminder[]): Promise<void> {
+  return invoke('save_reminders', { reminders });
+}
+
+export async function getEndOfDaySummaryTime(): Promise<string> {
+  return invoke('get_end_of_day_summary_time', {});
+}
+
+export async function saveEndOfDaySummaryTime(time: string): Promise<void> {
+  return invoke('save_end_of_day_summary_time', { time });
+}
+
+// Subscribe to reminders emitted by the backend scheduler; returns an unsubscribe function
+export async function onReminderDue(handler: (reminder: DueReminder) => void): Promise<() => void> {
+  if (!isTauri()) {
+    return () => {};
+  }
+  const { listen } = await import('@tauri-apps/api/event');
+  return listen<DueReminder>('reminder-due', (event) => handler(event.payload));
+}
+
+export async function getFxRates(): Promise<FxRate[]> {
+  return invoke('get_fx_rates', {});
+}
+
+export async function saveFxRates(rates: FxRate[]): Promise<number> {
+  return invoke('save_fx_rates', { rates });
+}
+
+export async function deleteFxRate(
+  baseCurrency: string,
+  quoteCurrency: string,
+  date: string
+): Promise<void> {
+  return invoke('delete_fx_rate', { baseCurrency, quoteCurrency, date });
+}
+
+export async function refreshFxRates(): Promise<FxRefreshResult> {
+  return invoke('refresh_fx_rates', {});
+}
+
+export async function runMaintenance(): Promise<MaintenanceReport> {
+  return invoke('run_maintenance', {});
+}
+
+/**
+ * Recompute the daily performance summary from all closed trades; returns the account days written
+ */
+export async function rebuildDailyPerformance(): Promise<number> {
+  return invoke('rebuild_daily_performance', {});
+}
+
+/**
+ * Startup configuration from config.toml in the app data directory and FTJ_ environment variables
+ * Secrets are masked; changes take effect on the next start
+ */
+export async function getConfig(): Promise<ConfigView> {
+  return invoke('get_config', {});
+}
+
+/**
+ * Developer check: recompute derived metrics per trade and in SQL and list where they disagree.
+ * Fails unless the developer_tools feature flag is on (see getConfig).
+ */
+export async function verifyCalculations(): Promise<CalculationReport> {
+  return invoke('verify_calculations', {});
+}
+
+export async function getLastMaintenanceReport(): Promise<MaintenanceReport | null> {
+  return invoke('get_last_maintenance_report', {});
+}
+
+export async function getMaintenanceIntervalDays(): Promise<number | null> {
+  return invoke('get_maintenance_interval_days', {});
+}
+
+export async function saveMaintenanceIntervalDays(days: number | null): Promise<void> {
+  return invoke('save_maintenance_interval_days', { days });
+}
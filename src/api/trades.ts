@@ -1,26 +1,73 @@
 import { invoke } from '@/mocks/invoke';
-import type { TradeWithDerived, CreateTradeInput, UpdateTradeInput } from '@/types';
+import type {
+  BulkCreateResult,
+  TradeWithDerived,
+  CreateTradeInput,
+  UpdateTradeInput,
+  TradeFilters,
+  TradeResult,
+  TradeStats,
+  RollPositionInput,
+  OptionOutcome,
+  BorrowAvailability,
+  ArchivedYear,
+  ArchiveResult,
+} from '@/types';
 
 export async function getTrades(params?: {
   accountId?: string;
   startDate?: string;
   endDate?: string;
+  includeExecutions?: boolean;
 }): Promise<TradeWithDerived[]> {
   return invoke('get_trades', {
     accountId: params?.accountId,
     startDate: params?.startDate,
     endDate: params?.endDate,
+    includeExecutions: params?.includeExecutions,
   });
 }
 
-export async function getTrade(id: string): Promise<TradeWithDerived | null> {
-  return invoke('get_trade', { id });
+export async function getTrade(id: string, includeExecutions = false): Promise<TradeWithDerived | null> {
+  return invoke('get_trade', { id, includeExecutions });
+}
+
+function toFilterPayload(filters: TradeFilters) {
+  return {
+    account_id: filters.accountId,
+    start_date: filters.startDate,
+    end_date: filters.endDate,
+    symbol: filters.symbol,
+    direction: filters.direction,
+    result: filters.result,
+    min_r_multiple: filters.minRMultiple,
+    max_duration_minutes: filters.maxDurationMinutes,
+    trade_type: filters.tradeType,
+    date_attribution: filters.dateAttribution,
+    archived: filters.archived,
+  };
+}
+
+export async function findTrades(filters: TradeFilters, includeExecutions = false): Promise<TradeWithDerived[]> {
+  return invoke('find_trades', { filter: toFilterPayload(filters), includeExecutions });
+}
+
+export async function getTradeStats(filters: TradeFilters): Promise<TradeStats> {
+  return invoke('get_trade_stats', { filter: toFilterPayload(filters) });
+}
+
+export async function resolveTradeRef(refCode: string): Promise<TradeWithDerived | null> {
+  return invoke('resolve_trade_ref', { refCode });
 }
 
 export async function createTrade(input: CreateTradeInput): Promise<TradeWithDerived> {
   return invoke('create_trade', { input });
 }
 
+export async function createTrades(inputs: CreateTradeInput[]): Promise<BulkCreateResult> {
+  return invoke('create_trades', { inputs });
+}
+
 export async function updateTrade(id: string, input: UpdateTradeInput): Promise<TradeWithDerived> {
   return invoke('update_trade', { id, input });
 }
@@ -28,3 +75,61 @@ export async function updateTrade(id: string, input: UpdateTradeInput): Promise<
 export async function deleteTrade(id: string): Promise<void> {
   return invoke('delete_trade', { id });
 }
+
+export async function setTradeResultOverride(
+  id: string,
+  result: TradeResult | null
+): Promise<TradeWithDerived> {
+  return invoke('set_trade_result_override', { id, result });
+}
+
+export async function setTradeOptionOutcome(
+  id: string,
+  outcome: OptionOutcome | null
+): Promise<TradeWithDerived> {
+  return invoke('set_trade_option_outcome', { id, outcome });
+}
+
+export async function setTradeBorrowAvailability(
+  id: string,
+  availability: BorrowAvailability | null,
+  note?: string | null
+): Promise<TradeWithDerived> {
+  return invoke('set_trade_borrow_availability', { id, availability, note });
+}
+
+export async function getUnresolvedPositions(
+  periodEnd: string,
+  accountId?: string
+): Promise<TradeWithDerived[]> {
+  return invoke('get_unresolved_positions', { periodEnd, accountId });
+}
+
+export async function rollPosition(input: RollPositionInput): Promise<TradeWithDerived> {
+  return invoke('roll_position', { input });
+}
+
+export async function clearPositionRoll(tradeId: string): Promise<TradeWithDerived> {
+  return invoke('clear_position_roll', { tradeId });
+}
+
+// Compacting shrinks the main database file but rewrites all of it
+export async function archiveTradeYear(year: number, compact = false): Promise<number> {
+  return invoke('archive_trade_year', { year, compact });
+}
+
+export async function unarchiveTradeYear(year: number): Promise<number> {
+  return invoke('unarchive_trade_year', { year });
+}
+
+export async function archiveTradesOlderThan(years: number, compact = false): Promise<ArchiveResult> {
+  return invoke('archive_trades_older_than', { years, compact });
+}
+
+export async function getArchivedYears(): Promise<ArchivedYear[]> {
+  return invoke('get_archived_years', {});
+}
+
+export async function getArchivedTrades(year: number): Promise<TradeWithDerived[]> {
+  return invoke('get_archived_trades', { year });
+}
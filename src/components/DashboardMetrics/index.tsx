@@ -252,7 +252,11 @@ export default function DashboardMetrics({ metrics }: DashboardMetricsProps) {
             </div>
           </div>
         </div>
-        <p className="mt-3 text-xs text-stone-500 dark:text-stone-400">Win consistency across closed outcomes.</p>
+        <p className="mt-3 text-xs text-stone-500 dark:text-stone-400">
+          {metrics.win_rate_low != null && metrics.win_rate_high != null
+            ? `95% range ${(metrics.win_rate_low * 100).toFixed(0)}–${(metrics.win_rate_high * 100).toFixed(0)}% over ${metrics.win_count + metrics.loss_count} trades.`
+            : 'Win consistency across closed outcomes.'}
+        </p>
       </div>
 
       <div className="app-panel dashboard-card dashboard-card-kpi px-4 py-4 md:col-span-3 xl:col-span-2">
@@ -300,7 +304,11 @@ export default function DashboardMetrics({ metrics }: DashboardMetricsProps) {
             {metrics.expectancy !== null ? formatCurrency(metrics.expectancy) : 'N/A'}
           </span>
         </div>
-        <p className="mt-3 text-xs text-stone-500 dark:text-stone-400">Expected value per trade based on the current mix of wins and losses.</p>
+        <p className="mt-3 text-xs text-stone-500 dark:text-stone-400">
+          {metrics.expectancy_low != null && metrics.expectancy_high != null
+            ? `Win-rate-driven 95% range ${formatCurrency(metrics.expectancy_low)} to ${formatCurrency(metrics.expectancy_high)}, with the average win and loss held fixed.`
+            : 'Expected value per trade based on the current mix of wins and losses.'}
+        </p>
       </div>
     </div>
   );
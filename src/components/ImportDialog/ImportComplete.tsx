@@ -57,6 +57,13 @@ export default function ImportComplete({ onClose }: ImportCompleteProps) {
           trade{result.imported_count !== 1 ? 's' : ''} imported successfully
         </p>
 
+        {result.roll_links > 0 && (
+          <p>
+            <span className="font-medium text-purple-600">{result.roll_links}</span> option roll
+            {result.roll_links !== 1 ? 's' : ''} linked
+          </p>
+        )}
+
         {result.skipped_duplicates > 0 && (
           <p>
             <span className="font-medium text-gray-500">{result.skipped_duplicates}</span> duplicate
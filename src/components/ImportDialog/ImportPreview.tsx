@@ -18,7 +18,7 @@ function formatCurrency(value: number | null): string {
 }
 
 export default function ImportPreview({ onImport }: ImportPreviewProps) {
-  const { preview, selectedTradeKeys, selectAllTrades, deselectAllTrades, toggleTradeSelection, isLoading } = useImportStore();
+  const { preview, selectedTradeKeys, selectAllTrades, deselectAllTrades, toggleTradeSelection, linkRollChains, setLinkRollChains, isLoading } = useImportStore();
   const selectedCount = useSelectedTradeCount();
   const totalCount = useTradesToImportCount();
   const openCount = useOpenPositionsCount();
@@ -167,6 +167,30 @@ export default function ImportPreview({ onImport }: ImportPreviewProps) {
         ))}
       </div>
 
+      {/* Roll Chains */}
+      {preview.roll_chains.length > 0 && (
+        <div className="border border-purple-200 dark:border-purple-700 rounded-lg p-3 bg-purple-50 dark:bg-purple-900/20">
+          <label className="flex items-center gap-2 text-sm font-medium text-purple-700 dark:text-purple-300">
+            <input
+              type="checkbox"
+              checked={linkRollChains}
+              onChange={e => setLinkRollChains(e.target.checked)}
+            />
+            Link {preview.roll_chains.length} detected option roll{preview.roll_chains.length !== 1 ? 's' : ''}
+          </label>
+          <div className="mt-2 space-y-1">
+            {preview.roll_chains.map(chain => (
+              <div key={chain.keys[0]} className="flex justify-between text-sm text-purple-600 dark:text-purple-400">
+                <span>
+                  {chain.underlying_symbol} {chain.option_type?.toUpperCase()} - {chain.keys.length} legs
+                </span>
+                <span className="font-medium">{formatCurrency(chain.combined_net_pnl)}</span>
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+
       {/* Open Positions */}
       {openCount > 0 && (
         <div>
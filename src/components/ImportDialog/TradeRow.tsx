@@ -73,6 +73,11 @@ export default function TradeRow({ trade, isSelected, onToggle }: TradeRowProps)
                 {trade.option_type?.charAt(0).toUpperCase()}
               </span>
             )}
+            {trade.suggested_strategy && (
+              <span className="text-xs text-gray-500 dark:text-gray-400 ml-2" title="Suggested by your strategy rules">
+                {trade.suggested_strategy}
+              </span>
+            )}
           </div>
           <div>
             <span
@@ -29,7 +29,8 @@ export function calculateDerivedFields(trade: Trade): TradeWithDerived {
 
   // Calculate gross and net PnL
   const gross_pnl = pnl_per_share * trade.quantity * multiplier;
-  const net_pnl = gross_pnl - trade.fees;
+  const net_pnl =
+    gross_pnl - trade.fees - (trade.carrying_costs ?? 0) + (trade.dividends ?? 0);
 
   // Calculate risk per share (only if stop_loss_price is set)
   let risk_per_share: number | null = null;
@@ -25,6 +25,7 @@ export const mockAccounts: Account[] = [
     user_id: DEFAULT_USER_ID,
     name: 'Main Trading Account',
     base_currency: 'USD',
+    is_paper: false,
     created_at: daysAgo(90),
   },
 ];
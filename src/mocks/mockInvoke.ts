@@ -41,12 +41,13 @@ function getAccounts(): Account[] {
   return accounts;
 }
 
-function createAccount(name: string, baseCurrency?: string): Account {
+function createAccount(name: string, baseCurrency?: string, isPaper?: boolean): Account {
   const account: Account = {
     id: generateId(),
     user_id: 'mock-user-001',
     name,
     base_currency: baseCurrency || 'USD',
+    is_paper: isPaper ?? false,
     created_at: now(),
   };
   accounts.push(account);
@@ -180,23 +181,43 @@ function getDailyPerformance(
   const results: DailyPerformance[] = [];
   for (const [date, dayTrades] of byDate) {
     let realized_net_pnl = 0;
+    let gross_pnl = 0;
+    let total_fees = 0;
+    let total_volume = 0;
+    let largest_win: number | null = null;
+    let largest_loss: number | null = null;
     let win_count = 0;
     let loss_count = 0;
+    let breakeven_count = 0;
 
     for (const trade of dayTrades) {
       if (trade.net_pnl !== null) {
         realized_net_pnl += trade.net_pnl;
+        if (trade.net_pnl > 0) largest_win = Math.max(largest_win ?? trade.net_pnl, trade.net_pnl);
+        if (trade.net_pnl < 0) largest_loss = Math.min(largest_loss ?? trade.net_pnl, trade.net_pnl);
       }
+      gross_pnl += trade.gross_pnl ?? 0;
+      total_fees += trade.fees;
+      total_volume += trade.quantity ?? 0;
       if (trade.result === 'win') win_count++;
       if (trade.result === 'loss') loss_count++;
+      if (trade.result === 'breakeven') breakeven_count++;
     }
 
     results.push({
       date,
       realized_net_pnl,
+      gross_pnl,
+      total_fees,
+      total_volume,
+      largest_win,
+      largest_loss,
       trade_count: dayTrades.length,
       win_count,
       loss_count,
+      breakeven_count,
+      scratch_rate: breakeven_count / dayTrades.length,
+      avg_trade_pnl: realized_net_pnl / dayTrades.length,
     });
   }
 
@@ -233,9 +254,12 @@ function calculatePeriodMetrics(tradesWithDerived: TradeWithDerived[]): PeriodMe
   let max_drawdown = 0;
   let cumulative = 0;
 
-  // Sort by date for streak and drawdown calculations
-  const sorted = [...closedTrades].sort((a, b) =>
-    a.trade_date.localeCompare(b.trade_date)
+  // Sort by date, then entry time and creation, for streak and drawdown calculations
+  const sorted = [...closedTrades].sort(
+    (a, b) =>
+      a.trade_date.localeCompare(b.trade_date) ||
+      (a.entry_time ?? '').localeCompare(b.entry_time ?? '') ||
+      a.created_at.localeCompare(b.created_at)
   );
 
   let current_win_streak = 0;
@@ -314,6 +338,8 @@ function calculatePeriodMetrics(tradesWithDerived: TradeWithDerived[]): PeriodMe
     max_drawdown,
     max_win_streak,
     max_loss_streak,
+    current_win_streak,
+    current_loss_streak,
   };
 }
 
@@ -490,7 +516,8 @@ export async function mockInvoke<T>(cmd: string, args?: InvokeArgs): Promise<T>
     case 'create_account':
       return createAccount(
         args?.name as string,
-        args?.baseCurrency as string | undefined
+        args?.baseCurrency as string | undefined,
+        args?.isPaper as boolean | undefined
       ) as T;
 
     // Trades
@@ -13,6 +13,7 @@ interface ImportState {
   // Preview state
   preview: ImportPreview | null;
   selectedTradeKeys: Set<string>;
+  linkRollChains: boolean; // Link detected option rolls on import
 
   // Result state
   result: ImportResult | null;
@@ -29,6 +30,7 @@ interface ImportState {
   toggleTradeSelection: (tradeKey: string) => void;
   selectAllTrades: () => void;
   deselectAllTrades: () => void;
+  setLinkRollChains: (link: boolean) => void;
   executeImport: (accountId: string, skipDuplicates?: boolean) => Promise<ImportResult>;
   reset: () => void;
 }
@@ -39,6 +41,7 @@ const initialState = {
   filePath: null,
   preview: null,
   selectedTradeKeys: new Set<string>(),
+  linkRollChains: true,
   result: null,
   isLoading: false,
   error: null,
@@ -124,8 +127,12 @@ export const useImportStore = create<ImportState>((set, get) => ({
     set({ selectedTradeKeys: new Set() });
   },
 
+  setLinkRollChains: (link: boolean) => {
+    set({ linkRollChains: link });
+  },
+
   executeImport: async (accountId: string, skipDuplicates = true) => {
-    const { preview, selectedTradeKeys } = get();
+    const { preview, selectedTradeKeys, linkRollChains, filePath } = get();
     if (!preview) {
       throw new Error('No preview loaded');
     }
@@ -142,7 +149,15 @@ export const useImportStore = create<ImportState>((set, get) => ({
     set({ step: 'importing', isLoading: true, error: null });
 
     try {
-      const result = await api.executeTlgImport(accountId, selectedTrades, skipDuplicates);
+      const rollChains = linkRollChains ? preview.roll_chains : [];
+      const result = await api.executeTlgImport(
+        accountId,
+        selectedTrades,
+        skipDuplicates,
+        rollChains,
+        filePath ?? undefined,
+        preview.parse_errors
+      );
 
       set({
         result,
@@ -3,5 +3,113 @@ export interface Account {
   user_id: string;
   name: string;
   base_currency: string;
+  is_paper: boolean;
+  starting_balance?: number | null;
   created_at: string;
 }
+
+export interface PeriodLock {
+  account_id: string;
+  locked_through: string; // YYYY-MM-DD format
+  locked_at: string;
+}
+
+export interface CommissionSchedule {
+  account_id: string;
+  per_order: number;
+  per_share: number;
+  per_contract: number;
+  minimum_per_order: number;
+}
+
+export interface SeedPosition {
+  id: string;
+  user_id: string;
+  account_id: string;
+  symbol: string;
+  direction: 'long' | 'short';
+  quantity: number;
+  avg_cost: number;
+  open_date: string; // YYYY-MM-DD format
+  as_of_date: string; // YYYY-MM-DD format
+  created_at: string;
+}
+
+export type FinancingChargeType = 'margin_interest' | 'financing';
+
+export interface FinancingCharge {
+  id: string;
+  account_id: string;
+  charge_month: string; // YYYY-MM-DD format, first day of the month
+  charge_type: FinancingChargeType;
+  amount: number; // Positive = cost, negative = credit
+  source: 'manual' | 'import';
+  notes?: string | null;
+  created_at: string;
+}
+
+export interface CreateFinancingChargeInput {
+  account_id: string;
+  charge_month: string; // YYYY-MM-DD format, any day of the month
+  charge_type: FinancingChargeType;
+  amount: number;
+  notes?: string | null;
+}
+
+export interface FinancingImportResult {
+  imported: number;
+  replaced: number;
+  errors: string[];
+}
+
+export interface AccountPerformanceMonth {
+  month: string; // YYYY-MM-DD format, first day of the month
+  trade_net_pnl: number;
+  financing_costs: number;
+  net_pnl: number;
+}
+
+export interface AccountPerformance {
+  account_id: string;
+  trade_net_pnl: number;
+  financing_costs: number;
+  net_pnl: number; // Trade PnL minus financing costs
+  starting_balance?: number | null;
+  return_pct?: number | null;
+  months: AccountPerformanceMonth[];
+}
+
+export interface CashTransaction {
+  id: string;
+  account_id: string;
+  transaction_date: string; // YYYY-MM-DD format
+  amount: number; // Positive = deposit, negative = withdrawal
+  notes?: string | null;
+  created_at: string;
+}
+
+export interface CreateCashTransactionInput {
+  account_id: string;
+  transaction_date: string; // YYYY-MM-DD format
+  amount: number;
+  notes?: string | null;
+}
+
+export interface AccountReturnDay {
+  date: string; // YYYY-MM-DD format
+  net_flows: number;
+  pnl: number;
+  end_value: number;
+  cumulative_twr_pct: number;
+}
+
+export interface AccountReturns {
+  account_id: string;
+  start_value: number;
+  end_value: number;
+  net_flows: number;
+  total_pnl: number;
+  twr_pct?: number | null; // Time-weighted
+  mwr_pct?: number | null; // Money-weighted, for the period (not annualized)
+  days: AccountReturnDay[];
+}
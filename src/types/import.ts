@@ -5,6 +5,7 @@ export interface TlgParseError {
 }
 
 export interface Execution {
+  id?: string | null;
   execution_type: 'entry' | 'exit';
   execution_date: string;
   execution_time: string | null;
@@ -13,16 +14,22 @@ export interface Execution {
   fees: number;
   exchange: string | null;
   broker_execution_id: string;
+  intended_price?: number | null;
+  order_type?: 'market' | 'limit' | 'stop' | 'stop_limit' | null;
+  fees_estimated?: boolean; // Fees computed from the account's commission schedule
 }
 
 export interface AggregatedTrade {
   key: string;
   symbol: string;
   underlying_symbol: string;
-  asset_class: 'stock' | 'option';
+  asset_class: 'stock' | 'option' | 'future' | 'forex' | 'crypto';
   option_type: 'call' | 'put' | null;
   strike_price: number | null;
   expiration_date: string | null;
+  multiplier?: number | null; // Contract multiplier; null uses the asset class default
+  fx_rate?: number | null; // Converts PnL from the quote currency to the account's base currency
+  currency?: string | null; // Quote currency of a crypto pair, converted to the account's on import
   direction: 'long' | 'short';
   trade_date: string;
   entries: Execution[];
@@ -34,19 +41,72 @@ export interface AggregatedTrade {
   avg_exit_price: number | null;
   total_fees: number;
   net_pnl: number | null;
+  suggested_strategy?: string | null; // From strategy rules, saved as the strategy on import
+}
+
+export interface ChangedTrade {
+  existing_trade_id: string;
+  trade: AggregatedTrade;
+  changes: string[];
 }
 
 export interface ImportPreview {
   trades_to_import: AggregatedTrade[];
   open_positions: AggregatedTrade[];
-  duplicate_count: number;
+  duplicate_count: number; // Already imported and unchanged
+  changed_trades: ChangedTrade[];
   parse_errors: TlgParseError[];
+  roll_chains: RollChainCandidate[]; // Option legs that look like rolls of one position
+}
+
+// Option legs closed and reopened at a different strike or expiry, in roll order
+export interface RollChainCandidate {
+  underlying_symbol: string;
+  option_type: string | null;
+  keys: string[]; // Aggregated trade keys, earliest leg first
+  combined_net_pnl: number; // Realized PnL summed across the closed legs
+}
+
+// A broker whose export can be previewed, as listed by listSupportedBrokers
+export interface SupportedBroker {
+  id: string; // Passed to previewBrokerImport, e.g. "webull"
+  name: string;
 }
 
 export interface ImportResult {
   imported_count: number;
+  updated_count: number;
   skipped_duplicates: number;
   errors: string[];
+  roll_links: number; // Imported legs linked to the next leg of their roll chain
+  batch_id: string | null; // Passed to undoImport; null for updates to imported trades
+}
+
+// An executed import as listed by getImportHistory, which undoImport rolls back
+export interface ImportBatch {
+  id: string;
+  account_id: string;
+  file_name: string | null;
+  trade_count: number;
+  execution_count: number;
+  skipped_duplicates: number;
+  error_count: number;
+  errors: string[]; // The first 20 error messages
+  created_at: string;
+  undone_at: string | null;
+}
+
+// Folder whose new TLG and CSV exports are imported into an account automatically
+export interface WatchFolder {
+  directory: string;
+  account_id: string;
+}
+
+// Payload of the 'watched-file-imported' event, one per file imported from the watch folder
+export interface WatchedImport {
+  file_name: string;
+  result: ImportResult | null;
+  error: string | null;
 }
 
 // Group trades by underlying symbol for UI display
@@ -4,6 +4,9 @@ export * from './account';
 export * from './import';
 export * from './market';
 export * from './settings';
+export * from './journal';
+
+import type { DateAttribution, TradeType } from './trade';
 
 export interface DateRange {
   start: string;
@@ -17,4 +20,9 @@ export interface TradeFilters {
   symbol?: string;
   direction?: 'long' | 'short';
   result?: 'win' | 'loss' | 'breakeven';
+  minRMultiple?: number;
+  maxDurationMinutes?: number;
+  tradeType?: TradeType;
+  dateAttribution?: DateAttribution; // Overrides the date attribution setting
+  archived?: boolean; // Search the archive database instead of the main one
 }
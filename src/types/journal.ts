@@ -0,0 +1,22 @@
+import type { DailyPerformance } from './metrics';
+
+export type JournalEntryStatus = 'draft' | 'reviewed';
+
+export interface JournalEntry {
+  id: string;
+  entry_date: string; // YYYY-MM-DD format
+  content: string;
+  status: JournalEntryStatus;
+  created_at: string;
+  updated_at: string;
+}
+
+// Payload of the 'end-of-day-summary' event
+export interface EndOfDaySummary {
+  date: string; // YYYY-MM-DD format
+  performance: DailyPerformance | null; // null when no trade entered that day is closed
+  trades_opened: number;
+  open_positions: number; // Entered on or before the day and not yet closed
+  symbols: string[];
+  journal_entry: JournalEntry;
+}
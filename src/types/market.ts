@@ -16,3 +16,14 @@ export interface MarketTapeQuote {
   change: number;
   change_percent: number;
 }
+
+export interface ExcursionBackfillResult {
+  updated_count: number;
+  skipped_no_times: number;
+  skipped_no_candles: number;
+}
+
+export interface EntryAtrBackfillResult {
+  updated_count: number;
+  skipped_no_bars: number;
+}
@@ -1,25 +1,47 @@
+import type { CostBasisMethod, Direction } from './trade';
+
 export interface DailyPerformance {
   date: string;
   realized_net_pnl: number;
+  gross_pnl?: number;
+  total_fees?: number;
+  total_volume?: number; // Shares/contracts traded
+  largest_win?: number | null;
+  largest_loss?: number | null;
   trade_count: number;
   win_count: number;
   loss_count: number;
+  breakeven_count?: number;
+  scratch_rate?: number; // Breakeven trades / trade count
+  avg_trade_pnl?: number;
+  unrealized_pnl_change?: number | null; // Open positions marked to daily closes
 }
 
 export interface PeriodMetrics {
   total_net_pnl: number;
+  total_gross_pnl?: number; // Before fees and other costs
   trade_count: number;
   win_count: number;
   loss_count: number;
   breakeven_count: number;
   win_rate: number | null;
+  win_rate_low?: number | null; // 95% Wilson interval around win_rate for its sample size
+  win_rate_high?: number | null;
+  override_count?: number;
+  adjusted_win_rate?: number | null;
   avg_win: number | null;
   avg_loss: number | null;
   profit_factor: number | null;
   expectancy: number | null;
+  expectancy_low?: number | null; // Expectancy at the bounds of the win rate interval; avg win and loss held fixed
+  expectancy_high?: number | null;
   max_drawdown: number;
+  max_drawdown_r?: number | null;
+  max_drawdown_pct?: number | null;
   max_win_streak: number;
   max_loss_streak: number;
+  current_win_streak?: number;
+  current_loss_streak?: number;
 }
 
 export interface EquityPoint {
@@ -28,6 +50,231 @@ export interface EquityPoint {
   drawdown: number;
 }
 
+// An account's key metrics at the close of a day, stored nightly
+export interface MetricSnapshot {
+  account_id: string;
+  date: string;
+  balance: number | null; // Starting balance plus PnL and net deposits; null without a starting balance
+  cumulative_pnl: number;
+  drawdown: number; // Below the highest cumulative PnL so far
+  max_drawdown: number;
+  trade_count: number; // Closed trades so far
+  win_count: number;
+  loss_count: number;
+  win_rate: number | null; // Breakevens excluded
+}
+
+export interface EquitySeries {
+  key: string; // Strategy name or account ID
+  points: EquityPoint[];
+}
+
+// Performance of a strategy over the window of days ending on a day it traded
+export interface StrategyTrendPoint {
+  date: string;
+  trade_count: number; // Closed trades in the window
+  net_pnl: number;
+  expectancy: number | null; // Average net PnL per trade
+  win_rate: number | null; // Excluding breakeven
+}
+
+// Rolling expectancy of one strategy over time
+export interface StrategyTrend {
+  strategy: string;
+  points: StrategyTrendPoint[];
+}
+
+// Peak margin and leverage of one account on one day
+export interface MarginUtilizationDay {
+  date: string;
+  account_id: string;
+  account_equity: number; // Starting balance plus PnL of trades closed before the day
+  peak_margin: number;
+  peak_notional: number;
+  utilization_pct: number; // peak_margin as a percent of account_equity
+  leverage: number; // peak_notional / account_equity
+  over_threshold: boolean;
+}
+
+export interface MarginUtilizationReport {
+  max_utilization_pct: number;
+  days: MarginUtilizationDay[];
+  over_threshold_count: number;
+  skipped_no_balance: number; // Account-days with positions but no starting balance
+}
+
+// Closed-trade performance over a group of trades
+export interface PerformanceBucket {
+  key: string;
+  trade_count: number;
+  win_count: number;
+  loss_count: number;
+  net_pnl: number;
+  avg_net_pnl: number | null;
+  win_rate: number | null; // Excluding breakeven
+}
+
+// Closed trades entered in one month, compared with the month before it
+export interface EntryCohort {
+  performance: PerformanceBucket; // Keyed by entry month, e.g. "2024-01"
+  expectancy_change: number | null; // Average net PnL minus that of the previous cohort
+}
+
+// Closed trades of one symbol in one month
+export interface SymbolMonthCell {
+  net_pnl: number;
+  trade_count: number;
+}
+
+// One symbol's row of the symbol by month matrix
+export interface SymbolMonthRow {
+  symbol: string;
+  net_pnl: number;
+  trade_count: number;
+  cells: SymbolMonthCell[]; // One per month of the matrix; zero for months without trades
+}
+
+// Net PnL and trade count of closed trades by symbol and month, for a heatmap
+export interface SymbolMonthMatrix {
+  months: string[]; // Every month from the first to the last with trades, e.g. "2024-01"
+  rows: SymbolMonthRow[]; // Best symbol first
+}
+
+// Closed trades at one end of the trading history
+export interface SkillSample {
+  trade_count: number;
+  first_date: string | null;
+  last_date: string | null;
+  win_rate: number | null; // Excluding breakeven
+  avg_r: number | null;
+  r_trade_count: number; // Trades with an R-multiple
+  mistakes_per_trade: number | null;
+}
+
+// Change of one measure from the earliest to the most recent trades, with a two-sided z-test
+export interface SkillComparison {
+  difference: number; // Recent minus earliest
+  z_score: number;
+  p_value: number;
+  significant: boolean; // p_value below 0.05
+}
+
+// First closed trades against the most recent ones
+// Mistakes are missing stops, widened stops and entries before the checklist was confirmed
+export interface SkillProgressionReport {
+  sample_size: number; // Trades in each sample
+  earliest: SkillSample;
+  recent: SkillSample;
+  win_rate: SkillComparison | null;
+  avg_r: SkillComparison | null;
+  mistakes_per_trade: SkillComparison | null; // Lower is better
+}
+
+// Closed trades grouped with the trades most like them
+export interface TradeCluster {
+  performance: PerformanceBucket; // Keyed by rank, "1" being the best average net PnL
+  traits: string[]; // What sets the cluster apart, e.g. "Earlier entries (avg 09:48)"
+  avg_entry_minute: number | null; // Minutes after midnight
+  avg_hold_minutes: number | null;
+  avg_r: number | null;
+  avg_atr_pct: number | null; // ATR at entry as a fraction of the entry price
+  top_symbols: string[];
+  tags: string[]; // Tags on at least half the cluster's trades
+  trade_ids: string[];
+}
+
+export interface TradeClusterReport {
+  trade_count: number;
+  clusters: TradeCluster[]; // Best average net PnL first
+}
+
+// Option trades on one side of the premium: sold (short) or bought (long)
+export interface OptionsPremiumBucket {
+  performance: PerformanceBucket; // Closed trades
+  open_count: number;
+  premium: number; // Entry price × contracts × multiplier, open trades included
+  expired_count: number;
+  assigned_count: number;
+  exercised_count: number;
+  assignment_rate: number | null; // assigned_count / closed trade count
+}
+
+export interface OptionsPremiumReport {
+  sold: OptionsPremiumBucket;
+  bought: OptionsPremiumBucket;
+  net_premium: number; // Premium collected minus premium paid
+}
+
+export interface SideCostSummary {
+  performance: PerformanceBucket;
+  total_fees: number;
+  total_carrying_costs: number;
+  cost_per_trade?: number | null;
+  cost_pct_of_notional?: number | null;
+}
+
+export interface HoldingDaysBucket {
+  label: string;
+  min_days: number;
+  max_days?: number | null;
+  trade_count: number;
+  net_pnl: number;
+  carrying_costs: number;
+}
+
+export interface ShortSideReport {
+  long: SideCostSummary;
+  short: SideCostSummary;
+  short_cost_premium_pct?: number | null; // Short minus long costs as a percent of notional
+  short_holding_days: HoldingDaysBucket[];
+  by_borrow_availability: PerformanceBucket[];
+}
+
+export interface FuturesSessionReport {
+  trade_count: number;
+  by_contract_month: PerformanceBucket[]; // Key YYYY-MM, or "unknown" without a month code
+  by_session: PerformanceBucket[]; // Keys "rth", "overnight" (Globex) or "unknown"
+}
+
+export interface LotDisposal {
+  trade_id: string; // Trade the disposal closed part of
+  lot_trade_id: string; // Trade the lot was acquired in
+  symbol: string;
+  quantity: number;
+  acquired_date: string;
+  disposed_date: string;
+  cost_basis: number; // Including the lot's share of its entry fees
+  proceeds: number; // Net of the disposal's share of its exit fees
+  gain: number;
+  long_term: boolean; // Held for more than a year
+}
+
+export interface CryptoTaxReport {
+  method: CostBasisMethod;
+  proceeds: number;
+  cost_basis: number;
+  short_term_gain: number;
+  long_term_gain: number;
+  total_gain: number;
+  unmatched_quantity: number; // Sold without an acquisition on record; left out of the gains
+  disposals: LotDisposal[];
+}
+
+export interface TimelineEvent {
+  trade_id: string;
+  symbol: string;
+  direction: Direction;
+  execution_type: 'entry' | 'exit';
+  execution_time: string | null;
+  quantity: number;
+  price: number;
+  fees: number;
+  position: number; // Signed position in the symbol after this fill (short < 0)
+  realized_pnl: number; // Realized by this fill, net of its fees
+  symbol_pnl: number; // Running realized PnL of the symbol for the day
+  day_pnl: number; // Running realized PnL across symbols for the day
+}
+
 export interface MonthlyPerformance {
   yearMonth: string;      // "2024-01"
   year: number;
@@ -37,3 +284,19 @@ export interface MonthlyPerformance {
   win_count: number;
   loss_count: number;
 }
+
+export interface ProcessStreak {
+  current: number; // Ending at the most recent traded day
+  longest: number;
+  days_met: number;
+  last_broken: string | null; // YYYY-MM-DD format
+}
+
+// Streaks count traded days only; days without trades neither extend nor break them
+export interface ProcessStreaks {
+  days_traded: number;
+  last_traded: string | null; // YYYY-MM-DD format
+  journaled: ProcessStreak; // Every trade of the day has notes
+  daily_loss_limit: number | null;
+  within_loss_limit: ProcessStreak | null; // Only when a daily loss limit is set
+}
@@ -3,3 +3,65 @@ export interface AlpacaKeysStatus {
   has_secret_key: boolean;
   masked_key_id: string | null;
 }
+
+export interface FxRate {
+  base_currency: string;
+  quote_currency: string;
+  date: string; // YYYY-MM-DD format
+  rate: number; // Quote currency units per base currency unit
+  source?: 'manual' | 'ecb'; // Manual rates override fetched ones
+}
+
+export interface FxRefreshResult {
+  pairs: string[];
+  fetched: number;
+}
+
+export type ReminderKind = 'journal_tonight' | 'weekly_review' | 'import_statements';
+
+export interface Reminder {
+  kind: ReminderKind;
+  enabled: boolean;
+  weekdays: number[]; // Days since Monday, 0-6
+  time: string; // HH:MM, local time
+}
+
+// Payload of the 'reminder-due' event
+export interface DueReminder {
+  kind: ReminderKind;
+  message: string;
+  scheduled_for: string; // YYYY-MM-DDTHH:MM:SS, local time
+}
+
+export interface MaintenanceReport {
+  ran_at: string;
+  bytes_before: number;
+  bytes_after: number;
+  bytes_reclaimed: number;
+  integrity_issues: string[]; // Empty when the integrity check passed
+  wal_frames_checkpointed: number | null; // null when the database is not in WAL mode
+}
+
+export type LogLevel = 'off' | 'error' | 'warn' | 'info' | 'debug' | 'trace';
+
+export interface ConfigView {
+  config_file: string | null; // The config.toml read, null when there is none
+  database_path: string;
+  log_level: LogLevel;
+  masked_alpaca_key_id: string | null;
+  has_alpaca_secret_key: boolean;
+  features: Record<string, boolean>;
+}
+
+export interface CalculationDiscrepancy {
+  scope: string; // "Journal", an account name, or a trade's reference code
+  metric: string;
+  per_trade: string;
+  sql: string;
+}
+
+export interface CalculationReport {
+  checked_at: string;
+  trade_count: number;
+  discrepancies: CalculationDiscrepancy[]; // Empty when both ways agree
+}
@@ -1,7 +1,14 @@
 export type Direction = 'long' | 'short';
 export type Status = 'open' | 'closed';
 export type TradeResult = 'win' | 'loss' | 'breakeven';
-export type AssetClass = 'stock' | 'option';
+export type AssetClass = 'stock' | 'option' | 'future' | 'forex' | 'crypto';
+export type RollType = 'rolled' | 'transferred';
+export type OptionOutcome = 'expired' | 'assigned' | 'exercised';
+export type DateAttribution = 'entry' | 'exit' | 'both'; // Date closed trades count toward in filters and daily metrics
+export type TradeType = 'scalp' | 'day' | 'swing' | 'position';
+export type CostBasisMethod = 'fifo' | 'lifo' | 'hifo'; // Order crypto disposals use up acquired lots for tax
+
+export type BorrowAvailability = 'easy' | 'hard_to_borrow' | 'locate_required';
 
 export interface ExitExecution {
   id?: string;
@@ -19,31 +26,69 @@ export interface Trade {
   instrument_id: string;
   symbol: string;
   asset_class: AssetClass;
+  contract_multiplier?: number | null; // From the instrument; null uses the asset class default
+  fx_rate?: number | null; // Account base currency per unit of the quote currency; null when they are the same
   trade_number: number | null;
+  ref_code?: string | null; // Stable reference, e.g. T-2024-0193
   trade_date: string; // YYYY-MM-DD format
   direction: Direction;
   quantity: number | null;
   entry_price: number;
   exit_price: number | null;
   stop_loss_price: number | null;
+  target_price?: number | null;
+  max_favorable_price?: number | null;
+  max_adverse_price?: number | null;
+  entry_atr?: number | null; // Average true range before the trade date
   entry_time: string | null;
   exit_time: string | null;
+  exit_date?: string | null; // Date of the last exit execution
   fees: number;
+  carrying_costs?: number; // Borrow fees, margin interest and swaps
+  dividends?: number; // Linked dividends counted in net PnL
   strategy: string | null;
   notes: string | null;
   screenshot_url?: string | null;
   status: Status;
+  result_override?: TradeResult | null; // Manual classification, kept apart from the computed result
+  roll_type?: RollType | null; // Set when an open position was carried into a continuation trade
+  rolled_on?: string | null; // Exposure belongs to the continuation trade from this date
+  continuation_trade_id?: string | null;
+  option_outcome?: OptionOutcome | null; // Expired, assigned or exercised instead of closed by a trade
+  borrow_availability?: BorrowAvailability | null; // Short trades only
+  borrow_note?: string | null;
+  is_locked?: boolean; // Within a finalized period of its account
   created_at: string;
   updated_at: string;
 }
 
+// Trades closed the same day are scalps up to scalp_max_minutes, held overnight swings up to swing_max_days
+export interface TradeTypeThresholds {
+  scalp_max_minutes: number;
+  swing_max_days: number;
+}
+
+export interface TradeExecutionRecord {
+  id: string;
+  trade_id: string;
+  execution_type: 'entry' | 'exit';
+  execution_date: string;
+  execution_time: string | null;
+  quantity: number;
+  price: number;
+  fees: number;
+}
+
 export interface TradeWithDerived extends Trade {
   gross_pnl: number | null;
   net_pnl: number | null;
   pnl_per_share: number | null;
   risk_per_share: number | null;
   r_multiple: number | null;
+  atr_multiple?: number | null; // pnl_per_share / entry_atr
   result: TradeResult | null;
+  trade_type?: TradeType | null; // Closed trades only
+  executions?: TradeExecutionRecord[]; // Only present when requested with includeExecutions
 }
 
 export interface CreateTradeInput {
@@ -57,6 +102,8 @@ export interface CreateTradeInput {
   entry_price: number;
   exit_price?: number;
   stop_loss_price?: number;
+  target_price?: number;
+  max_favorable_price?: number;
   entry_time?: string;
   exit_time?: string;
   fees?: number;
@@ -77,6 +124,8 @@ export interface UpdateTradeInput {
   entry_price?: number;
   exit_price?: number;
   stop_loss_price?: number;
+  target_price?: number;
+  max_favorable_price?: number;
   entry_time?: string;
   exit_time?: string;
   fees?: number;
@@ -85,3 +134,41 @@ export interface UpdateTradeInput {
   screenshot_url?: string;
   status?: Status;
 }
+
+export interface RollPositionInput {
+  trade_id: string;
+  roll_type: RollType;
+  rolled_on: string; // YYYY-MM-DD format
+  continuation_trade_id?: string;
+}
+
+export interface BulkRowError {
+  row: number; // 1-based position in the submitted list
+  message: string;
+}
+
+export interface BulkCreateResult {
+  created: TradeWithDerived[];
+  errors: BulkRowError[];
+}
+
+export interface TradeStats {
+  trade_count: number;
+  closed_count: number; // Trades with a net PnL
+  total_pnl: number;
+  win_count: number;
+  loss_count: number;
+  win_rate: number | null; // Excluding breakeven trades
+  first_trade_date: string | null;
+  last_trade_date: string | null;
+}
+
+export interface ArchivedYear {
+  year: number;
+  trade_count: number;
+}
+
+export interface ArchiveResult {
+  years: number[];
+  trades_archived: number;
+}
@@ -336,7 +336,7 @@ export default function TradeDetail() {
   const entries = executions.filter(e => e.execution_type === 'entry');
   const exits = executions.filter(e => e.execution_type === 'exit');
   const resultPnlClass = pnlClass(trade.net_pnl ?? trade.gross_pnl);
-  const multiplier = trade.asset_class === 'option' ? 100 : 1;
+  const multiplier = trade.contract_multiplier ?? (trade.asset_class === 'option' ? 100 : 1);
   const directionMultiplier = trade.direction === 'long' ? 1 : -1;
   const entryQtyTotal = entries.reduce((sum, entry) => sum + entry.quantity, 0);
   const entryValueTotal = entries.reduce((sum, entry) => sum + (entry.quantity * entry.price), 0);
@@ -477,7 +477,7 @@ export default function TradeDetail() {
     if (candlesInWindow.length === 0) return [];
 
     const dirSign = trade.direction === 'long' ? 1 : -1;
-    const contractMultiplier = trade.asset_class === 'option' ? 100 : 1;
+    const contractMultiplier = trade.contract_multiplier ?? (trade.asset_class === 'option' ? 100 : 1);
 
     let execIndex = 0;
     let openQty = 0;
@@ -594,8 +594,8 @@ export default function TradeDetail() {
           />
           <StatCard
             label="Position Size"
-            value={trade.quantity ? `${trade.quantity} ${trade.asset_class === 'option' ? 'contracts' : 'shares'}` : '-'}
-            hint={trade.asset_class === 'option' ? 'Contracts' : 'Shares'}
+            value={trade.quantity ? `${trade.quantity} ${trade.asset_class === 'stock' ? 'shares' : 'contracts'}` : '-'}
+            hint={trade.asset_class === 'stock' ? 'Shares' : 'Contracts'}
           />
         </div>
       </section>